@@ -1,18 +1,35 @@
 //! Adapter for converting aither agent events to ACP session updates.
 
+use std::path::Path;
+
 use aither_agent::{AgentEvent, TodoItem, TodoStatus};
+use serde_json::Value;
 
 use crate::protocol::{
-    ContentBlock, ContentChunk, Plan, PlanEntry, PlanEntryPriority, PlanEntryStatus, SessionUpdate,
-    TextContent, ToolCall, ToolCallStatus, ToolCallUpdate, ToolKind,
+    ContentBlock, ContentChunk, Diff, Plan, PlanEntry, PlanEntryPriority, PlanEntryStatus,
+    SessionUpdate, TextContent, ToolCall, ToolCallContent, ToolCallLocation, ToolCallStatus,
+    ToolCallUpdate, ToolKind,
 };
 
 /// Convert an `AgentEvent` to an ACP `SessionUpdate`.
 ///
 /// Returns `None` for events that don't have a direct ACP mapping
 /// (like `TurnComplete` and `Complete`, which are handled separately).
+///
+/// File paths in tool call locations and diffs are left as the tool received
+/// them; use [`agent_event_to_session_update_in`] to make them absolute.
 #[must_use]
 pub fn agent_event_to_session_update(event: &AgentEvent) -> Option<SessionUpdate> {
+    agent_event_to_session_update_in(event, Path::new(""))
+}
+
+/// Convert an `AgentEvent` to an ACP `SessionUpdate`, resolving the file
+/// paths of tool calls against the session's working directory.
+///
+/// Tool calls report the files they touch as `locations`, and file edits
+/// carry a `diff` so editors can show what the agent changed.
+#[must_use]
+pub fn agent_event_to_session_update_in(event: &AgentEvent, cwd: &Path) -> Option<SessionUpdate> {
     match event {
         AgentEvent::Text(text) => Some(SessionUpdate::AgentMessageChunk(ContentChunk {
             content: ContentBlock::Text(TextContent {
@@ -32,16 +49,23 @@ pub fn agent_event_to_session_update(event: &AgentEvent) -> Option<SessionUpdate
             id,
             name,
             arguments,
-        } => Some(SessionUpdate::ToolCall(ToolCall {
-            tool_call_id: id.clone(),
-            title: format_tool_title(name, arguments),
-            kind: Some(infer_tool_kind(name)),
-            status: Some(ToolCallStatus::Pending),
-            content: vec![],
-            locations: vec![],
-            raw_input: serde_json::from_str(arguments).ok(),
-            raw_output: None,
-        })),
+        } => {
+            let kind = infer_tool_kind(name, arguments);
+            let raw_input: Option<Value> = serde_json::from_str(arguments).ok();
+            let (content, locations) = raw_input.as_ref().map_or_else(Default::default, |args| {
+                (tool_diffs(name, kind, args, cwd), tool_locations(args, cwd))
+            });
+            Some(SessionUpdate::ToolCall(ToolCall {
+                tool_call_id: id.clone(),
+                title: format_tool_title(name, arguments),
+                kind: Some(kind),
+                status: Some(ToolCallStatus::Pending),
+                content,
+                locations,
+                raw_input,
+                raw_output: None,
+            }))
+        }
 
         AgentEvent::ToolCallEnd {
             id,
@@ -52,6 +76,12 @@ pub fn agent_event_to_session_update(event: &AgentEvent) -> Option<SessionUpdate
                 Ok(output) => (ToolCallStatus::Completed, Some(output.clone())),
                 Err(error) => (ToolCallStatus::Error, Some(error.clone())),
             };
+            // Edits report the line they changed, which the start event can't know.
+            let locations = result
+                .as_ref()
+                .ok()
+                .and_then(|output| edited_location(output, cwd))
+                .map(|location| vec![location]);
 
             Some(SessionUpdate::ToolCallUpdate(ToolCallUpdate {
                 tool_call_id: id.clone(),
@@ -59,12 +89,21 @@ pub fn agent_event_to_session_update(event: &AgentEvent) -> Option<SessionUpdate
                 content: None,
                 title: None,
                 kind: None,
-                locations: None,
+                locations,
                 raw_input: None,
                 raw_output: output.map(serde_json::Value::String),
             }))
         }
 
+        // The complete call follows as `ToolCallStart`
+        AgentEvent::ToolCallDelta { .. } => None,
+
+        // The message text links its sources; ACP has no separate update
+        AgentEvent::Citation(_) => None,
+
+        // The refusal already reaches the client as the tool call's error
+        AgentEvent::AccessBlocked { .. } => None,
+
         // These events are handled at a higher level
         AgentEvent::TurnComplete { .. } => None,
         AgentEvent::Complete { .. } => None,
@@ -73,6 +112,75 @@ pub fn agent_event_to_session_update(event: &AgentEvent) -> Option<SessionUpdate
     }
 }
 
+/// Convert an ACP `SessionUpdate` from another agent into an `AgentEvent`.
+///
+/// This is the reverse of [`agent_event_to_session_update`], used when aither
+/// drives an external ACP agent. Tool call start events use the call's title
+/// as the tool name, and updates that finish a call become
+/// [`AgentEvent::ToolCallEnd`] with an empty name (callers that track titles by
+/// ID can fill it in). Returns `None` for updates without an event equivalent,
+/// such as plans, user message replays, and in-progress tool updates.
+#[must_use]
+pub fn session_update_to_agent_event(update: &SessionUpdate) -> Option<AgentEvent> {
+    match update {
+        SessionUpdate::AgentMessageChunk(chunk) => {
+            content_text(&chunk.content).map(AgentEvent::Text)
+        }
+        SessionUpdate::AgentThoughtChunk(chunk) => {
+            content_text(&chunk.content).map(AgentEvent::Reasoning)
+        }
+        SessionUpdate::ToolCall(call) => Some(AgentEvent::ToolCallStart {
+            id: call.tool_call_id.clone(),
+            name: call.title.clone(),
+            arguments: call
+                .raw_input
+                .as_ref()
+                .map_or_else(|| "{}".to_string(), Value::to_string),
+        }),
+        SessionUpdate::ToolCallUpdate(update) => {
+            let output = update
+                .raw_output
+                .as_ref()
+                .map(|output| match output {
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                })
+                .or_else(|| {
+                    let text: Vec<String> = update
+                        .content
+                        .iter()
+                        .flatten()
+                        .filter_map(|content| match content {
+                            ToolCallContent::Content { content } => content_text(content),
+                            _ => None,
+                        })
+                        .collect();
+                    (!text.is_empty()).then(|| text.join("\n"))
+                })
+                .unwrap_or_default();
+            let result = match update.status? {
+                ToolCallStatus::Completed => Ok(output),
+                ToolCallStatus::Error => Err(output),
+                ToolCallStatus::Pending | ToolCallStatus::InProgress => return None,
+            };
+            Some(AgentEvent::ToolCallEnd {
+                id: update.tool_call_id.clone(),
+                name: update.title.clone().unwrap_or_default(),
+                result,
+            })
+        }
+        SessionUpdate::UserMessageChunk(_) | SessionUpdate::Plan(_) => None,
+    }
+}
+
+/// Text of a content block, if it has any.
+fn content_text(content: &ContentBlock) -> Option<String> {
+    match content {
+        ContentBlock::Text(text) => Some(text.text.clone()),
+        _ => None,
+    }
+}
+
 /// Convert a todo list to an ACP Plan.
 #[must_use]
 pub fn todos_to_plan(todos: &[TodoItem]) -> Plan {
@@ -82,7 +190,11 @@ pub fn todos_to_plan(todos: &[TodoItem]) -> Plan {
             .map(|item| PlanEntry {
                 content: item.content.clone(),
                 status: todo_status_to_plan_status(item.status),
-                priority: PlanEntryPriority::Medium,
+                priority: if item.stretch {
+                    PlanEntryPriority::Low
+                } else {
+                    PlanEntryPriority::Medium
+                },
             })
             .collect(),
     }
@@ -99,7 +211,7 @@ pub const fn todo_status_to_plan_status(status: TodoStatus) -> PlanEntryStatus {
 }
 
 /// Format a human-readable title for a tool call.
-fn format_tool_title(name: &str, arguments: &str) -> String {
+pub(crate) fn format_tool_title(name: &str, arguments: &str) -> String {
     // Try to extract relevant info from arguments for better titles
     match name {
         "bash" => {
@@ -140,6 +252,27 @@ fn format_tool_title(name: &str, arguments: &str) -> String {
             }
             "Editing file".to_string()
         }
+        "filesystem" => {
+            let args = serde_json::from_str::<serde_json::Value>(arguments).unwrap_or_default();
+            let target = ["path", "from", "pattern"]
+                .into_iter()
+                .find_map(|key| args.get(key)?.as_str())
+                .unwrap_or_default();
+            let verb = match args.get("operation").and_then(|v| v.as_str()) {
+                Some("read" | "head" | "tail") => "Reading",
+                Some("write" | "append") => "Writing",
+                Some("edit") => "Editing",
+                Some("delete") => "Deleting",
+                Some("move") => "Moving",
+                Some("copy") => "Copying",
+                Some("undo") => "Reverting",
+                Some("create_dir") => "Creating",
+                Some("list") => "Listing",
+                Some("glob" | "grep") => "Searching",
+                _ => return "Accessing files".to_string(),
+            };
+            format!("{verb} {target}").trim_end().to_string()
+        }
         "glob" | "Glob" => "Searching files".to_string(),
         "grep" | "Grep" => "Searching content".to_string(),
         "websearch" | "WebSearch" => "Searching web".to_string(),
@@ -149,20 +282,101 @@ fn format_tool_title(name: &str, arguments: &str) -> String {
     }
 }
 
-/// Infer the tool kind from the tool name.
-fn infer_tool_kind(name: &str) -> ToolKind {
+/// Infer the tool kind from the tool name and, for the filesystem tool, its operation.
+pub(crate) fn infer_tool_kind(name: &str, arguments: &str) -> ToolKind {
     match name.to_lowercase().as_str() {
         "read" | "glob" | "grep" | "webfetch" => ToolKind::Read,
         "write" | "edit" => ToolKind::Edit,
         "websearch" => ToolKind::Search,
         "bash" | "command" => ToolKind::Execute,
+        "filesystem" => filesystem_kind(arguments),
+        _ => ToolKind::Other,
+    }
+}
+
+/// Kind of a filesystem tool call, from its `operation` argument.
+fn filesystem_kind(arguments: &str) -> ToolKind {
+    let operation = serde_json::from_str::<serde_json::Value>(arguments)
+        .ok()
+        .and_then(|args| args.get("operation")?.as_str().map(str::to_string));
+    match operation.as_deref() {
+        Some("read" | "head" | "tail" | "list" | "glob" | "grep") => ToolKind::Read,
+        Some("delete") => ToolKind::Delete,
+        Some("move") => ToolKind::Move,
+        Some("write" | "edit" | "append" | "copy" | "undo" | "create_dir") => ToolKind::Edit,
         _ => ToolKind::Other,
     }
 }
 
+/// Files a tool call touches, taken from its path arguments.
+fn tool_locations(args: &Value, cwd: &Path) -> Vec<ToolCallLocation> {
+    let line = args
+        .get("offset")
+        .and_then(Value::as_u64)
+        .and_then(|line| u32::try_from(line).ok());
+    ["path", "file_path", "from", "to"]
+        .into_iter()
+        .filter_map(|key| args.get(key)?.as_str())
+        .filter(|path| !path.is_empty())
+        .map(|path| ToolCallLocation {
+            path: cwd.join(path),
+            line,
+        })
+        .collect()
+}
+
+/// Diff content for tool calls that replace text or whole files.
+fn tool_diffs(name: &str, kind: ToolKind, args: &Value, cwd: &Path) -> Vec<ToolCallContent> {
+    if kind != ToolKind::Edit {
+        return Vec::new();
+    }
+    let text = |keys: &[&str]| keys.iter().find_map(|key| args.get(*key)?.as_str());
+    let Some(path) = text(&["path", "file_path"]) else {
+        return Vec::new();
+    };
+    let operation = args.get("operation").and_then(Value::as_str);
+    let (old_text, new_text) = match (
+        text(&["old_text", "old_string"]),
+        text(&["new_text", "new_string"]),
+    ) {
+        (Some(old), Some(new)) => (Some(old.to_string()), new.to_string()),
+        // Whole-file writes; appends only show the added text, so they get no diff.
+        _ if matches!(operation, Some("write")) || name.eq_ignore_ascii_case("write") => {
+            match text(&["content"]) {
+                Some(content) => (None, content.to_string()),
+                None => return Vec::new(),
+            }
+        }
+        _ => return Vec::new(),
+    };
+    vec![ToolCallContent::Diff(Diff {
+        path: cwd.join(path),
+        old_text,
+        new_text,
+    })]
+}
+
+/// Location reported by a filesystem edit result ("Edited {path} at line {line}").
+fn edited_location(output: &str, cwd: &Path) -> Option<ToolCallLocation> {
+    let (path, line) = output.strip_prefix("Edited ")?.rsplit_once(" at line ")?;
+    Some(ToolCallLocation {
+        path: cwd.join(path),
+        line: Some(line.trim().parse().ok()?),
+    })
+}
+
+/// Whether calls of this kind change the workspace and need the user's approval.
+pub(crate) const fn is_destructive(kind: ToolKind) -> bool {
+    matches!(
+        kind,
+        ToolKind::Edit | ToolKind::Delete | ToolKind::Move | ToolKind::Execute
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aither_agent::{StepKind, TodoKind};
 
     #[test]
     fn test_text_event_conversion() {
@@ -199,6 +413,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_filesystem_kind_follows_operation() {
+        let kind = |operation: &str| {
+            infer_tool_kind("filesystem", &format!(r#"{{"operation": "{operation}"}}"#))
+        };
+        assert_eq!(kind("read"), ToolKind::Read);
+        assert_eq!(kind("grep"), ToolKind::Read);
+        assert_eq!(kind("edit"), ToolKind::Edit);
+        assert_eq!(kind("delete"), ToolKind::Delete);
+        assert_eq!(kind("move"), ToolKind::Move);
+        assert!(!is_destructive(kind("list")));
+        assert!(is_destructive(kind("write")));
+        assert!(is_destructive(infer_tool_kind("bash", "{}")));
+    }
+
+    #[test]
+    fn test_filesystem_edit_reports_diff_and_location() {
+        let event = AgentEvent::ToolCallStart {
+            id: "1".to_string(),
+            name: "filesystem".to_string(),
+            arguments:
+                r#"{"operation": "edit", "path": "src/lib.rs", "old_text": "a", "new_text": "b"}"#
+                    .to_string(),
+        };
+        let Some(SessionUpdate::ToolCall(call)) =
+            agent_event_to_session_update_in(&event, Path::new("/work"))
+        else {
+            panic!("Expected ToolCall");
+        };
+        assert_eq!(call.locations.len(), 1);
+        assert_eq!(call.locations[0].path, Path::new("/work/src/lib.rs"));
+        match &call.content[..] {
+            [ToolCallContent::Diff(diff)] => {
+                assert_eq!(diff.old_text.as_deref(), Some("a"));
+                assert_eq!(diff.new_text, "b");
+            }
+            other => panic!("Expected one diff, got {other:?}"),
+        }
+
+        let event = AgentEvent::ToolCallEnd {
+            id: "1".to_string(),
+            name: "filesystem".to_string(),
+            result: Ok("Edited src/lib.rs at line 42".to_string()),
+        };
+        let Some(SessionUpdate::ToolCallUpdate(update)) =
+            agent_event_to_session_update_in(&event, Path::new("/work"))
+        else {
+            panic!("Expected ToolCallUpdate");
+        };
+        let locations = update.locations.unwrap();
+        assert_eq!(locations[0].line, Some(42));
+    }
+
+    #[test]
+    fn test_session_update_round_trips_to_agent_event() {
+        let update = agent_event_to_session_update(&AgentEvent::text("Hi")).unwrap();
+        assert!(matches!(
+            session_update_to_agent_event(&update),
+            Some(AgentEvent::Text(text)) if text == "Hi"
+        ));
+
+        let update = SessionUpdate::ToolCallUpdate(ToolCallUpdate {
+            tool_call_id: "7".to_string(),
+            status: Some(ToolCallStatus::Error),
+            content: None,
+            title: None,
+            kind: None,
+            locations: None,
+            raw_input: None,
+            raw_output: Some(Value::String("boom".to_string())),
+        });
+        assert!(matches!(
+            session_update_to_agent_event(&update),
+            Some(AgentEvent::ToolCallEnd { id, result: Err(error), .. }) if id == "7" && error == "boom"
+        ));
+    }
+
     #[test]
     fn test_todos_to_plan() {
         let todos = vec![
@@ -206,11 +497,19 @@ mod tests {
                 content: "Task 1".to_string(),
                 status: TodoStatus::Completed,
                 active_form: "Completing task 1".to_string(),
+                stretch: false,
+                kind: TodoKind::Action,
+                check: None,
+                step: StepKind::General,
             },
             TodoItem {
                 content: "Task 2".to_string(),
                 status: TodoStatus::InProgress,
                 active_form: "Working on task 2".to_string(),
+                stretch: false,
+                kind: TodoKind::Action,
+                check: None,
+                step: StepKind::General,
             },
         ];
 
@@ -0,0 +1,385 @@
+//! ACP client for driving other ACP agents.
+//!
+//! [`AcpClient`] is the editor side of the protocol: it launches an external
+//! agent (Gemini CLI, another aither instance, ...) as a child process,
+//! opens sessions, and streams each prompt's `session/update` notifications
+//! back as [`AgentEvent`]s, so an aither orchestrator can treat the external
+//! agent like one of its own sub-agents.
+//!
+//! ```ignore
+//! use aither_acp::AcpClient;
+//!
+//! let mut client = AcpClient::spawn("gemini", &["--experimental-acp"])?.auto_approve();
+//! client.initialize().await?;
+//! let session = client.new_session(std::env::current_dir()?).await?;
+//! let stop = client
+//!     .prompt(&session, "Summarize src/lib.rs", |event| println!("{event:?}"))
+//!     .await?;
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use aither_agent::AgentEvent;
+use aither_mcp::transport::{BidirectionalTransport, ChildProcessTransport, Transport};
+use tracing::debug;
+
+use crate::adapter::session_update_to_agent_event;
+use crate::protocol::{
+    AcpError, ClientCapabilities, ContentBlock, Implementation, InitializeParams, InitializeResult,
+    JsonRpcError, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse,
+    PROTOCOL_VERSION, PermissionOptionKind, PromptParams, PromptResult, RequestPermissionOutcome,
+    RequestPermissionParams, RequestPermissionResult, Result, SessionCancelParams,
+    SessionNewParams, SessionNewResult, SessionNotification, StopReason, TextContent,
+};
+
+type PermissionHandler =
+    Arc<dyn Fn(&RequestPermissionParams) -> RequestPermissionOutcome + Send + Sync>;
+
+/// Next thing a running prompt has to handle.
+enum PromptEvent {
+    Incoming(Result<Option<JsonRpcMessage>>),
+    Cancel,
+}
+
+/// Client side of ACP, driving an external agent over stdio.
+///
+/// The client advertises no file system or terminal capabilities, so the
+/// agent works on disk directly. Permission prompts from the agent are
+/// answered by the handler set with [`on_permission`](Self::on_permission);
+/// by default every prompt is rejected.
+pub struct AcpClient {
+    transport: ChildProcessTransport,
+    info: Implementation,
+    agent: Option<InitializeResult>,
+    permissions: PermissionHandler,
+}
+
+impl std::fmt::Debug for AcpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcpClient")
+            .field("info", &self.info)
+            .field("agent", &self.agent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AcpClient {
+    /// Launch an ACP agent as a child process.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process cannot be spawned.
+    pub fn spawn(program: &str, args: &[&str]) -> Result<Self> {
+        let transport = ChildProcessTransport::spawn(program, args)
+            .map_err(|e| AcpError::Transport(e.to_string()))?;
+        Ok(Self::from_transport(transport))
+    }
+
+    /// Drive an agent over an existing child process transport.
+    #[must_use]
+    pub fn from_transport(transport: ChildProcessTransport) -> Self {
+        Self {
+            transport,
+            info: Implementation {
+                name: "aither".to_string(),
+                title: None,
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            agent: None,
+            permissions: Arc::new(|_| RequestPermissionOutcome::Cancelled),
+        }
+    }
+
+    /// Set the client name and version sent in `initialize`.
+    #[must_use]
+    pub fn client_info(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.info.name = name.into();
+        self.info.version = version.into();
+        self
+    }
+
+    /// Answer the agent's permission prompts with `handler`.
+    #[must_use]
+    pub fn on_permission(
+        mut self,
+        handler: impl Fn(&RequestPermissionParams) -> RequestPermissionOutcome + Send + Sync + 'static,
+    ) -> Self {
+        self.permissions = Arc::new(handler);
+        self
+    }
+
+    /// Allow every tool call the agent asks about, once.
+    #[must_use]
+    pub fn auto_approve(self) -> Self {
+        self.on_permission(|params| {
+            params
+                .options
+                .iter()
+                .find(|option| option.kind == PermissionOptionKind::AllowOnce)
+                .map_or(RequestPermissionOutcome::Cancelled, |option| {
+                    RequestPermissionOutcome::Selected {
+                        option_id: option.option_id.clone(),
+                    }
+                })
+        })
+    }
+
+    /// The agent's `initialize` response, once [`initialize`](Self::initialize) has run.
+    #[must_use]
+    pub const fn agent(&self) -> Option<&InitializeResult> {
+        self.agent.as_ref()
+    }
+
+    /// Perform the `initialize` handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the agent rejects it.
+    pub async fn initialize(&mut self) -> Result<&InitializeResult> {
+        let params = InitializeParams {
+            protocol_version: PROTOCOL_VERSION.to_string(),
+            client_capabilities: ClientCapabilities::default(),
+            client_info: Some(self.info.clone()),
+        };
+        let result: InitializeResult = self.request("initialize", &params).await?;
+        debug!("Connected to ACP agent: {}", result.agent_info.name);
+        Ok(self.agent.insert(result))
+    }
+
+    /// Open a session working in `cwd` and return its ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the agent rejects it.
+    pub async fn new_session(&mut self, cwd: impl Into<PathBuf>) -> Result<String> {
+        let params = SessionNewParams {
+            cwd: cwd.into(),
+            mcp_servers: Vec::new(),
+        };
+        let result: SessionNewResult = self.request("session/new", &params).await?;
+        Ok(result.session_id)
+    }
+
+    /// Send a prompt and stream the agent's updates until the turn ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails or the agent rejects the prompt.
+    pub async fn prompt(
+        &mut self,
+        session_id: &str,
+        text: impl Into<String>,
+        on_event: impl FnMut(AgentEvent),
+    ) -> Result<StopReason> {
+        self.prompt_until(session_id, text, std::future::pending(), on_event)
+            .await
+    }
+
+    /// Like [`prompt`](Self::prompt), but sends `session/cancel` once `cancel`
+    /// completes and then waits for the agent to wind the turn down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails or the agent rejects the prompt.
+    pub async fn prompt_until(
+        &mut self,
+        session_id: &str,
+        text: impl Into<String>,
+        cancel: impl Future<Output = ()>,
+        mut on_event: impl FnMut(AgentEvent),
+    ) -> Result<StopReason> {
+        let params = PromptParams {
+            session_id: session_id.to_string(),
+            prompt: vec![ContentBlock::Text(TextContent {
+                text: text.into(),
+                annotations: None,
+            })],
+        };
+        let request = JsonRpcRequest::with_params(0, "session/prompt", &params);
+        let id = self
+            .transport
+            .send_request(request)
+            .await
+            .map_err(|e| AcpError::Transport(e.to_string()))?;
+
+        // Titles of started tool calls, to name their end events.
+        let mut titles: HashMap<String, String> = HashMap::new();
+        let mut cancel = std::pin::pin!(cancel);
+        let mut cancel_sent = false;
+
+        loop {
+            let event = if cancel_sent {
+                PromptEvent::Incoming(self.recv().await)
+            } else {
+                let transport = &mut self.transport;
+                futures_lite::future::or(
+                    async {
+                        PromptEvent::Incoming(
+                            transport
+                                .recv()
+                                .await
+                                .map_err(|e| AcpError::Transport(e.to_string())),
+                        )
+                    },
+                    async {
+                        cancel.as_mut().await;
+                        PromptEvent::Cancel
+                    },
+                )
+                .await
+            };
+
+            match event {
+                PromptEvent::Cancel => {
+                    cancel_sent = true;
+                    self.cancel(session_id).await?;
+                }
+                PromptEvent::Incoming(message) => match message?
+                    .ok_or(AcpError::ConnectionClosed)?
+                {
+                    JsonRpcMessage::Response(response) if response.id == id => {
+                        let result: PromptResult = serde_json::from_value(response.into_result()?)?;
+                        return Ok(result.stop_reason);
+                    }
+                    JsonRpcMessage::Notification(notif) => {
+                        if let Some(event) = self.session_event(&notif, session_id, &mut titles) {
+                            on_event(event);
+                        }
+                    }
+                    JsonRpcMessage::Request(req) => {
+                        let response = self.handle_agent_request(req);
+                        self.transport
+                            .respond(response)
+                            .await
+                            .map_err(|e| AcpError::Transport(e.to_string()))?;
+                    }
+                    JsonRpcMessage::Response(_) => debug!("Ignoring unexpected response"),
+                },
+            }
+        }
+    }
+
+    /// Ask the agent to stop the running turn in `session_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification cannot be sent.
+    pub async fn cancel(&mut self, session_id: &str) -> Result<()> {
+        let notif = JsonRpcNotification::with_params(
+            "session/cancel",
+            SessionCancelParams {
+                session_id: session_id.to_string(),
+            },
+        );
+        self.transport
+            .notify(notif)
+            .await
+            .map_err(|e| AcpError::Transport(e.to_string()))
+    }
+
+    /// Shut the agent process down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transport fails to close.
+    pub async fn close(&mut self) -> Result<()> {
+        self.transport
+            .close()
+            .await
+            .map_err(|e| AcpError::Transport(e.to_string()))
+    }
+
+    async fn request<R: serde::de::DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: &impl serde::Serialize,
+    ) -> Result<R> {
+        let request = JsonRpcRequest::with_params(0, method, params);
+        let response = self
+            .transport
+            .request(request)
+            .await
+            .map_err(|e| AcpError::Transport(e.to_string()))?;
+        Ok(serde_json::from_value(response.into_result()?)?)
+    }
+
+    async fn recv(&mut self) -> Result<Option<JsonRpcMessage>> {
+        self.transport
+            .recv()
+            .await
+            .map_err(|e| AcpError::Transport(e.to_string()))
+    }
+
+    /// Map a `session/update` for `session_id` to an agent event.
+    fn session_event(
+        &self,
+        notif: &JsonRpcNotification,
+        session_id: &str,
+        titles: &mut HashMap<String, String>,
+    ) -> Option<AgentEvent> {
+        if notif.method != "session/update" {
+            return None;
+        }
+        let notification: SessionNotification = match serde_json::from_value(notif.params.clone()?)
+        {
+            Ok(notification) => notification,
+            Err(e) => {
+                debug!("Ignoring malformed session/update: {e}");
+                return None;
+            }
+        };
+        if notification.session_id != session_id {
+            return None;
+        }
+        match session_update_to_agent_event(&notification.update)? {
+            AgentEvent::ToolCallStart {
+                id,
+                name,
+                arguments,
+            } => {
+                titles.insert(id.clone(), name.clone());
+                Some(AgentEvent::ToolCallStart {
+                    id,
+                    name,
+                    arguments,
+                })
+            }
+            AgentEvent::ToolCallEnd { id, name, result } => {
+                let name = titles.remove(&id).unwrap_or(name);
+                Some(AgentEvent::ToolCallEnd { id, name, result })
+            }
+            other => Some(other),
+        }
+    }
+
+    /// Answer a request the agent sent while a prompt was running.
+    fn handle_agent_request(&self, req: JsonRpcRequest) -> JsonRpcResponse {
+        match req.method.as_str() {
+            "session/request_permission" => {
+                let params: RequestPermissionParams =
+                    match req.params.map(serde_json::from_value).transpose() {
+                        Ok(Some(params)) => params,
+                        Ok(None) => {
+                            return JsonRpcResponse::error(
+                                req.id,
+                                JsonRpcError::invalid_params("Missing params"),
+                            );
+                        }
+                        Err(e) => {
+                            return JsonRpcResponse::error(
+                                req.id,
+                                JsonRpcError::invalid_params(e.to_string()),
+                            );
+                        }
+                    };
+                let outcome = (self.permissions)(&params);
+                JsonRpcResponse::success(req.id, RequestPermissionResult { outcome })
+            }
+            // No fs or terminal capabilities are advertised.
+            method => JsonRpcResponse::error(req.id, JsonRpcError::method_not_found(method)),
+        }
+    }
+}
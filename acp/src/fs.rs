@@ -0,0 +1,323 @@
+//! File access proxied through the editor.
+//!
+//! Editors that advertise the `fs.readTextFile` / `fs.writeTextFile` client
+//! capabilities can serve reads from open buffers, including unsaved edits,
+//! and apply writes to those buffers so the user sees them immediately.
+//! [`AcpFsBridge`] sends these requests through the
+//! [`AcpServer`](crate::AcpServer), and [`AcpFileSystem`] plugs the bridge into
+//! [`FileSystemTool`](aither_fs::FileSystemTool):
+//!
+//! ```ignore
+//! use aither_acp::AcpFileSystem;
+//! use aither_fs::{FileSystemTool, LocalFileSystem};
+//!
+//! let local = LocalFileSystem::new(&cwd)?;
+//! let fs = AcpFileSystem::new(local, server.fs_bridge(), &session_id, &cwd);
+//! agent_builder.tool(FileSystemTool::with_filesystem(fs));
+//! ```
+
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use aither_fs::{DirEntry, FileMetadata, FileSystem, GrepMatch};
+use async_channel::{Receiver, Sender};
+use regex::Regex;
+use tracing::debug;
+
+use crate::protocol::{
+    AcpError, FileSystemCapability, ReadTextFileParams, ReadTextFileResult, Result,
+    WriteTextFileParams,
+};
+
+/// An `fs/*` request waiting to be forwarded to the editor.
+pub(crate) struct PendingFsRequest {
+    pub(crate) method: &'static str,
+    pub(crate) params: serde_json::Value,
+    pub(crate) reply: Sender<Result<serde_json::Value>>,
+}
+
+/// Handle for reading and writing files through the editor.
+///
+/// Obtained from [`AcpServer::fs_bridge`](crate::AcpServer::fs_bridge).
+/// Cloning is cheap; all clones feed the same server.
+#[derive(Debug, Clone)]
+pub struct AcpFsBridge {
+    tx: Sender<PendingFsRequest>,
+    capabilities: Arc<RwLock<FileSystemCapability>>,
+}
+
+impl AcpFsBridge {
+    pub(crate) fn channel() -> (Self, Receiver<PendingFsRequest>) {
+        let (tx, rx) = async_channel::unbounded();
+        let bridge = Self {
+            tx,
+            capabilities: Arc::default(),
+        };
+        (bridge, rx)
+    }
+
+    /// Record the file system capabilities the editor sent in `initialize`.
+    pub(crate) fn set_capabilities(&self, capabilities: FileSystemCapability) {
+        *self
+            .capabilities
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = capabilities;
+    }
+
+    /// File system capabilities of the connected editor.
+    ///
+    /// Both are `false` until the editor has sent `initialize`.
+    #[must_use]
+    pub fn capabilities(&self) -> FileSystemCapability {
+        *self
+            .capabilities
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Read a text file, including unsaved changes in the editor's buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the editor does not support reads, the server has
+    /// stopped, or the editor rejects the request.
+    pub async fn read_text_file(&self, params: ReadTextFileParams) -> Result<String> {
+        let value = self.request("fs/read_text_file", &params).await?;
+        let result: ReadTextFileResult = serde_json::from_value(value)?;
+        Ok(result.content)
+    }
+
+    /// Write a text file through the editor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the editor does not support writes, the server has
+    /// stopped, or the editor rejects the request.
+    pub async fn write_text_file(&self, params: WriteTextFileParams) -> Result<()> {
+        self.request("fs/write_text_file", &params).await?;
+        Ok(())
+    }
+
+    async fn request(
+        &self,
+        method: &'static str,
+        params: &impl serde::Serialize,
+    ) -> Result<serde_json::Value> {
+        let params = serde_json::to_value(params)?;
+        let (reply, response) = async_channel::bounded(1);
+        self.tx
+            .send(PendingFsRequest {
+                method,
+                params,
+                reply,
+            })
+            .await
+            .map_err(|_| AcpError::ConnectionClosed)?;
+        response
+            .recv()
+            .await
+            .map_err(|_| AcpError::ConnectionClosed)?
+    }
+}
+
+impl std::fmt::Debug for PendingFsRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingFsRequest")
+            .field("method", &self.method)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+/// [`FileSystem`] that reads and writes text through the editor when it can.
+///
+/// Text reads and writes go to the editor for paths under `root`, so the agent
+/// sees unsaved buffers and its edits land in them. Everything else (listing,
+/// deleting, binary files, grep) is handled by the inner file system, which is
+/// also the fallback whenever the editor lacks the capability or rejects a
+/// request.
+#[derive(Debug, Clone)]
+pub struct AcpFileSystem<FS> {
+    inner: FS,
+    bridge: AcpFsBridge,
+    session_id: String,
+    root: PathBuf,
+}
+
+impl<FS: FileSystem> AcpFileSystem<FS> {
+    /// Wrap `inner`, proxying text access for `session_id` through `bridge`.
+    ///
+    /// `root` is the absolute directory relative paths are resolved against,
+    /// normally the session's working directory.
+    pub fn new(
+        inner: FS,
+        bridge: AcpFsBridge,
+        session_id: impl Into<String>,
+        root: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            inner,
+            bridge,
+            session_id: session_id.into(),
+            root: root.into(),
+        }
+    }
+
+    /// Absolute path the editor knows `path` by, or `None` if it leaves `root`.
+    fn editor_path(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let mut resolved = self.root.clone();
+        for component in relative.components() {
+            match component {
+                Component::CurDir => {}
+                Component::Normal(part) => resolved.push(part),
+                Component::ParentDir => {
+                    if !resolved.pop() || !resolved.starts_with(&self.root) {
+                        return None;
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => return None,
+            }
+        }
+        resolved.to_str().map(str::to_string)
+    }
+
+    /// Read through the editor, or `None` if it can't serve this path.
+    async fn editor_read(&self, path: &Path) -> Option<String> {
+        if !self.bridge.capabilities().read_text_file {
+            return None;
+        }
+        let params = ReadTextFileParams {
+            session_id: self.session_id.clone(),
+            path: self.editor_path(path)?,
+            line: None,
+            limit: None,
+        };
+        match self.bridge.read_text_file(params).await {
+            Ok(content) => Some(content),
+            Err(e) => {
+                debug!("Editor read of {} failed: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Write through the editor, handing `contents` back if it can't serve this path.
+    async fn editor_write(&self, path: &Path, contents: String) -> Option<String> {
+        if !self.bridge.capabilities().write_text_file {
+            return Some(contents);
+        }
+        let Some(editor_path) = self.editor_path(path) else {
+            return Some(contents);
+        };
+        let params = WriteTextFileParams {
+            session_id: self.session_id.clone(),
+            path: editor_path,
+            content: contents,
+        };
+        match self.bridge.write_text_file(params.clone()).await {
+            Ok(()) => None,
+            Err(e) => {
+                debug!("Editor write of {} failed: {e}", path.display());
+                Some(params.content)
+            }
+        }
+    }
+}
+
+impl<FS: FileSystem> FileSystem for AcpFileSystem<FS> {
+    async fn read_file<'a>(&'a self, path: &'a Path) -> io::Result<String> {
+        match self.editor_read(path).await {
+            Some(content) => Ok(content),
+            None => self.inner.read_file(path).await,
+        }
+    }
+
+    async fn write_file<'a>(&'a self, path: &'a Path, contents: String) -> io::Result<()> {
+        match self.editor_write(path, contents).await {
+            None => Ok(()),
+            Some(contents) => self.inner.write_file(path, contents).await,
+        }
+    }
+
+    async fn write_bytes<'a>(&'a self, path: &'a Path, contents: Vec<u8>) -> io::Result<()> {
+        match String::from_utf8(contents) {
+            Ok(text) => self.write_file(path, text).await,
+            Err(err) => self.inner.write_bytes(path, err.into_bytes()).await,
+        }
+    }
+
+    async fn append_file<'a>(&'a self, path: &'a Path, contents: String) -> io::Result<()> {
+        if !self.bridge.capabilities().write_text_file {
+            return self.inner.append_file(path, contents).await;
+        }
+        let mut current = match self.read_file(path).await {
+            Ok(current) => current,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        current.push_str(&contents);
+        self.write_file(path, current).await
+    }
+
+    async fn remove_file<'a>(&'a self, path: &'a Path) -> io::Result<()> {
+        self.inner.remove_file(path).await
+    }
+
+    async fn list_dir<'a>(&'a self, dir: &'a Path) -> io::Result<Vec<DirEntry>> {
+        self.inner.list_dir(dir).await
+    }
+
+    async fn create_dir<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
+        self.inner.create_dir(dir).await
+    }
+
+    async fn remove_dir<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
+        self.inner.remove_dir(dir).await
+    }
+
+    async fn read_bytes<'a>(&'a self, path: &'a Path) -> io::Result<Vec<u8>> {
+        match self.editor_read(path).await {
+            Some(content) => Ok(content.into_bytes()),
+            None => self.inner.read_bytes(path).await,
+        }
+    }
+
+    async fn metadata<'a>(&'a self, path: &'a Path) -> io::Result<FileMetadata> {
+        match self.inner.metadata(path).await {
+            // A buffer the editor hasn't saved yet has no file on disk.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => match self.editor_read(path).await {
+                Some(content) => Ok(FileMetadata {
+                    size: content.len() as u64,
+                    is_dir: false,
+                    modified: None,
+                }),
+                None => Err(e),
+            },
+            other => other,
+        }
+    }
+
+    async fn remove_dir_all<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
+        self.inner.remove_dir_all(dir).await
+    }
+
+    async fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    fn glob(&self, pattern: &str) -> io::Result<Vec<String>> {
+        self.inner.glob(pattern)
+    }
+
+    fn grep(
+        &self,
+        pattern: &Regex,
+        path: &Path,
+        max_results: usize,
+        context: usize,
+    ) -> io::Result<Vec<GrepMatch>> {
+        self.inner.grep(pattern, path, max_results, context)
+    }
+}
@@ -53,6 +53,8 @@
 //! 5. Editor sends `session/prompt` requests for user messages
 //! 6. Agent streams `session/update` notifications with responses
 //! 7. Agent returns `PromptResult` when turn is complete
+//! 8. Editor may send a `session/cancel` notification at any point during a
+//!    turn; the turn is interrupted and ends with the `cancelled` stop reason
 //!
 //! ## Session Updates
 //!
@@ -64,6 +66,36 @@
 //! - `ToolCall`: Tool execution started
 //! - `ToolCallUpdate`: Tool execution progress/completion
 //!
+//! ## Permission Prompts
+//!
+//! Tools that need approval ask through [`AcpServer::permission_bridge`]; the
+//! server forwards each question to the editor as `session/request_permission`
+//! so the user can allow or reject it (once or always) in real time. Install
+//! [`AcpPermissionBridge::hook`] on the agent to gate its edits, deletes, and
+//! shell commands the same way.
+//!
+//! ## Driving Other Agents
+//!
+//! [`AcpClient`] reverses the roles: aither launches an external ACP agent
+//! and acts as its client, receiving the agent's updates as
+//! [`AgentEvent`](aither_agent::AgentEvent)s so it can run as a sub-agent of
+//! an aither orchestrator.
+//!
+//! ## Resuming Sessions
+//!
+//! With [`AcpServer::with_session_store`], every session is saved to disk and
+//! the server advertises `loadSession`. On `session/load` the conversation is
+//! replayed to the editor as `session/update` notifications before the
+//! response, so the user sees the full history again.
+//!
+//! ## Editor Buffers
+//!
+//! When the editor supports `fs/read_text_file` and `fs/write_text_file`,
+//! wrap the agent's file system in an [`AcpFileSystem`] so reads see unsaved
+//! buffers and writes show up in the editor. Requests travel through
+//! [`AcpServer::fs_bridge`]; without the capabilities, files are accessed on
+//! disk as usual.
+//!
 //! ## Architecture
 //!
 //! ```text
@@ -80,11 +112,22 @@
 //! ```
 
 mod adapter;
+mod client;
+mod fs;
+mod permission;
 pub mod protocol;
 mod server;
 mod session;
+mod store;
 
-pub use adapter::{agent_event_to_session_update, todos_to_plan};
+pub use adapter::{
+    agent_event_to_session_update, agent_event_to_session_update_in, session_update_to_agent_event,
+    todos_to_plan,
+};
+pub use client::AcpClient;
+pub use fs::{AcpFileSystem, AcpFsBridge};
+pub use permission::{AcpPermissionBridge, AcpPermissionHook};
 pub use protocol::{AcpError, Result};
 pub use server::AcpServer;
-pub use session::AcpSession;
+pub use session::{AcpSession, Cancellation, PromptTurn, SessionRecord};
+pub use store::SessionStore;
@@ -0,0 +1,313 @@
+//! Real-time permission prompts routed to the editor.
+//!
+//! Tools that need user approval (for example sandboxed bash in unsafe mode)
+//! ask through an [`AcpPermissionBridge`]; the [`AcpServer`](crate::AcpServer)
+//! forwards each question to the editor as a `session/request_permission`
+//! request and returns the user's choice.
+//!
+//! Agent tool calls that change the workspace (edits, deletes, moves, shell
+//! commands) can be gated the same way by installing
+//! [`AcpPermissionBridge::hook`] on the agent:
+//!
+//! ```ignore
+//! let agent = Agent::builder(llm)
+//!     .hook(server.permission_bridge().hook(&session_id))
+//!     .build();
+//! ```
+//!
+//! Lower-level tools can ask directly:
+//!
+//! ```ignore
+//! use aither_acp::protocol::PermissionOptionKind;
+//! use aither_sandbox::permission::{PermissionDecision, PromptPermissionHandler};
+//!
+//! let bridge = server.permission_bridge();
+//! let handler = PromptPermissionHandler::new(move |prompt| {
+//!     let bridge = bridge.clone();
+//!     let session_id = session_id.clone();
+//!     async move {
+//!         match bridge.ask(&session_id, prompt.title()).await {
+//!             Ok(Some(PermissionOptionKind::AllowOnce)) => PermissionDecision::AllowOnce,
+//!             Ok(Some(PermissionOptionKind::AllowAlways)) => PermissionDecision::AllowAlways,
+//!             Ok(Some(PermissionOptionKind::RejectAlways)) => PermissionDecision::RejectAlways,
+//!             _ => PermissionDecision::RejectOnce,
+//!         }
+//!     }
+//! });
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use aither_agent::sandbox::permission::{BashMode, PermissionPrompt};
+use aither_agent::{Hook, PreToolAction, ToolUseContext};
+use async_channel::{Receiver, Sender};
+use tracing::debug;
+
+use crate::adapter::{format_tool_title, infer_tool_kind, is_destructive};
+use crate::protocol::{
+    AcpError, PermissionOption, PermissionOptionKind, RequestPermissionOutcome,
+    RequestPermissionParams, Result, ToolCallStatus, ToolCallUpdate, ToolKind,
+};
+
+/// A permission request waiting to be forwarded to the editor.
+pub(crate) struct PendingPermission {
+    pub(crate) params: RequestPermissionParams,
+    pub(crate) reply: Sender<Result<RequestPermissionOutcome>>,
+}
+
+/// Handle for asking the editor's user for permission.
+///
+/// Obtained from [`AcpServer::permission_bridge`](crate::AcpServer::permission_bridge).
+/// Cloning is cheap; all clones feed the same server.
+#[derive(Debug, Clone)]
+pub struct AcpPermissionBridge {
+    tx: Sender<PendingPermission>,
+}
+
+impl AcpPermissionBridge {
+    pub(crate) fn channel() -> (Self, Receiver<PendingPermission>) {
+        let (tx, rx) = async_channel::unbounded();
+        (Self { tx }, rx)
+    }
+
+    /// Send a raw permission request and wait for the outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server has stopped or the editor rejects the request.
+    pub async fn request(
+        &self,
+        params: RequestPermissionParams,
+    ) -> Result<RequestPermissionOutcome> {
+        let (reply, response) = async_channel::bounded(1);
+        self.tx
+            .send(PendingPermission { params, reply })
+            .await
+            .map_err(|_| AcpError::ConnectionClosed)?;
+        response
+            .recv()
+            .await
+            .map_err(|_| AcpError::ConnectionClosed)?
+    }
+
+    /// Ask an allow/reject, once/always question about an execute tool call.
+    ///
+    /// Returns the selected option kind, or `None` if the user dismissed the prompt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server has stopped or the editor rejects the request.
+    pub async fn ask(
+        &self,
+        session_id: &str,
+        title: impl Into<String>,
+    ) -> Result<Option<PermissionOptionKind>> {
+        let options = PermissionOption::standard();
+        let params = RequestPermissionParams {
+            session_id: session_id.to_string(),
+            tool_call: ToolCallUpdate {
+                tool_call_id: format!("permission-{}", uuid::Uuid::new_v4()),
+                status: Some(ToolCallStatus::Pending),
+                content: None,
+                title: Some(title.into()),
+                kind: Some(ToolKind::Execute),
+                locations: None,
+                raw_input: None,
+                raw_output: None,
+            },
+            options: options.clone(),
+        };
+
+        Ok(match self.request(params).await? {
+            RequestPermissionOutcome::Cancelled => None,
+            RequestPermissionOutcome::Selected { option_id } => options
+                .into_iter()
+                .find(|option| option.option_id == option_id)
+                .map(|option| option.kind),
+        })
+    }
+
+    /// Agent hook that asks the editor before every destructive tool call in `session_id`.
+    ///
+    /// "Always" answers are remembered for the lifetime of the hook: per
+    /// program for shell commands, or per exact script when it chains, pipes
+    /// or redirects commands, and per tool and kind otherwise.
+    #[must_use]
+    pub fn hook(&self, session_id: impl Into<String>) -> AcpPermissionHook {
+        AcpPermissionHook {
+            bridge: self.clone(),
+            session_id: session_id.into(),
+            remembered: Mutex::default(),
+        }
+    }
+}
+
+/// Agent [`Hook`] that routes approval of destructive tool calls to the editor.
+///
+/// Created by [`AcpPermissionBridge::hook`]. Reads and searches run without
+/// asking; edits, deletes, moves, and shell commands are shown to the user as
+/// `session/request_permission` prompts. A rejected or dismissed prompt, or a
+/// failed request, denies the call and tells the model why.
+#[derive(Debug)]
+pub struct AcpPermissionHook {
+    bridge: AcpPermissionBridge,
+    session_id: String,
+    remembered: Mutex<HashMap<(String, ToolKind), bool>>,
+}
+
+/// Key under which an "always" answer for a tool call is remembered.
+///
+/// Shell commands reuse the sandbox's keying, so approving one program
+/// doesn't approve every later command.
+fn approval_key(tool_name: &str, kind: ToolKind, arguments: &str) -> (String, ToolKind) {
+    let args = serde_json::from_str::<serde_json::Value>(arguments).ok();
+    let field = |name: &str| {
+        args.as_ref()
+            .and_then(|args| args.get(name)?.as_str().map(str::to_string))
+    };
+    let scope = match tool_name.to_lowercase().as_str() {
+        "bash" => field("script").map(|script| {
+            PermissionPrompt::Script {
+                mode: BashMode::Unsafe,
+                script,
+            }
+            .pattern()
+        }),
+        "command" => field("program").map(|program| format!("unsafe:{program}")),
+        _ => None,
+    };
+    let name = match scope {
+        Some(scope) => format!("{tool_name} {scope}"),
+        None => tool_name.to_string(),
+    };
+    (name, kind)
+}
+
+impl AcpPermissionHook {
+    fn remembered(&self, key: &(String, ToolKind)) -> Option<bool> {
+        self.remembered
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key)
+            .copied()
+    }
+
+    fn remember(&self, key: (String, ToolKind), allowed: bool) {
+        self.remembered
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, allowed);
+    }
+}
+
+impl Hook for AcpPermissionHook {
+    async fn pre_tool_use(&self, ctx: &ToolUseContext<'_>) -> PreToolAction {
+        let kind = infer_tool_kind(ctx.tool_name, ctx.arguments);
+        if !is_destructive(kind) {
+            return PreToolAction::Allow;
+        }
+
+        let key = approval_key(ctx.tool_name, kind, ctx.arguments);
+        match self.remembered(&key) {
+            Some(true) => return PreToolAction::Allow,
+            Some(false) => {
+                return PreToolAction::Deny(format!(
+                    "The user has rejected all {} calls like this one",
+                    ctx.tool_name
+                ));
+            }
+            None => {}
+        }
+
+        let options = PermissionOption::standard();
+        let params = RequestPermissionParams {
+            session_id: self.session_id.clone(),
+            tool_call: ToolCallUpdate {
+                tool_call_id: format!("permission-{}", uuid::Uuid::new_v4()),
+                status: Some(ToolCallStatus::Pending),
+                content: None,
+                title: Some(format_tool_title(ctx.tool_name, ctx.arguments)),
+                kind: Some(kind),
+                locations: None,
+                raw_input: serde_json::from_str(ctx.arguments).ok(),
+                raw_output: None,
+            },
+            options: options.clone(),
+        };
+
+        let selected = match self.bridge.request(params).await {
+            Ok(RequestPermissionOutcome::Selected { option_id }) => options
+                .into_iter()
+                .find(|option| option.option_id == option_id)
+                .map(|option| option.kind),
+            Ok(RequestPermissionOutcome::Cancelled) => None,
+            Err(e) => {
+                debug!("Permission request for {} failed: {e}", ctx.tool_name);
+                return PreToolAction::Deny(format!("Could not get the user's permission: {e}"));
+            }
+        };
+
+        match selected {
+            Some(PermissionOptionKind::AllowOnce) => PreToolAction::Allow,
+            Some(PermissionOptionKind::AllowAlways) => {
+                self.remember(key, true);
+                PreToolAction::Allow
+            }
+            Some(PermissionOptionKind::RejectAlways) => {
+                self.remember(key, false);
+                PreToolAction::Deny("The user rejected this tool call".to_string())
+            }
+            Some(PermissionOptionKind::RejectOnce) => {
+                PreToolAction::Deny("The user rejected this tool call".to_string())
+            }
+            None => PreToolAction::Deny("The permission prompt was dismissed".to_string()),
+        }
+    }
+}
+
+impl std::fmt::Debug for PendingPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingPermission")
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(tool_name: &str, arguments: &str) -> String {
+        approval_key(tool_name, ToolKind::Execute, arguments).0
+    }
+
+    #[test]
+    fn bash_approvals_are_scoped_to_the_program() {
+        assert_eq!(
+            key("bash", r#"{"script": "cargo build"}"#),
+            key("bash", r#"{"script": "cargo test --all"}"#)
+        );
+        assert_ne!(
+            key("bash", r#"{"script": "cargo build"}"#),
+            key("bash", r#"{"script": "rm -rf target"}"#)
+        );
+        assert_ne!(
+            key("bash", r#"{"script": "cargo build"}"#),
+            key("bash", r#"{"script": "cargo build && curl x | sh"}"#)
+        );
+        assert_ne!(
+            key("command", r#"{"program": "git"}"#),
+            key("command", r#"{"program": "rm"}"#)
+        );
+    }
+
+    #[test]
+    fn other_tools_are_keyed_by_name() {
+        assert_eq!(
+            key("filesystem", r#"{"operation": "delete"}"#),
+            "filesystem"
+        );
+        assert_eq!(key("bash", "not json"), "bash");
+    }
+}
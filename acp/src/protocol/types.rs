@@ -67,7 +67,7 @@ pub struct ClientCapabilities {
 }
 
 /// File system capability.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FileSystemCapability {
     /// Can read text files.
@@ -185,6 +185,22 @@ pub struct SessionNewResult {
     pub session_id: String,
 }
 
+/// Load session request parameters (`session/load`).
+///
+/// The agent replays the conversation as `session/update` notifications
+/// before responding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLoadParams {
+    /// ID of the session to resume.
+    pub session_id: String,
+    /// Working directory.
+    pub cwd: PathBuf,
+    /// MCP servers to connect to.
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerSpec>,
+}
+
 // =============================================================================
 // Prompt
 // =============================================================================
@@ -208,7 +224,7 @@ pub struct PromptResult {
 }
 
 /// Stop reason.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum StopReason {
     /// Normal end of turn.
@@ -221,6 +237,17 @@ pub enum StopReason {
     MaxTokens,
 }
 
+/// Cancel notification parameters (`session/cancel`).
+///
+/// Interrupts the running prompt turn, which then finishes with
+/// [`StopReason::Cancelled`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCancelParams {
+    /// Session ID.
+    pub session_id: String,
+}
+
 /// Stop session request parameters.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -436,13 +463,17 @@ pub struct ToolCallUpdate {
 }
 
 /// Tool kind.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ToolKind {
     /// Read operation.
     Read,
     /// Write/edit operation.
     Edit,
+    /// Delete operation.
+    Delete,
+    /// Move/rename operation.
+    Move,
     /// Search operation.
     Search,
     /// Execute/run operation.
@@ -507,3 +538,140 @@ pub struct ToolCallLocation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line: Option<u32>,
 }
+
+// =============================================================================
+// Permissions
+// =============================================================================
+
+/// Permission request parameters (`session/request_permission`, agent to client).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPermissionParams {
+    /// Session ID.
+    pub session_id: String,
+    /// The tool call awaiting approval.
+    pub tool_call: ToolCallUpdate,
+    /// Choices offered to the user.
+    pub options: Vec<PermissionOption>,
+}
+
+/// A choice offered in a permission request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionOption {
+    /// Option ID returned when selected.
+    pub option_id: String,
+    /// Label shown to the user.
+    pub name: String,
+    /// What selecting the option means.
+    pub kind: PermissionOptionKind,
+}
+
+impl PermissionOption {
+    /// The four standard choices: allow/reject, once/always.
+    #[must_use]
+    pub fn standard() -> Vec<Self> {
+        [
+            (PermissionOptionKind::AllowOnce, "Allow"),
+            (PermissionOptionKind::AllowAlways, "Always allow"),
+            (PermissionOptionKind::RejectOnce, "Reject"),
+            (PermissionOptionKind::RejectAlways, "Always reject"),
+        ]
+        .into_iter()
+        .map(|(kind, name)| Self {
+            option_id: kind.as_str().to_string(),
+            name: name.to_string(),
+            kind,
+        })
+        .collect()
+    }
+}
+
+/// Permission option kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionOptionKind {
+    /// Allow this once.
+    AllowOnce,
+    /// Allow this and remember the choice.
+    AllowAlways,
+    /// Reject this once.
+    RejectOnce,
+    /// Reject this and remember the choice.
+    RejectAlways,
+}
+
+impl PermissionOptionKind {
+    /// Wire name of the kind.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::AllowOnce => "allow_once",
+            Self::AllowAlways => "allow_always",
+            Self::RejectOnce => "reject_once",
+            Self::RejectAlways => "reject_always",
+        }
+    }
+}
+
+/// Permission request response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestPermissionResult {
+    /// The user's answer.
+    pub outcome: RequestPermissionOutcome,
+}
+
+/// Outcome of a permission request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RequestPermissionOutcome {
+    /// The prompt was dismissed or the turn was cancelled.
+    Cancelled,
+    /// The user picked an option.
+    Selected {
+        /// ID of the selected option.
+        #[serde(rename = "optionId")]
+        option_id: String,
+    },
+}
+
+// =============================================================================
+// File System
+// =============================================================================
+
+/// Read request parameters (`fs/read_text_file`, agent to client).
+///
+/// The client answers from its open buffer when the file has unsaved edits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTextFileParams {
+    /// Session ID.
+    pub session_id: String,
+    /// Absolute path of the file.
+    pub path: String,
+    /// 1-based line to start reading from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// Maximum number of lines to read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Read response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadTextFileResult {
+    /// File contents.
+    pub content: String,
+}
+
+/// Write request parameters (`fs/write_text_file`, agent to client).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteTextFileParams {
+    /// Session ID.
+    pub session_id: String,
+    /// Absolute path of the file.
+    pub path: String,
+    /// New file contents.
+    pub content: String,
+}
@@ -2,17 +2,44 @@
 
 use std::collections::HashMap;
 
-use aither_mcp::transport::{BidirectionalTransport, StdioTransport};
+use aither_mcp::transport::{BidirectionalTransport, StdioTransport, Transport};
+use async_channel::{Receiver, Sender};
 use tracing::debug;
 
+use crate::fs::{AcpFsBridge, PendingFsRequest};
+use crate::permission::{AcpPermissionBridge, PendingPermission};
 use crate::protocol::{
     AcpError, AgentCapabilities, Implementation, InitializeParams, InitializeResult, JsonRpcError,
     JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, McpCapabilities,
-    PROTOCOL_VERSION, PromptCapabilities, PromptParams, PromptResult, Result, SessionCapabilities,
+    PROTOCOL_VERSION, PromptCapabilities, PromptParams, PromptResult, RequestId,
+    RequestPermissionResult, Result, SessionCancelParams, SessionCapabilities, SessionLoadParams,
     SessionNewParams, SessionNewResult, SessionNotification, SessionStopParams, SessionUpdate,
     StopReason,
 };
 use crate::session::AcpSession;
+use crate::store::SessionStore;
+
+/// Next thing the server loop has to handle.
+enum ServerEvent {
+    Incoming(Result<Option<JsonRpcMessage>>),
+    Permission(PendingPermission),
+    FileSystem(PendingFsRequest),
+    Turn(TurnEvent),
+}
+
+/// Progress of a prompt turn running on its own thread.
+enum TurnEvent {
+    Update {
+        session_id: String,
+        update: SessionUpdate,
+    },
+    Finished {
+        request_id: RequestId,
+        session_id: String,
+        turn_id: u64,
+        result: std::result::Result<StopReason, String>,
+    },
+}
 
 /// ACP server that exposes aither agents to code editors.
 ///
@@ -35,6 +62,13 @@ pub struct AcpServer<T: BidirectionalTransport> {
     info: Implementation,
     sessions: HashMap<String, AcpSession>,
     initialized: bool,
+    permission_bridge: AcpPermissionBridge,
+    permission_rx: Receiver<PendingPermission>,
+    fs_bridge: AcpFsBridge,
+    fs_rx: Receiver<PendingFsRequest>,
+    store: Option<SessionStore>,
+    turn_tx: Sender<TurnEvent>,
+    turn_rx: Receiver<TurnEvent>,
 }
 
 impl<T: BidirectionalTransport> std::fmt::Debug for AcpServer<T> {
@@ -63,6 +97,9 @@ impl AcpServer<StdioTransport> {
     /// Returns an error if stdio cannot be initialized.
     pub fn stdio(name: impl Into<String>, version: impl Into<String>) -> Result<Self> {
         let transport = StdioTransport::new().map_err(|e| AcpError::Transport(e.to_string()))?;
+        let (permission_bridge, permission_rx) = AcpPermissionBridge::channel();
+        let (fs_bridge, fs_rx) = AcpFsBridge::channel();
+        let (turn_tx, turn_rx) = async_channel::unbounded();
         Ok(Self {
             transport,
             info: Implementation {
@@ -72,14 +109,51 @@ impl AcpServer<StdioTransport> {
             },
             sessions: HashMap::new(),
             initialized: false,
+            permission_bridge,
+            permission_rx,
+            fs_bridge,
+            fs_rx,
+            store: None,
+            turn_tx,
+            turn_rx,
         })
     }
 }
 
 impl<T: BidirectionalTransport> AcpServer<T> {
+    /// Persist sessions to `store` and advertise `session/load`.
+    ///
+    /// Sessions are saved when created and after every prompt, so editors can
+    /// resume them after the agent restarts.
+    #[must_use]
+    pub fn with_session_store(mut self, store: SessionStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Handle for asking the editor's user for permission.
+    ///
+    /// Questions sent through the bridge are forwarded by [`run`](Self::run) as
+    /// `session/request_permission` requests.
+    #[must_use]
+    pub fn permission_bridge(&self) -> AcpPermissionBridge {
+        self.permission_bridge.clone()
+    }
+
+    /// Handle for reading and writing files through the editor.
+    ///
+    /// Requests sent through the bridge are forwarded by [`run`](Self::run) as
+    /// `fs/read_text_file` and `fs/write_text_file` requests, if the editor
+    /// advertised those capabilities in `initialize`.
+    #[must_use]
+    pub fn fs_bridge(&self) -> AcpFsBridge {
+        self.fs_bridge.clone()
+    }
+
     /// Run the server main loop.
     ///
-    /// This processes incoming requests until the connection is closed.
+    /// This processes incoming requests and forwards permission prompts and
+    /// file requests until the connection is closed.
     ///
     /// # Errors
     ///
@@ -88,25 +162,129 @@ impl<T: BidirectionalTransport> AcpServer<T> {
         debug!("ACP server starting: {}", self.info.name);
 
         loop {
-            if let Some(msg) = self.recv().await? {
-                if let Err(e) = self.handle_message(msg).await {
-                    debug!("Error handling message: {e}");
+            match self.next_event().await {
+                ServerEvent::Incoming(msg) => {
+                    if let Some(msg) = msg? {
+                        if let Err(e) = self.handle_message(msg).await {
+                            debug!("Error handling message: {e}");
+                        }
+                    } else {
+                        debug!("Connection closed");
+                        break;
+                    }
+                }
+                ServerEvent::Permission(pending) => self.forward_permission(pending).await,
+                ServerEvent::FileSystem(pending) => self.forward_fs(pending).await,
+                ServerEvent::Turn(event) => {
+                    if let Err(e) = self.handle_turn_event(event).await {
+                        debug!("Error reporting prompt turn: {e}");
+                    }
                 }
-            } else {
-                debug!("Connection closed");
-                break;
             }
         }
 
         Ok(())
     }
 
-    /// Receive the next message from the transport.
-    async fn recv(&mut self) -> Result<Option<JsonRpcMessage>> {
-        self.transport
-            .recv()
+    /// Wait for the next incoming message, queued client request, or turn progress.
+    async fn next_event(&mut self) -> ServerEvent {
+        let transport = &mut self.transport;
+        let permission_rx = &self.permission_rx;
+        let fs_rx = &self.fs_rx;
+        let turn_rx = &self.turn_rx;
+        futures_lite::future::or(
+            async {
+                ServerEvent::Incoming(
+                    transport
+                        .recv()
+                        .await
+                        .map_err(|e| AcpError::Transport(e.to_string())),
+                )
+            },
+            futures_lite::future::or(
+                async {
+                    // The server keeps a sender alive, so the channel never closes.
+                    match permission_rx.recv().await {
+                        Ok(pending) => ServerEvent::Permission(pending),
+                        Err(_) => std::future::pending().await,
+                    }
+                },
+                futures_lite::future::or(
+                    async {
+                        match fs_rx.recv().await {
+                            Ok(pending) => ServerEvent::FileSystem(pending),
+                            Err(_) => std::future::pending().await,
+                        }
+                    },
+                    async {
+                        match turn_rx.recv().await {
+                            Ok(event) => ServerEvent::Turn(event),
+                            Err(_) => std::future::pending().await,
+                        }
+                    },
+                ),
+            ),
+        )
+        .await
+    }
+
+    /// Send a request to the client and wait for its result.
+    async fn client_request(
+        &mut self,
+        method: &str,
+        params: &impl serde::Serialize,
+    ) -> Result<serde_json::Value> {
+        let request = JsonRpcRequest::with_params(0, method, params);
+        let result = match self.transport.request(request).await {
+            Ok(response) => response.into_result().map_err(AcpError::from),
+            Err(e) => Err(AcpError::Transport(e.to_string())),
+        };
+        // Notifications that arrived while waiting, such as `session/cancel`.
+        for notif in self.transport.take_notifications() {
+            self.handle_notification(&notif);
+        }
+        result
+    }
+
+    /// Ask the editor for permission and hand the outcome back to the waiting tool.
+    async fn forward_permission(&mut self, pending: PendingPermission) {
+        debug!(
+            "Requesting permission for session {}",
+            pending.params.session_id
+        );
+        let outcome = self
+            .client_request("session/request_permission", &pending.params)
             .await
-            .map_err(|e| AcpError::Transport(e.to_string()))
+            .and_then(|value| {
+                serde_json::from_value::<RequestPermissionResult>(value).map_err(AcpError::from)
+            })
+            .map(|result| result.outcome);
+        if let Err(e) = &outcome {
+            debug!("Permission request failed: {e}");
+        }
+        let _ = pending.reply.send(outcome).await;
+    }
+
+    /// Forward a file request to the editor, refusing it if the editor lacks the capability.
+    async fn forward_fs(&mut self, pending: PendingFsRequest) {
+        let capabilities = self.fs_bridge.capabilities();
+        let supported = match pending.method {
+            "fs/read_text_file" => capabilities.read_text_file,
+            "fs/write_text_file" => capabilities.write_text_file,
+            _ => false,
+        };
+        let result = if supported {
+            self.client_request(pending.method, &pending.params).await
+        } else {
+            Err(AcpError::InvalidState(format!(
+                "client does not support {}",
+                pending.method
+            )))
+        };
+        if let Err(e) = &result {
+            debug!("{} failed: {e}", pending.method);
+        }
+        let _ = pending.reply.send(result).await;
     }
 
     /// Send a notification to the client.
@@ -129,16 +307,11 @@ impl<T: BidirectionalTransport> AcpServer<T> {
     async fn handle_message(&mut self, msg: JsonRpcMessage) -> Result<()> {
         match msg {
             JsonRpcMessage::Request(req) => {
-                let response = self.handle_request(req).await;
-                self.respond(response).await?;
-            }
-            JsonRpcMessage::Notification(notif) => {
-                debug!("Received notification: {}", notif.method);
-                // Handle notifications
-                if notif.method == "notifications/initialized" {
-                    debug!("Client initialized");
+                if let Some(response) = self.handle_request(req).await {
+                    self.respond(response).await?;
                 }
             }
+            JsonRpcMessage::Notification(notif) => self.handle_notification(&notif),
             JsonRpcMessage::Response(_) => {
                 // We don't expect responses as a server
                 debug!("Unexpected response message");
@@ -147,22 +320,48 @@ impl<T: BidirectionalTransport> AcpServer<T> {
         Ok(())
     }
 
+    /// Handle an incoming notification.
+    fn handle_notification(&mut self, notif: &JsonRpcNotification) {
+        debug!("Received notification: {}", notif.method);
+        match notif.method.as_str() {
+            "notifications/initialized" => debug!("Client initialized"),
+            "session/cancel" => {
+                let params = notif
+                    .params
+                    .clone()
+                    .map(serde_json::from_value::<SessionCancelParams>);
+                match params {
+                    Some(Ok(params)) => match self.sessions.get(&params.session_id) {
+                        Some(session) => session.stop(),
+                        None => debug!("Cancel for unknown session: {}", params.session_id),
+                    },
+                    _ => debug!("Ignoring malformed session/cancel"),
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Handle an incoming request.
-    async fn handle_request(&mut self, req: JsonRpcRequest) -> JsonRpcResponse {
+    ///
+    /// Returns `None` when the response is sent later, as for `session/prompt`.
+    async fn handle_request(&mut self, req: JsonRpcRequest) -> Option<JsonRpcResponse> {
         debug!("Handling request: {}", req.method);
 
-        match req.method.as_str() {
+        let response = match req.method.as_str() {
             "initialize" => self.handle_initialize(req),
             "session/new" => self.handle_session_new(req).await,
-            "session/prompt" => self.handle_session_prompt(req).await,
+            "session/load" => self.handle_session_load(req).await,
+            "session/prompt" => return self.handle_session_prompt(req),
             "session/stop" => self.handle_session_stop(req).await,
             method => JsonRpcResponse::error(req.id, JsonRpcError::method_not_found(method)),
-        }
+        };
+        Some(response)
     }
 
     /// Handle initialize request.
     fn handle_initialize(&mut self, req: JsonRpcRequest) -> JsonRpcResponse {
-        let _params: Option<InitializeParams> = match req
+        let params: Option<InitializeParams> = match req
             .params
             .map(serde_json::from_value)
             .transpose()
@@ -174,11 +373,15 @@ impl<T: BidirectionalTransport> AcpServer<T> {
         };
 
         self.initialized = true;
+        if let Some(params) = params {
+            self.fs_bridge
+                .set_capabilities(params.client_capabilities.fs);
+        }
 
         let result = InitializeResult {
             protocol_version: PROTOCOL_VERSION.to_string(),
             agent_capabilities: AgentCapabilities {
-                load_session: false, // No session persistence
+                load_session: self.store.is_some(),
                 prompt_capabilities: PromptCapabilities {
                     image: false,
                     audio: false,
@@ -217,13 +420,14 @@ impl<T: BidirectionalTransport> AcpServer<T> {
         let session_id = session.id().to_string();
 
         self.sessions.insert(session_id.clone(), session);
+        self.save_session(&session_id).await;
 
         JsonRpcResponse::success(req.id, SessionNewResult { session_id })
     }
 
-    /// Handle session/prompt request.
-    async fn handle_session_prompt(&mut self, req: JsonRpcRequest) -> JsonRpcResponse {
-        let params: PromptParams = match req.params.map(serde_json::from_value).transpose() {
+    /// Handle session/load request: restore the session and replay its history.
+    async fn handle_session_load(&mut self, req: JsonRpcRequest) -> JsonRpcResponse {
+        let params: SessionLoadParams = match req.params.map(serde_json::from_value).transpose() {
             Ok(Some(p)) => p,
             Ok(None) => {
                 return JsonRpcResponse::error(
@@ -236,22 +440,86 @@ impl<T: BidirectionalTransport> AcpServer<T> {
             }
         };
 
-        let session = match self.sessions.get_mut(&params.session_id) {
-            Some(s) => s,
+        let record = match self.sessions.remove(&params.session_id) {
+            Some(session) => session.to_record(),
             None => {
-                return JsonRpcResponse::error(
+                let loaded = match &self.store {
+                    Some(store) => store.load(&params.session_id).await,
+                    None => Err(AcpError::SessionNotFound(params.session_id.clone())),
+                };
+                match loaded {
+                    Ok(record) => record,
+                    Err(e) => {
+                        return JsonRpcResponse::error(
+                            req.id,
+                            JsonRpcError::invalid_params(e.to_string()),
+                        );
+                    }
+                }
+            }
+        };
+
+        let session = AcpSession::restore(record, params.cwd, params.mcp_servers);
+        let history = session.history().to_vec();
+        self.sessions.insert(params.session_id.clone(), session);
+
+        // The protocol requires the whole history before the load response.
+        for update in history {
+            if let Err(e) = self.send_update(&params.session_id, update).await {
+                debug!("Failed to replay session history: {e}");
+                return JsonRpcResponse::error(req.id, JsonRpcError::internal_error(e.to_string()));
+            }
+        }
+
+        JsonRpcResponse::success(req.id, serde_json::Value::Null)
+    }
+
+    /// Persist a session if a store is configured.
+    async fn save_session(&self, session_id: &str) {
+        let (Some(store), Some(session)) = (&self.store, self.sessions.get(session_id)) else {
+            return;
+        };
+        if let Err(e) = store.save(session).await {
+            debug!("Failed to save session {session_id}: {e}");
+        }
+    }
+
+    /// Handle session/prompt request by starting a turn on its own thread.
+    ///
+    /// The response is sent when the turn finishes, so the loop keeps serving
+    /// `session/cancel` and client requests in the meantime.
+    fn handle_session_prompt(&mut self, req: JsonRpcRequest) -> Option<JsonRpcResponse> {
+        let params: PromptParams = match req.params.map(serde_json::from_value).transpose() {
+            Ok(Some(p)) => p,
+            Ok(None) => {
+                return Some(JsonRpcResponse::error(
                     req.id,
-                    JsonRpcError::invalid_params(format!(
-                        "Session not found: {}",
-                        params.session_id
-                    )),
-                );
+                    JsonRpcError::invalid_params("Missing params"),
+                ));
+            }
+            Err(e) => {
+                return Some(JsonRpcResponse::error(
+                    req.id,
+                    JsonRpcError::invalid_params(e.to_string()),
+                ));
             }
         };
 
-        // Process prompt and stream updates
-        // For now, we'll implement a simple placeholder
-        // The actual implementation will integrate with the agent
+        let Some(session) = self.sessions.get_mut(&params.session_id) else {
+            return Some(JsonRpcResponse::error(
+                req.id,
+                JsonRpcError::invalid_params(format!("Session not found: {}", params.session_id)),
+            ));
+        };
+        if session.is_busy() {
+            return Some(JsonRpcResponse::error(
+                req.id,
+                JsonRpcError::invalid_params(format!(
+                    "Session {} is already processing a prompt",
+                    params.session_id
+                )),
+            ));
+        }
 
         // Extract text from prompt
         let prompt_text = params
@@ -267,23 +535,67 @@ impl<T: BidirectionalTransport> AcpServer<T> {
             .collect::<Vec<_>>()
             .join("\n");
 
-        // Run the agent and stream updates
-        let stop_reason = match session.prompt(&prompt_text, |update| {
-            // Send session update notification
-            let session_id = params.session_id.clone();
-            let notif = SessionNotification { session_id, update };
-            // Note: In a real implementation, we'd need async notification sending
-            // For now, we'll collect updates and send them after
-            debug!("Session update: {:?}", notif);
-        }) {
-            Ok(()) => StopReason::EndTurn,
-            Err(e) => {
-                debug!("Agent error: {e}");
-                StopReason::Error
-            }
-        };
+        let turn = session.prompt(&prompt_text);
+        let turn_id = turn.id();
+        let tx = self.turn_tx.clone();
+        let session_id = params.session_id;
+        let request_id = req.id;
+        std::thread::spawn(move || {
+            async_io::block_on(async move {
+                let updates = tx.clone();
+                let update_session = session_id.clone();
+                let result = turn
+                    .run(move |update| {
+                        // Unbounded, so this only fails once the server is gone.
+                        let _ = updates.try_send(TurnEvent::Update {
+                            session_id: update_session.clone(),
+                            update,
+                        });
+                    })
+                    .await;
+                let _ = tx
+                    .send(TurnEvent::Finished {
+                        request_id,
+                        session_id,
+                        turn_id,
+                        result,
+                    })
+                    .await;
+            });
+        });
+        None
+    }
 
-        JsonRpcResponse::success(req.id, PromptResult { stop_reason })
+    /// Stream a turn's update to the client, or answer its prompt request once it ends.
+    async fn handle_turn_event(&mut self, event: TurnEvent) -> Result<()> {
+        match event {
+            TurnEvent::Update { session_id, update } => {
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.record(update.clone());
+                }
+                self.send_update(&session_id, update).await
+            }
+            TurnEvent::Finished {
+                request_id,
+                session_id,
+                turn_id,
+                result,
+            } => {
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.finish_turn(turn_id);
+                }
+                let stop_reason = result.unwrap_or_else(|e| {
+                    debug!("Agent error: {e}");
+                    StopReason::Error
+                });
+                self.save_session(&session_id).await;
+                self.respond(JsonRpcResponse::success(
+                    request_id,
+                    PromptResult { stop_reason },
+                ))
+                .await
+            }
+        }
     }
 
     /// Handle session/stop request.
@@ -1,14 +1,37 @@
 //! ACP session management.
 //!
-//! Each session represents an active conversation with the agent.
+//! Each session represents an active conversation with the agent. A prompt
+//! runs as a [`PromptTurn`] that can be interrupted through its
+//! [`Cancellation`] when the editor sends `session/cancel`.
 
+use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use async_channel::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::protocol::{McpServerSpec, SessionUpdate};
+use crate::protocol::{
+    ContentBlock, ContentChunk, McpServerSpec, SessionUpdate, StopReason, TextContent,
+};
+
+/// Persisted form of an [`AcpSession`], written by [`SessionStore`](crate::SessionStore).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRecord {
+    /// Session ID.
+    pub session_id: String,
+    /// Working directory the session last ran in.
+    pub cwd: PathBuf,
+    /// Every update sent to the client, in order, for replay on `session/load`.
+    #[serde(default)]
+    pub history: Vec<SessionUpdate>,
+    /// Opaque agent state, e.g. a serialized conversation context.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub state: serde_json::Value,
+}
 
 /// An active ACP session.
 ///
@@ -19,6 +42,11 @@ pub struct AcpSession {
     cwd: PathBuf,
     mcp_servers: Vec<McpServerSpec>,
     cancelled: Arc<AtomicBool>,
+    /// ID of the running turn and the sender closed to interrupt it.
+    turn: Option<(u64, Sender<()>)>,
+    turns_started: u64,
+    history: Vec<SessionUpdate>,
+    state: serde_json::Value,
 }
 
 impl AcpSession {
@@ -30,9 +58,56 @@ impl AcpSession {
             cwd,
             mcp_servers,
             cancelled: Arc::new(AtomicBool::new(false)),
+            turn: None,
+            turns_started: 0,
+            history: Vec::new(),
+            state: serde_json::Value::Null,
         }
     }
 
+    /// Resume a persisted session in `cwd` with the client's MCP servers.
+    #[must_use]
+    pub fn restore(record: SessionRecord, cwd: PathBuf, mcp_servers: Vec<McpServerSpec>) -> Self {
+        Self {
+            id: record.session_id,
+            cwd,
+            mcp_servers,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            turn: None,
+            turns_started: 0,
+            history: record.history,
+            state: record.state,
+        }
+    }
+
+    /// Snapshot the session for persistence.
+    #[must_use]
+    pub fn to_record(&self) -> SessionRecord {
+        SessionRecord {
+            session_id: self.id.clone(),
+            cwd: self.cwd.clone(),
+            history: self.history.clone(),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Updates sent to the client so far, in order.
+    #[must_use]
+    pub fn history(&self) -> &[SessionUpdate] {
+        &self.history
+    }
+
+    /// Opaque agent state saved with the session.
+    #[must_use]
+    pub const fn state(&self) -> &serde_json::Value {
+        &self.state
+    }
+
+    /// Replace the agent state saved with the session.
+    pub fn set_state(&mut self, state: serde_json::Value) {
+        self.state = state;
+    }
+
     /// Get the session ID.
     #[must_use]
     pub fn id(&self) -> &str {
@@ -58,8 +133,40 @@ impl AcpSession {
     }
 
     /// Cancel the current operation.
+    ///
+    /// A running [`PromptTurn`] is interrupted at once: whatever it is awaiting
+    /// through its [`Cancellation`] is dropped and it finishes with
+    /// [`StopReason::Cancelled`].
     pub fn stop(&self) {
         self.cancelled.store(true, Ordering::SeqCst);
+        if let Some((_, turn)) = &self.turn {
+            turn.close();
+        }
+    }
+
+    /// Returns `true` while a prompt turn is running and not cancelled.
+    ///
+    /// A cancelled turn no longer blocks the next prompt, even before it has
+    /// finished unwinding.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.turn
+            .as_ref()
+            .is_some_and(|(_, turn)| !turn.is_closed())
+    }
+
+    /// Mark a turn as finished.
+    ///
+    /// Ignored if a newer turn has started since.
+    pub fn finish_turn(&mut self, turn_id: u64) {
+        if self.turn.as_ref().is_some_and(|(id, _)| *id == turn_id) {
+            self.turn = None;
+        }
+    }
+
+    /// Append an update sent to the client to the replayable history.
+    pub fn record(&mut self, update: SessionUpdate) {
+        self.history.push(update);
     }
 
     /// Reset cancellation flag.
@@ -67,30 +174,167 @@ impl AcpSession {
         self.cancelled.store(false, Ordering::SeqCst);
     }
 
-    /// Process a prompt and emit updates via the callback.
+    /// Start a prompt turn.
     ///
-    /// This is a placeholder implementation. The full implementation
-    /// will integrate with the aither agent to process prompts and
-    /// stream events as ACP session updates.
-    pub fn prompt<F>(&mut self, prompt: &str, mut on_update: F) -> Result<(), String>
-    where
-        F: FnMut(SessionUpdate),
-    {
-        // Reset cancellation flag
+    /// The prompt is recorded in [`history`](Self::history) right away; the
+    /// updates the turn produces should be passed to [`record`](Self::record)
+    /// as they are sent to the client.
+    pub fn prompt(&mut self, prompt: &str) -> PromptTurn {
         self.reset();
-
-        // Placeholder: Echo back the prompt as an agent message
-        // In the real implementation, this will run the agent loop
-        use crate::protocol::{ContentBlock, ContentChunk, TextContent};
-
-        // Send agent message chunk
-        on_update(SessionUpdate::AgentMessageChunk(ContentChunk {
+        self.record(SessionUpdate::UserMessageChunk(ContentChunk {
             content: ContentBlock::Text(TextContent {
-                text: format!("Received prompt in {}: {}", self.cwd.display(), prompt),
+                text: prompt.to_string(),
                 annotations: None,
             }),
         }));
 
-        Ok(())
+        let (tx, rx) = async_channel::bounded(1);
+        self.turns_started += 1;
+        self.turn = Some((self.turns_started, tx));
+        PromptTurn {
+            id: self.turns_started,
+            cwd: self.cwd.clone(),
+            prompt: prompt.to_string(),
+            cancellation: Cancellation { rx },
+        }
+    }
+}
+
+/// Signal that the running turn was cancelled.
+///
+/// Cloning is cheap; every clone observes the same cancellation.
+#[derive(Debug, Clone)]
+pub struct Cancellation {
+    rx: Receiver<()>,
+}
+
+impl Cancellation {
+    /// Returns `true` once the turn has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.rx.is_closed()
+    }
+
+    /// Wait until the turn is cancelled.
+    pub async fn cancelled(&self) {
+        // Nothing is ever sent; `recv` only returns once the channel is closed.
+        let _ = self.rx.recv().await;
+    }
+
+    /// Run `future` unless the turn is cancelled first.
+    ///
+    /// Returns `None` if cancelled, in which case `future` is dropped.
+    pub async fn run<T>(&self, future: impl Future<Output = T>) -> Option<T> {
+        if self.is_cancelled() {
+            return None;
+        }
+        futures_lite::future::or(async { Some(future.await) }, async {
+            self.cancelled().await;
+            None
+        })
+        .await
+    }
+}
+
+/// One prompt turn, detached from its session so it can run concurrently
+/// with the server loop.
+#[derive(Debug)]
+pub struct PromptTurn {
+    id: u64,
+    cwd: PathBuf,
+    prompt: String,
+    cancellation: Cancellation,
+}
+
+impl PromptTurn {
+    /// ID to pass to [`AcpSession::finish_turn`].
+    #[must_use]
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Cancellation signal for this turn.
+    #[must_use]
+    pub const fn cancellation(&self) -> &Cancellation {
+        &self.cancellation
+    }
+
+    /// Run the turn, emitting updates via the callback.
+    ///
+    /// Returns [`StopReason::Cancelled`] if the session was stopped while the
+    /// turn was running.
+    ///
+    /// This is a placeholder implementation that echoes the prompt; it does not
+    /// drive an agent yet. Once it does, the agent's stream must be awaited
+    /// through [`Cancellation::run`] for `session/cancel` to stop it mid-stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent fails.
+    pub async fn run<F>(self, mut on_update: F) -> Result<StopReason, String>
+    where
+        F: FnMut(SessionUpdate) + Send,
+    {
+        let Self {
+            id: _,
+            cwd,
+            prompt,
+            cancellation,
+        } = self;
+        let work = async move {
+            // Placeholder: Echo back the prompt as an agent message
+            // In the real implementation, this will run the agent loop
+            on_update(SessionUpdate::AgentMessageChunk(ContentChunk {
+                content: ContentBlock::Text(TextContent {
+                    text: format!("Received prompt in {}: {}", cwd.display(), prompt),
+                    annotations: None,
+                }),
+            }));
+            Ok(StopReason::EndTurn)
+        };
+        cancellation
+            .run(work)
+            .await
+            .unwrap_or(Ok(StopReason::Cancelled))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancelled_turn_stops_and_frees_session() {
+        let mut session = AcpSession::new(PathBuf::from("/work"), vec![]);
+        let turn = session.prompt("first");
+        assert!(session.is_busy());
+
+        session.stop();
+        assert!(!session.is_busy());
+        assert!(turn.cancellation().is_cancelled());
+        assert_eq!(turn.run(|_| {}).await, Ok(StopReason::Cancelled));
+
+        let next = session.prompt("second");
+        // A stale finish from the cancelled turn must not end the new one.
+        session.finish_turn(next.id() - 1);
+        assert!(session.is_busy());
+        assert_eq!(next.run(|_| {}).await, Ok(StopReason::EndTurn));
+    }
+
+    #[tokio::test]
+    async fn test_stop_drops_work_in_progress() {
+        let mut session = AcpSession::new(PathBuf::from("/work"), vec![]);
+        let turn = session.prompt("hello");
+        let cancellation = turn.cancellation().clone();
+
+        let work = tokio::spawn(async move {
+            cancellation
+                .run(futures_lite::future::pending::<()>())
+                .await
+        });
+        tokio::task::yield_now().await;
+        session.stop();
+
+        assert_eq!(work.await.unwrap(), None);
     }
 }
@@ -0,0 +1,101 @@
+//! On-disk persistence for ACP sessions.
+//!
+//! A [`SessionStore`] keeps one JSON file per session so `session/load` can
+//! resume conversations after the agent process restarts.
+
+use std::path::{Path, PathBuf};
+
+use crate::protocol::{AcpError, Result};
+use crate::session::{AcpSession, SessionRecord};
+
+/// Directory of saved sessions, one `<session-id>.json` file each.
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    /// Store sessions in `dir`, which is created on first save.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the sessions are stored in.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write the session's record, replacing any earlier save.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record cannot be serialized or written.
+    pub async fn save(&self, session: &AcpSession) -> Result<()> {
+        let path = self.path_for(session.id())?;
+        async_fs::create_dir_all(&self.dir).await?;
+        let json = serde_json::to_vec_pretty(&session.to_record())?;
+        // Write then rename so a crash never leaves a truncated record behind.
+        let tmp = path.with_extension("json.tmp");
+        async_fs::write(&tmp, json).await?;
+        async_fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    /// Read a saved session.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AcpError::SessionNotFound`] if no session with this ID was
+    /// saved, or another error if the record cannot be read.
+    pub async fn load(&self, session_id: &str) -> Result<SessionRecord> {
+        let path = self.path_for(session_id)?;
+        let json = match async_fs::read(&path).await {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(AcpError::SessionNotFound(session_id.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// File holding `session_id`, refusing IDs that could name a path outside the store.
+    fn path_for(&self, session_id: &str) -> Result<PathBuf> {
+        let valid = !session_id.is_empty()
+            && session_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(AcpError::SessionNotFound(session_id.to_string()));
+        }
+        Ok(self.dir.join(format!("{session_id}.json")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("aither-acp-{}", uuid::Uuid::new_v4()));
+        let store = SessionStore::new(&dir);
+        let mut session = AcpSession::new(PathBuf::from("/work"), vec![]);
+        let _turn = session.prompt("hello");
+        session.set_state(serde_json::json!({ "turns": 1 }));
+        store.save(&session).await.unwrap();
+
+        let record = store.load(session.id()).await.unwrap();
+        assert_eq!(record.session_id, session.id());
+        assert_eq!(record.history.len(), session.history().len());
+        assert_eq!(record.state["turns"], 1);
+
+        assert!(matches!(
+            store.load("../escape").await,
+            Err(AcpError::SessionNotFound(_))
+        ));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
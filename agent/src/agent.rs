@@ -9,23 +9,29 @@ use std::time::{Duration, Instant};
 
 use aither_core::{
     LanguageModel,
-    llm::{Event, LLMRequest, Message, model::Profile as ModelProfile},
+    llm::{
+        Event, LLMRequest, Message, Usage,
+        model::{Ability, Parameters, Profile as ModelProfile},
+    },
 };
 use futures_core::Stream;
 use futures_lite::StreamExt;
 
 use crate::{
-    compression::{ContextStrategy, estimate_context_usage},
+    compression::{ContextStrategy, estimate_context_usage, select_retained},
     config::{AgentConfig, AgentKind},
     context::Context,
     error::AgentError,
     event::AgentEvent,
+    executor::{StepExecutor, StepExecutors, StepKind},
+    feedback::FeedbackHandle,
     hook::{
         Hook, PostToolAction, PreToolAction, StopContext, StopReason, ToolResultContext,
         ToolUseContext,
     },
-    todo::{TodoItem, TodoList, TodoStatus},
-    tools::AgentTools,
+    report::{IterationReport, RunReport, RunStatus},
+    todo::{PlanBudget, TodoItem, TodoList, TodoStatus, due_checks, started_tasks},
+    tools::{AgentTools, split_tool_outputs},
     transcript::Transcript,
     working_docs,
 };
@@ -139,9 +145,15 @@ pub struct Agent<Advanced, Balanced = Advanced, Fast = Balanced, H = ()> {
     /// Whether tools have been bootstrapped.
     pub(crate) initialized: bool,
 
+    /// Whether requests ask the provider for native web search.
+    pub(crate) native_websearch: bool,
+
     /// Todo list for tracking long tasks.
     pub(crate) todo_list: Option<TodoList>,
 
+    /// Executors that carry out todo tasks by kind.
+    pub(crate) executors: StepExecutors,
+
     /// Output store for lazy URL allocation during compression.
     pub(crate) output_store: Option<Arc<OutputStore>>,
 
@@ -155,6 +167,16 @@ pub struct Agent<Advanced, Balanced = Advanced, Fast = Balanced, H = ()> {
 
     /// Optional sandbox directory for working-doc supervision (TODO.md/PLAN.md).
     pub(crate) sandbox_dir: Option<PathBuf>,
+
+    /// Filesystem jail audit and how many of its refusals were already streamed.
+    #[cfg(feature = "filesystem")]
+    pub(crate) access_audit: Option<(aither_fs::AccessAudit, usize)>,
+
+    /// User feedback queued while a run is in progress.
+    pub(crate) feedback: FeedbackHandle,
+
+    /// Statistics of the current or most recent run.
+    pub(crate) report: RunReport,
 }
 
 impl<LLM: LanguageModel + Clone> Agent<LLM, LLM, LLM, ()> {
@@ -183,12 +205,18 @@ impl<LLM: LanguageModel + Clone> Agent<LLM, LLM, LLM, ()> {
             profile: None,
             fast_profile: None,
             initialized: false,
+            native_websearch: false,
             todo_list: None,
+            executors: StepExecutors::new(),
             output_store: None,
             background_receiver: None,
             job_registry: None,
             transcript: None,
             sandbox_dir: None,
+            #[cfg(feature = "filesystem")]
+            access_audit: None,
+            feedback: FeedbackHandle::default(),
+            report: RunReport::default(),
         }
     }
 }
@@ -260,6 +288,9 @@ where
     ///     }
     /// }
     /// ```
+    ///
+    /// Afterwards, [`run_report`](Self::run_report) describes how the result
+    /// was produced.
     #[must_use]
     pub fn run(
         &mut self,
@@ -269,6 +300,38 @@ where
         let prompt = prompt.to_string();
         let attachments: Vec<url::Url> = attachments.into_iter().collect();
 
+        async_stream::stream! {
+            self.report = RunReport::default();
+            let start = Instant::now();
+            let mut status = RunStatus::Running;
+            {
+                let events = self.run_loop(prompt, attachments);
+                futures_lite::pin!(events);
+                while let Some(event) = events.next().await {
+                    match &event {
+                        Ok(AgentEvent::Complete { .. }) => status = RunStatus::Completed,
+                        Err(error) => status = RunStatus::Failed(error.clone()),
+                        Ok(_) => {}
+                    }
+                    yield event;
+                }
+            }
+            self.report.status = status;
+            self.report.duration = start.elapsed();
+        }
+    }
+
+    /// Returns statistics of the current or most recent [`run`](Self::run).
+    #[must_use]
+    pub const fn run_report(&self) -> &RunReport {
+        &self.report
+    }
+
+    fn run_loop(
+        &mut self,
+        prompt: String,
+        attachments: Vec<url::Url>,
+    ) -> impl Stream<Item = Result<AgentEvent, AgentError>> + '_ {
         async_stream::try_stream! {
             self.ensure_initialized().await;
 
@@ -282,6 +345,13 @@ where
                 transcript.write_user_message(&prompt).await;
             }
 
+            // Seed planning with a template for this kind of task.
+            if self.todo_list.is_some()
+                && let Some(template) = self.config.plan_template.resolve(&prompt)
+            {
+                self.context.push(Message::system(template.render()));
+            }
+
             // Run the tool loop
             let mut iteration = 0;
             let mut all_text_chunks: Vec<String> = Vec::new();
@@ -294,18 +364,26 @@ where
                     })?;
                 }
 
+                self.deliver_feedback().await;
+                self.update_plan_budget(iteration);
+                self.sync_mcp_config();
+
                 // Build messages
                 let messages = self.build_request_messages().await;
 
                 // Create request with tool definitions
                 let tool_defs = self.tools.active_definitions();
-                let request = LLMRequest::new(messages).with_tool_definitions(tool_defs);
+                let request = LLMRequest::new(messages)
+                    .with_tool_definitions(tool_defs)
+                    .with_parameters(self.request_parameters());
 
                 // Stream the response and yield text events as they arrive
                 let mut text_chunks: Vec<String> = Vec::new();
                 let mut tool_calls = Vec::new();
                 let mut malformed_function_call = false;
                 let mut error: Option<String> = None;
+                let mut usage = Usage::default();
+                let request_start = Instant::now();
 
                 // Process stream based on tier
                 match self.tier {
@@ -325,12 +403,19 @@ where
                                     yield AgentEvent::Reasoning(r);
                                 }
                                 Ok(Event::ToolCall(call)) => tool_calls.push(call),
+                                Ok(Event::ToolCallDelta(delta)) => {
+                                    yield AgentEvent::tool_delta(delta.id, delta.name, delta.arguments);
+                                }
                                 Ok(Event::BuiltInToolResult { tool, result }) => {
                                     let formatted = format!("[{tool}] {result}");
                                     yield AgentEvent::Text(formatted.clone());
                                     text_chunks.push(formatted);
                                 }
+                                Ok(Event::Citation(citation)) => {
+                                    yield AgentEvent::Citation(citation);
+                                }
                                 Ok(Event::Usage(u)) => {
+                                    usage.accumulate(&u);
                                     yield AgentEvent::Usage(u);
                                 }
                                 Err(e) => {
@@ -361,12 +446,19 @@ where
                                     yield AgentEvent::Reasoning(r);
                                 }
                                 Ok(Event::ToolCall(call)) => tool_calls.push(call),
+                                Ok(Event::ToolCallDelta(delta)) => {
+                                    yield AgentEvent::tool_delta(delta.id, delta.name, delta.arguments);
+                                }
                                 Ok(Event::BuiltInToolResult { tool, result }) => {
                                     let formatted = format!("[{tool}] {result}");
                                     yield AgentEvent::Text(formatted.clone());
                                     text_chunks.push(formatted);
                                 }
+                                Ok(Event::Citation(citation)) => {
+                                    yield AgentEvent::Citation(citation);
+                                }
                                 Ok(Event::Usage(u)) => {
+                                    usage.accumulate(&u);
                                     yield AgentEvent::Usage(u);
                                 }
                                 Err(e) => {
@@ -397,12 +489,19 @@ where
                                     yield AgentEvent::Reasoning(r);
                                 }
                                 Ok(Event::ToolCall(call)) => tool_calls.push(call),
+                                Ok(Event::ToolCallDelta(delta)) => {
+                                    yield AgentEvent::tool_delta(delta.id, delta.name, delta.arguments);
+                                }
                                 Ok(Event::BuiltInToolResult { tool, result }) => {
                                     let formatted = format!("[{tool}] {result}");
                                     yield AgentEvent::Text(formatted.clone());
                                     text_chunks.push(formatted);
                                 }
+                                Ok(Event::Citation(citation)) => {
+                                    yield AgentEvent::Citation(citation);
+                                }
                                 Ok(Event::Usage(u)) => {
+                                    usage.accumulate(&u);
                                     yield AgentEvent::Usage(u);
                                 }
                                 Err(e) => {
@@ -420,6 +519,12 @@ where
                     }
                 }
 
+                self.report.record_iteration(IterationReport {
+                    usage,
+                    tool_calls: 0,
+                    duration: request_start.elapsed(),
+                });
+
                 if let Some(e) = error {
                     Err(AgentError::Llm(e))?;
                 }
@@ -440,6 +545,9 @@ where
                             transcript.write_assistant_text(&response_text).await;
                         }
                     }
+                    if self.feedback.has_pending() {
+                        continue;
+                    }
                     if self.inject_working_doc_continue_reminder().await {
                         continue;
                     }
@@ -490,7 +598,7 @@ where
                             message_count,
                         };
 
-                        let (result, duration) = match hooks.pre_tool_use(&tool_ctx).await {
+                        let (parts, duration) = match hooks.pre_tool_use(&tool_ctx).await {
                             PreToolAction::Abort(reason) => {
                                 return Err(AgentError::HookRejected {
                                     hook: "pre_tool_use",
@@ -502,11 +610,14 @@ where
                             }
                             PreToolAction::Allow => {
                                 let start = Instant::now();
-                                let result = tools.call(&call.name, &args_json).await;
-                                let result = result.map(|output| output.as_str().unwrap_or("").to_string());
-                                (result, start.elapsed())
+                                let result = tools.call_parts(&call.name, &args_json).await;
+                                (result.map(split_tool_outputs), start.elapsed())
                             }
                         };
+                        let (result, attachments) = match parts {
+                            Ok((text, attachments)) => (Ok(text), attachments),
+                            Err(e) => (Err(e), Vec::new()),
+                        };
 
                         let result_ref = result
                             .as_ref()
@@ -537,12 +648,13 @@ where
                                 .map_err(|e| format!("Error: {e}")),
                         };
 
-                        Ok((call.id.clone(), call.name.clone(), tool_result))
+                        Ok((call.id.clone(), call.name.clone(), tool_result, attachments, duration))
                     }
                 });
 
                 // Wait for all tool calls to complete
-                let results: Vec<Result<(String, String, Result<String, String>), AgentError>> =
+                #[allow(clippy::type_complexity)]
+                let results: Vec<Result<(String, String, Result<String, String>, Vec<url::Url>, Duration), AgentError>> =
                     futures::future::join_all(tool_futures).await;
 
                 // Check if todo tool was called
@@ -550,9 +662,11 @@ where
 
                 // Add results to memory and yield tool end events
                 let mut has_tool_error = false;
+                let mut tool_images = Vec::new();
                 for result in results {
-                    let (call_id, call_name, tool_result) = result?;
+                    let (call_id, call_name, tool_result, attachments, duration) = result?;
                     let is_bash_call = call_name == "bash";
+                    self.report.record_tool(&call_name, duration, tool_result.is_err());
 
                     if let Some(transcript) = &self.transcript {
                         transcript.write_tool_result(&call_name, &tool_result).await;
@@ -586,6 +700,13 @@ where
                     {
                         self.context.push(Message::system(reminder));
                     }
+                    if !attachments.is_empty() {
+                        tool_images.push((call_name, attachments));
+                    }
+                }
+                self.push_tool_images(tool_images);
+                for event in self.blocked_access_events() {
+                    yield event;
                 }
 
                 // If there was a tool error, inject a reminder
@@ -595,12 +716,104 @@ where
 
                 // If todo tool was called, inject updated todo list
                 if todo_tool_called {
-                    let new_items = self
+                    let mut new_items = self
                         .todo_list
                         .as_ref()
                         .map(super::todo::TodoList::items)
                         .unwrap_or_default();
 
+                    // Hand tasks that just started to the executor for their kind.
+                    let started: Vec<TodoItem> = started_tasks(&old_todo_items, &new_items)
+                        .into_iter()
+                        .filter(|task| self.executors.contains(task.step))
+                        .collect();
+                    for (index, task) in started.iter().enumerate() {
+                        let id = format!("execute-{iteration}-{index}");
+                        let name = format!("{}_executor", task.step.as_str());
+                        let arguments = serde_json::json!({ "task": task.content }).to_string();
+                        if let Some(transcript) = &self.transcript {
+                            transcript.write_tool_call(&name, &arguments).await;
+                        }
+                        yield AgentEvent::ToolCallStart {
+                            id: id.clone(),
+                            name: name.clone(),
+                            arguments,
+                        };
+                        let start = Instant::now();
+                        let result = self
+                            .executors
+                            .execute(task)
+                            .await
+                            .unwrap_or_else(|| Err(anyhow::anyhow!("no executor registered")))
+                            .map_err(|e| e.to_string());
+                        self.report.record_tool(&name, start.elapsed(), result.is_err());
+                        if let Some(transcript) = &self.transcript {
+                            transcript.write_tool_result(&name, &result).await;
+                        }
+                        yield AgentEvent::ToolCallEnd {
+                            id,
+                            name: name.clone(),
+                            result: result.clone(),
+                        };
+
+                        let reminder = match result {
+                            Ok(report) => {
+                                if let Some(list) = &self.todo_list {
+                                    list.complete(&task.content);
+                                }
+                                format!(
+                                    "<system-reminder>\nTask \"{}\" was carried out by the {name}:\n{report}\n</system-reminder>",
+                                    task.content
+                                )
+                            }
+                            Err(error) => format!(
+                                "<system-reminder>\nThe {name} failed on task \"{}\": {error}\nDo the task yourself.\n</system-reminder>",
+                                task.content
+                            ),
+                        };
+                        self.context.push(Message::system(reminder));
+                    }
+                    if !started.is_empty() {
+                        new_items = self
+                            .todo_list
+                            .as_ref()
+                            .map(super::todo::TodoList::items)
+                            .unwrap_or_default();
+                    }
+
+                    // Run verification checks for actions that were just completed.
+                    let mut reopened = false;
+                    for (index, check) in due_checks(&old_todo_items, &new_items).into_iter().enumerate() {
+                        let id = format!("verify-{iteration}-{index}");
+                        let arguments = check_arguments(&check.command);
+                        if let Some(transcript) = &self.transcript {
+                            transcript.write_tool_call("bash", &arguments).await;
+                        }
+                        yield AgentEvent::ToolCallStart {
+                            id: id.clone(),
+                            name: "bash".to_string(),
+                            arguments,
+                        };
+                        let start = Instant::now();
+                        let (passed, output) = self.run_check(&check.command, iteration).await;
+                        self.report.record_tool("bash", start.elapsed(), !passed);
+                        let result = if passed { Ok(output.clone()) } else { Err(output.clone()) };
+                        if let Some(transcript) = &self.transcript {
+                            transcript.write_tool_result("bash", &result).await;
+                        }
+                        yield AgentEvent::ToolCallEnd {
+                            id,
+                            name: "bash".to_string(),
+                            result,
+                        };
+
+                        if let Some(list) = &self.todo_list {
+                            list.record_check(&check, passed);
+                        }
+                        reopened |= !passed;
+                        self.context.push(Message::system(check.reminder(passed, &output)));
+                    }
+
                     let newly_completed: Vec<_> = new_items
                         .iter()
                         .filter(|new_item| {
@@ -612,7 +825,11 @@ where
                         })
                         .collect();
 
-                    if let Some(completed) = newly_completed.first() {
+                    if reopened {
+                        if let Some(reminder) = self.format_todo_reminder() {
+                            self.context.push(Message::system(&reminder));
+                        }
+                    } else if let Some(completed) = newly_completed.first() {
                         if let Some(reminder) = self.format_next_task_reminder(&completed.content) {
                             self.context.push(Message::system(&reminder));
                         }
@@ -700,6 +917,42 @@ where
         self.tools.register(tool);
     }
 
+    /// Routes todo tasks of `kind` to `executor` instead of doing them in
+    /// the main loop.
+    ///
+    /// Register executors before the first run; the model learns about them
+    /// when the agent initializes.
+    pub fn register_step_executor(
+        &mut self,
+        kind: StepKind,
+        executor: impl StepExecutor + 'static,
+    ) {
+        self.executors.register(kind, executor);
+    }
+
+    /// Disables a registered tool until [`enable_tool`](Self::enable_tool)
+    /// is called.
+    ///
+    /// The model still sees the tool, with `reason` in its description, but
+    /// calls to it fail. Returns `false` if no tool has this name.
+    pub fn disable_tool(&mut self, name: &str, reason: impl Into<String>) -> bool {
+        self.tools.eager_mut().disable(name, reason)
+    }
+
+    /// Enables a tool disabled by [`disable_tool`](Self::disable_tool).
+    ///
+    /// Returns `true` if the tool was disabled.
+    pub fn enable_tool(&mut self, name: &str) -> bool {
+        self.tools.eager_mut().enable(name)
+    }
+
+    /// Replaces the description the model sees for a registered tool.
+    ///
+    /// Returns `false` if no tool has this name.
+    pub fn set_tool_description(&mut self, name: &str, description: impl Into<String>) -> bool {
+        self.tools.eager_mut().set_description(name, description)
+    }
+
     /// Returns a reference to the unified context manager.
     #[must_use]
     pub fn context(&self) -> &Context {
@@ -725,6 +978,10 @@ where
 
     /// Compacts the conversation by generating a structured handoff and starting fresh.
     ///
+    /// With [`ContextStrategy::Smart`], up to `preserve_recent` messages picked
+    /// by its [`scorer`](crate::SmartCompressionConfig::scorer) are kept
+    /// verbatim after the handoff.
+    ///
     /// # Errors
     ///
     /// Returns an error if handoff generation fails.
@@ -746,17 +1003,31 @@ where
             transcript.write_compact_marker().await;
         }
 
+        // Keep the most important messages verbatim alongside the summary,
+        // within a tenth of the context window.
+        let retained = match &self.config.context {
+            ContextStrategy::Unlimited => Vec::new(),
+            ContextStrategy::Smart(config) => select_retained(
+                &messages,
+                config.scorer.as_ref(),
+                config.preserve_recent,
+                self.context_length() / 10,
+            ),
+        };
+        let messages_remaining = retained.len();
+
         self.context.clear_conversation();
         // Push the handoff summary as a system message in the conversation.
         // This preserves it as part of the conversation flow.
         self.context.push(Message::system(&summary));
+        self.context.extend(retained);
         self.context.push(Message::system(
             "Session continues from compacted context. Continue without asking the user to repeat details. Recover missing details from files, TODO.md/PLAN.md, or transcript when needed.",
         ));
 
         Ok(Some(CompactResult {
-            messages_compacted,
-            messages_remaining: 0,
+            messages_compacted: messages_compacted - messages_remaining,
+            messages_remaining,
             summary,
         }))
     }
@@ -837,6 +1108,64 @@ where
         Ok(summary)
     }
 
+    /// Moves queued user feedback into the conversation.
+    async fn deliver_feedback(&mut self) {
+        for text in self.feedback.take() {
+            if let Some(transcript) = &self.transcript {
+                transcript.write_user_message(&text).await;
+            }
+            self.context.push(Message::user(text));
+        }
+    }
+
+    /// Runs a verification check through the `bash` tool.
+    ///
+    /// Returns whether the command exited with status 0, and its output.
+    async fn run_check(&self, command: &str, turn: usize) -> (bool, String) {
+        let arguments = check_arguments(command);
+        let tool_ctx = ToolUseContext {
+            tool_name: "bash",
+            arguments: &arguments,
+            turn,
+            message_count: self.context.len_recent(),
+        };
+        match self.hooks.pre_tool_use(&tool_ctx).await {
+            PreToolAction::Allow => {}
+            PreToolAction::Deny(reason) | PreToolAction::Abort(reason) => {
+                return (false, format!("Check blocked by hook: {reason}"));
+            }
+        }
+
+        match self.tools.call_parts("bash", &arguments).await {
+            Ok(parts) => {
+                let output = split_tool_outputs(parts).0;
+                // A check that was moved to the background hasn't finished.
+                let passed = serde_json::from_str::<serde_json::Value>(&output)
+                    .ok()
+                    .filter(|result| result.get("status").is_none())
+                    .and_then(|result| result.get("exit_code")?.as_i64())
+                    == Some(0);
+                (passed, output)
+            }
+            Err(e) => (false, format!("Error: {e}")),
+        }
+    }
+
+    /// Tells the todo list how much of the run's budget is left.
+    fn update_plan_budget(&self, iteration: usize) {
+        let Some(list) = &self.todo_list else {
+            return;
+        };
+        let used = self.report.usage.total_tokens.map_or(0, u64::from);
+        list.set_budget(PlanBudget {
+            remaining_iterations: self.config.max_iterations.saturating_sub(iteration),
+            remaining_tokens: self
+                .config
+                .token_budget
+                .map(|budget| budget.saturating_sub(used)),
+        });
+    }
+
     /// Injects a continuation reminder when working documents still have pending tasks.
     async fn inject_working_doc_continue_reminder(&mut self) -> bool {
         let Some(sandbox_dir) = self.sandbox_dir.as_deref() else {
@@ -860,12 +1189,36 @@ where
         self.context.conversation_messages()
     }
 
+    /// Queues a user correction for the current or next run.
+    ///
+    /// It is added to the conversation at the next iteration boundary, and
+    /// keeps the agent going if the model was about to finish. Use
+    /// [`feedback_handle`](Self::feedback_handle) to inject while
+    /// [`run`](Self::run) holds the agent.
+    pub fn inject_feedback(&self, text: impl Into<String>) {
+        self.feedback.inject(text);
+    }
+
+    /// Returns a handle for injecting feedback from other tasks or threads.
+    #[must_use]
+    pub fn feedback_handle(&self) -> FeedbackHandle {
+        self.feedback.clone()
+    }
+
     /// Returns the model profile if available.
     #[must_use]
     pub const fn profile(&self) -> Option<&ModelProfile> {
         self.profile.as_ref()
     }
 
+    /// Parameters for main-loop requests.
+    fn request_parameters(&self) -> Parameters {
+        self.config
+            .request_defaults
+            .parameters(self.profile.as_ref())
+            .websearch(self.native_websearch)
+    }
+
     /// Ensures the agent is initialized (profiles fetched, static blocks set up).
     async fn ensure_initialized(&mut self) {
         if self.initialized {
@@ -883,6 +1236,13 @@ where
         // We always need this because compression uses the fast model
         self.fast_profile = Some(self.fast.profile().await);
 
+        // Native search makes a client-side search tool redundant.
+        let native_search = self
+            .profile
+            .as_ref()
+            .is_some_and(|profile| profile.abilities.contains(&Ability::WebSearch));
+        self.native_websearch = self.tools.resolve_websearch(native_search);
+
         // Populate static system blocks in Context from AgentConfig.
         // These form the stable, cacheable prefix.
         self.populate_system_blocks();
@@ -933,6 +1293,22 @@ where
             self.context.insert_system_named("tool_hints", &tool_hints);
         }
 
+        if self.todo_list.is_some() && !self.executors.is_empty() {
+            let kinds: Vec<&str> = self
+                .executors
+                .kinds()
+                .into_iter()
+                .map(StepKind::as_str)
+                .collect();
+            self.context.insert_system_named(
+                "step_executors",
+                format!(
+                    "Todo tasks with `step` set to one of {} are carried out by dedicated executors as soon as you mark them in_progress; the executor's report completes the task. Do all other tasks yourself.",
+                    kinds.join(", ")
+                ),
+            );
+        }
+
         // Insert application-provided context blocks (sorted by priority).
         let mut blocks = self.config.context_blocks.clone();
         blocks.sort_by_key(|b| b.priority.rank());
@@ -969,7 +1345,21 @@ where
             let running = job_registry.format_running_jobs().await;
             if !running.is_empty() {
                 ephemeral.push(Message::system(format!(
-                    "<system-reminder>\nRunning background terminals:\n{running}Read redirected output files via bash (head/tail/grep/cat). Use input_terminal for stdin and kill_terminal to stop when needed.\n</system-reminder>"
+                    "<system-reminder>\nRunning background terminals:\n{running}Read redirected output files via bash (head/tail/grep/cat) or check on tasks with `job-status <task_id> --wait <secs>` and `job-logs <task_id>`. Use input_terminal for stdin and kill_terminal to stop when needed.\n</system-reminder>"
+                )));
+            }
+        }
+
+        #[cfg(feature = "mcp")]
+        {
+            let unavailable = self.tools.mcp_unavailable();
+            if !unavailable.is_empty() {
+                let servers: String = unavailable
+                    .iter()
+                    .map(|(name, state)| format!("- {name}: {state}\n"))
+                    .collect();
+                ephemeral.push(Message::system(format!(
+                    "<system-reminder>\nSome MCP servers are unavailable and their tools will fail until they reconnect:\n{servers}Prefer other tools for now, or retry later.\n</system-reminder>"
                 )));
             }
         }
@@ -1085,13 +1475,20 @@ where
                 return events;
             }
 
+            self.deliver_feedback().await;
+            self.update_plan_budget(iteration);
+            self.sync_mcp_config();
             let messages = self.build_request_messages().await;
             let tool_defs = self.tools.active_definitions();
-            let request = LLMRequest::new(messages).with_tool_definitions(tool_defs);
+            let request = LLMRequest::new(messages)
+                .with_tool_definitions(tool_defs)
+                .with_parameters(self.request_parameters());
 
             let mut text_chunks = Vec::new();
             let mut tool_calls = Vec::new();
             let mut error: Option<String> = None;
+            let mut usage = Usage::default();
+            let request_start = Instant::now();
 
             // Process stream based on tier
             match self.tier {
@@ -1107,12 +1504,21 @@ where
                             }
                             Ok(Event::Reasoning(r)) => events.push(Ok(AgentEvent::Reasoning(r))),
                             Ok(Event::ToolCall(call)) => tool_calls.push(call),
+                            Ok(Event::ToolCallDelta(delta)) => events.push(Ok(
+                                AgentEvent::tool_delta(delta.id, delta.name, delta.arguments),
+                            )),
                             Ok(Event::BuiltInToolResult { tool, result }) => {
                                 let formatted = format!("[{tool}] {result}");
                                 events.push(Ok(AgentEvent::Text(formatted.clone())));
                                 text_chunks.push(formatted);
                             }
-                            Ok(Event::Usage(u)) => events.push(Ok(AgentEvent::Usage(u))),
+                            Ok(Event::Citation(citation)) => {
+                                events.push(Ok(AgentEvent::Citation(citation)));
+                            }
+                            Ok(Event::Usage(u)) => {
+                                usage.accumulate(&u);
+                                events.push(Ok(AgentEvent::Usage(u)));
+                            }
                             Err(e) => {
                                 error = Some(e.to_string());
                                 break;
@@ -1132,12 +1538,21 @@ where
                             }
                             Ok(Event::Reasoning(r)) => events.push(Ok(AgentEvent::Reasoning(r))),
                             Ok(Event::ToolCall(call)) => tool_calls.push(call),
+                            Ok(Event::ToolCallDelta(delta)) => events.push(Ok(
+                                AgentEvent::tool_delta(delta.id, delta.name, delta.arguments),
+                            )),
                             Ok(Event::BuiltInToolResult { tool, result }) => {
                                 let formatted = format!("[{tool}] {result}");
                                 events.push(Ok(AgentEvent::Text(formatted.clone())));
                                 text_chunks.push(formatted);
                             }
-                            Ok(Event::Usage(u)) => events.push(Ok(AgentEvent::Usage(u))),
+                            Ok(Event::Citation(citation)) => {
+                                events.push(Ok(AgentEvent::Citation(citation)));
+                            }
+                            Ok(Event::Usage(u)) => {
+                                usage.accumulate(&u);
+                                events.push(Ok(AgentEvent::Usage(u)));
+                            }
                             Err(e) => {
                                 error = Some(e.to_string());
                                 break;
@@ -1157,12 +1572,21 @@ where
                             }
                             Ok(Event::Reasoning(r)) => events.push(Ok(AgentEvent::Reasoning(r))),
                             Ok(Event::ToolCall(call)) => tool_calls.push(call),
+                            Ok(Event::ToolCallDelta(delta)) => events.push(Ok(
+                                AgentEvent::tool_delta(delta.id, delta.name, delta.arguments),
+                            )),
                             Ok(Event::BuiltInToolResult { tool, result }) => {
                                 let formatted = format!("[{tool}] {result}");
                                 events.push(Ok(AgentEvent::Text(formatted.clone())));
                                 text_chunks.push(formatted);
                             }
-                            Ok(Event::Usage(u)) => events.push(Ok(AgentEvent::Usage(u))),
+                            Ok(Event::Citation(citation)) => {
+                                events.push(Ok(AgentEvent::Citation(citation)));
+                            }
+                            Ok(Event::Usage(u)) => {
+                                usage.accumulate(&u);
+                                events.push(Ok(AgentEvent::Usage(u)));
+                            }
                             Err(e) => {
                                 error = Some(e.to_string());
                                 break;
@@ -1172,6 +1596,12 @@ where
                 }
             }
 
+            self.report.record_iteration(IterationReport {
+                usage,
+                tool_calls: 0,
+                duration: request_start.elapsed(),
+            });
+
             if let Some(e) = error {
                 events.push(Err(AgentError::Llm(e)));
                 return events;
@@ -1183,6 +1613,9 @@ where
                 if !response_text.is_empty() {
                     self.context.push(Message::assistant(&response_text));
                 }
+                if self.feedback.has_pending() {
+                    continue;
+                }
                 events.push(Ok(AgentEvent::Complete {
                     final_text: response_text,
                     turns: iteration,
@@ -1200,23 +1633,42 @@ where
             let tool_futures = tool_calls.iter().map(|call| {
                 let args_json = call.arguments.to_string();
                 async move {
-                    let result = tools
-                        .call(&call.name, &args_json)
-                        .await
-                        .map(|output| output.as_str().unwrap_or("").to_string())
-                        .map_err(|e| format!("Error: {e}"));
-                    (call.id.clone(), call.name.clone(), result)
+                    let start = Instant::now();
+                    let (result, attachments) = match tools.call_parts(&call.name, &args_json).await
+                    {
+                        Ok(parts) => {
+                            let (text, attachments) = split_tool_outputs(parts);
+                            (Ok(text), attachments)
+                        }
+                        Err(e) => (Err(format!("Error: {e}")), Vec::new()),
+                    };
+                    (
+                        call.id.clone(),
+                        call.name.clone(),
+                        result,
+                        attachments,
+                        start.elapsed(),
+                    )
                 }
             });
 
-            let results: Vec<(String, String, Result<String, String>)> =
-                futures::future::join_all(tool_futures).await;
-
-            for (call_id, call_name, tool_result) in results {
+            #[allow(clippy::type_complexity)]
+            let results: Vec<(
+                String,
+                String,
+                Result<String, String>,
+                Vec<url::Url>,
+                Duration,
+            )> = futures::future::join_all(tool_futures).await;
+
+            let mut tool_images = Vec::new();
+            for (call_id, call_name, tool_result, attachments, duration) in results {
                 let is_bash_call = call_name == "bash";
+                self.report
+                    .record_tool(&call_name, duration, tool_result.is_err());
                 events.push(Ok(AgentEvent::ToolCallEnd {
                     id: call_id.clone(),
-                    name: call_name,
+                    name: call_name.clone(),
                     result: tool_result.clone(),
                 }));
                 let content = match &tool_result {
@@ -1232,7 +1684,11 @@ where
                 {
                     self.context.push(Message::system(reminder));
                 }
+                if !attachments.is_empty() {
+                    tool_images.push((call_name, attachments));
+                }
             }
+            self.push_tool_images(tool_images);
 
             if let Some(ref receiver) = self.background_receiver {
                 let completed_tasks = receiver.take_completed();
@@ -1244,6 +1700,26 @@ where
         }
     }
 
+    /// Context window used for compression decisions.
+    ///
+    /// Uses the minimum of both context windows (most constrained).
+    /// This ensures:
+    /// 1. The selected tier doesn't run out of context for reasoning
+    /// 2. The fast model can see all content during compression
+    fn context_length(&self) -> usize {
+        let tier_context = self
+            .profile
+            .as_ref()
+            .map_or(100_000, |p| p.context_length as usize);
+
+        let fast_context = self
+            .fast_profile
+            .as_ref()
+            .map_or(100_000, |p| p.context_length as usize);
+
+        tier_context.min(fast_context)
+    }
+
     /// Compresses context if needed.
     ///
     /// Considers BOTH the selected tier's context window AND the fast model's
@@ -1259,21 +1735,21 @@ where
     /// 2. Lets the fast LLM decide which URLs to reference in the summary
     /// 3. Only writes files for URLs actually referenced in the summary
     async fn maybe_compress(&mut self) -> Result<(), AgentError> {
-        // Use the minimum of both context windows (most constrained)
-        // This ensures:
-        // 1. The selected tier doesn't run out of context for reasoning
-        // 2. The fast model can see all content during compression
-        let tier_context = self
-            .profile
-            .as_ref()
-            .map_or(100_000, |p| p.context_length as usize);
-
-        let fast_context = self
-            .fast_profile
-            .as_ref()
-            .map_or(100_000, |p| p.context_length as usize);
+        let context_length = self.context_length();
+
+        if let ContextStrategy::Smart(config) = &self.config.context
+            && let Some(min_len) = config.dedupe_min_len
+        {
+            let stats = self.context.dedupe_tool_results(min_len);
+            if stats.duplicates > 0 {
+                tracing::debug!(
+                    duplicates = stats.duplicates,
+                    tokens_saved = stats.tokens_saved(),
+                    "deduplicated tool results"
+                );
+            }
+        }
 
-        let context_length = tier_context.min(fast_context);
         let usage = estimate_context_usage(&self.context.conversation_messages(), context_length);
 
         match &self.config.context {
@@ -1298,6 +1774,59 @@ where
         result.to_string()
     }
 
+    /// Applies changes from a watched MCP config file and tells the model
+    /// which servers came and went, so it stops calling removed tools.
+    fn sync_mcp_config(&mut self) {
+        #[cfg(feature = "mcp")]
+        {
+            let changes = self.tools.sync_mcp_config();
+            if !changes.is_empty() {
+                let changes = changes.join("\n");
+                self.context.push(Message::system(format!(
+                    "<system-reminder>\nMCP servers changed and the tool list was updated:\n{changes}\n</system-reminder>"
+                )));
+            }
+        }
+    }
+
+    /// Events for accesses the filesystem jail refused since the last call.
+    fn blocked_access_events(&mut self) -> Vec<AgentEvent> {
+        #[cfg(feature = "filesystem")]
+        {
+            if let Some((audit, seen)) = &mut self.access_audit {
+                let blocked = audit.blocked();
+                let events = blocked
+                    .get(*seen..)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|access| AgentEvent::AccessBlocked {
+                        path: access.path.clone(),
+                        reason: access.reason.to_string(),
+                    })
+                    .collect();
+                *seen = blocked.len();
+                return events;
+            }
+        }
+        Vec::new()
+    }
+
+    /// Forwards images returned by tools as user-message attachments.
+    ///
+    /// Tool messages are text-only, so vision-capable models only see images
+    /// (such as screenshots from MCP servers) when they are attached to a
+    /// user message that follows the tool results.
+    fn push_tool_images(&mut self, tool_images: Vec<(String, Vec<url::Url>)>) {
+        for (tool_name, attachments) in tool_images {
+            let note = format!(
+                "[{} image(s) returned by the `{tool_name}` tool]",
+                attachments.len()
+            );
+            self.context
+                .push(Message::user(note).with_attachments(attachments));
+        }
+    }
+
     /// Formats the todo list as a system reminder.
     ///
     /// Returns None if there's no todo list or it's empty.
@@ -1314,17 +1843,32 @@ where
     }
 
     /// Formats the current todo list for context injection before each request.
+    ///
+    /// Includes planning guidance when the remaining budget limits the plan.
     fn format_todo_context(&self) -> Option<String> {
         let list = self.todo_list.as_ref()?;
         let items = list.items();
-        if items.is_empty() {
+        let guidance = list
+            .budget()
+            .filter(PlanBudget::is_tight)
+            .map(|budget| budget.guidance());
+        if items.is_empty() && guidance.is_none() {
             return None;
         }
 
-        let items_json = format_todo_items_json(&items);
-        Some(format!(
-            "<system-reminder>\nCurrent todo list (do not mention this explicitly to the user):\n\n{items_json}\n</system-reminder>"
-        ))
+        let mut context = String::from("<system-reminder>\n");
+        if !items.is_empty() {
+            let items_json = format_todo_items_json(&items);
+            context.push_str(&format!(
+                "Current todo list (do not mention this explicitly to the user):\n\n{items_json}\n"
+            ));
+        }
+        if let Some(guidance) = guidance {
+            context.push_str(&guidance);
+            context.push('\n');
+        }
+        context.push_str("</system-reminder>");
+        Some(context)
     }
 
     /// Formats a reminder when `bash` has been auto-promoted to background.
@@ -1426,6 +1970,14 @@ where
     }
 }
 
+/// `bash` arguments for a verification check.
+fn check_arguments(command: &str) -> String {
+    /// Long enough for a test suite; checks that take longer fail.
+    const CHECK_TIMEOUT_SECS: u64 = 600;
+
+    serde_json::json!({ "script": command, "timeout": CHECK_TIMEOUT_SECS }).to_string()
+}
+
 /// Formats todo items into the JSON-ish list used in system reminders.
 fn format_todo_items_json(items: &[TodoItem]) -> String {
     serde_json::to_string(items).unwrap_or_else(|_| "[]".to_string())
@@ -676,6 +676,25 @@ where
         self
     }
 
+    /// Sets the number of tokens a run is expected to use.
+    pub fn token_budget(mut self, tokens: u64) -> Self {
+        self.inner = self.inner.token_budget(tokens);
+        self
+    }
+
+    /// Applies the agent settings of a configuration file.
+    ///
+    /// A configured system prompt is used as is, like
+    /// [`system_prompt_raw`](Self::system_prompt_raw), so don't call
+    /// [`with_default_prompt`](Self::with_default_prompt) afterwards. Skill
+    /// and subagent directories are left to [`with_skills`](Self::with_skills)
+    /// and [`with_subagents`](Self::with_subagents).
+    #[cfg(feature = "config")]
+    pub fn settings(mut self, settings: &aither_config::AgentSettings) -> Self {
+        self.inner = self.inner.settings(settings);
+        self
+    }
+
     /// Returns the list of registered tool descriptions.
     ///
     /// Useful for dynamically building system prompts.
@@ -11,14 +11,19 @@ use aither_sandbox::{BackgroundTaskReceiver, JobRegistry, OutputStore};
 use crate::{
     agent::{Agent, ModelTier},
     compression::ContextStrategy,
-    config::{AgentConfig, AgentKind, ContextBlock},
+    config::{AgentConfig, AgentKind, ContextBlock, RequestDefaults},
     context::Context,
+    executor::{StepExecutor, StepExecutors, StepKind},
+    feedback::FeedbackHandle,
     hook::{HCons, Hook},
+    report::RunReport,
     todo::{TodoList, TodoTool},
     tools::AgentTools,
     transcript::Transcript,
 };
 
+#[cfg(feature = "config")]
+use aither_config::AgentSettings;
 #[cfg(feature = "mcp")]
 use aither_mcp::McpConnection;
 
@@ -53,11 +58,14 @@ pub struct AgentBuilder<Advanced, Balanced = Advanced, Fast = Balanced, H = ()>
     hooks: H,
     config: AgentConfig,
     todo_list: Option<TodoList>,
+    executors: StepExecutors,
     output_store: Option<Arc<OutputStore>>,
     background_receiver: Option<BackgroundTaskReceiver>,
     job_registry: Option<JobRegistry>,
     transcript: Option<Transcript>,
     sandbox_dir: Option<std::path::PathBuf>,
+    #[cfg(feature = "filesystem")]
+    access_audit: Option<aither_fs::AccessAudit>,
 }
 
 impl<Advanced, Balanced, Fast, H> std::fmt::Debug for AgentBuilder<Advanced, Balanced, Fast, H> {
@@ -84,11 +92,14 @@ impl<LLM: LanguageModel + Clone> AgentBuilder<LLM, LLM, LLM, ()> {
             hooks: (),
             config: AgentConfig::default(),
             todo_list: None,
+            executors: StepExecutors::new(),
             output_store: None,
             background_receiver: None,
             job_registry: None,
             transcript: None,
             sandbox_dir: None,
+            #[cfg(feature = "filesystem")]
+            access_audit: None,
         }
     }
 }
@@ -117,11 +128,14 @@ where
             hooks: self.hooks,
             config: self.config,
             todo_list: self.todo_list,
+            executors: self.executors,
             output_store: self.output_store,
             background_receiver: self.background_receiver,
             job_registry: self.job_registry,
             transcript: self.transcript,
             sandbox_dir: self.sandbox_dir,
+            #[cfg(feature = "filesystem")]
+            access_audit: self.access_audit,
         }
     }
 
@@ -142,11 +156,14 @@ where
             hooks: self.hooks,
             config: self.config,
             todo_list: self.todo_list,
+            executors: self.executors,
             output_store: self.output_store,
             background_receiver: self.background_receiver,
             job_registry: self.job_registry,
             transcript: self.transcript,
             sandbox_dir: self.sandbox_dir,
+            #[cfg(feature = "filesystem")]
+            access_audit: self.access_audit,
         }
     }
 
@@ -180,6 +197,20 @@ where
         self
     }
 
+    /// Enables web search, preferring the model's own.
+    ///
+    /// If the model profile advertises [`Ability::WebSearch`], requests ask
+    /// the provider to search natively and `tool` is never mounted; the
+    /// sources it used stream as [`AgentEvent::Citation`].
+    /// Otherwise `tool` is registered like any other tool.
+    ///
+    /// [`Ability::WebSearch`]: aither_core::llm::model::Ability::WebSearch
+    /// [`AgentEvent::Citation`]: crate::AgentEvent::Citation
+    pub fn enable_websearch<T: Tool + 'static>(mut self, tool: T) -> Self {
+        self.tools.websearch_fallback(tool);
+        self
+    }
+
     /// Registers a dynamic bash tool (type-erased).
     ///
     /// This is used for child bash tools in subagents where the concrete type
@@ -213,11 +244,14 @@ where
             hooks: HCons::new(hook, self.hooks),
             config: self.config,
             todo_list: self.todo_list,
+            executors: self.executors,
             output_store: self.output_store,
             background_receiver: self.background_receiver,
             job_registry: self.job_registry,
             transcript: self.transcript,
             sandbox_dir: self.sandbox_dir,
+            #[cfg(feature = "filesystem")]
+            access_audit: self.access_audit,
         }
     }
 
@@ -238,6 +272,15 @@ where
         self
     }
 
+    /// Sets the number of tokens a run is expected to use.
+    ///
+    /// The todo list is sized to fit the remaining budget; the run itself is
+    /// not stopped when it runs out.
+    pub const fn token_budget(mut self, tokens: u64) -> Self {
+        self.config.token_budget = Some(tokens);
+        self
+    }
+
     /// Sets the context compression strategy.
     pub const fn context_strategy(mut self, strategy: ContextStrategy) -> Self {
         self.config.context = strategy;
@@ -274,12 +317,69 @@ where
         self
     }
 
+    /// Streams accesses refused by a filesystem jail as
+    /// [`AgentEvent::AccessBlocked`](crate::AgentEvent::AccessBlocked).
+    ///
+    /// Pass a clone of the audit given to
+    /// [`LocalFileSystem::with_audit`](aither_fs::LocalFileSystem::with_audit).
+    #[cfg(feature = "filesystem")]
+    pub fn access_audit(mut self, audit: aither_fs::AccessAudit) -> Self {
+        self.access_audit = Some(audit);
+        self
+    }
+
+    /// Overrides the sampling parameters derived from the model profile.
+    pub const fn request_defaults(mut self, defaults: RequestDefaults) -> Self {
+        self.config.request_defaults = defaults;
+        self
+    }
+
+    /// Always seeds todo-list runs with `template` instead of inferring one
+    /// from the prompt.
+    pub const fn plan_template(mut self, template: PlanTemplate) -> Self {
+        self.config.plan_template = PlanTemplateSelection::Use(template);
+        self
+    }
+
     /// Adds a structured context block.
     pub fn context_block(mut self, block: ContextBlock) -> Self {
         self.config.context_blocks.push(block);
         self
     }
 
+    /// Applies the agent settings of a configuration file.
+    ///
+    /// Unset fields are left alone. Skill and subagent directories need a
+    /// sandbox, so hosts load them through
+    /// [`BashAgentBuilder`](crate::BashAgentBuilder).
+    ///
+    /// ```rust,ignore
+    /// let config = aither_config::Config::load("aither.toml").await?;
+    /// let agent = Agent::builder(llm)
+    ///     .settings(&config.agent)
+    ///     .hook(config.tools.clone())
+    ///     .build();
+    /// ```
+    #[cfg(feature = "config")]
+    pub fn settings(mut self, settings: &AgentSettings) -> Self {
+        if let Some(limit) = settings.max_iterations {
+            self = self.max_iterations(limit);
+        }
+        if let Some(tokens) = settings.token_budget {
+            self = self.token_budget(tokens);
+        }
+        if let Some(prompt) = &settings.system_prompt {
+            self = self.system_prompt(prompt.clone());
+        }
+        if let Some(prompt) = &settings.persona_prompt {
+            self = self.persona_prompt(prompt.clone());
+        }
+        if let Some(path) = &settings.transcript {
+            self = self.transcript(path.clone());
+        }
+        self
+    }
+
     /// Registers an MCP connection.
     ///
     /// All tools from the MCP server will be available for the agent to use.
@@ -330,6 +430,25 @@ where
         self
     }
 
+    /// Connects to the MCP servers in a config file and keeps them in sync
+    /// with it for the rest of the session.
+    ///
+    /// The file uses the Claude Desktop format (with or without the
+    /// `mcpServers` wrapper). Edits are picked up before the next model
+    /// request: new servers are connected, changed ones reconnected, and
+    /// removed ones dropped along with their tools.
+    ///
+    /// ```rust,ignore
+    /// let agent = Agent::builder(llm)
+    ///     .mcp_config("mcp.json")
+    ///     .build();
+    /// ```
+    #[cfg(feature = "mcp")]
+    pub fn mcp_config(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.tools.watch_mcp_config(path);
+        self
+    }
+
     /// Registers a bash tool for script execution in a sandbox.
     ///
     /// The bash tool enables script execution with configurable permission modes
@@ -399,6 +518,15 @@ where
         self
     }
 
+    /// Routes todo tasks of `kind` to `executor` instead of doing them in
+    /// the main loop.
+    ///
+    /// Only takes effect with a todo list enabled.
+    pub fn step_executor(mut self, kind: StepKind, executor: impl StepExecutor + 'static) -> Self {
+        self.executors.register(kind, executor);
+        self
+    }
+
     /// Enables todo list tracking with a shared list.
     ///
     /// Use this when you want to share a todo list between multiple agents
@@ -424,12 +552,18 @@ where
             profile: None,
             fast_profile: None,
             initialized: false,
+            native_websearch: false,
             todo_list: self.todo_list,
+            executors: self.executors,
             output_store: self.output_store,
             background_receiver: self.background_receiver,
             job_registry: self.job_registry,
             transcript: self.transcript,
             sandbox_dir: self.sandbox_dir,
+            #[cfg(feature = "filesystem")]
+            access_audit: self.access_audit.map(|audit| (audit, 0)),
+            feedback: FeedbackHandle::default(),
+            report: RunReport::default(),
         }
     }
 }
@@ -437,70 +571,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::borrow::Cow;
-
-    use schemars::JsonSchema;
-    use serde::Deserialize;
-
-    use futures_core::Stream;
-
-    // Mock error type for testing
-    #[derive(Debug)]
-    struct MockError;
-
-    impl std::fmt::Display for MockError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "mock error")
-        }
-    }
-
-    impl std::error::Error for MockError {}
-
-    // Mock LLM for testing
-    #[derive(Clone)]
-    struct MockLlm;
-
-    impl LanguageModel for MockLlm {
-        type Error = MockError;
-
-        fn respond(
-            &self,
-            _request: aither_core::llm::LLMRequest,
-        ) -> impl Stream<Item = Result<aither_core::llm::Event, Self::Error>> + Send {
-            futures_lite::stream::empty()
-        }
-
-        async fn profile(&self) -> aither_core::llm::model::Profile {
-            aither_core::llm::model::Profile::new(
-                "mock",
-                "test",
-                "mock-model",
-                "A mock model for testing",
-                100_000,
-            )
-        }
-    }
-
-    // Mock tool
-    struct MockTool;
-
-    #[derive(Debug, JsonSchema, Deserialize)]
-    struct MockArgs;
-
-    impl Tool for MockTool {
-        fn name(&self) -> Cow<'static, str> {
-            "mock_tool".into()
-        }
-
-        type Arguments = MockArgs;
-
-        async fn call(
-            &self,
-            _args: Self::Arguments,
-        ) -> aither_core::Result<aither_core::llm::ToolOutput> {
-            Ok(aither_core::llm::ToolOutput::text("ok"))
-        }
-    }
+    use aither_test::{MockLanguageModel, MockTool};
 
     // Mock hook
     struct MockHook;
@@ -509,19 +580,21 @@ mod tests {
 
     #[test]
     fn test_builder_basic() {
-        let agent = AgentBuilder::new(MockLlm).build();
+        let agent = AgentBuilder::new(MockLanguageModel::new()).build();
         assert!(agent.tools.definitions().is_empty());
     }
 
     #[test]
     fn test_builder_with_tool() {
-        let agent = AgentBuilder::new(MockLlm).tool(MockTool).build();
+        let agent = AgentBuilder::new(MockLanguageModel::new())
+            .tool(MockTool::new("mock_tool"))
+            .build();
         assert_eq!(agent.tools.definitions().len(), 1);
     }
 
     #[test]
     fn test_builder_with_system_prompt() {
-        let agent = AgentBuilder::new(MockLlm)
+        let agent = AgentBuilder::new(MockLanguageModel::new())
             .system_prompt("You are helpful.")
             .build();
         assert_eq!(
@@ -532,28 +605,48 @@ mod tests {
 
     #[test]
     fn test_builder_with_hook() {
-        let _agent = AgentBuilder::new(MockLlm).hook(MockHook).build();
+        let _agent = AgentBuilder::new(MockLanguageModel::new())
+            .hook(MockHook)
+            .build();
         // Type check: agent has HCons<MockHook, ()> as hook type
     }
 
     #[test]
     fn test_builder_with_multiple_hooks() {
-        let _agent = AgentBuilder::new(MockLlm)
+        let _agent = AgentBuilder::new(MockLanguageModel::new())
             .hook(MockHook)
             .hook(MockHook)
             .build();
         // Type check: agent has HCons<MockHook, HCons<MockHook, ()>>
     }
 
+    #[test]
+    fn test_builder_websearch_fallback() {
+        let mut agent = AgentBuilder::new(MockLanguageModel::new())
+            .enable_websearch(MockTool::new("mock_tool"))
+            .build();
+        assert!(agent.tools.definitions().is_empty());
+        assert!(agent.tools.resolve_websearch(true));
+        assert!(agent.tools.definitions().is_empty());
+
+        let mut agent = AgentBuilder::new(MockLanguageModel::new())
+            .enable_websearch(MockTool::new("mock_tool"))
+            .build();
+        assert!(!agent.tools.resolve_websearch(false));
+        assert_eq!(agent.tools.definitions().len(), 1);
+    }
+
     #[test]
     fn test_builder_max_iterations() {
-        let agent = AgentBuilder::new(MockLlm).max_iterations(100).build();
+        let agent = AgentBuilder::new(MockLanguageModel::new())
+            .max_iterations(100)
+            .build();
         assert_eq!(agent.config.max_iterations, 100);
     }
 
     #[test]
     fn test_builder_default_config() {
-        let agent = AgentBuilder::new(MockLlm).build();
+        let agent = AgentBuilder::new(MockLanguageModel::new()).build();
         assert_eq!(
             agent.config.max_iterations,
             AgentConfig::default().max_iterations
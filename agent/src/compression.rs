@@ -1,8 +1,14 @@
 //! Smart context compression for managing conversation history.
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 
-use aither_core::{LanguageModel, llm::Message};
+use aither_core::{LanguageModel, llm::Message, prompt::PromptLibrary};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::AgentError;
 
 /// Strategy for managing conversation context.
 #[derive(Debug, Clone)]
@@ -20,6 +26,19 @@ impl Default for ContextStrategy {
     }
 }
 
+impl ContextStrategy {
+    /// Returns the scorer that picks messages to keep through compaction.
+    ///
+    /// `None` for [`Unlimited`](Self::Unlimited), which never compacts.
+    #[must_use]
+    pub fn scorer(&self) -> Option<&dyn MessageScorer> {
+        match self {
+            Self::Unlimited => None,
+            Self::Smart(config) => Some(config.scorer.as_ref()),
+        }
+    }
+}
+
 /// Configuration for smart context compression.
 #[derive(Debug, Clone)]
 pub struct SmartCompressionConfig {
@@ -29,7 +48,8 @@ pub struct SmartCompressionConfig {
     /// Emergency compaction threshold (default: 0.9).
     pub emergency_threshold: f32,
 
-    /// Number of recent messages to always keep verbatim.
+    /// Number of messages kept verbatim after the handoff summary when the
+    /// conversation is compacted, chosen by [`scorer`](Self::scorer).
     pub preserve_recent: usize,
 
     /// Types of content to preserve during compression.
@@ -37,6 +57,20 @@ pub struct SmartCompressionConfig {
 
     /// Compression level (trade-off between quality and size).
     pub level: CompressionLevel,
+
+    /// Tool results at least this many bytes long that repeat an earlier
+    /// result are replaced by a reference to it before usage is estimated.
+    /// `None` disables deduplication (default: 256).
+    pub dedupe_min_len: Option<usize>,
+
+    /// Rates messages by importance when choosing which to keep through
+    /// compaction (default: [`DefaultScorer`]).
+    pub scorer: Arc<dyn MessageScorer>,
+
+    /// Prompts used to summarize compacted messages (default:
+    /// [`default_prompts`](crate::default_prompts), which lists their names
+    /// and variables).
+    pub prompts: PromptLibrary,
 }
 
 impl Default for SmartCompressionConfig {
@@ -47,10 +81,128 @@ impl Default for SmartCompressionConfig {
             preserve_recent: 8,
             preserve: PreserveConfig::default(),
             level: CompressionLevel::Standard,
+            dedupe_min_len: Some(256),
+            scorer: Arc::new(DefaultScorer::default()),
+            prompts: crate::default_prompts(),
         }
     }
 }
 
+/// Rates how much a message is worth keeping verbatim when the conversation
+/// is compacted.
+///
+/// Only messages that stand on their own are scored: tool results and
+/// assistant turns that request tools are always folded into the summary,
+/// since keeping half of a call/result pair would break the conversation.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Debug)]
+/// struct KeepUserMessages;
+///
+/// impl MessageScorer for KeepUserMessages {
+///     fn score(&self, message: &Message, age: usize) -> f32 {
+///         if message.role() == Role::User { 1.0 } else { 0.0 }
+///     }
+/// }
+///
+/// let config = SmartCompressionConfig::default().with_scorer(KeepUserMessages);
+/// ```
+pub trait MessageScorer: fmt::Debug + Send + Sync {
+    /// Scores `message`, where `age` is the number of messages after it.
+    ///
+    /// Higher scores are kept first; messages scoring zero or less are never
+    /// kept.
+    fn score(&self, message: &Message, age: usize) -> f32;
+}
+
+/// Weights messages by type, then halves the weight every
+/// [`half_life`](Self::half_life) messages of age.
+///
+/// User messages carry goals and corrections, so they weigh the most,
+/// followed by assistant conclusions. System reminders and tool chatter are
+/// cheap to recover and weigh the least.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultScorer {
+    /// Number of messages after which a message's weight halves.
+    pub half_life: usize,
+}
+
+impl Default for DefaultScorer {
+    fn default() -> Self {
+        Self { half_life: 20 }
+    }
+}
+
+impl DefaultScorer {
+    /// Weight of a message before recency is applied.
+    #[must_use]
+    pub const fn type_weight(message: &Message) -> f32 {
+        match message {
+            Message::User { .. } => 1.0,
+            Message::Assistant { tool_calls, .. } if tool_calls.is_empty() => 0.7,
+            Message::System { .. } => 0.3,
+            Message::Assistant { .. } | Message::Tool { .. } => 0.1,
+        }
+    }
+}
+
+impl MessageScorer for DefaultScorer {
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self, message: &Message, age: usize) -> f32 {
+        let decay = 0.5_f32.powf(age as f32 / self.half_life.max(1) as f32);
+        Self::type_weight(message) * decay
+    }
+}
+
+impl SmartCompressionConfig {
+    /// Replaces the scorer that picks messages to keep through compaction.
+    #[must_use]
+    pub fn with_scorer(mut self, scorer: impl MessageScorer + 'static) -> Self {
+        self.scorer = Arc::new(scorer);
+        self
+    }
+}
+
+/// Picks up to `count` of the highest-scoring messages that fit in
+/// `token_budget`, returned in their original order.
+pub(crate) fn select_retained(
+    messages: &[Message],
+    scorer: &dyn MessageScorer,
+    count: usize,
+    token_budget: usize,
+) -> Vec<Message> {
+    let mut candidates: Vec<(usize, f32)> = messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| {
+            !matches!(message, Message::Tool { .. }) && message.tool_calls().is_empty()
+        })
+        .map(|(index, message)| (index, scorer.score(message, messages.len() - index - 1)))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    // Stable sort, so ties go to the older message as they appear first.
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut kept = Vec::new();
+    let mut tokens = 0;
+    for (index, _) in candidates {
+        if kept.len() == count {
+            break;
+        }
+        let cost = estimate_tokens(messages[index].content());
+        if tokens + cost <= token_budget {
+            tokens += cost;
+            kept.push(index);
+        }
+    }
+    kept.sort_unstable();
+    kept.into_iter()
+        .map(|index| messages[index].clone())
+        .collect()
+}
+
 /// Configuration for what content to preserve during compression.
 #[derive(Debug, Clone)]
 pub struct PreserveConfig {
@@ -117,10 +269,98 @@ pub fn estimate_context_usage(messages: &[Message], context_window: usize) -> f3
     message_tokens as f32 / context_window as f32
 }
 
-// Prompt templates loaded from files
-const COMPRESSION_SYSTEM_PROMPT: &str = include_str!("prompts/compression_system.txt");
-const COMPRESSION_USER_TEMPLATE: &str = include_str!("prompts/compression_user.txt");
-const COMPRESSION_URLS_TEMPLATE: &str = include_str!("prompts/compression_urls.txt");
+/// Savings from replacing repeated tool results with references.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupStats {
+    /// Tool results replaced by a reference.
+    pub duplicates: usize,
+    /// Bytes removed from the conversation.
+    pub bytes_saved: usize,
+}
+
+impl DedupStats {
+    /// Estimated tokens saved, using the same approximation as [`estimate_tokens`].
+    #[must_use]
+    pub const fn tokens_saved(&self) -> usize {
+        self.bytes_saved / 4
+    }
+
+    pub(crate) const fn accumulate(&mut self, other: Self) {
+        self.duplicates += other.duplicates;
+        self.bytes_saved += other.bytes_saved;
+    }
+}
+
+/// Replaces tool results identical to an earlier one with a reference to the
+/// first occurrence.
+///
+/// Agents often re-read the same file or re-run the same listing; the repeats
+/// cost context without telling the model anything new. Results shorter than
+/// `min_len` are left alone, which also keeps earlier references from being
+/// matched again.
+pub(crate) fn dedupe_tool_results(messages: &mut [Message], min_len: usize) -> DedupStats {
+    let mut first_seen: HashMap<[u8; 32], String> = HashMap::new();
+    let mut stats = DedupStats::default();
+
+    for message in messages {
+        let Message::Tool {
+            content,
+            tool_call_id,
+        } = message
+        else {
+            continue;
+        };
+        if content.len() < min_len {
+            continue;
+        }
+
+        let fingerprint: [u8; 32] = Sha256::digest(content.as_bytes()).into();
+        match first_seen.get(&fingerprint) {
+            Some(first_id) => {
+                let reference = format!("[Identical to the result of tool call {first_id} above.]");
+                if reference.len() < content.len() {
+                    stats.duplicates += 1;
+                    stats.bytes_saved += content.len() - reference.len();
+                    *content = reference;
+                }
+            }
+            None => {
+                first_seen.insert(fingerprint, tool_call_id.clone());
+            }
+        }
+    }
+
+    stats
+}
+
+/// Variables of the `agent.compression_user` and `agent.compression_urls` prompts.
+#[derive(Serialize)]
+struct CompressionPrompt<'a> {
+    file_paths: String,
+    errors: String,
+    commands: String,
+    running_jobs: String,
+    dialogue: Option<String>,
+    content_with_urls: Option<&'a str>,
+}
+
+impl CompressionPrompt<'_> {
+    fn new(preserved: &PreservedContent) -> Self {
+        Self {
+            file_paths: preserved.file_paths.join(", "),
+            errors: preserved.errors.join("\n"),
+            commands: preserved.commands.join("\n"),
+            running_jobs: preserved
+                .running_jobs
+                .as_ref()
+                .map_or(String::new(), |jobs| {
+                    format!("- Running background jobs:\n{jobs}")
+                }),
+            dialogue: None,
+            content_with_urls: None,
+        }
+    }
+}
 
 /// Result of a compaction operation with URL tracking.
 #[derive(Debug, Clone)]
@@ -225,30 +465,19 @@ impl SmartCompressionConfig {
     ///
     /// # Errors
     ///
-    /// Returns an error if the LLM fails to generate a summary.
+    /// Returns an error if a prompt fails to render or the LLM fails to
+    /// generate a summary.
     pub async fn generate_summary<LLM: LanguageModel>(
         &self,
         llm: &LLM,
         messages: &[Message],
         preserved: &PreservedContent,
-    ) -> Result<String, LLM::Error> {
-        let running_jobs = preserved
-            .running_jobs
-            .as_ref()
-            .map_or(String::new(), |jobs| {
-                format!("- Running background jobs:\n{jobs}")
-            });
-
-        let prompt = COMPRESSION_USER_TEMPLATE
-            .replace("{file_paths}", &preserved.file_paths.join(", "))
-            .replace("{errors}", &preserved.errors.join("\n"))
-            .replace("{commands}", &preserved.commands.join("\n"))
-            .replace("{running_jobs}", &running_jobs)
-            .replace("{dialogue}", &format_messages(messages));
-
-        let request = aither_core::llm::oneshot(COMPRESSION_SYSTEM_PROMPT, prompt);
-        let stream = llm.respond(request);
-        aither_core::llm::collect_text(stream).await
+    ) -> Result<String, AgentError> {
+        let vars = CompressionPrompt {
+            dialogue: Some(format_messages(messages)),
+            ..CompressionPrompt::new(preserved)
+        };
+        self.summarize(llm, "agent.compression_user", &vars).await
     }
 
     /// Generate a compressed summary with URL tracking for tool outputs.
@@ -270,34 +499,22 @@ impl SmartCompressionConfig {
     ///
     /// # Errors
     ///
-    /// Returns an error if the LLM fails to generate a summary.
+    /// Returns an error if a prompt fails to render or the LLM fails to
+    /// generate a summary.
     pub async fn generate_summary_with_urls<LLM: LanguageModel>(
         &self,
         llm: &LLM,
         messages: &[Message],
         preserved: &PreservedContent,
         pending_urls: &[ContentWithUrl],
-    ) -> Result<CompactionResult, LLM::Error> {
+    ) -> Result<CompactionResult, AgentError> {
         // Build content with URLs section
         let content_with_urls = format_content_with_urls(messages, pending_urls);
-
-        let running_jobs = preserved
-            .running_jobs
-            .as_ref()
-            .map_or(String::new(), |jobs| {
-                format!("- Running background jobs:\n{jobs}")
-            });
-
-        let prompt = COMPRESSION_URLS_TEMPLATE
-            .replace("{content_with_urls}", &content_with_urls)
-            .replace("{file_paths}", &preserved.file_paths.join(", "))
-            .replace("{errors}", &preserved.errors.join("\n"))
-            .replace("{commands}", &preserved.commands.join("\n"))
-            .replace("{running_jobs}", &running_jobs);
-
-        let request = aither_core::llm::oneshot(COMPRESSION_SYSTEM_PROMPT, prompt);
-        let stream = llm.respond(request);
-        let summary = aither_core::llm::collect_text(stream).await?;
+        let vars = CompressionPrompt {
+            content_with_urls: Some(&content_with_urls),
+            ..CompressionPrompt::new(preserved)
+        };
+        let summary = self.summarize(llm, "agent.compression_urls", &vars).await?;
 
         // Extract which URLs were actually referenced
         let referenced_urls = extract_referenced_urls(&summary);
@@ -307,6 +524,27 @@ impl SmartCompressionConfig {
             referenced_urls,
         })
     }
+
+    /// Ask `llm` for a summary, rendering the user prompt from the template `name`.
+    async fn summarize<LLM: LanguageModel>(
+        &self,
+        llm: &LLM,
+        name: &str,
+        vars: &CompressionPrompt<'_>,
+    ) -> Result<String, AgentError> {
+        let system = self
+            .prompts
+            .render("agent.compression_system", &())
+            .map_err(|e| AgentError::Config(e.to_string()))?;
+        let prompt = self
+            .prompts
+            .render(name, vars)
+            .map_err(|e| AgentError::Config(e.to_string()))?;
+        let request = aither_core::llm::oneshot(system, prompt);
+        aither_core::llm::collect_text(llm.respond(request))
+            .await
+            .map_err(|e| AgentError::Llm(e.to_string()))
+    }
 }
 
 /// Extract file paths from content.
@@ -504,6 +742,34 @@ fn is_valid_output_filename(filename: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aither_core::llm::ToolCall;
+
+    #[test]
+    fn test_default_compression_prompts_render() {
+        let prompts = crate::default_prompts();
+        let preserved = PreservedContent {
+            file_paths: vec!["src/main.rs".to_string()],
+            running_jobs: Some("task-a".to_string()),
+            ..PreservedContent::default()
+        };
+
+        let user = CompressionPrompt {
+            dialogue: Some("user: fix the {build}".to_string()),
+            ..CompressionPrompt::new(&preserved)
+        };
+        let rendered = prompts.render("agent.compression_user", &user).unwrap();
+        assert!(rendered.contains("File paths: src/main.rs"));
+        assert!(rendered.contains("- Running background jobs:\ntask-a"));
+        assert!(rendered.contains("user: fix the {build}"));
+
+        let urls = CompressionPrompt {
+            content_with_urls: Some("outputs/a.txt"),
+            ..CompressionPrompt::new(&preserved)
+        };
+        let rendered = prompts.render("agent.compression_urls", &urls).unwrap();
+        assert!(rendered.contains("outputs/a.txt"));
+        assert!(prompts.render("agent.compression_system", &()).is_ok());
+    }
 
     #[test]
     fn test_extract_file_paths() {
@@ -522,6 +788,68 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_dedupe_tool_results() {
+        let listing = "src/main.rs\nsrc/lib.rs\n".repeat(20);
+        let mut messages = vec![
+            Message::tool("call_1", listing.clone()),
+            Message::assistant("Let me check again."),
+            Message::tool("call_2", listing.clone()),
+            Message::tool("call_3", "OK"),
+            Message::tool("call_4", "OK"),
+        ];
+
+        let stats = dedupe_tool_results(&mut messages, 64);
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(messages[0].content(), listing);
+        assert!(messages[2].content().contains("call_1"));
+        assert_eq!(
+            stats.bytes_saved,
+            listing.len() - messages[2].content().len()
+        );
+        assert_eq!(messages[4].content(), "OK");
+
+        // A second pass finds nothing new.
+        assert_eq!(
+            dedupe_tool_results(&mut messages, 64),
+            DedupStats::default()
+        );
+    }
+
+    #[test]
+    fn test_select_retained_prefers_goals_over_tool_chatter() {
+        let messages = vec![
+            Message::user("Port the parser to the new AST."),
+            Message::assistant_with_tool_calls(
+                "",
+                vec![ToolCall::new(
+                    "call_1",
+                    "read",
+                    serde_json::json!({"path": "parser.rs"}),
+                )],
+            ),
+            Message::tool("call_1", "fn parse() {}"),
+            Message::assistant("Decided to keep the old error type."),
+            Message::system("<system-reminder>Update TODO.md</system-reminder>"),
+            Message::tool("call_2", "ok"),
+        ];
+        let scorer = DefaultScorer::default();
+
+        let kept = select_retained(&messages, &scorer, 2, usize::MAX);
+        let kept: Vec<&str> = kept.iter().map(Message::content).collect();
+        assert_eq!(
+            kept,
+            [
+                "Port the parser to the new AST.",
+                "Decided to keep the old error type."
+            ]
+        );
+
+        let kept = select_retained(&messages, &scorer, 1, usize::MAX);
+        assert_eq!(kept[0].content(), "Port the parser to the new AST.");
+        assert!(select_retained(&messages, &scorer, 2, 0).is_empty());
+    }
+
     #[test]
     fn test_estimate_tokens() {
         let content = "This is a test string with some content";
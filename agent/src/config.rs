@@ -1,6 +1,9 @@
 //! Agent configuration.
 
+use aither_core::llm::model::{Ability, Parameters, Profile as ModelProfile, ReasoningEffort};
+
 use crate::compression::ContextStrategy;
+use crate::plan_template::PlanTemplateSelection;
 
 /// Agent specialization mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -66,6 +69,22 @@ impl ContextBlock {
         self.priority = priority;
         self
     }
+
+    /// Creates a block from resource contents read from an MCP server.
+    ///
+    /// ```rust,ignore
+    /// for contents in conn.read_resource("file:///repo/README.md").await? {
+    ///     builder = builder.context_block(ContextBlock::from_mcp_resource(&contents));
+    /// }
+    /// ```
+    #[cfg(feature = "mcp")]
+    #[must_use]
+    pub fn from_mcp_resource(contents: &aither_mcp::protocol::ResourceContents) -> Self {
+        Self::new(
+            "mcp_resource",
+            format!("uri: {}\n\n{}", contents.uri, contents.to_context_text()),
+        )
+    }
 }
 
 /// Prompt-level context assembly settings.
@@ -89,12 +108,123 @@ impl Default for ContextAssemblerConfig {
     }
 }
 
+/// Overrides for the sampling parameters of main-loop requests.
+///
+/// Unset fields fall back to what `aither-models` knows about the model:
+/// its default reasoning effort, and its output limit capped at a quarter of
+/// the context window so long runs keep room for history. Temperatures are
+/// clamped to the provider's accepted range. Models the registry doesn't
+/// know get provider defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestDefaults {
+    /// Reasoning effort for models that reason.
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Maximum tokens per response.
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature.
+    pub temperature: Option<f32>,
+    /// Upper bound applied to [`temperature`](Self::temperature).
+    pub max_temperature: Option<f32>,
+}
+
+impl RequestDefaults {
+    /// Sets the reasoning effort.
+    #[must_use]
+    pub const fn with_reasoning_effort(mut self, effort: ReasoningEffort) -> Self {
+        self.reasoning_effort = Some(effort);
+        self
+    }
+
+    /// Sets the maximum tokens per response.
+    #[must_use]
+    pub const fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Sets the sampling temperature.
+    #[must_use]
+    pub const fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Caps the sampling temperature.
+    #[must_use]
+    pub const fn with_max_temperature(mut self, max: f32) -> Self {
+        self.max_temperature = Some(max);
+        self
+    }
+
+    /// Parameters for requests to the model described by `profile`.
+    #[must_use]
+    pub fn parameters(&self, profile: Option<&ModelProfile>) -> Parameters {
+        let mut parameters = Parameters::default();
+        let info = profile.and_then(|profile| {
+            aither_models::lookup(&profile.slug).or_else(|| aither_models::lookup(&profile.name))
+        });
+
+        let reasons = profile
+            .is_some_and(|profile| profile.abilities.contains(&Ability::Reasoning))
+            || info.is_some_and(|info| info.abilities.contains(&Ability::Reasoning));
+        let effort = self.reasoning_effort.or_else(|| {
+            let default = aither_models::reasoning_meta(info?.id).default_effort;
+            reasons.then_some(default?).and_then(parse_effort)
+        });
+        if let Some(effort) = effort {
+            parameters = parameters.reasoning_effort(effort);
+        }
+
+        let max_tokens = self.max_tokens.or_else(|| {
+            let info = info?;
+            let limit = info.max_output_tokens?;
+            Some(if info.context_window > 0 {
+                limit.min(info.context_window / 4)
+            } else {
+                limit
+            })
+        });
+        if let Some(max_tokens) = max_tokens {
+            parameters = parameters.max_tokens(max_tokens);
+        }
+
+        if let Some(temperature) = self.temperature {
+            let provider_max = match info.map(|info| info.provider) {
+                Some("anthropic") => 1.0,
+                _ => 2.0,
+            };
+            let max = self
+                .max_temperature
+                .map_or(provider_max, |max| max.min(provider_max));
+            parameters = parameters.temperature(temperature.clamp(0.0, max));
+        }
+        parameters
+    }
+}
+
+/// Maps a registry effort label onto the closest [`ReasoningEffort`].
+fn parse_effort(label: &str) -> Option<ReasoningEffort> {
+    match label {
+        "none" | "minimal" | "minimum" => Some(ReasoningEffort::Minimum),
+        "low" => Some(ReasoningEffort::Low),
+        "medium" => Some(ReasoningEffort::Medium),
+        "high" | "xhigh" | "max" => Some(ReasoningEffort::High),
+        _ => None,
+    }
+}
+
 /// Configuration for agent behavior.
 #[derive(Debug, Clone)]
 pub struct AgentConfig {
     /// Maximum number of agent loop iterations.
     pub max_iterations: usize,
 
+    /// Tokens a run is expected to use.
+    ///
+    /// Advisory: the todo list is sized to fit what remains, but the run is
+    /// not stopped when it runs out.
+    pub token_budget: Option<u64>,
+
     /// Context management strategy.
     pub context: ContextStrategy,
 
@@ -115,6 +245,12 @@ pub struct AgentConfig {
 
     /// Context assembly behavior.
     pub context_assembler: ContextAssemblerConfig,
+
+    /// Sampling parameter overrides for main-loop requests.
+    pub request_defaults: RequestDefaults,
+
+    /// Plan template seeded into runs that use the todo list.
+    pub plan_template: PlanTemplateSelection,
 }
 
 impl Default for AgentConfig {
@@ -123,6 +259,7 @@ impl Default for AgentConfig {
             // Very high default - should effectively never hit this limit
             // Individual use cases can set lower limits if needed
             max_iterations: 10_000,
+            token_budget: None,
             context: ContextStrategy::default(),
             system_prompt: None,
             persona_prompt: None,
@@ -130,6 +267,8 @@ impl Default for AgentConfig {
             transcript_path: None,
             context_blocks: Vec::new(),
             context_assembler: ContextAssemblerConfig::default(),
+            request_defaults: RequestDefaults::default(),
+            plan_template: PlanTemplateSelection::default(),
         }
     }
 }
@@ -148,6 +287,13 @@ impl AgentConfig {
         self
     }
 
+    /// Sets the advisory token budget.
+    #[must_use]
+    pub const fn with_token_budget(mut self, tokens: u64) -> Self {
+        self.token_budget = Some(tokens);
+        self
+    }
+
     /// Sets the context strategy.
     #[must_use]
     pub const fn with_context(mut self, strategy: ContextStrategy) -> Self {
@@ -189,4 +335,51 @@ impl AgentConfig {
         self.context_blocks.push(block);
         self
     }
+
+    /// Sets how the plan template is chosen.
+    #[must_use]
+    pub const fn with_plan_template(mut self, selection: PlanTemplateSelection) -> Self {
+        self.plan_template = selection;
+        self
+    }
+
+    /// Sets the sampling parameter overrides.
+    #[must_use]
+    pub const fn with_request_defaults(mut self, defaults: RequestDefaults) -> Self {
+        self.request_defaults = defaults;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_defaults_follow_model_metadata() {
+        let profile = ModelProfile::new(
+            "Claude Opus 4.6",
+            "anthropic",
+            "claude-opus-4-6",
+            "",
+            1_000_000,
+        );
+
+        let parameters = RequestDefaults::default().parameters(Some(&profile));
+        assert_eq!(parameters.reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(parameters.max_tokens, Some(64_000));
+        assert_eq!(parameters.temperature, None);
+
+        let parameters = RequestDefaults::default()
+            .with_reasoning_effort(ReasoningEffort::Low)
+            .with_temperature(1.5)
+            .parameters(Some(&profile));
+        assert_eq!(parameters.reasoning_effort, Some(ReasoningEffort::Low));
+        assert_eq!(parameters.temperature, Some(1.0));
+
+        let unknown = ModelProfile::new("Local", "me", "local-model", "", 8192);
+        let parameters = RequestDefaults::default().parameters(Some(&unknown));
+        assert_eq!(parameters.reasoning_effort, None);
+        assert_eq!(parameters.max_tokens, None);
+    }
 }
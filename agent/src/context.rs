@@ -25,6 +25,8 @@ use serde::{Deserialize, Serialize, ser::SerializeMap};
 
 use aither_core::llm::Message;
 
+use crate::compression::{DedupStats, dedupe_tool_results};
+
 /// The entire context window state. Fully serializable for session persistence.
 ///
 /// `system_blocks` is an [`IndexMap`] to preserve insertion order, producing a
@@ -41,6 +43,10 @@ pub struct Context {
     /// All conversation messages: user, assistant, tool, system reminders, handoff.
     /// Everything that is NOT part of the stable system prefix lives here.
     recent: Vec<Message>,
+
+    /// Savings from tool-result deduplication. Not persisted.
+    #[serde(skip)]
+    dedup: DedupStats,
 }
 
 // Custom Serialize so we can emit system_blocks as a map.
@@ -185,6 +191,23 @@ impl Context {
         self.recent.clone()
     }
 
+    /// Replaces tool results that repeat an earlier one with a reference to
+    /// the first occurrence, skipping results shorter than `min_len` bytes.
+    ///
+    /// Returns the savings of this pass; [`dedup_stats`](Self::dedup_stats)
+    /// keeps the running total.
+    pub fn dedupe_tool_results(&mut self, min_len: usize) -> DedupStats {
+        let stats = dedupe_tool_results(&mut self.recent, min_len);
+        self.dedup.accumulate(stats);
+        stats
+    }
+
+    /// Returns the total savings from [`dedupe_tool_results`](Self::dedupe_tool_results).
+    #[must_use]
+    pub const fn dedup_stats(&self) -> DedupStats {
+        self.dedup
+    }
+
     // ── Build messages for LLM request ────────────────────────────────
 
     /// Build the full message array for the LLM request.
@@ -21,6 +21,20 @@ pub enum AgentEvent {
         arguments: String,
     },
 
+    /// Fragment of a tool call's arguments while the model is still writing
+    /// them.
+    ///
+    /// For display only; [`AgentEvent::ToolCallStart`] follows with the
+    /// complete arguments.
+    ToolCallDelta {
+        /// Identifier matching the later start event.
+        id: String,
+        /// Name of the tool being called.
+        name: String,
+        /// Next fragment of the JSON-encoded arguments.
+        arguments: String,
+    },
+
     /// Tool execution completed.
     ToolCallEnd {
         /// Unique identifier matching the start event.
@@ -47,6 +61,17 @@ pub enum AgentEvent {
         turns: usize,
     },
 
+    /// A filesystem tool refused a path outside its root.
+    AccessBlocked {
+        /// Path as requested, relative to the root.
+        path: std::path::PathBuf,
+        /// Why it was refused.
+        reason: String,
+    },
+
+    /// Source cited by the model's native web search.
+    Citation(aither_core::llm::Citation),
+
     /// Token usage information from LLM.
     Usage(aither_core::llm::Usage),
 
@@ -81,6 +106,20 @@ impl AgentEvent {
         }
     }
 
+    /// Creates a new tool call delta event.
+    #[must_use]
+    pub fn tool_delta(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        arguments: impl Into<String>,
+    ) -> Self {
+        Self::ToolCallDelta {
+            id: id.into(),
+            name: name.into(),
+            arguments: arguments.into(),
+        }
+    }
+
     /// Creates a new tool call end event with success.
     #[must_use]
     pub fn tool_success(
@@ -0,0 +1,228 @@
+//! Executors that carry out todo tasks of a given kind.
+//!
+//! By default the agent works through every todo task itself. Registering a
+//! [`StepExecutor`] for a [`StepKind`] hands tasks of that kind to it instead:
+//! a subagent with editing tools for code edits, a research agent for
+//! research, and so on. The executor runs when the model marks a task
+//! `in_progress`, and its output completes the task.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let agent = Agent::builder(llm.clone())
+//!     .todo()
+//!     .step_executor(
+//!         StepKind::Research,
+//!         AgentExecutor::new(llm, |llm| {
+//!             AgentBuilder::new(llm).system_prompt("You are a careful researcher.")
+//!         }),
+//!     )
+//!     .build();
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use aither_core::LanguageModel;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::specialized::task::SubagentBuilder;
+use crate::{AgentBuilder, TodoItem};
+
+/// What kind of work a todo task is, used to pick its executor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StepKind {
+    /// Anything else; done by the agent itself unless an executor is registered.
+    #[default]
+    General,
+    /// Editing source code.
+    CodeEdit,
+    /// Running shell commands.
+    Shell,
+    /// Finding and reading sources.
+    Research,
+}
+
+impl StepKind {
+    /// Every kind, in display order.
+    pub const ALL: [Self; 4] = [Self::General, Self::CodeEdit, Self::Shell, Self::Research];
+
+    /// Name of the kind as used in todo lists.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::General => "general",
+            Self::CodeEdit => "code_edit",
+            Self::Shell => "shell",
+            Self::Research => "research",
+        }
+    }
+
+    pub(crate) const fn is_general(&self) -> bool {
+        matches!(self, Self::General)
+    }
+}
+
+/// Carries out todo tasks on the agent's behalf.
+pub trait StepExecutor: Send + Sync {
+    /// Performs `task` and returns a report of what was done.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task could not be done; the agent then does it
+    /// itself.
+    fn execute(&self, task: &TodoItem) -> impl Future<Output = aither_core::Result<String>> + Send;
+}
+
+/// Object-safe form of [`StepExecutor`], so executors of different types
+/// can share a registry.
+trait DynStepExecutor: Send + Sync {
+    fn execute<'a>(
+        &'a self,
+        task: &'a TodoItem,
+    ) -> Pin<Box<dyn Future<Output = aither_core::Result<String>> + Send + 'a>>;
+}
+
+impl<T: StepExecutor> DynStepExecutor for T {
+    fn execute<'a>(
+        &'a self,
+        task: &'a TodoItem,
+    ) -> Pin<Box<dyn Future<Output = aither_core::Result<String>> + Send + 'a>> {
+        Box::pin(StepExecutor::execute(self, task))
+    }
+}
+
+/// Executors registered per [`StepKind`].
+#[derive(Default)]
+pub struct StepExecutors {
+    executors: HashMap<StepKind, Box<dyn DynStepExecutor>>,
+}
+
+impl std::fmt::Debug for StepExecutors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StepExecutors")
+            .field("kinds", &self.kinds())
+            .finish()
+    }
+}
+
+impl StepExecutors {
+    /// Creates an empty registry, so the agent does every task itself.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes tasks of `kind` to `executor`, replacing any previous one.
+    pub fn register(&mut self, kind: StepKind, executor: impl StepExecutor + 'static) {
+        self.executors.insert(kind, Box::new(executor));
+    }
+
+    /// Returns `true` if tasks of `kind` have an executor.
+    #[must_use]
+    pub fn contains(&self, kind: StepKind) -> bool {
+        self.executors.contains_key(&kind)
+    }
+
+    /// Returns `true` if no executor is registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.executors.is_empty()
+    }
+
+    /// Kinds with an executor, in display order.
+    #[must_use]
+    pub fn kinds(&self) -> Vec<StepKind> {
+        StepKind::ALL
+            .into_iter()
+            .filter(|kind| self.contains(*kind))
+            .collect()
+    }
+
+    /// Runs `task` on the executor for its kind, if there is one.
+    pub(crate) async fn execute(&self, task: &TodoItem) -> Option<aither_core::Result<String>> {
+        let executor = self.executors.get(&task.step)?;
+        Some(executor.execute(task).await)
+    }
+}
+
+/// Runs each task with a fresh agent, like the `subagent` tool does.
+pub struct AgentExecutor<LLM> {
+    llm: LLM,
+    builder: SubagentBuilder<LLM>,
+}
+
+impl<LLM> std::fmt::Debug for AgentExecutor<LLM> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AgentExecutor").finish_non_exhaustive()
+    }
+}
+
+impl<LLM> AgentExecutor<LLM> {
+    /// Creates an executor that configures each agent with `builder`.
+    pub fn new<F>(llm: LLM, builder: F) -> Self
+    where
+        F: Fn(LLM) -> AgentBuilder<LLM, LLM, LLM, ()> + Send + Sync + 'static,
+    {
+        Self {
+            llm,
+            builder: Arc::new(builder),
+        }
+    }
+}
+
+impl<LLM> StepExecutor for AgentExecutor<LLM>
+where
+    LLM: LanguageModel + Clone + 'static,
+{
+    async fn execute(&self, task: &TodoItem) -> aither_core::Result<String> {
+        let mut agent = (self.builder)(self.llm.clone()).build();
+        agent
+            .query(&format!(
+                "Complete this task, then report what you did:\n\n{}",
+                task.content
+            ))
+            .await
+            .map_err(|e| anyhow::anyhow!("executor agent failed: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TodoKind, TodoStatus};
+
+    struct Echo;
+
+    impl StepExecutor for Echo {
+        async fn execute(&self, task: &TodoItem) -> aither_core::Result<String> {
+            Ok(format!("did {}", task.content))
+        }
+    }
+
+    #[tokio::test]
+    async fn routes_by_step_kind() {
+        let mut executors = StepExecutors::new();
+        executors.register(StepKind::Shell, Echo);
+        assert_eq!(executors.kinds(), [StepKind::Shell]);
+
+        let mut task = TodoItem {
+            content: "Run the migration".to_string(),
+            status: TodoStatus::InProgress,
+            active_form: "Running the migration".to_string(),
+            stretch: false,
+            kind: TodoKind::Action,
+            check: None,
+            step: StepKind::Shell,
+        };
+        let output = executors.execute(&task).await.unwrap().unwrap();
+        assert_eq!(output, "did Run the migration");
+
+        task.step = StepKind::General;
+        assert!(executors.execute(&task).await.is_none());
+    }
+}
@@ -0,0 +1,74 @@
+//! User feedback injected while the agent is running.
+//!
+//! [`Agent::run`](crate::Agent::run) borrows the agent mutably for the whole
+//! run, so hosts steer it through a [`FeedbackHandle`] obtained beforehand.
+//! Queued feedback enters the conversation as user messages at the next
+//! iteration boundary: before the next model request, or instead of
+//! finishing if the model was about to stop.
+
+use std::sync::{Arc, Mutex};
+
+/// Cloneable, thread-safe handle for queueing feedback to a running agent.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let feedback = agent.feedback_handle();
+/// std::thread::spawn(move || {
+///     feedback.inject("Use the v2 API instead.");
+/// });
+/// let stream = agent.run("Migrate the client", std::iter::empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FeedbackHandle {
+    queue: Arc<Mutex<Vec<String>>>,
+}
+
+impl FeedbackHandle {
+    /// Queues `text` for the next iteration boundary.
+    ///
+    /// Blank feedback is ignored.
+    pub fn inject(&self, text: impl Into<String>) {
+        let text = text.into();
+        if text.trim().is_empty() {
+            return;
+        }
+        self.lock().push(text);
+    }
+
+    /// Returns `true` if feedback is waiting to be delivered.
+    #[must_use]
+    pub fn has_pending(&self) -> bool {
+        !self.lock().is_empty()
+    }
+
+    /// Removes and returns the queued feedback, oldest first.
+    pub(crate) fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        // A panic while holding the lock can't leave the queue inconsistent.
+        self.queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_queue() {
+        let handle = FeedbackHandle::default();
+        let remote = handle.clone();
+
+        remote.inject("Use the v2 API.");
+        remote.inject("   ");
+        assert!(handle.has_pending());
+
+        assert_eq!(handle.take(), vec!["Use the v2 API.".to_string()]);
+        assert!(!remote.has_pending());
+    }
+}
@@ -208,6 +208,24 @@ where
     }
 }
 
+/// Denies tools the configured policy doesn't allow.
+///
+/// Only the tool name is checked; bash approval levels are enforced by the
+/// sandbox's permission handler.
+#[cfg(feature = "config")]
+impl Hook for aither_config::ToolPolicy {
+    async fn pre_tool_use(&self, ctx: &ToolUseContext<'_>) -> PreToolAction {
+        if self.allows(ctx.tool_name) {
+            PreToolAction::Allow
+        } else {
+            PreToolAction::Deny(format!(
+                "The `{}` tool is disabled by the tool policy.",
+                ctx.tool_name
+            ))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
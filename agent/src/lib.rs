@@ -39,9 +39,14 @@ mod config;
 mod context;
 mod error;
 mod event;
+mod executor;
+mod feedback;
 mod fs_util;
 mod hook;
 mod model_group;
+mod plan_template;
+mod prompts;
+mod report;
 mod stream;
 mod subagent_file;
 mod todo;
@@ -75,20 +80,27 @@ pub use agent::{Agent, CompactResult};
 pub use bash_agent::BashAgentBuilder;
 pub use builder::AgentBuilder;
 pub use compression::{
-    CompressionLevel, ContextStrategy, PreserveConfig, PreservedContent, SmartCompressionConfig,
+    CompressionLevel, ContextStrategy, DedupStats, DefaultScorer, MessageScorer, PreserveConfig,
+    PreservedContent, SmartCompressionConfig,
 };
 pub use config::{
     AgentConfig, AgentKind, ContextAssemblerConfig, ContextBlock, ContextBlockPriority,
+    RequestDefaults,
 };
 pub use context::{Context, ContextCheckpoint, ConversationMemory, MemoryCheckpoint};
 pub use error::AgentError;
 pub use event::AgentEvent;
+pub use executor::{AgentExecutor, StepExecutor, StepExecutors, StepKind};
+pub use feedback::FeedbackHandle;
 pub use hook::{
     HCons, Hook, PostToolAction, PreToolAction, StopContext, StopReason, ToolResultContext,
     ToolUseContext,
 };
+pub use plan_template::{PlanTemplate, PlanTemplateSelection};
+pub use prompts::default_prompts;
+pub use report::{IterationReport, RunReport, RunStatus, ToolStats};
 pub use stream::AgentStream;
-pub use todo::{TodoItem, TodoList, TodoStatus, TodoTool, TodoWriteArgs};
+pub use todo::{PlanBudget, TodoItem, TodoKind, TodoList, TodoStatus, TodoTool, TodoWriteArgs};
 pub use tools::AgentTools;
 
 // Model groups for budget tracking and fallback
@@ -97,7 +109,7 @@ pub use model_group::{
 };
 
 // Re-export core tool trait for convenience
-pub use aither_attachments::{CacheEntry, FileCache};
+pub use aither_attachments::{CacheEntry, CachePolicy, FileCache};
 pub use aither_core::llm::Tool;
 
 /// Default system prompt for bash-centric agents.
@@ -105,13 +117,17 @@ pub use aither_core::llm::Tool;
 /// This prompt teaches the LLM to use bash as the primary tool interface,
 /// with all capabilities exposed as CLI commands that can be piped and composed.
 ///
+/// It is registered as `agent.bash_system` in [`default_prompts`]; render it
+/// from there so a replacement template takes effect.
+///
 /// # Example
 ///
 /// ```rust,ignore
-/// use aither_agent::{Agent, BASH_SYSTEM_PROMPT};
+/// use aither_agent::{Agent, default_prompts};
 ///
+/// let prompts = default_prompts();
 /// let agent = Agent::builder(llm)
-///     .system_prompt(BASH_SYSTEM_PROMPT)
+///     .system_prompt(prompts.render("agent.bash_system", &())?)
 ///     .bash(permission_handler, config, output_store)
 ///     .build();
 /// ```
@@ -0,0 +1,215 @@
+//! Plan templates for common workflows.
+//!
+//! A template gives the model a proven step structure and the checks the
+//! work must pass, so its todo list starts from something that works instead
+//! of being invented from scratch on every run.
+
+/// A step structure for a kind of task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlanTemplate {
+    /// Reproduce, diagnose, fix and confirm a defect.
+    Bugfix,
+    /// Implement new functionality with tests and docs.
+    Feature,
+    /// Collect, read and synthesize sources on a topic.
+    LiteratureReview,
+    /// Pull structured records out of documents or pages.
+    DataExtraction,
+}
+
+/// How the agent picks a [`PlanTemplate`] for a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlanTemplateSelection {
+    /// Infer the template from the goal, using none if nothing matches.
+    #[default]
+    Infer,
+    /// Always use this template.
+    Use(PlanTemplate),
+    /// Don't seed plans with a template.
+    Disabled,
+}
+
+impl PlanTemplateSelection {
+    /// Returns the template to use for `goal`.
+    #[must_use]
+    pub fn resolve(self, goal: &str) -> Option<PlanTemplate> {
+        match self {
+            Self::Infer => PlanTemplate::infer(goal),
+            Self::Use(template) => Some(template),
+            Self::Disabled => None,
+        }
+    }
+}
+
+impl PlanTemplate {
+    /// Every template, in the order inference tries them.
+    pub const ALL: [Self; 4] = [
+        Self::LiteratureReview,
+        Self::DataExtraction,
+        Self::Bugfix,
+        Self::Feature,
+    ];
+
+    /// Short identifier of the template.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Bugfix => "bugfix",
+            Self::Feature => "feature",
+            Self::LiteratureReview => "literature_review",
+            Self::DataExtraction => "data_extraction",
+        }
+    }
+
+    /// Steps to adapt into the todo list, in order.
+    #[must_use]
+    pub const fn steps(self) -> &'static [&'static str] {
+        match self {
+            Self::Bugfix => &[
+                "Reproduce the bug with a failing test or command",
+                "Locate the root cause",
+                "Fix the root cause, not the symptom",
+                "Rerun the reproduction and the surrounding tests",
+            ],
+            Self::Feature => &[
+                "Read the code the feature touches",
+                "Decide on the interface and where the change lives",
+                "Implement the feature",
+                "Add tests covering the new behavior",
+                "Update documentation",
+            ],
+            Self::LiteratureReview => &[
+                "Define the research questions and search terms",
+                "Search for and collect candidate sources",
+                "Read each source and note its key findings",
+                "Compare findings and record disagreements",
+                "Write the review with citations",
+            ],
+            Self::DataExtraction => &[
+                "Inspect the source format and a few sample records",
+                "Define the output schema",
+                "Extract the records",
+                "Validate the records against the schema",
+                "Write the output",
+            ],
+        }
+    }
+
+    /// Checks the work must pass before it is reported as done.
+    #[must_use]
+    pub const fn verification(self) -> &'static [&'static str] {
+        match self {
+            Self::Bugfix => &[
+                "The reproduction no longer fails",
+                "The existing tests still pass",
+            ],
+            Self::Feature => &["The project builds and its tests and lints pass"],
+            Self::LiteratureReview => &[
+                "Every claim cites a source that was actually read",
+                "Cited links resolve",
+            ],
+            Self::DataExtraction => &[
+                "Record counts match the source",
+                "Spot-checked records match the source exactly",
+            ],
+        }
+    }
+
+    /// Words in a goal that suggest this template.
+    const fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::Bugfix => &[
+                "fix",
+                "bug",
+                "bugs",
+                "crash",
+                "crashes",
+                "broken",
+                "regression",
+                "failing",
+                "fails",
+            ],
+            Self::Feature => &["implement", "add", "feature", "support", "build", "create"],
+            Self::LiteratureReview => {
+                &["literature", "papers", "survey", "studies", "publications"]
+            }
+            Self::DataExtraction => &["extract", "scrape", "csv", "dataset", "records"],
+        }
+    }
+
+    /// Guesses the template from the words in `goal`.
+    ///
+    /// Returns `None` when no template's keywords appear.
+    #[must_use]
+    pub fn infer(goal: &str) -> Option<Self> {
+        let goal = goal.to_lowercase();
+        let words: Vec<&str> = goal
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        Self::ALL
+            .into_iter()
+            .find(|template| template.keywords().iter().any(|kw| words.contains(kw)))
+    }
+
+    /// Renders the template as planning instructions for the model.
+    #[must_use]
+    pub fn render(self) -> String {
+        let mut text = format!(
+            "<system-reminder>\nPlan template: {}. Adapt these steps into your todo list:\n",
+            self.name()
+        );
+        for (i, step) in self.steps().iter().enumerate() {
+            text.push_str(&format!("{}. {step}\n", i + 1));
+        }
+        text.push_str(
+            "Before reporting the work as done, verify the following, as `verification` tasks with a `check` command where one exists:\n",
+        );
+        for check in self.verification() {
+            text.push_str(&format!("- {check}\n"));
+        }
+        text.push_str("</system-reminder>");
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_template_from_goal() {
+        assert_eq!(
+            PlanTemplate::infer("Fix the crash when the config is empty"),
+            Some(PlanTemplate::Bugfix)
+        );
+        assert_eq!(
+            PlanTemplate::infer("Survey recent papers on retrieval augmentation"),
+            Some(PlanTemplate::LiteratureReview)
+        );
+        assert_eq!(
+            PlanTemplate::infer("Extract every invoice total into a CSV"),
+            Some(PlanTemplate::DataExtraction)
+        );
+        assert_eq!(
+            PlanTemplate::infer("Add support for YAML configs"),
+            Some(PlanTemplate::Feature)
+        );
+        // "prefix" must not match "fix".
+        assert_eq!(PlanTemplate::infer("Explain the prefix"), None);
+    }
+
+    #[test]
+    fn selection_overrides_inference() {
+        let goal = "Fix the login bug";
+        assert_eq!(
+            PlanTemplateSelection::Use(PlanTemplate::Feature).resolve(goal),
+            Some(PlanTemplate::Feature)
+        );
+        assert_eq!(PlanTemplateSelection::Disabled.resolve(goal), None);
+
+        let rendered = PlanTemplate::Bugfix.render();
+        assert!(rendered.contains("1. Reproduce the bug"));
+        assert!(rendered.contains("- The existing tests still pass"));
+    }
+}
@@ -0,0 +1,31 @@
+//! Built-in prompts, registered under stable names so they can be replaced.
+
+use aither_core::prompt::PromptLibrary;
+
+/// The agent's built-in prompts.
+///
+/// - `agent.bash_system`: [`BASH_SYSTEM_PROMPT`](crate::BASH_SYSTEM_PROMPT).
+/// - `agent.compression_system`: system prompt for context compression.
+/// - `agent.compression_user`: conversation to compress, with the
+///   `file_paths`, `errors`, `commands`, `running_jobs` and `dialogue`
+///   variables.
+/// - `agent.compression_urls`: content to compact with its URLs, with the
+///   `content_with_urls`, `file_paths`, `errors`, `commands` and
+///   `running_jobs` variables.
+#[must_use]
+pub fn default_prompts() -> PromptLibrary {
+    PromptLibrary::new()
+        .with("agent.bash_system", crate::BASH_SYSTEM_PROMPT)
+        .with(
+            "agent.compression_system",
+            include_str!("prompts/compression_system.txt"),
+        )
+        .with(
+            "agent.compression_user",
+            include_str!("prompts/compression_user.txt"),
+        )
+        .with(
+            "agent.compression_urls",
+            include_str!("prompts/compression_urls.txt"),
+        )
+}
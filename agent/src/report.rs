@@ -0,0 +1,135 @@
+//! Statistics describing how a run produced its result.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use aither_core::llm::Usage;
+
+use crate::error::AgentError;
+
+/// How a run ended.
+#[derive(Debug, Clone, Default)]
+pub enum RunStatus {
+    /// The run is in progress, or its stream was dropped before it ended.
+    #[default]
+    Running,
+    /// The agent produced a final response.
+    Completed,
+    /// The run stopped with an error.
+    Failed(AgentError),
+}
+
+/// Call statistics for one tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolStats {
+    /// Number of calls.
+    pub calls: usize,
+    /// Calls that returned an error or were denied by a hook.
+    pub failures: usize,
+    /// Time spent executing the tool, summed over calls.
+    pub total_duration: Duration,
+    /// Longest single call.
+    pub max_duration: Duration,
+}
+
+impl ToolStats {
+    /// Average time per call.
+    #[must_use]
+    pub fn average_duration(&self) -> Duration {
+        u32::try_from(self.calls)
+            .ok()
+            .filter(|&calls| calls > 0)
+            .map_or(Duration::ZERO, |calls| self.total_duration / calls)
+    }
+}
+
+/// One model request and the tool calls it made.
+#[derive(Debug, Clone, Default)]
+pub struct IterationReport {
+    /// Token usage reported for the request.
+    pub usage: Usage,
+    /// Number of tool calls the model made.
+    pub tool_calls: usize,
+    /// Time the model took to respond.
+    pub duration: Duration,
+}
+
+/// Summary of the most recent [`Agent::run`](crate::Agent::run).
+///
+/// Lets hosts log and display how a result was produced without parsing
+/// the conversation.
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    /// How the run ended.
+    pub status: RunStatus,
+    /// Model requests in order.
+    pub iterations: Vec<IterationReport>,
+    /// Statistics per tool name.
+    pub tools: BTreeMap<String, ToolStats>,
+    /// Token usage summed over all iterations.
+    pub usage: Usage,
+    /// Wall-clock time of the run.
+    pub duration: Duration,
+}
+
+impl RunReport {
+    /// Total number of tool calls.
+    #[must_use]
+    pub fn tool_calls(&self) -> usize {
+        self.tools.values().map(|stats| stats.calls).sum()
+    }
+
+    /// Total number of failed tool calls.
+    #[must_use]
+    pub fn tool_failures(&self) -> usize {
+        self.tools.values().map(|stats| stats.failures).sum()
+    }
+
+    pub(crate) fn record_iteration(&mut self, iteration: IterationReport) {
+        self.usage.accumulate(&iteration.usage);
+        self.iterations.push(iteration);
+    }
+
+    /// Adds tool calls to the latest iteration.
+    pub(crate) fn record_tool(&mut self, name: &str, duration: Duration, failed: bool) {
+        let stats = self.tools.entry(name.to_string()).or_default();
+        stats.calls += 1;
+        stats.failures += usize::from(failed);
+        stats.total_duration += duration;
+        stats.max_duration = stats.max_duration.max(duration);
+        if let Some(iteration) = self.iterations.last_mut() {
+            iteration.tool_calls += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_tool_calls() {
+        let mut report = RunReport::default();
+        report.record_iteration(IterationReport {
+            usage: Usage::new(100, 20),
+            ..IterationReport::default()
+        });
+        report.record_tool("bash", Duration::from_millis(30), false);
+        report.record_tool("bash", Duration::from_millis(10), true);
+        report.record_tool("read", Duration::from_millis(5), false);
+        report.record_iteration(IterationReport {
+            usage: Usage::new(150, 10),
+            ..IterationReport::default()
+        });
+
+        let bash = report.tools["bash"];
+        assert_eq!(bash.calls, 2);
+        assert_eq!(bash.failures, 1);
+        assert_eq!(bash.max_duration, Duration::from_millis(30));
+        assert_eq!(bash.average_duration(), Duration::from_millis(20));
+        assert_eq!(report.tool_calls(), 3);
+        assert_eq!(report.tool_failures(), 1);
+        assert_eq!(report.iterations[0].tool_calls, 3);
+        assert_eq!(report.usage.prompt_tokens, Some(250));
+    }
+}
@@ -52,12 +52,86 @@ mod tests {
             serde_json::to_string_pretty(&schema).unwrap()
         );
     }
+
+    #[test]
+    fn plan_budget_limits_required_tasks() {
+        let budget = PlanBudget {
+            remaining_iterations: 12,
+            remaining_tokens: Some(60_000),
+        };
+        assert_eq!(budget.max_tasks(), 2);
+        assert!(budget.is_tight());
+
+        let task = |content: &str, status, stretch| TodoItem {
+            content: content.to_string(),
+            status,
+            active_form: content.to_string(),
+            stretch,
+            kind: TodoKind::Action,
+            check: None,
+            step: StepKind::General,
+        };
+        let mut todos = vec![
+            task("Fix bug", TodoStatus::Completed, false),
+            task("Add test", TodoStatus::InProgress, false),
+            task("Update docs", TodoStatus::Pending, false),
+            task("Refactor module", TodoStatus::Pending, true),
+        ];
+        assert!(budget.check(&todos).is_ok());
+
+        todos[3].stretch = false;
+        assert!(budget.check(&todos).is_err());
+    }
+
+    #[test]
+    fn failed_check_reopens_action() {
+        let task = |content: &str, status, kind, check: Option<&str>| TodoItem {
+            content: content.to_string(),
+            status,
+            active_form: content.to_string(),
+            stretch: false,
+            kind,
+            check: check.map(str::to_string),
+            step: StepKind::General,
+        };
+        let old = vec![
+            task("Fix parser", TodoStatus::InProgress, TodoKind::Action, None),
+            task(
+                "Run parser tests",
+                TodoStatus::Pending,
+                TodoKind::Verification,
+                Some("cargo test parser"),
+            ),
+        ];
+        let mut new = old.clone();
+        new[0].status = TodoStatus::Completed;
+
+        assert!(due_checks(&old, &old).is_empty());
+        let due = due_checks(&old, &new);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].command, "cargo test parser");
+
+        let list = TodoList::new();
+        list.write(new);
+        list.record_check(&due[0], false);
+        let items = list.items();
+        assert_eq!(items[0].status, TodoStatus::Pending);
+        assert_eq!(items[1].status, TodoStatus::Pending);
+
+        let mut retried = items;
+        retried[0].status = TodoStatus::Completed;
+        list.write(retried);
+        list.record_check(&due[0], true);
+        assert_eq!(list.items()[1].status, TodoStatus::Completed);
+    }
 }
 
 use aither_core::llm::{Tool, ToolOutput};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::executor::StepKind;
+
 /// Status of a todo item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -70,6 +144,23 @@ pub enum TodoStatus {
     Completed,
 }
 
+/// Whether a todo item does the work or checks it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoKind {
+    /// Changes something: edits code, writes a file, fetches data.
+    #[default]
+    Action,
+    /// Confirms the action before it worked: reruns tests, re-fetches a URL.
+    Verification,
+}
+
+impl TodoKind {
+    const fn is_action(&self) -> bool {
+        matches!(self, Self::Action)
+    }
+}
+
 /// A single todo item.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TodoItem {
@@ -82,12 +173,161 @@ pub struct TodoItem {
     /// Example: "Running tests", "Fixing authentication bug"
     #[serde(rename = "activeForm")]
     pub active_form: String,
+    /// Optional work to attempt only if budget remains once the required
+    /// tasks are done.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stretch: bool,
+    /// Whether the task does the work or verifies the action before it.
+    #[serde(default, skip_serializing_if = "TodoKind::is_action")]
+    pub kind: TodoKind,
+    /// For verification tasks: a bash command that exits with status 0 when
+    /// the check passes, such as `cargo test` or `curl -fsS <url>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check: Option<String>,
+    /// Kind of work, which picks the executor that carries the task out.
+    #[serde(default, skip_serializing_if = "StepKind::is_general")]
+    pub step: StepKind,
+}
+
+/// A verification task whose action was just completed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DueCheck {
+    /// Content of the action being verified.
+    pub action: String,
+    /// Content of the verification task.
+    pub verification: String,
+    /// Command to run.
+    pub command: String,
+}
+
+impl DueCheck {
+    /// Reminder telling the model how the check went.
+    pub fn reminder(&self, passed: bool, output: &str) -> String {
+        if passed {
+            format!(
+                "<system-reminder>\nVerification \"{}\" passed.\n</system-reminder>",
+                self.verification
+            )
+        } else {
+            format!(
+                "<system-reminder>\nVerification \"{}\" failed, so \"{}\" was reopened. Output of `{}`:\n{output}\n</system-reminder>",
+                self.verification, self.action, self.command
+            )
+        }
+    }
+}
+
+/// Tasks that became in progress between `old` and `new`.
+pub(crate) fn started_tasks(old: &[TodoItem], new: &[TodoItem]) -> Vec<TodoItem> {
+    new.iter()
+        .filter(|task| {
+            task.status == TodoStatus::InProgress
+                && !old
+                    .iter()
+                    .any(|o| o.content == task.content && o.status == TodoStatus::InProgress)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Finds verification tasks with a check command that follow an action
+/// completed between `old` and `new`.
+pub(crate) fn due_checks(old: &[TodoItem], new: &[TodoItem]) -> Vec<DueCheck> {
+    let mut due = Vec::new();
+    let mut completed_action: Option<&TodoItem> = None;
+    for item in new {
+        match item.kind {
+            TodoKind::Action => {
+                completed_action = Some(item).filter(|action| {
+                    action.status == TodoStatus::Completed
+                        && !old.iter().any(|o| {
+                            o.content == action.content && o.status == TodoStatus::Completed
+                        })
+                });
+            }
+            TodoKind::Verification => {
+                if let (Some(action), Some(command)) = (completed_action, item.check.as_deref()) {
+                    due.push(DueCheck {
+                        action: action.content.clone(),
+                        verification: item.content.clone(),
+                        command: command.to_string(),
+                    });
+                }
+            }
+        }
+    }
+    due
+}
+
+/// Remaining run budget a todo list has to fit in, supplied by the agent loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlanBudget {
+    /// Agent loop iterations left before the run stops.
+    pub remaining_iterations: usize,
+    /// Tokens left of [`AgentConfig::token_budget`](crate::AgentConfig::token_budget),
+    /// if one is set.
+    pub remaining_tokens: Option<u64>,
+}
+
+impl PlanBudget {
+    /// Iterations a typical task takes: reading, acting and checking.
+    pub const ITERATIONS_PER_TASK: usize = 3;
+    /// Tokens a typical task takes, prompt included.
+    pub const TOKENS_PER_TASK: u64 = 25_000;
+
+    /// Number of unfinished required tasks that fit in the budget.
+    ///
+    /// Always at least one, so the agent can still finish what it started.
+    #[must_use]
+    pub fn max_tasks(&self) -> usize {
+        let by_iterations = self.remaining_iterations / Self::ITERATIONS_PER_TASK;
+        let by_tokens = self.remaining_tokens.map_or(usize::MAX, |tokens| {
+            usize::try_from(tokens / Self::TOKENS_PER_TASK).unwrap_or(usize::MAX)
+        });
+        by_iterations.min(by_tokens).max(1)
+    }
+
+    /// Returns `true` if the budget limits how large a plan can be.
+    #[must_use]
+    pub fn is_tight(&self) -> bool {
+        self.max_tasks() < 20
+    }
+
+    /// Planning instructions shown to the model alongside the todo list.
+    #[must_use]
+    pub fn guidance(&self) -> String {
+        let tokens = self
+            .remaining_tokens
+            .map(|tokens| format!(" and about {tokens} tokens"))
+            .unwrap_or_default();
+        format!(
+            "Budget: {} iterations{tokens} left. Keep the todo list to at most {} unfinished required tasks and mark anything beyond that as `stretch`.",
+            self.remaining_iterations,
+            self.max_tasks()
+        )
+    }
+
+    /// Rejects todo lists whose unfinished required tasks don't fit.
+    pub(crate) fn check(&self, todos: &[TodoItem]) -> aither_core::Result<()> {
+        let required = todos
+            .iter()
+            .filter(|t| !t.stretch && t.status != TodoStatus::Completed)
+            .count();
+        let max = self.max_tasks();
+        if required > max {
+            return Err(anyhow::anyhow!(
+                "The todo list has {required} unfinished required tasks but the remaining budget fits about {max}. Merge tasks or mark the rest as `stretch`."
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Shared todo list state.
 #[derive(Debug, Clone, Default)]
 pub struct TodoList {
     items: Arc<RwLock<Vec<TodoItem>>>,
+    budget: Arc<RwLock<Option<PlanBudget>>>,
 }
 
 impl TodoList {
@@ -108,6 +348,47 @@ impl TodoList {
         *self.items.write().unwrap() = items;
     }
 
+    /// Returns the budget new todo lists must fit in, if the agent set one.
+    #[must_use]
+    pub fn budget(&self) -> Option<PlanBudget> {
+        *self.budget.read().unwrap()
+    }
+
+    /// Sets the budget new todo lists must fit in.
+    pub fn set_budget(&self, budget: PlanBudget) {
+        *self.budget.write().unwrap() = Some(budget);
+    }
+
+    /// Marks the task with this content completed.
+    pub(crate) fn complete(&self, content: &str) {
+        let mut items = self.items.write().unwrap();
+        for item in items.iter_mut().filter(|item| item.content == content) {
+            item.status = TodoStatus::Completed;
+        }
+    }
+
+    /// Applies the outcome of a verification check.
+    ///
+    /// A passing check completes the verification task; a failing one puts
+    /// both it and its action back to pending.
+    pub(crate) fn record_check(&self, check: &DueCheck, passed: bool) {
+        let mut items = self.items.write().unwrap();
+        for item in items.iter_mut() {
+            let is_verification =
+                item.kind == TodoKind::Verification && item.content == check.verification;
+            let is_action = item.kind == TodoKind::Action && item.content == check.action;
+            if is_verification {
+                item.status = if passed {
+                    TodoStatus::Completed
+                } else {
+                    TodoStatus::Pending
+                };
+            } else if is_action && !passed {
+                item.status = TodoStatus::Pending;
+            }
+        }
+    }
+
     /// Clears all tasks.
     pub fn clear(&self) {
         self.items.write().unwrap().clear();
@@ -170,6 +451,16 @@ impl TodoList {
 /// Task states: pending, `in_progress`, completed.
 /// Keep exactly one task `in_progress` at a time.
 /// Mark tasks complete immediately when done.
+///
+/// Size the list to the remaining budget shown in context. Mark optional
+/// tasks `stretch` so they are only attempted once required work is done.
+///
+/// To verify a task, follow it with a `verification` task whose `check` is a
+/// bash command that succeeds when the work is correct. The check runs
+/// automatically when the task is marked completed, and a failure reopens it.
+///
+/// Set `step` (`code_edit`, `shell`, `research`) when the system prompt lists
+/// an executor for that kind of work.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TodoWriteArgs {
     /// The complete updated todo list. This replaces any existing todos.
@@ -234,6 +525,10 @@ impl Tool for TodoTool {
             ));
         }
 
+        if let Some(budget) = self.list.budget() {
+            budget.check(&arguments.todos)?;
+        }
+
         self.list.write(arguments.todos);
 
         // TodoWrite succeeds with no output - the UI shows the todo list separately
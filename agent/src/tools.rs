@@ -2,9 +2,11 @@
 //!
 //! All registered tools are always loaded into the LLM context.
 
-use aither_core::llm::tool::{Tool, ToolDefinition, ToolOutput, Tools as CoreTools};
+use aither_core::llm::tool::{
+    Mime, Tool, ToolContent, ToolDefinition, ToolOutput, Tools as CoreTools,
+};
 #[cfg(feature = "mcp")]
-use aither_mcp::{McpConnection, McpToolService};
+use aither_mcp::{McpConfigEvent, McpConfigWatcher, McpConnection, McpToolService};
 
 /// Tools registry used by the agent.
 ///
@@ -15,7 +17,23 @@ pub struct AgentTools {
 
     /// MCP connections (when mcp feature is enabled).
     #[cfg(feature = "mcp")]
-    mcp: Vec<McpToolService>,
+    mcp: Vec<McpEntry>,
+
+    /// Watched MCP config file, if any.
+    #[cfg(feature = "mcp")]
+    mcp_watcher: Option<McpConfigWatcher>,
+
+    /// Client-side web search, registered only if the model can't search natively.
+    websearch_fallback: Option<Box<dyn FnOnce(&mut CoreTools) + Send + Sync>>,
+}
+
+/// A registered MCP server.
+#[cfg(feature = "mcp")]
+#[derive(Debug)]
+struct McpEntry {
+    /// Key in the watched config file, for servers added by the watcher.
+    config_name: Option<String>,
+    service: McpToolService,
 }
 
 impl Default for AgentTools {
@@ -30,6 +48,9 @@ impl std::fmt::Debug for AgentTools {
         s.field("eager", &self.eager);
         #[cfg(feature = "mcp")]
         s.field("mcp", &self.mcp);
+        #[cfg(feature = "mcp")]
+        s.field("mcp_watcher", &self.mcp_watcher);
+        s.field("websearch_fallback", &self.websearch_fallback.is_some());
         s.finish()
     }
 }
@@ -42,6 +63,9 @@ impl AgentTools {
             eager: CoreTools::new(),
             #[cfg(feature = "mcp")]
             mcp: Vec::new(),
+            #[cfg(feature = "mcp")]
+            mcp_watcher: None,
+            websearch_fallback: None,
         }
     }
 
@@ -50,6 +74,28 @@ impl AgentTools {
         self.eager.register(tool);
     }
 
+    /// Sets a web search tool to fall back on when the model has no native search.
+    ///
+    /// The tool is registered by [`resolve_websearch`](Self::resolve_websearch).
+    pub fn websearch_fallback<T: Tool + 'static>(&mut self, tool: T) {
+        self.websearch_fallback = Some(Box::new(move |tools: &mut CoreTools| tools.register(tool)));
+    }
+
+    /// Decides between native and client-side web search.
+    ///
+    /// Registers the fallback tool unless the model searches `natively`.
+    /// Returns `true` if native search should be requested, which is only the
+    /// case when web search was enabled.
+    pub fn resolve_websearch(&mut self, natively: bool) -> bool {
+        let Some(register) = self.websearch_fallback.take() else {
+            return false;
+        };
+        if !natively {
+            register(&mut self.eager);
+        }
+        natively
+    }
+
     /// Registers a dynamic bash tool (type-erased).
     ///
     /// This is used for child bash tools in subagents where the concrete type
@@ -82,8 +128,8 @@ impl AgentTools {
         #[cfg(feature = "mcp")]
         {
             let mut defs = self.definitions();
-            for conn in &self.mcp {
-                defs.extend(conn.definitions());
+            for entry in &self.mcp {
+                defs.extend(entry.service.definitions());
             }
             defs
         }
@@ -96,18 +142,36 @@ impl AgentTools {
 
     /// Calls a tool by name with JSON arguments.
     ///
-    /// Searches eager tools first, then MCP tools.
+    /// Searches eager tools first, then MCP tools. Multi-part MCP results are
+    /// flattened to text; use [`call_parts`](Self::call_parts) to keep images.
     ///
     /// # Errors
     ///
     /// Returns an error if the tool is not found or execution fails.
     pub async fn call(&self, name: &str, args: &str) -> aither_core::Result<ToolOutput> {
+        let parts = self.call_parts(name, args).await?;
+        if let [output] = parts.as_slice() {
+            return Ok(output.clone());
+        }
+        Ok(ToolOutput::text(split_tool_outputs(parts).0))
+    }
+
+    /// Calls a tool and returns every part of its output.
+    ///
+    /// Eager tools always produce a single part; MCP tools produce one part
+    /// per content item, so screenshots and other images survive as binary
+    /// outputs with their MIME type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tool is not found or execution fails.
+    pub async fn call_parts(&self, name: &str, args: &str) -> aither_core::Result<Vec<ToolOutput>> {
         if self.eager.definitions().iter().any(|d| d.name() == name) {
-            return self.eager.call(name, args).await;
+            return Ok(vec![self.eager.call(name, args).await?]);
         }
 
         #[cfg(feature = "mcp")]
-        for conn in &self.mcp {
+        for conn in self.mcp.iter().map(|entry| &entry.service) {
             if conn.has_tool(name) {
                 let args_value: serde_json::Value =
                     serde_json::from_str(args).map_err(|e| anyhow::anyhow!("Invalid JSON: {e}"))?;
@@ -117,20 +181,10 @@ impl AgentTools {
                     .await
                     .map_err(|e| anyhow::anyhow!("MCP tool error: {e}"))?;
 
-                let output = result
-                    .content
-                    .into_iter()
-                    .filter_map(|c| match c {
-                        aither_mcp::Content::Text(t) => Some(t.text),
-                        _ => None,
-                    })
-                    .collect::<Vec<_>>()
-                    .join("\n");
-
                 return if result.is_error {
-                    Err(anyhow::anyhow!("{output}"))
+                    Err(anyhow::anyhow!("{}", result.text_content()))
                 } else {
-                    Ok(ToolOutput::text(output))
+                    Ok(result.into_tool_outputs())
                 };
             }
         }
@@ -154,7 +208,69 @@ impl AgentTools {
     /// All tools from the MCP server will be available for the agent to use.
     #[cfg(feature = "mcp")]
     pub fn register_mcp(&mut self, conn: McpConnection) {
-        self.mcp.push(McpToolService::new(conn));
+        self.mcp.push(McpEntry {
+            config_name: None,
+            service: McpToolService::new(conn),
+        });
+    }
+
+    /// Watches an MCP config file and keeps the registered servers in sync
+    /// with it.
+    ///
+    /// Changes are applied by [`sync_mcp_config`](Self::sync_mcp_config),
+    /// which the agent calls before every model request.
+    #[cfg(feature = "mcp")]
+    pub fn watch_mcp_config(&mut self, path: impl Into<std::path::PathBuf>) {
+        self.mcp_watcher = Some(McpConnection::watch_config(path));
+    }
+
+    /// Applies pending changes from the watched MCP config file.
+    ///
+    /// Returns a one-line description of each change, empty if nothing changed.
+    #[cfg(feature = "mcp")]
+    pub fn sync_mcp_config(&mut self) -> Vec<String> {
+        let mut changes = Vec::new();
+        while let Some(event) = self
+            .mcp_watcher
+            .as_ref()
+            .and_then(McpConfigWatcher::try_next)
+        {
+            match event {
+                McpConfigEvent::Connected { name, connection }
+                | McpConfigEvent::Reconfigured { name, connection } => {
+                    let service = McpToolService::new(connection);
+                    let tools = service.mcp_definitions().len();
+                    changes.push(format!("MCP server '{name}' connected ({tools} tools)"));
+                    self.remove_configured_mcp(&name);
+                    self.mcp.push(McpEntry {
+                        config_name: Some(name),
+                        service,
+                    });
+                }
+                McpConfigEvent::Removed { name } => {
+                    if self.remove_configured_mcp(&name) {
+                        changes.push(format!("MCP server '{name}' removed"));
+                    }
+                }
+                McpConfigEvent::Failed { name, error } => {
+                    changes.push(format!("MCP server '{name}' failed to connect: {error}"));
+                }
+                McpConfigEvent::InvalidConfig(error) => {
+                    tracing::warn!("Ignoring invalid MCP config: {error}");
+                }
+                _ => {}
+            }
+        }
+        changes
+    }
+
+    /// Removes the server registered under a config-file key.
+    #[cfg(feature = "mcp")]
+    fn remove_configured_mcp(&mut self, name: &str) -> bool {
+        let before = self.mcp.len();
+        self.mcp
+            .retain(|entry| entry.config_name.as_deref() != Some(name));
+        self.mcp.len() != before
     }
 
     /// Returns the number of registered MCP connections.
@@ -164,13 +280,35 @@ impl AgentTools {
         self.mcp.len()
     }
 
+    /// Returns the server name and state of every MCP connection that is
+    /// currently down. Their tools fail immediately until they reconnect.
+    #[cfg(feature = "mcp")]
+    #[must_use]
+    pub fn mcp_unavailable(&self) -> Vec<(String, aither_mcp::ConnectionState)> {
+        self.mcp
+            .iter()
+            .filter_map(|entry| {
+                let state = entry.service.state();
+                (!state.is_connected()).then(|| {
+                    let name = entry
+                        .config_name
+                        .as_deref()
+                        .or_else(|| entry.service.server_name())
+                        .unwrap_or("unnamed")
+                        .to_string();
+                    (name, state)
+                })
+            })
+            .collect()
+    }
+
     /// Returns definitions from all MCP connections.
     #[cfg(feature = "mcp")]
     #[must_use]
     pub fn mcp_definitions(&self) -> Vec<ToolDefinition> {
         self.mcp
             .iter()
-            .flat_map(aither_mcp::McpToolService::definitions)
+            .flat_map(|entry| entry.service.definitions())
             .collect()
     }
 
@@ -182,6 +320,53 @@ impl AgentTools {
     }
 }
 
+/// Splits tool output parts into text for the tool message and data URLs
+/// for images, which are sent to the model as attachments.
+///
+/// Each image leaves a placeholder in the text so the model can tell which
+/// attachment came from where; content blocks keep their order, and other
+/// blocks and non-UTF-8 binary parts are rendered as text.
+#[must_use]
+pub(crate) fn split_tool_outputs(parts: Vec<ToolOutput>) -> (String, Vec<url::Url>) {
+    let mut text = Vec::new();
+    let mut attachments = Vec::new();
+    for part in parts {
+        match part {
+            ToolOutput::Done => {}
+            ToolOutput::Output { mime, content } => {
+                if mime.type_().as_str() == "image"
+                    && let Some(url) = image_data_url(&mime, &content)
+                {
+                    text.push(format!("[image attached: {}]", mime.essence_str()));
+                    attachments.push(url);
+                    continue;
+                }
+                text.push(ToolOutput::Output { mime, content }.to_text());
+            }
+            ToolOutput::Blocks(blocks) => {
+                for block in blocks {
+                    if let ToolContent::Image { mime, data } = &block
+                        && let Some(url) = image_data_url(mime, data)
+                    {
+                        text.push(format!("[image attached: {}]", mime.essence_str()));
+                        attachments.push(url);
+                        continue;
+                    }
+                    text.push(block.to_text());
+                }
+            }
+        }
+    }
+    (text.join("\n"), attachments)
+}
+
+fn image_data_url(mime: &Mime, data: &[u8]) -> Option<url::Url> {
+    use base64::Engine as _;
+
+    let data = base64::engine::general_purpose::STANDARD.encode(data);
+    url::Url::parse(&format!("data:{};base64,{data}", mime.essence_str())).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,4 +402,35 @@ mod tests {
 
         assert_eq!(tools.definitions().len(), 1);
     }
+
+    #[test]
+    fn test_split_tool_outputs_attaches_images() {
+        let (text, attachments) = split_tool_outputs(vec![
+            ToolOutput::text("Screenshot taken"),
+            ToolOutput::image(vec![0x89, b'P', b'N', b'G'], "image/png"),
+            ToolOutput::Done,
+        ]);
+
+        assert_eq!(text, "Screenshot taken\n[image attached: image/png]");
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].as_str(), "data:image/png;base64,iVBORw==");
+    }
+
+    #[test]
+    fn test_split_tool_outputs_keeps_block_order() {
+        let (text, attachments) = split_tool_outputs(vec![ToolOutput::blocks([
+            ToolContent::image(vec![0x89, b'P', b'N', b'G'], "image/png"),
+            ToolContent::text("Sign in"),
+            ToolContent::json(&serde_json::json!({ "width": 1280 })).unwrap(),
+            ToolContent::file("outputs/page.html").with_mime("text/html"),
+            ToolContent::error("favicon not found"),
+        ])]);
+
+        assert_eq!(
+            text,
+            "[image attached: image/png]\nSign in\n{\"width\":1280}\n\
+             [file: outputs/page.html (text/html)]\nError: favicon not found"
+        );
+        assert_eq!(attachments.len(), 1);
+    }
 }
@@ -0,0 +1,269 @@
+//! Packing directories into a single attachment.
+//!
+//! [`DirectoryArchive`] walks a directory, skips hidden entries and anything
+//! matched by `.gitignore`/`.aitherignore`, and writes the rest into a tar
+//! archive. Headers carry no timestamps or ownership, so packing unchanged
+//! content yields a byte-identical archive and the cached upload is reused.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aither_fs::{IGNORE_FILES, IgnoreRules};
+use futures_lite::StreamExt;
+use sha2::{Digest, Sha256};
+
+/// Default cap on the total size of packed files.
+pub const DEFAULT_MAX_ARCHIVE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Default cap on the number of packed files.
+pub const DEFAULT_MAX_ARCHIVE_FILES: usize = 10_000;
+
+/// Settings for packing a directory into a tar archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectoryArchive {
+    max_bytes: u64,
+    max_files: usize,
+    max_file_bytes: Option<u64>,
+    include_hidden: bool,
+    include_ignored: bool,
+}
+
+impl Default for DirectoryArchive {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_ARCHIVE_BYTES,
+            max_files: DEFAULT_MAX_ARCHIVE_FILES,
+            max_file_bytes: None,
+            include_hidden: false,
+            include_ignored: false,
+        }
+    }
+}
+
+/// A directory packed by [`DirectoryArchive::pack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedDirectory {
+    /// Path of the written tar archive.
+    pub path: PathBuf,
+    /// Number of files in the archive.
+    pub files: usize,
+    /// Total size of the packed files, before tar framing.
+    pub bytes: u64,
+    /// Files left out for exceeding the per-file size cap, relative to the directory.
+    pub skipped: Vec<PathBuf>,
+}
+
+impl DirectoryArchive {
+    /// Create packing settings with the default caps.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the cap on the total size of packed files.
+    #[must_use]
+    pub const fn max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Set the cap on the number of packed files.
+    #[must_use]
+    pub const fn max_files(mut self, files: usize) -> Self {
+        self.max_files = files;
+        self
+    }
+
+    /// Leave out individual files larger than `bytes` instead of failing.
+    #[must_use]
+    pub const fn max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = Some(bytes);
+        self
+    }
+
+    /// Include hidden files and directories (default false).
+    ///
+    /// `.git` is never included.
+    #[must_use]
+    pub const fn include_hidden(mut self, include: bool) -> Self {
+        self.include_hidden = include;
+        self
+    }
+
+    /// Include files matched by ignore files (default false).
+    #[must_use]
+    pub const fn include_ignored(mut self, include: bool) -> Self {
+        self.include_ignored = include;
+        self
+    }
+
+    /// Pack `dir` into a tar archive inside `out_dir`.
+    ///
+    /// The archive name is derived from the directory path, so packing the
+    /// same directory again replaces the previous archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `dir` cannot be read, the archive cannot be
+    /// written, or the directory exceeds the file count or total size caps.
+    pub async fn pack(&self, dir: &Path, out_dir: &Path) -> io::Result<PackedDirectory> {
+        let dir = async_fs::canonicalize(dir).await?;
+        let files = self.collect(&dir).await?;
+
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut packed = PackedDirectory {
+            path: out_dir.join(archive_name(&dir)),
+            files: 0,
+            bytes: 0,
+            skipped: Vec::new(),
+        };
+        for (relative, size) in files {
+            if self.max_file_bytes.is_some_and(|max| size > max) {
+                packed.skipped.push(relative);
+                continue;
+            }
+            packed.files += 1;
+            packed.bytes += size;
+            if packed.files > self.max_files {
+                return Err(too_large(format!(
+                    "{} has more than {} files",
+                    dir.display(),
+                    self.max_files
+                )));
+            }
+            if packed.bytes > self.max_bytes {
+                return Err(too_large(format!(
+                    "{} exceeds {} bytes",
+                    dir.display(),
+                    self.max_bytes
+                )));
+            }
+
+            let data = async_fs::read(dir.join(&relative)).await?;
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(0);
+            builder.append_data(&mut header, &relative, data.as_slice())?;
+        }
+
+        let archive = builder.into_inner()?;
+        async_fs::create_dir_all(out_dir).await?;
+        async_fs::write(&packed.path, archive).await?;
+        Ok(packed)
+    }
+
+    /// List the files to pack as (path relative to `dir`, size), sorted by path.
+    async fn collect(&self, dir: &Path) -> io::Result<Vec<(PathBuf, u64)>> {
+        let mut rules = IgnoreRules::default();
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            if !self.include_ignored {
+                for name in IGNORE_FILES {
+                    if let Ok(contents) = async_fs::read_to_string(current.join(name)).await {
+                        rules.extend(&current, &contents);
+                    }
+                }
+            }
+
+            let mut entries = async_fs::read_dir(&current).await?;
+            while let Some(entry) = entries.next().await {
+                let entry = entry?;
+                let name = entry.file_name();
+                let hidden = name.to_string_lossy().starts_with('.');
+                if name == ".git" || (hidden && !self.include_hidden) {
+                    continue;
+                }
+                let path = entry.path();
+                // Symlinks are skipped so the archive can't reach outside `dir`.
+                let file_type = entry.file_type().await?;
+                if !self.include_ignored && rules.is_ignored(&path, file_type.is_dir()) {
+                    continue;
+                }
+                if file_type.is_dir() {
+                    pending.push(path);
+                } else if file_type.is_file() {
+                    let size = entry.metadata().await?.len();
+                    let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+                    files.push((relative, size));
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Archive file name for `dir`: its name plus a short hash of the full path.
+fn archive_name(dir: &Path) -> String {
+    let digest = format!("{:x}", Sha256::digest(dir.to_string_lossy().as_bytes()));
+    let name = dir
+        .file_name()
+        .map_or_else(|| "root".into(), |name| name.to_string_lossy());
+    format!("{name}-{}.tar", &digest[..16]).replace(char::is_whitespace, "_")
+}
+
+fn too_large(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_respects_ignore_rules_and_is_stable() {
+        tokio_test::block_on(async {
+            let dir = tempfile::tempdir().expect("create temp dir");
+            let project = dir.path().join("project");
+            async_fs::create_dir_all(project.join("src"))
+                .await
+                .expect("create src");
+            async_fs::create_dir_all(project.join("target"))
+                .await
+                .expect("create target");
+            async_fs::write(project.join(".gitignore"), "target/\n*.log\n")
+                .await
+                .expect("write gitignore");
+            async_fs::write(project.join("src/main.rs"), "fn main() {}")
+                .await
+                .expect("write main");
+            async_fs::write(project.join("debug.log"), "noise")
+                .await
+                .expect("write log");
+            async_fs::write(project.join("target/out.bin"), "binary")
+                .await
+                .expect("write target");
+            async_fs::write(project.join("big.txt"), "x".repeat(100))
+                .await
+                .expect("write big");
+
+            let out = dir.path().join("archives");
+            let archive = DirectoryArchive::new().max_file_bytes(50);
+            let packed = archive.pack(&project, &out).await.expect("pack");
+            assert_eq!(packed.files, 1);
+            assert_eq!(packed.skipped, [PathBuf::from("big.txt")]);
+
+            let bytes = async_fs::read(&packed.path).await.expect("read archive");
+            let mut reader = tar::Archive::new(bytes.as_slice());
+            let names: Vec<PathBuf> = reader
+                .entries()
+                .expect("entries")
+                .map(|entry| entry.expect("entry").path().expect("path").into_owned())
+                .collect();
+            assert_eq!(names, [PathBuf::from("src/main.rs")]);
+
+            let repacked = archive.pack(&project, &out).await.expect("repack");
+            assert_eq!(repacked.path, packed.path);
+            let rebytes = async_fs::read(&repacked.path).await.expect("read archive");
+            assert_eq!(bytes, rebytes);
+
+            let capped = DirectoryArchive::new()
+                .max_bytes(10)
+                .pack(&project, &out)
+                .await;
+            assert!(capped.is_err());
+        });
+    }
+}
@@ -1,16 +1,39 @@
 //! Attachment cache for provider file uploads.
+//!
+//! [`FileCache`] remembers which provider reference belongs to which local
+//! file; [`AttachmentManager`] builds on it to upload files through a
+//! provider's [`AttachmentUploader`] only when needed, and
+//! [`AttachmentPolicy`] decides whether a file should be uploaded at all or
+//! sent inline. Directories are packed with [`DirectoryArchive`] and
+//! uploaded as a single archive.
 
-use std::collections::HashMap;
+mod archive;
+mod manager;
+mod policy;
+
+pub use aither_models::AttachmentLimits;
+pub use archive::{
+    DEFAULT_MAX_ARCHIVE_BYTES, DEFAULT_MAX_ARCHIVE_FILES, DirectoryArchive, PackedDirectory,
+};
+pub use manager::{AttachmentManager, AttachmentUploader, UploadedFile};
+pub use policy::{AttachmentDelivery, AttachmentPolicy, DEFAULT_INLINE_THRESHOLD};
+
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 use async_fs::File;
+use async_lock::Mutex;
 use futures_lite::io::AsyncReadExt;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 const CACHE_FILE_NAME: &str = "file_cache.json";
 
+/// Serializes cache writes within the process so concurrent agents sharing
+/// a cache directory don't interleave writes to the same file.
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
 /// Returns the default cache directory for attachments.
 #[must_use]
 pub fn default_cache_dir() -> PathBuf {
@@ -32,12 +55,15 @@ pub struct CacheEntry {
     /// When this reference expires (None if it never expires).
     #[serde(with = "system_time_option")]
     pub expires_at: Option<SystemTime>,
+    /// When the file was uploaded (None for entries written before this was tracked).
+    #[serde(default, with = "system_time_option")]
+    pub uploaded_at: Option<SystemTime>,
 }
 
 impl CacheEntry {
-    /// Create a new cache entry.
+    /// Create a new cache entry uploaded now.
     #[must_use]
-    pub const fn new(
+    pub fn new(
         content_hash: String,
         provider: String,
         reference: String,
@@ -48,6 +74,7 @@ impl CacheEntry {
             provider,
             reference,
             expires_at,
+            uploaded_at: Some(SystemTime::now()),
         }
     }
 
@@ -60,6 +87,71 @@ impl CacheEntry {
         self.expires_at
             .is_none_or(|expires| SystemTime::now() < expires)
     }
+
+    /// Check if this entry was uploaded more than `max_age` ago.
+    ///
+    /// Entries without an upload time are treated as too old.
+    #[must_use]
+    pub fn is_older_than(&self, max_age: Duration) -> bool {
+        self.uploaded_at.is_none_or(|uploaded| {
+            SystemTime::now()
+                .duration_since(uploaded)
+                .is_ok_and(|age| age > max_age)
+        })
+    }
+
+    /// Check if this entry expires within `margin` from now (or already has).
+    #[must_use]
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        self.expires_at.is_some_and(|expires| {
+            SystemTime::now()
+                .checked_add(margin)
+                .is_none_or(|deadline| deadline >= expires)
+        })
+    }
+}
+
+/// Limits applied by [`FileCache::gc`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Maximum number of entries kept; the oldest uploads are evicted first.
+    pub max_entries: Option<usize>,
+    /// Maximum time since upload before an entry is evicted.
+    pub max_age: Option<Duration>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: Some(1000),
+            max_age: Some(Duration::from_secs(30 * 24 * 60 * 60)),
+        }
+    }
+}
+
+impl CachePolicy {
+    /// A policy that only removes expired entries and entries for deleted files.
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_entries: None,
+            max_age: None,
+        }
+    }
+
+    /// Set the maximum number of entries.
+    #[must_use]
+    pub const fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Set the maximum time since upload.
+    #[must_use]
+    pub const fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
 }
 
 /// File cache for tracking uploaded files across providers.
@@ -73,6 +165,9 @@ pub struct FileCache {
     cache_dir: PathBuf,
     /// Map of (`canonical_path`, provider) -> `CacheEntry`.
     entries: HashMap<String, CacheEntry>,
+    /// Keys removed since the cache was opened, so saving doesn't bring them back.
+    #[serde(skip)]
+    removed: HashSet<String>,
 }
 
 impl FileCache {
@@ -84,36 +179,64 @@ impl FileCache {
     ///
     /// Returns an error when the cache file cannot be read or parsed.
     pub async fn open(cache_dir: PathBuf) -> std::io::Result<Self> {
-        let cache_file = cache_dir.join(CACHE_FILE_NAME);
-        if async_fs::metadata(&cache_file).await.is_ok() {
-            let contents = async_fs::read_to_string(&cache_file).await?;
-            let cache: Self = serde_json::from_str(&contents)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-            return Ok(Self {
-                cache_dir,
-                entries: cache.entries,
-            });
-        }
+        let entries = read_entries(&cache_dir.join(CACHE_FILE_NAME))
+            .await?
+            .unwrap_or_default();
         Ok(Self {
             cache_dir,
-            entries: HashMap::new(),
+            entries,
+            removed: HashSet::new(),
         })
     }
 
     /// Save cache to disk.
     ///
+    /// Entries another cache instance saved in the meantime are kept unless
+    /// this instance removed or replaced them. The file is replaced
+    /// atomically, and saves within the process are serialized.
+    ///
     /// # Errors
     ///
     /// Returns an error when the cache directory cannot be created or file cannot be written.
     pub async fn save(&self) -> std::io::Result<()> {
+        let _guard = SAVE_LOCK.lock().await;
         async_fs::create_dir_all(&self.cache_dir).await?;
         let cache_file = self.cache_dir.join(CACHE_FILE_NAME);
-        let contents = serde_json::to_string_pretty(self)
+
+        // A corrupt file on disk is replaced rather than merged.
+        let mut entries = read_entries(&cache_file)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        entries.retain(|key, _| !self.removed.contains(key));
+        entries.extend(
+            self.entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.clone())),
+        );
+
+        let merged = Self {
+            cache_dir: self.cache_dir.clone(),
+            entries,
+            removed: HashSet::new(),
+        };
+        let contents = serde_json::to_string_pretty(&merged)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        async_fs::write(cache_file, contents).await?;
+        let tmp_file = self
+            .cache_dir
+            .join(format!("{CACHE_FILE_NAME}.{}.tmp", std::process::id()));
+        async_fs::write(&tmp_file, contents).await?;
+        async_fs::rename(&tmp_file, &cache_file).await?;
         Ok(())
     }
 
+    /// Directory the cache is persisted in.
+    #[must_use]
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
     /// Generate a cache key from file path and provider.
     fn cache_key(file_path: &Path, provider: &str) -> String {
         format!("{}::{}", file_path.display(), provider)
@@ -164,14 +287,40 @@ impl FileCache {
         let content_hash = hash_file(file_path).await?;
         let key = Self::cache_key(file_path, provider);
         let entry = CacheEntry::new(content_hash, provider.to_string(), reference, expires_at);
+        self.removed.remove(&key);
         self.entries.insert(key, entry);
         Ok(())
     }
 
+    /// Get the entry for a file without validating it against the file content.
+    #[must_use]
+    pub fn entry(&self, file_path: &Path, provider: &str) -> Option<&CacheEntry> {
+        self.entries.get(&Self::cache_key(file_path, provider))
+    }
+
+    /// Insert an entry whose content hash has already been computed.
+    pub fn insert_entry(&mut self, file_path: &Path, entry: CacheEntry) {
+        let key = Self::cache_key(file_path, &entry.provider);
+        self.removed.remove(&key);
+        self.entries.insert(key, entry);
+    }
+
     /// Remove a cache entry.
     pub fn remove(&mut self, file_path: &Path, provider: &str) -> Option<CacheEntry> {
         let key = Self::cache_key(file_path, provider);
-        self.entries.remove(&key)
+        self.take(&key)
+    }
+
+    /// Number of cached entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the cache has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
     }
 
     /// Remove all expired entries from the cache.
@@ -179,10 +328,60 @@ impl FileCache {
     /// Returns true if any entries were removed.
     pub fn prune_expired(&mut self) -> bool {
         let now = SystemTime::now();
-        let before = self.entries.len();
-        self.entries
-            .retain(|_, entry| entry.expires_at.is_none_or(|expires| now < expires));
-        before != self.entries.len()
+        let expired: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.expires_at.is_some_and(|expires| now >= expires))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            self.take(key);
+        }
+        !expired.is_empty()
+    }
+
+    /// Remove stale entries and return them.
+    ///
+    /// An entry is stale if it expired, its file no longer exists, or it is
+    /// older than [`CachePolicy::max_age`]. If more than
+    /// [`CachePolicy::max_entries`] remain, the oldest uploads are evicted.
+    /// Callers can use the returned entries to delete the remote files.
+    pub async fn gc(&mut self, policy: &CachePolicy) -> Vec<CacheEntry> {
+        let now = SystemTime::now();
+        let mut stale = Vec::new();
+        for (key, entry) in &self.entries {
+            let expired = entry.expires_at.is_some_and(|expires| now >= expires);
+            let too_old = policy.max_age.is_some_and(|age| entry.is_older_than(age));
+            if expired || too_old || !file_exists(key).await {
+                stale.push(key.clone());
+            }
+        }
+        let mut removed: Vec<CacheEntry> = stale.iter().filter_map(|key| self.take(key)).collect();
+
+        if let Some(max_entries) = policy.max_entries
+            && self.entries.len() > max_entries
+        {
+            let mut by_age: Vec<(String, Option<SystemTime>)> = self
+                .entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.uploaded_at))
+                .collect();
+            by_age.sort_by_key(|(_, uploaded_at)| *uploaded_at);
+            let excess = self.entries.len() - max_entries;
+            removed.extend(
+                by_age
+                    .iter()
+                    .take(excess)
+                    .filter_map(|(key, _)| self.take(key)),
+            );
+        }
+        removed
+    }
+
+    fn take(&mut self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.removed.insert(key.to_string());
+        Some(entry)
     }
 
     /// Calculate expiration time from a duration.
@@ -192,7 +391,36 @@ impl FileCache {
     }
 }
 
-async fn hash_file(path: &Path) -> std::io::Result<String> {
+/// Read cache entries from `cache_file`, or `None` if it doesn't exist.
+async fn read_entries(cache_file: &Path) -> std::io::Result<Option<HashMap<String, CacheEntry>>> {
+    let contents = match async_fs::read_to_string(cache_file).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let cache: FileCache = serde_json::from_str(&contents)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(Some(cache.entries))
+}
+
+/// Whether the file behind a cache key still exists.
+///
+/// Errors other than `NotFound` count as existing, so a transient failure
+/// doesn't evict entries.
+async fn file_exists(key: &str) -> bool {
+    let path = key.rsplit_once("::").map_or(key, |(path, _)| path);
+    !matches!(
+        async_fs::metadata(path).await,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound
+    )
+}
+
+/// SHA-256 hash of a file's content, as stored in [`CacheEntry::content_hash`].
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read.
+pub async fn hash_file(path: &Path) -> std::io::Result<String> {
     let mut file = File::open(path).await?;
     let mut hasher = Sha256::new();
     let mut buffer = [0u8; 8192];
@@ -267,4 +495,57 @@ mod tests {
             assert_eq!(entry.reference, "file-1");
         });
     }
+
+    #[test]
+    fn test_file_cache_gc() {
+        tokio_test::block_on(async {
+            let dir = tempfile::tempdir().expect("create temp dir");
+            let cache_dir = dir.path().join("cache");
+            let mut cache = FileCache::open(cache_dir.clone())
+                .await
+                .expect("open cache");
+
+            let mut paths = Vec::new();
+            for i in 0..3u64 {
+                let path = dir.path().join(format!("file-{i}.txt"));
+                async_fs::write(&path, b"hello").await.expect("write file");
+                let mut entry = CacheEntry::new(
+                    "hash".to_string(),
+                    "openai".to_string(),
+                    format!("file-{i}"),
+                    None,
+                );
+                entry.uploaded_at = SystemTime::now().checked_sub(Duration::from_secs(100 - i));
+                cache.insert_entry(&path, entry);
+                paths.push(path);
+            }
+            let mut stale = CacheEntry::new(
+                "hash".to_string(),
+                "openai".to_string(),
+                "file-old".to_string(),
+                None,
+            );
+            stale.uploaded_at = SystemTime::now().checked_sub(Duration::from_secs(1000));
+            cache.insert_entry(&dir.path().join("file-0.txt.bak"), stale);
+            async_fs::remove_file(&paths[2]).await.expect("remove file");
+            cache.save().await.expect("save");
+
+            let policy = CachePolicy::unlimited()
+                .max_entries(1)
+                .max_age(Duration::from_secs(500));
+            let mut removed: Vec<String> = cache
+                .gc(&policy)
+                .await
+                .into_iter()
+                .map(|entry| entry.reference)
+                .collect();
+            removed.sort();
+            assert_eq!(removed, ["file-0", "file-2", "file-old"]);
+            cache.save().await.expect("save");
+
+            let cache = FileCache::open(cache_dir).await.expect("reload");
+            assert_eq!(cache.len(), 1);
+            assert!(cache.entry(&paths[1], "openai").is_some());
+        });
+    }
 }
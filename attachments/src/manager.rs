@@ -0,0 +1,318 @@
+//! Provider-independent attachment uploads.
+//!
+//! Providers implement [`AttachmentUploader`] for their files API;
+//! [`AttachmentManager`] does the rest: it hashes the local file, reuses a
+//! cached upload when the content is unchanged and the reference has not
+//! expired, uploads again otherwise, and returns the URL to put in the
+//! message.
+//!
+//! ```ignore
+//! let mut manager = AttachmentManager::open(GeminiUploader::new(&cfg)).await?;
+//! let url = manager.resolve(Path::new("report.pdf")).await?;
+//! manager.save().await?;
+//! ```
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use url::Url;
+
+use crate::{
+    CacheEntry, CachePolicy, DirectoryArchive, FileCache, PackedDirectory, default_cache_dir,
+    hash_file,
+};
+
+/// Default margin before expiry at which a cached upload is refreshed.
+const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// A file stored by a provider's files API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedFile {
+    /// Provider-specific reference (file URI or `file_id`).
+    pub reference: String,
+    /// When the provider deletes the file (None if it never expires).
+    pub expires_at: Option<SystemTime>,
+}
+
+impl UploadedFile {
+    /// Create an uploaded file record.
+    #[must_use]
+    pub fn new(reference: impl Into<String>, expires_at: Option<SystemTime>) -> Self {
+        Self {
+            reference: reference.into(),
+            expires_at,
+        }
+    }
+}
+
+/// Provider adapter used by [`AttachmentManager`].
+pub trait AttachmentUploader: Send + Sync {
+    /// Error returned by uploads; cache I/O errors are converted into it.
+    type Error: From<std::io::Error> + Send;
+
+    /// Provider name used to key the cache (e.g., "gemini", "openai").
+    fn provider(&self) -> &str;
+
+    /// Upload the file at `path` and wait until the provider can use it.
+    fn upload<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Future<Output = Result<UploadedFile, Self::Error>> + Send + 'a;
+
+    /// Build the message attachment URL for an uploaded `reference` of `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the reference cannot be turned into a URL.
+    fn to_url(&self, path: &Path, reference: &str) -> Result<Url, Self::Error>;
+
+    /// Delete an uploaded file evicted from the cache.
+    ///
+    /// The default keeps the file and lets the provider expire it.
+    fn delete<'a>(
+        &'a self,
+        _reference: &'a str,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'a {
+        std::future::ready(Ok(()))
+    }
+}
+
+/// Uploads local attachments once and reuses the provider reference.
+#[derive(Debug)]
+pub struct AttachmentManager<U> {
+    uploader: U,
+    cache: FileCache,
+    dirty: bool,
+    refresh_margin: Duration,
+}
+
+impl<U: AttachmentUploader> AttachmentManager<U> {
+    /// Open a manager backed by the cache in [`default_cache_dir`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the cache file cannot be read or parsed.
+    pub async fn open(uploader: U) -> Result<Self, U::Error> {
+        Self::open_in(uploader, default_cache_dir()).await
+    }
+
+    /// Open a manager backed by the cache in `cache_dir`.
+    ///
+    /// Expired entries are dropped on open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the cache file cannot be read or parsed.
+    pub async fn open_in(uploader: U, cache_dir: PathBuf) -> Result<Self, U::Error> {
+        let cache = FileCache::open(cache_dir).await?;
+        Ok(Self::with_cache(uploader, cache))
+    }
+
+    /// Create a manager around an already opened cache.
+    #[must_use]
+    pub fn with_cache(uploader: U, mut cache: FileCache) -> Self {
+        let dirty = cache.prune_expired();
+        Self {
+            uploader,
+            cache,
+            dirty,
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
+        }
+    }
+
+    /// Re-upload cached files that expire within `margin`.
+    ///
+    /// Defaults to five minutes, so a reference does not expire while the
+    /// request using it is still in flight.
+    #[must_use]
+    pub const fn refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// The provider adapter.
+    #[must_use]
+    pub const fn uploader(&self) -> &U {
+        &self.uploader
+    }
+
+    /// The underlying cache.
+    #[must_use]
+    pub const fn cache(&self) -> &FileCache {
+        &self.cache
+    }
+
+    /// Returns `true` if the cache has changes that are not saved yet.
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Return a ready-to-use attachment URL for the local file at `path`.
+    ///
+    /// The file is uploaded only if it has no cached reference, its content
+    /// changed since the last upload, or the reference is about to expire.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file cannot be read, the upload fails, or
+    /// the reference cannot be turned into a URL.
+    pub async fn resolve(&mut self, path: &Path) -> Result<Url, U::Error> {
+        let provider = self.uploader.provider().to_string();
+        let content_hash = hash_file(path).await?;
+
+        if let Some(entry) = self.cache.entry(path, &provider)
+            && entry.content_hash == content_hash
+            && !entry.expires_within(self.refresh_margin)
+        {
+            return self.uploader.to_url(path, &entry.reference);
+        }
+
+        let uploaded = self.uploader.upload(path).await?;
+        let url = self.uploader.to_url(path, &uploaded.reference)?;
+        self.cache.insert_entry(
+            path,
+            CacheEntry::new(
+                content_hash,
+                provider,
+                uploaded.reference,
+                uploaded.expires_at,
+            ),
+        );
+        self.dirty = true;
+        Ok(url)
+    }
+
+    /// Pack the directory at `dir` and return an attachment URL for the archive.
+    ///
+    /// Archives are written to an `archives` directory next to the cache.
+    /// Packing is deterministic, so an unchanged directory reuses its upload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when packing fails (including exceeding the archive
+    /// caps), the upload fails, or the reference cannot be turned into a URL.
+    pub async fn resolve_dir(
+        &mut self,
+        dir: &Path,
+        archive: &DirectoryArchive,
+    ) -> Result<(Url, PackedDirectory), U::Error> {
+        let out_dir = self.cache.cache_dir().join("archives");
+        let packed = archive.pack(dir, &out_dir).await?;
+        let url = self.resolve(&packed.path).await?;
+        Ok((url, packed))
+    }
+
+    /// Evict stale cache entries and delete this provider's evicted uploads.
+    ///
+    /// Deletion failures are ignored; the provider expires those files on
+    /// its own. Returns the number of evicted entries.
+    pub async fn gc(&mut self, policy: &CachePolicy) -> usize {
+        let removed = self.cache.gc(policy).await;
+        for entry in &removed {
+            if entry.provider == self.uploader.provider() {
+                let _ = self.uploader.delete(&entry.reference).await;
+            }
+        }
+        self.dirty |= !removed.is_empty();
+        removed.len()
+    }
+
+    /// Persist the cache if anything changed since it was opened or last saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the cache file cannot be written.
+    pub async fn save(&mut self) -> Result<(), U::Error> {
+        if self.dirty {
+            self.cache.save().await?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    struct CountingUploader {
+        uploads: AtomicUsize,
+        expires_in: Option<Duration>,
+    }
+
+    impl CountingUploader {
+        fn new(expires_in: Option<Duration>) -> Self {
+            Self {
+                uploads: AtomicUsize::new(0),
+                expires_in,
+            }
+        }
+
+        fn uploads(&self) -> usize {
+            self.uploads.load(Ordering::SeqCst)
+        }
+    }
+
+    impl AttachmentUploader for CountingUploader {
+        type Error = std::io::Error;
+
+        fn provider(&self) -> &'static str {
+            "test"
+        }
+
+        async fn upload<'a>(&'a self, _path: &'a Path) -> std::io::Result<UploadedFile> {
+            let n = self.uploads.fetch_add(1, Ordering::SeqCst) + 1;
+            let expires_at = self.expires_in.and_then(FileCache::expires_in);
+            Ok(UploadedFile::new(format!("file-{n}"), expires_at))
+        }
+
+        fn to_url(&self, _path: &Path, reference: &str) -> std::io::Result<Url> {
+            Url::parse(&format!("test://files/{reference}"))
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        }
+    }
+
+    #[test]
+    fn test_manager_reuses_and_refreshes_uploads() {
+        tokio_test::block_on(async {
+            let dir = tempfile::tempdir().expect("create temp dir");
+            let cache_dir = dir.path().join("cache");
+            let file_path = dir.path().join("file.txt");
+            async_fs::write(&file_path, b"hello")
+                .await
+                .expect("write file");
+
+            let mut manager = AttachmentManager::open_in(CountingUploader::new(None), cache_dir)
+                .await
+                .expect("open manager");
+            let first = manager.resolve(&file_path).await.expect("resolve");
+            let second = manager.resolve(&file_path).await.expect("resolve again");
+            assert_eq!(first.as_str(), "test://files/file-1");
+            assert_eq!(first, second);
+            assert_eq!(manager.uploader().uploads(), 1);
+
+            async_fs::write(&file_path, b"changed")
+                .await
+                .expect("rewrite file");
+            let changed = manager.resolve(&file_path).await.expect("resolve changed");
+            assert_eq!(changed.as_str(), "test://files/file-2");
+            assert!(manager.is_dirty());
+            manager.save().await.expect("save");
+            assert!(!manager.is_dirty());
+
+            let mut expiring = AttachmentManager::open_in(
+                CountingUploader::new(Some(Duration::from_secs(60))),
+                dir.path().join("expiring"),
+            )
+            .await
+            .expect("open manager");
+            expiring.resolve(&file_path).await.expect("resolve");
+            expiring.resolve(&file_path).await.expect("resolve again");
+            assert_eq!(expiring.uploader().uploads(), 2);
+        });
+    }
+}
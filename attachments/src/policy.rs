@@ -0,0 +1,141 @@
+//! Choosing between inline content and provider uploads.
+//!
+//! Small files are cheapest to send inline as base64, which skips the upload
+//! round trip. Large files must go through the provider's files API, and
+//! files too large for either path are rejected before any request is made.
+//! [`AttachmentPolicy`] makes that call from a size threshold and the limits
+//! documented in [`aither_models`].
+
+use std::path::Path;
+
+use aither_models::AttachmentLimits;
+
+/// Files up to this size are inlined by default.
+pub const DEFAULT_INLINE_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+/// How an attachment should reach the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentDelivery {
+    /// Embed the content in the request as base64.
+    Inline,
+    /// Upload through the provider's files API and send the reference.
+    Upload,
+    /// The file exceeds every limit the provider supports.
+    TooLarge,
+}
+
+/// Size-based decision between inlining and uploading attachments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttachmentPolicy {
+    inline_threshold: u64,
+    limits: AttachmentLimits,
+}
+
+impl Default for AttachmentPolicy {
+    fn default() -> Self {
+        Self::from_limits(AttachmentLimits {
+            max_inline_bytes: None,
+            max_upload_bytes: None,
+        })
+    }
+}
+
+impl AttachmentPolicy {
+    /// Create a policy for explicit limits.
+    #[must_use]
+    pub const fn from_limits(limits: AttachmentLimits) -> Self {
+        Self {
+            inline_threshold: DEFAULT_INLINE_THRESHOLD,
+            limits,
+        }
+    }
+
+    /// Create a policy from the registry limits of `model_id`.
+    ///
+    /// Returns `None` if the model is not in the registry.
+    #[must_use]
+    pub fn for_model(model_id: &str) -> Option<Self> {
+        aither_models::attachment_limits(model_id).map(Self::from_limits)
+    }
+
+    /// Create a policy from the documented defaults of `provider`.
+    #[must_use]
+    pub fn for_provider(provider: &str) -> Self {
+        Self::from_limits(aither_models::provider_attachment_limits(provider))
+    }
+
+    /// Set the largest file that is inlined when uploading is available.
+    #[must_use]
+    pub const fn inline_threshold(mut self, bytes: u64) -> Self {
+        self.inline_threshold = bytes;
+        self
+    }
+
+    /// The limits this policy enforces.
+    #[must_use]
+    pub const fn limits(&self) -> AttachmentLimits {
+        self.limits
+    }
+
+    /// Decide how to deliver a file of `size` bytes.
+    ///
+    /// Without a files API, everything that fits inline is inlined. With
+    /// one, files above the threshold (or too large to inline) are uploaded.
+    #[must_use]
+    pub fn decide(&self, size: u64) -> AttachmentDelivery {
+        let fits_inline = self
+            .limits
+            .max_inline_bytes
+            .is_none_or(|max| base64_len(size) <= max);
+        match self.limits.max_upload_bytes {
+            None if fits_inline => AttachmentDelivery::Inline,
+            Some(_) if fits_inline && size <= self.inline_threshold => AttachmentDelivery::Inline,
+            Some(max) if size <= max => AttachmentDelivery::Upload,
+            _ => AttachmentDelivery::TooLarge,
+        }
+    }
+
+    /// Decide how to deliver the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file metadata cannot be read.
+    pub async fn decide_for_path(&self, path: &Path) -> std::io::Result<AttachmentDelivery> {
+        let metadata = async_fs::metadata(path).await?;
+        Ok(self.decide(metadata.len()))
+    }
+}
+
+/// Length of `size` bytes once base64-encoded.
+const fn base64_len(size: u64) -> u64 {
+    size.div_ceil(3).saturating_mul(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn test_policy_decisions() {
+        let policy = AttachmentPolicy::from_limits(AttachmentLimits {
+            max_inline_bytes: Some(20 * MIB),
+            max_upload_bytes: Some(100 * MIB),
+        });
+        assert_eq!(policy.decide(MIB), AttachmentDelivery::Inline);
+        assert_eq!(policy.decide(10 * MIB), AttachmentDelivery::Upload);
+        assert_eq!(policy.decide(200 * MIB), AttachmentDelivery::TooLarge);
+
+        let raised = policy.inline_threshold(18 * MIB);
+        // 18 MiB of base64 no longer fits in a 20 MiB request.
+        assert_eq!(raised.decide(18 * MIB), AttachmentDelivery::Upload);
+
+        let inline_only = AttachmentPolicy::from_limits(AttachmentLimits {
+            max_inline_bytes: Some(20 * MIB),
+            max_upload_bytes: None,
+        });
+        assert_eq!(inline_only.decide(10 * MIB), AttachmentDelivery::Inline);
+        assert_eq!(inline_only.decide(16 * MIB), AttachmentDelivery::TooLarge);
+    }
+}
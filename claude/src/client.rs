@@ -1,6 +1,7 @@
 //! Claude API client implementation.
 
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use aither_core::{
     LanguageModel,
@@ -19,10 +20,13 @@ use crate::{
     constant::{ANTHROPIC_VERSION, CLAUDE_BASE_URL, DEFAULT_MAX_TOKENS, DEFAULT_MODEL},
     error::ClaudeError,
     request::{
-        CacheControlPayload, MessagesRequest, ParameterSnapshot, convert_tools,
-        filter_tool_definitions, to_claude_messages, tool_choice_payload,
+        CacheControlPayload, ContentBlock, MessagesRequest, OUTPUT_TOOL_NAME, ParameterSnapshot,
+        ThinkingPayload, ToolChoicePayload, ToolEntry, attach_thinking, convert_tools,
+        filter_tool_definitions, output_text, output_tool, system_payload, thinking_budget,
+        to_claude_messages, tool_choice_payload,
     },
     response::{StreamState, parse_event, should_skip_event},
+    server_tool::{CODE_EXECUTION_BETA, ServerToolPayload, WebSearch},
 };
 
 /// Claude chat model client for the Anthropic Messages API.
@@ -80,6 +84,15 @@ impl Claude {
         self
     }
 
+    /// Enable extended thinking by default with the given token budget.
+    ///
+    /// Requests can still override it with `Parameters::thinking_budget`.
+    #[must_use]
+    pub fn with_thinking_budget(mut self, budget_tokens: u32) -> Self {
+        Arc::make_mut(&mut self.inner).thinking_budget = Some(budget_tokens);
+        self
+    }
+
     pub(crate) fn config(&self) -> Arc<Config> {
         self.inner.clone()
     }
@@ -94,19 +107,100 @@ impl LanguageModel for Claude {
     ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
         let cfg = self.config();
         let (core_messages, parameters, tool_definitions) = request.into_parts();
-        let (system_prompt, claude_messages) = to_claude_messages(&core_messages);
+        let (system_prompt, mut claude_messages) = to_claude_messages(&core_messages);
         let snapshot = ParameterSnapshot::from(&parameters);
+        let output_schema = snapshot.output_schema.clone();
+        // Structured output forces a tool call, which Claude does not allow
+        // while thinking.
+        let thinking_budget = if output_schema.is_some() {
+            None
+        } else {
+            thinking_budget(&snapshot, cfg.thinking_budget, &cfg.model)
+        };
+        if thinking_budget.is_some() {
+            attach_thinking(&mut claude_messages, |id| cfg.thinking_cache.get(id));
+        }
         let filtered_tool_definitions =
             filter_tool_definitions(tool_definitions, &snapshot.tool_choice);
         let missing_exact_tool = match &snapshot.tool_choice {
             ToolChoice::Exact(name) if filtered_tool_definitions.is_empty() => Some(name.clone()),
             _ => None,
         };
-        let has_tools = !filtered_tool_definitions.is_empty();
-        let claude_tools = has_tools.then(|| convert_tools(&filtered_tool_definitions));
-        let claude_tool_choice = tool_choice_payload(&snapshot.tool_choice, has_tools);
+        let mut has_tools = !filtered_tool_definitions.is_empty();
+        let mut claude_tools = has_tools.then(|| convert_tools(&filtered_tool_definitions));
+        if let Some(schema) = &output_schema {
+            claude_tools
+                .get_or_insert_with(Vec::new)
+                .push(output_tool(schema));
+            has_tools = true;
+        }
 
-        let max_tokens = snapshot.max_tokens.unwrap_or(cfg.default_max_tokens);
+        // Server tools are on when configured on the client or requested
+        // per call, unless the tool choice rules them out.
+        let server_tools_allowed = output_schema.is_none()
+            && !matches!(
+                snapshot.tool_choice,
+                ToolChoice::None | ToolChoice::Exact(_)
+            );
+        let mut server_tools = Vec::new();
+        if server_tools_allowed {
+            if let Some(search) = cfg
+                .web_search
+                .clone()
+                .or_else(|| snapshot.websearch.then(WebSearch::default))
+            {
+                server_tools.push(ServerToolPayload::from(&search));
+            }
+            if cfg.code_execution || snapshot.code_execution {
+                server_tools.push(ServerToolPayload::code_execution());
+            }
+        }
+        let code_execution = server_tools
+            .iter()
+            .any(|tool| matches!(tool, ServerToolPayload::CodeExecution { .. }));
+
+        // Cache breakpoints cover everything before them, so one after the
+        // last tool caches all tool definitions.
+        let breakpoint = CacheControlPayload::from(snapshot.cache.unwrap_or_default());
+        let system_prompt = system_payload(
+            system_prompt,
+            snapshot.cache_hints.system.then(|| breakpoint.clone()),
+        );
+        if snapshot.cache_hints.tools
+            && let Some(last) = claude_tools.as_mut().and_then(|tools| tools.last_mut())
+        {
+            last.cache_control = Some(breakpoint);
+        }
+        let claude_tool_choice = if output_schema.is_some() {
+            Some(ToolChoicePayload::Tool {
+                name: OUTPUT_TOOL_NAME.to_string(),
+            })
+        } else {
+            tool_choice_payload(&snapshot.tool_choice, has_tools)
+        };
+        // Server tools go first so the breakpoint on the last client tool
+        // covers them too.
+        let claude_tools = (has_tools || !server_tools.is_empty()).then(|| {
+            server_tools
+                .into_iter()
+                .map(ToolEntry::Server)
+                .chain(claude_tools.into_iter().flatten().map(ToolEntry::Function))
+                .collect::<Vec<_>>()
+        });
+
+        let mut max_tokens = snapshot.max_tokens.unwrap_or(cfg.default_max_tokens);
+        // Thinking counts against `max_tokens` and the budget must be smaller,
+        // so leave room for the answer. Claude also rejects custom
+        // temperature and top-k, and top-p below 0.95, while thinking.
+        let (temperature, top_p, top_k) = match thinking_budget {
+            Some(budget) => {
+                if max_tokens <= budget {
+                    max_tokens = budget + cfg.default_max_tokens;
+                }
+                (None, snapshot.top_p.filter(|p| *p >= 0.95), None)
+            }
+            None => (snapshot.temperature, snapshot.top_p, snapshot.top_k),
+        };
 
         async_stream::stream! {
             if parameters.cache.openai.is_some() || parameters.cache.gemini.is_some() {
@@ -130,13 +224,14 @@ impl LanguageModel for Claude {
                 messages: claude_messages,
                 system: system_prompt,
                 stream: true,
-                temperature: snapshot.temperature,
-                top_p: snapshot.top_p,
-                top_k: snapshot.top_k,
+                temperature,
+                top_p,
+                top_k,
                 stop_sequences: snapshot.stop_sequences.clone(),
                 tools: claude_tools,
                 tool_choice: claude_tool_choice,
                 cache_control: snapshot.cache.map(CacheControlPayload::from),
+                thinking: thinking_budget.map(ThinkingPayload::enabled),
             };
 
             debug!("Claude request: {:?}", request_body);
@@ -152,6 +247,13 @@ impl LanguageModel for Claude {
                 .and_then(|b| b.header(header::CONTENT_TYPE.as_str(), "application/json"))
                 .and_then(|b| b.header(header::ACCEPT.as_str(), "text/event-stream"))
                 .and_then(|b| b.header(header::USER_AGENT.as_str(), "aither-claude/0.1"))
+                .and_then(|b| {
+                    if code_execution {
+                        b.header("anthropic-beta", CODE_EXECUTION_BETA)
+                    } else {
+                        Ok(b)
+                    }
+                })
                 .and_then(|b| b.json_body(&request_body))
             {
                 Ok(b) => b,
@@ -198,8 +300,20 @@ impl LanguageModel for Claude {
                 }
             }
 
+            if let Some(first) = state.tool_calls.first()
+                && !state.thinking_blocks.is_empty()
+            {
+                cfg.thinking_cache.insert(first.id.clone(), state.thinking_blocks);
+            }
+
             // Yield tool call events (NOT executed - consumer handles execution)
             for call in state.tool_calls {
+                if let Some(schema) = &output_schema
+                    && call.name == OUTPUT_TOOL_NAME
+                {
+                    yield Ok(Event::Text(output_text(schema, call.input)));
+                    continue;
+                }
                 yield Ok(Event::ToolCall(aither_core::llm::ToolCall {
                     id: call.id,
                     name: call.name,
@@ -221,6 +335,12 @@ impl LanguageModel for Claude {
     fn profile(&self) -> impl core::future::Future<Output = ModelProfile> + Send {
         let cfg = self.inner.clone();
         async move {
+            if let Some(warning) =
+                aither_models::lookup_status(&cfg.model).and_then(|status| status.warning())
+            {
+                tracing::warn!("{warning}");
+            }
+
             // Try to fetch context window from proxy API (e.g., OpenRouter)
             // Native Anthropic API doesn't expose this
             let context_length = match fetch_model_context_length(&cfg).await {
@@ -315,6 +435,9 @@ pub struct Builder {
     base_url: String,
     model: String,
     default_max_tokens: u32,
+    thinking_budget: Option<u32>,
+    web_search: Option<WebSearch>,
+    code_execution: bool,
     native_abilities: Vec<Ability>,
 }
 
@@ -325,6 +448,9 @@ impl Builder {
             base_url: CLAUDE_BASE_URL.to_string(),
             model: DEFAULT_MODEL.to_string(),
             default_max_tokens: DEFAULT_MAX_TOKENS,
+            thinking_budget: None,
+            web_search: None,
+            code_execution: false,
             native_abilities: Vec::new(),
         }
     }
@@ -350,6 +476,41 @@ impl Builder {
         self
     }
 
+    /// Give the model Anthropic's server-side web search.
+    ///
+    /// The model profile then advertises [`Ability::WebSearch`], so agents
+    /// skip mounting a client-side search tool.
+    #[must_use]
+    pub fn web_search(mut self, search: WebSearch) -> Self {
+        self.web_search = Some(search);
+        self.native_capabilities([Ability::WebSearch])
+    }
+
+    /// Give the model server-side web search without limits.
+    #[must_use]
+    pub fn enable_web_search(self) -> Self {
+        self.web_search(WebSearch::default())
+    }
+
+    /// Give the model Anthropic's sandboxed code execution.
+    ///
+    /// The model profile then advertises [`Ability::CodeExecution`].
+    #[must_use]
+    pub fn enable_code_execution(mut self) -> Self {
+        self.code_execution = true;
+        self.native_capabilities([Ability::CodeExecution])
+    }
+
+    /// Enable extended thinking by default with the given token budget.
+    ///
+    /// Requests can still override it with `Parameters::thinking_budget`,
+    /// and `0` there turns thinking off.
+    #[must_use]
+    pub const fn thinking_budget(mut self, budget_tokens: u32) -> Self {
+        self.thinking_budget = Some(budget_tokens);
+        self
+    }
+
     /// Declare extra native capabilities supported by the model.
     #[must_use]
     pub fn native_capabilities(mut self, abilities: impl IntoIterator<Item = Ability>) -> Self {
@@ -376,7 +537,11 @@ impl Builder {
                 base_url: self.base_url,
                 model: self.model,
                 default_max_tokens: self.default_max_tokens,
+                thinking_budget: self.thinking_budget,
+                web_search: self.web_search,
+                code_execution: self.code_execution,
                 native_abilities: self.native_abilities,
+                thinking_cache: Arc::default(),
             }),
         }
     }
@@ -389,7 +554,11 @@ pub struct Config {
     pub(crate) base_url: String,
     pub(crate) model: String,
     pub(crate) default_max_tokens: u32,
+    pub(crate) thinking_budget: Option<u32>,
+    pub(crate) web_search: Option<WebSearch>,
+    pub(crate) code_execution: bool,
     pub(crate) native_abilities: Vec<Ability>,
+    pub(crate) thinking_cache: Arc<ThinkingCache>,
 }
 
 impl Config {
@@ -402,6 +571,38 @@ impl Config {
     }
 }
 
+/// Number of tool-use turns whose thinking is remembered.
+const THINKING_CACHE_CAPACITY: usize = 64;
+
+/// Signed thinking from recent tool-use turns, keyed by the turn's first
+/// tool call ID.
+///
+/// Claude requires this thinking to be sent back with the tool results, but
+/// core messages have no place for it, so the client remembers it instead.
+#[derive(Debug, Default)]
+pub(crate) struct ThinkingCache {
+    turns: Mutex<VecDeque<(String, Vec<ContentBlock>)>>,
+}
+
+impl ThinkingCache {
+    fn insert(&self, tool_call_id: String, blocks: Vec<ContentBlock>) {
+        let mut turns = self.turns.lock().unwrap_or_else(PoisonError::into_inner);
+        if turns.len() == THINKING_CACHE_CAPACITY {
+            turns.pop_front();
+        }
+        turns.push_back((tool_call_id, blocks));
+    }
+
+    fn get(&self, tool_call_id: &str) -> Option<Vec<ContentBlock>> {
+        self.turns
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|(id, _)| id == tool_call_id)
+            .map(|(_, blocks)| blocks.clone())
+    }
+}
+
 fn sanitize_model(model: impl Into<String>) -> String {
     model.into().trim().to_string()
 }
@@ -1,5 +1,9 @@
 //! Error types for the Claude API client.
 
+use aither_core::{
+    llm::{LlmError, LlmErrorKind},
+    retry::{ProviderRetryInfo, RetryInfo, is_transient_status},
+};
 use core::fmt;
 use zenwave::{BodyError, Error as ZenwaveError, sse::ParseError as SseParseError};
 
@@ -32,6 +36,63 @@ impl fmt::Display for ClaudeError {
 
 impl std::error::Error for ClaudeError {}
 
+impl RetryInfo for ClaudeError {
+    /// Rate limits (429), overload (529) and other server errors, timeouts
+    /// and network failures are retryable.
+    fn retry_info(&self) -> Option<ProviderRetryInfo> {
+        match self {
+            Self::Http(ZenwaveError::Http { status, .. }) => {
+                is_transient_status(status.as_u16()).then(ProviderRetryInfo::new)
+            }
+            Self::Http(err) => {
+                (err.is_network_error() || err.is_timeout()).then(ProviderRetryInfo::new)
+            }
+            Self::Body(_) | Self::Stream(_) | Self::Json(_) | Self::Api(_) => None,
+        }
+    }
+}
+
+impl From<ClaudeError> for LlmError {
+    fn from(err: ClaudeError) -> Self {
+        let kind = match &err {
+            ClaudeError::Http(http @ ZenwaveError::Http { status, .. }) => {
+                LlmErrorKind::classify(status.as_u16(), http.response_body().unwrap_or_default())
+            }
+            ClaudeError::Http(http) if http.is_network_error() || http.is_timeout() => {
+                LlmErrorKind::Network
+            }
+            ClaudeError::Api(message) => stream_error_kind(message),
+            ClaudeError::Http(_)
+            | ClaudeError::Body(_)
+            | ClaudeError::Stream(_)
+            | ClaudeError::Json(_) => LlmErrorKind::Other,
+        };
+        let error = Self::new(kind, err.to_string());
+        match err.retry_info() {
+            Some(retry) => error.with_retry(retry),
+            None => error,
+        }
+    }
+}
+
+/// Classify an error reported inside the event stream.
+///
+/// Mid-stream errors carry no status, only a message such as
+/// "Overloaded", so overload and rate limits are recognised by name.
+fn stream_error_kind(message: &str) -> LlmErrorKind {
+    if let Some(kind) = LlmErrorKind::from_message(message) {
+        return kind;
+    }
+    let message = message.to_ascii_lowercase();
+    if message.contains("overloaded") {
+        LlmErrorKind::Unavailable
+    } else if message.contains("rate limit") {
+        LlmErrorKind::RateLimit
+    } else {
+        LlmErrorKind::Other
+    }
+}
+
 impl From<ZenwaveError> for ClaudeError {
     fn from(value: ZenwaveError) -> Self {
         Self::Http(value)
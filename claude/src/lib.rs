@@ -12,6 +12,7 @@
 //! - **Tool Use**: Function calling with automatic iteration loop
 //! - **Vision**: Image understanding via base64 or URL references
 //! - **Extended Thinking**: Support for Claude's reasoning/thinking mode
+//! - **Server Tools**: Anthropic-hosted web search and code execution
 //!
 //! ## Getting Started
 //!
@@ -56,6 +57,40 @@
 //!
 //! let response = client.respond(LLMRequest::new([message])).await?;
 //! ```
+//!
+//! ## Extended Thinking
+//!
+//! Thinking is off unless a budget is set, either per client or per request.
+//! Thinking text streams as `Event::Reasoning`:
+//!
+//! ```ignore
+//! let client = Claude::builder(api_key).thinking_budget(4096).build();
+//!
+//! // Think harder for this request only.
+//! let request = LLMRequest::new([message])
+//!     .with_parameters(Parameters::default().thinking_budget(16_384));
+//! ```
+//!
+//! `Parameters::reasoning_effort` maps to a budget when none is given.
+//! Thinking that precedes a tool call, including redacted thinking, is
+//! remembered by the client and sent back with the tool results, as Claude
+//! requires.
+//!
+//! ## Server Tools
+//!
+//! Web search and code execution can run on Anthropic's side. Enabled on the
+//! builder, they are advertised in the model profile as native abilities;
+//! `Parameters::websearch` and `Parameters::code_execution` turn them on for
+//! a single request. Their results stream as `Event::BuiltInToolResult`.
+//!
+//! ```ignore
+//! use aither_claude::{Claude, WebSearch};
+//!
+//! let client = Claude::builder(api_key)
+//!     .web_search(WebSearch::new().max_uses(5))
+//!     .enable_code_execution()
+//!     .build();
+//! ```
 
 mod client;
 mod constant;
@@ -63,8 +98,10 @@ mod error;
 mod provider;
 mod request;
 mod response;
+mod server_tool;
 
 pub use client::{Builder, Claude};
 pub use constant::*;
 pub use error::ClaudeError;
 pub use provider::ClaudeProvider;
+pub use server_tool::WebSearch;
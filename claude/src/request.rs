@@ -1,13 +1,19 @@
 //! Request building and message conversion for the Claude API.
 
 use aither_core::llm::{
-    Message, Role,
-    model::{ClaudePromptCache, ClaudePromptCacheTtl, Parameters, ToolChoice},
+    ContentPart, Message, Role,
+    model::{
+        Ability, CacheHints, ClaudePromptCache, ClaudePromptCacheTtl, Parameters, ReasoningEffort,
+        ToolChoice,
+    },
     tool::ToolDefinition,
 };
 use base64::Engine;
+use schemars::Schema;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Value, json};
+
+use crate::server_tool::ServerToolPayload;
 
 /// Claude Messages API request body.
 #[derive(Debug, Serialize)]
@@ -20,7 +26,7 @@ pub struct MessagesRequest {
     pub messages: Vec<MessagePayload>,
     /// System prompt (extracted from messages).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<SystemPayload>,
     /// Enable streaming.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub stream: bool,
@@ -38,13 +44,75 @@ pub struct MessagesRequest {
     pub stop_sequences: Option<Vec<String>>,
     /// Available tools.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tools: Option<Vec<ToolPayload>>,
+    pub tools: Option<Vec<ToolEntry>>,
     /// Tool choice policy.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<ToolChoicePayload>,
     /// Prompt cache control.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_control: Option<CacheControlPayload>,
+    /// Extended thinking configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<ThinkingPayload>,
+}
+
+/// Extended thinking configuration.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThinkingPayload {
+    /// Thinking mode, always "enabled".
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Maximum tokens Claude may spend thinking.
+    pub budget_tokens: u32,
+}
+
+impl ThinkingPayload {
+    /// Enable thinking with the given budget.
+    pub const fn enabled(budget_tokens: u32) -> Self {
+        Self {
+            kind: "enabled",
+            budget_tokens,
+        }
+    }
+}
+
+/// System prompt - either a simple string or text blocks with cache breakpoints.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SystemPayload {
+    /// Plain system prompt.
+    Text(String),
+    /// System prompt blocks.
+    Blocks(Vec<SystemBlock>),
+}
+
+/// Text block of a system prompt.
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemBlock {
+    /// Block type, always "text".
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// The text content.
+    pub text: String,
+    /// Cache breakpoint placed after this block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControlPayload>,
+}
+
+/// Build the system payload, placing a cache breakpoint after it if requested.
+pub fn system_payload(
+    system: Option<String>,
+    cache_control: Option<CacheControlPayload>,
+) -> Option<SystemPayload> {
+    let text = system?;
+    Some(match cache_control {
+        Some(cache_control) => SystemPayload::Blocks(vec![SystemBlock {
+            kind: "text",
+            text,
+            cache_control: Some(cache_control),
+        }]),
+        None => SystemPayload::Text(text),
+    })
 }
 
 /// Individual message in Claude format.
@@ -82,6 +150,12 @@ pub enum ContentBlock {
         /// Image source (base64 or URL).
         source: ImageSource,
     },
+    /// PDF document block.
+    #[serde(rename = "document")]
+    Document {
+        /// Document source (base64 or URL), shaped like an image source.
+        source: ImageSource,
+    },
     /// Tool use block (in assistant responses).
     #[serde(rename = "tool_use")]
     ToolUse {
@@ -100,6 +174,20 @@ pub enum ContentBlock {
         /// Tool output content.
         content: String,
     },
+    /// Thinking block replayed from an earlier assistant turn.
+    #[serde(rename = "thinking")]
+    Thinking {
+        /// The thinking text.
+        thinking: String,
+        /// Signature proving the thinking came from Claude.
+        signature: String,
+    },
+    /// Encrypted thinking block replayed from an earlier assistant turn.
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking {
+        /// Opaque encrypted thinking.
+        data: String,
+    },
 }
 
 /// Image source for vision requests.
@@ -122,6 +210,16 @@ pub enum ImageSource {
     },
 }
 
+/// Entry in the request's `tools` array.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ToolEntry {
+    /// Server tool run by Anthropic.
+    Server(ServerToolPayload),
+    /// Client tool the caller executes.
+    Function(ToolPayload),
+}
+
 /// Tool definition in Claude format.
 #[derive(Debug, Clone, Serialize)]
 pub struct ToolPayload {
@@ -131,6 +229,9 @@ pub struct ToolPayload {
     pub description: String,
     /// JSON schema for tool input.
     pub input_schema: Value,
+    /// Cache breakpoint placed after this tool (and all tools before it).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControlPayload>,
 }
 
 /// Claude prompt cache control payload.
@@ -188,10 +289,22 @@ pub struct ParameterSnapshot {
     pub stop_sequences: Option<Vec<String>>,
     /// Whether to include reasoning/thinking.
     pub include_reasoning: bool,
+    /// Preferred reasoning effort.
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Explicit thinking budget in tokens.
+    pub thinking_budget: Option<u32>,
     /// Tool choice policy.
     pub tool_choice: ToolChoice,
+    /// Whether to enable the web search server tool.
+    pub websearch: bool,
+    /// Whether to enable the code execution server tool.
+    pub code_execution: bool,
     /// Claude-specific cache controls.
     pub cache: Option<ClaudePromptCache>,
+    /// Request parts marked cacheable.
+    pub cache_hints: CacheHints,
+    /// Schema the response must match.
+    pub output_schema: Option<Schema>,
 }
 
 impl From<&Parameters> for ParameterSnapshot {
@@ -203,8 +316,74 @@ impl From<&Parameters> for ParameterSnapshot {
             max_tokens: params.max_tokens,
             stop_sequences: params.stop.clone(),
             include_reasoning: params.include_reasoning,
+            reasoning_effort: params.reasoning_effort,
+            thinking_budget: params.thinking_budget,
             tool_choice: params.tool_choice.clone(),
+            websearch: params.websearch,
+            code_execution: params.code_execution,
             cache: params.cache.claude,
+            cache_hints: params.cache.hints,
+            output_schema: params.response_format.clone(),
+        }
+    }
+}
+
+/// Smallest thinking budget Claude accepts.
+pub const MIN_THINKING_BUDGET: u32 = 1024;
+
+/// Resolve the thinking budget for a request.
+///
+/// An explicit budget wins over reasoning effort, which wins over the
+/// client default. The result is clamped to the range the model accepts;
+/// `None` means thinking stays off, including for models known not to
+/// support it.
+pub fn thinking_budget(
+    params: &ParameterSnapshot,
+    default: Option<u32>,
+    model: &str,
+) -> Option<u32> {
+    let budget = params
+        .thinking_budget
+        .or_else(|| {
+            params.reasoning_effort.map(|effort| match effort {
+                ReasoningEffort::Minimum => 0,
+                ReasoningEffort::Low => 1024,
+                ReasoningEffort::Medium => 4096,
+                ReasoningEffort::High => 16_384,
+            })
+        })
+        .or(default)?;
+    if budget == 0
+        || aither_models::lookup(model)
+            .is_some_and(|info| !info.abilities.contains(&Ability::Reasoning))
+    {
+        return None;
+    }
+
+    let meta = aither_models::reasoning_meta(model);
+    let budget = budget.max(meta.budget_tokens_min.unwrap_or(MIN_THINKING_BUDGET));
+    Some(meta.budget_tokens_max.map_or(budget, |max| budget.min(max)))
+}
+
+/// Prepend replayed thinking to assistant turns that called tools.
+///
+/// With thinking enabled, Claude requires the thinking that preceded a tool
+/// call to come back with the call. `lookup` maps the ID of the turn's first
+/// tool call to the blocks recorded when the turn was streamed.
+pub fn attach_thinking(
+    messages: &mut [MessagePayload],
+    lookup: impl Fn(&str) -> Option<Vec<ContentBlock>>,
+) {
+    for message in messages.iter_mut().filter(|m| m.role == "assistant") {
+        let ContentPayload::Blocks(blocks) = &mut message.content else {
+            continue;
+        };
+        let first_call = blocks.iter().find_map(|block| match block {
+            ContentBlock::ToolUse { id, .. } => Some(id.as_str()),
+            _ => None,
+        });
+        if let Some(thinking) = first_call.and_then(&lookup) {
+            blocks.splice(0..0, thinking);
         }
     }
 }
@@ -259,27 +438,33 @@ fn build_user_content(message: &Message) -> ContentPayload {
         return ContentPayload::Text(flatten_content(message));
     }
 
-    let mut blocks: Vec<ContentBlock> = Vec::new();
-
-    // Process image attachments
-    for attachment in attachments {
-        let url_str = attachment.as_str();
-        if let Some(source) = parse_image_source(url_str) {
-            blocks.push(ContentBlock::Image { source });
-        }
-    }
-
-    // Add text content
-    let text = flatten_content(message);
-    if !text.is_empty() {
-        blocks.push(ContentBlock::Text { text });
-    }
+    // Attachments first, then text; parts Claude cannot read are described.
+    let mut blocks: Vec<ContentBlock> = message
+        .parts()
+        .into_iter()
+        .map(|part| {
+            let block = match &part {
+                ContentPart::Text(text) => Some(ContentBlock::Text { text: text.clone() }),
+                ContentPart::Image(url) => {
+                    parse_image_source(url.as_str()).map(|source| ContentBlock::Image { source })
+                }
+                ContentPart::File(url)
+                    if part.media_type().as_deref() == Some("application/pdf") =>
+                {
+                    parse_document_source(url.as_str())
+                        .map(|source| ContentBlock::Document { source })
+                }
+                _ => None,
+            };
+            block.unwrap_or_else(|| ContentBlock::Text {
+                text: part.placeholder(),
+            })
+        })
+        .collect();
 
     // Optimize: if only one text block, use simple string
-    if blocks.len() == 1 {
-        if let Some(ContentBlock::Text { text }) = blocks.pop() {
-            return ContentPayload::Text(text);
-        }
+    if let [ContentBlock::Text { text }] = blocks.as_mut_slice() {
+        return ContentPayload::Text(std::mem::take(text));
     }
 
     ContentPayload::Blocks(blocks)
@@ -347,6 +532,28 @@ fn parse_image_source(url: &str) -> Option<ImageSource> {
     }
 }
 
+/// Parse a PDF URL into a document source.
+///
+/// Handles base64 `data:` URLs, `file://` paths and remote URLs.
+fn parse_document_source(url: &str) -> Option<ImageSource> {
+    if let Some(after_data) = url.strip_prefix("data:") {
+        let (header, data) = after_data.split_once(',')?;
+        let media_type = header.strip_suffix(";base64")?;
+        Some(ImageSource::Base64 {
+            media_type: media_type.to_string(),
+            data: data.to_string(),
+        })
+    } else if url.starts_with("file://") {
+        read_file_to_base64_source(url)
+    } else if url.starts_with("http://") || url.starts_with("https://") {
+        Some(ImageSource::Url {
+            url: url.to_string(),
+        })
+    } else {
+        None
+    }
+}
+
 /// Read a file:// URL and convert to base64 image source.
 fn read_file_to_base64_source(file_url: &str) -> Option<ImageSource> {
     let url = url::Url::parse(file_url).ok()?;
@@ -425,10 +632,55 @@ pub fn convert_tools(definitions: &[ToolDefinition]) -> Vec<ToolPayload> {
             name: tool.name().to_string(),
             description: tool.description().to_string(),
             input_schema: tool.arguments_openai_schema(),
+            cache_control: None,
         })
         .collect()
 }
 
+/// Name of the tool Claude is forced to call for structured output.
+pub const OUTPUT_TOOL_NAME: &str = "structured_output";
+
+/// Tool whose input is the structured response.
+///
+/// Claude has no JSON schema response format, so structured output is
+/// requested by forcing a call to this tool. Tool inputs must be objects;
+/// other schemas are wrapped in a `value` property.
+pub fn output_tool(schema: &Schema) -> ToolPayload {
+    let mut input_schema = schema.as_value().clone();
+    if wraps_output(schema) {
+        // References resolve against the root, so definitions stay there.
+        let defs = input_schema
+            .as_object_mut()
+            .and_then(|schema| schema.remove("$defs"));
+        input_schema = json!({
+            "type": "object",
+            "properties": { "value": input_schema },
+            "required": ["value"],
+        });
+        if let Some(defs) = defs {
+            input_schema["$defs"] = defs;
+        }
+    }
+    ToolPayload {
+        name: OUTPUT_TOOL_NAME.to_string(),
+        description: "Respond with the final answer as the input of this tool.".to_string(),
+        input_schema,
+        cache_control: None,
+    }
+}
+
+/// The structured response carried by a call to the [`output_tool`].
+pub fn output_text(schema: &Schema, mut input: Value) -> String {
+    if wraps_output(schema) {
+        input = input["value"].take();
+    }
+    input.to_string()
+}
+
+fn wraps_output(schema: &Schema) -> bool {
+    schema.as_value().get("type").and_then(Value::as_str) != Some("object")
+}
+
 pub fn filter_tool_definitions(
     definitions: Vec<ToolDefinition>,
     choice: &ToolChoice,
@@ -460,7 +712,7 @@ mod tests {
     use super::*;
     use aither_core::llm::{
         ToolCall,
-        model::{ClaudePromptCache, ClaudePromptCacheTtl, Parameters, ToolChoice},
+        model::{ClaudePromptCache, ClaudePromptCacheTtl, Parameters, ReasoningEffort, ToolChoice},
     };
 
     #[test]
@@ -510,6 +762,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn user_parts_map_to_image_document_and_placeholder_blocks() {
+        let messages = vec![Message::user_with_parts([
+            ContentPart::image_bytes("image/png", b"png"),
+            ContentPart::file_bytes("application/pdf", b"%PDF"),
+            ContentPart::audio_bytes("audio/wav", b"RIFF"),
+            ContentPart::text("Compare them."),
+        ])];
+        let (_, encoded) = to_claude_messages(&messages);
+        let json = serde_json::to_value(&encoded[0].content).expect("serialize content");
+        let types: Vec<_> = json
+            .as_array()
+            .expect("blocks payload")
+            .iter()
+            .map(|block| block["type"].as_str().unwrap_or_default())
+            .collect();
+        assert_eq!(types, ["image", "document", "text", "text"]);
+        assert_eq!(json[1]["source"]["media_type"], "application/pdf");
+        assert!(
+            json[2]["text"]
+                .as_str()
+                .is_some_and(|text| text.starts_with("[audio attachment"))
+        );
+    }
+
     #[test]
     fn required_tool_choice_maps_to_any() {
         let payload = tool_choice_payload(&ToolChoice::Required, true)
@@ -538,6 +815,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn system_payload_places_cache_breakpoint() {
+        let plain = system_payload(Some("Be brief.".to_string()), None).expect("system");
+        let plain_json = serde_json::to_value(plain).expect("serialize plain system");
+        assert_eq!(plain_json, "Be brief.");
+
+        let cached = system_payload(
+            Some("Be brief.".to_string()),
+            Some(CacheControlPayload::from(ClaudePromptCache::default())),
+        )
+        .expect("system");
+        let cached_json = serde_json::to_value(cached).expect("serialize cached system");
+        assert_eq!(cached_json[0]["type"], "text");
+        assert_eq!(cached_json[0]["text"], "Be brief.");
+        assert_eq!(cached_json[0]["cache_control"]["type"], "ephemeral");
+
+        assert!(system_payload(None, None).is_none());
+    }
+
+    #[test]
+    fn thinking_budget_prefers_explicit_budget_and_clamps() {
+        let effort = ParameterSnapshot::from(
+            &Parameters::default().reasoning_effort(ReasoningEffort::Medium),
+        );
+        assert_eq!(
+            thinking_budget(&effort, None, "claude-opus-4.5"),
+            Some(4096)
+        );
+
+        let explicit = ParameterSnapshot::from(
+            &Parameters::default()
+                .reasoning_effort(ReasoningEffort::Medium)
+                .thinking_budget(100_000),
+        );
+        assert_eq!(
+            thinking_budget(&explicit, None, "claude-opus-4.5"),
+            Some(32_768)
+        );
+
+        let tiny = ParameterSnapshot::from(&Parameters::default().thinking_budget(10));
+        assert_eq!(
+            thinking_budget(&tiny, None, "claude-custom"),
+            Some(MIN_THINKING_BUDGET)
+        );
+
+        let off = ParameterSnapshot::from(&Parameters::default().thinking_budget(0));
+        assert_eq!(thinking_budget(&off, Some(8192), "claude-opus-4.5"), None);
+
+        let default = ParameterSnapshot::default();
+        assert_eq!(
+            thinking_budget(&default, Some(8192), "claude-opus-4.5"),
+            Some(8192)
+        );
+        assert_eq!(
+            thinking_budget(&default, Some(8192), "claude-3-5-haiku"),
+            None
+        );
+        assert_eq!(thinking_budget(&default, None, "claude-opus-4.5"), None);
+    }
+
+    #[test]
+    fn thinking_is_replayed_before_tool_use() {
+        let messages = vec![Message::assistant_with_tool_calls(
+            "",
+            vec![ToolCall {
+                id: "call_1".to_string(),
+                name: "lookup".to_string(),
+                arguments: serde_json::json!({}),
+            }],
+        )];
+        let (_, mut encoded) = to_claude_messages(&messages);
+        attach_thinking(&mut encoded, |id| {
+            (id == "call_1").then(|| {
+                vec![ContentBlock::Thinking {
+                    thinking: "Need to look it up.".to_string(),
+                    signature: "sig".to_string(),
+                }]
+            })
+        });
+        let json = serde_json::to_value(&encoded[0].content).expect("serialize content");
+        assert_eq!(json[0]["type"], "thinking");
+        assert_eq!(json[0]["signature"], "sig");
+        assert_eq!(json[1]["type"], "tool_use");
+    }
+
+    #[test]
+    fn server_tools_serialize_with_versioned_types() {
+        let search = crate::server_tool::WebSearch::new()
+            .max_uses(3)
+            .allowed_domains(["docs.rs"]);
+        let tools = vec![
+            ToolEntry::Server(ServerToolPayload::from(&search)),
+            ToolEntry::Server(ServerToolPayload::code_execution()),
+        ];
+        let json = serde_json::to_value(&tools).expect("serialize server tools");
+        assert_eq!(json[0]["type"], "web_search_20250305");
+        assert_eq!(json[0]["name"], "web_search");
+        assert_eq!(json[0]["max_uses"], 3);
+        assert_eq!(json[0]["allowed_domains"][0], "docs.rs");
+        assert!(json[0].get("blocked_domains").is_none());
+        assert_eq!(json[1]["type"], "code_execution_20250825");
+    }
+
     #[test]
     fn cache_control_payload_serializes_expected_shape() {
         let one_hour =
@@ -552,4 +932,27 @@ mod tests {
         assert_eq!(short_json["type"], "ephemeral");
         assert!(short_json.get("ttl").is_none());
     }
+
+    #[test]
+    fn output_tool_wraps_non_object_schemas() {
+        let object = schemars::json_schema!({
+            "type": "object",
+            "properties": { "a": { "type": "integer" } },
+        });
+        let tool = output_tool(&object);
+        assert_eq!(tool.name, OUTPUT_TOOL_NAME);
+        assert_eq!(tool.input_schema["properties"]["a"]["type"], "integer");
+        assert_eq!(output_text(&object, json!({ "a": 1 })), r#"{"a":1}"#);
+
+        let array = schemars::json_schema!({
+            "type": "array",
+            "items": { "$ref": "#/$defs/Item" },
+            "$defs": { "Item": { "type": "string" } },
+        });
+        let tool = output_tool(&array);
+        assert_eq!(tool.input_schema["type"], "object");
+        assert_eq!(tool.input_schema["properties"]["value"]["type"], "array");
+        assert_eq!(tool.input_schema["$defs"]["Item"]["type"], "string");
+        assert_eq!(output_text(&array, json!({ "value": ["x"] })), r#"["x"]"#);
+    }
 }
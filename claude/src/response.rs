@@ -1,11 +1,15 @@
 //! SSE response parsing for the Claude API.
 
-use aither_core::llm::{Event as LLMEvent, Usage as TokenUsage};
+use aither_core::llm::{Citation, Event as LLMEvent, ToolCallDelta, Usage as TokenUsage};
 use serde::Deserialize;
 use serde_json::Value;
 use zenwave::sse::Event;
 
-use crate::error::ClaudeError;
+use crate::{
+    error::ClaudeError,
+    request::{ContentBlock, OUTPUT_TOOL_NAME},
+    server_tool,
+};
 
 /// Initial `message_start` event data.
 #[derive(Debug, Deserialize)]
@@ -73,6 +77,12 @@ pub enum ContentBlockType {
         /// Initial thinking text.
         thinking: String,
     },
+    /// Thinking flagged by safety systems, delivered encrypted.
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking {
+        /// Opaque encrypted thinking.
+        data: String,
+    },
     /// Tool use content block.
     #[serde(rename = "tool_use")]
     ToolUse {
@@ -84,6 +94,31 @@ pub enum ContentBlockType {
         #[serde(default)]
         input: Value,
     },
+    /// Server tool call, executed by Anthropic.
+    #[serde(rename = "server_tool_use")]
+    ServerToolUse {
+        /// Unique ID for this tool use.
+        id: String,
+        /// Server tool name.
+        name: String,
+    },
+    /// Server tool result, delivered whole.
+    #[serde(
+        rename = "web_search_tool_result",
+        alias = "code_execution_tool_result",
+        alias = "bash_code_execution_tool_result",
+        alias = "text_editor_code_execution_tool_result"
+    )]
+    ServerToolResult {
+        /// ID of the `server_tool_use` this answers.
+        tool_use_id: String,
+        /// Result payload.
+        #[serde(default)]
+        content: Value,
+    },
+    /// Block types this client does not handle.
+    #[serde(other)]
+    Other,
 }
 
 /// Content block delta event data.
@@ -112,12 +147,27 @@ pub enum DeltaType {
         /// Thinking fragment to append.
         thinking: String,
     },
+    /// Signature of a thinking block, sent before the block stops.
+    #[serde(rename = "signature_delta")]
+    SignatureDelta {
+        /// Signature fragment to append.
+        signature: String,
+    },
     /// Input JSON delta for tool use.
     #[serde(rename = "input_json_delta")]
     InputJsonDelta {
         /// Partial JSON to append.
         partial_json: String,
     },
+    /// Source cited by the text block, such as a web search result.
+    #[serde(rename = "citations_delta")]
+    CitationsDelta {
+        /// Citation object; only web search locations carry a URL.
+        citation: Value,
+    },
+    /// Delta types this client does not handle.
+    #[serde(other)]
+    Other,
 }
 
 /// Message delta event data (final updates).
@@ -171,6 +221,8 @@ pub struct StreamState {
     pub blocks: Vec<BlockState>,
     /// Completed tool calls.
     pub tool_calls: Vec<ToolCall>,
+    /// Completed thinking blocks, kept for replay alongside tool calls.
+    pub thinking_blocks: Vec<ContentBlock>,
     /// Final stop reason.
     pub stop_reason: Option<String>,
     /// Prompt/input token usage.
@@ -183,6 +235,8 @@ pub struct StreamState {
     pub cache_write_tokens: Option<u32>,
     /// Whether usage has already been emitted.
     pub usage_emitted: bool,
+    /// URLs already reported as citations.
+    pub cited_urls: Vec<String>,
 }
 
 /// State of an individual content block.
@@ -191,7 +245,21 @@ pub enum BlockState {
     /// Text block with accumulated text.
     Text(String),
     /// Thinking block with accumulated reasoning.
-    Thinking(String),
+    Thinking {
+        /// Accumulated thinking text.
+        thinking: String,
+        /// Accumulated signature.
+        signature: String,
+    },
+    /// Redacted thinking block with its encrypted data.
+    RedactedThinking(String),
+    /// Server tool call; its input is not needed.
+    ServerToolUse {
+        /// Tool use ID.
+        id: String,
+        /// Server tool name.
+        name: String,
+    },
     /// Tool use block with accumulated JSON.
     ToolUse {
         /// Tool use ID.
@@ -244,6 +312,18 @@ impl StreamState {
     }
 }
 
+/// Convert a `citations_delta` citation into a web citation, if it has a URL.
+fn web_citation(citation: &Value) -> Option<Citation> {
+    let mut web = Citation::new(citation["url"].as_str()?);
+    if let Some(title) = citation["title"].as_str() {
+        web = web.title(title);
+    }
+    if let Some(text) = citation["cited_text"].as_str() {
+        web = web.cited_text(text);
+    }
+    Some(web)
+}
+
 /// Parse a single SSE event into LLM events.
 ///
 /// Updates the stream state and returns events to emit.
@@ -277,11 +357,19 @@ pub fn parse_event(event: &Event, state: &mut StreamState) -> Result<Vec<LLMEven
                     }
                 }
                 ContentBlockType::Thinking { thinking } => {
-                    state.blocks[ev.index] = BlockState::Thinking(thinking.clone());
+                    state.blocks[ev.index] = BlockState::Thinking {
+                        thinking: thinking.clone(),
+                        signature: String::new(),
+                    };
                     if !thinking.is_empty() {
                         events.push(LLMEvent::Reasoning(thinking));
                     }
                 }
+                ContentBlockType::RedactedThinking { data } => {
+                    // Encrypted, so there is nothing to show; it is only
+                    // kept so it can be sent back.
+                    state.blocks[ev.index] = BlockState::RedactedThinking(data);
+                }
                 ContentBlockType::ToolUse { id, name, .. } => {
                     state.blocks[ev.index] = BlockState::ToolUse {
                         id,
@@ -289,11 +377,44 @@ pub fn parse_event(event: &Event, state: &mut StreamState) -> Result<Vec<LLMEven
                         input_json: String::new(),
                     };
                 }
+                ContentBlockType::ServerToolUse { id, name } => {
+                    state.blocks[ev.index] = BlockState::ServerToolUse { id, name };
+                }
+                ContentBlockType::ServerToolResult {
+                    tool_use_id,
+                    content,
+                } => {
+                    let tool = state
+                        .blocks
+                        .iter()
+                        .find_map(|block| match block {
+                            BlockState::ServerToolUse { id, name } if *id == tool_use_id => {
+                                Some(name.clone())
+                            }
+                            _ => None,
+                        })
+                        .unwrap_or_default();
+                    events.push(LLMEvent::BuiltInToolResult {
+                        tool,
+                        result: server_tool::result_text(&content),
+                    });
+                }
+                ContentBlockType::Other => {}
             }
         }
         "content_block_delta" => {
             let ev: ContentBlockDeltaEvent = serde_json::from_str(data)?;
 
+            if let DeltaType::CitationsDelta { citation } = &ev.delta {
+                if let Some(citation) = web_citation(citation)
+                    && !state.cited_urls.contains(&citation.url)
+                {
+                    state.cited_urls.push(citation.url.clone());
+                    events.push(LLMEvent::Citation(citation));
+                }
+                return Ok(events);
+            }
+
             if let Some(block) = state.blocks.get_mut(ev.index) {
                 match (&mut *block, ev.delta) {
                     (BlockState::Text(text), DeltaType::TextDelta { text: delta }) => {
@@ -301,17 +422,36 @@ pub fn parse_event(event: &Event, state: &mut StreamState) -> Result<Vec<LLMEven
                         events.push(LLMEvent::Text(delta));
                     }
                     (
-                        BlockState::Thinking(thinking),
+                        BlockState::Thinking { thinking, .. },
                         DeltaType::ThinkingDelta { thinking: delta },
                     ) => {
                         thinking.push_str(&delta);
                         events.push(LLMEvent::Reasoning(delta));
                     }
                     (
-                        BlockState::ToolUse { input_json, .. },
+                        BlockState::Thinking { signature, .. },
+                        DeltaType::SignatureDelta { signature: delta },
+                    ) => {
+                        signature.push_str(&delta);
+                    }
+                    (
+                        BlockState::ToolUse {
+                            id,
+                            name,
+                            input_json,
+                        },
                         DeltaType::InputJsonDelta { partial_json },
                     ) => {
                         input_json.push_str(&partial_json);
+                        // The structured output tool surfaces as text once
+                        // complete, never as a tool call.
+                        if !partial_json.is_empty() && name.as_str() != OUTPUT_TOOL_NAME {
+                            events.push(LLMEvent::ToolCallDelta(ToolCallDelta::new(
+                                id.as_str(),
+                                name.as_str(),
+                                partial_json,
+                            )));
+                        }
                     }
                     _ => {
                         // Mismatched delta type - ignore
@@ -327,20 +467,36 @@ pub fn parse_event(event: &Event, state: &mut StreamState) -> Result<Vec<LLMEven
             }
             let ev: StopEvent = serde_json::from_str(data)?;
 
-            if let Some(BlockState::ToolUse {
-                id,
-                name,
-                input_json,
-            }) = state.blocks.get(ev.index)
-            {
-                // Parse the accumulated JSON
-                let input = serde_json::from_str(input_json)
-                    .unwrap_or(Value::Object(serde_json::Map::new()));
-                state.tool_calls.push(ToolCall {
-                    id: id.clone(),
-                    name: name.clone(),
-                    input,
-                });
+            match state.blocks.get(ev.index) {
+                Some(BlockState::ToolUse {
+                    id,
+                    name,
+                    input_json,
+                }) => {
+                    // Parse the accumulated JSON
+                    let input = serde_json::from_str(input_json)
+                        .unwrap_or(Value::Object(serde_json::Map::new()));
+                    state.tool_calls.push(ToolCall {
+                        id: id.clone(),
+                        name: name.clone(),
+                        input,
+                    });
+                }
+                Some(BlockState::Thinking {
+                    thinking,
+                    signature,
+                }) => {
+                    state.thinking_blocks.push(ContentBlock::Thinking {
+                        thinking: thinking.clone(),
+                        signature: signature.clone(),
+                    });
+                }
+                Some(BlockState::RedactedThinking(data)) => {
+                    state
+                        .thinking_blocks
+                        .push(ContentBlock::RedactedThinking { data: data.clone() });
+                }
+                _ => {}
             }
         }
         "message_delta" => {
@@ -432,6 +588,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn thinking_signature_and_redacted_blocks_parse() {
+        let delta: ContentBlockDeltaEvent = serde_json::from_str(
+            r#"{"index":0,"delta":{"type":"signature_delta","signature":"sig"}}"#,
+        )
+        .expect("parse signature delta");
+        assert!(matches!(
+            delta.delta,
+            DeltaType::SignatureDelta { signature } if signature == "sig"
+        ));
+
+        let start: ContentBlockStartEvent = serde_json::from_str(
+            r#"{"index":1,"content_block":{"type":"redacted_thinking","data":"enc"}}"#,
+        )
+        .expect("parse redacted thinking");
+        assert!(matches!(
+            start.content_block,
+            ContentBlockType::RedactedThinking { data } if data == "enc"
+        ));
+    }
+
+    #[test]
+    fn server_tool_blocks_and_unknown_deltas_parse() {
+        let start: ContentBlockStartEvent = serde_json::from_str(
+            r#"{"index":2,"content_block":{"type":"bash_code_execution_tool_result","tool_use_id":"srv_1","content":{"type":"bash_code_execution_result","stdout":"4\n","stderr":"","return_code":0}}}"#,
+        )
+        .expect("parse code execution result");
+        match start.content_block {
+            ContentBlockType::ServerToolResult {
+                tool_use_id,
+                content,
+            } => {
+                assert_eq!(tool_use_id, "srv_1");
+                assert_eq!(server_tool::result_text(&content), "```output\n4\n```");
+            }
+            other => panic!("expected server tool result, got: {other:?}"),
+        }
+
+        let search = serde_json::json!([
+            {"type": "web_search_result", "url": "https://www.rust-lang.org", "title": "Rust"}
+        ]);
+        assert_eq!(
+            server_tool::result_text(&search),
+            "- [Rust](https://www.rust-lang.org)"
+        );
+
+        let unknown: ContentBlockStartEvent =
+            serde_json::from_str(r#"{"index":3,"content_block":{"type":"container_upload"}}"#)
+                .expect("parse unknown block");
+        assert!(matches!(unknown.content_block, ContentBlockType::Other));
+
+        let unknown: ContentBlockDeltaEvent =
+            serde_json::from_str(r#"{"index":0,"delta":{"type":"future_delta"}}"#)
+                .expect("parse unknown delta");
+        assert!(matches!(unknown.delta, DeltaType::Other));
+    }
+
+    #[test]
+    fn web_search_citations_parse() {
+        let delta: ContentBlockDeltaEvent = serde_json::from_str(
+            r#"{"index":0,"delta":{"type":"citations_delta","citation":{"type":"web_search_result_location","url":"https://example.com","title":"Example","cited_text":"Quoted."}}}"#,
+        )
+        .expect("parse citations delta");
+        let DeltaType::CitationsDelta { citation } = delta.delta else {
+            panic!("expected citations delta");
+        };
+        assert_eq!(
+            web_citation(&citation),
+            Some(
+                Citation::new("https://example.com")
+                    .title("Example")
+                    .cited_text("Quoted.")
+            )
+        );
+
+        let document = serde_json::json!({ "type": "char_location", "cited_text": "x" });
+        assert_eq!(web_citation(&document), None);
+    }
+
     #[test]
     fn usage_event_is_not_reemitted() {
         let mut state = StreamState::new();
@@ -0,0 +1,126 @@
+//! Server tools executed by Anthropic: web search and code execution.
+//!
+//! Server tools run on Anthropic's side within a single request. Their calls
+//! and results stream back as `Event::BuiltInToolResult` and never reach the
+//! caller's tool loop.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// `anthropic-beta` value that unlocks the code execution tool.
+pub(crate) const CODE_EXECUTION_BETA: &str = "code-execution-2025-08-25";
+
+/// Settings for the server-side web search tool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebSearch {
+    max_uses: Option<u32>,
+    allowed_domains: Vec<String>,
+    blocked_domains: Vec<String>,
+}
+
+impl WebSearch {
+    /// Web search without limits.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the number of searches per request.
+    #[must_use]
+    pub const fn max_uses(mut self, max_uses: u32) -> Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Only search these domains.
+    #[must_use]
+    pub fn allowed_domains(mut self, domains: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.allowed_domains = domains.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Never search these domains.
+    #[must_use]
+    pub fn blocked_domains(mut self, domains: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.blocked_domains = domains.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Server tool entry in the request's `tools` array.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum ServerToolPayload {
+    /// Web search.
+    #[serde(rename = "web_search_20250305")]
+    WebSearch {
+        /// Tool name, always `web_search`.
+        name: &'static str,
+        /// Maximum searches per request.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_uses: Option<u32>,
+        /// Domains to search exclusively.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        allowed_domains: Vec<String>,
+        /// Domains to never search.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        blocked_domains: Vec<String>,
+    },
+    /// Sandboxed code execution.
+    #[serde(rename = "code_execution_20250825")]
+    CodeExecution {
+        /// Tool name, always `code_execution`.
+        name: &'static str,
+    },
+}
+
+impl ServerToolPayload {
+    /// The code execution tool.
+    pub const fn code_execution() -> Self {
+        Self::CodeExecution {
+            name: "code_execution",
+        }
+    }
+}
+
+impl From<&WebSearch> for ServerToolPayload {
+    fn from(search: &WebSearch) -> Self {
+        Self::WebSearch {
+            name: "web_search",
+            max_uses: search.max_uses,
+            allowed_domains: search.allowed_domains.clone(),
+            blocked_domains: search.blocked_domains.clone(),
+        }
+    }
+}
+
+/// Render a server tool result block's content for `Event::BuiltInToolResult`.
+///
+/// Search results become a Markdown link list and code execution output a
+/// fenced block; anything else, including errors, is passed through as JSON.
+pub fn result_text(content: &Value) -> String {
+    if let Some(results) = content.as_array() {
+        return results
+            .iter()
+            .filter_map(|result| {
+                let url = result["url"].as_str()?;
+                let title = result["title"].as_str().unwrap_or(url);
+                Some(format!("- [{title}]({url})"))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    let stdout = content["stdout"].as_str();
+    let stderr = content["stderr"].as_str();
+    if stdout.is_some() || stderr.is_some() {
+        let output = [stdout, stderr]
+            .into_iter()
+            .flatten()
+            .map(str::trim_end)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        return format!("```output\n{output}\n```");
+    }
+    content.to_string()
+}
@@ -5,9 +5,12 @@
 //! # Features
 //!
 //! - Interactive REPL with streaming responses
-//! - Multi-provider support (`OpenAI`, Claude, Gemini)
+//! - Goal mode (`aither run <goal>`) for unattended runs
+//! - Multi-provider support (`OpenAI`, Claude, Gemini, local OpenAI-compatible servers)
+//! - ACP server mode (`aither --acp`) for editor integration
 //! - Built-in filesystem and command tools
 //! - MCP server support via configuration file
+//! - Providers, tool policy and agent settings from an `aither-config` file
 //! - Debug hook for logging tool calls
 //!
 //! # Usage
@@ -29,4 +32,4 @@ mod provider;
 
 pub use aither_cloud::{CloudError, CloudProvider};
 pub use hook::DebugHook;
-pub use provider::{Provider, auto_detect};
+pub use provider::{Provider, auto_detect, from_config};
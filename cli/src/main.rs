@@ -16,14 +16,22 @@
 //! # Usage
 //!
 //! ```bash
-//! # Auto-detect provider from available API keys
+//! # Auto-detect provider from available API keys and chat
 //! cargo run -p aither-cli
+//! cargo run -p aither-cli -- chat
+//!
+//! # Work on a goal until done, streaming progress, then exit
+//! cargo run -p aither-cli -- run "Add a --verbose flag to the server"
+//! cargo run -p aither-cli -- run "Summarize README.md" --max-iterations 10
 //!
 //! # Explicit provider
 //! OPENAI_API_KEY=xxx cargo run -p aither-cli -- --provider openai
 //! ANTHROPIC_API_KEY=xxx cargo run -p aither-cli -- --provider claude
 //! GEMINI_API_KEY=xxx cargo run -p aither-cli -- --provider gemini
 //!
+//! # Local OpenAI-compatible server (ollama by default, --base-url for others)
+//! cargo run -p aither-cli -- --provider local --model qwen3:8b
+//!
 //! # Headless mode (single query, useful for testing/scripting)
 //! cargo run -p aither-cli -- --prompt "What is 2+2?"
 //! cargo run -p aither-cli -- --prompt "List files" --quiet
@@ -33,12 +41,19 @@
 //!
 //! # Disable Context7 (documentation lookup)
 //! cargo run -p aither-cli -- --no-context7
+//!
+//! # Providers, tool policy, MCP servers and agent settings from a file
+//! # (defaults to ~/.aither/config.toml when it exists)
+//! cargo run -p aither-cli -- --config aither.toml
+//!
+//! # Serve the Agent Client Protocol over stdio (Zed and other editors)
+//! cargo run -p aither-cli -- --acp
 //! ```
 
 mod hook;
 
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use aither_agent::sandbox::{
@@ -47,16 +62,18 @@ use aither_agent::sandbox::{
     schema_to_help,
 };
 use aither_agent::specialized::SubagentTool;
-use aither_agent::{Agent, BashAgentBuilder, Hook};
+use aither_agent::{Agent, AgentEvent, BashAgentBuilder, Hook};
+use aither_config::{BashApproval, Config, ToolPolicy};
 use aither_core::LanguageModel;
-use aither_core::llm::Role;
-use aither_mcp::{McpConnection, McpServersConfig};
+use aither_core::llm::{Role, Tool};
+use aither_mcp::{McpConnection, McpServerConfig, McpServersConfig};
 use anyhow::{Context, Result};
 use async_lock::Mutex as AsyncMutex;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use executor_core::tokio::TokioGlobal;
+use futures_lite::StreamExt;
 use std::sync::Arc;
 use tracing_subscriber::EnvFilter;
 
@@ -95,8 +112,14 @@ const DEFAULT_DOMAIN_WHITELIST: &[&str] = &[
 /// - Sandboxed: always allow (no prompt)
 /// - Network: ask once, then auto-approve all domains
 /// - Unsafe: always ask for each script
+///
+/// Modes up to the tool policy's `bash` level run without asking.
 #[derive(Debug, Default)]
 struct InteractivePermissionHandler {
+    /// Most permissive mode approved without asking.
+    auto_approve: BashApproval,
+    /// Domains the tool policy allows without asking.
+    policy_domains: Vec<String>,
     /// Whether network mode has been approved (auto-approves all domains).
     network_approved: std::sync::atomic::AtomicBool,
     /// Cache of approved domains for cases where network mode isn't blanket approved.
@@ -104,8 +127,10 @@ struct InteractivePermissionHandler {
 }
 
 impl InteractivePermissionHandler {
-    fn new() -> Self {
+    fn new(policy: &ToolPolicy) -> Self {
         Self {
+            auto_approve: policy.bash,
+            policy_domains: policy.network_allow.clone(),
             network_approved: std::sync::atomic::AtomicBool::new(false),
             approved_domains: std::sync::RwLock::new(std::collections::HashSet::new()),
         }
@@ -114,6 +139,15 @@ impl InteractivePermissionHandler {
 
 impl PermissionHandler for InteractivePermissionHandler {
     async fn check(&self, mode: BashMode, script: &str) -> Result<bool, PermissionError> {
+        let level = match mode {
+            BashMode::Sandboxed => BashApproval::Sandboxed,
+            BashMode::Network => BashApproval::Network,
+            BashMode::Unsafe => BashApproval::Unsafe,
+        };
+        if level <= self.auto_approve {
+            return Ok(true);
+        }
+
         // This will be wrapped in StatefulPermissionHandler for network mode
         match mode {
             BashMode::Sandboxed => Ok(true), // Always allow
@@ -153,8 +187,10 @@ impl PermissionHandler for InteractivePermissionHandler {
             return true;
         }
 
-        // Check default whitelist
-        if DEFAULT_DOMAIN_WHITELIST.contains(&domain) {
+        // Check default whitelist and the tool policy
+        if DEFAULT_DOMAIN_WHITELIST.contains(&domain)
+            || self.policy_domains.iter().any(|allowed| allowed == domain)
+        {
             return true;
         }
 
@@ -235,11 +271,16 @@ fn read_yes_no() -> Result<bool> {
 /// Register MCP tools as bash IPC commands.
 ///
 /// This makes MCP tools available as bash commands (e.g., `resolve-library-id "tokio"`)
-/// instead of direct LLM tool calls.
-fn register_mcp_tools(conn: McpConnection, registry: &mut ToolRegistryBuilder) {
+/// instead of direct LLM tool calls. Tools the policy denies are skipped.
+fn register_mcp_tools(
+    conn: McpConnection,
+    registry: &mut ToolRegistryBuilder,
+    policy: &ToolPolicy,
+) {
     let tools: Vec<_> = conn
         .mcp_definitions()
         .iter()
+        .filter(|def| policy.allows(&def.name))
         .map(|def| {
             let name = def.name.clone();
             let description = def.description.clone().unwrap_or_default();
@@ -320,28 +361,35 @@ fn parse_cli_args_to_json(
 #[derive(Parser, Debug)]
 #[command(name = "aither", version, about)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Path to a configuration file (TOML or JSON). Defaults to ~/.aither/config.toml if present.
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
     /// Model to use. Auto-detects provider from model name prefix.
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     model: Option<String>,
 
-    /// Provider to use (openai, claude, gemini). Auto-detected if not specified.
-    #[arg(short, long)]
+    /// Provider to use (openai, claude, gemini, local). Auto-detected if not specified.
+    #[arg(short, long, global = true)]
     provider: Option<Provider>,
 
     /// Custom API base URL (for OpenAI-compatible endpoints, proxies, etc.)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     base_url: Option<String>,
 
     /// Path to MCP servers configuration file (JSON).
-    #[arg(long)]
+    #[arg(long, global = true)]
     mcp: Option<PathBuf>,
 
     /// System prompt for the agent.
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     system: Option<String>,
 
     /// Disable Context7 MCP server (documentation lookup).
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_context7: bool,
 
     /// Single prompt to run (headless mode). Runs query and exits.
@@ -349,15 +397,15 @@ struct Args {
     prompt: Option<String>,
 
     /// Quiet mode. Only output the response (useful with --prompt for scripting).
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     quiet: bool,
 
     /// Path to skills directory. Skills are loaded from SKILL.md files in subdirectories.
-    #[arg(long)]
+    #[arg(long, global = true)]
     skills: Option<PathBuf>,
 
     /// Path to subagents directory. Subagents are markdown files invoked via `task <path> <prompt>`.
-    #[arg(long)]
+    #[arg(long, global = true)]
     subagents: Option<PathBuf>,
 
     /// Run as ACP server (for integration with Zed and other editors).
@@ -365,6 +413,25 @@ struct Args {
     acp: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Chat with the agent interactively (the default).
+    Chat,
+    /// Work on a goal until it is done, streaming progress, then exit.
+    Run {
+        /// What the agent should accomplish.
+        goal: String,
+
+        /// Maximum number of model requests before giving up.
+        #[arg(long)]
+        max_iterations: Option<usize>,
+
+        /// Tokens the run may spend; plans are sized to fit.
+        #[arg(long)]
+        token_budget: Option<u64>,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt()
@@ -378,7 +445,9 @@ async fn main() -> Result<()> {
         return run_acp_server().await;
     }
 
-    // Create cloud provider
+    let config = load_config(args.config.as_deref()).await?;
+
+    // Create cloud provider: --provider, then the config file, then auto-detect
     let base_url = args.base_url.as_deref();
     let (cloud, model, provider_name) = if let Some(provider) = args.provider {
         let model = args.model.as_deref().unwrap_or(provider.default_model());
@@ -387,6 +456,9 @@ async fn main() -> Result<()> {
             model.to_string(),
             provider.to_string(),
         )
+    } else if let Some((name, provider)) = config.provider(None) {
+        let (cloud, model) = provider::from_config(provider, args.model.as_deref(), base_url)?;
+        (cloud, model, name.to_string())
     } else {
         let (cloud, model) = provider::auto_detect(args.model.as_deref(), base_url)?;
         let provider_name = match &cloud {
@@ -394,26 +466,58 @@ async fn main() -> Result<()> {
             CloudProvider::Claude(_) => "Claude",
             CloudProvider::Gemini(_) => "Gemini",
             CloudProvider::Copilot(_) => "Copilot",
+            CloudProvider::Compatible(_) => "OpenAI-compatible",
         };
         (cloud, model, provider_name.to_string())
     };
 
+    let goal = match &args.command {
+        Some(Command::Run { goal, .. }) => Some(goal.as_str()),
+        Some(Command::Chat) | None => None,
+    };
+
     if !args.quiet {
         println!("Aither Agent CLI");
         println!("Provider: {provider_name}");
         println!("Model: {model}");
-        if args.prompt.is_none() {
+        if args.prompt.is_none() && goal.is_none() {
             println!("Commands: /quit, /clear, /history, /compact");
         }
         println!();
     }
 
+    // Goal mode: stream a full run and exit
+    if let Some(goal) = goal {
+        return run_goal(cloud, &args, &config, goal).await;
+    }
+
     // Headless mode: run single prompt and exit
     if let Some(ref prompt) = args.prompt {
-        return run_headless(cloud, &args, prompt).await;
+        return run_headless(cloud, &args, &config, prompt).await;
     }
 
-    run_repl(cloud, &args).await
+    run_repl(cloud, &args, &config).await
+}
+
+/// Load the configuration file given with `--config`, or
+/// `~/.aither/config.toml` if it exists.
+async fn load_config(path: Option<&Path>) -> Result<Config> {
+    let path = match path {
+        Some(path) => expand_tilde(path),
+        None => {
+            let Some(home) = dirs::home_dir() else {
+                return Ok(Config::default());
+            };
+            let default = home.join(".aither").join("config.toml");
+            if !path_exists(&default).await? {
+                return Ok(Config::default());
+            }
+            default
+        }
+    };
+    Config::load(&path)
+        .await
+        .with_context(|| format!("failed to load config from {}", path.display()))
 }
 
 /// Run as ACP server for editor integration.
@@ -428,6 +532,7 @@ async fn run_acp_server() -> Result<()> {
 async fn build_agent(
     cloud: CloudProvider,
     args: &Args,
+    config: &Config,
 ) -> Result<Agent<CloudProvider, CloudProvider, CloudProvider, aither_agent::HCons<DebugHook, ()>>>
 {
     // Create bash tool (creates random four-word working dir under system temp)
@@ -436,23 +541,35 @@ async fn build_agent(
     // - Network: ask once, then remember
     // - Unsafe: always ask for each script
     let workdir_parent = std::env::temp_dir().join("aither");
-    let permission_handler = StatefulPermissionHandler::new(InteractivePermissionHandler::new());
+    let policy = &config.tools;
+    let permission_handler =
+        StatefulPermissionHandler::new(InteractivePermissionHandler::new(policy));
     let bash_tool =
         aither_agent::sandbox::BashTool::new_in(&workdir_parent, permission_handler, TokioGlobal)
             .await?;
 
     // Create bash-centric agent builder
-    // All tools become IPC commands accessible via bash
-    let mut builder = BashAgentBuilder::new(cloud.clone(), bash_tool)
-        .tool(aither_agent::websearch::WebSearchTool::default())
-        .tool(aither_agent::webfetch::WebFetchTool::new())
-        .tool(aither_agent::TodoTool::new())
-        .tool(aither_agent::sandbox::builtin::AskCommand::new(
-            cloud.clone(),
-        ));
+    // All tools become IPC commands accessible via bash, unless the policy denies them
+    let mut builder = BashAgentBuilder::new(cloud.clone(), bash_tool).settings(&config.agent);
+    let websearch = aither_agent::websearch::WebSearchTool::default();
+    if policy.allows(&websearch.name()) {
+        builder = builder.tool(websearch);
+    }
+    let webfetch = aither_agent::webfetch::WebFetchTool::new();
+    if policy.allows(&webfetch.name()) {
+        builder = builder.tool(webfetch);
+    }
+    let todo = aither_agent::TodoTool::new();
+    if policy.allows(&todo.name()) {
+        builder = builder.tool(todo);
+    }
+    let ask = aither_agent::sandbox::builtin::AskCommand::new(cloud.clone());
+    if policy.allows(&ask.name()) {
+        builder = builder.tool(ask);
+    }
 
     // Load skills if path provided
-    if let Some(ref skills_path) = args.skills {
+    if let Some(skills_path) = args.skills.as_ref().or(config.agent.skills.as_ref()) {
         // Expand ~ to home directory
         let expanded = expand_tilde(skills_path);
         if path_exists(&expanded).await? {
@@ -469,7 +586,7 @@ async fn build_agent(
     }
 
     // Set up subagents directory if path provided
-    if let Some(ref subagents_path) = args.subagents {
+    if let Some(subagents_path) = args.subagents.as_ref().or(config.agent.subagents.as_ref()) {
         let expanded = expand_tilde(subagents_path);
         if path_exists(&expanded).await? {
             builder = builder.with_subagents(&expanded).await?;
@@ -493,11 +610,14 @@ async fn build_agent(
                 }
                 // Collect MCP tool descriptions and register
                 for def in conn.mcp_definitions() {
+                    if !policy.allows(&def.name) {
+                        continue;
+                    }
                     let desc = def.description.clone().unwrap_or_default();
                     let desc = desc.split('.').next().unwrap_or(&desc).trim().to_string();
                     builder = builder.tool_description(def.name.clone(), desc);
                 }
-                register_mcp_tools(conn, builder.tool_registry_mut());
+                register_mcp_tools(conn, builder.tool_registry_mut(), policy);
             }
             Err(e) => {
                 if !args.quiet {
@@ -507,25 +627,43 @@ async fn build_agent(
         }
     }
 
-    // Add MCP connections from config file
+    // Add MCP connections from the config file, then from --mcp (which wins on name clashes)
+    let mut servers: McpServersConfig = config
+        .mcp
+        .iter()
+        .map(|(name, server)| {
+            let server = McpServerConfig {
+                command: server.command.clone(),
+                args: server.args.clone(),
+                url: server.url.clone(),
+                env: server.env.clone().into_iter().collect(),
+            };
+            (name.clone(), server)
+        })
+        .collect();
     if let Some(ref mcp_path) = args.mcp {
         let config_str = tokio::fs::read_to_string(mcp_path)
             .await
             .with_context(|| format!("failed to read MCP config from {}", mcp_path.display()))?;
-        let config: McpServersConfig =
+        let file_servers: McpServersConfig =
             serde_json::from_str(&config_str).with_context(|| "failed to parse MCP config")?;
-
-        let connections = McpConnection::from_configs(&config).await?;
+        servers.extend(file_servers);
+    }
+    if !servers.is_empty() {
+        let connections = McpConnection::from_configs(&servers).await?;
         for (name, conn) in connections {
             if !args.quiet {
                 println!("Connected to MCP server: {name}");
             }
             for def in conn.mcp_definitions() {
+                if !policy.allows(&def.name) {
+                    continue;
+                }
                 let desc = def.description.clone().unwrap_or_default();
                 let desc = desc.split('.').next().unwrap_or(&desc).trim().to_string();
                 builder = builder.tool_description(def.name.clone(), desc);
             }
-            register_mcp_tools(conn, builder.tool_registry_mut());
+            register_mcp_tools(conn, builder.tool_registry_mut(), policy);
         }
     }
 
@@ -535,19 +673,38 @@ async fn build_agent(
         .with_builtins()
         .with_base_dir(builder.sandbox_dir().to_string())
         .with_bash_tool_factory(builder.bash_tool_factory());
-    let mut subagent_desc = String::from("Spawn subagent for complex tasks (types: ");
-    let subagent_names: Vec<_> = subagent_tool
-        .type_descriptions()
-        .iter()
-        .map(|(n, _)| *n)
-        .collect();
-    subagent_desc.push_str(&subagent_names.join(", "));
-    subagent_desc.push(')');
-    builder = builder.tool_with_desc(subagent_tool, subagent_desc);
+    if policy.allows(&subagent_tool.name()) {
+        let mut subagent_desc = String::from("Spawn subagent for complex tasks (types: ");
+        let subagent_names: Vec<_> = subagent_tool
+            .type_descriptions()
+            .iter()
+            .map(|(n, _)| *n)
+            .collect();
+        subagent_desc.push_str(&subagent_names.join(", "));
+        subagent_desc.push(')');
+        builder = builder.tool_with_desc(subagent_tool, subagent_desc);
+    }
+
+    // Apply limits for goal runs (overriding the config file)
+    if let Some(Command::Run {
+        max_iterations,
+        token_budget,
+        ..
+    }) = &args.command
+    {
+        if let Some(limit) = max_iterations {
+            builder = builder.max_iterations(*limit);
+        }
+        if let Some(tokens) = token_budget {
+            builder = builder.token_budget(*tokens);
+        }
+    }
 
-    // Add system prompt
+    // Add system prompt: --system, then the config file, then the default
     let builder = if let Some(ref system) = args.system {
         builder.system_prompt_raw(system)
+    } else if config.agent.system_prompt.is_some() {
+        builder
     } else {
         builder.with_default_prompt()
     };
@@ -556,8 +713,13 @@ async fn build_agent(
 }
 
 /// Run a single prompt and exit (headless mode).
-async fn run_headless(cloud: CloudProvider, args: &Args, prompt: &str) -> Result<()> {
-    let mut agent = build_agent(cloud, args).await?;
+async fn run_headless(
+    cloud: CloudProvider,
+    args: &Args,
+    config: &Config,
+    prompt: &str,
+) -> Result<()> {
+    let mut agent = build_agent(cloud, args, config).await?;
 
     match agent.query(prompt).await {
         Ok(response) => {
@@ -571,8 +733,64 @@ async fn run_headless(cloud: CloudProvider, args: &Args, prompt: &str) -> Result
     }
 }
 
-async fn run_repl(cloud: CloudProvider, args: &Args) -> Result<()> {
-    let mut agent = build_agent(cloud, args).await?;
+/// Work on a goal until done (goal mode).
+///
+/// Response text goes to stdout; tool activity and the run summary go to
+/// stderr so the output can be piped.
+async fn run_goal(cloud: CloudProvider, args: &Args, config: &Config, goal: &str) -> Result<()> {
+    let mut agent = build_agent(cloud, args, config).await?;
+
+    let mut failed = false;
+    {
+        let stream = agent.run(goal, std::iter::empty());
+        futures_lite::pin!(stream);
+        while let Some(event) = stream.next().await {
+            match event {
+                Ok(AgentEvent::Text(text)) => {
+                    print!("{text}");
+                    io::stdout().flush().ok();
+                }
+                Ok(AgentEvent::ToolCallStart {
+                    name, arguments, ..
+                }) if !args.quiet => {
+                    eprintln!("\x1b[2m→ {name} {}\x1b[22m", truncate(&arguments, 120));
+                }
+                Ok(AgentEvent::ToolCallEnd {
+                    name,
+                    result: Err(error),
+                    ..
+                }) if !args.quiet => {
+                    eprintln!("\x1b[31m✗ {name}: {}\x1b[0m", truncate(&error, 200));
+                }
+                Ok(AgentEvent::Error(e)) | Err(e) => {
+                    eprintln!("\x1b[31mError: {e}\x1b[0m");
+                    failed = true;
+                }
+                Ok(_) => {}
+            }
+        }
+    }
+    println!();
+
+    if !args.quiet {
+        let report = agent.run_report();
+        eprintln!(
+            "\x1b[2m{} iterations, {} tool calls ({} failed), {:.1}s\x1b[22m",
+            report.iterations.len(),
+            report.tool_calls(),
+            report.tool_failures(),
+            report.duration.as_secs_f64()
+        );
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_repl(cloud: CloudProvider, args: &Args, config: &Config) -> Result<()> {
+    let mut agent = build_agent(cloud, args, config).await?;
 
     println!("Agent ready. Type your message or a command.\n");
 
@@ -668,10 +886,9 @@ where
 }
 
 fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len])
+    match s.char_indices().nth(max_len) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_string(),
     }
 }
 
@@ -1,6 +1,7 @@
 //! Provider detection and construction for the CLI.
 
-use aither_cloud::{Claude, CloudProvider, Gemini, OpenAI};
+use aither_cloud::{Claude, CloudProvider, Compatible, Gemini, OLLAMA_BASE_URL, OpenAI};
+use aither_config::{ProviderConfig, ProviderKind};
 use anyhow::{Result, bail};
 
 /// Supported cloud providers.
@@ -13,6 +14,8 @@ pub enum Provider {
     /// Google Gemini models (gemini-2.5-pro, gemini-2.5-flash, etc.)
     #[default]
     Gemini,
+    /// A local OpenAI-compatible server (ollama, vLLM, llama.cpp), ollama by default.
+    Local,
 }
 
 impl Provider {
@@ -38,17 +41,21 @@ impl Provider {
             "openai" | "gpt" => Some(Self::OpenAI),
             "claude" | "anthropic" => Some(Self::Claude),
             "gemini" | "google" => Some(Self::Gemini),
+            "local" | "ollama" => Some(Self::Local),
             _ => None,
         }
     }
 
     /// Get environment variable name for API key.
+    ///
+    /// The key is optional for [`Provider::Local`].
     #[must_use]
     pub const fn env_var(self) -> &'static str {
         match self {
             Self::OpenAI => "OPENAI_API_KEY",
             Self::Claude => "ANTHROPIC_API_KEY",
             Self::Gemini => "GEMINI_API_KEY",
+            Self::Local => "LOCAL_API_KEY",
         }
     }
 
@@ -59,15 +66,27 @@ impl Provider {
             Self::OpenAI => "gpt-4o-mini",
             Self::Claude => "claude-sonnet-4-20250514",
             Self::Gemini => "gemini-2.5-flash",
+            Self::Local => "qwen3:8b",
         }
     }
 
     /// Create a cloud provider from this provider type.
     pub fn create(self, model: &str, base_url: Option<&str>) -> Result<CloudProvider> {
+        if self == Self::Local {
+            // Local servers usually run without authentication.
+            let api_key = std::env::var(self.env_var()).unwrap_or_else(|_| "local".to_string());
+            return Ok(self.client(api_key, model, base_url));
+        }
+
         let api_key = std::env::var(self.env_var())
             .map_err(|_| anyhow::anyhow!("Set {} in your environment", self.env_var()))?;
 
-        Ok(match self {
+        Ok(self.client(api_key, model, base_url))
+    }
+
+    /// Create a cloud client authenticated with `api_key`.
+    fn client(self, api_key: String, model: &str, base_url: Option<&str>) -> CloudProvider {
+        match self {
             Self::OpenAI => {
                 let mut client = OpenAI::new(api_key).with_model(model);
                 if let Some(url) = base_url {
@@ -93,7 +112,10 @@ impl Provider {
                 }
                 CloudProvider::from(client)
             }
-        })
+            Self::Local => Compatible::new(base_url.unwrap_or(OLLAMA_BASE_URL), api_key)
+                .model(model)
+                .into(),
+        }
     }
 }
 
@@ -103,6 +125,7 @@ impl std::fmt::Display for Provider {
             Self::OpenAI => write!(f, "OpenAI"),
             Self::Claude => write!(f, "Claude"),
             Self::Gemini => write!(f, "Gemini"),
+            Self::Local => write!(f, "Local"),
         }
     }
 }
@@ -140,3 +163,32 @@ pub fn auto_detect(model: Option<&str>, base_url: Option<&str>) -> Result<(Cloud
 
     bail!("No API key found. Set one of: GEMINI_API_KEY, OPENAI_API_KEY, or ANTHROPIC_API_KEY");
 }
+
+/// Create a provider from a configuration file entry.
+///
+/// `model` and `base_url` override the entry's own. Cloud providers without
+/// an `api_key` fall back to their environment variable.
+pub fn from_config(
+    config: &ProviderConfig,
+    model: Option<&str>,
+    base_url: Option<&str>,
+) -> Result<(CloudProvider, String)> {
+    let provider = match config.kind {
+        ProviderKind::OpenAI => Provider::OpenAI,
+        ProviderKind::Claude => Provider::Claude,
+        ProviderKind::Gemini => Provider::Gemini,
+        ProviderKind::Compatible => Provider::Local,
+    };
+    let model = match model.or(config.model.as_deref()) {
+        Some(model) => model,
+        None if provider == Provider::Local => bail!("Set a model for the compatible provider"),
+        None => provider.default_model(),
+    };
+    let base_url = base_url.or(config.base_url.as_deref());
+
+    let cloud = match &config.api_key {
+        Some(api_key) => provider.client(api_key.clone(), model, base_url),
+        None => provider.create(model, base_url)?,
+    };
+    Ok((cloud, model.to_string()))
+}
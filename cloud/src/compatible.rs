@@ -0,0 +1,141 @@
+//! Generic OpenAI-compatible endpoints.
+//!
+//! Many hosts speak the `OpenAI` chat completions protocol: `OpenRouter`,
+//! Together, Groq, vLLM and ollama among them. [`Compatible`] describes such
+//! an endpoint and turns into a [`CloudProvider`] or [`CloudModelProvider`]:
+//!
+//! ```ignore
+//! let groq: CloudProvider = Compatible::groq(key).model("llama-3.3-70b-versatile").into();
+//! let local: CloudProvider = Compatible::ollama().model("qwen3:8b").into();
+//! ```
+
+use aither_openai::{ApiKind, OPENROUTER_BASE_URL, OpenAI, OpenAIProvider};
+
+use crate::{CloudModelProvider, CloudProvider};
+
+/// [Together](https://www.together.ai)'s OpenAI-compatible base URL.
+pub const TOGETHER_BASE_URL: &str = "https://api.together.xyz/v1";
+/// [Groq](https://groq.com)'s OpenAI-compatible base URL.
+pub const GROQ_BASE_URL: &str = "https://api.groq.com/openai/v1";
+/// Default base URL of a local vLLM server.
+pub const VLLM_BASE_URL: &str = "http://localhost:8000/v1";
+/// Default base URL of a local ollama server.
+pub const OLLAMA_BASE_URL: &str = "http://localhost:11434/v1";
+
+/// An OpenAI-compatible endpoint.
+#[derive(Debug, Clone)]
+pub struct Compatible {
+    base_url: String,
+    api_key: String,
+    auth_header: Option<String>,
+    model: Option<String>,
+}
+
+impl Compatible {
+    /// Describe the endpoint at `base_url`, authenticating with `api_key`.
+    ///
+    /// The key is sent as `Authorization: Bearer <key>`; servers without
+    /// authentication accept any key.
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key: api_key.into(),
+            auth_header: None,
+            model: None,
+        }
+    }
+
+    /// [`OpenRouter`](https://openrouter.ai).
+    #[must_use]
+    pub fn openrouter(api_key: impl Into<String>) -> Self {
+        Self::new(OPENROUTER_BASE_URL, api_key)
+    }
+
+    /// [Together](https://www.together.ai).
+    #[must_use]
+    pub fn together(api_key: impl Into<String>) -> Self {
+        Self::new(TOGETHER_BASE_URL, api_key)
+    }
+
+    /// [Groq](https://groq.com).
+    #[must_use]
+    pub fn groq(api_key: impl Into<String>) -> Self {
+        Self::new(GROQ_BASE_URL, api_key)
+    }
+
+    /// A local vLLM server on its default port.
+    #[must_use]
+    pub fn vllm() -> Self {
+        Self::new(VLLM_BASE_URL, "")
+    }
+
+    /// A local ollama server on its default port.
+    #[must_use]
+    pub fn ollama() -> Self {
+        Self::new(OLLAMA_BASE_URL, "ollama")
+    }
+
+    /// Override the base URL, e.g. for a server on another host.
+    #[must_use]
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Send the API key verbatim in header `name` instead of `Authorization: Bearer`.
+    #[must_use]
+    pub fn auth_header(mut self, name: impl Into<String>) -> Self {
+        self.auth_header = Some(name.into());
+        self
+    }
+
+    /// Set the model name, exactly as the endpoint expects it.
+    #[must_use]
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Build a chat completions client for the configured model.
+    ///
+    /// Without a model, the `OpenAI` client's default model name is used,
+    /// which most compatible endpoints will reject.
+    #[must_use]
+    pub fn client(&self) -> OpenAI {
+        let mut builder = OpenAI::builder(self.api_key.clone())
+            .base_url(self.base_url.clone())
+            .use_chat_completions_api();
+        if let Some(name) = &self.auth_header {
+            builder = builder.auth_header(name.clone());
+        }
+        if let Some(model) = &self.model {
+            builder = builder.model(model.clone());
+        }
+        builder.build()
+    }
+
+    /// Build a provider that lists and instantiates the endpoint's models.
+    #[must_use]
+    pub fn provider(&self) -> OpenAIProvider {
+        let mut provider = OpenAIProvider::new(self.api_key.clone())
+            .base_url(self.base_url.clone())
+            .api(ApiKind::ChatCompletions);
+        if let Some(name) = &self.auth_header {
+            provider = provider.auth_header(name.clone());
+        }
+        provider
+    }
+}
+
+impl From<Compatible> for CloudProvider {
+    fn from(endpoint: Compatible) -> Self {
+        Self::Compatible(endpoint.client())
+    }
+}
+
+impl From<Compatible> for CloudModelProvider {
+    fn from(endpoint: Compatible) -> Self {
+        Self::Compatible(endpoint.provider())
+    }
+}
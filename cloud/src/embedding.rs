@@ -0,0 +1,72 @@
+//! Provider-agnostic text embeddings.
+
+use aither_core::{EmbeddingModel, Result};
+
+use crate::{CloudProvider, Gemini, OpenAI};
+
+/// Embedding model from any cloud provider with an embeddings API.
+///
+/// Claude and Copilot have no embeddings API, so they have no variant here.
+#[derive(Clone, Debug)]
+pub enum CloudEmbedding {
+    /// `OpenAI` embeddings.
+    OpenAI(OpenAI),
+    /// Google Gemini embeddings.
+    Gemini(Gemini),
+    /// Embeddings from an OpenAI-compatible endpoint.
+    Compatible(OpenAI),
+}
+
+impl From<OpenAI> for CloudEmbedding {
+    fn from(client: OpenAI) -> Self {
+        Self::OpenAI(client)
+    }
+}
+
+impl From<Gemini> for CloudEmbedding {
+    fn from(client: Gemini) -> Self {
+        Self::Gemini(client)
+    }
+}
+
+impl CloudProvider {
+    /// The embedding model of this provider, if it has one.
+    ///
+    /// Uses the embedding model configured on the underlying client.
+    #[must_use]
+    pub fn embedding(&self) -> Option<CloudEmbedding> {
+        match self {
+            Self::OpenAI(client) => Some(CloudEmbedding::OpenAI(client.clone())),
+            Self::Gemini(client) => Some(CloudEmbedding::Gemini(client.clone())),
+            Self::Compatible(client) => Some(CloudEmbedding::Compatible(client.clone())),
+            Self::Claude(_) | Self::Copilot(_) => None,
+        }
+    }
+}
+
+impl EmbeddingModel for CloudEmbedding {
+    fn dim(&self) -> usize {
+        match self {
+            Self::OpenAI(inner) | Self::Compatible(inner) => inner.dim(),
+            Self::Gemini(inner) => inner.dim(),
+        }
+    }
+
+    fn embed(&self, text: &str) -> impl Future<Output = Result<Vec<f32>>> + Send {
+        async move {
+            match self {
+                Self::OpenAI(inner) | Self::Compatible(inner) => inner.embed(text).await,
+                Self::Gemini(inner) => inner.embed(text).await,
+            }
+        }
+    }
+
+    fn embed_batch(&self, texts: &[&str]) -> impl Future<Output = Result<Vec<Vec<f32>>>> + Send {
+        async move {
+            match self {
+                Self::OpenAI(inner) | Self::Compatible(inner) => inner.embed_batch(texts).await,
+                Self::Gemini(inner) => inner.embed_batch(texts).await,
+            }
+        }
+    }
+}
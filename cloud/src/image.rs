@@ -0,0 +1,97 @@
+//! Provider-agnostic image generation.
+
+use aither_core::image::{Data, ImageGenerator, Prompt, Size};
+use futures_core::Stream;
+use futures_lite::StreamExt;
+
+use crate::{CloudError, CloudProvider, Gemini, OpenAI};
+
+/// Image generator from any cloud provider with an image API.
+///
+/// Claude and Copilot cannot generate images, so they have no variant here.
+#[derive(Clone, Debug)]
+pub enum CloudImageGenerator {
+    /// `OpenAI` image models.
+    OpenAI(OpenAI),
+    /// Google Gemini image models.
+    Gemini(Gemini),
+}
+
+impl From<OpenAI> for CloudImageGenerator {
+    fn from(client: OpenAI) -> Self {
+        Self::OpenAI(client)
+    }
+}
+
+impl From<Gemini> for CloudImageGenerator {
+    fn from(client: Gemini) -> Self {
+        Self::Gemini(client)
+    }
+}
+
+impl CloudProvider {
+    /// The image generator of this provider, if it has one.
+    ///
+    /// Uses the image model configured on the underlying client.
+    #[must_use]
+    pub fn image_generator(&self) -> Option<CloudImageGenerator> {
+        match self {
+            Self::OpenAI(client) => Some(CloudImageGenerator::OpenAI(client.clone())),
+            Self::Gemini(client) => Some(CloudImageGenerator::Gemini(client.clone())),
+            Self::Claude(_) | Self::Copilot(_) | Self::Compatible(_) => None,
+        }
+    }
+}
+
+impl ImageGenerator for CloudImageGenerator {
+    type Error = CloudError;
+
+    fn create(
+        &self,
+        prompt: Prompt,
+        size: Size,
+    ) -> impl Stream<Item = Result<Data, Self::Error>> + Send {
+        let generator = self.clone();
+        async_stream::stream! {
+            match generator {
+                Self::OpenAI(inner) => {
+                    let mut stream = std::pin::pin!(inner.create(prompt, size));
+                    while let Some(result) = stream.next().await {
+                        yield result.map_err(CloudError::from);
+                    }
+                }
+                Self::Gemini(inner) => {
+                    let mut stream = std::pin::pin!(inner.create(prompt, size));
+                    while let Some(result) = stream.next().await {
+                        yield result.map_err(CloudError::from);
+                    }
+                }
+            }
+        }
+    }
+
+    fn edit(
+        &self,
+        prompt: Prompt,
+        mask: &[u8],
+    ) -> impl Stream<Item = Result<Data, Self::Error>> + Send {
+        let generator = self.clone();
+        let mask = mask.to_vec();
+        async_stream::stream! {
+            match generator {
+                Self::OpenAI(inner) => {
+                    let mut stream = std::pin::pin!(inner.edit(prompt, &mask));
+                    while let Some(result) = stream.next().await {
+                        yield result.map_err(CloudError::from);
+                    }
+                }
+                Self::Gemini(inner) => {
+                    let mut stream = std::pin::pin!(inner.edit(prompt, &mask));
+                    while let Some(result) = stream.next().await {
+                        yield result.map_err(CloudError::from);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -5,16 +5,44 @@
 //!
 //! It also provides `CloudModelProvider` enum that implements `LanguageModelProvider`,
 //! allowing unified model listing and instantiation across providers.
+//!
+//! [`Compatible`] covers any other OpenAI-compatible endpoint, such as
+//! `OpenRouter`, Together, Groq, vLLM or ollama.
+//!
+//! [`CloudEmbedding`] and [`CloudImageGenerator`] do the same for the
+//! providers' embedding and image APIs, so higher-level crates can stay
+//! provider-agnostic at runtime.
+//!
+//! [`RoutedModel`] fails over between several providers when one of them is
+//! rate limited or unavailable, and [`ModelMiddleware`] adds logging,
+//! redaction, request rewriting, retries and usage accounting (see
+//! [`usage`]) on top of any of them.
+
+mod compatible;
+mod embedding;
+mod image;
+mod middleware;
+mod router;
+pub mod usage;
 
 pub use aither_claude::{self as claude, Claude, ClaudeProvider};
 pub use aither_copilot::{self as copilot, Copilot, CopilotProvider};
 pub use aither_gemini::{self as gemini, Gemini, GeminiProvider};
 pub use aither_openai::{self as openai, OpenAI, OpenAIProvider};
 
+pub use compatible::{
+    Compatible, GROQ_BASE_URL, OLLAMA_BASE_URL, TOGETHER_BASE_URL, VLLM_BASE_URL,
+};
+pub use embedding::CloudEmbedding;
+pub use image::CloudImageGenerator;
+pub use middleware::{Logging, Middleware, ModelMiddleware, Redact, RetryPolicy};
+pub use router::{Route, RoutedModel, RoutingPolicy, RoutingStrategy};
+pub use usage::{UsageAggregator, UsageRecord, UsageSink, UsageTotals};
+
 use aither_core::{
-    LanguageModel,
+    LanguageModel, ProviderRetryInfo, RetryInfo,
     llm::{
-        Event, LLMRequest, LanguageModelProvider, model::Profile,
+        Event, LLMRequest, LanguageModelProvider, LlmError, LlmErrorKind, model::Profile,
         provider::Profile as ProviderProfile,
     },
 };
@@ -32,6 +60,8 @@ pub enum CloudProvider {
     Gemini(Gemini),
     /// GitHub Copilot models.
     Copilot(Copilot),
+    /// Models behind any OpenAI-compatible endpoint (see [`Compatible`]).
+    Compatible(OpenAI),
 }
 
 impl From<OpenAI> for CloudProvider {
@@ -65,6 +95,7 @@ impl std::fmt::Debug for CloudProvider {
             Self::Claude(_) => f.debug_tuple("CloudProvider::Claude").finish(),
             Self::Gemini(_) => f.debug_tuple("CloudProvider::Gemini").finish(),
             Self::Copilot(_) => f.debug_tuple("CloudProvider::Copilot").finish(),
+            Self::Compatible(_) => f.debug_tuple("CloudProvider::Compatible").finish(),
         }
     }
 }
@@ -84,6 +115,43 @@ pub enum CloudError {
     /// GitHub Copilot API error.
     #[error("Copilot error: {0}")]
     Copilot(#[from] aither_copilot::CopilotError),
+    /// A [`RoutedModel`] was used without any routes.
+    #[error("no routes configured")]
+    NoRoutes,
+}
+
+impl CloudError {
+    /// Returns `true` if the request may succeed when retried or sent to
+    /// another provider: rate limits, server errors, timeouts and network
+    /// failures.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        self.retry_info().is_some()
+    }
+}
+
+impl RetryInfo for CloudError {
+    fn retry_info(&self) -> Option<ProviderRetryInfo> {
+        match self {
+            Self::OpenAI(err) => err.retry_info(),
+            Self::Claude(err) => err.retry_info(),
+            Self::Gemini(err) => err.retry_info(),
+            Self::Copilot(err) => err.retry_info(),
+            Self::NoRoutes => None,
+        }
+    }
+}
+
+impl From<CloudError> for LlmError {
+    fn from(err: CloudError) -> Self {
+        match err {
+            CloudError::OpenAI(err) => err.into(),
+            CloudError::Claude(err) => err.into(),
+            CloudError::Gemini(err) => err.into(),
+            CloudError::Copilot(err) => err.into(),
+            CloudError::NoRoutes => Self::new(LlmErrorKind::InvalidRequest, err.to_string()),
+        }
+    }
 }
 
 impl LanguageModel for CloudProvider {
@@ -98,6 +166,7 @@ impl LanguageModel for CloudProvider {
             Self::Claude(inner) => ProviderInner::Claude(inner.clone()),
             Self::Gemini(inner) => ProviderInner::Gemini(inner.clone()),
             Self::Copilot(inner) => ProviderInner::Copilot(inner.clone()),
+            Self::Compatible(inner) => ProviderInner::OpenAI(inner.clone()),
         };
 
         async_stream::stream! {
@@ -137,6 +206,7 @@ impl LanguageModel for CloudProvider {
                 Self::Claude(inner) => inner.profile().await,
                 Self::Gemini(inner) => inner.profile().await,
                 Self::Copilot(inner) => inner.profile().await,
+                Self::Compatible(inner) => inner.profile().await,
             }
         }
     }
@@ -150,7 +220,8 @@ enum ProviderInner {
     Copilot(Copilot),
 }
 
-/// Unified model provider wrapping `OpenAI`, Claude, Gemini, and Copilot providers.
+/// Unified model provider wrapping `OpenAI`, Claude, Gemini, Copilot, and
+/// OpenAI-compatible providers.
 ///
 /// Implements `LanguageModelProvider` to allow unified model listing and instantiation.
 #[derive(Clone, Debug)]
@@ -163,6 +234,8 @@ pub enum CloudModelProvider {
     Gemini(GeminiProvider),
     /// GitHub Copilot provider.
     Copilot(CopilotProvider),
+    /// Any OpenAI-compatible endpoint (see [`Compatible`]).
+    Compatible(OpenAIProvider),
 }
 
 impl From<OpenAIProvider> for CloudModelProvider {
@@ -203,6 +276,7 @@ impl LanguageModelProvider for CloudModelProvider {
                 Self::Claude(p) => p.list_models().await.map_err(CloudError::from),
                 Self::Gemini(p) => p.list_models().await.map_err(CloudError::from),
                 Self::Copilot(p) => p.list_models().await.map_err(CloudError::from),
+                Self::Compatible(p) => p.list_models().await.map_err(CloudError::from),
             }
         }
     }
@@ -235,6 +309,11 @@ impl LanguageModelProvider for CloudModelProvider {
                     .await
                     .map(CloudProvider::from)
                     .map_err(CloudError::from),
+                Self::Compatible(p) => p
+                    .get_model(&name)
+                    .await
+                    .map(CloudProvider::Compatible)
+                    .map_err(CloudError::from),
             }
         }
     }
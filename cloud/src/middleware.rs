@@ -0,0 +1,324 @@
+//! Request-level middleware for any cloud model.
+//!
+//! [`ModelMiddleware`] wraps a [`CloudProvider`](crate::CloudProvider) or
+//! [`RoutedModel`](crate::RoutedModel) and runs every request and event
+//! through a stack of [`Middleware`] layers, so cross-cutting concerns are
+//! configured once regardless of the provider underneath:
+//!
+//! ```ignore
+//! let model = ModelMiddleware::new(cloud)
+//!     .layer(Logging)
+//!     .layer(Redact::new(|text| text.replace(&api_token, "[REDACTED]")))
+//!     .retry(RetryPolicy::default())
+//!     .usage_sink(usage.clone());
+//! ```
+
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use aither_core::{
+    LanguageModel, ProviderRetryInfo, RetryInfo,
+    llm::{Event, LLMRequest, Message, model::Profile},
+};
+use futures_core::Stream;
+use futures_lite::StreamExt;
+
+use crate::{CloudError, UsageRecord, UsageSink};
+
+/// A layer that observes or rewrites requests and responses.
+///
+/// All methods default to doing nothing.
+pub trait Middleware: Send + Sync {
+    /// Inspect or rewrite a request before it is sent.
+    ///
+    /// Runs once per call, before any retries.
+    fn on_request(&self, request: &mut LLMRequest) {
+        let _ = request;
+    }
+
+    /// Inspect or rewrite an event before it reaches the caller.
+    fn on_event(&self, event: &mut Event) {
+        let _ = event;
+    }
+
+    /// Observe an error, including errors that are about to be retried.
+    fn on_error(&self, error: &CloudError) {
+        let _ = error;
+    }
+}
+
+/// Retry policy for overloaded or unavailable providers.
+///
+/// Only retryable errors (see [`CloudError::is_retryable`]) that happen
+/// before the first event are retried, so no output is ever duplicated.
+/// When the provider says how long to wait, that delay is used instead of
+/// the exponential backoff, still capped at the maximum delay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    /// Set the maximum number of retries.
+    #[must_use]
+    pub const fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set the delay before the first retry; later retries double it.
+    #[must_use]
+    pub const fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Cap the delay between retries.
+    #[must_use]
+    pub const fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    fn delay_for_attempt(&self, attempt: u32, retry: ProviderRetryInfo) -> Duration {
+        retry
+            .retry_after
+            .unwrap_or_else(|| {
+                self.initial_delay
+                    .saturating_mul(2u32.saturating_pow(attempt))
+            })
+            .min(self.max_delay)
+    }
+}
+
+/// A cloud model wrapped in middleware layers.
+#[derive(Clone)]
+pub struct ModelMiddleware<M> {
+    model: M,
+    layers: Vec<Arc<dyn Middleware>>,
+    retry: RetryPolicy,
+    sinks: Vec<Arc<dyn UsageSink>>,
+    /// Model name for usage records, read from the profile on first use.
+    model_name: Arc<OnceLock<String>>,
+}
+
+impl<M: std::fmt::Debug> std::fmt::Debug for ModelMiddleware<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelMiddleware")
+            .field("model", &self.model)
+            .field("layers", &self.layers.len())
+            .field("retry", &self.retry)
+            .field("sinks", &self.sinks.len())
+            .finish()
+    }
+}
+
+impl<M> ModelMiddleware<M> {
+    /// Wrap `model` without layers or retries.
+    #[must_use]
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            layers: Vec::new(),
+            retry: RetryPolicy::none(),
+            sinks: Vec::new(),
+            model_name: Arc::default(),
+        }
+    }
+
+    /// Add a layer. Layers run in the order they were added.
+    #[must_use]
+    pub fn layer(mut self, layer: impl Middleware + 'static) -> Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// Retry overloaded or unavailable providers with `policy`.
+    #[must_use]
+    pub const fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Report the usage of every request to `sink`.
+    ///
+    /// Records are labelled with the name from the wrapped model's profile,
+    /// which is read once on the first report.
+    #[must_use]
+    pub fn usage_sink(mut self, sink: impl UsageSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// The wrapped model.
+    #[must_use]
+    pub const fn inner(&self) -> &M {
+        &self.model
+    }
+}
+
+impl<M: LanguageModel<Error = CloudError>> LanguageModel for ModelMiddleware<M> {
+    type Error = CloudError;
+
+    fn respond(
+        &self,
+        mut request: LLMRequest,
+    ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+        for layer in &self.layers {
+            layer.on_request(&mut request);
+        }
+
+        async_stream::stream! {
+            let start = Instant::now();
+            let mut attempt = 0;
+            'attempts: loop {
+                let mut stream = std::pin::pin!(self.model.respond(request.clone()));
+                let mut started = false;
+                while let Some(result) = stream.next().await {
+                    match result {
+                        Ok(mut event) => {
+                            started = true;
+                            for layer in &self.layers {
+                                layer.on_event(&mut event);
+                            }
+                            if let Event::Usage(usage) = &event
+                                && !self.sinks.is_empty()
+                            {
+                                let model = self.model_name().await;
+                                let record = UsageRecord::new(model, usage.clone(), start.elapsed());
+                                for sink in &self.sinks {
+                                    sink.record(&record);
+                                }
+                            }
+                            yield Ok(event);
+                        }
+                        Err(err) => {
+                            for layer in &self.layers {
+                                layer.on_error(&err);
+                            }
+                            if !started
+                                && attempt < self.retry.max_retries
+                                && let Some(retry) = err.retry_info()
+                            {
+                                sleep(self.retry.delay_for_attempt(attempt, retry)).await;
+                                attempt += 1;
+                                continue 'attempts;
+                            }
+                            yield Err(err);
+                            return;
+                        }
+                    }
+                }
+                return;
+            }
+        }
+    }
+
+    fn profile(&self) -> impl std::future::Future<Output = Profile> + Send {
+        self.model.profile()
+    }
+}
+
+impl<M: LanguageModel> ModelMiddleware<M> {
+    async fn model_name(&self) -> String {
+        if let Some(name) = self.model_name.get() {
+            return name.clone();
+        }
+        let name = self.model.profile().await.name;
+        self.model_name.get_or_init(|| name).clone()
+    }
+}
+
+/// Logs requests, usage and errors with `tracing`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Logging;
+
+impl Middleware for Logging {
+    fn on_request(&self, request: &mut LLMRequest) {
+        tracing::info!(
+            messages = request.messages().len(),
+            tools = request.tool_definitions().len(),
+            "Sending model request"
+        );
+    }
+
+    fn on_event(&self, event: &mut Event) {
+        if let Event::Usage(usage) = event {
+            tracing::info!(
+                prompt_tokens = ?usage.prompt_tokens,
+                completion_tokens = ?usage.completion_tokens,
+                cache_read_tokens = ?usage.cache_read_tokens,
+                cost_usd = ?usage.cost_usd,
+                "Model request finished"
+            );
+        }
+    }
+
+    fn on_error(&self, error: &CloudError) {
+        tracing::warn!(%error, retryable = error.is_retryable(), "Model request failed");
+    }
+}
+
+/// Rewrites message text before it leaves the process.
+///
+/// Applies to every message in the request: system, user, assistant and tool
+/// results. Streamed output is left alone, since a sensitive value may be
+/// split across chunks.
+pub struct Redact<F> {
+    redact: F,
+}
+
+impl<F> std::fmt::Debug for Redact<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Redact").finish_non_exhaustive()
+    }
+}
+
+impl<F: Fn(&str) -> String + Send + Sync> Redact<F> {
+    /// Redact message text with `redact`.
+    #[must_use]
+    pub const fn new(redact: F) -> Self {
+        Self { redact }
+    }
+}
+
+impl<F: Fn(&str) -> String + Send + Sync> Middleware for Redact<F> {
+    fn on_request(&self, request: &mut LLMRequest) {
+        for message in request.messages_mut() {
+            let content = match message {
+                Message::User { content, .. }
+                | Message::Assistant { content, .. }
+                | Message::System { content }
+                | Message::Tool { content, .. } => content,
+            };
+            *content = (self.redact)(content);
+        }
+    }
+}
+
+/// Sleep for the given duration (runtime-agnostic).
+async fn sleep(duration: Duration) {
+    async_io::Timer::after(duration).await;
+}
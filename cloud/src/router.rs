@@ -0,0 +1,279 @@
+//! Failover routing across cloud providers.
+//!
+//! [`RoutedModel`] tries its routes in policy order and moves on to the next
+//! one when a provider is rate limited, overloaded or unreachable. Routes
+//! that keep failing are benched for a cooldown so later requests skip them
+//! without paying for another failed round trip.
+//!
+//! ```ignore
+//! let model = RoutedModel::new(RoutingPolicy::default())
+//!     .route(Route::new(Claude::new(claude_key).with_model("claude-sonnet-4.5")))
+//!     .route(Route::new(OpenAI::new(openai_key).with_model("gpt-4.1")));
+//! ```
+//!
+//! Failover only happens before the first event is delivered. Once a
+//! provider has started streaming, a later error is passed through as is,
+//! since replaying the request elsewhere would duplicate output.
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use aither_core::{
+    LanguageModel,
+    llm::{Event, LLMRequest, model::Profile},
+};
+use futures_core::Stream;
+use futures_lite::StreamExt;
+
+use crate::{CloudError, CloudProvider};
+
+/// Default number of consecutive failures before a route is benched.
+const DEFAULT_MAX_FAILURES: u32 = 3;
+
+/// Default time a benched route is skipped.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Order in which routes are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoutingStrategy {
+    /// Try routes in the order they were added.
+    #[default]
+    Priority,
+    /// Try the cheapest route first; routes without a cost come last, in
+    /// the order they were added.
+    Cost,
+}
+
+/// How a [`RoutedModel`] picks and benches routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoutingPolicy {
+    strategy: RoutingStrategy,
+    max_failures: u32,
+    cooldown: Duration,
+}
+
+impl Default for RoutingPolicy {
+    fn default() -> Self {
+        Self {
+            strategy: RoutingStrategy::Priority,
+            max_failures: DEFAULT_MAX_FAILURES,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+}
+
+impl RoutingPolicy {
+    /// Create a priority-ordered policy with default health settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the order in which routes are tried.
+    #[must_use]
+    pub const fn strategy(mut self, strategy: RoutingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Bench a route after `failures` consecutive failures (minimum 1).
+    #[must_use]
+    pub const fn max_failures(mut self, failures: u32) -> Self {
+        self.max_failures = if failures == 0 { 1 } else { failures };
+        self
+    }
+
+    /// Set how long a benched route is skipped.
+    #[must_use]
+    pub const fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+/// A provider the router can send requests to.
+#[derive(Debug)]
+pub struct Route {
+    provider: CloudProvider,
+    cost: Option<f64>,
+    health: Mutex<Health>,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    benched_until: Option<Instant>,
+}
+
+impl Route {
+    /// Create a route without cost information.
+    #[must_use]
+    pub fn new(provider: impl Into<CloudProvider>) -> Self {
+        Self {
+            provider: provider.into(),
+            cost: None,
+            health: Mutex::new(Health::default()),
+        }
+    }
+
+    /// Set the relative cost used by [`RoutingStrategy::Cost`].
+    ///
+    /// Any consistent unit works, e.g. USD per million output tokens.
+    #[must_use]
+    pub const fn cost(mut self, cost: f64) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// The wrapped provider.
+    #[must_use]
+    pub const fn provider(&self) -> &CloudProvider {
+        &self.provider
+    }
+
+    /// Returns `true` if the route is not benched.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.health
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .benched_until
+            .is_none_or(|until| Instant::now() >= until)
+    }
+
+    fn record_success(&self) {
+        *self.health.lock().unwrap_or_else(PoisonError::into_inner) = Health::default();
+    }
+
+    fn record_failure(&self, policy: &RoutingPolicy) {
+        let mut health = self.health.lock().unwrap_or_else(PoisonError::into_inner);
+        health.consecutive_failures += 1;
+        if health.consecutive_failures >= policy.max_failures {
+            health.benched_until = Some(Instant::now() + policy.cooldown);
+            health.consecutive_failures = 0;
+        }
+    }
+}
+
+/// A language model that fails over between cloud providers.
+///
+/// Cloning is cheap and clones share route health.
+#[derive(Debug, Clone)]
+pub struct RoutedModel {
+    routes: Arc<Vec<Route>>,
+    policy: RoutingPolicy,
+}
+
+impl RoutedModel {
+    /// Create a router without routes.
+    #[must_use]
+    pub fn new(policy: RoutingPolicy) -> Self {
+        Self {
+            routes: Arc::new(Vec::new()),
+            policy,
+        }
+    }
+
+    /// Add a route.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the router was already cloned; add all routes first.
+    #[must_use]
+    pub fn route(mut self, route: Route) -> Self {
+        Arc::get_mut(&mut self.routes)
+            .expect("routes must be added before the router is cloned")
+            .push(route);
+        self
+    }
+
+    /// The configured routes, in the order they were added.
+    #[must_use]
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// The routing policy.
+    #[must_use]
+    pub const fn policy(&self) -> &RoutingPolicy {
+        &self.policy
+    }
+
+    /// Route indices in the order they should be tried.
+    ///
+    /// Benched routes are kept as a last resort after the healthy ones.
+    fn candidates(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.routes.len()).collect();
+        if self.policy.strategy == RoutingStrategy::Cost {
+            // Stable sort keeps insertion order among equal or unknown costs.
+            order.sort_by(|&a, &b| {
+                let cost = |i: usize| self.routes[i].cost.unwrap_or(f64::INFINITY);
+                cost(a).total_cmp(&cost(b))
+            });
+        }
+        let (healthy, benched): (Vec<usize>, Vec<usize>) = order
+            .into_iter()
+            .partition(|&i| self.routes[i].is_healthy());
+        healthy.into_iter().chain(benched).collect()
+    }
+}
+
+impl LanguageModel for RoutedModel {
+    type Error = CloudError;
+
+    fn respond(
+        &self,
+        request: LLMRequest,
+    ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+        let routes = self.routes.clone();
+        let policy = self.policy;
+        let candidates = self.candidates();
+
+        async_stream::stream! {
+            let mut last_error = None;
+            for index in candidates {
+                let route = &routes[index];
+                let mut stream = std::pin::pin!(route.provider.respond(request.clone()));
+
+                match stream.next().await {
+                    Some(Err(err)) if err.is_retryable() => {
+                        route.record_failure(&policy);
+                        last_error = Some(err);
+                        continue;
+                    }
+                    Some(Err(err)) => {
+                        yield Err(err);
+                        return;
+                    }
+                    Some(Ok(event)) => yield Ok(event),
+                    None => {
+                        route.record_success();
+                        return;
+                    }
+                }
+
+                let mut failed = false;
+                while let Some(result) = stream.next().await {
+                    failed |= result.as_ref().is_err_and(CloudError::is_retryable);
+                    yield result;
+                }
+                if failed {
+                    route.record_failure(&policy);
+                } else {
+                    route.record_success();
+                }
+                return;
+            }
+            yield Err(last_error.unwrap_or(CloudError::NoRoutes));
+        }
+    }
+
+    fn profile(&self) -> impl std::future::Future<Output = Profile> + Send {
+        let routes = self.routes.clone();
+        let first = self.candidates().first().copied();
+        async move {
+            let index = first.expect("RoutedModel has no routes");
+            routes[index].provider.profile().await
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Usage accounting for billing and dashboards.
+//!
+//! [`ModelMiddleware::usage_sink`](crate::ModelMiddleware::usage_sink) turns
+//! every [`Event::Usage`](aither_core::llm::Event::Usage) into a
+//! [`UsageRecord`] and hands it to a [`UsageSink`]. [`UsageAggregator`]
+//! keeps records in memory and exports them as CSV or JSON:
+//!
+//! ```ignore
+//! let usage = Arc::new(UsageAggregator::new());
+//! let model = ModelMiddleware::new(cloud).usage_sink(usage.clone());
+//! // ... run requests ...
+//! std::fs::write("usage.csv", usage.to_csv())?;
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aither_core::llm::Usage;
+use serde::Serialize;
+
+/// Usage of a single model request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageRecord {
+    /// When the usage was reported.
+    pub timestamp: SystemTime,
+    /// Model that served the request.
+    pub model: String,
+    /// Provider of the model, e.g. `openai` or `anthropic`.
+    pub provider: String,
+    /// Token counts and cost.
+    ///
+    /// `cost_usd` is estimated from `aither-models` pricing when the
+    /// provider did not report it.
+    pub usage: Usage,
+    /// Time from sending the request until usage was reported, including
+    /// retries.
+    pub latency: Duration,
+}
+
+impl UsageRecord {
+    /// Record `usage` of `model`, filling in the provider and cost from the
+    /// model registry where possible.
+    #[must_use]
+    pub fn new(model: impl Into<String>, mut usage: Usage, latency: Duration) -> Self {
+        let model = model.into();
+        let info = aither_models::lookup(&model);
+        if usage.cost_usd.is_none() {
+            usage.cost_usd = aither_models::estimate_cost(&model, &usage);
+        }
+        Self {
+            timestamp: SystemTime::now(),
+            provider: info.map_or_else(String::new, |info| info.provider.to_string()),
+            model,
+            usage,
+            latency,
+        }
+    }
+}
+
+/// Receives a [`UsageRecord`] for every model request.
+///
+/// Sinks are called on the response stream, so they should hand slow work,
+/// such as network writes, off to another task.
+pub trait UsageSink: Send + Sync {
+    /// Record the usage of one request.
+    fn record(&self, record: &UsageRecord);
+}
+
+impl<S: UsageSink + ?Sized> UsageSink for Arc<S> {
+    fn record(&self, record: &UsageRecord) {
+        (**self).record(record);
+    }
+}
+
+/// Summed usage over several requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    /// Number of requests.
+    pub requests: u64,
+    /// Prompt tokens, including cached tokens where the provider counts them.
+    pub prompt_tokens: u64,
+    /// Completion tokens.
+    pub completion_tokens: u64,
+    /// Reasoning tokens.
+    pub reasoning_tokens: u64,
+    /// Tokens read from the prompt cache.
+    pub cache_read_tokens: u64,
+    /// Tokens written to the prompt cache.
+    pub cache_write_tokens: u64,
+    /// Cost in USD of the requests with known cost.
+    pub cost_usd: f64,
+    /// Summed latency.
+    pub latency: Duration,
+}
+
+impl UsageTotals {
+    /// Add one request.
+    pub fn add(&mut self, record: &UsageRecord) {
+        let tokens = |count: Option<u32>| count.map_or(0, u64::from);
+        let usage = &record.usage;
+        self.requests += 1;
+        self.prompt_tokens += tokens(usage.prompt_tokens);
+        self.completion_tokens += tokens(usage.completion_tokens);
+        self.reasoning_tokens += tokens(usage.reasoning_tokens);
+        self.cache_read_tokens += tokens(usage.cache_read_tokens);
+        self.cache_write_tokens += tokens(usage.cache_write_tokens);
+        self.cost_usd += usage.cost_usd.unwrap_or(0.0);
+        self.latency += record.latency;
+    }
+
+    /// Mean latency per request.
+    #[must_use]
+    pub fn mean_latency(&self) -> Duration {
+        u32::try_from(self.requests)
+            .ok()
+            .filter(|&requests| requests > 0)
+            .map_or(Duration::ZERO, |requests| self.latency / requests)
+    }
+}
+
+/// In-memory [`UsageSink`] that keeps every record.
+#[derive(Debug, Default)]
+pub struct UsageAggregator {
+    records: Mutex<Vec<UsageRecord>>,
+}
+
+impl UsageAggregator {
+    /// Create an empty aggregator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All records, oldest first.
+    #[must_use]
+    pub fn records(&self) -> Vec<UsageRecord> {
+        self.lock().clone()
+    }
+
+    /// Totals over all records.
+    #[must_use]
+    pub fn totals(&self) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for record in self.lock().iter() {
+            totals.add(record);
+        }
+        totals
+    }
+
+    /// Totals per model.
+    #[must_use]
+    pub fn totals_by_model(&self) -> BTreeMap<String, UsageTotals> {
+        let mut totals = BTreeMap::<String, UsageTotals>::new();
+        for record in self.lock().iter() {
+            totals.entry(record.model.clone()).or_default().add(record);
+        }
+        totals
+    }
+
+    /// Remove all records.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Export all records as CSV. See [`to_csv`].
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        to_csv(&self.lock())
+    }
+
+    /// Export all records as JSON. See [`to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        to_json(&self.lock())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<UsageRecord>> {
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl UsageSink for UsageAggregator {
+    fn record(&self, record: &UsageRecord) {
+        self.lock().push(record.clone());
+    }
+}
+
+/// Flat export row shared by the CSV and JSON exporters.
+#[derive(Serialize)]
+struct Row<'a> {
+    timestamp: u64,
+    model: &'a str,
+    provider: &'a str,
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+    reasoning_tokens: Option<u32>,
+    cache_read_tokens: Option<u32>,
+    cache_write_tokens: Option<u32>,
+    latency_ms: u64,
+    cost_usd: Option<f64>,
+}
+
+impl<'a> From<&'a UsageRecord> for Row<'a> {
+    fn from(record: &'a UsageRecord) -> Self {
+        Self {
+            timestamp: record
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            model: &record.model,
+            provider: &record.provider,
+            prompt_tokens: record.usage.prompt_tokens,
+            completion_tokens: record.usage.completion_tokens,
+            reasoning_tokens: record.usage.reasoning_tokens,
+            cache_read_tokens: record.usage.cache_read_tokens,
+            cache_write_tokens: record.usage.cache_write_tokens,
+            latency_ms: u64::try_from(record.latency.as_millis()).unwrap_or(u64::MAX),
+            cost_usd: record.usage.cost_usd,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "timestamp,model,provider,prompt_tokens,completion_tokens,reasoning_tokens,cache_read_tokens,cache_write_tokens,latency_ms,cost_usd";
+
+/// Export records as CSV, one row per request.
+///
+/// Timestamps are Unix seconds; unknown counts and costs are left empty.
+#[must_use]
+pub fn to_csv(records: &[UsageRecord]) -> String {
+    fn field(value: Option<impl ToString>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for row in records.iter().map(Row::from) {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{}",
+            row.timestamp,
+            csv_text(row.model),
+            csv_text(row.provider),
+            field(row.prompt_tokens),
+            field(row.completion_tokens),
+            field(row.reasoning_tokens),
+            field(row.cache_read_tokens),
+            field(row.cache_write_tokens),
+            row.latency_ms,
+            field(row.cost_usd),
+        );
+    }
+    csv
+}
+
+/// Export records as a JSON array with the same fields as [`to_csv`].
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn to_json(records: &[UsageRecord]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&records.iter().map(Row::from).collect::<Vec<_>>())
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break.
+fn csv_text(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
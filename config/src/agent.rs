@@ -0,0 +1,42 @@
+//! Agent behavior settings.
+
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Settings applied to every agent built from the configuration.
+///
+/// Unset fields keep the agent's defaults.
+///
+/// ```toml
+/// [agent]
+/// max_iterations = 50
+/// token_budget = 2000000
+/// skills = "/home/me/.aither/skills"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AgentSettings {
+    /// Maximum number of model requests per run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<usize>,
+    /// Tokens a run may spend; plans are sized to fit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<u64>,
+    /// System prompt replacing the default one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Persona prompt added to the system prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona_prompt: Option<String>,
+    /// File the conversation transcript is written to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<PathBuf>,
+    /// Directory of skills to load.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<PathBuf>,
+    /// Directory of subagent definitions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagents: Option<PathBuf>,
+}
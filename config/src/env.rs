@@ -0,0 +1,115 @@
+//! Environment variable expansion in configuration values.
+//!
+//! Every string in the file may reference the environment: `${NAME}` is
+//! replaced by the variable's value, and `${NAME:-fallback}` uses
+//! `fallback` when the variable is unset or empty. `$${` writes a literal
+//! `${`. A `$` not followed by `{` is kept as is.
+
+use serde_json::Value;
+
+use crate::ConfigError;
+
+/// Expands references in every string of `value`, in place.
+///
+/// `field` is the dotted path of `value`, used in errors.
+pub fn expand_value(
+    value: &mut Value,
+    field: &str,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), ConfigError> {
+    match value {
+        Value::String(text) => *text = expand(text, field, env)?,
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                expand_value(item, &format!("{field}[{index}]"), env)?;
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                let path = if field.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{field}.{key}")
+                };
+                expand_value(item, &path, env)?;
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+/// Expands the references in `text`.
+pub fn expand(
+    text: &str,
+    field: &str,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, ConfigError> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix("${") {
+            out.push_str("${");
+            rest = escaped;
+        } else if let Some(reference) = after.strip_prefix('{') {
+            let end = reference
+                .find('}')
+                .ok_or_else(|| ConfigError::UnterminatedReference {
+                    field: field.to_string(),
+                })?;
+            let (name, fallback) = match reference[..end].split_once(":-") {
+                Some((name, fallback)) => (name, Some(fallback)),
+                None => (&reference[..end], None),
+            };
+            let value = env(name).filter(|value| fallback.is_none() || !value.is_empty());
+            match (value, fallback) {
+                (Some(value), _) => out.push_str(&value),
+                (None, Some(fallback)) => out.push_str(fallback),
+                (None, None) => {
+                    return Err(ConfigError::MissingEnv {
+                        var: name.to_string(),
+                        field: field.to_string(),
+                    });
+                }
+            }
+            rest = &reference[end + 1..];
+        } else {
+            out.push('$');
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "KEY" => Some("secret".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expands_references() {
+        let expand = |text| expand(text, "field", &env);
+        assert_eq!(expand("Bearer ${KEY}").unwrap(), "Bearer secret");
+        assert_eq!(expand("${MISSING:-none}").unwrap(), "none");
+        assert_eq!(expand("${EMPTY:-none}").unwrap(), "none");
+        assert_eq!(expand("cost: $5, $${KEY}").unwrap(), "cost: $5, ${KEY}");
+        assert!(matches!(
+            expand("${MISSING}"),
+            Err(ConfigError::MissingEnv { var, .. }) if var == "MISSING"
+        ));
+        assert!(matches!(
+            expand("${KEY"),
+            Err(ConfigError::UnterminatedReference { .. })
+        ));
+    }
+}
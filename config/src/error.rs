@@ -0,0 +1,71 @@
+//! Error types for loading configuration.
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Errors that can occur when loading or validating a configuration.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// Failed to read the configuration file.
+    #[error("failed to read config file at {path}: {source}")]
+    ReadFile {
+        /// Path to the file that couldn't be read.
+        path: PathBuf,
+        /// The underlying IO error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The file extension is neither `.toml` nor `.json`.
+    #[error("unsupported config format at {path}: expected a .toml or .json file")]
+    UnknownFormat {
+        /// Path to the file.
+        path: PathBuf,
+    },
+
+    /// The file is not valid TOML.
+    #[error("failed to parse TOML config: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    /// The file is not valid JSON.
+    #[error("failed to parse JSON config: {0}")]
+    Json(serde_json::Error),
+
+    /// The file parsed but doesn't match the configuration schema.
+    #[error("config does not match the schema: {0}")]
+    Schema(serde_json::Error),
+
+    /// A `${VAR}` reference names an unset environment variable.
+    #[error("environment variable {var} referenced by {field} is not set")]
+    MissingEnv {
+        /// Name of the variable.
+        var: String,
+        /// Field containing the reference.
+        field: String,
+    },
+
+    /// A `${` reference is never closed.
+    #[error("unterminated ${{...}} reference in {field}")]
+    UnterminatedReference {
+        /// Field containing the reference.
+        field: String,
+    },
+
+    /// A value is well-formed but not allowed.
+    #[error("invalid config at {field}: {reason}")]
+    Invalid {
+        /// Field holding the value.
+        field: String,
+        /// Why the value was rejected.
+        reason: String,
+    },
+}
+
+impl ConfigError {
+    pub(crate) fn invalid(field: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Invalid {
+            field: field.into(),
+            reason: reason.into(),
+        }
+    }
+}
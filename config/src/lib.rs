@@ -0,0 +1,341 @@
+//! Aither Configuration
+//!
+//! One file configures everything a host needs to run an agent: providers
+//! and their API keys, default models, tool permissions, MCP servers and
+//! agent settings. The agent builder and the `aither` CLI both read it.
+//!
+//! # Format
+//!
+//! The file is TOML or JSON, chosen by extension. Strings may reference
+//! environment variables as `${NAME}` or `${NAME:-fallback}`, so keys need
+//! not be written into the file.
+//!
+//! ```toml
+//! default_provider = "claude"
+//!
+//! [providers.claude]
+//! kind = "claude"
+//! api_key = "${ANTHROPIC_API_KEY}"
+//! model = "claude-sonnet-4-20250514"
+//!
+//! [providers.local]
+//! kind = "compatible"
+//! base_url = "${OLLAMA_URL:-http://localhost:11434/v1}"
+//! model = "qwen3:8b"
+//!
+//! [agent]
+//! max_iterations = 50
+//! skills = "/home/me/.aither/skills"
+//!
+//! [tools]
+//! deny = ["webfetch"]
+//! bash = "network"
+//!
+//! [mcp.github]
+//! command = "npx"
+//! args = ["-y", "@modelcontextprotocol/server-github"]
+//! env = { GITHUB_TOKEN = "${GITHUB_TOKEN}" }
+//! ```
+//!
+//! # Validation
+//!
+//! Unknown fields and wrongly typed values are rejected, as are
+//! inconsistent settings such as a `default_provider` that isn't defined.
+//! [`Config::json_schema`] returns the schema for editor support.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use aither_config::Config;
+//!
+//! let config = Config::load("aither.toml").await?;
+//! if let Some((name, provider)) = config.provider(None) {
+//!     println!("{name}: {:?}", provider.model);
+//! }
+//! ```
+
+mod agent;
+mod env;
+mod error;
+mod mcp;
+mod provider;
+mod tools;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub use agent::AgentSettings;
+pub use error::ConfigError;
+pub use mcp::McpServer;
+pub use provider::{ProviderConfig, ProviderKind};
+pub use tools::{BashApproval, ToolPolicy};
+
+/// Syntax of a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// TOML.
+    Toml,
+    /// JSON.
+    Json,
+}
+
+impl Format {
+    /// Picks the format from the extension of `path`.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A complete configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Provider used when none is chosen explicitly. Must be a key of
+    /// `providers`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_provider: Option<String>,
+    /// Providers by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, ProviderConfig>,
+    /// Agent settings.
+    #[serde(default)]
+    pub agent: AgentSettings,
+    /// Tool permissions.
+    #[serde(default)]
+    pub tools: ToolPolicy,
+    /// MCP servers by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mcp: BTreeMap<String, McpServer>,
+}
+
+impl Config {
+    /// Loads and validates the file at `path`, expanding references to the
+    /// process environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, has an unknown
+    /// extension, or fails to [parse](Self::parse).
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let format = Format::from_path(path).ok_or_else(|| ConfigError::UnknownFormat {
+            path: path.to_path_buf(),
+        })?;
+        let text =
+            async_fs::read_to_string(path)
+                .await
+                .map_err(|source| ConfigError::ReadFile {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+        Self::parse(&text, format)
+    }
+
+    /// Parses and validates `text`, expanding references to the process
+    /// environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is malformed, doesn't match the schema,
+    /// references an unset variable without a fallback, or fails
+    /// [validation](Self::validate).
+    pub fn parse(text: &str, format: Format) -> Result<Self, ConfigError> {
+        Self::parse_with_env(text, format, |name| std::env::var(name).ok())
+    }
+
+    /// Like [`parse`](Self::parse), but looks variables up with `env`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`parse`](Self::parse).
+    pub fn parse_with_env(
+        text: &str,
+        format: Format,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let mut value: serde_json::Value = match format {
+            Format::Toml => toml::from_str(text)?,
+            Format::Json => serde_json::from_str(text).map_err(ConfigError::Json)?,
+        };
+        env::expand_value(&mut value, "", &env)?;
+        let config: Self = serde_json::from_value(value).map_err(ConfigError::Schema)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks settings that the schema alone can't.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] for the first inconsistency found.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(name) = &self.default_provider
+            && !self.providers.contains_key(name)
+        {
+            return Err(ConfigError::invalid(
+                "default_provider",
+                format!("no provider named '{name}' is defined"),
+            ));
+        }
+
+        for (name, provider) in &self.providers {
+            if provider.kind == ProviderKind::Compatible && provider.base_url.is_none() {
+                return Err(ConfigError::invalid(
+                    format!("providers.{name}"),
+                    "compatible providers need a base_url",
+                ));
+            }
+        }
+
+        if self.agent.max_iterations == Some(0) {
+            return Err(ConfigError::invalid(
+                "agent.max_iterations",
+                "must be at least 1",
+            ));
+        }
+        if self.agent.token_budget == Some(0) {
+            return Err(ConfigError::invalid(
+                "agent.token_budget",
+                "must be at least 1",
+            ));
+        }
+
+        if let Some(tool) = self
+            .tools
+            .allow
+            .iter()
+            .find(|tool| self.tools.deny.contains(tool))
+        {
+            return Err(ConfigError::invalid(
+                "tools",
+                format!("'{tool}' is both allowed and denied"),
+            ));
+        }
+
+        for (name, server) in &self.mcp {
+            if server.command.is_some() == server.url.is_some() {
+                return Err(ConfigError::invalid(
+                    format!("mcp.{name}"),
+                    "set exactly one of command and url",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the provider named `name`, or the default one.
+    ///
+    /// Without a name, this is `default_provider`, or the only provider if
+    /// exactly one is defined.
+    #[must_use]
+    pub fn provider(&self, name: Option<&str>) -> Option<(&str, &ProviderConfig)> {
+        let name = match name.or(self.default_provider.as_deref()) {
+            Some(name) => name,
+            None if self.providers.len() == 1 => self.providers.keys().next()?,
+            None => return None,
+        };
+        self.providers
+            .get_key_value(name)
+            .map(|(name, provider)| (name.as_str(), provider))
+    }
+
+    /// JSON Schema of the configuration file.
+    #[must_use]
+    pub fn json_schema() -> serde_json::Value {
+        schemars::schema_for!(Self).to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+default_provider = "claude"
+
+[providers.claude]
+kind = "claude"
+api_key = "${ANTHROPIC_API_KEY}"
+model = "claude-sonnet-4-20250514"
+
+[providers.local]
+kind = "compatible"
+base_url = "${OLLAMA_URL:-http://localhost:11434/v1}"
+
+[agent]
+max_iterations = 50
+
+[tools]
+deny = ["webfetch"]
+bash = "network"
+
+[mcp.github]
+command = "npx"
+args = ["-y", "@modelcontextprotocol/server-github"]
+env = { GITHUB_TOKEN = "${GITHUB_TOKEN}" }
+"#;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "ANTHROPIC_API_KEY" => Some("sk-ant".to_string()),
+            "GITHUB_TOKEN" => Some("ghp".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parses_toml_with_env_references() {
+        let config = Config::parse_with_env(EXAMPLE, Format::Toml, env).unwrap();
+
+        let (name, claude) = config.provider(None).unwrap();
+        assert_eq!(name, "claude");
+        assert_eq!(claude.api_key.as_deref(), Some("sk-ant"));
+        assert_eq!(
+            config.providers["local"].base_url.as_deref(),
+            Some("http://localhost:11434/v1")
+        );
+        assert_eq!(config.agent.max_iterations, Some(50));
+        assert_eq!(config.tools.bash, BashApproval::Network);
+        assert!(!config.tools.allows("webfetch"));
+        assert!(config.tools.allows("bash"));
+        assert_eq!(config.mcp["github"].env["GITHUB_TOKEN"], "ghp");
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(Config::parse(&json, Format::Json).unwrap(), config);
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let parse = |text| Config::parse_with_env(text, Format::Toml, env);
+
+        assert!(matches!(
+            parse("default_provider = \"openai\""),
+            Err(ConfigError::Invalid { field, .. }) if field == "default_provider"
+        ));
+        assert!(matches!(
+            parse("[providers.local]\nkind = \"compatible\""),
+            Err(ConfigError::Invalid { field, .. }) if field == "providers.local"
+        ));
+        assert!(matches!(
+            parse("[agent]\nmax_iteration = 5"),
+            Err(ConfigError::Schema(_))
+        ));
+        assert!(matches!(
+            parse("[mcp.docs]\nurl = \"https://example.com\"\ncommand = \"docs\""),
+            Err(ConfigError::Invalid { field, .. }) if field == "mcp.docs"
+        ));
+        assert!(matches!(
+            parse("[providers.openai]\nkind = \"openai\"\napi_key = \"${OPENAI_API_KEY}\""),
+            Err(ConfigError::MissingEnv { var, .. }) if var == "OPENAI_API_KEY"
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+//! Language model providers.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// API a provider speaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    /// `OpenAI`.
+    OpenAI,
+    /// Anthropic Claude.
+    Claude,
+    /// Google Gemini.
+    Gemini,
+    /// Any OpenAI-compatible endpoint, such as ollama, vLLM or `OpenRouter`.
+    /// Requires `base_url`.
+    Compatible,
+}
+
+/// A configured provider.
+///
+/// ```toml
+/// [providers.claude]
+/// kind = "claude"
+/// api_key = "${ANTHROPIC_API_KEY}"
+/// model = "claude-sonnet-4-20250514"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderConfig {
+    /// API the provider speaks.
+    pub kind: ProviderKind,
+    /// API key, usually a `${VAR}` reference. Local servers may omit it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Endpoint overriding the provider's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Model used unless another is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
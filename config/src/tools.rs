@@ -0,0 +1,55 @@
+//! Which tools an agent may use.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Most permissive bash mode that runs without asking the user.
+///
+/// Mirrors the sandbox's bash modes: each level includes the ones before it.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BashApproval {
+    /// Only sandboxed scripts run unasked.
+    #[default]
+    Sandboxed,
+    /// Scripts with network access also run unasked.
+    Network,
+    /// Every script runs unasked, including unsandboxed ones.
+    Unsafe,
+}
+
+/// Tool permissions.
+///
+/// ```toml
+/// [tools]
+/// deny = ["webfetch"]
+/// bash = "network"
+/// network_allow = ["docs.rs"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ToolPolicy {
+    /// Tools the agent may use. Empty allows every tool not denied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Tools the agent may not use.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    /// Bash scripts up to this mode run without asking.
+    #[serde(default)]
+    pub bash: BashApproval,
+    /// Domains bash scripts may reach without asking.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_allow: Vec<String>,
+}
+
+impl ToolPolicy {
+    /// Returns `true` if the agent may use `tool`.
+    #[must_use]
+    pub fn allows(&self, tool: &str) -> bool {
+        let listed = |names: &[String]| names.iter().any(|name| name == tool);
+        !listed(&self.deny) && (self.allow.is_empty() || listed(&self.allow))
+    }
+}
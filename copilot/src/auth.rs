@@ -6,6 +6,11 @@
 //! 2. Display the user code and verification URL to the user
 //! 3. Poll the token endpoint until the user completes authorization
 //!
+//! [`device_login`] runs all three steps, and a [`TokenStore`] keeps the
+//! resulting OAuth token between runs. Most callers use
+//! [`CopilotProvider::login`](crate::CopilotProvider::login), which combines
+//! both and refreshes session tokens automatically.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -31,9 +36,16 @@ use crate::{
     constant::{COPILOT_CLIENT_ID, GITHUB_DEVICE_CODE_URL, GITHUB_TOKEN_URL},
 };
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, SystemTime};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use zenwave::{Client, client};
 
+/// Session tokens are refreshed this long before they expire.
+const SESSION_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
 /// Response from the device code request.
 #[derive(Debug, Clone, Deserialize)]
 pub struct DeviceCodeResponse {
@@ -210,6 +222,29 @@ pub async fn try_get_token(device_code: &str) -> Result<CopilotToken, CopilotErr
     })
 }
 
+/// Run the whole device flow.
+///
+/// `on_code` is called once with the code to show the user. Polling stops
+/// with [`CopilotError::DeviceCodeExpired`] when the code expires.
+///
+/// # Errors
+///
+/// Returns an error if authorization fails, expires, or is denied.
+pub async fn device_login(
+    on_code: impl FnOnce(&DeviceCodeResponse) + Send,
+) -> Result<CopilotToken, CopilotError> {
+    let device = request_device_code().await?;
+    on_code(&device);
+
+    let expires_in = Duration::from_secs(device.expires_in);
+    let login = poll_for_token(&device.device_code, device.interval);
+    futures_lite::future::or(login, async {
+        sleep(expires_in).await;
+        Err(CopilotError::DeviceCodeExpired)
+    })
+    .await
+}
+
 /// Sleep for the given duration (runtime-agnostic).
 async fn sleep(duration: Duration) {
     #[cfg(not(target_arch = "wasm32"))]
@@ -236,6 +271,19 @@ pub struct SessionToken {
     pub expires_at: i64,
 }
 
+impl SessionToken {
+    /// Returns `true` if the token expires within `margin`.
+    #[must_use]
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        let deadline = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .saturating_add(margin)
+            .as_secs();
+        i64::try_from(deadline).unwrap_or(i64::MAX) >= self.expires_at
+    }
+}
+
 /// Endpoints returned from the token exchange.
 #[derive(Debug, Deserialize)]
 struct EndpointsResponse {
@@ -298,3 +346,171 @@ pub async fn get_session_token(oauth_token: &str) -> Result<SessionToken, Copilo
         expires_at: response.expires_at,
     })
 }
+
+/// The latest session token, shared by clones of a client or provider so a
+/// refresh by one is picked up by all.
+#[derive(Debug, Default)]
+pub(crate) struct SessionCache {
+    session: Mutex<Option<SessionToken>>,
+}
+
+impl SessionCache {
+    pub(crate) fn new(session: Option<SessionToken>) -> Self {
+        Self {
+            session: Mutex::new(session),
+        }
+    }
+
+    /// The cached session, unless it is missing or about to expire.
+    pub(crate) fn fresh(&self) -> Option<SessionToken> {
+        self.session
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .filter(|session| !session.expires_within(SESSION_REFRESH_MARGIN))
+    }
+
+    /// Returns `true` if a session was cached and is about to expire.
+    pub(crate) fn is_stale(&self) -> bool {
+        self.session
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .is_some_and(|session| session.expires_within(SESSION_REFRESH_MARGIN))
+    }
+
+    /// Exchange `oauth_token` for a new session and cache it.
+    pub(crate) async fn refresh(&self, oauth_token: &str) -> Result<SessionToken, CopilotError> {
+        let session = get_session_token(oauth_token).await?;
+        *self.session.lock().unwrap_or_else(PoisonError::into_inner) = Some(session.clone());
+        Ok(session)
+    }
+}
+
+/// Persistent storage for the OAuth token obtained by the device flow.
+///
+/// The OAuth token does not expire on its own, so storing it lets users log
+/// in once instead of on every run.
+pub trait TokenStore: Send + Sync {
+    /// Load the stored token, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage cannot be read.
+    fn load(&self) -> Result<Option<CopilotToken>, CopilotError>;
+
+    /// Store `token`, replacing any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage cannot be written.
+    fn save(&self, token: &CopilotToken) -> Result<(), CopilotError>;
+
+    /// Remove the stored token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage cannot be written.
+    fn clear(&self) -> Result<(), CopilotError>;
+}
+
+/// Stores the OAuth token as JSON in a file only the current user can read.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl Default for FileTokenStore {
+    fn default() -> Self {
+        Self::new(default_config_dir().join("copilot.json"))
+    }
+}
+
+impl FileTokenStore {
+    /// Store the token at `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the token file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<CopilotToken>, CopilotError> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn save(&self, token: &CopilotToken) -> Result<(), CopilotError> {
+        if let Some(dir) = self.path.parent() {
+            create_private_dir(dir)?;
+        }
+        let tmp = self
+            .path
+            .with_extension(format!("{}.tmp", std::process::id()));
+        write_private_file(&tmp, &serde_json::to_vec_pretty(token)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), CopilotError> {
+        match std::fs::remove_file(&self.path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns the directory aither keeps credentials in.
+///
+/// `AITHER_CONFIG_DIR` takes precedence over the platform's config directory.
+fn default_config_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("AITHER_CONFIG_DIR") {
+        return PathBuf::from(dir);
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("aither")
+}
+
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)
+}
+
+#[cfg(unix)]
+fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::{io::Write, os::unix::fs::OpenOptionsExt};
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(contents)
+}
+
+#[cfg(not(unix))]
+fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
@@ -2,12 +2,14 @@
 
 use crate::{
     CopilotError,
+    auth::{SessionCache, SessionToken},
     constant::{COPILOT_BASE_URL, COPILOT_INTEGRATION_ID, DEFAULT_MODEL, EDITOR_VERSION},
 };
 use aither_core::{
     LanguageModel,
     llm::{
-        Event, LLMRequest, Message, Role, ToolCall, Usage,
+        ContentPart as MessagePart, Event, LLMRequest, Message, Role, ToolCall, ToolCallDelta,
+        Usage,
         model::{
             Ability, OpenAIPromptCacheRetention, Parameters, Profile as ModelProfile, ToolChoice,
         },
@@ -138,6 +140,16 @@ struct Config {
     editor_version: String,
     integration_id: String,
     oauth_token: Option<String>,
+    session: Arc<SessionCache>,
+}
+
+impl Config {
+    /// Use `session` for the token and API endpoint.
+    fn with_session(mut self, session: SessionToken) -> Self {
+        self.token = session.token;
+        self.base_url = session.api_endpoint;
+        self
+    }
 }
 
 /// Builder for [`Copilot`] clients.
@@ -149,6 +161,7 @@ pub struct Builder {
     editor_version: String,
     integration_id: String,
     oauth_token: Option<String>,
+    session: Arc<SessionCache>,
 }
 
 impl Builder {
@@ -160,6 +173,7 @@ impl Builder {
             editor_version: EDITOR_VERSION.to_string(),
             integration_id: COPILOT_INTEGRATION_ID.to_string(),
             oauth_token: None,
+            session: Arc::default(),
         }
     }
 
@@ -198,6 +212,13 @@ impl Builder {
         self
     }
 
+    /// Share session token refreshes with a provider.
+    #[must_use]
+    pub(crate) fn session(mut self, session: Arc<SessionCache>) -> Self {
+        self.session = session;
+        self
+    }
+
     /// Build the Copilot client.
     #[must_use]
     pub fn build(self) -> Copilot {
@@ -209,6 +230,7 @@ impl Builder {
                 editor_version: self.editor_version,
                 integration_id: self.integration_id,
                 oauth_token: self.oauth_token,
+                session: self.session,
             }),
         }
     }
@@ -424,27 +446,29 @@ fn to_chat_messages(messages: &[Message]) -> Vec<ChatMessagePayload> {
 }
 
 fn build_content(message: &Message) -> ContentPayload {
-    let attachments = message.attachments();
-
-    if attachments.is_empty() {
+    if message.attachments().is_empty() {
         return ContentPayload::Text(message.content().to_owned());
     }
 
-    let mut parts = Vec::new();
-
-    for attachment in attachments {
-        if let Some(data_url) = url_to_data_url(attachment) {
-            parts.push(ContentPart::ImageUrl {
-                image_url: ImageUrlPayload { url: data_url },
-            });
-        }
-    }
-
-    if !message.content().is_empty() {
-        parts.push(ContentPart::Text {
-            text: message.content().to_owned(),
-        });
-    }
+    // Copilot only takes images; other attachments are described in text.
+    let parts = message
+        .parts()
+        .into_iter()
+        .map(|part| match &part {
+            MessagePart::Text(text) => ContentPart::Text { text: text.clone() },
+            MessagePart::Image(url) => url_to_data_url(url).map_or_else(
+                || ContentPart::Text {
+                    text: part.placeholder(),
+                },
+                |url| ContentPart::ImageUrl {
+                    image_url: ImageUrlPayload { url },
+                },
+            ),
+            _ => ContentPart::Text {
+                text: part.placeholder(),
+            },
+        })
+        .collect();
 
     ContentPayload::Parts(parts)
 }
@@ -587,13 +611,23 @@ async fn refresh_session_config(cfg: &Config) -> Result<Option<Config>, CopilotE
         return Ok(None);
     };
 
-    let session = crate::auth::get_session_token(oauth_token).await?;
-
-    let mut refreshed = cfg.clone();
-    refreshed.token = session.token;
-    refreshed.base_url = session.api_endpoint;
+    let session = cfg.session.refresh(oauth_token).await?;
+    Ok(Some(cfg.with_session(session)))
+}
 
-    Ok(Some(refreshed))
+/// Apply the latest shared session token, refreshing it first if it is about
+/// to expire.
+async fn current_session_config(cfg: Config) -> Result<Config, CopilotError> {
+    if let Some(session) = cfg.session.fresh() {
+        return Ok(cfg.with_session(session));
+    }
+    match cfg.oauth_token.as_deref() {
+        Some(oauth_token) if cfg.session.is_stale() => {
+            let session = cfg.session.refresh(oauth_token).await?;
+            Ok(cfg.with_session(session))
+        }
+        _ => Ok(cfg),
+    }
 }
 
 fn chat_completions_stream(
@@ -619,7 +653,13 @@ fn chat_completions_stream_inner(
 ) -> impl Stream<Item = Result<Event, CopilotError>> + Send {
     async_stream::stream! {
         let has_tools = tools.as_ref().is_some_and(|t| !t.is_empty());
-        let mut cfg = cfg.as_ref().clone();
+        let mut cfg = match current_session_config(cfg.as_ref().clone()).await {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                yield Err(e);
+                return;
+            }
+        };
         let request = ChatCompletionRequest {
             model: cfg.model.clone(),
             messages: payload_messages,
@@ -792,6 +832,11 @@ fn chat_completions_stream_inner(
                                                     }
                                                     if let Some(args) = &function.arguments {
                                                         acc.arguments.push_str(args);
+                                                        if let (Some(id), Some(name)) = (&acc.id, &acc.name)
+                                                            && !args.is_empty()
+                                                        {
+                                                            yield Ok(Event::ToolCallDelta(ToolCallDelta::new(id, name, args.as_str())));
+                                                        }
                                                     }
                                                 }
                                             }
@@ -1,5 +1,9 @@
 //! Error types for GitHub Copilot API integration.
 
+use aither_core::{
+    llm::{LlmError, LlmErrorKind},
+    retry::{ProviderRetryInfo, RetryInfo, is_transient_status},
+};
 use std::time::Duration;
 
 /// Errors that can arise when calling the GitHub Copilot API.
@@ -21,6 +25,10 @@ pub enum CopilotError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// Token storage I/O errors.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// API contract violations or unsupported operations.
     #[error("{0}")]
     Api(String),
@@ -59,3 +67,54 @@ pub enum CopilotError {
     #[error("Request timed out")]
     Timeout,
 }
+
+impl RetryInfo for CopilotError {
+    fn retry_info(&self) -> Option<ProviderRetryInfo> {
+        match self {
+            Self::Http(zenwave::Error::Http { status, .. }) => {
+                is_transient_status(status.as_u16()).then(ProviderRetryInfo::new)
+            }
+            Self::Http(err) => {
+                (err.is_network_error() || err.is_timeout()).then(ProviderRetryInfo::new)
+            }
+            Self::RateLimit { retry_after, .. } => {
+                Some(ProviderRetryInfo::new().retry_after(*retry_after))
+            }
+            Self::ServerError { .. } | Self::Timeout => Some(ProviderRetryInfo::new()),
+            _ => None,
+        }
+    }
+}
+
+impl From<CopilotError> for LlmError {
+    /// Failed or expired OAuth flows count as [`LlmErrorKind::Auth`].
+    fn from(err: CopilotError) -> Self {
+        let kind = match &err {
+            CopilotError::Http(http @ zenwave::Error::Http { status, .. }) => {
+                LlmErrorKind::classify(status.as_u16(), http.response_body().unwrap_or_default())
+            }
+            CopilotError::Http(http) if http.is_network_error() || http.is_timeout() => {
+                LlmErrorKind::Network
+            }
+            CopilotError::RateLimit { .. } => LlmErrorKind::RateLimit,
+            CopilotError::ServerError { .. } => LlmErrorKind::Unavailable,
+            CopilotError::Timeout => LlmErrorKind::Network,
+            CopilotError::AuthorizationPending
+            | CopilotError::DeviceCodeExpired
+            | CopilotError::AccessDenied => LlmErrorKind::Auth,
+            CopilotError::Api(message) => {
+                LlmErrorKind::from_message(message).unwrap_or(LlmErrorKind::Other)
+            }
+            CopilotError::Http(_)
+            | CopilotError::Body(_)
+            | CopilotError::Stream(_)
+            | CopilotError::Json(_)
+            | CopilotError::Io(_) => LlmErrorKind::Other,
+        };
+        let error = Self::new(kind, err.to_string());
+        match err.retry_info() {
+            Some(retry) => error.with_retry(retry),
+            None => error,
+        }
+    }
+}
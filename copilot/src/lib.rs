@@ -3,7 +3,25 @@
 //! This crate provides a `Copilot` client that implements the `LanguageModel` trait,
 //! allowing you to use GitHub Copilot's chat API through the unified aither interface.
 //!
-//! GitHub Copilot uses OAuth device flow for authentication. The typical flow is:
+//! GitHub Copilot uses OAuth device flow for authentication.
+//! [`CopilotProvider::login`] runs it once, stores the OAuth token, and keeps
+//! session tokens refreshed:
+//!
+//! ```no_run
+//! use aither_copilot::{CopilotProvider, auth::FileTokenStore};
+//! use aither_core::llm::LanguageModelProvider;
+//!
+//! # async fn demo() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = CopilotProvider::login(&FileTokenStore::default(), |device| {
+//!     println!("Visit {} and enter code: {}", device.verification_uri, device.user_code);
+//! })
+//! .await?;
+//! let copilot = provider.get_model("gpt-4o").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The flow can also be driven step by step:
 //!
 //! 1. Call [`auth::request_device_code`] to get a device code and verification URL
 //! 2. Direct the user to the verification URL to enter the code
@@ -2,7 +2,7 @@
 
 use crate::{
     Copilot,
-    auth::get_session_token,
+    auth::{self, DeviceCodeResponse, SessionCache, SessionToken, TokenStore},
     constant::{COPILOT_BASE_URL, COPILOT_INTEGRATION_ID, EDITOR_VERSION},
     error::CopilotError,
 };
@@ -30,10 +30,76 @@ impl CopilotProvider {
                 editor_version: EDITOR_VERSION.to_string(),
                 integration_id: COPILOT_INTEGRATION_ID.to_string(),
                 oauth_token: None,
+                session: Arc::default(),
             }),
         }
     }
 
+    /// Create a provider from a GitHub OAuth token.
+    ///
+    /// The OAuth token is exchanged for a session token right away, and the
+    /// session token is refreshed automatically before it expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token exchange fails, e.g. because the token
+    /// was revoked or the account has no Copilot subscription.
+    pub async fn from_oauth_token(oauth_token: impl Into<String>) -> Result<Self, CopilotError> {
+        let oauth_token = oauth_token.into();
+        let session = auth::get_session_token(&oauth_token).await?;
+        Ok(Self {
+            inner: Arc::new(ProviderConfig {
+                token: session.token.clone(),
+                base_url: session.api_endpoint.clone(),
+                editor_version: EDITOR_VERSION.to_string(),
+                integration_id: COPILOT_INTEGRATION_ID.to_string(),
+                oauth_token: Some(oauth_token),
+                session: Arc::new(SessionCache::new(Some(session))),
+            }),
+        })
+    }
+
+    /// Log in with the token in `store`, or through the device flow.
+    ///
+    /// A stored token that GitHub rejects is cleared. When the device flow
+    /// runs, `on_code` is called with the code to show the user and the new
+    /// token is saved to `store`.
+    ///
+    /// ```no_run
+    /// use aither_copilot::{CopilotProvider, auth::FileTokenStore};
+    ///
+    /// # async fn demo() -> Result<(), aither_copilot::CopilotError> {
+    /// let provider = CopilotProvider::login(&FileTokenStore::default(), |device| {
+    ///     println!("Visit {} and enter code: {}", device.verification_uri, device.user_code);
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be accessed, the device flow
+    /// fails, or the token exchange fails.
+    pub async fn login(
+        store: &impl TokenStore,
+        on_code: impl FnOnce(&DeviceCodeResponse) + Send,
+    ) -> Result<Self, CopilotError> {
+        if let Some(token) = store.load()? {
+            match Self::from_oauth_token(token.access_token).await {
+                Err(err) if is_unauthorized(&err) => {
+                    tracing::info!("Stored Copilot token was rejected, logging in again");
+                    store.clear()?;
+                }
+                result => return result,
+            }
+        }
+
+        let token = auth::device_login(on_code).await?;
+        store.save(&token)?;
+        Self::from_oauth_token(token.access_token).await
+    }
+
     /// Set the base URL for API calls.
     #[must_use]
     pub fn base_url(mut self, url: impl Into<String>) -> Self {
@@ -56,7 +122,7 @@ impl LanguageModelProvider for CopilotProvider {
     fn list_models(&self) -> impl Future<Output = Result<Vec<ModelProfile>, Self::Error>> + Send {
         let cfg = self.inner.clone();
         async move {
-            let mut cfg = cfg.as_ref().clone();
+            let mut cfg = current_session_config(cfg.as_ref().clone()).await?;
             let response = match fetch_models(&cfg).await {
                 Ok(response) => response,
                 Err(err) if is_unauthorized(&err) => match refresh_provider_config(&cfg).await? {
@@ -104,9 +170,11 @@ impl LanguageModelProvider for CopilotProvider {
         let cfg = self.inner.clone();
         let name = name.to_string();
         async move {
+            let cfg = current_session_config(cfg.as_ref().clone()).await?;
             let mut builder = Copilot::builder(cfg.token.clone())
                 .base_url(cfg.base_url.clone())
-                .model(name);
+                .model(name)
+                .session(cfg.session.clone());
             if let Some(oauth_token) = &cfg.oauth_token {
                 builder = builder.oauth_token(oauth_token.clone());
             }
@@ -126,6 +194,16 @@ struct ProviderConfig {
     editor_version: String,
     integration_id: String,
     oauth_token: Option<String>,
+    session: Arc<SessionCache>,
+}
+
+impl ProviderConfig {
+    /// Use `session` for the token and API endpoint.
+    fn with_session(mut self, session: SessionToken) -> Self {
+        self.token = session.token;
+        self.base_url = session.api_endpoint;
+        self
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -183,9 +261,21 @@ async fn refresh_provider_config(
     let Some(oauth_token) = cfg.oauth_token.as_deref() else {
         return Ok(None);
     };
-    let session = get_session_token(oauth_token).await?;
-    let mut refreshed = cfg.clone();
-    refreshed.token = session.token;
-    refreshed.base_url = session.api_endpoint;
-    Ok(Some(refreshed))
+    let session = cfg.session.refresh(oauth_token).await?;
+    Ok(Some(cfg.clone().with_session(session)))
+}
+
+/// Apply the latest shared session token, refreshing it first if it is about
+/// to expire.
+async fn current_session_config(cfg: ProviderConfig) -> Result<ProviderConfig, CopilotError> {
+    if let Some(session) = cfg.session.fresh() {
+        return Ok(cfg.with_session(session));
+    }
+    match cfg.oauth_token.as_deref() {
+        Some(oauth_token) if cfg.session.is_stale() => {
+            let session = cfg.session.refresh(oauth_token).await?;
+            Ok(cfg.with_session(session))
+        }
+        _ => Ok(cfg),
+    }
 }
@@ -1,5 +1,6 @@
-use alloc::{string::String, vec::Vec};
+use alloc::{boxed::Box, collections::VecDeque, string::String, vec::Vec};
 use futures_core::Stream;
+use futures_lite::StreamExt;
 
 /// Audio data as bytes.
 ///
@@ -26,6 +27,87 @@ pub trait AudioGenerator {
     ///
     /// Returns a [`Stream`] of [`Data`] chunks.
     fn generate(&self, prompt: &str) -> impl Stream<Item = Data> + Send;
+
+    /// Generates audio while the text is still arriving, such as from a
+    /// language model's streamed reply.
+    ///
+    /// Returns a [`Stream`] of [`Data`] chunks that starts before `text`
+    /// ends. The default implementation speaks each complete sentence with
+    /// [`generate`](Self::generate); providers that accept streamed text
+    /// should override it.
+    fn generate_stream<S>(&self, text: S) -> impl Stream<Item = Data> + Send
+    where
+        S: Stream<Item = String> + Send,
+        Self: Sync,
+    {
+        let state = SpeechState {
+            text: Box::pin(text),
+            buffer: String::new(),
+            audio: VecDeque::new(),
+            text_done: false,
+        };
+        futures_lite::stream::unfold(state, move |mut state| async move {
+            loop {
+                if let Some(chunk) = state.audio.pop_front() {
+                    return Some((chunk, state));
+                }
+                if let Some(sentence) = take_sentence(&mut state.buffer, state.text_done) {
+                    state.audio = self.generate(&sentence).collect().await;
+                } else if state.text_done {
+                    return None;
+                } else if let Some(text) = state.text.next().await {
+                    state.buffer.push_str(&text);
+                } else {
+                    state.text_done = true;
+                }
+            }
+        })
+    }
+}
+
+struct SpeechState<S> {
+    text: core::pin::Pin<Box<S>>,
+    buffer: String,
+    audio: VecDeque<Data>,
+    text_done: bool,
+}
+
+/// Characters that end a sentence when followed by whitespace.
+const SENTENCE_ENDS: &[char] = &['.', '!', '?', ';'];
+
+/// Characters that end a sentence on their own.
+const HARD_SENTENCE_ENDS: &[char] = &['\n', '。', '！', '？', '；'];
+
+/// Removes the first complete sentence from `buffer`.
+///
+/// A period followed by more text, as in `3.14`, does not end a sentence,
+/// and one at the very end of the buffer only does once `flush` is set.
+fn take_sentence(buffer: &mut String, flush: bool) -> Option<String> {
+    let mut end = None;
+    let mut chars = buffer.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let ends = HARD_SENTENCE_ENDS.contains(&c)
+            || (SENTENCE_ENDS.contains(&c)
+                && chars.peek().is_some_and(|(_, next)| next.is_whitespace()));
+        if ends {
+            end = Some(index + c.len_utf8());
+            break;
+        }
+    }
+
+    let end = match end {
+        Some(end) => end,
+        None if flush && !buffer.is_empty() => buffer.len(),
+        None => return None,
+    };
+    let sentence: String = buffer.drain(..end).collect();
+    let sentence = sentence.trim();
+    if sentence.is_empty() {
+        // Only whitespace: keep looking in what is left.
+        take_sentence(buffer, flush)
+    } else {
+        Some(sentence.into())
+    }
 }
 
 /// Transcribes audio to text.
@@ -49,6 +131,59 @@ pub trait AudioTranscriber {
     ///
     /// Returns a [`Stream`] of transcribed text chunks.
     fn transcribe(&self, audio: &[u8]) -> impl Stream<Item = String> + Send;
+
+    /// Transcribes audio while it is still being recorded.
+    ///
+    /// Returns a [`Stream`] of [`TranscriptUpdate`]s. The default
+    /// implementation waits for `audio` to end and transcribes it with
+    /// [`transcribe`](Self::transcribe), yielding a single
+    /// [`TranscriptUpdate::Final`]; providers with streaming recognition
+    /// should override it.
+    fn transcribe_stream<S>(&self, audio: S) -> impl Stream<Item = TranscriptUpdate> + Send
+    where
+        S: Stream<Item = Data> + Send,
+        Self: Sync,
+    {
+        futures_lite::stream::once_future(async move {
+            let mut recording = Vec::new();
+            let mut audio = core::pin::pin!(audio);
+            while let Some(chunk) = audio.next().await {
+                recording.extend_from_slice(&chunk);
+            }
+            let text: String = self.transcribe(&recording).collect().await;
+            TranscriptUpdate::Final(text)
+        })
+        .filter(|update| !update.text().is_empty())
+    }
+}
+
+/// Progress of a streaming transcription.
+///
+/// Partial text may still change as more audio arrives; final text does
+/// not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptUpdate {
+    /// More text of the utterance in progress, to append to the earlier
+    /// partial text.
+    Partial(String),
+    /// The complete text of an utterance, replacing its partial text.
+    Final(String),
+}
+
+impl TranscriptUpdate {
+    /// The text carried by this update.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Partial(text) | Self::Final(text) => text,
+        }
+    }
+
+    /// Returns `true` for [`Self::Final`].
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(self, Self::Final(_))
+    }
 }
 
 #[cfg(test)]
@@ -213,6 +348,50 @@ mod tests {
         assert!(text_chunks.is_empty());
     }
 
+    #[tokio::test]
+    async fn generate_stream_speaks_complete_sentences() {
+        let text = futures_lite::stream::iter(vec![
+            "Hello the".to_string(),
+            "re. Pi is 3.".to_string(),
+            "14. Hi".to_string(),
+        ]);
+        let chunks: Vec<Data> = MockAudioGenerator.generate_stream(text).collect().await;
+
+        // "Hello there." and "Pi is 3.14." get three chunks, the flushed "Hi" one.
+        assert_eq!(chunks.len(), 7);
+        assert_eq!(chunks[6], vec![0x01; 512]);
+    }
+
+    #[test]
+    fn sentences_split_on_terminators() {
+        let mut buffer = "First. Second?\nThird".to_string();
+        assert_eq!(take_sentence(&mut buffer, false).as_deref(), Some("First."));
+        assert_eq!(
+            take_sentence(&mut buffer, false).as_deref(),
+            Some("Second?")
+        );
+        assert_eq!(take_sentence(&mut buffer, false), None);
+        assert_eq!(take_sentence(&mut buffer, true).as_deref(), Some("Third"));
+
+        let mut buffer = "你好。世界".to_string();
+        assert_eq!(take_sentence(&mut buffer, false).as_deref(), Some("你好。"));
+    }
+
+    #[tokio::test]
+    async fn transcribe_stream_transcribes_whole_recording() {
+        let audio = futures_lite::stream::iter(vec![vec![0x01; 60], vec![0x01; 60]]);
+        let updates: Vec<_> = MockAudioTranscriber
+            .transcribe_stream(audio)
+            .collect()
+            .await;
+
+        assert_eq!(
+            updates,
+            vec![TranscriptUpdate::Final("Hello world".to_string())]
+        );
+        assert!(updates[0].is_final());
+    }
+
     #[test]
     fn data_type_alias() {
         let data: Data = vec![1, 2, 3, 4, 5];
@@ -104,7 +104,7 @@ pub type Embedding = Vec<f32>;
 ///
 /// # Performance Considerations
 ///
-/// - Batch multiple texts when possible to reduce API calls
+/// - Use [`embed_batch`](EmbeddingModel::embed_batch) for many texts to reduce API calls
 /// - Consider caching embeddings for frequently used texts
 /// - Be aware of rate limits when using cloud-based embedding services
 pub trait EmbeddingModel: Send + Sized + Sync {
@@ -131,6 +131,55 @@ pub trait EmbeddingModel: Send + Sized + Sync {
     ///
     /// Implementations that need mutable state should use interior mutability.
     fn embed(&self, text: &str) -> impl Future<Output = crate::Result<Vec<f32>>> + Send;
+
+    /// Converts several texts to embedding vectors, in input order.
+    ///
+    /// The default implementation calls [`embed`](EmbeddingModel::embed) once
+    /// per text. Backends that can embed many texts in one request or forward
+    /// pass should override it.
+    fn embed_batch(
+        &self,
+        texts: &[&str],
+    ) -> impl Future<Output = crate::Result<Vec<Vec<f32>>>> + Send {
+        async move {
+            let mut embeddings = Vec::with_capacity(texts.len());
+            for text in texts {
+                embeddings.push(self.embed(text).await?);
+            }
+            Ok(embeddings)
+        }
+    }
+
+    /// Converts a search query to an embedding vector.
+    ///
+    /// Asymmetric models such as E5 or Qwen3-Embedding expect queries and
+    /// documents to be marked differently, for example with a `query: `
+    /// prefix or a task instruction. Retrieval code should embed queries
+    /// with this method and indexed texts with
+    /// [`embed_documents`](EmbeddingModel::embed_documents).
+    ///
+    /// The default implementation calls [`embed`](EmbeddingModel::embed).
+    fn embed_query(&self, query: &str) -> impl Future<Output = crate::Result<Vec<f32>>> + Send {
+        self.embed(query)
+    }
+
+    /// Converts a document to be searched to an embedding vector.
+    ///
+    /// The default implementation calls [`embed`](EmbeddingModel::embed).
+    fn embed_document(&self, text: &str) -> impl Future<Output = crate::Result<Vec<f32>>> + Send {
+        self.embed(text)
+    }
+
+    /// Converts several documents to embedding vectors, in input order.
+    ///
+    /// The default implementation calls
+    /// [`embed_batch`](EmbeddingModel::embed_batch).
+    fn embed_documents(
+        &self,
+        texts: &[&str],
+    ) -> impl Future<Output = crate::Result<Vec<Vec<f32>>>> + Send {
+        self.embed_batch(texts)
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +256,27 @@ mod tests {
         assert_eq!(embedding[2], 0.02); // length 0 + index 2 = 2 * 0.01
     }
 
+    #[tokio::test]
+    async fn embed_batch_defaults_to_sequential_embeds() {
+        let model = MockEmbeddingModel { dimension: 2 };
+        let batch = model.embed_batch(&["a", "abc"]).await.unwrap();
+
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[0], model.embed("a").await.unwrap());
+        assert_eq!(batch[1], model.embed("abc").await.unwrap());
+        assert!(model.embed_batch(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn query_and_document_default_to_embed() {
+        let model = MockEmbeddingModel { dimension: 2 };
+        let plain = model.embed("abc").await.unwrap();
+
+        assert_eq!(model.embed_query("abc").await.unwrap(), plain);
+        assert_eq!(model.embed_document("abc").await.unwrap(), plain);
+        assert_eq!(model.embed_documents(&["abc"]).await.unwrap(), vec![plain]);
+    }
+
     #[tokio::test]
     async fn embedding_large_dimension() {
         let model = MockEmbeddingModel { dimension: 1536 }; // Common OpenAI dimension
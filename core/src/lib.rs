@@ -26,6 +26,7 @@
 //! |------------|-------|-------------|
 //! | **Language Models** | [`LanguageModel`] | Streaming events (text, reasoning, tool calls) |
 //! | **Embeddings** | [`EmbeddingModel`] | Convert text to vectors for semantic search |
+//! | **Reranking** | [`Reranker`] | Order documents by relevance to a query |
 //! | **Image Generation** | [`ImageGenerator`] | Create images with progressive quality improvement |
 //! | **Text-to-Speech** | [`AudioGenerator`] | Generate speech audio from text |
 //! | **Speech-to-Text** | [`AudioTranscriber`] | Transcribe audio to text |
@@ -150,7 +151,9 @@
 //! - [`embedding`] — turn text into dense vectors.
 //! - [`image`] — image generation + editing APIs.
 //! - [`llm`] — request builders, messages, provider traits, reasoning streams.
-//! - [`moderation`] — moderation scoring traits.
+//! - [`moderation`] — moderation scoring traits and a moderated model wrapper.
+//! - [`prompt`] — prompt templates with partials and few-shot examples.
+//! - [`retry`] — retry hints shared by provider errors.
 //!
 //!
 
@@ -175,9 +178,26 @@ pub mod llm;
 
 /// Content moderation utilities.
 ///
-/// Contains traits and types for detecting and handling unsafe or inappropriate content.
+/// Contains traits and types for detecting and handling unsafe or inappropriate content,
+/// and [`ModeratedModel`](moderation::ModeratedModel), which applies them to a language model.
 pub mod moderation;
 
+/// Prompt templates.
+///
+/// Contains [`PromptTemplate`](prompt::PromptTemplate) and the
+/// [`PromptLibrary`](prompt::PromptLibrary) that lets built-in prompts be overridden.
+pub mod prompt;
+
+/// Document reranking.
+///
+/// Contains the [`Reranker`] trait for scoring documents against a query.
+pub mod rerank;
+
+/// Retry hints for transient provider errors.
+///
+/// Contains [`ProviderRetryInfo`] and the [`RetryInfo`] trait.
+pub mod retry;
+
 use alloc::string::String;
 
 #[doc(inline)]
@@ -190,6 +210,10 @@ pub use image::ImageGenerator;
 pub use llm::LanguageModel;
 #[doc(inline)]
 pub use moderation::Moderation;
+#[doc(inline)]
+pub use rerank::Reranker;
+#[doc(inline)]
+pub use retry::{ProviderRetryInfo, RetryInfo};
 
 /// Result type used throughout the crate.
 ///
@@ -0,0 +1,281 @@
+//! # Error Taxonomy
+//!
+//! Every provider reports failures differently: HTTP statuses, error codes in
+//! JSON bodies, or only a message. [`LlmError`] sorts them into a few
+//! [`LlmErrorKind`]s so callers can decide what to do without knowing which
+//! provider failed. Provider errors convert into it with [`From`]:
+//!
+//! ```rust,ignore
+//! use aither_core::llm::{LlmError, LlmErrorKind};
+//!
+//! match LlmError::from(err).kind() {
+//!     LlmErrorKind::RateLimit | LlmErrorKind::Network | LlmErrorKind::Unavailable => retry(),
+//!     LlmErrorKind::ContextOverflow => compact_and_retry(),
+//!     LlmErrorKind::Auth => switch_model(),
+//!     _ => give_up(),
+//! }
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::retry::{ProviderRetryInfo, RetryInfo};
+
+/// What went wrong in a language model call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LlmErrorKind {
+    /// Credentials are missing, invalid or lack permission for the model.
+    Auth,
+    /// The provider's rate limit or quota was exceeded.
+    RateLimit,
+    /// The prompt does not fit in the model's context window.
+    ContextOverflow,
+    /// The provider's safety system blocked the prompt or the response.
+    ContentFilter,
+    /// The provider could not be reached, or the connection dropped or timed
+    /// out.
+    Network,
+    /// The provider is overloaded or failed internally.
+    Unavailable,
+    /// The request was malformed or asked for something the model does not
+    /// support.
+    InvalidRequest,
+    /// Anything else, such as a response that could not be parsed.
+    Other,
+}
+
+/// Phrases providers use for context window overflows.
+const CONTEXT_OVERFLOW_MARKERS: &[&str] = &[
+    "context_length_exceeded",
+    "maximum context length",
+    "context window",
+    "prompt is too long",
+    "input is too long",
+    "too many tokens",
+    "exceeds the context",
+];
+
+/// Phrases providers use for safety blocks.
+const CONTENT_FILTER_MARKERS: &[&str] = &[
+    "content_filter",
+    "content filter",
+    "content_policy",
+    "content management policy",
+    "response blocked",
+    "safety",
+];
+
+impl LlmErrorKind {
+    /// Classify an HTTP status code.
+    #[must_use]
+    pub const fn from_status(status: u16) -> Self {
+        match status {
+            401 | 403 => Self::Auth,
+            408 => Self::Network,
+            413 => Self::ContextOverflow,
+            429 => Self::RateLimit,
+            400..=499 => Self::InvalidRequest,
+            500..=599 => Self::Unavailable,
+            _ => Self::Other,
+        }
+    }
+
+    /// Recognise context overflows and safety blocks from an error message.
+    ///
+    /// Providers report both as ordinary bad requests, so the message is the
+    /// only way to tell them apart. Returns `None` for other messages.
+    #[must_use]
+    pub fn from_message(message: &str) -> Option<Self> {
+        let message = message.to_ascii_lowercase();
+        let mentions = |markers: &[&str]| markers.iter().any(|marker| message.contains(marker));
+        if mentions(CONTEXT_OVERFLOW_MARKERS) {
+            Some(Self::ContextOverflow)
+        } else if mentions(CONTENT_FILTER_MARKERS) {
+            Some(Self::ContentFilter)
+        } else {
+            None
+        }
+    }
+
+    /// Classify a failed HTTP response from its status and body.
+    ///
+    /// The body takes precedence, since context overflows and safety blocks
+    /// usually arrive as a plain 400.
+    #[must_use]
+    pub fn classify(status: u16, body: &str) -> Self {
+        Self::from_message(body).unwrap_or_else(|| Self::from_status(status))
+    }
+
+    /// Returns `true` if the same request may succeed later.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::RateLimit | Self::Network | Self::Unavailable)
+    }
+}
+
+impl fmt::Display for LlmErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auth => "authentication failed",
+            Self::RateLimit => "rate limited",
+            Self::ContextOverflow => "context window exceeded",
+            Self::ContentFilter => "blocked by content filter",
+            Self::Network => "network error",
+            Self::Unavailable => "provider unavailable",
+            Self::InvalidRequest => "invalid request",
+            Self::Other => "model error",
+        })
+    }
+}
+
+/// A provider error sorted into an [`LlmErrorKind`].
+///
+/// Keeps the provider's message for display and any retry hints it gave.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LlmError {
+    kind: LlmErrorKind,
+    message: String,
+    retry: Option<ProviderRetryInfo>,
+}
+
+impl LlmError {
+    /// Create an error of `kind`.
+    ///
+    /// Retryable kinds get empty retry hints; use [`Self::with_retry`] to
+    /// pass on what the provider said.
+    #[must_use]
+    pub fn new(kind: LlmErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            retry: kind.is_retryable().then(ProviderRetryInfo::new),
+        }
+    }
+
+    /// Attach the provider's retry hints, marking the error retryable.
+    #[must_use]
+    pub const fn with_retry(mut self, retry: ProviderRetryInfo) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// What went wrong.
+    #[must_use]
+    pub const fn kind(&self) -> LlmErrorKind {
+        self.kind
+    }
+
+    /// The provider's error message.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns `true` if the same request may succeed later.
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        self.retry.is_some()
+    }
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+impl core::error::Error for LlmError {}
+
+impl RetryInfo for LlmError {
+    fn retry_info(&self) -> Option<ProviderRetryInfo> {
+        self.retry
+    }
+}
+
+/// Structured output that still did not parse after every repair attempt.
+///
+/// Returned, wrapped in [`crate::Error`], by
+/// [`LanguageModel::generate`](crate::LanguageModel::generate); recover it
+/// with `err.downcast_ref::<StructuredOutputError>()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StructuredOutputError {
+    /// The model's last response, as received.
+    pub raw: String,
+    /// Responses requested in total, including the first.
+    pub attempts: u32,
+    /// Why the last response could not be parsed.
+    pub reason: String,
+}
+
+impl StructuredOutputError {
+    /// Create an error for the final failed response.
+    #[must_use]
+    pub fn new(raw: impl Into<String>, attempts: u32, reason: impl Into<String>) -> Self {
+        Self {
+            raw: raw.into(),
+            attempts,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for StructuredOutputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "structured output still invalid after {} attempt(s): {}",
+            self.attempts, self.reason
+        )
+    }
+}
+
+impl core::error::Error for StructuredOutputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use core::time::Duration;
+
+    #[test]
+    fn body_overrides_bad_request_status() {
+        let overflow = r#"{"error":{"code":"context_length_exceeded","message":"..."}}"#;
+        assert_eq!(
+            LlmErrorKind::classify(400, overflow),
+            LlmErrorKind::ContextOverflow
+        );
+        assert_eq!(
+            LlmErrorKind::classify(400, "prompt is too long: 210000 tokens > 200000 maximum"),
+            LlmErrorKind::ContextOverflow
+        );
+        assert_eq!(
+            LlmErrorKind::classify(400, "Response blocked due to SAFETY"),
+            LlmErrorKind::ContentFilter
+        );
+        assert_eq!(
+            LlmErrorKind::classify(400, "unknown parameter"),
+            LlmErrorKind::InvalidRequest
+        );
+    }
+
+    #[test]
+    fn status_codes_map_to_kinds() {
+        assert_eq!(LlmErrorKind::from_status(401), LlmErrorKind::Auth);
+        assert_eq!(LlmErrorKind::from_status(429), LlmErrorKind::RateLimit);
+        assert_eq!(LlmErrorKind::from_status(529), LlmErrorKind::Unavailable);
+        assert_eq!(LlmErrorKind::from_status(404), LlmErrorKind::InvalidRequest);
+    }
+
+    #[test]
+    fn retry_hints_default_from_kind() {
+        assert!(LlmError::new(LlmErrorKind::RateLimit, "slow down").is_retryable());
+        assert!(!LlmError::new(LlmErrorKind::Auth, "bad key").is_retryable());
+
+        let hint = ProviderRetryInfo::new().retry_after(Some(Duration::from_secs(3)));
+        let error = LlmError::new(LlmErrorKind::RateLimit, "slow down").with_retry(hint);
+        assert_eq!(error.retry_info(), Some(hint));
+        assert_eq!(error.to_string(), "rate limited: slow down");
+    }
+}
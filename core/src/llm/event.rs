@@ -9,7 +9,9 @@
 //! - [`Event::Text`] - Visible text output
 //! - [`Event::Reasoning`] - Internal reasoning/thinking (for reasoning models)
 //! - [`Event::ToolCall`] - Request to execute a tool (NOT auto-executed)
+//! - [`Event::ToolCallDelta`] - Fragment of a tool call still being written
 //! - [`Event::BuiltInToolResult`] - Result from provider's built-in tool (e.g., Google Search)
+//! - [`Event::Citation`] - Source the response relies on, from native web search
 //! - [`Event::Usage`] - Token usage and cost information
 //!
 //! # Design
@@ -118,7 +120,7 @@ impl Usage {
             *self.cost_usd.get_or_insert(0.0) += v;
         }
         if self.stop_reason.is_none() {
-            self.stop_reason = other.stop_reason.clone();
+            self.stop_reason.clone_from(&other.stop_reason);
         }
     }
 }
@@ -143,9 +145,16 @@ impl Usage {
 ///             let result = execute_tool(&call).await;
 ///             // ... add result to messages and continue
 ///         }
+///         Event::ToolCallDelta(delta) => {
+///             // Render the arguments as they arrive
+///             print!("{}", delta.arguments);
+///         }
 ///         Event::BuiltInToolResult { tool, result } => {
 ///             println!("[{}] {}", tool, result);
 ///         }
+///         Event::Citation(citation) => {
+///             println!("Source: {}", citation.url);
+///         }
 ///         Event::Usage(usage) => {
 ///             println!("Tokens used: {:?}", usage.total_tokens);
 ///         }
@@ -153,6 +162,8 @@ impl Usage {
 /// }
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Event {
     /// Visible text chunk from the model.
     ///
@@ -176,6 +187,14 @@ pub enum Event {
     /// 3. Continue the conversation with the model
     ToolCall(ToolCall),
 
+    /// Fragment of a tool call's arguments while the model is still writing
+    /// them.
+    ///
+    /// Emitted only by providers that stream tool arguments, and only for
+    /// display: the complete call always follows as [`Event::ToolCall`], so
+    /// consumers that just execute tools can ignore these.
+    ToolCallDelta(ToolCallDelta),
+
     /// Result from a provider's built-in tool.
     ///
     /// Some providers have native tools that are executed server-side:
@@ -191,6 +210,14 @@ pub enum Event {
         result: String,
     },
 
+    /// Source the response relies on.
+    ///
+    /// Emitted by providers whose native web search grounds the answer
+    /// (see [`Parameters::websearch`](crate::llm::model::Parameters::websearch)).
+    /// A source is reported once per response, even if the text cites it
+    /// several times.
+    Citation(Citation),
+
     /// Token usage and cost information.
     ///
     /// Emitted at the end of a response stream with usage statistics.
@@ -230,6 +257,12 @@ impl Event {
         }
     }
 
+    /// Creates a citation event.
+    #[must_use]
+    pub fn citation(url: impl Into<String>) -> Self {
+        Self::Citation(Citation::new(url))
+    }
+
     /// Creates a usage event.
     #[must_use]
     pub const fn usage(usage: Usage) -> Self {
@@ -263,6 +296,24 @@ impl Event {
         }
     }
 
+    /// Returns the partial tool call if this is a `ToolCallDelta` event.
+    #[must_use]
+    pub const fn as_tool_call_delta(&self) -> Option<&ToolCallDelta> {
+        match self {
+            Self::ToolCallDelta(delta) => Some(delta),
+            _ => None,
+        }
+    }
+
+    /// Returns the citation if this is a `Citation` event.
+    #[must_use]
+    pub const fn as_citation(&self) -> Option<&Citation> {
+        match self {
+            Self::Citation(citation) => Some(citation),
+            _ => None,
+        }
+    }
+
     /// Returns true if this is a text event.
     #[must_use]
     pub const fn is_text(&self) -> bool {
@@ -335,6 +386,79 @@ impl ToolCall {
     }
 }
 
+/// A streamed fragment of a tool call.
+///
+/// Concatenating the `arguments` of every delta with the same `id` yields the
+/// JSON-encoded arguments of the final [`ToolCall`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ToolCallDelta {
+    /// Identifier of the tool call, matching the final [`ToolCall::id`].
+    pub id: String,
+
+    /// Name of the tool being called.
+    pub name: String,
+
+    /// Next fragment of the JSON-encoded arguments.
+    pub arguments: String,
+}
+
+impl ToolCallDelta {
+    /// Creates a tool call delta.
+    #[must_use]
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        arguments: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            arguments: arguments.into(),
+        }
+    }
+}
+
+/// A web source cited by a model response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Citation {
+    /// URL of the source.
+    pub url: String,
+
+    /// Page title, when the provider reports one.
+    pub title: Option<String>,
+
+    /// Passage of the source the response quotes, when reported.
+    pub cited_text: Option<String>,
+}
+
+impl Citation {
+    /// Creates a citation pointing to `url`.
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            title: None,
+            cited_text: None,
+        }
+    }
+
+    /// Sets the page title.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the quoted passage.
+    #[must_use]
+    pub fn cited_text(mut self, text: impl Into<String>) -> Self {
+        self.cited_text = Some(text.into());
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,11 +2,20 @@
 //!
 //! This module provides types for representing messages in conversations with AI language models.
 //! Messages are represented as an enum with variants for different roles (User, Assistant, System, Tool).
+//!
+//! User messages can carry images, audio and files besides text. Build them from
+//! [`ContentPart`]s with [`Message::user_with_parts`] and read them back with
+//! [`Message::parts`].
 
-use alloc::{string::String, vec::Vec};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use base64::Engine as _;
 use url::Url;
 
-use super::event::ToolCall;
+use super::{event::ToolCall, model::Ability};
 
 /// Conversation participant role.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -91,6 +100,24 @@ impl Message {
         }
     }
 
+    /// Returns the content of a user message as typed parts.
+    ///
+    /// Attachments come first, classified by their media type, followed by
+    /// the text. Other messages return their text as a single part.
+    #[must_use]
+    pub fn parts(&self) -> Vec<ContentPart> {
+        let mut parts: Vec<ContentPart> = self
+            .attachments()
+            .iter()
+            .cloned()
+            .map(ContentPart::from_attachment)
+            .collect();
+        if !self.content().is_empty() {
+            parts.push(ContentPart::Text(self.content().to_string()));
+        }
+        parts
+    }
+
     /// Returns the attachment URLs (only for User messages).
     #[must_use]
     pub fn attachments(&self) -> &[Url] {
@@ -126,6 +153,27 @@ impl Message {
         }
     }
 
+    /// Creates a user message from text, image, audio and file parts.
+    ///
+    /// Text parts are joined with blank lines; all other parts become
+    /// attachments, which providers send ahead of the text.
+    pub fn user_with_parts(parts: impl IntoIterator<Item = ContentPart>) -> Self {
+        let mut texts = Vec::new();
+        let mut attachments = Vec::new();
+        for part in parts {
+            match part {
+                ContentPart::Text(text) => texts.push(text),
+                ContentPart::Image(url) | ContentPart::Audio(url) | ContentPart::File(url) => {
+                    attachments.push(url);
+                }
+            }
+        }
+        Self::User {
+            content: texts.join("\n\n"),
+            attachments,
+        }
+    }
+
     /// Creates a new assistant message.
     pub fn assistant(content: impl Into<String>) -> Self {
         Self::Assistant {
@@ -178,6 +226,39 @@ impl Message {
         self
     }
 
+    /// Replaces attachments the model cannot read with text placeholders.
+    ///
+    /// Attachments are kept when [`ContentPart::is_supported_by`] accepts
+    /// them for `abilities`; the rest are described in the message text so the
+    /// model still knows they were there. Only affects User messages.
+    #[must_use]
+    pub fn degrade_attachments(mut self, abilities: &[Ability]) -> Self {
+        if let Self::User {
+            content,
+            attachments,
+        } = &mut self
+        {
+            let mut placeholders = Vec::new();
+            attachments.retain(|url| {
+                let part = ContentPart::from_attachment(url.clone());
+                let supported = part.is_supported_by(abilities);
+                if !supported {
+                    placeholders.push(part.placeholder());
+                }
+                supported
+            });
+            if !placeholders.is_empty() {
+                let placeholders = placeholders.join("\n");
+                *content = if content.is_empty() {
+                    placeholders
+                } else {
+                    format!("{placeholders}\n\n{content}")
+                };
+            }
+        }
+        self
+    }
+
     /// Adds tool calls to the message (only works for Assistant messages).
     #[must_use]
     pub fn with_tool_calls(mut self, calls: Vec<ToolCall>) -> Self {
@@ -188,6 +269,192 @@ impl Message {
     }
 }
 
+/// One piece of a user message.
+///
+/// Media parts reference their content by URL: `data:` URLs for inline bytes
+/// (see [`ContentPart::image_bytes`] and friends), `http(s)://` for remote
+/// content, `file://` for local files, and provider file references for
+/// content already uploaded. Providers map each kind to their native content
+/// blocks and describe parts they cannot send in text instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum ContentPart {
+    /// Plain text.
+    Text(String),
+    /// An image, such as a PNG or JPEG.
+    Image(Url),
+    /// An audio clip, such as a WAV or MP3 recording.
+    Audio(Url),
+    /// Any other file, such as a PDF, video or text document.
+    File(Url),
+}
+
+impl ContentPart {
+    /// Creates a text part.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// Creates an image part from raw bytes of the given media type.
+    #[must_use]
+    pub fn image_bytes(media_type: &str, bytes: &[u8]) -> Self {
+        Self::Image(data_url(media_type, bytes))
+    }
+
+    /// Creates an audio part from raw bytes of the given media type.
+    #[must_use]
+    pub fn audio_bytes(media_type: &str, bytes: &[u8]) -> Self {
+        Self::Audio(data_url(media_type, bytes))
+    }
+
+    /// Creates a file part from raw bytes of the given media type.
+    #[must_use]
+    pub fn file_bytes(media_type: &str, bytes: &[u8]) -> Self {
+        Self::File(data_url(media_type, bytes))
+    }
+
+    /// Classifies an attachment URL by its media type.
+    ///
+    /// `image/*` becomes [`ContentPart::Image`], `audio/*`
+    /// [`ContentPart::Audio`], and everything else, including URLs of unknown
+    /// type, [`ContentPart::File`].
+    #[must_use]
+    pub fn from_attachment(url: Url) -> Self {
+        match media_type_of(&url) {
+            Some(media_type) if media_type.starts_with("image/") => Self::Image(url),
+            Some(media_type) if media_type.starts_with("audio/") => Self::Audio(url),
+            _ => Self::File(url),
+        }
+    }
+
+    /// Returns the URL of a media part.
+    #[must_use]
+    pub const fn url(&self) -> Option<&Url> {
+        match self {
+            Self::Text(_) => None,
+            Self::Image(url) | Self::Audio(url) | Self::File(url) => Some(url),
+        }
+    }
+
+    /// Returns the media type of a media part, if it can be determined.
+    ///
+    /// Read from the header of `data:` URLs, from a fragment holding a media
+    /// type (as used for provider file references), or guessed from the file
+    /// extension.
+    #[must_use]
+    pub fn media_type(&self) -> Option<String> {
+        self.url().and_then(media_type_of)
+    }
+
+    /// Returns whether a model with `abilities` can read this part.
+    ///
+    /// Images need [`Ability::Vision`], audio [`Ability::Audio`], videos
+    /// [`Ability::Video`] and other files [`Ability::Pdf`]. Text is always
+    /// supported.
+    #[must_use]
+    pub fn is_supported_by(&self, abilities: &[Ability]) -> bool {
+        let required = match self {
+            Self::Text(_) => return true,
+            Self::Image(_) => Ability::Vision,
+            Self::Audio(_) => Ability::Audio,
+            Self::File(_) => {
+                if self
+                    .media_type()
+                    .is_some_and(|media_type| media_type.starts_with("video/"))
+                {
+                    Ability::Video
+                } else {
+                    Ability::Pdf
+                }
+            }
+        };
+        abilities.contains(&required)
+    }
+
+    /// Describes the part in text, for models that cannot read it.
+    #[must_use]
+    pub fn placeholder(&self) -> String {
+        let (kind, url) = match self {
+            Self::Text(text) => return text.clone(),
+            Self::Image(url) => ("image", url),
+            Self::Audio(url) => ("audio", url),
+            Self::File(url) => ("file", url),
+        };
+        let name = match url.scheme() {
+            "data" => self
+                .media_type()
+                .unwrap_or_else(|| "inline data".to_string()),
+            _ => url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|name| !name.is_empty())
+                .map_or_else(|| url.as_str().to_string(), ToString::to_string),
+        };
+        format!("[{kind} attachment not supported by this model: {name}]")
+    }
+}
+
+impl From<String> for ContentPart {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for ContentPart {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+fn data_url(media_type: &str, bytes: &[u8]) -> Url {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Url::parse(&format!("data:{media_type};base64,{encoded}"))
+        .expect("data URLs with base64 payloads are always valid")
+}
+
+/// Media type of an attachment URL, see [`ContentPart::media_type`].
+fn media_type_of(url: &Url) -> Option<String> {
+    if url.scheme() == "data" {
+        let header = url.path().split_once(',')?.0;
+        let media_type = header.split(';').next()?;
+        return (!media_type.is_empty()).then(|| media_type.to_ascii_lowercase());
+    }
+    if let Some(fragment) = url.fragment()
+        && fragment.contains('/')
+    {
+        return Some(fragment.to_ascii_lowercase());
+    }
+    let name = url.path_segments()?.next_back()?;
+    let extension = name.rsplit_once('.')?.1.to_ascii_lowercase();
+    let media_type = match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "heic" => "image/heic",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" | "oga" => "audio/ogg",
+        "flac" => "audio/flac",
+        "m4a" => "audio/mp4",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mov" => "video/quicktime",
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        _ => return None,
+    };
+    Some(media_type.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec;
@@ -261,6 +528,68 @@ mod tests {
         assert!(message.attachments().is_empty());
     }
 
+    #[test]
+    fn parts_round_trip_through_user_messages() {
+        let image = ContentPart::image_bytes("image/png", b"png");
+        let audio: ContentPart =
+            ContentPart::from_attachment("https://example.com/clip.mp3".parse::<Url>().unwrap());
+        let pdf = ContentPart::from_attachment("file:///tmp/report.pdf".parse::<Url>().unwrap());
+        let message = Message::user_with_parts([
+            ContentPart::text("Summarize these."),
+            image.clone(),
+            audio.clone(),
+            pdf.clone(),
+            ContentPart::text("Be brief."),
+        ]);
+
+        assert_eq!(message.content(), "Summarize these.\n\nBe brief.");
+        assert_eq!(
+            message.parts(),
+            vec![
+                image,
+                audio,
+                pdf,
+                ContentPart::text("Summarize these.\n\nBe brief.")
+            ]
+        );
+    }
+
+    #[test]
+    fn media_types_come_from_data_urls_fragments_and_extensions() {
+        let image = ContentPart::image_bytes("image/png", b"png");
+        assert_eq!(image.media_type().as_deref(), Some("image/png"));
+
+        let uploaded = ContentPart::from_attachment(
+            "https://files.example.com/v1/files/abc#audio/wav"
+                .parse::<Url>()
+                .unwrap(),
+        );
+        assert!(matches!(uploaded, ContentPart::Audio(_)));
+
+        let unknown =
+            ContentPart::from_attachment("https://example.com/blob".parse::<Url>().unwrap());
+        assert!(matches!(unknown, ContentPart::File(_)));
+        assert_eq!(unknown.media_type(), None);
+    }
+
+    #[test]
+    fn degrade_attachments_describes_unsupported_parts() {
+        let message = Message::user("What is in these?")
+            .with_attachment("https://example.com/cat.png".parse::<Url>().unwrap())
+            .with_attachment("https://example.com/song.mp3".parse::<Url>().unwrap());
+
+        let degraded = message.degrade_attachments(&[Ability::Vision]);
+        assert_eq!(degraded.attachments().len(), 1);
+        assert_eq!(
+            degraded.content(),
+            "[audio attachment not supported by this model: song.mp3]\n\nWhat is in these?"
+        );
+
+        let text_only = degraded.degrade_attachments(&[]);
+        assert!(text_only.attachments().is_empty());
+        assert!(text_only.content().starts_with("[image attachment"));
+    }
+
     #[test]
     fn message_clone() {
         let original = Message::user("Original");
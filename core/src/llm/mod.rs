@@ -9,7 +9,9 @@
 //! - **[`LLMRequest`]** - Encapsulates messages, tools, and parameters for model calls
 //! - **[`Event`]** - Stream events from the model (text, reasoning, tool calls)
 //! - **[`Message`]** - Represents individual messages in a conversation
+//! - **[`Thread`]** - Append-only conversation history that pairs tool calls with results
 //! - **[`Tool`]** - Function calling interface for extending model capabilities
+//! - **[`LlmError`]** - Provider-independent classification of failed calls
 //!
 //! ## Design Philosophy
 //!
@@ -76,6 +78,8 @@
 
 /// Assistant module for managing assistant-related functionality.
 pub mod assistant;
+/// Provider-independent error classification.
+pub mod error;
 /// Event types for streaming responses.
 pub mod event;
 /// Message types and conversation handling.
@@ -86,8 +90,12 @@ pub mod model;
 pub mod provider;
 /// Deep research workflows and agent capabilities.
 pub mod researcher;
+/// Append-only conversation history.
+pub mod thread;
 /// Tool system for function calling.
 pub mod tool;
+/// Source reliability scoring and contradiction detection for research.
+pub mod verification;
 
 use crate::llm::{model::Parameters, tool::Tools};
 use alloc::{
@@ -100,18 +108,22 @@ use alloc::{
 };
 use anyhow::{Context, anyhow};
 use core::{any::TypeId, future::Future};
-pub use event::{Event, ToolCall, Usage};
+pub use error::{LlmError, LlmErrorKind, StructuredOutputError};
+pub use event::{Citation, Event, ToolCall, ToolCallDelta, Usage};
 use futures_core::Stream;
 use futures_lite::{StreamExt, pin};
-pub use message::{Message, Role};
+pub use message::{ContentPart, Message, Role};
 pub use provider::LanguageModelProvider;
 pub use researcher::{
-    ResearchCitation, ResearchEvent, ResearchFinding, ResearchOptions, ResearchReport,
-    ResearchRequest, ResearchSource, ResearchStage, Researcher, ResearcherProfile,
+    Contradiction, ExportFormat, ResearchCitation, ResearchEvent, ResearchFinding, ResearchOptions,
+    ResearchReport, ResearchRequest, ResearchSource, ResearchStage, ResearchStep, Researcher,
+    ResearcherProfile,
 };
-use schemars::{JsonSchema, schema_for};
+use schemars::{JsonSchema, Schema, schema_for};
 use serde::de::DeserializeOwned;
-pub use tool::{Tool, ToolOutput};
+pub use thread::Thread;
+pub use tool::{Tool, ToolContent, ToolOutput};
+pub use verification::{FindingVerifier, SourceReliability, Verified};
 
 use crate::llm::{model::Profile, tool::json};
 
@@ -160,6 +172,46 @@ impl LLMRequest {
         self
     }
 
+    /// Constrains the response to JSON matching `schema`.
+    ///
+    /// Providers with native structured output enforce the schema while
+    /// decoding: `OpenAI` through a `json_schema` response format, Gemini
+    /// through `responseSchema`, Claude by forcing a tool call and local
+    /// llama.cpp models through a sampling grammar. Other providers at most
+    /// switch to a JSON mode.
+    #[must_use]
+    pub fn with_output_schema(mut self, schema: Schema) -> Self {
+        self.parameters.structured_outputs = true;
+        self.parameters.response_format = Some(schema);
+        self
+    }
+
+    /// Sets how often [`LanguageModel::generate`] asks the model to repair a
+    /// response that does not parse. See
+    /// [`Parameters::structured_repair_attempts`].
+    #[must_use]
+    pub const fn with_repair_attempts(mut self, attempts: u32) -> Self {
+        self.parameters.structured_repair_attempts = Some(attempts);
+        self
+    }
+
+    /// Marks the system prompt as cacheable across calls.
+    ///
+    /// Providers with explicit prompt caching place a cache breakpoint after
+    /// it; others cache prompt prefixes automatically.
+    #[must_use]
+    pub const fn cache_system_prompt(mut self) -> Self {
+        self.parameters.cache.hints.system = true;
+        self
+    }
+
+    /// Marks the tool definitions as cacheable across calls.
+    #[must_use]
+    pub const fn cache_tools(mut self) -> Self {
+        self.parameters.cache.hints.tools = true;
+        self
+    }
+
     /// Returns the current conversation messages.
     #[must_use]
     pub fn messages(&self) -> &[Message] {
@@ -259,7 +311,9 @@ pub trait LanguageModel: Sized + Send + Sync {
     /// - `Event::Text` - Visible text chunks
     /// - `Event::Reasoning` - Internal reasoning (for reasoning models)
     /// - `Event::ToolCall` - Requests to execute tools (NOT auto-executed)
+    /// - `Event::ToolCallDelta` - Partial tool arguments, for display while streaming
     /// - `Event::BuiltInToolResult` - Results from provider's built-in tools
+    /// - `Event::Citation` - Sources cited by native web search
     fn respond(&self, request: LLMRequest)
     -> impl Stream<Item = Result<Event, Self::Error>> + Send;
 
@@ -281,6 +335,13 @@ pub trait LanguageModel: Sized + Send + Sync {
     /// By default, we use a system prompt to instruct the model to generate structured output based on the provided JSON schema.
     /// However, that is not efficient enough, if supported, provider should override this method to provide native structured generation support.
     /// Native structured generation can apply decode rules in token-level, ensuring the output is always valid JSON, and reducing parsing errors.
+    ///
+    /// # Errors
+    ///
+    /// Responses that do not parse are sent back to the model with the parse
+    /// error, up to [`LLMRequest::with_repair_attempts`] times. If the last
+    /// one still fails, the error downcasts to [`StructuredOutputError`],
+    /// which holds the raw response and the number of attempts.
     fn generate<T: JsonSchema + DeserializeOwned + 'static>(
         &self,
         request: LLMRequest,
@@ -443,6 +504,10 @@ where
     Ok(result)
 }
 
+/// How often a response that fails to parse is sent back for repair, unless
+/// the request says otherwise.
+const DEFAULT_REPAIR_ATTEMPTS: u32 = 2;
+
 async fn structured_generate<T: JsonSchema + DeserializeOwned + 'static, M: LanguageModel>(
     model: &M,
     mut request: LLMRequest,
@@ -450,22 +515,44 @@ async fn structured_generate<T: JsonSchema + DeserializeOwned + 'static, M: Lang
     let schema = schema_for!(T);
 
     // If it is a string, we are not required to set up structured generation and JSON schema.
-    let json = if schema.as_value().is_string() {
+    if schema.as_value().is_string() {
         let stream = model.respond(request);
         let response = collect_text(stream).await?;
         // Let's encode it as JSON string.
-        serde_json::to_string(&response)?
-    } else {
-        let schema = json(&schema);
-        let prompt = prompts::generate(&schema);
-        request.messages.push(Message::system(prompt));
-        request.parameters.structured_outputs = true;
-
-        let stream = model.respond(request);
-        collect_text(stream).await?
-    };
-
-    parse_json_with_recovery(&json)
+        return parse_json_with_recovery(&serde_json::to_string(&response)?);
+    }
+
+    let prompt = prompts::generate(&json(&schema));
+    request.messages.push(Message::system(prompt));
+    let mut request = request.with_output_schema(schema);
+    let max_repairs = request
+        .parameters
+        .structured_repair_attempts
+        .unwrap_or(DEFAULT_REPAIR_ATTEMPTS);
+
+    // Providers without native schema enforcement can still get it wrong;
+    // show the model its output and the error and let it try again.
+    let mut repairs = 0;
+    loop {
+        let text = collect_text(model.respond(request.clone())).await?;
+        match parse_json_with_recovery(&text) {
+            Ok(value) => return Ok(value),
+            Err(err) if repairs < max_repairs => {
+                repairs += 1;
+                request.messages.push(Message::assistant(text));
+                request
+                    .messages
+                    .push(Message::user(prompts::repair(&format!("{err:#}"))));
+            }
+            Err(err) => {
+                return Err(anyhow::Error::new(StructuredOutputError::new(
+                    text,
+                    repairs + 1,
+                    format!("{err:#}"),
+                )));
+            }
+        }
+    }
 }
 
 /// Convenience helper that creates a single system + user [`LLMRequest`].
@@ -640,15 +727,84 @@ fn is_string_type<T: 'static>() -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_json_with_recovery;
-    use alloc::string::String;
+    extern crate std;
+
+    use super::{
+        Event, LLMRequest, LanguageModel, Message, StructuredOutputError, parse_json_with_recovery,
+    };
+    use crate::llm::model::Profile;
+    use alloc::{string::String, vec, vec::Vec};
+    use futures_core::Stream;
+    use schemars::JsonSchema;
     use serde::Deserialize;
+    use std::sync::Mutex;
 
-    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    #[derive(Debug, Deserialize, PartialEq, Eq, JsonSchema)]
     struct Foo {
         a: u8,
     }
 
+    /// Replies with canned text and records every request.
+    struct Scripted {
+        replies: Mutex<Vec<&'static str>>,
+        requests: Mutex<Vec<LLMRequest>>,
+    }
+
+    impl LanguageModel for Scripted {
+        type Error = core::convert::Infallible;
+
+        fn respond(
+            &self,
+            request: LLMRequest,
+        ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+            self.requests.lock().unwrap().push(request);
+            let reply = self.replies.lock().unwrap().remove(0);
+            futures_lite::stream::once(Ok(Event::Text(reply.into())))
+        }
+
+        async fn profile(&self) -> Profile {
+            Profile::new("scripted", "test", "scripted", "Canned replies", 1024)
+        }
+    }
+
+    #[test]
+    fn generate_repairs_unparseable_output() {
+        let model = Scripted {
+            replies: Mutex::new(vec!["not json", r#"{"a":6}"#]),
+            requests: Mutex::new(Vec::new()),
+        };
+        let request = LLMRequest::new([Message::user("Give me a Foo")]);
+
+        let foo: Foo = futures_lite::future::block_on(model.generate(request)).unwrap();
+        assert_eq!(foo, Foo { a: 6 });
+
+        let requests = model.requests.into_inner().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].parameters().response_format.is_some());
+        let retry = requests[1].messages();
+        assert_eq!(retry[retry.len() - 2].content(), "not json");
+        assert!(
+            retry[retry.len() - 1]
+                .content()
+                .contains("could not be parsed")
+        );
+    }
+
+    #[test]
+    fn generate_reports_raw_text_after_last_repair() {
+        let model = Scripted {
+            replies: Mutex::new(vec!["not json", "still not json"]),
+            requests: Mutex::new(Vec::new()),
+        };
+        let request = LLMRequest::new([Message::user("Give me a Foo")]).with_repair_attempts(1);
+
+        let err = futures_lite::future::block_on(model.generate::<Foo>(request)).unwrap_err();
+        let err = err.downcast_ref::<StructuredOutputError>().unwrap();
+        assert_eq!(err.raw, "still not json");
+        assert_eq!(err.attempts, 2);
+        assert_eq!(model.requests.into_inner().unwrap().len(), 2);
+    }
+
     #[test]
     fn parses_plain_json() {
         let foo: Foo = parse_json_with_recovery(r#"{"a":1}"#).unwrap();
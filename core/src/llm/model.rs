@@ -129,6 +129,12 @@ pub struct Parameters {
     /// Preferred reasoning effort when supported.
     pub reasoning_effort: Option<ReasoningEffort>,
 
+    /// Token budget for extended thinking when supported.
+    ///
+    /// Takes precedence over [`reasoning_effort`](Self::reasoning_effort) on
+    /// providers that size thinking in tokens. `0` disables thinking.
+    pub thinking_budget: Option<u32>,
+
     /// Whether the provider should include reasoning summaries in the response stream.
     pub include_reasoning: bool,
 
@@ -141,7 +147,21 @@ pub struct Parameters {
     ///
     /// When set, the model will attempt to return outputs matching this schema.
     pub response_format: Option<Schema>,
-    /// Whether to enable native Search tool for grounding.
+    /// GBNF grammar that sampling must follow, with `root` as start rule.
+    ///
+    /// Only local backends built on llama.cpp support grammars; other
+    /// providers ignore it. Takes precedence over
+    /// [`response_format`](Self::response_format) on those backends.
+    pub grammar: Option<String>,
+    /// How often [`generate`](crate::LanguageModel::generate) sends an
+    /// unparseable response back to the model for repair.
+    ///
+    /// Defaults to 2 when unset; `0` fails on the first bad response.
+    pub structured_repair_attempts: Option<u32>,
+    /// Whether to enable the provider's native web search for grounding.
+    ///
+    /// Claude, Gemini and `OpenAI` run the search server-side and report
+    /// the sources they used as [`Event::Citation`](crate::llm::Event::Citation).
     pub websearch: bool,
     /// Whether to enable native Code Execution tool.
     pub code_execution: bool,
@@ -192,6 +212,9 @@ impl_with_methods! {
         logprobs: bool,
         top_logprobs: u8,
         stop: Vec<String>,
+        thinking_budget: u32,
+        structured_repair_attempts: u32,
+        grammar: String,
     }
 }
 
@@ -210,7 +233,7 @@ impl Parameters {
         self
     }
 
-    /// Sets whether to enable native Google Search tool.
+    /// Sets whether to enable the provider's native web search.
     #[must_use]
     pub const fn websearch(mut self, enabled: bool) -> Self {
         self.websearch = enabled;
@@ -260,14 +283,24 @@ impl Parameters {
         self
     }
 
-    /// Clears all provider-specific cache options.
+    /// Marks the system prompt as cacheable (see [`CacheHints`]).
+    #[must_use]
+    pub const fn cache_system_prompt(mut self, enabled: bool) -> Self {
+        self.cache.hints.system = enabled;
+        self
+    }
+
+    /// Marks the tool definitions as cacheable (see [`CacheHints`]).
+    #[must_use]
+    pub const fn cache_tools(mut self, enabled: bool) -> Self {
+        self.cache.hints.tools = enabled;
+        self
+    }
+
+    /// Clears all provider-specific cache options and cache hints.
     #[must_use]
     pub fn without_cache(mut self) -> Self {
-        self.cache = CacheOptions {
-            openai: None,
-            claude: None,
-            gemini: None,
-        };
+        self.cache = CacheOptions::default();
         self
     }
 
@@ -347,13 +380,46 @@ pub struct CacheOptions {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub gemini: Option<GeminiPromptCache>,
+    /// Provider-independent hints about which request parts are cacheable.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "CacheHints::is_empty")
+    )]
+    pub hints: CacheHints,
 }
 
 impl CacheOptions {
     /// Returns true when no provider cache options are set.
     #[must_use]
     pub const fn is_empty(&self) -> bool {
-        self.openai.is_none() && self.claude.is_none() && self.gemini.is_none()
+        self.openai.is_none()
+            && self.claude.is_none()
+            && self.gemini.is_none()
+            && self.hints.is_empty()
+    }
+}
+
+/// Request parts worth caching across calls.
+///
+/// Providers with explicit caching (Claude) place cache breakpoints after the
+/// marked parts; providers that cache prompt prefixes automatically (`OpenAI`,
+/// Gemini) ignore the hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheHints {
+    /// Cache the system prompt.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub system: bool,
+    /// Cache the tool definitions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tools: bool,
+}
+
+impl CacheHints {
+    /// Returns true when nothing is marked cacheable.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        !self.system && !self.tools
     }
 }
 
@@ -375,12 +441,12 @@ pub struct OpenAIPromptCache {
     pub retention: Option<OpenAIPromptCacheRetention>,
 }
 
-/// OpenAI prompt cache retention policies.
+/// `OpenAI` prompt cache retention policies.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum OpenAIPromptCacheRetention {
-    /// Keep cache entries in-memory (default OpenAI retention mode).
+    /// Keep cache entries in-memory (default `OpenAI` retention mode).
     InMemory,
     /// Keep cache entries for 24 hours.
     #[cfg_attr(feature = "serde", serde(rename = "24h"))]
@@ -1065,6 +1131,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cache_hints_mark_options_non_empty() {
+        let params = Parameters::default()
+            .cache_system_prompt(true)
+            .cache_tools(true);
+        assert!(params.cache.hints.system && params.cache.hints.tools);
+        assert!(!params.cache.is_empty());
+        assert!(params.without_cache().cache.is_empty());
+    }
+
     #[test]
     fn cache_options_empty_state_changes_with_provider_values() {
         let mut cache = CacheOptions::default();
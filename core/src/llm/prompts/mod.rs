@@ -17,3 +17,13 @@ Requirements:
 Example format: {{"field1": "value1", "field2": 123}}"#
     )
 }
+
+pub fn repair(error: &str) -> String {
+    format!(
+        r"Your previous response could not be parsed against the required JSON schema:
+
+{error}
+
+Respond again with only the corrected JSON, no additional text or code blocks."
+    )
+}
@@ -3,10 +3,17 @@
 //! This module provides abstractions for AI-powered research agents that can conduct
 //! in-depth investigations by planning, searching, reading sources, and synthesizing findings.
 
-use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
-use core::future::Future;
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{fmt::Write, future::Future};
 use futures_core::Stream;
 use futures_lite::{StreamExt, pin};
+use serde_json::{Value, json};
 
 /// Request describing a deep-research task.
 #[derive(Clone, Debug)]
@@ -189,12 +196,38 @@ pub enum ResearchStage {
     Searching,
     /// Reading primary sources.
     Reading,
+    /// Cross-checking findings against each other and their sources.
+    Verifying,
     /// Writing the report.
     Writing,
     /// Report finalized.
     Completed,
 }
 
+impl ResearchStage {
+    /// Lowercase name of the stage, as used in exports.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Planning => "planning",
+            Self::Searching => "searching",
+            Self::Reading => "reading",
+            Self::Verifying => "verifying",
+            Self::Writing => "writing",
+            Self::Completed => "completed",
+        }
+    }
+}
+
+/// Stage update recorded while a report was produced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResearchStep {
+    /// Stage the update belongs to.
+    pub stage: ResearchStage,
+    /// Human-readable update, such as the URL being fetched.
+    pub message: String,
+}
+
 /// Structured findings gathered by the researcher.
 #[derive(Clone, Debug)]
 pub struct ResearchFinding {
@@ -206,6 +239,8 @@ pub struct ResearchFinding {
     pub confidence: Option<f32>,
     /// Citations backing the claim.
     pub citations: Vec<ResearchCitation>,
+    /// Other findings this one disagrees with.
+    pub contradictions: Vec<Contradiction>,
 }
 
 impl ResearchFinding {
@@ -217,6 +252,7 @@ impl ResearchFinding {
             summary: summary.into(),
             confidence: None,
             citations: Vec::new(),
+            contradictions: Vec::new(),
         }
     }
 
@@ -233,6 +269,33 @@ impl ResearchFinding {
         self.citations.push(citation);
         self
     }
+
+    /// Flags a contradiction with another finding.
+    #[must_use]
+    pub fn contradiction(mut self, contradiction: Contradiction) -> Self {
+        self.contradictions.push(contradiction);
+        self
+    }
+}
+
+/// Disagreement between two findings that cannot both be true.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contradiction {
+    /// Title of the conflicting finding.
+    pub finding: String,
+    /// What the two findings disagree about.
+    pub explanation: String,
+}
+
+impl Contradiction {
+    /// Creates a contradiction with the finding titled `finding`.
+    #[must_use]
+    pub fn new(finding: impl Into<String>, explanation: impl Into<String>) -> Self {
+        Self {
+            finding: finding.into(),
+            explanation: explanation.into(),
+        }
+    }
 }
 
 /// Normalized citation metadata.
@@ -244,6 +307,10 @@ pub struct ResearchCitation {
     pub title: Option<String>,
     /// Optional snippet for quick reference.
     pub snippet: Option<String>,
+    /// Publication date, as an ISO 8601 date or year.
+    pub published: Option<String>,
+    /// How trustworthy the source is (0-1), when it has been scored.
+    pub reliability: Option<f32>,
 }
 
 impl ResearchCitation {
@@ -254,6 +321,8 @@ impl ResearchCitation {
             url: url.into(),
             title: None,
             snippet: None,
+            published: None,
+            reliability: None,
         }
     }
 
@@ -270,17 +339,46 @@ impl ResearchCitation {
         self.snippet = Some(snippet.into());
         self
     }
+
+    /// Adds the publication date (`2024-05-01` or `2024`).
+    #[must_use]
+    pub fn published(mut self, date: impl Into<String>) -> Self {
+        self.published = Some(date.into());
+        self
+    }
+
+    /// Sets a reliability score.
+    #[must_use]
+    pub const fn reliability(mut self, reliability: f32) -> Self {
+        self.reliability = Some(reliability);
+        self
+    }
 }
 
 /// Final report returned by the researcher.
 #[derive(Clone, Debug, Default)]
 pub struct ResearchReport {
+    /// Question the report answers.
+    pub query: Option<String>,
     /// Executive summary.
     pub summary: String,
     /// Structured findings.
     pub findings: Vec<ResearchFinding>,
     /// Deduplicated citation list.
     pub citations: Vec<ResearchCitation>,
+    /// Stage updates emitted while researching, in order.
+    pub trace: Vec<ResearchStep>,
+}
+
+/// Output formats for [`ResearchReport::export`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// Markdown with YAML front matter.
+    Markdown,
+    /// Standalone HTML fragment wrapped in an `<article>`.
+    Html,
+    /// Pretty-printed JSON.
+    Json,
 }
 
 impl ResearchReport {
@@ -300,6 +398,300 @@ impl ResearchReport {
     pub fn push_citation(&mut self, citation: ResearchCitation) {
         self.citations.push(citation);
     }
+
+    /// Renders the report for display or further processing.
+    ///
+    /// Every format numbers sources from 1: the report's citations first,
+    /// then any only cited by findings, without duplicate URLs. Findings
+    /// refer to sources by these numbers.
+    ///
+    /// The JSON export has the shape
+    /// `{"query", "summary", "findings": [{"claim", "summary", "confidence",
+    /// "sources", "contradictions"}], "citations": [{"id", "url", "title",
+    /// "snippet", "published", "reliability"}], "trace": [{"stage",
+    /// "message"}]}`.
+    #[must_use]
+    pub fn export(&self, format: ExportFormat) -> String {
+        let sources = self.sources();
+        match format {
+            ExportFormat::Markdown => self.to_markdown(&sources),
+            ExportFormat::Html => self.to_html(&sources),
+            ExportFormat::Json => self.to_json(&sources),
+        }
+    }
+
+    fn title(&self) -> &str {
+        self.query.as_deref().unwrap_or("Research report")
+    }
+
+    /// All cited sources, deduplicated by URL.
+    fn sources(&self) -> Vec<&ResearchCitation> {
+        let mut sources: Vec<&ResearchCitation> = Vec::new();
+        let cited = self
+            .citations
+            .iter()
+            .chain(self.findings.iter().flat_map(|finding| &finding.citations));
+        for citation in cited {
+            if !sources.iter().any(|source| source.url == citation.url) {
+                sources.push(citation);
+            }
+        }
+        sources
+    }
+
+    /// 1-based numbers of the sources `finding` cites.
+    fn source_ids(finding: &ResearchFinding, sources: &[&ResearchCitation]) -> Vec<usize> {
+        finding
+            .citations
+            .iter()
+            .filter_map(|citation| {
+                sources
+                    .iter()
+                    .position(|source| source.url == citation.url)
+                    .map(|index| index + 1)
+            })
+            .collect()
+    }
+
+    fn to_markdown(&self, sources: &[&ResearchCitation]) -> String {
+        let contradictions: usize = self
+            .findings
+            .iter()
+            .map(|finding| finding.contradictions.len())
+            .sum();
+        let mut out = String::from("---\n");
+        let _ = writeln!(out, "title: {}", yaml_string(self.title()));
+        let _ = writeln!(out, "findings: {}", self.findings.len());
+        let _ = writeln!(out, "sources: {}", sources.len());
+        // Each contradiction is recorded on both findings.
+        let _ = writeln!(out, "contradictions: {}", contradictions / 2);
+        out.push_str("---\n\n");
+
+        let _ = writeln!(out, "# {}\n", self.title());
+        if !self.summary.is_empty() {
+            let _ = writeln!(out, "{}\n", self.summary.trim());
+        }
+
+        if !self.findings.is_empty() {
+            out.push_str("## Findings\n\n");
+        }
+        for (index, finding) in self.findings.iter().enumerate() {
+            let _ = writeln!(out, "### {}. {}\n", index + 1, finding.title);
+            if !finding.summary.is_empty() {
+                let _ = writeln!(out, "{}\n", finding.summary.trim());
+            }
+            if let Some(confidence) = finding.confidence {
+                let _ = writeln!(out, "Confidence: {confidence:.2}\n");
+            }
+            for contradiction in &finding.contradictions {
+                let _ = writeln!(
+                    out,
+                    "> **Contradicts \"{}\":** {}\n",
+                    contradiction.finding, contradiction.explanation
+                );
+            }
+            let ids = Self::source_ids(finding, sources);
+            if !ids.is_empty() {
+                let refs: Vec<String> = ids.iter().map(|id| format!("[{id}]")).collect();
+                let _ = writeln!(out, "Sources: {}\n", refs.join(", "));
+            }
+        }
+
+        if !sources.is_empty() {
+            out.push_str("## Sources\n\n");
+        }
+        for (index, source) in sources.iter().enumerate() {
+            let _ = write!(
+                out,
+                "{}. [{}]({})",
+                index + 1,
+                source.title.as_deref().unwrap_or(&source.url),
+                source.url
+            );
+            if let Some(published) = &source.published {
+                let _ = write!(out, " ({published})");
+            }
+            if let Some(snippet) = &source.snippet {
+                let _ = write!(out, ": {}", snippet.trim());
+            }
+            out.push('\n');
+        }
+
+        if !self.trace.is_empty() {
+            out.push_str("\n## Trace\n\n");
+        }
+        for step in &self.trace {
+            let _ = writeln!(out, "- **{}**: {}", step.stage.as_str(), step.message);
+        }
+        out
+    }
+
+    fn to_html(&self, sources: &[&ResearchCitation]) -> String {
+        let mut out = String::from("<article class=\"research-report\">\n");
+        let _ = writeln!(out, "<h1>{}</h1>", escape_html(self.title()));
+        for paragraph in paragraphs(&self.summary) {
+            let _ = writeln!(out, "<p>{}</p>", escape_html(paragraph));
+        }
+
+        if !self.findings.is_empty() {
+            out.push_str("<section class=\"findings\">\n<h2>Findings</h2>\n");
+            for (index, finding) in self.findings.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "<section class=\"finding\" id=\"finding-{}\">\n<h3>{}</h3>",
+                    index + 1,
+                    escape_html(&finding.title)
+                );
+                for paragraph in paragraphs(&finding.summary) {
+                    let _ = writeln!(out, "<p>{}</p>", escape_html(paragraph));
+                }
+                if let Some(confidence) = finding.confidence {
+                    let _ = writeln!(
+                        out,
+                        "<p class=\"confidence\">Confidence: {confidence:.2}</p>"
+                    );
+                }
+                if !finding.contradictions.is_empty() {
+                    out.push_str("<ul class=\"contradictions\">\n");
+                    for contradiction in &finding.contradictions {
+                        let _ = writeln!(
+                            out,
+                            "<li>Contradicts <q>{}</q>: {}</li>",
+                            escape_html(&contradiction.finding),
+                            escape_html(&contradiction.explanation)
+                        );
+                    }
+                    out.push_str("</ul>\n");
+                }
+                let ids = Self::source_ids(finding, sources);
+                if !ids.is_empty() {
+                    let refs: Vec<String> = ids
+                        .iter()
+                        .map(|id| format!("<a href=\"#source-{id}\">[{id}]</a>"))
+                        .collect();
+                    let _ = writeln!(out, "<p class=\"sources\">{}</p>", refs.join(" "));
+                }
+                out.push_str("</section>\n");
+            }
+            out.push_str("</section>\n");
+        }
+
+        if !sources.is_empty() {
+            out.push_str("<section class=\"sources\">\n<h2>Sources</h2>\n<ol>\n");
+            for (index, source) in sources.iter().enumerate() {
+                let _ = write!(
+                    out,
+                    "<li id=\"source-{}\"><a href=\"{}\">{}</a>",
+                    index + 1,
+                    escape_html(&source.url),
+                    escape_html(source.title.as_deref().unwrap_or(&source.url))
+                );
+                if let Some(published) = &source.published {
+                    let _ = write!(out, " <time>{}</time>", escape_html(published));
+                }
+                if let Some(snippet) = &source.snippet {
+                    let _ = write!(out, " <blockquote>{}</blockquote>", escape_html(snippet));
+                }
+                out.push_str("</li>\n");
+            }
+            out.push_str("</ol>\n</section>\n");
+        }
+
+        if !self.trace.is_empty() {
+            out.push_str("<section class=\"trace\">\n<h2>Trace</h2>\n<ol>\n");
+            for step in &self.trace {
+                let _ = writeln!(
+                    out,
+                    "<li data-stage=\"{}\">{}</li>",
+                    step.stage.as_str(),
+                    escape_html(&step.message)
+                );
+            }
+            out.push_str("</ol>\n</section>\n");
+        }
+        out.push_str("</article>\n");
+        out
+    }
+
+    fn to_json(&self, sources: &[&ResearchCitation]) -> String {
+        let findings: Vec<Value> = self
+            .findings
+            .iter()
+            .map(|finding| {
+                let contradictions: Vec<Value> = finding
+                    .contradictions
+                    .iter()
+                    .map(|contradiction| {
+                        json!({
+                            "finding": contradiction.finding,
+                            "explanation": contradiction.explanation,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "claim": finding.title,
+                    "summary": finding.summary,
+                    "confidence": finding.confidence,
+                    "sources": Self::source_ids(finding, sources),
+                    "contradictions": contradictions,
+                })
+            })
+            .collect();
+        let citations: Vec<Value> = sources
+            .iter()
+            .enumerate()
+            .map(|(index, source)| {
+                json!({
+                    "id": index + 1,
+                    "url": source.url,
+                    "title": source.title,
+                    "snippet": source.snippet,
+                    "published": source.published,
+                    "reliability": source.reliability,
+                })
+            })
+            .collect();
+        let trace: Vec<Value> = self
+            .trace
+            .iter()
+            .map(|step| json!({ "stage": step.stage.as_str(), "message": step.message }))
+            .collect();
+
+        let report = json!({
+            "query": self.query,
+            "summary": self.summary,
+            "findings": findings,
+            "citations": citations,
+            "trace": trace,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_else(|_| report.to_string())
+    }
+}
+
+/// A YAML double-quoted scalar; JSON string syntax is valid YAML.
+fn yaml_string(value: &str) -> String {
+    Value::String(value.to_string()).to_string()
+}
+
+fn paragraphs(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 /// Metadata describing capabilities of a research provider.
@@ -390,10 +782,70 @@ async fn research_report<R: Researcher>(
         match event {
             ResearchEvent::Finding(finding) => report.push_finding(finding),
             ResearchEvent::Citation(citation) => report.push_citation(citation),
-            ResearchEvent::Finalized(final_report) => return Ok(final_report),
-            ResearchEvent::Stage { .. } => {}
+            ResearchEvent::Finalized(mut final_report) => {
+                if final_report.trace.is_empty() {
+                    final_report.trace = report.trace;
+                }
+                report = final_report;
+                break;
+            }
+            ResearchEvent::Stage { stage, message } => {
+                report.trace.push(ResearchStep { stage, message });
+            }
         }
     }
 
+    report.query.get_or_insert_with(|| request.query.clone());
     Ok(report)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ResearchReport {
+        let source = ResearchCitation::new("https://example.org/a")
+            .title("A <study>")
+            .published("2024");
+        ResearchReport {
+            query: Some(String::from("Is coffee healthy?")),
+            summary: String::from("Mostly."),
+            findings: alloc::vec![
+                ResearchFinding::new("Lowers risk", "Moderate intake helps.")
+                    .confidence(0.8)
+                    .citation(source.clone())
+                    .contradiction(Contradiction::new("Raises risk", "Opposite effect.")),
+                ResearchFinding::new("Raises risk", "Heavy intake hurts.")
+                    .citation(ResearchCitation::new("https://example.com/b"))
+                    .contradiction(Contradiction::new("Lowers risk", "Opposite effect.")),
+            ],
+            citations: alloc::vec![source],
+            trace: alloc::vec![ResearchStep {
+                stage: ResearchStage::Reading,
+                message: String::from("https://example.org/a"),
+            }],
+        }
+    }
+
+    #[test]
+    fn exports_numbered_sources() {
+        let report = report();
+
+        let markdown = report.export(ExportFormat::Markdown);
+        assert!(markdown.starts_with("---\ntitle: \"Is coffee healthy?\"\n"));
+        assert!(markdown.contains("contradictions: 1\n"));
+        assert!(markdown.contains("### 2. Raises risk"));
+        assert!(markdown.contains("Sources: [2]"));
+        assert!(markdown.contains("- **reading**: https://example.org/a"));
+
+        let html = report.export(ExportFormat::Html);
+        assert!(html.contains("A &lt;study&gt;"));
+        assert!(html.contains("<a href=\"#source-1\">[1]</a>"));
+
+        let json: Value = serde_json::from_str(&report.export(ExportFormat::Json)).unwrap();
+        assert_eq!(json["findings"][1]["sources"], json!([2]));
+        assert_eq!(json["citations"].as_array().unwrap().len(), 2);
+        assert_eq!(json["citations"][0]["published"], "2024");
+        assert_eq!(json["trace"][0]["stage"], "reading");
+    }
+}
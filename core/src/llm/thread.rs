@@ -0,0 +1,269 @@
+//! # Conversation Threads
+//!
+//! A [`Thread`] is the message history of one conversation. It only grows:
+//! messages are appended in order and never rewritten, so a thread can be
+//! persisted, forked or replayed without surprises. It also keeps track of
+//! tool calls the model made that have not been answered yet, which most
+//! providers reject:
+//!
+//! ```rust,ignore
+//! use aither_core::llm::{Event, Thread};
+//!
+//! let mut thread = Thread::with_system("You are a helpful assistant.");
+//! thread.user("What's the weather in Paris?");
+//!
+//! let mut calls = Vec::new();
+//! let mut text = String::new();
+//! let mut stream = model.respond(thread.to_request());
+//! while let Some(event) = stream.next().await {
+//!     match event? {
+//!         Event::Text(chunk) => text.push_str(&chunk),
+//!         Event::ToolCall(call) => calls.push(call),
+//!         _ => {}
+//!     }
+//! }
+//! thread.assistant_with_tool_calls(text, calls);
+//!
+//! for call in thread.pending_tool_calls().cloned().collect::<Vec<_>>() {
+//!     thread.tool_result(&call.id, run(&call).await)?;
+//! }
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use anyhow::bail;
+
+use super::{LLMRequest, Message, ToolCall};
+
+/// Append-only message history of a conversation.
+///
+/// Serializes as a plain array of [`Message`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Thread {
+    messages: Vec<Message>,
+}
+
+impl Thread {
+    /// Creates an empty thread.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            messages: Vec::new(),
+        }
+    }
+
+    /// Creates a thread that starts with a system prompt.
+    #[must_use]
+    pub fn with_system(content: impl Into<String>) -> Self {
+        let mut thread = Self::new();
+        thread.system(content);
+        thread
+    }
+
+    /// Appends a message as is.
+    ///
+    /// Unlike [`Self::tool_result`], tool results pushed this way are not
+    /// checked against the calls they answer.
+    pub fn push(&mut self, message: Message) -> &mut Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// Appends a system message.
+    pub fn system(&mut self, content: impl Into<String>) -> &mut Self {
+        self.push(Message::system(content))
+    }
+
+    /// Appends a user message.
+    pub fn user(&mut self, content: impl Into<String>) -> &mut Self {
+        self.push(Message::user(content))
+    }
+
+    /// Appends an assistant message.
+    pub fn assistant(&mut self, content: impl Into<String>) -> &mut Self {
+        self.push(Message::assistant(content))
+    }
+
+    /// Appends an assistant message that requests tool calls.
+    ///
+    /// Each call stays pending until [`Self::tool_result`] answers it.
+    pub fn assistant_with_tool_calls(
+        &mut self,
+        content: impl Into<String>,
+        tool_calls: Vec<ToolCall>,
+    ) -> &mut Self {
+        self.push(Message::assistant_with_tool_calls(content, tool_calls))
+    }
+
+    /// Appends the result of a pending tool call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no pending call has the id `tool_call_id`, either
+    /// because the model never made it or because it already has a result.
+    pub fn tool_result(
+        &mut self,
+        tool_call_id: &str,
+        content: impl Into<String>,
+    ) -> crate::Result<&mut Self> {
+        if !self
+            .pending_tool_calls()
+            .any(|call| call.id == tool_call_id)
+        {
+            bail!("no pending tool call with id `{tool_call_id}`");
+        }
+        Ok(self.push(Message::tool(tool_call_id, content)))
+    }
+
+    /// Answers every pending tool call with `content`.
+    ///
+    /// Use this when a turn is abandoned, such as after a cancellation, so
+    /// the thread can be sent to a provider again.
+    pub fn close_pending(&mut self, content: &str) -> &mut Self {
+        let ids: Vec<String> = self
+            .pending_tool_calls()
+            .map(|call| call.id.clone())
+            .collect();
+        for id in ids {
+            self.push(Message::tool(id, content));
+        }
+        self
+    }
+
+    /// Tool calls without a result, in the order the model made them.
+    pub fn pending_tool_calls(&self) -> impl Iterator<Item = &ToolCall> {
+        self.messages
+            .iter()
+            .enumerate()
+            .flat_map(|(index, message)| message.tool_calls().iter().map(move |call| (index, call)))
+            .filter(|(index, call)| {
+                !self.messages[index + 1..]
+                    .iter()
+                    .any(|message| message.tool_call_id() == Some(call.id.as_str()))
+            })
+            .map(|(_, call)| call)
+    }
+
+    /// Returns `true` if every tool call has a result.
+    #[must_use]
+    pub fn is_settled(&self) -> bool {
+        self.pending_tool_calls().next().is_none()
+    }
+
+    /// The messages, oldest first.
+    #[must_use]
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// The most recent message.
+    #[must_use]
+    pub fn last(&self) -> Option<&Message> {
+        self.messages.last()
+    }
+
+    /// Number of messages.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if the thread has no messages.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Builds a request for the whole conversation with default parameters.
+    #[must_use]
+    pub fn to_request(&self) -> LLMRequest {
+        LLMRequest::new(self.messages.clone())
+    }
+
+    /// Returns the messages, consuming the thread.
+    #[must_use]
+    pub fn into_messages(self) -> Vec<Message> {
+        self.messages
+    }
+}
+
+impl From<Vec<Message>> for Thread {
+    fn from(messages: Vec<Message>) -> Self {
+        Self { messages }
+    }
+}
+
+impl FromIterator<Message> for Thread {
+    fn from_iter<I: IntoIterator<Item = Message>>(iter: I) -> Self {
+        Self {
+            messages: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Message> for Thread {
+    fn extend<I: IntoIterator<Item = Message>>(&mut self, iter: I) {
+        self.messages.extend(iter);
+    }
+}
+
+impl From<Thread> for LLMRequest {
+    fn from(thread: Thread) -> Self {
+        Self::new(thread.messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn call(id: &str) -> ToolCall {
+        ToolCall::new(id, "weather", serde_json::json!({ "city": "Paris" }))
+    }
+
+    #[test]
+    fn tool_results_pair_with_pending_calls() {
+        let mut thread = Thread::with_system("be brief");
+        thread
+            .user("weather?")
+            .assistant_with_tool_calls("", vec![call("a"), call("b")]);
+        assert_eq!(thread.pending_tool_calls().count(), 2);
+
+        thread.tool_result("b", "sunny").unwrap();
+        let pending: Vec<_> = thread.pending_tool_calls().map(|call| &call.id).collect();
+        assert_eq!(pending, ["a"]);
+
+        assert!(thread.tool_result("b", "again").is_err());
+        assert!(thread.tool_result("missing", "?").is_err());
+
+        thread.close_pending("cancelled");
+        assert!(thread.is_settled());
+        assert_eq!(thread.last(), Some(&Message::tool("a", "cancelled")));
+    }
+
+    #[test]
+    fn converts_into_request() {
+        let mut thread = Thread::new();
+        thread.user("hi").assistant("hello");
+        assert_eq!(thread.to_request().messages(), thread.messages());
+
+        let request = LLMRequest::from(thread.clone());
+        assert_eq!(request.messages().len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_message_array() {
+        let mut thread = Thread::new();
+        thread.user("hi");
+        let json = serde_json::to_value(&thread).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{ "role": "user", "content": "hi" }])
+        );
+        assert_eq!(serde_json::from_value::<Thread>(json).unwrap(), thread);
+    }
+}
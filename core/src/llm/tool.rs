@@ -162,11 +162,13 @@ use serde::{Serialize, de::DeserializeOwned};
 /// Tools return either:
 /// - `Done` - operation completed with no output (e.g., "file deleted")
 /// - `Output` - operation produced content with a MIME type
+/// - `Blocks` - operation produced several [`ToolContent`] blocks, such as a
+///   screenshot together with the text extracted from it
 ///
 /// # Example
 ///
 /// ```rust,ignore
-/// use aither::llm::tool::ToolOutput;
+/// use aither::llm::tool::{ToolContent, ToolOutput};
 ///
 /// // Tool that produces text
 /// fn search_tool() -> ToolOutput {
@@ -177,6 +179,15 @@ use serde::{Serialize, de::DeserializeOwned};
 /// fn delete_tool() -> ToolOutput {
 ///     ToolOutput::Done
 /// }
+///
+/// // Tool that returns a screenshot, the text on it and some metadata
+/// fn screenshot_tool(png: Vec<u8>, ocr: String) -> aither::Result<ToolOutput> {
+///     Ok(ToolOutput::blocks([
+///         ToolContent::image(png, "image/png"),
+///         ToolContent::text(ocr),
+///         ToolContent::json(&serde_json::json!({ "width": 1280, "height": 720 }))?,
+///     ]))
+/// }
 /// ```
 #[derive(Debug, Clone)]
 pub enum ToolOutput {
@@ -191,6 +202,109 @@ pub enum ToolOutput {
         /// Raw content bytes
         content: Vec<u8>,
     },
+
+    /// Tool produced an ordered list of content blocks.
+    Blocks(Vec<ToolContent>),
+}
+
+/// One block of a multi-part [`ToolOutput`].
+///
+/// Blocks keep their order when they are handed to the model. Text, JSON,
+/// file references and errors become part of the tool message; images are
+/// attached to the conversation for providers that accept them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolContent {
+    /// Plain text.
+    Text(String),
+    /// Structured data.
+    Json(Value),
+    /// Image data.
+    Image {
+        /// MIME type of the image (e.g., `image/png`)
+        mime: Mime,
+        /// Raw image bytes
+        data: Vec<u8>,
+    },
+    /// Reference to a file the tool produced or found, by path or URI.
+    File {
+        /// Path or URI of the file
+        uri: String,
+        /// MIME type of the file, if known
+        mime: Option<Mime>,
+    },
+    /// A failure that doesn't fail the whole call, such as one URL out of
+    /// several that could not be fetched.
+    Error(String),
+}
+
+impl ToolContent {
+    /// Creates a text block.
+    #[must_use]
+    pub fn text(s: impl Into<String>) -> Self {
+        Self::Text(s.into())
+    }
+
+    /// Creates a JSON block from a serializable value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn json<T: Serialize>(value: &T) -> Result<Self> {
+        Ok(Self::Json(serde_json::to_value(value)?))
+    }
+
+    /// Creates an image block.
+    #[must_use]
+    pub fn image(data: Vec<u8>, media_type: &str) -> Self {
+        Self::Image {
+            mime: media_type.parse().unwrap_or(mime::APPLICATION_OCTET_STREAM),
+            data,
+        }
+    }
+
+    /// Creates a file reference block.
+    #[must_use]
+    pub fn file(uri: impl Into<String>) -> Self {
+        Self::File {
+            uri: uri.into(),
+            mime: None,
+        }
+    }
+
+    /// Creates an error block.
+    #[must_use]
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::Error(message.into())
+    }
+
+    /// Sets the MIME type of a file reference block.
+    ///
+    /// Other blocks are returned unchanged.
+    #[must_use]
+    pub fn with_mime(mut self, media_type: &str) -> Self {
+        if let Self::File { mime, .. } = &mut self {
+            *mime = media_type.parse().ok();
+        }
+        self
+    }
+
+    /// Text representation of this block, with placeholders for images.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Json(value) => value.to_string(),
+            Self::Image { mime, data } => {
+                format!("[image: {}, {} bytes]", mime.essence_str(), data.len())
+            }
+            Self::File {
+                uri,
+                mime: Some(mime),
+            } => format!("[file: {uri} ({})]", mime.essence_str()),
+            Self::File { uri, mime: None } => format!("[file: {uri}]"),
+            Self::Error(message) => format!("Error: {message}"),
+        }
+    }
 }
 
 impl ToolOutput {
@@ -235,6 +349,12 @@ impl ToolOutput {
         }
     }
 
+    /// Creates an output from content blocks, keeping their order.
+    #[must_use]
+    pub fn blocks(blocks: impl IntoIterator<Item = ToolContent>) -> Self {
+        Self::Blocks(blocks.into_iter().collect())
+    }
+
     /// Returns `true` if this is a `Done` variant.
     #[must_use]
     pub const fn is_done(&self) -> bool {
@@ -245,8 +365,8 @@ impl ToolOutput {
     #[must_use]
     pub fn content(&self) -> Option<&[u8]> {
         match self {
-            Self::Done => None,
             Self::Output { content, .. } => Some(content),
+            Self::Done | Self::Blocks(_) => None,
         }
     }
 
@@ -254,8 +374,17 @@ impl ToolOutput {
     #[must_use]
     pub const fn mime(&self) -> Option<&Mime> {
         match self {
-            Self::Done => None,
             Self::Output { mime, .. } => Some(mime),
+            Self::Done | Self::Blocks(_) => None,
+        }
+    }
+
+    /// Returns the content blocks if this is a `Blocks` variant.
+    #[must_use]
+    pub fn as_blocks(&self) -> Option<&[ToolContent]> {
+        match self {
+            Self::Blocks(blocks) => Some(blocks),
+            Self::Done | Self::Output { .. } => None,
         }
     }
 
@@ -264,11 +393,42 @@ impl ToolOutput {
     /// Returns `None` if:
     /// - This is a `Done` variant
     /// - The content is not valid UTF-8
+    /// - This is a `Blocks` variant with anything but a single text block
     #[must_use]
     pub fn as_str(&self) -> Option<&str> {
         match self {
             Self::Done => None,
             Self::Output { content, .. } => core::str::from_utf8(content).ok(),
+            Self::Blocks(blocks) => match blocks.as_slice() {
+                [ToolContent::Text(text)] => Some(text),
+                _ => None,
+            },
+        }
+    }
+
+    /// Text representation of the whole output.
+    ///
+    /// Blocks are joined by newlines, with placeholders for images; binary
+    /// content that is not UTF-8 is only described.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Done => String::new(),
+            Self::Output { mime, content } => core::str::from_utf8(content).map_or_else(
+                |_| {
+                    format!(
+                        "[binary output: {}, {} bytes]",
+                        mime.essence_str(),
+                        content.len()
+                    )
+                },
+                ToString::to_string,
+            ),
+            Self::Blocks(blocks) => blocks
+                .iter()
+                .map(ToolContent::to_text)
+                .collect::<Vec<_>>()
+                .join("\n"),
         }
     }
 }
@@ -441,15 +601,27 @@ impl<T: Tool + 'static> ToolImpl for T {
 /// // tools.register(Calculator);
 /// let definitions = tools.definitions();
 /// // let result = tools.call("calculator", r#"{"operation": "add", "a": 5, "b": 3}"#).await;
+///
+/// // Only offer `apply_patch` once there is a diff to apply.
+/// tools.disable("apply_patch", "no diff has been produced yet");
+/// // ...
+/// tools.enable("apply_patch");
+/// tools.set_description("apply_patch", "Applies the diff for src/main.rs");
 /// ```
 pub struct Tools {
-    tools: BTreeMap<Cow<'static, str>, Box<dyn ToolImpl>>,
+    registry: BTreeMap<Cow<'static, str>, Box<dyn ToolImpl>>,
+    /// Reasons of disabled tools, by tool name.
+    disabled: BTreeMap<String, String>,
+    /// Descriptions replacing the tools' own, by tool name.
+    descriptions: BTreeMap<String, String>,
 }
 
 impl Debug for Tools {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Tools")
-            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .field("tools", &self.registry.keys().collect::<Vec<_>>())
+            .field("disabled", &self.disabled)
+            .field("descriptions", &self.descriptions)
             .finish()
     }
 }
@@ -482,10 +654,15 @@ impl ToolDefinition {
             schema_for!(ToolArgument<T::Arguments>)
         };
 
-        // Extract description from schema (rustdoc on Args struct)
-        let description = arguments
-            .clone()
-            .to_value()
+        // Extract description from schema (rustdoc on Args struct), with
+        // examples of the whole arguments object folded in
+        let mut root = arguments.clone().to_value();
+        if let Value::Object(map) = &mut root
+            && let Some(Value::Array(examples)) = map.remove("examples")
+        {
+            fold_examples(map, &examples);
+        }
+        let description = root
             .get("description")
             .and_then(|v| v.as_str())
             .map(|s| Cow::Owned(s.to_string()))
@@ -534,6 +711,13 @@ impl ToolDefinition {
         &self.description
     }
 
+    /// Replaces the tool's description.
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.description = description.into();
+        self
+    }
+
     /// Return an OpenAI-compatible JSON schema for the tool's arguments.
     ///
     /// This schema would have an object type at the root, as required by `OpenAI`.
@@ -677,10 +861,10 @@ fn resolve_and_clean_inner(
                                         if let Value::Object(val_obj) = val {
                                             if let Some(Value::Array(arr)) = val_obj.get("enum") {
                                                 Some(arr.clone())
-                                            } else if let Some(const_val) = val_obj.get("const") {
-                                                Some(alloc::vec![const_val.clone()])
                                             } else {
-                                                None
+                                                val_obj
+                                                    .get("const")
+                                                    .map(|const_val| alloc::vec![const_val.clone()])
                                             }
                                         } else {
                                             None
@@ -729,6 +913,11 @@ fn resolve_and_clean_inner(
                 }
             }
 
+            // Most providers reject "examples", so keep them in the description
+            if !inside_properties && let Some(Value::Array(examples)) = map.remove("examples") {
+                fold_examples(map, &examples);
+            }
+
             // Only filter schema keywords, not property names inside "properties"
             // OpenAPI schema subset supported by most LLM providers
             if !inside_properties {
@@ -772,6 +961,27 @@ fn resolve_and_clean_inner(
     }
 }
 
+/// Appends `examples` to the description of a schema object.
+fn fold_examples(map: &mut serde_json::Map<String, Value>, examples: &[Value]) {
+    if examples.is_empty() {
+        return;
+    }
+    let label = if examples.len() == 1 {
+        "Example"
+    } else {
+        "Examples"
+    };
+    let rendered: Vec<String> = examples.iter().map(Value::to_string).collect();
+    let note = format!("{label}: {}", rendered.join(", "));
+    let description = match map.get("description") {
+        Some(Value::String(description)) if !description.is_empty() => {
+            format!("{description}\n{note}")
+        }
+        _ => note,
+    };
+    map.insert("description".to_string(), Value::String(description));
+}
+
 /// Resolves a `$ref` path like `#/$defs/FsOperation` to its definition.
 fn resolve_ref(ref_path: &str, defs: &serde_json::Map<String, Value>) -> Option<Value> {
     // Handle common patterns: #/$defs/Name or #/definitions/Name
@@ -793,7 +1003,9 @@ impl Tools {
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            tools: BTreeMap::new(),
+            registry: BTreeMap::new(),
+            disabled: BTreeMap::new(),
+            descriptions: BTreeMap::new(),
         }
     }
 
@@ -805,7 +1017,7 @@ impl Tools {
     where
         T: Tool + 'static,
     {
-        self.tools
+        self.registry
             .values()
             .find_map(|tool| (tool as &dyn Any).downcast_ref::<T>())
     }
@@ -818,15 +1030,88 @@ impl Tools {
     where
         T: Tool + 'static,
     {
-        self.tools
+        self.registry
             .values_mut()
             .find_map(|tool| (tool as &mut dyn Any).downcast_mut::<T>())
     }
 
     /// Returns definitions of all registered tools.
+    ///
+    /// Descriptions set with [`Self::set_description`] replace the tools'
+    /// own. Disabled tools are still listed, with the reason appended to
+    /// their description, so the model knows why it can't use them yet.
     #[must_use]
     pub fn definitions(&self) -> Vec<ToolDefinition> {
-        self.tools.values().map(|tool| tool.definition()).collect()
+        self.registry
+            .iter()
+            .map(|(name, tool)| {
+                let mut definition = tool.definition();
+                if let Some(description) = self.descriptions.get(name.as_ref()) {
+                    definition.description = Cow::Owned(description.clone());
+                }
+                if let Some(reason) = self.disabled.get(name.as_ref()) {
+                    let note = format!("Currently disabled: {reason}");
+                    definition.description = if definition.description.is_empty() {
+                        Cow::Owned(note)
+                    } else {
+                        Cow::Owned(format!("{}\n\n{note}", definition.description))
+                    };
+                }
+                definition
+            })
+            .collect()
+    }
+
+    /// Disables a tool until [`Self::enable`] is called.
+    ///
+    /// The tool stays registered and listed in [`Self::definitions`], but
+    /// calls to it fail with `reason`. Disabling a disabled tool replaces
+    /// the reason.
+    ///
+    /// Returns `false` if no tool has this name.
+    pub fn disable(&mut self, name: &str, reason: impl Into<String>) -> bool {
+        if !self.registry.contains_key(name) {
+            return false;
+        }
+        self.disabled.insert(name.to_string(), reason.into());
+        true
+    }
+
+    /// Enables a tool disabled by [`Self::disable`].
+    ///
+    /// Returns `true` if the tool was disabled.
+    pub fn enable(&mut self, name: &str) -> bool {
+        self.disabled.remove(name).is_some()
+    }
+
+    /// Returns `true` if a tool with this name is registered and enabled.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.registry.contains_key(name) && !self.disabled.contains_key(name)
+    }
+
+    /// Returns why a tool is disabled, or `None` if it isn't.
+    #[must_use]
+    pub fn disabled_reason(&self, name: &str) -> Option<&str> {
+        self.disabled.get(name).map(String::as_str)
+    }
+
+    /// Replaces a tool's description until [`Self::reset_description`] is
+    /// called.
+    ///
+    /// Returns `false` if no tool has this name.
+    pub fn set_description(&mut self, name: &str, description: impl Into<String>) -> bool {
+        if !self.registry.contains_key(name) {
+            return false;
+        }
+        self.descriptions
+            .insert(name.to_string(), description.into());
+        true
+    }
+
+    /// Restores a tool's own description.
+    pub fn reset_description(&mut self, name: &str) {
+        self.descriptions.remove(name);
     }
 
     /// Registers a new tool. Replaces existing tool with same name.
@@ -840,11 +1125,12 @@ impl Tools {
         let name = tool.name();
         // Check if conflict exists
         assert!(
-            !self.tools.contains_key(&name),
+            !self.registry.contains_key(&name),
             "Tool with name '{name}' is already registered"
         );
 
-        self.tools.insert(name, Box::new(tool) as Box<dyn ToolImpl>);
+        self.registry
+            .insert(name, Box::new(tool) as Box<dyn ToolImpl>);
     }
 
     /// Registers a dynamic tool with a pre-made definition and handler.
@@ -864,11 +1150,11 @@ impl Tools {
     {
         let name = definition.name.clone();
         assert!(
-            !self.tools.contains_key(&name),
+            !self.registry.contains_key(&name),
             "Tool with name '{name}' is already registered"
         );
 
-        self.tools.insert(
+        self.registry.insert(
             name,
             Box::new(DynToolImpl {
                 definition,
@@ -879,17 +1165,24 @@ impl Tools {
 
     /// Removes a tool from the registry.
     pub fn unregister(&mut self, name: &str) {
-        self.tools.remove(name);
+        self.registry.remove(name);
+        self.disabled.remove(name);
+        self.descriptions.remove(name);
     }
 
     /// Calls a tool by name with JSON arguments.
     ///
     /// # Errors
     ///
-    /// Returns an error if the tool is not found, arguments cannot be parsed,
-    /// or tool execution fails.
+    /// Returns an error if the tool is not found or disabled, arguments
+    /// cannot be parsed, or tool execution fails.
     pub async fn call(&self, name: &str, args: &str) -> Result<ToolOutput> {
-        if let Some(tool) = self.tools.get(name) {
+        if let Some(reason) = self.disabled.get(name) {
+            return Err(anyhow::Error::msg(format!(
+                "Tool '{name}' is disabled: {reason}"
+            )));
+        }
+        if let Some(tool) = self.registry.get(name) {
             tool.call(args).await
         } else {
             Err(anyhow::Error::msg(format!("Tool '{name}' not found")))
@@ -1145,6 +1438,39 @@ mod tests {
         assert_eq!(tools.definitions().len(), 0);
     }
 
+    #[tokio::test]
+    async fn tools_disable_and_describe() {
+        let mut tools = Tools::new();
+        tools.register(Greeter);
+        assert!(!tools.disable("missing", "never registered"));
+
+        assert!(tools.disable("greeter", "nobody to greet yet"));
+        assert!(!tools.is_enabled("greeter"));
+        assert_eq!(
+            tools.definitions()[0].description(),
+            "Greets a person by name.\n\nCurrently disabled: nobody to greet yet"
+        );
+        let err = tools
+            .call("greeter", r#"{"name": "Alice"}"#)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Tool 'greeter' is disabled: nobody to greet yet"
+        );
+
+        assert!(tools.enable("greeter"));
+        assert!(tools.set_description("greeter", "Greets Alice."));
+        assert_eq!(tools.definitions()[0].description(), "Greets Alice.");
+        assert!(tools.call("greeter", r#"{"name": "Alice"}"#).await.is_ok());
+
+        tools.reset_description("greeter");
+        assert_eq!(
+            tools.definitions()[0].description(),
+            "Greets a person by name."
+        );
+    }
+
     #[test]
     fn tools_debug() {
         let mut tools = Tools::new();
@@ -1329,4 +1655,45 @@ mod tests {
             serde_json::to_string_pretty(&schema).unwrap()
         );
     }
+
+    #[test]
+    fn schema_folds_examples_into_descriptions() {
+        /// Looks up the weather.
+        #[derive(JsonSchema, Deserialize)]
+        #[schemars(example = serde_json::json!({ "city": "Paris" }))]
+        #[allow(dead_code)]
+        struct WeatherArgs {
+            /// City name
+            #[schemars(example = serde_json::json!("Paris"), example = serde_json::json!("Tokyo"))]
+            city: String,
+        }
+
+        struct Weather;
+
+        impl Tool for Weather {
+            fn name(&self) -> Cow<'static, str> {
+                "weather".into()
+            }
+            type Arguments = WeatherArgs;
+
+            async fn call(&self, _args: Self::Arguments) -> Result<ToolOutput> {
+                Ok(ToolOutput::Done)
+            }
+        }
+
+        let def = ToolDefinition::new(&Weather);
+        assert_eq!(
+            def.description(),
+            "Looks up the weather.\nExample: {\"city\":\"Paris\"}"
+        );
+
+        let schema = def.arguments_openai_schema();
+        assert_eq!(
+            schema["properties"]["city"],
+            serde_json::json!({
+                "type": "string",
+                "description": "City name\nExamples: \"Paris\", \"Tokyo\""
+            })
+        );
+    }
 }
@@ -0,0 +1,543 @@
+//! Verification phase for research findings.
+//!
+//! Research providers report what their sources say, and sources disagree.
+//! [`Verified`] wraps any [`Researcher`] and, once it has its findings,
+//! scores every citation with [`SourceReliability`], asks a language model
+//! which findings contradict each other, and re-emits the findings with
+//! adjusted confidence and their contradictions flagged.
+//!
+//! ```rust,ignore
+//! use aither::llm::{Researcher, ResearchRequest, SourceReliability, Verified};
+//!
+//! let researcher = Verified::new(researcher, model)
+//!     .with_reliability(SourceReliability::new().current_year(2025));
+//! let report = researcher.report(&ResearchRequest::new("Do heat pumps work below -20°C?")).await?;
+//! for finding in &report.findings {
+//!     for contradiction in &finding.contradictions {
+//!         println!("{} conflicts with {}", finding.title, contradiction.finding);
+//!     }
+//! }
+//! ```
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Write, pin::Pin};
+use futures_core::Stream;
+use futures_lite::StreamExt;
+use url::Url;
+
+use super::{
+    LLMRequest, LanguageModel, Message,
+    researcher::{
+        Contradiction, ResearchCitation, ResearchEvent, ResearchFinding, ResearchReport,
+        ResearchRequest, ResearchStage, Researcher, ResearcherProfile,
+    },
+};
+
+/// Score for sources nothing is known about.
+const UNKNOWN_SOURCE: f32 = 0.5;
+
+/// Reliability of well-known domains, matched on the host and its parents.
+const KNOWN_DOMAINS: &[(&str, f32)] = &[
+    ("doi.org", 0.9),
+    ("nature.com", 0.9),
+    ("science.org", 0.9),
+    ("thelancet.com", 0.9),
+    ("nejm.org", 0.9),
+    ("ieee.org", 0.85),
+    ("acm.org", 0.85),
+    ("springer.com", 0.85),
+    ("sciencedirect.com", 0.85),
+    ("reuters.com", 0.8),
+    ("apnews.com", 0.8),
+    ("arxiv.org", 0.75),
+    ("bbc.com", 0.75),
+    ("bbc.co.uk", 0.75),
+    ("wikipedia.org", 0.7),
+    ("github.com", 0.6),
+    ("stackoverflow.com", 0.5),
+    ("medium.com", 0.35),
+    ("substack.com", 0.35),
+    ("quora.com", 0.3),
+    ("reddit.com", 0.3),
+    ("youtube.com", 0.3),
+    ("x.com", 0.25),
+    ("twitter.com", 0.25),
+    ("facebook.com", 0.25),
+    ("tiktok.com", 0.2),
+];
+
+/// Heuristic trust score for a citation, from its domain and age.
+///
+/// Government, academic and intergovernmental domains, journals and wire
+/// services score high; social media and blogging platforms score low;
+/// anything else is neutral. When the current year is set, sources more
+/// than two years old lose up to 30% of their score.
+#[derive(Clone, Debug, Default)]
+pub struct SourceReliability {
+    overrides: BTreeMap<String, f32>,
+    current_year: Option<u16>,
+}
+
+impl SourceReliability {
+    /// Creates a scorer using the built-in domain heuristics.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the score (0-1) of `domain` and its subdomains.
+    #[must_use]
+    pub fn with_domain(mut self, domain: impl Into<String>, score: f32) -> Self {
+        self.overrides
+            .insert(domain.into().to_lowercase(), score.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Sets the year recency is measured against.
+    ///
+    /// Without it, publication dates are ignored.
+    #[must_use]
+    pub const fn current_year(mut self, year: u16) -> Self {
+        self.current_year = Some(year);
+        self
+    }
+
+    /// Scores `citation` between 0 and 1.
+    #[must_use]
+    pub fn score(&self, citation: &ResearchCitation) -> f32 {
+        let domain = host(&citation.url).map_or(UNKNOWN_SOURCE - 0.1, |host| self.domain(&host));
+        domain * self.recency(citation.published.as_deref())
+    }
+
+    fn domain(&self, host: &str) -> f32 {
+        let matches = |domain: &str| host == domain || host.ends_with(&format!(".{domain}"));
+        if let Some((_, score)) = self.overrides.iter().find(|(domain, _)| matches(domain)) {
+            return *score;
+        }
+        if let Some((_, score)) = KNOWN_DOMAINS.iter().find(|(domain, _)| matches(domain)) {
+            return *score;
+        }
+
+        let labels: Vec<&str> = host.split('.').collect();
+        let institutional = ["gov", "mil", "edu", "int"];
+        match labels.as_slice() {
+            [.., tld] if institutional.contains(tld) => 0.9,
+            // Country second-level domains such as `gov.uk` or `ac.jp`.
+            [.., second, _] if institutional.contains(second) || *second == "ac" => 0.9,
+            _ => UNKNOWN_SOURCE,
+        }
+    }
+
+    fn recency(&self, published: Option<&str>) -> f32 {
+        let (Some(current), Some(year)) = (
+            self.current_year,
+            published.and_then(|date| date.get(..4)?.parse::<u16>().ok()),
+        ) else {
+            return 1.0;
+        };
+        let age = current.saturating_sub(year).saturating_sub(2);
+        (1.0 - 0.05 * f32::from(age)).max(0.7)
+    }
+}
+
+/// Lowercased host of `url`, without a leading `www.`.
+fn host(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?.to_lowercase();
+    Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+}
+
+const CROSS_CHECK_PROMPT: &str = "You fact-check the findings of a research report. \
+Find every pair of findings that make claims about the same thing which cannot both be true: \
+different figures, dates or outcomes, or opposite conclusions. \
+Differences in scope, emphasis or level of detail are not contradictions. \
+Return an empty list when the findings agree.";
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct CrossCheck {
+    /// Pairs of findings that contradict each other.
+    contradictions: Vec<Conflict>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct Conflict {
+    /// Number of the first finding.
+    first: usize,
+    /// Number of the second finding.
+    second: usize,
+    /// One sentence on what the two findings disagree about.
+    explanation: String,
+}
+
+/// Scores findings and detects contradictions between them.
+#[derive(Clone, Debug)]
+pub struct FindingVerifier<M> {
+    model: M,
+    reliability: SourceReliability,
+}
+
+impl<M: LanguageModel> FindingVerifier<M> {
+    /// Creates a verifier that cross-checks findings with `model`.
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            reliability: SourceReliability::new(),
+        }
+    }
+
+    /// Replaces the citation scorer.
+    #[must_use]
+    pub fn with_reliability(mut self, reliability: SourceReliability) -> Self {
+        self.reliability = reliability;
+        self
+    }
+
+    /// Flags contradictions between `findings`, then [scores](Self::score)
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cross-check request fails.
+    pub async fn verify(
+        &self,
+        query: &str,
+        mut findings: Vec<ResearchFinding>,
+    ) -> crate::Result<Vec<ResearchFinding>> {
+        if findings.len() > 1 {
+            let request = LLMRequest::new([
+                Message::system(CROSS_CHECK_PROMPT),
+                Message::user(cross_check_prompt(query, &findings)),
+            ]);
+            let check: CrossCheck = self.model.generate(request).await?;
+            for conflict in check.contradictions {
+                // Findings are numbered from 1 in the prompt.
+                let (Some(first), Some(second)) = (
+                    conflict.first.checked_sub(1),
+                    conflict.second.checked_sub(1),
+                ) else {
+                    continue;
+                };
+                if first == second || first >= findings.len() || second >= findings.len() {
+                    continue;
+                }
+                let first_title = findings[first].title.clone();
+                let second_title = findings[second].title.clone();
+                findings[first].contradictions.push(Contradiction::new(
+                    second_title,
+                    conflict.explanation.clone(),
+                ));
+                findings[second]
+                    .contradictions
+                    .push(Contradiction::new(first_title, conflict.explanation));
+            }
+        }
+        Ok(self.score(findings))
+    }
+
+    /// Scores every citation and sets each finding's confidence, without
+    /// calling the model.
+    ///
+    /// Confidence is the mean reliability of the finding's citations, reduced
+    /// when fewer than three independent domains back it and when it is
+    /// contradicted, then averaged with any confidence the provider gave.
+    #[must_use]
+    pub fn score(&self, mut findings: Vec<ResearchFinding>) -> Vec<ResearchFinding> {
+        for finding in &mut findings {
+            for citation in &mut finding.citations {
+                citation.reliability = Some(self.reliability.score(citation));
+            }
+
+            let scores: Vec<f32> = finding
+                .citations
+                .iter()
+                .filter_map(|citation| citation.reliability)
+                .collect();
+            #[allow(clippy::cast_precision_loss)]
+            let support = if scores.is_empty() {
+                0.3
+            } else {
+                scores.iter().sum::<f32>() / scores.len() as f32
+            };
+            let domains: BTreeSet<String> = finding
+                .citations
+                .iter()
+                .filter_map(|citation| host(&citation.url))
+                .collect();
+            let corroboration = match domains.len() {
+                0 | 1 => 0.8,
+                2 => 0.9,
+                _ => 1.0,
+            };
+            let mut confidence = support * corroboration;
+            if !finding.contradictions.is_empty() {
+                confidence *= 0.7;
+            }
+            if let Some(reported) = finding.confidence {
+                confidence = f32::midpoint(confidence, reported);
+            }
+            finding.confidence = Some(confidence.clamp(0.0, 1.0));
+        }
+        findings
+    }
+}
+
+fn cross_check_prompt(query: &str, findings: &[ResearchFinding]) -> String {
+    let mut prompt = format!("Research question: {query}\n\nFindings:\n");
+    for (index, finding) in findings.iter().enumerate() {
+        let _ = write!(
+            prompt,
+            "\n{}. {}\n{}\n",
+            index + 1,
+            finding.title,
+            finding.summary
+        );
+        for citation in &finding.citations {
+            let _ = write!(prompt, "- Source: {}", citation.url);
+            if let Some(snippet) = &citation.snippet {
+                let _ = write!(prompt, " \"{snippet}\"");
+            }
+            prompt.push('\n');
+        }
+    }
+    prompt
+}
+
+/// A [`Researcher`] whose findings go through a [`FindingVerifier`] before
+/// the report is finalized.
+///
+/// Stage updates and citations stream through unchanged. Findings are held
+/// back until the wrapped researcher finishes, then emitted verified,
+/// followed by the report containing them. If the cross-check fails, a
+/// [`ResearchStage::Verifying`] update says so and the findings are only
+/// scored.
+#[derive(Clone, Debug)]
+pub struct Verified<R, M> {
+    researcher: R,
+    verifier: FindingVerifier<M>,
+}
+
+impl<R: Researcher, M: LanguageModel> Verified<R, M> {
+    /// Wraps `researcher`, cross-checking its findings with `model`.
+    pub fn new(researcher: R, model: M) -> Self {
+        Self {
+            researcher,
+            verifier: FindingVerifier::new(model),
+        }
+    }
+
+    /// Replaces the citation scorer.
+    #[must_use]
+    pub fn with_reliability(mut self, reliability: SourceReliability) -> Self {
+        self.verifier = self.verifier.with_reliability(reliability);
+        self
+    }
+}
+
+struct VerifyState<S> {
+    inner: Pin<Box<S>>,
+    findings: Vec<ResearchFinding>,
+    queue: VecDeque<ResearchEvent>,
+    /// Findings waiting for verification, and the report they belong to.
+    pending: Option<(Vec<ResearchFinding>, Option<ResearchReport>)>,
+    finished: bool,
+}
+
+impl<S> VerifyState<S> {
+    /// Queues the verification of everything collected so far.
+    fn finish(&mut self, report: Option<ResearchReport>) {
+        let findings = match &report {
+            Some(report) if !report.findings.is_empty() => report.findings.clone(),
+            _ => core::mem::take(&mut self.findings),
+        };
+        self.queue.push_back(ResearchEvent::Stage {
+            stage: ResearchStage::Verifying,
+            message: format!("Cross-checking {} findings", findings.len()),
+        });
+        self.pending = Some((findings, report));
+    }
+}
+
+impl<R: Researcher, M: LanguageModel> Researcher for Verified<R, M> {
+    type Error = R::Error;
+
+    fn research(
+        &self,
+        request: &ResearchRequest,
+    ) -> impl Stream<Item = Result<ResearchEvent, Self::Error>> + Send {
+        let state = VerifyState {
+            inner: Box::pin(self.researcher.research(request)),
+            findings: Vec::new(),
+            queue: VecDeque::new(),
+            pending: None,
+            finished: false,
+        };
+        let query = request.query.clone();
+
+        futures_lite::stream::unfold(state, move |mut state| {
+            let query = query.clone();
+            async move {
+                loop {
+                    if let Some(event) = state.queue.pop_front() {
+                        return Some((Ok(event), state));
+                    }
+                    if let Some((findings, report)) = state.pending.take() {
+                        let findings = match self.verifier.verify(&query, findings.clone()).await {
+                            Ok(findings) => findings,
+                            Err(err) => {
+                                state.queue.push_back(ResearchEvent::Stage {
+                                    stage: ResearchStage::Verifying,
+                                    message: format!(
+                                        "Cross-check failed, confidence reflects sources only: {err}"
+                                    ),
+                                });
+                                self.verifier.score(findings)
+                            }
+                        };
+                        state
+                            .queue
+                            .extend(findings.iter().cloned().map(ResearchEvent::Finding));
+                        if let Some(mut report) = report {
+                            report.findings = findings;
+                            state.queue.push_back(ResearchEvent::Finalized(report));
+                        }
+                        state.finished = true;
+                        continue;
+                    }
+                    if state.finished {
+                        return None;
+                    }
+                    match state.inner.next().await {
+                        Some(Ok(ResearchEvent::Finding(finding))) => state.findings.push(finding),
+                        Some(Ok(ResearchEvent::Finalized(report))) => state.finish(Some(report)),
+                        Some(event) => return Some((event, state)),
+                        None => state.finish(None),
+                    }
+                }
+            }
+        })
+    }
+
+    async fn profile(&self) -> ResearcherProfile {
+        let mut profile = self.researcher.profile().await;
+        profile.name = format!("{} (verified)", profile.name);
+        profile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::llm::{Event, model::Profile};
+    use alloc::vec;
+
+    #[test]
+    fn scores_domains_and_recency() {
+        let reliability = SourceReliability::new().current_year(2025);
+        let score = |url: &str| reliability.score(&ResearchCitation::new(url));
+
+        assert!(score("https://www.nih.gov/news") > score("https://example.com/post"));
+        assert!(score("https://example.com/post") > score("https://www.reddit.com/r/x"));
+        assert!((score("https://www.ox.ac.uk/") - 0.9).abs() < 1e-6);
+
+        let fresh =
+            reliability.score(&ResearchCitation::new("https://example.com").published("2024"));
+        let stale = reliability
+            .score(&ResearchCitation::new("https://example.com").published("2010-03-01"));
+        assert!((fresh - UNKNOWN_SOURCE).abs() < 1e-6);
+        assert!((stale - UNKNOWN_SOURCE * 0.7).abs() < 1e-6);
+
+        let custom = SourceReliability::new().with_domain("example.com", 0.95);
+        assert!(
+            (custom.score(&ResearchCitation::new("https://docs.example.com")) - 0.95).abs() < 1e-6
+        );
+    }
+
+    struct Canned(&'static str);
+
+    impl LanguageModel for Canned {
+        type Error = core::convert::Infallible;
+
+        fn respond(
+            &self,
+            _request: LLMRequest,
+        ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+            futures_lite::stream::once(Ok(Event::Text(self.0.into())))
+        }
+
+        async fn profile(&self) -> Profile {
+            Profile::new("canned", "test", "canned", "Canned replies", 1024)
+        }
+    }
+
+    struct Fixed(Vec<ResearchFinding>);
+
+    impl Researcher for Fixed {
+        type Error = core::convert::Infallible;
+
+        fn research(
+            &self,
+            _request: &ResearchRequest,
+        ) -> impl Stream<Item = Result<ResearchEvent, Self::Error>> + Send {
+            let mut events: Vec<_> = self
+                .0
+                .iter()
+                .cloned()
+                .map(|finding| Ok(ResearchEvent::Finding(finding)))
+                .collect();
+            events.push(Ok(ResearchEvent::Finalized(ResearchReport::default())));
+            futures_lite::stream::iter(events)
+        }
+
+        async fn profile(&self) -> ResearcherProfile {
+            ResearcherProfile {
+                name: "fixed".into(),
+                supports_streaming: true,
+                supports_web_browsing: false,
+                supports_code_execution: false,
+            }
+        }
+    }
+
+    #[test]
+    fn flags_contradictions_in_final_findings() {
+        let researcher = Verified::new(
+            Fixed(vec![
+                ResearchFinding::new("Launch in 2019", "The probe launched in 2019.")
+                    .citation(ResearchCitation::new("https://www.nasa.gov/probe")),
+                ResearchFinding::new("Launch in 2021", "The probe launched in 2021.")
+                    .citation(ResearchCitation::new("https://www.reddit.com/r/space")),
+            ]),
+            Canned(
+                r#"{"contradictions":[{"first":1,"second":2,"explanation":"Different launch years."}]}"#,
+            ),
+        );
+
+        let report = futures_lite::future::block_on(
+            researcher.report(&ResearchRequest::new("When did the probe launch?")),
+        )
+        .unwrap();
+
+        assert_eq!(report.findings.len(), 2);
+        let [official, forum] = report.findings.as_slice() else {
+            unreachable!()
+        };
+        assert_eq!(
+            official.contradictions,
+            vec![Contradiction::new(
+                "Launch in 2021",
+                "Different launch years."
+            )]
+        );
+        assert_eq!(forum.contradictions[0].finding, "Launch in 2019");
+        assert!(official.confidence.unwrap() > forum.confidence.unwrap());
+        assert!(official.citations[0].reliability.is_some());
+    }
+}
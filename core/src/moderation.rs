@@ -1,5 +1,17 @@
-use alloc::vec::Vec;
-use core::future::Future;
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::VecDeque,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, future::Future, mem, pin::Pin};
+
+use futures_core::Stream;
+use futures_lite::StreamExt;
+
+use crate::llm::{Event, LLMRequest, LanguageModel, Message, model::Profile};
 
 /// Trait for content moderation services.
 pub trait Moderation {
@@ -139,6 +151,487 @@ pub enum ModerationCategory {
     },
 }
 
+impl ModerationCategory {
+    /// The category name as used by the `OpenAI` moderation API, e.g.
+    /// `"hate/threatening"`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Hate { .. } => "hate",
+            Self::HateThreatening { .. } => "hate/threatening",
+            Self::Harassment { .. } => "harassment",
+            Self::HarassmentThreatening { .. } => "harassment/threatening",
+            Self::Sexual { .. } => "sexual",
+            Self::SexualMinors { .. } => "sexual/minors",
+            Self::Violence { .. } => "violence",
+            Self::ViolenceGraphic { .. } => "violence/graphic",
+            Self::Illicit { .. } => "illicit",
+            Self::IllicitViolent { .. } => "illicit/violent",
+            Self::SelfHarm { .. } => "self-harm",
+            Self::SelfHarmIntent { .. } => "self-harm/intent",
+            Self::SelfHarmInstructions { .. } => "self-harm/instructions",
+        }
+    }
+
+    /// The confidence score of this category (0.0-1.0).
+    #[must_use]
+    pub const fn score(&self) -> f32 {
+        match self {
+            Self::Hate { score }
+            | Self::HateThreatening { score }
+            | Self::Harassment { score }
+            | Self::HarassmentThreatening { score }
+            | Self::Sexual { score }
+            | Self::SexualMinors { score }
+            | Self::Violence { score }
+            | Self::ViolenceGraphic { score }
+            | Self::Illicit { score }
+            | Self::IllicitViolent { score }
+            | Self::SelfHarm { score }
+            | Self::SelfHarmIntent { score }
+            | Self::SelfHarmInstructions { score } => *score,
+        }
+    }
+}
+
+/// What [`ModeratedModel`] does with content that violates its policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationAction {
+    /// Fail the request with [`ModeratedError::Blocked`].
+    Block,
+    /// Replace the content with the policy's redaction text.
+    Redact,
+    /// Let the content through, followed by a note naming the flagged
+    /// categories.
+    Annotate,
+}
+
+/// Whether moderated content was sent to or received from the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationStage {
+    /// The user's message, before it reaches the model.
+    Input,
+    /// The model's streamed text.
+    Output,
+}
+
+/// When and how [`ModeratedModel`] intervenes.
+///
+/// Content violates the policy when any reported category scores at or above
+/// its threshold. Providers that flag content without reporting categories
+/// are trusted as is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModerationPolicy {
+    input: Option<ModerationAction>,
+    output: Option<ModerationAction>,
+    threshold: f32,
+    category_thresholds: Vec<(&'static str, f32)>,
+    segment_chars: usize,
+    redaction: String,
+}
+
+impl Default for ModerationPolicy {
+    fn default() -> Self {
+        Self {
+            input: Some(ModerationAction::Block),
+            output: Some(ModerationAction::Block),
+            threshold: 0.5,
+            category_thresholds: Vec::new(),
+            segment_chars: 500,
+            redaction: "[removed by moderation]".to_string(),
+        }
+    }
+}
+
+impl ModerationPolicy {
+    /// Set the action for the user's message.
+    #[must_use]
+    pub const fn input(mut self, action: ModerationAction) -> Self {
+        self.input = Some(action);
+        self
+    }
+
+    /// Set the action for the model's output.
+    #[must_use]
+    pub const fn output(mut self, action: ModerationAction) -> Self {
+        self.output = Some(action);
+        self
+    }
+
+    /// Do not moderate the user's message.
+    #[must_use]
+    pub const fn skip_input(mut self) -> Self {
+        self.input = None;
+        self
+    }
+
+    /// Do not moderate the model's output.
+    #[must_use]
+    pub const fn skip_output(mut self) -> Self {
+        self.output = None;
+        self
+    }
+
+    /// Set the score at which a category counts as a violation.
+    #[must_use]
+    pub const fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Override the threshold for one category, named as in
+    /// [`ModerationCategory::name`].
+    #[must_use]
+    pub fn category_threshold(mut self, category: &'static str, threshold: f32) -> Self {
+        self.category_thresholds
+            .retain(|(name, _)| *name != category);
+        self.category_thresholds.push((category, threshold));
+        self
+    }
+
+    /// Set how much output text is held back and moderated at once.
+    ///
+    /// Larger segments mean fewer moderation calls but choppier streaming.
+    #[must_use]
+    pub const fn segment_chars(mut self, chars: usize) -> Self {
+        self.segment_chars = chars;
+        self
+    }
+
+    /// Set the text that replaces redacted content.
+    #[must_use]
+    pub fn redaction(mut self, text: impl Into<String>) -> Self {
+        self.redaction = text.into();
+        self
+    }
+
+    /// Returns `true` if `result` violates this policy.
+    #[must_use]
+    pub fn violates(&self, result: &ModerationResult) -> bool {
+        if result.categories().is_empty() {
+            return result.is_flagged();
+        }
+        result
+            .categories()
+            .iter()
+            .any(|category| category.score() >= self.threshold_for(category.name()))
+    }
+
+    fn threshold_for(&self, category: &str) -> f32 {
+        self.category_thresholds
+            .iter()
+            .find(|(name, _)| *name == category)
+            .map_or(self.threshold, |(_, threshold)| *threshold)
+    }
+
+    fn annotation(result: &ModerationResult) -> String {
+        if result.categories().is_empty() {
+            return "[flagged by moderation]".to_string();
+        }
+        let names: Vec<_> = result
+            .categories()
+            .iter()
+            .map(ModerationCategory::name)
+            .collect();
+        format!("[flagged by moderation: {}]", names.join(", "))
+    }
+}
+
+/// Errors from a [`ModeratedModel`].
+#[derive(Debug)]
+pub enum ModeratedError<E, M> {
+    /// The wrapped model failed.
+    Model(E),
+    /// The moderation service failed.
+    Moderation(M),
+    /// Content violated the policy and the action was
+    /// [`ModerationAction::Block`].
+    Blocked {
+        /// Where the content was found.
+        stage: ModerationStage,
+        /// The moderation verdict.
+        result: ModerationResult,
+    },
+}
+
+impl<E: fmt::Display, M: fmt::Display> fmt::Display for ModeratedError<E, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Model(err) => write!(f, "{err}"),
+            Self::Moderation(err) => write!(f, "moderation failed: {err}"),
+            Self::Blocked { stage, result } => {
+                let stage = match stage {
+                    ModerationStage::Input => "input",
+                    ModerationStage::Output => "output",
+                };
+                write!(
+                    f,
+                    "{stage} blocked by moderation {}",
+                    ModerationPolicy::annotation(result)
+                )
+            }
+        }
+    }
+}
+
+impl<E, M> core::error::Error for ModeratedError<E, M>
+where
+    E: core::error::Error + 'static,
+    M: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Model(err) => Some(err),
+            Self::Moderation(err) => Some(err),
+            Self::Blocked { .. } => None,
+        }
+    }
+}
+
+/// A language model whose input and output pass through a [`Moderation`]
+/// service.
+///
+/// The last user message is moderated before the request is sent. Streamed
+/// text is held back in segments of
+/// [`segment_chars`](ModerationPolicy::segment_chars) and released once
+/// moderated. Reasoning and tool calls are passed through unmoderated.
+///
+/// ```rust,ignore
+/// use aither_core::moderation::{ModeratedModel, ModerationAction, ModerationPolicy};
+///
+/// let model = ModeratedModel::new(model, moderation).with_policy(
+///     ModerationPolicy::default()
+///         .output(ModerationAction::Redact)
+///         .category_threshold("violence", 0.8),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModeratedModel<M, Mod> {
+    model: M,
+    moderation: Mod,
+    policy: ModerationPolicy,
+}
+
+impl<M, Mod> ModeratedModel<M, Mod> {
+    /// Wrap `model` with the default policy, which blocks violations in both
+    /// directions.
+    #[must_use]
+    pub fn new(model: M, moderation: Mod) -> Self {
+        Self {
+            model,
+            moderation,
+            policy: ModerationPolicy::default(),
+        }
+    }
+
+    /// Replace the moderation policy.
+    #[must_use]
+    pub fn with_policy(mut self, policy: ModerationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The wrapped model.
+    #[must_use]
+    pub const fn inner(&self) -> &M {
+        &self.model
+    }
+}
+
+impl<M, Mod> LanguageModel for ModeratedModel<M, Mod>
+where
+    M: LanguageModel,
+    Mod: Moderation + Send + Sync,
+{
+    type Error = ModeratedError<M::Error, Mod::Error>;
+
+    fn respond(
+        &self,
+        request: LLMRequest,
+    ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+        let state = ModerationState {
+            model: &self.model,
+            moderation: &self.moderation,
+            policy: &self.policy,
+            request: Some(request),
+            inner: None,
+            buffer: String::new(),
+            queue: VecDeque::new(),
+            finished: false,
+        };
+        futures_lite::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.queue.pop_front() {
+                    return Some((item, state));
+                }
+                if state.finished {
+                    return None;
+                }
+                state.advance().await;
+            }
+        })
+    }
+
+    fn profile(&self) -> impl Future<Output = Profile> + Send {
+        self.model.profile()
+    }
+}
+
+type EventStream<'a, E> = Pin<Box<dyn Stream<Item = Result<Event, E>> + Send + 'a>>;
+
+type ModeratedItem<M, Mod> =
+    Result<Event, ModeratedError<<M as LanguageModel>::Error, <Mod as Moderation>::Error>>;
+
+/// Progress of one moderated response.
+struct ModerationState<'a, M: LanguageModel, Mod: Moderation> {
+    model: &'a M,
+    moderation: &'a Mod,
+    policy: &'a ModerationPolicy,
+    /// The request, until input moderation has run.
+    request: Option<LLMRequest>,
+    inner: Option<EventStream<'a, M::Error>>,
+    /// Output text not yet moderated.
+    buffer: String,
+    /// Items ready to be yielded.
+    queue: VecDeque<ModeratedItem<M, Mod>>,
+    finished: bool,
+}
+
+impl<M: LanguageModel, Mod: Moderation + Sync> ModerationState<'_, M, Mod> {
+    /// Run the next step, queueing any items it produces.
+    async fn advance(&mut self) {
+        if let Some(mut request) = self.request.take() {
+            match Self::moderate_input(self.moderation, self.policy, &mut request).await {
+                Ok(()) => self.inner = Some(Box::pin(self.model.respond(request))),
+                Err(err) => self.fail(err),
+            }
+            return;
+        }
+        let Some(inner) = self.inner.as_mut() else {
+            self.finished = true;
+            return;
+        };
+        match inner.next().await {
+            Some(Ok(Event::Text(text))) => {
+                self.buffer.push_str(&text);
+                if self.policy.output.is_none()
+                    || self.buffer.chars().count() >= self.policy.segment_chars
+                {
+                    self.flush().await;
+                }
+            }
+            Some(Ok(event @ (Event::Reasoning(_) | Event::ToolCallDelta(_)))) => {
+                self.queue.push_back(Ok(event));
+            }
+            Some(Ok(event)) => {
+                self.flush().await;
+                if !self.finished {
+                    self.queue.push_back(Ok(event));
+                }
+            }
+            Some(Err(err)) => {
+                self.flush().await;
+                if !self.finished {
+                    self.fail(ModeratedError::Model(err));
+                }
+            }
+            None => {
+                self.flush().await;
+                self.finished = true;
+            }
+        }
+    }
+
+    /// Moderate the last user message of `request`.
+    ///
+    /// Takes the moderator and policy rather than `&self`: the state holds a
+    /// boxed stream that isn't `Sync`, so a shared borrow of it can't be held
+    /// across an await in a `Send` future.
+    async fn moderate_input(
+        moderation: &Mod,
+        policy: &ModerationPolicy,
+        request: &mut LLMRequest,
+    ) -> Result<(), ModeratedError<M::Error, Mod::Error>> {
+        let Some(action) = policy.input else {
+            return Ok(());
+        };
+        let last_user = request
+            .messages_mut()
+            .iter_mut()
+            .rev()
+            .find_map(|message| match message {
+                Message::User { content, .. } if !content.is_empty() => Some(content),
+                _ => None,
+            });
+        let Some(content) = last_user else {
+            return Ok(());
+        };
+        let result = moderation
+            .moderate(content)
+            .await
+            .map_err(ModeratedError::Moderation)?;
+        if !policy.violates(&result) {
+            return Ok(());
+        }
+        match action {
+            ModerationAction::Block => {
+                return Err(ModeratedError::Blocked {
+                    stage: ModerationStage::Input,
+                    result,
+                });
+            }
+            ModerationAction::Redact => policy.redaction.clone_into(content),
+            ModerationAction::Annotate => {
+                content.push_str("\n\n");
+                content.push_str(&ModerationPolicy::annotation(&result));
+            }
+        }
+        Ok(())
+    }
+
+    /// Moderate and release the held-back output text.
+    async fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let text = mem::take(&mut self.buffer);
+        let Some(action) = self.policy.output else {
+            self.queue.push_back(Ok(Event::Text(text)));
+            return;
+        };
+        let result = match self.moderation.moderate(&text).await {
+            Ok(result) => result,
+            Err(err) => return self.fail(ModeratedError::Moderation(err)),
+        };
+        if !self.policy.violates(&result) {
+            self.queue.push_back(Ok(Event::Text(text)));
+            return;
+        }
+        match action {
+            ModerationAction::Block => self.fail(ModeratedError::Blocked {
+                stage: ModerationStage::Output,
+                result,
+            }),
+            ModerationAction::Redact => {
+                self.queue
+                    .push_back(Ok(Event::Text(self.policy.redaction.clone())));
+            }
+            ModerationAction::Annotate => {
+                self.queue.push_back(Ok(Event::Text(text)));
+                let note = ModerationPolicy::annotation(&result);
+                self.queue.push_back(Ok(Event::Text(format!("\n\n{note}"))));
+            }
+        }
+    }
+
+    /// Queue `err` and stop reading from the model.
+    fn fail(&mut self, err: ModeratedError<M::Error, Mod::Error>) {
+        self.queue.push_back(Err(err));
+        self.inner = None;
+        self.finished = true;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,6 +855,79 @@ mod tests {
         assert!(!result.has_violations());
     }
 
+    /// Streams fixed text chunks, then usage.
+    struct Chunks(Vec<&'static str>);
+
+    impl LanguageModel for Chunks {
+        type Error = Infallible;
+
+        fn respond(
+            &self,
+            _request: LLMRequest,
+        ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+            let events: Vec<_> = self
+                .0
+                .iter()
+                .map(|chunk| Ok(Event::text(*chunk)))
+                .chain([Ok(Event::Usage(crate::llm::Usage::new(1, 1)))])
+                .collect();
+            futures_lite::stream::iter(events)
+        }
+
+        async fn profile(&self) -> Profile {
+            Profile::new("chunks", "test", "chunks", "Canned chunks", 1024)
+        }
+    }
+
+    #[tokio::test]
+    async fn moderated_model_blocks_flagged_input() {
+        let model = ModeratedModel::new(Chunks(vec!["hello"]), MockModeration);
+        let request = LLMRequest::new([Message::user("something bad")]);
+
+        let events: Vec<_> = model.respond(request).collect().await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            Err(ModeratedError::Blocked {
+                stage: ModerationStage::Input,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn moderated_model_redacts_flagged_output_segments() {
+        let model = ModeratedModel::new(
+            Chunks(vec!["fine text. ", "now something bad"]),
+            MockModeration,
+        )
+        .with_policy(
+            ModerationPolicy::default()
+                .output(ModerationAction::Redact)
+                .segment_chars(10),
+        );
+        let request = LLMRequest::new([Message::user("hi")]);
+
+        let events: Vec<_> = model.respond(request).collect().await;
+        let text: String = events
+            .iter()
+            .filter_map(|event| event.as_ref().ok().and_then(Event::as_text))
+            .collect();
+        assert_eq!(text, "fine text. [removed by moderation]");
+        assert!(events.last().unwrap().as_ref().unwrap().is_usage());
+    }
+
+    #[test]
+    fn policy_thresholds_override_per_category() {
+        let result = ModerationResult::new(true, vec![ModerationCategory::Violence { score: 0.6 }]);
+        assert!(ModerationPolicy::default().violates(&result));
+        assert!(
+            !ModerationPolicy::default()
+                .category_threshold("violence", 0.9)
+                .violates(&result)
+        );
+    }
+
     #[tokio::test]
     async fn moderation_whitespace_content() {
         let moderation = MockModeration;
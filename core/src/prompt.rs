@@ -0,0 +1,487 @@
+//! # Prompt Templates
+//!
+//! Prompts are plain text with `{name}` placeholders, filled from any
+//! [`Serialize`] value whose fields match the placeholders:
+//!
+//! ```rust,ignore
+//! use aither_core::prompt::PromptTemplate;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Greeting<'a> {
+//!     name: &'a str,
+//! }
+//!
+//! let template = PromptTemplate::new("Say hello to {name}. Reply as JSON: {\"greeting\": ...}");
+//! let prompt = template.render(&Greeting { name: "Ada" }).unwrap();
+//! assert_eq!(prompt, "Say hello to Ada. Reply as JSON: {\"greeting\": ...}");
+//! ```
+//!
+//! ## Syntax
+//!
+//! - `{name}` inserts the variable `name`. Strings are inserted as they are,
+//!   numbers and booleans as text, `null` as nothing and anything else as
+//!   JSON. Inserted values are never parsed as template syntax.
+//! - `{> name}` inserts the template called `name` from the same
+//!   [`PromptLibrary`], rendered with the same variables.
+//! - `{{` and `}}` are literal braces; [`escape`] produces them.
+//! - Any other brace, such as the ones in a JSON example, is kept as is.
+//!
+//! ## Overriding built-in prompts
+//!
+//! Crates that ship prompts register them in a [`PromptLibrary`] under
+//! stable names and render them from there. Replacing a template with
+//! [`PromptLibrary::insert`], for example with the contents of a file, changes
+//! the prompt without touching the crate's source.
+//!
+//! [`FewShot`] renders example input and output pairs for a placeholder.
+
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use anyhow::{anyhow, bail};
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// How deeply partials may include other partials.
+const MAX_PARTIAL_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Variable(String),
+    Partial(String),
+}
+
+/// A parsed prompt template.
+///
+/// See the [module documentation](crate::prompt) for the syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplate {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl PromptTemplate {
+    /// Parses `source`.
+    ///
+    /// Parsing never fails: text that is not a placeholder is kept literally.
+    #[must_use]
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        let segments = parse(&source);
+        Self { source, segments }
+    }
+
+    /// The template as written.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Names of the variables the template uses, in order of appearance.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Variable(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Names of the partials the template includes, in order of appearance.
+    pub fn partials(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Partial(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Fills in the template from `vars`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `vars` does not serialize to a map, a variable is
+    /// missing, or the template includes a partial. Render templates with
+    /// partials through [`PromptLibrary::render`].
+    pub fn render<T: Serialize + ?Sized>(&self, vars: &T) -> crate::Result<String> {
+        let vars = to_variables(vars)?;
+        let mut output = String::with_capacity(self.source.len());
+        self.render_into(&mut output, &vars, None, 0)?;
+        Ok(output)
+    }
+
+    fn render_into(
+        &self,
+        output: &mut String,
+        vars: &Map<String, Value>,
+        library: Option<&PromptLibrary>,
+        depth: usize,
+    ) -> crate::Result<()> {
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Variable(name) => {
+                    let value = vars
+                        .get(name)
+                        .ok_or_else(|| anyhow!("missing prompt variable `{name}`"))?;
+                    push_value(output, value);
+                }
+                Segment::Partial(name) => {
+                    let Some(library) = library else {
+                        bail!("partial `{name}` can only be rendered through a PromptLibrary");
+                    };
+                    if depth >= MAX_PARTIAL_DEPTH {
+                        bail!("prompt partials nested too deeply at `{name}`; is there a cycle?");
+                    }
+                    library
+                        .get(name)
+                        .ok_or_else(|| anyhow!("unknown prompt partial `{name}`"))?
+                        .render_into(output, vars, Some(library), depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<String> for PromptTemplate {
+    fn from(source: String) -> Self {
+        Self::new(source)
+    }
+}
+
+impl From<&str> for PromptTemplate {
+    fn from(source: &str) -> Self {
+        Self::new(source)
+    }
+}
+
+/// Named prompt templates that can include each other.
+///
+/// Inserting a template under an existing name replaces it, which is how
+/// built-in prompts are overridden.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptLibrary {
+    templates: BTreeMap<String, PromptTemplate>,
+}
+
+impl PromptLibrary {
+    /// Creates an empty library.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            templates: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a template, replacing any template with the same name.
+    #[must_use]
+    pub fn with(mut self, name: impl Into<String>, template: impl Into<PromptTemplate>) -> Self {
+        self.insert(name, template);
+        self
+    }
+
+    /// Adds a template and returns the one it replaced.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        template: impl Into<PromptTemplate>,
+    ) -> Option<PromptTemplate> {
+        self.templates.insert(name.into(), template.into())
+    }
+
+    /// Returns the template called `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Returns `true` if a template is called `name`.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    /// Names of all templates, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// Renders the template called `name`, resolving partials from this
+    /// library.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template or one of its partials does not
+    /// exist, partials include each other in a cycle, `vars` does not
+    /// serialize to a map, or a variable is missing.
+    pub fn render<T: Serialize + ?Sized>(&self, name: &str, vars: &T) -> crate::Result<String> {
+        let template = self
+            .get(name)
+            .ok_or_else(|| anyhow!("unknown prompt template `{name}`"))?;
+        let vars = to_variables(vars)?;
+        let mut output = String::with_capacity(template.source.len());
+        template.render_into(&mut output, &vars, Some(self), 0)?;
+        Ok(output)
+    }
+}
+
+impl<N: Into<String>, T: Into<PromptTemplate>> Extend<(N, T)> for PromptLibrary {
+    fn extend<I: IntoIterator<Item = (N, T)>>(&mut self, iter: I) {
+        for (name, template) in iter {
+            self.insert(name, template);
+        }
+    }
+}
+
+/// One input and the output the model should give for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// What the model is given.
+    pub input: String,
+    /// What the model should answer.
+    pub output: String,
+}
+
+impl Example {
+    /// Creates an example.
+    #[must_use]
+    pub fn new(input: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+        }
+    }
+}
+
+/// Few-shot examples, rendered as `<example>` blocks.
+///
+/// Serializes as the rendered text, so it can be a field of the variables
+/// passed to [`PromptTemplate::render`]:
+///
+/// ```text
+/// <example>
+/// <input>
+/// 2 + 2
+/// </input>
+/// <output>
+/// 4
+/// </output>
+/// </example>
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FewShot {
+    examples: Vec<Example>,
+}
+
+impl FewShot {
+    /// Creates an empty set of examples.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            examples: Vec::new(),
+        }
+    }
+
+    /// Adds an example.
+    #[must_use]
+    pub fn example(mut self, input: impl Into<String>, output: impl Into<String>) -> Self {
+        self.push(Example::new(input, output));
+        self
+    }
+
+    /// Adds an example.
+    pub fn push(&mut self, example: Example) {
+        self.examples.push(example);
+    }
+
+    /// The examples, in order.
+    #[must_use]
+    pub fn examples(&self) -> &[Example] {
+        &self.examples
+    }
+}
+
+impl FromIterator<Example> for FewShot {
+    fn from_iter<I: IntoIterator<Item = Example>>(iter: I) -> Self {
+        Self {
+            examples: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for FewShot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, example) in self.examples.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n\n")?;
+            }
+            write!(
+                f,
+                "<example>\n<input>\n{}\n</input>\n<output>\n{}\n</output>\n</example>",
+                example.input.trim(),
+                example.output.trim()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for FewShot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Escapes braces so `text` renders literally when used as template source.
+#[must_use]
+pub fn escape(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
+
+fn to_variables<T: Serialize + ?Sized>(vars: &T) -> crate::Result<Map<String, Value>> {
+    match serde_json::to_value(vars)? {
+        Value::Object(map) => Ok(map),
+        Value::Null => Ok(Map::new()),
+        other => bail!("prompt variables must serialize to a map, not `{other}`"),
+    }
+}
+
+fn push_value(output: &mut String, value: &Value) {
+    match value {
+        Value::Null => {}
+        Value::String(text) => output.push_str(text),
+        other => output.push_str(&other.to_string()),
+    }
+}
+
+fn parse(source: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = source;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        text.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if let Some(after) = rest.strip_prefix("{{") {
+            text.push('{');
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("}}") {
+            text.push('}');
+            rest = after;
+            continue;
+        }
+
+        let parsed = rest.strip_prefix('{').and_then(|inner| {
+            let end = inner.find(['{', '}'])?;
+            if !inner[end..].starts_with('}') {
+                return None;
+            }
+            let segment = placeholder(&inner[..end])?;
+            Some((segment, &inner[end + 1..]))
+        });
+        if let Some((segment, after)) = parsed {
+            if !text.is_empty() {
+                segments.push(Segment::Text(core::mem::take(&mut text)));
+            }
+            segments.push(segment);
+            rest = after;
+        } else {
+            // A lone brace, such as in a JSON example.
+            text.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    segments
+}
+
+fn placeholder(inner: &str) -> Option<Segment> {
+    if let Some(name) = inner.strip_prefix('>') {
+        let name = name.trim();
+        let valid = !name.is_empty() && !name.contains(char::is_whitespace);
+        return valid.then(|| Segment::Partial(name.to_owned()));
+    }
+    let mut chars = inner.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then(|| Segment::Variable(inner.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fills_placeholders_and_keeps_other_braces() {
+        let template = PromptTemplate::new(
+            "Facts about {topic} ({count}):\n{{not a var}}\nExample: {\"facts\": []}",
+        );
+        assert_eq!(template.variables().collect::<Vec<_>>(), ["topic", "count"]);
+
+        let prompt = template
+            .render(&json!({ "topic": "{Rust}", "count": 3 }))
+            .unwrap();
+        assert_eq!(
+            prompt,
+            "Facts about {Rust} (3):\n{not a var}\nExample: {\"facts\": []}"
+        );
+
+        let err = template.render(&json!({ "topic": "Rust" })).unwrap_err();
+        assert!(err.to_string().contains("`count`"));
+        assert_eq!(
+            PromptTemplate::new(escape("{x}")).render(&()).unwrap(),
+            "{x}"
+        );
+    }
+
+    #[test]
+    fn library_resolves_partials_and_overrides() {
+        let mut library = PromptLibrary::new()
+            .with("rules", "Answer in {language}.")
+            .with("system", "You are helpful. {> rules}");
+        let vars = json!({ "language": "French" });
+        assert_eq!(
+            library.render("system", &vars).unwrap(),
+            "You are helpful. Answer in French."
+        );
+        assert!(library.get("system").unwrap().render(&vars).is_err());
+
+        let replaced = library.insert("rules", "Be terse.");
+        assert!(replaced.is_some());
+        assert_eq!(
+            library.render("system", &vars).unwrap(),
+            "You are helpful. Be terse."
+        );
+
+        library.insert("rules", "{> system}");
+        assert!(library.render("system", &vars).is_err());
+    }
+
+    #[test]
+    fn few_shot_renders_example_blocks() {
+        let shots = FewShot::new().example("2 + 2", "4").example("3 * 3", "9");
+        let prompt = PromptTemplate::new("Solve.\n\n{examples}")
+            .render(&json!({ "examples": shots }))
+            .unwrap();
+        assert_eq!(
+            prompt,
+            "Solve.\n\n<example>\n<input>\n2 + 2\n</input>\n<output>\n4\n</output>\n</example>\n\n\
+             <example>\n<input>\n3 * 3\n</input>\n<output>\n9\n</output>\n</example>"
+        );
+    }
+}
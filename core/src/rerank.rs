@@ -0,0 +1,129 @@
+//! # Rerank Module
+//!
+//! Rerankers score how relevant each document is to a query. Unlike embeddings,
+//! which compare independently computed vectors, a reranker (usually a
+//! cross-encoder) reads the query and document together, which makes it more
+//! accurate but too slow to run over a whole corpus.
+//!
+//! The usual pattern is two-stage retrieval: fetch a few dozen candidates with
+//! an [`EmbeddingModel`](crate::EmbeddingModel), then let a [`Reranker`] pick
+//! the best few.
+//!
+//! ```rust,ignore
+//! use aither_core::Reranker;
+//!
+//! async fn best<R: Reranker>(reranker: &R, query: &str, candidates: &[&str]) -> aither_core::Result<()> {
+//!     for ranked in reranker.rerank(query, candidates).await?.iter().take(3) {
+//!         println!("{:.3}: {}", ranked.score, candidates[ranked.index]);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use alloc::vec::Vec;
+use core::future::Future;
+
+/// A document's position in the input and its relevance to the query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankedDocument {
+    /// Index of the document in the slice passed to [`Reranker::rerank`].
+    pub index: usize,
+    /// Relevance score; higher is more relevant.
+    ///
+    /// Scales differ between models, so only compare scores from the same
+    /// reranker.
+    pub score: f32,
+}
+
+impl RankedDocument {
+    /// Creates a ranked document.
+    #[must_use]
+    pub const fn new(index: usize, score: f32) -> Self {
+        Self { index, score }
+    }
+}
+
+/// Orders documents by relevance to a query.
+///
+/// See the [module documentation](crate::rerank) for how rerankers fit into
+/// retrieval.
+pub trait Reranker: Send + Sync {
+    /// Scores `documents` against `query`.
+    ///
+    /// Returns one [`RankedDocument`] per input document, most relevant
+    /// first.
+    fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+    ) -> impl Future<Output = crate::Result<Vec<RankedDocument>>> + Send;
+}
+
+/// Sorts ranked documents by descending score, keeping input order for ties.
+///
+/// Helper for [`Reranker`] implementations whose backend does not return
+/// results in order.
+pub fn sort_by_score(ranked: &mut [RankedDocument]) {
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Scores documents by how many query words they contain.
+    struct WordOverlap;
+
+    impl Reranker for WordOverlap {
+        #[allow(clippy::cast_precision_loss)]
+        async fn rerank(
+            &self,
+            query: &str,
+            documents: &[&str],
+        ) -> crate::Result<Vec<RankedDocument>> {
+            let mut ranked: Vec<_> = documents
+                .iter()
+                .enumerate()
+                .map(|(index, document)| {
+                    let overlap = query
+                        .split_whitespace()
+                        .filter(|word| document.contains(word))
+                        .count();
+                    RankedDocument::new(index, overlap as f32)
+                })
+                .collect();
+            sort_by_score(&mut ranked);
+            Ok(ranked)
+        }
+    }
+
+    #[tokio::test]
+    async fn reranks_most_relevant_first() {
+        let documents = [
+            "bananas are yellow",
+            "rust borrow checker",
+            "rust ownership and borrow rules",
+        ];
+        let ranked = WordOverlap
+            .rerank("rust borrow rules", &documents)
+            .await
+            .unwrap();
+
+        let order: Vec<_> = ranked.iter().map(|ranked| ranked.index).collect();
+        assert_eq!(order, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn sort_keeps_ties_in_input_order() {
+        let mut ranked = vec![
+            RankedDocument::new(0, 0.5),
+            RankedDocument::new(1, 0.9),
+            RankedDocument::new(2, 0.5),
+        ];
+        sort_by_score(&mut ranked);
+
+        let order: Vec<_> = ranked.iter().map(|ranked| ranked.index).collect();
+        assert_eq!(order, vec![1, 0, 2]);
+    }
+}
@@ -0,0 +1,144 @@
+use core::time::Duration;
+
+/// Retry hints attached to a transient provider error.
+///
+/// Every provider reports rate limits differently: `OpenAI` and Anthropic use
+/// headers, Gemini puts a `RetryInfo` detail in the error body, and some only
+/// say "try again in 20s" in the message. Provider errors translate all of
+/// these into this one type through [`RetryInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct ProviderRetryInfo {
+    /// How long the provider asked callers to wait before retrying.
+    pub retry_after: Option<Duration>,
+    /// Requests left in the current rate-limit window.
+    pub remaining_requests: Option<u64>,
+    /// Tokens left in the current rate-limit window.
+    pub remaining_tokens: Option<u64>,
+}
+
+impl ProviderRetryInfo {
+    /// Retry hints without any provider guidance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            retry_after: None,
+            remaining_requests: None,
+            remaining_tokens: None,
+        }
+    }
+
+    /// Set the delay the provider asked for.
+    #[must_use]
+    pub const fn retry_after(mut self, delay: Option<Duration>) -> Self {
+        self.retry_after = delay;
+        self
+    }
+
+    /// Read retry hints from response headers.
+    ///
+    /// Understands `retry-after` (in seconds), `retry-after-ms`, and the
+    /// remaining-quota headers of `OpenAI` (`x-ratelimit-remaining-*`) and
+    /// Anthropic (`anthropic-ratelimit-*-remaining`). Header names are
+    /// matched case-insensitively; unknown headers are ignored.
+    #[must_use]
+    pub fn from_headers<'a>(headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut info = Self::new();
+        let mut retry_after_ms = None;
+        for (name, value) in headers {
+            let value = value.trim();
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("retry-after") {
+                info.retry_after = value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+            } else if name.eq_ignore_ascii_case("retry-after-ms") {
+                retry_after_ms = value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|ms| Duration::try_from_secs_f64(ms / 1000.0).ok());
+            } else if name.eq_ignore_ascii_case("x-ratelimit-remaining-requests")
+                || name.eq_ignore_ascii_case("anthropic-ratelimit-requests-remaining")
+            {
+                info.remaining_requests = value.parse().ok();
+            } else if name.eq_ignore_ascii_case("x-ratelimit-remaining-tokens")
+                || name.eq_ignore_ascii_case("anthropic-ratelimit-tokens-remaining")
+            {
+                info.remaining_tokens = value.parse().ok();
+            }
+        }
+        // The millisecond header is the more precise of the two.
+        if retry_after_ms.is_some() {
+            info.retry_after = retry_after_ms;
+        }
+        info
+    }
+}
+
+/// Errors that know whether, and when, the failed call can be retried.
+pub trait RetryInfo {
+    /// Retry hints if the error is transient, such as a rate limit, an
+    /// overloaded server or a network failure.
+    ///
+    /// Returns `None` when retrying the same request cannot succeed.
+    fn retry_info(&self) -> Option<ProviderRetryInfo>;
+}
+
+/// Returns `true` for HTTP statuses worth retrying: request timeouts, rate
+/// limits and server errors.
+#[must_use]
+pub const fn is_transient_status(status: u16) -> bool {
+    status == 408 || status == 429 || status >= 500
+}
+
+/// Parse a delay such as `"30s"`, `"1.5s"` or `"850ms"`.
+///
+/// Providers use this shape in error bodies and messages.
+#[must_use]
+pub fn parse_delay(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let (number, scale) = if let Some(ms) = text.strip_suffix("ms") {
+        (ms, 0.001)
+    } else {
+        (text.strip_suffix('s')?, 1.0)
+    };
+    let value = number.trim().parse::<f64>().ok()?;
+    Duration::try_from_secs_f64(value * scale).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_openai_and_anthropic_headers() {
+        let openai = ProviderRetryInfo::from_headers([
+            ("Retry-After", "2"),
+            ("retry-after-ms", "1500"),
+            ("x-ratelimit-remaining-requests", "0"),
+            ("x-ratelimit-remaining-tokens", "1200"),
+        ]);
+        assert_eq!(openai.retry_after, Some(Duration::from_millis(1500)));
+        assert_eq!(openai.remaining_requests, Some(0));
+        assert_eq!(openai.remaining_tokens, Some(1200));
+
+        let anthropic = ProviderRetryInfo::from_headers([
+            ("retry-after", "7"),
+            ("anthropic-ratelimit-requests-remaining", "3"),
+            ("content-type", "application/json"),
+        ]);
+        assert_eq!(anthropic.retry_after, Some(Duration::from_secs(7)));
+        assert_eq!(anthropic.remaining_requests, Some(3));
+        assert_eq!(anthropic.remaining_tokens, None);
+    }
+
+    #[test]
+    fn parses_delays() {
+        assert_eq!(parse_delay("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_delay("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_delay("850ms"), Some(Duration::from_millis(850)));
+        assert_eq!(parse_delay("soon"), None);
+        assert_eq!(parse_delay("-1s"), None);
+    }
+}
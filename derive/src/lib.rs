@@ -82,6 +82,27 @@
 //! }
 //! ```
 //!
+//! ### Documented Parameters
+//!
+//! With several parameters, the macro generates the arguments struct itself.
+//! The function's doc comment becomes the tool description, parameter doc
+//! comments become argument descriptions, and `#[tool(example = ...)]` adds
+//! examples to the schema:
+//!
+//! ```rust
+//! /// Get the weather forecast for a city.
+//! #[tool(example = r#"{"city": "Paris", "days": 3}"#)]
+//! pub async fn forecast(
+//!     /// City name, in English
+//!     #[tool(example = "Tokyo")]
+//!     city: String,
+//!     /// Number of days to forecast, 1 to 7
+//!     days: u8,
+//! ) -> Result<String> {
+//!     Ok(format!("{days} sunny days in {city}"))
+//! }
+//! ```
+//!
 //! ## Requirements
 //!
 //! - Functions must be `async`
@@ -94,7 +115,7 @@ use convert_case::{Case, Casing};
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
-    FnArg, Ident, ItemFn, LitStr, Token, Type, Visibility,
+    Attribute, FnArg, Ident, ItemFn, Lit, LitStr, Token, Type, Visibility,
     parse::{Parse, ParseStream},
     parse_macro_input, parse_quote,
 };
@@ -102,6 +123,7 @@ use syn::{
 /// Arguments for the `#[tool]` attribute macro
 struct ToolArgs {
     rename: Option<String>,
+    examples: Vec<LitStr>,
 }
 
 impl Parse for ToolArgs {
@@ -109,8 +131,10 @@ impl Parse for ToolArgs {
     ///
     /// Supports:
     /// - `rename = "..."` (optional): Custom name for the tool (defaults to function name)
+    /// - `example = "..."` (repeatable): Example arguments as a JSON object
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut rename = None;
+        let mut examples = Vec::new();
 
         while !input.is_empty() {
             let ident: Ident = input.parse()?;
@@ -119,10 +143,11 @@ impl Parse for ToolArgs {
 
             match ident.to_string().as_str() {
                 "rename" => rename = Some(value.value()),
+                "example" => examples.push(value),
                 _ => {
                     return Err(syn::Error::new_spanned(
                         ident,
-                        "unknown attribute. Supported: rename",
+                        "unknown attribute. Supported: rename, example",
                     ));
                 }
             }
@@ -132,10 +157,64 @@ impl Parse for ToolArgs {
             }
         }
 
-        Ok(Self { rename })
+        Ok(Self { rename, examples })
+    }
+}
+
+/// Attributes lifted from a function parameter.
+#[derive(Default)]
+struct ParamAttrs {
+    /// Doc comments, kept as `#[doc]` attributes
+    docs: Vec<Attribute>,
+    /// Values from `#[tool(example = ...)]`
+    examples: Vec<Lit>,
+}
+
+impl ParamAttrs {
+    const fn is_empty(&self) -> bool {
+        self.docs.is_empty() && self.examples.is_empty()
     }
 }
 
+/// Removes doc comments and `#[tool(...)]` attributes from a parameter.
+///
+/// Rust rejects both on function parameters, so they must not survive in
+/// the emitted function.
+fn take_param_attrs(attrs: &mut Vec<Attribute>) -> syn::Result<ParamAttrs> {
+    let mut lifted = ParamAttrs::default();
+    let mut kept = Vec::with_capacity(attrs.len());
+
+    for attr in attrs.drain(..) {
+        if attr.path().is_ident("doc") {
+            lifted.docs.push(attr);
+        } else if attr.path().is_ident("tool") {
+            attr.parse_args_with(|input: ParseStream| {
+                while !input.is_empty() {
+                    let ident: Ident = input.parse()?;
+                    if ident != "example" {
+                        return Err(syn::Error::new_spanned(
+                            ident,
+                            "unknown parameter attribute. Supported: example",
+                        ));
+                    }
+                    let _: Token![=] = input.parse()?;
+                    lifted.examples.push(input.parse()?);
+
+                    if input.peek(Token![,]) {
+                        let _: Token![,] = input.parse()?;
+                    }
+                }
+                Ok(())
+            })?;
+        } else {
+            kept.push(attr);
+        }
+    }
+
+    *attrs = kept;
+    Ok(lifted)
+}
+
 /// Converts an async function into an AI tool that can be called by language models.
 ///
 /// This procedural macro generates the necessary boilerplate code to make your function
@@ -146,6 +225,11 @@ impl Parse for ToolArgs {
 /// # Arguments
 ///
 /// - `rename` (optional): A custom name for the tool. If not provided, uses the function name.
+/// - `example` (optional, repeatable): Example arguments as a JSON object, added to the
+///   schema. Requires the arguments to be declared as function parameters.
+///
+/// Parameters accept doc comments, which become argument descriptions, and
+/// `#[tool(example = ...)]` with a literal example value.
 ///
 /// # Examples
 ///
@@ -209,7 +293,8 @@ impl Parse for ToolArgs {
 ///
 /// For a function named `search`, the macro generates:
 ///
-/// 1. A `SearchArgs` struct (if the function has multiple parameters)
+/// 1. A `SearchArgs` struct (if the function has multiple parameters), documented with the
+///    function's and the parameters' doc comments
 /// 2. A `Search` struct that implements `aither::llm::Tool`
 /// 3. All necessary trait implementations for JSON schema generation and deserialization
 ///
@@ -228,6 +313,8 @@ impl Parse for ToolArgs {
 /// - The function has `self` parameters
 /// - The function has more than the supported number of parameters
 /// - Required attributes are missing
+/// - Examples or parameter doc comments are used with fewer than two parameters, where the
+///   arguments type is not generated by the macro
 #[proc_macro_attribute]
 pub fn tool(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(args as ToolArgs);
@@ -243,19 +330,41 @@ pub fn tool(args: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// This function performs the actual code generation, transforming the annotated async function
 /// into a struct that implements the `Tool` trait.
-fn tool_impl(args: ToolArgs, input_fn: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
-    let fn_name = &input_fn.sig.ident;
+fn tool_impl(args: ToolArgs, mut input_fn: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let fn_name = input_fn.sig.ident.clone();
     let tool_name = args.rename.unwrap_or_else(|| fn_name.to_string());
-    let fn_vis = &input_fn.vis;
+    let fn_vis = input_fn.vis.clone();
 
     let tool_struct_name = format_ident!("{}", fn_name.to_string().to_case(Case::Pascal));
 
+    let mut param_attrs = Vec::with_capacity(input_fn.sig.inputs.len());
+    for arg in &mut input_fn.sig.inputs {
+        if let FnArg::Typed(pat_type) = arg {
+            param_attrs.push(take_param_attrs(&mut pat_type.attrs)?);
+        }
+    }
+
+    let fn_docs: Vec<&Attribute> = input_fn
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .collect();
+
     // Analyze function signature
     let AnalyzedArgs {
         args_type,
         params,
         stream,
-    } = analyze_function_args(fn_vis, &tool_struct_name, &input_fn.sig.inputs)?;
+    } = analyze_function_args(
+        &fn_vis,
+        &tool_struct_name,
+        &input_fn.sig.inputs,
+        &GeneratedDocs {
+            fn_docs: &fn_docs,
+            examples: &args.examples,
+            params: &param_attrs,
+        },
+    )?;
 
     if input_fn.sig.asyncness.is_none() {
         return Err(syn::Error::new_spanned(
@@ -318,6 +427,16 @@ struct AnalyzedArgs {
     stream: proc_macro2::TokenStream,
 }
 
+/// Documentation for a generated arguments struct.
+struct GeneratedDocs<'a> {
+    /// Doc comments of the function, used as the tool description
+    fn_docs: &'a [&'a Attribute],
+    /// Examples of the whole arguments object, as JSON
+    examples: &'a [LitStr],
+    /// Attributes lifted from each typed parameter, in order
+    params: &'a [ParamAttrs],
+}
+
 /// Analyzes function parameters and generates appropriate argument types.
 ///
 /// This function handles three cases:
@@ -328,7 +447,29 @@ fn analyze_function_args(
     fn_vis: &Visibility,
     struct_name: &Ident,
     inputs: &syn::punctuated::Punctuated<FnArg, syn::Token![,]>,
+    docs: &GeneratedDocs<'_>,
 ) -> syn::Result<AnalyzedArgs> {
+    if inputs.len() < 2 {
+        if let Some(example) = docs.examples.first() {
+            return Err(syn::Error::new_spanned(
+                example,
+                "`example` needs the arguments declared as function parameters; \
+                 use #[schemars(example = ...)] on the arguments type instead",
+            ));
+        }
+        if let Some((arg, _)) = inputs
+            .iter()
+            .zip(docs.params)
+            .find(|(_, attrs)| !attrs.is_empty())
+        {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "parameter docs and examples need at least two parameters; \
+                 document the fields of the arguments type instead",
+            ));
+        }
+    }
+
     match inputs.len() {
         0 => {
             // No arguments - use unit type
@@ -357,11 +498,15 @@ fn analyze_function_args(
         _ => {
             let mut attributes = Vec::new();
 
-            for arg in inputs {
+            for (arg, lifted) in inputs.iter().zip(docs.params) {
                 if let FnArg::Typed(pat_type) = arg {
                     let pat = &pat_type.pat;
                     let ty = &pat_type.ty;
+                    let field_docs = &lifted.docs;
+                    let field_examples = &lifted.examples;
                     attributes.push(quote! {
+                        #(#field_docs)*
+                        #(#[schemars(example = #field_examples)])*
                         #pat: #ty,
                     });
                 } else {
@@ -373,9 +518,13 @@ fn analyze_function_args(
             }
 
             let arg_struct_name = format_ident!("{}Args", struct_name);
+            let fn_docs = docs.fn_docs;
+            let examples = docs.examples;
 
             let new_type_gen = quote! {
+                #(#fn_docs)*
                 #[derive(::schemars::JsonSchema, ::serde::Deserialize,::core::fmt::Debug)]
+                #(#[schemars(example = ::aither::__hidden::example(#examples))])*
                 #fn_vis struct #arg_struct_name {
                     #(
                         #attributes
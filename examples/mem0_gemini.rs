@@ -27,6 +27,7 @@ async fn main() -> Result<()> {
         retrieve_count: 3,
         user_id: Some("priya_123".to_string()),
         agent_id: None,
+        ..Config::default()
     };
 
     let store = InMemoryStore::new();
@@ -26,9 +26,16 @@ pub struct SearchResult {
     url: String,
 }
 
-// Tool with multiple simple parameters
-#[tool]
-pub async fn search(keywords: Vec<String>, max_results: u32) -> Result<Vec<SearchResult>> {
+// Tool with multiple simple parameters, documented in place
+/// Search the web for pages matching all keywords.
+#[tool(example = r#"{"keywords": ["rust", "async"], "max_results": 5}"#)]
+pub async fn search(
+    /// Keywords that must all appear on the page
+    keywords: Vec<String>,
+    /// Maximum number of results to return
+    #[tool(example = 10)]
+    max_results: u32,
+) -> Result<Vec<SearchResult>> {
     // Simulate a search result
     let results = keywords
         .into_iter()
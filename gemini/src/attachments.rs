@@ -3,9 +3,14 @@ use crate::config::GeminiConfig;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::error::GeminiError;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::files::upload_file;
+use crate::files::{delete_file, upload_file};
 #[cfg(not(target_arch = "wasm32"))]
-use aither_attachments::{FileCache, default_cache_dir};
+use crate::llm::mime_from_path;
+#[cfg(not(target_arch = "wasm32"))]
+use aither_attachments::{
+    AttachmentDelivery, AttachmentManager, AttachmentPolicy, AttachmentUploader, CachePolicy,
+    UploadedFile,
+};
 #[cfg(not(target_arch = "wasm32"))]
 use aither_core::llm::Message;
 #[cfg(not(target_arch = "wasm32"))]
@@ -22,10 +27,11 @@ pub async fn resolve_messages(
         return Ok(messages);
     }
 
-    let mut cache = FileCache::open(default_cache_dir())
-        .await
-        .map_err(GeminiError::from)?;
-    let mut cache_dirty = cache.prune_expired();
+    let model = cfg.text_model.trim_start_matches("models/");
+    let policy = AttachmentPolicy::for_model(model)
+        .unwrap_or_else(|| AttachmentPolicy::for_provider("google"));
+    let mut manager = AttachmentManager::open(GeminiUploader { cfg }).await?;
+    manager.gc(&CachePolicy::default()).await;
 
     let mut resolved = Vec::with_capacity(messages.len());
     for message in messages {
@@ -40,9 +46,7 @@ pub async fn resolve_messages(
 
         let mut next_attachments = Vec::with_capacity(attachments.len());
         for attachment in attachments {
-            let resolved_url = resolve_attachment(cfg, &mut cache, &attachment).await?;
-            cache_dirty |= resolved_url.cache_updated;
-            next_attachments.push(resolved_url.url);
+            next_attachments.push(resolve_attachment(&policy, &mut manager, &attachment).await?);
         }
 
         resolved.push(Message::User {
@@ -51,38 +55,26 @@ pub async fn resolve_messages(
         });
     }
 
-    if cache_dirty {
-        cache.save().await.map_err(GeminiError::from)?;
-    }
-
+    manager.save().await?;
     Ok(resolved)
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-struct ResolvedUrl {
-    url: Url,
-    cache_updated: bool,
-}
-
 #[cfg(not(target_arch = "wasm32"))]
 async fn resolve_attachment(
-    cfg: &GeminiConfig,
-    cache: &mut FileCache,
+    policy: &AttachmentPolicy,
+    manager: &mut AttachmentManager<GeminiUploader<'_>>,
     attachment: &Url,
-) -> Result<ResolvedUrl, GeminiError> {
+) -> Result<Url, GeminiError> {
     match attachment.scheme() {
         "file" => {
             let path = attachment.to_file_path().map_err(|()| {
                 GeminiError::Api("Attachment file URL could not be converted to path".to_string())
             })?;
-            resolve_file_attachment(cfg, cache, &path).await
+            resolve_file_attachment(policy, manager, attachment, &path).await
         }
         "http" | "https" => {
             if is_gemini_file_uri(attachment) {
-                Ok(ResolvedUrl {
-                    url: attachment.clone(),
-                    cache_updated: false,
-                })
+                Ok(attachment.clone())
             } else {
                 Err(GeminiError::Api(
                     "HTTP attachments must be uploaded via Gemini Files API".to_string(),
@@ -98,48 +90,74 @@ async fn resolve_attachment(
     }
 }
 
+/// Keep small files as `file://` URLs, which the request builder inlines,
+/// and upload the rest.
 #[cfg(not(target_arch = "wasm32"))]
 async fn resolve_file_attachment(
-    cfg: &GeminiConfig,
-    cache: &mut FileCache,
+    policy: &AttachmentPolicy,
+    manager: &mut AttachmentManager<GeminiUploader<'_>>,
+    attachment: &Url,
     path: &Path,
-) -> Result<ResolvedUrl, GeminiError> {
-    let provider = "gemini";
-
-    if let Some(entry) = cache.get(path, provider).await.map_err(GeminiError::from)? {
-        let url = Url::parse(&entry.reference)
-            .map_err(|e| GeminiError::Api(format!("Invalid cached Gemini file URL: {e}")))?;
-        return Ok(ResolvedUrl {
-            url,
-            cache_updated: false,
-        });
+) -> Result<Url, GeminiError> {
+    match policy.decide_for_path(path).await? {
+        // Only known MIME types can be inlined; the Files API accepts any.
+        AttachmentDelivery::Inline if mime_from_path(path).is_some() => Ok(attachment.clone()),
+        AttachmentDelivery::Inline | AttachmentDelivery::Upload => manager.resolve(path).await,
+        AttachmentDelivery::TooLarge => Err(GeminiError::Api(format!(
+            "Attachment exceeds Gemini size limits: {}",
+            path.display()
+        ))),
     }
+}
+
+/// Uploads attachments through the Gemini Files API.
+#[cfg(not(target_arch = "wasm32"))]
+struct GeminiUploader<'a> {
+    cfg: &'a GeminiConfig,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AttachmentUploader for GeminiUploader<'_> {
+    type Error = GeminiError;
 
-    let file = upload_file(cfg, path).await?;
-    if !file.is_ready() {
-        return Err(GeminiError::Api(format!(
-            "Gemini file upload not ready (state: {:?})",
-            file.state
-        )));
+    fn provider(&self) -> &str {
+        "gemini"
     }
-    if file.uri.is_empty() {
-        return Err(GeminiError::Api(
-            "Gemini file upload missing URI".to_string(),
-        ));
+
+    async fn upload<'a>(&'a self, path: &'a Path) -> Result<UploadedFile, GeminiError> {
+        let file = upload_file(self.cfg, path).await?;
+        if !file.is_ready() {
+            return Err(GeminiError::Api(format!(
+                "Gemini file upload not ready (state: {:?})",
+                file.state
+            )));
+        }
+        if file.uri.is_empty() {
+            return Err(GeminiError::Api(
+                "Gemini file upload missing URI".to_string(),
+            ));
+        }
+        let expires_at = file.expiration();
+        Ok(UploadedFile::new(file.uri, expires_at))
     }
 
-    let expires_at = file.expiration();
-    cache
-        .insert(path, provider, file.uri.clone(), expires_at)
-        .await
-        .map_err(GeminiError::from)?;
+    fn to_url(&self, path: &Path, reference: &str) -> Result<Url, GeminiError> {
+        let mut url = Url::parse(reference)
+            .map_err(|e| GeminiError::Api(format!("Invalid Gemini file URI: {e}")))?;
+        // File URIs carry no type information, but `fileData` parts need one;
+        // the request builder reads it back from the fragment.
+        url.set_fragment(mime_guess::from_path(path).first_raw());
+        Ok(url)
+    }
 
-    let url = Url::parse(&file.uri)
-        .map_err(|e| GeminiError::Api(format!("Invalid Gemini file URI: {e}")))?;
-    Ok(ResolvedUrl {
-        url,
-        cache_updated: true,
-    })
+    async fn delete<'a>(&'a self, reference: &'a str) -> Result<(), GeminiError> {
+        // The cached URI ends with the resource name, e.g. ".../v1beta/files/abc123".
+        let name = reference
+            .find("files/")
+            .map(|start| &reference[start..])
+            .ok_or_else(|| GeminiError::Api(format!("Invalid Gemini file URI: {reference}")))?;
+        delete_file(self.cfg, name).await
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -7,7 +7,8 @@ use crate::{
     config::{AuthMode, GeminiConfig, USER_AGENT},
     error::GeminiError,
     types::{
-        EmbedContentRequest, EmbedContentResponse, GenerateContentRequest, GenerateContentResponse,
+        BatchEmbedContentsRequest, BatchEmbedContentsResponse, EmbedContentRequest,
+        EmbedContentResponse, GenerateContentRequest, GenerateContentResponse,
     },
 };
 
@@ -150,6 +151,18 @@ pub async fn embed_content(
     .await
 }
 
+pub async fn batch_embed_contents(
+    cfg: &GeminiConfig,
+    request: BatchEmbedContentsRequest,
+) -> Result<BatchEmbedContentsResponse, GeminiError> {
+    post_json(
+        cfg,
+        cfg.model_endpoint(&cfg.embedding_model, "batchEmbedContents"),
+        &request,
+    )
+    .await
+}
+
 #[allow(clippy::future_not_send)]
 async fn get_json<T: for<'de> serde::Deserialize<'de>>(
     cfg: &GeminiConfig,
@@ -1,11 +1,15 @@
 use aither_core::{EmbeddingModel, Error as AitherError, Result as AitherResult};
 
 use crate::{
-    client::embed_content,
+    client::{batch_embed_contents, embed_content},
     config::Gemini,
-    types::{EmbedContentRequest, GeminiContent},
+    error::GeminiError,
+    types::{BatchEmbedContentsRequest, EmbedContentRequest, GeminiContent},
 };
 
+/// Most requests `batchEmbedContents` accepts in one call.
+const MAX_BATCH_SIZE: usize = 100;
+
 impl EmbeddingModel for Gemini {
     fn dim(&self) -> usize {
         self.config().embedding_dimensions
@@ -26,4 +30,38 @@ impl EmbeddingModel for Gemini {
             Ok(response.embedding.values)
         }
     }
+
+    fn embed_batch(
+        &self,
+        texts: &[&str],
+    ) -> impl core::future::Future<Output = AitherResult<Vec<Vec<f32>>>> + Send {
+        let cfg = self.config();
+        async move {
+            let mut embeddings = Vec::with_capacity(texts.len());
+            for chunk in texts.chunks(MAX_BATCH_SIZE) {
+                let requests = chunk
+                    .iter()
+                    .map(|text| {
+                        EmbedContentRequest::new(
+                            &cfg.embedding_model,
+                            GeminiContent::text("user", *text),
+                        )
+                    })
+                    .collect();
+                let response = batch_embed_contents(cfg, BatchEmbedContentsRequest { requests })
+                    .await
+                    .map_err(AitherError::from)?;
+                if response.embeddings.len() != chunk.len() {
+                    return Err(GeminiError::Api(format!(
+                        "expected {} embeddings, got {}",
+                        chunk.len(),
+                        response.embeddings.len()
+                    ))
+                    .into());
+                }
+                embeddings.extend(response.embeddings.into_iter().map(|value| value.values));
+            }
+            Ok(embeddings)
+        }
+    }
 }
@@ -1,4 +1,9 @@
-use std::fmt;
+use std::{fmt, time::Duration};
+
+use aither_core::{
+    llm::{LlmError, LlmErrorKind},
+    retry::{ProviderRetryInfo, RetryInfo, is_transient_status},
+};
 
 use base64::DecodeError;
 use serde::Deserialize;
@@ -198,20 +203,8 @@ impl std::error::Error for GeminiError {}
 
 impl GeminiError {
     /// Check if this error is retryable.
-    pub const fn is_retryable(&self) -> bool {
-        match self {
-            Self::Http(err) => {
-                // Retry on rate limit, server errors, network errors, and timeouts
-                if let zenwave::Error::Http { status, .. } = err {
-                    return status.as_u16() == 429
-                        || status.as_u16() >= 500
-                        || status.as_u16() == 408;
-                }
-                err.is_network_error() || err.is_timeout()
-            }
-            Self::RateLimit { .. } => true,
-            _ => false,
-        }
+    pub fn is_retryable(&self) -> bool {
+        self.retry_info().is_some()
     }
 
     /// Get suggested retry delay in seconds.
@@ -256,6 +249,49 @@ impl GeminiError {
     }
 }
 
+impl RetryInfo for GeminiError {
+    /// Rate limits, server errors, network errors and timeouts are retryable.
+    fn retry_info(&self) -> Option<ProviderRetryInfo> {
+        let transient = match self {
+            Self::Http(zenwave::Error::Http { status, .. }) => is_transient_status(status.as_u16()),
+            Self::Http(err) => err.is_network_error() || err.is_timeout(),
+            Self::RateLimit { .. } => true,
+            _ => false,
+        };
+        transient.then(|| {
+            ProviderRetryInfo::new().retry_after(self.retry_delay_secs().map(Duration::from_secs))
+        })
+    }
+}
+
+impl From<GeminiError> for LlmError {
+    /// Blocked prompts surface as [`LlmErrorKind::ContentFilter`].
+    fn from(err: GeminiError) -> Self {
+        let kind = match &err {
+            GeminiError::Http(http @ zenwave::Error::Http { status, .. }) => {
+                LlmErrorKind::classify(status.as_u16(), http.response_body().unwrap_or_default())
+            }
+            GeminiError::Http(http) if http.is_network_error() || http.is_timeout() => {
+                LlmErrorKind::Network
+            }
+            GeminiError::RateLimit { .. } => LlmErrorKind::RateLimit,
+            GeminiError::Api(message) => {
+                LlmErrorKind::from_message(message).unwrap_or(LlmErrorKind::Other)
+            }
+            GeminiError::Http(_)
+            | GeminiError::Body(_)
+            | GeminiError::Json(_)
+            | GeminiError::Decode(_)
+            | GeminiError::Parse(_) => LlmErrorKind::Other,
+        };
+        let error = Self::new(kind, err.to_string());
+        match err.retry_info() {
+            Some(retry) => error.with_retry(retry),
+            None => error,
+        }
+    }
+}
+
 impl From<BodyError> for GeminiError {
     fn from(value: BodyError) -> Self {
         Self::Body(value)
@@ -1,7 +1,7 @@
 use aither_core::{
-    Error, LanguageModel,
+    LanguageModel,
     llm::{
-        Event, LLMRequest, Message, Role, Usage,
+        Citation, ContentPart as MessagePart, Event, LLMRequest, Message, Role, Usage,
         model::{Ability, Parameters, Profile, ReasoningEffort, ToolChoice},
         tool::ToolDefinition,
     },
@@ -9,8 +9,6 @@ use aither_core::{
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use futures_core::Stream;
 use futures_lite::StreamExt;
-use schemars::JsonSchema;
-use serde::de::DeserializeOwned;
 use tracing::debug;
 
 use crate::{
@@ -23,7 +21,6 @@ use crate::{
         ThinkingConfig, ToolConfig, UsageMetadata,
     },
 };
-use schemars::schema_for;
 
 impl LanguageModel for Gemini {
     type Error = GeminiError;
@@ -36,28 +33,15 @@ impl LanguageModel for Gemini {
         respond_stream(cfg.clone(), request)
     }
 
-    fn generate<T: JsonSchema + DeserializeOwned + 'static>(
-        &self,
-        mut request: LLMRequest,
-    ) -> impl core::future::Future<Output = aither_core::Result<T>> + Send {
-        let schema = schema_for!(T);
-        let mut params = request.parameters().clone();
-        params.structured_outputs = true;
-        params.response_format = Some(schema);
-        request = request.with_parameters(params);
-
-        let stream = self.respond(request);
-        async move {
-            let text = aither_core::llm::collect_text(stream).await?;
-            serde_json::from_str::<T>(&text)
-                .map_err(|err| Error::new(err).context("failed to parse structured output"))
-        }
-    }
-
     fn profile(&self) -> impl core::future::Future<Output = Profile> + Send {
         let cfg = self.config().clone();
         async move {
             let model_name = cfg.text_model.trim_start_matches("models/").to_string();
+            if let Some(warning) =
+                aither_models::lookup_status(&model_name).and_then(|status| status.warning())
+            {
+                tracing::warn!("{warning}");
+            }
 
             // Fetch actual context window from API, fallback to models database
             let context_length = match crate::client::get_model_info(&cfg, &cfg.text_model).await {
@@ -195,6 +179,7 @@ fn respond_stream_inner(
         futures_lite::pin!(stream);
         let mut usage: Option<Usage> = None;
         let mut finish_reason: Option<String> = None;
+        let mut cited_urls: Vec<String> = Vec::new();
 
         while let Some(result) = stream.next().await {
             let response = match result {
@@ -208,7 +193,7 @@ fn respond_stream_inner(
             debug!("Gemini stream chunk: {:?}", response);
 
             if let Some(meta) = &response.usage_metadata {
-                usage = Some(usage_from_metadata(meta));
+                usage = Some(usage_from_metadata(meta, &cfg.text_model));
             }
 
             let Some(candidate) = response.primary_candidate() else {
@@ -220,6 +205,23 @@ fn respond_stream_inner(
                 continue;
             };
 
+            // Grounding sources may repeat across chunks
+            let sources = candidate
+                .grounding_metadata
+                .iter()
+                .flat_map(|meta| &meta.grounding_chunks)
+                .filter_map(|chunk| chunk.web.as_ref());
+            for source in sources {
+                if !cited_urls.contains(&source.uri) {
+                    cited_urls.push(source.uri.clone());
+                    let citation = Citation::new(&source.uri);
+                    yield Ok(Event::Citation(match &source.title {
+                        Some(title) => citation.title(title),
+                        None => citation,
+                    }));
+                }
+            }
+
             let Some(content) = &candidate.content else {
                 if let Some(reason) = candidate.finish_reason.clone() {
                     finish_reason = Some(reason);
@@ -357,8 +359,8 @@ fn parse_tool_signature(id: &str) -> (String, Option<String>) {
     (id.to_string(), None)
 }
 
-fn usage_from_metadata(meta: &UsageMetadata) -> Usage {
-    Usage {
+fn usage_from_metadata(meta: &UsageMetadata, model: &str) -> Usage {
+    let mut usage = Usage {
         prompt_tokens: meta.prompt_token_count,
         completion_tokens: meta.candidates_token_count,
         total_tokens: meta.total_token_count,
@@ -367,7 +369,9 @@ fn usage_from_metadata(meta: &UsageMetadata) -> Usage {
         cache_write_tokens: None,
         cost_usd: None,
         stop_reason: None,
-    }
+    };
+    usage.cost_usd = aither_models::estimate_cost(model.trim_start_matches("models/"), &usage);
+    usage
 }
 
 fn messages_to_gemini(messages: &[Message]) -> (Option<GeminiContent>, Vec<GeminiContent>) {
@@ -392,20 +396,20 @@ fn messages_to_gemini(messages: &[Message]) -> (Option<GeminiContent>, Vec<Gemin
                 if attachments.is_empty() {
                     contents.push(GeminiContent::text("user", message.content()));
                 } else {
-                    // Build parts with attachments
-                    let mut parts = Vec::new();
-
-                    // Add attachment parts first
-                    for attachment in attachments {
-                        if let Some(part) = url_to_part(attachment) {
-                            parts.push(part);
-                        }
-                    }
-
-                    // Add text content
-                    if !message.content().is_empty() {
-                        parts.push(Part::text(message.content()));
-                    }
+                    let parts = message
+                        .parts()
+                        .into_iter()
+                        .filter_map(|part| match &part {
+                            MessagePart::Text(text) => {
+                                (!text.is_empty()).then(|| Part::text(text.as_str()))
+                            }
+                            _ => Some(
+                                part.url()
+                                    .and_then(url_to_part)
+                                    .unwrap_or_else(|| Part::text(part.placeholder())),
+                            ),
+                        })
+                        .collect();
 
                     contents.push(GeminiContent::with_parts("user", parts));
                 }
@@ -573,11 +577,13 @@ fn build_thinking_config(parameters: &Parameters) -> Option<ThinkingConfig> {
     }
     Some(ThinkingConfig {
         include_thoughts: Some(parameters.include_reasoning),
-        token_budget: parameters.reasoning_effort.map(|effort| match effort {
-            ReasoningEffort::Minimum => 0,
-            ReasoningEffort::Low => 1024,
-            ReasoningEffort::Medium => 4096,
-            ReasoningEffort::High => 10240,
+        token_budget: parameters.thinking_budget.or_else(|| {
+            parameters.reasoning_effort.map(|effort| match effort {
+                ReasoningEffort::Minimum => 0,
+                ReasoningEffort::Low => 1024,
+                ReasoningEffort::Medium => 4096,
+                ReasoningEffort::High => 10240,
+            })
         }),
         thinking_level: parameters.reasoning_effort.map(|effort| {
             // Gemini does not have a direct mapping for Minimum, so we map it to Low.
@@ -637,20 +643,14 @@ fn is_gemini_file_uri(url: &url::Url) -> bool {
 }
 
 /// Convert a Gemini Files API URI to a Part using file reference.
+///
+/// Uploaded attachments carry their MIME type in the URL fragment, e.g.
+/// `.../v1beta/files/abc123#video/mp4`; the fragment is not part of the URI.
 fn gemini_file_uri_to_part(url: &url::Url) -> Option<Part> {
-    // The file URI contains the path which includes the file type info
-    // We need to infer the MIME type from the original file or use a generic one
-    // For now, use application/octet-stream as the API will handle it
-    let mime_type = infer_mime_from_gemini_uri(url).unwrap_or("application/octet-stream");
-    Some(Part::from_file(mime_type, url.as_str()))
-}
-
-/// Try to infer MIME type from a Gemini file URI.
-/// The URI doesn't typically contain MIME info, so we return None.
-const fn infer_mime_from_gemini_uri(_url: &url::Url) -> Option<&'static str> {
-    // Gemini file URIs don't contain MIME type info in the URL
-    // The server knows the type from when it was uploaded
-    None
+    let mime_type = url.fragment().unwrap_or("application/octet-stream");
+    let mut file_uri = url.clone();
+    file_uri.set_fragment(None);
+    Some(Part::from_file(mime_type, file_uri.as_str()))
 }
 
 /// Parse a data URL into a Part with inline data.
@@ -684,7 +684,7 @@ fn read_file_to_part(_url: &url::Url) -> Option<Part> {
 }
 
 /// Get MIME type from file path extension.
-fn mime_from_path(path: &std::path::Path) -> Option<&'static str> {
+pub(crate) fn mime_from_path(path: &std::path::Path) -> Option<&'static str> {
     match path
         .extension()
         .and_then(|e| e.to_str())?
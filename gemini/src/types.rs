@@ -578,6 +578,28 @@ pub struct Candidate {
     pub(crate) finish_reason: Option<String>,
     #[serde(rename = "safetyRatings", default)]
     pub(crate) safety_ratings: Vec<SafetyRating>,
+    #[serde(rename = "groundingMetadata", default)]
+    pub(crate) grounding_metadata: Option<GroundingMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GroundingMetadata {
+    #[serde(default)]
+    pub(crate) grounding_chunks: Vec<GroundingChunk>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GroundingChunk {
+    #[serde(default)]
+    pub(crate) web: Option<WebSource>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebSource {
+    pub(crate) uri: String,
+    #[serde(default)]
+    pub(crate) title: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -628,6 +650,17 @@ impl EmbedContentRequest {
     }
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEmbedContentsRequest {
+    pub(crate) requests: Vec<EmbedContentRequest>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchEmbedContentsResponse {
+    #[serde(default)]
+    pub(crate) embeddings: Vec<EmbeddingValue>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct EmbedContentResponse {
     pub(crate) embedding: EmbeddingValue,
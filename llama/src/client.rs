@@ -1,8 +1,14 @@
-use crate::error::LlamaError;
+use crate::{
+    embedding::{Pooling, l2_normalize},
+    error::LlamaError,
+    grammar::json_schema_to_gbnf,
+    session::{SessionPool, common_prefix},
+    tool_parser::{self, ToolCallParser},
+};
 use aither_core::{
     EmbeddingModel, LanguageModel,
     llm::{
-        Event, LLMRequest, Message, Role, ToolCall,
+        Event, LLMRequest, Message, Role, ToolCall, Usage,
         model::{Ability, Parameters, Profile, ToolChoice},
         tool::ToolDefinition,
     },
@@ -10,12 +16,13 @@ use aither_core::{
 use futures_core::Stream;
 use llama_cpp_2::{
     LlamaCppError,
-    context::params::{LlamaContextParams, LlamaPoolingType},
+    context::{LlamaContext, params::LlamaContextParams},
     llama_backend::LlamaBackend,
     llama_batch::LlamaBatch,
     model::{AddBos, LlamaChatTemplate, LlamaModel, params::LlamaModelParams},
     openai::OpenAIChatTemplateParams,
     sampling::LlamaSampler,
+    token::LlamaToken,
 };
 use serde::Serialize;
 use serde_json::{Value, json};
@@ -41,6 +48,7 @@ pub struct Llama {
     inner: Arc<LlamaConfig>,
     model: Arc<LlamaModel>,
     backend: Arc<LlamaBackend>,
+    sessions: Option<Arc<SessionPool>>,
 }
 
 impl Llama {
@@ -68,6 +76,22 @@ impl Llama {
         Arc::make_mut(&mut self.inner).n_ctx = Some(n_ctx);
         self
     }
+
+    /// Constrain every response with a GBNF grammar.
+    ///
+    /// Requests that set their own grammar or an output schema override it.
+    #[must_use]
+    pub fn with_grammar(mut self, grammar: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner).grammar = Some(grammar.into());
+        self
+    }
+
+    /// Parse tool calls the chat template doesn't recognise with `parser`.
+    #[must_use]
+    pub fn with_tool_parser(mut self, parser: impl ToolCallParser + 'static) -> Self {
+        Arc::make_mut(&mut self.inner).tool_parser = Some(Arc::new(parser));
+        self
+    }
 }
 
 impl LanguageModel for Llama {
@@ -81,6 +105,7 @@ impl LanguageModel for Llama {
             self.model.clone(),
             self.backend.clone(),
             self.inner.clone(),
+            self.sessions.clone(),
             request,
         )
     }
@@ -143,26 +168,35 @@ impl EmbeddingModel for Llama {
                 .decode(&mut batch)
                 .map_err(|err| LlamaError::Decode(err.to_string()))?;
 
-            if let Ok(embedding) = context.embeddings_seq_ith(0) {
-                return Ok(embedding.to_vec());
+            let mut embedding = if let Ok(embedding) = context.embeddings_seq_ith(0) {
+                embedding.to_vec()
+            } else {
+                // The model declares no pooling: pool the token embeddings here.
+                let token_embeddings = (0..tokens.len() as i32)
+                    .map(|index| context.embeddings_ith(index))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| LlamaError::Decode(err.to_string()))?;
+                cfg.pooling.apply(&token_embeddings)
+            };
+            if cfg.normalize {
+                l2_normalize(&mut embedding);
             }
-
-            let last_index = (tokens.len() - 1) as i32;
-            let embedding = context
-                .embeddings_ith(last_index)
-                .map_err(|err| LlamaError::Decode(err.to_string()))?;
-            Ok(embedding.to_vec())
+            Ok(embedding)
         }
     }
 }
 
 #[derive(Debug, Clone)]
-struct LlamaConfig {
+pub(crate) struct LlamaConfig {
     model_path: PathBuf,
     chat_template: Option<String>,
     n_ctx: Option<u32>,
     n_threads: i32,
     n_threads_batch: i32,
+    grammar: Option<String>,
+    tool_parser: Option<Arc<dyn ToolCallParser>>,
+    pooling: Pooling,
+    normalize: bool,
 }
 
 /// Builder for local llama.cpp model configuration.
@@ -175,6 +209,11 @@ pub struct Builder {
     chat_template: Option<String>,
     n_threads: i32,
     n_threads_batch: i32,
+    grammar: Option<String>,
+    tool_parser: Option<Arc<dyn ToolCallParser>>,
+    sessions: usize,
+    pooling: Pooling,
+    normalize: bool,
     backend: Option<Arc<LlamaBackend>>,
 }
 
@@ -188,6 +227,11 @@ impl Builder {
             chat_template: None,
             n_threads: 4,
             n_threads_batch: 4,
+            grammar: None,
+            tool_parser: None,
+            sessions: 0,
+            pooling: Pooling::Model,
+            normalize: false,
             backend: None,
         }
     }
@@ -234,6 +278,53 @@ impl Builder {
         self
     }
 
+    /// Default GBNF grammar for responses, with `root` as start rule.
+    ///
+    /// Requests that set their own grammar or an output schema override it.
+    #[must_use]
+    pub fn grammar(mut self, grammar: impl Into<String>) -> Self {
+        self.grammar = Some(grammar.into());
+        self
+    }
+
+    /// Keep `sessions` contexts alive between requests and reuse their KV
+    /// cache.
+    ///
+    /// Each request goes to the idle session whose cache shares the longest
+    /// prefix with its prompt, so a conversation that grows turn by turn only
+    /// decodes the new messages. Every session holds a full context in
+    /// memory and runs on its own thread; with all sessions busy, requests
+    /// wait. `0`, the default, creates a fresh context per request.
+    #[must_use]
+    pub const fn sessions(mut self, sessions: usize) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    /// Parse tool calls with `parser` when the chat template finds none.
+    ///
+    /// Without one, Hermes and Llama 3.1 style calls are recognised when
+    /// the chat template uses them.
+    #[must_use]
+    pub fn tool_parser(mut self, parser: impl ToolCallParser + 'static) -> Self {
+        self.tool_parser = Some(Arc::new(parser));
+        self
+    }
+
+    /// How token embeddings are pooled into one vector per text.
+    #[must_use]
+    pub const fn pooling(mut self, pooling: Pooling) -> Self {
+        self.pooling = pooling;
+        self
+    }
+
+    /// Scale embeddings to unit length.
+    #[must_use]
+    pub const fn normalize(mut self, enabled: bool) -> Self {
+        self.normalize = enabled;
+        self
+    }
+
     /// Build the local llama provider.
     pub fn build(self) -> Result<Llama, LlamaError> {
         let model_params = LlamaModelParams::default()
@@ -244,8 +335,15 @@ impl Builder {
         } else {
             Arc::new(init_backend()?)
         };
-        let model = LlamaModel::load_from_file(backend.as_ref(), &self.model_path, &model_params)
-            .map_err(|err| LlamaError::Model(err.to_string()))?;
+        let model = Arc::new(
+            LlamaModel::load_from_file(backend.as_ref(), &self.model_path, &model_params)
+                .map_err(|err| LlamaError::Model(err.to_string()))?,
+        );
+        let sessions = if self.sessions == 0 {
+            None
+        } else {
+            Some(Arc::new(SessionPool::new(self.sessions, &model, &backend)?))
+        };
 
         Ok(Llama {
             inner: Arc::new(LlamaConfig {
@@ -254,9 +352,14 @@ impl Builder {
                 n_ctx: self.n_ctx,
                 n_threads: self.n_threads,
                 n_threads_batch: self.n_threads_batch,
+                grammar: self.grammar,
+                tool_parser: self.tool_parser,
+                pooling: self.pooling,
+                normalize: self.normalize,
             }),
-            model: Arc::new(model),
+            model,
             backend,
+            sessions,
         })
     }
 }
@@ -265,6 +368,7 @@ fn response_stream(
     model: Arc<LlamaModel>,
     backend: Arc<LlamaBackend>,
     cfg: Arc<LlamaConfig>,
+    sessions: Option<Arc<SessionPool>>,
     request: LLMRequest,
 ) -> impl Stream<Item = Result<Event, LlamaError>> + Send {
     let (sender, receiver) = async_channel::unbounded();
@@ -272,7 +376,8 @@ fn response_stream(
     match thread::Builder::new()
         .name("aither-llama-respond".to_string())
         .spawn(move || {
-            if let Err(err) = run_response_generation(model, backend, cfg, request, &worker_sender)
+            if let Err(err) =
+                run_response_generation(model, backend, cfg, sessions, request, &worker_sender)
             {
                 let _ = worker_sender.send_blocking(Err(err));
             }
@@ -288,63 +393,173 @@ fn response_stream(
     }
 }
 
+/// A request rendered and tokenized, ready to decode.
+struct Prepared {
+    prompt: Prompt,
+    tokens: Vec<LlamaToken>,
+    parameters: Parameters,
+    grammar: Option<String>,
+    /// Fallback for tool calls the chat template doesn't parse; only set
+    /// when tools were offered.
+    tool_parser: Option<Arc<dyn ToolCallParser>>,
+}
+
 fn run_response_generation(
     model: Arc<LlamaModel>,
     backend: Arc<LlamaBackend>,
     cfg: Arc<LlamaConfig>,
+    sessions: Option<Arc<SessionPool>>,
     request: LLMRequest,
     sender: &async_channel::Sender<Result<Event, LlamaError>>,
 ) -> Result<(), LlamaError> {
+    let prepared = prepare_response(model.as_ref(), cfg.as_ref(), request)?;
+
+    if let Some(sessions) = sessions {
+        let tokens = prepared.tokens.clone();
+        let sender = sender.clone();
+        sessions.submit(
+            cfg,
+            &tokens,
+            Box::new(move |slot| {
+                let result = slot.and_then(|slot| {
+                    generate(
+                        model.as_ref(),
+                        &mut slot.context,
+                        &mut slot.tokens,
+                        &prepared,
+                        &sender,
+                    )
+                });
+                if let Err(err) = result {
+                    let _ = sender.send_blocking(Err(err));
+                }
+            }),
+        );
+        return Ok(());
+    }
+
+    let mut context = create_context(model.as_ref(), backend.as_ref(), cfg.as_ref(), false)?;
+    generate(
+        model.as_ref(),
+        &mut context,
+        &mut Vec::new(),
+        &prepared,
+        sender,
+    )
+}
+
+fn prepare_response(
+    model: &LlamaModel,
+    cfg: &LlamaConfig,
+    request: LLMRequest,
+) -> Result<Prepared, LlamaError> {
     let (messages, parameters, tool_defs) = request.into_parts();
 
-    if messages.iter().any(|msg| !msg.attachments().is_empty()) {
-        return Err(LlamaError::Unsupported(
-            "attachments are not supported by aither-llama".to_string(),
-        ));
-    }
+    // Text-only: describe attachments instead of rejecting the request.
+    let messages: Vec<Message> = messages
+        .into_iter()
+        .map(|message| message.degrade_attachments(&[]))
+        .collect();
 
     let tool_defs = filter_tool_definitions(tool_defs, &parameters.tool_choice);
-    let template = resolve_chat_template(model.as_ref(), cfg.as_ref())?;
-    let prompt = build_prompt(
-        model.as_ref(),
-        &template,
-        &messages,
-        &parameters,
-        &tool_defs,
-    )?;
+    let template = resolve_chat_template(model, cfg)?;
+    let prompt = build_prompt(model, &template, &messages, &parameters, &tool_defs)?;
+    let grammar = resolve_grammar(&parameters, cfg, &prompt.template_result)?;
+    let tool_parser = if tool_defs.is_empty() {
+        None
+    } else {
+        cfg.tool_parser
+            .clone()
+            .or_else(|| template.to_str().ok().and_then(tool_parser::detect))
+    };
 
-    let mut context = create_context(model.as_ref(), backend.as_ref(), cfg.as_ref(), false)?;
-    let prompt_tokens = model
+    let tokens = model
         .str_to_token(&prompt.template_result.prompt, AddBos::Never)
         .map_err(|err| LlamaError::Token(err.to_string()))?;
-
-    if prompt_tokens.is_empty() {
+    if tokens.is_empty() {
         return Err(LlamaError::Unsupported(
             "empty prompt after template rendering".to_string(),
         ));
     }
 
-    let mut init_batch = LlamaBatch::new(prompt_tokens.len(), 1);
-    init_batch
-        .add_sequence(&prompt_tokens, 0, false)
-        .map_err(|err| LlamaError::Decode(err.to_string()))?;
+    Ok(Prepared {
+        prompt,
+        tokens,
+        parameters,
+        grammar,
+        tool_parser,
+    })
+}
+
+/// Decodes the prompt and streams the response.
+///
+/// `cached` holds the tokens already in the context's KV cache; the shared
+/// prefix is kept and only the rest of the prompt is decoded. On return it
+/// holds the tokens in the cache again.
+fn generate(
+    model: &LlamaModel,
+    context: &mut LlamaContext<'_>,
+    cached: &mut Vec<LlamaToken>,
+    prepared: &Prepared,
+    sender: &async_channel::Sender<Result<Event, LlamaError>>,
+) -> Result<(), LlamaError> {
+    let Prepared {
+        prompt,
+        tokens: prompt_tokens,
+        parameters,
+        grammar,
+        tool_parser,
+    } = prepared;
+
+    let grammar = grammar
+        .as_deref()
+        .map(|grammar| {
+            LlamaSampler::grammar(model, grammar, "root")
+                .map_err(|err| LlamaError::Unsupported(format!("invalid grammar: {err}")))
+        })
+        .transpose()?;
+
+    // Always decode at least the last prompt token, so there are logits to
+    // sample the first response token from.
+    let reuse = common_prefix(cached, prompt_tokens).min(prompt_tokens.len() - 1);
+    cached.truncate(reuse);
+    context
+        .clear_kv_cache_seq(Some(0), Some(reuse as u32), None)
+        .map_err(|err| LlamaError::Context(err.to_string()))?;
+
+    let mut init_batch = LlamaBatch::new(prompt_tokens.len() - reuse, 1);
+    for (pos, &token) in prompt_tokens.iter().enumerate().skip(reuse) {
+        init_batch
+            .add(token, pos as i32, &[0], pos == prompt_tokens.len() - 1)
+            .map_err(|err| LlamaError::Decode(err.to_string()))?;
+    }
     context
         .decode(&mut init_batch)
         .map_err(|err| LlamaError::Decode(err.to_string()))?;
+    cached.extend_from_slice(&prompt_tokens[reuse..]);
 
-    let mut sampler = build_sampler(&parameters);
+    let mut sampler = build_sampler(parameters);
     sampler.accept_many(prompt_tokens.iter());
+    if let Some(grammar) = grammar {
+        // The grammar goes first so the other samplers only see tokens it
+        // allows, and joins after the prompt so it only sees the response.
+        sampler = LlamaSampler::chain_simple([grammar, sampler]);
+    }
 
     let mut generated = String::new();
     let mut decoder = encoding_rs::UTF_8.new_decoder();
     let mut pos = prompt_tokens.len() as i32;
     let max_tokens = parameters.max_tokens.unwrap_or(512);
+    let mut completion_tokens = 0;
+    let mut stop_reason = "length";
 
     for _ in 0..max_tokens {
-        let token = sampler.sample(&context, -1);
+        let token = sampler.sample(context, -1);
         sampler.accept(token);
+        completion_tokens += 1;
 
         if model.is_eog_token(token) {
+            stop_reason = "stop";
             break;
         }
 
@@ -365,24 +580,73 @@ fn run_response_generation(
         context
             .decode(&mut step_batch)
             .map_err(|err| LlamaError::Decode(err.to_string()))?;
+        cached.push(token);
         pos += 1;
     }
 
-    if let Ok(parsed) = prompt
+    let mut calls = match prompt
         .template_result
         .parse_response_oaicompat(&generated, false)
     {
-        for call in parse_openai_message(&parsed)? {
-            if sender.send_blocking(Ok(Event::ToolCall(call))).is_err() {
-                return Ok(());
-            }
+        Ok(parsed) => parse_openai_message(&parsed)?,
+        Err(_) => Vec::new(),
+    };
+    if calls.is_empty()
+        && let Some(parser) = tool_parser
+    {
+        calls = parser.parse(&generated);
+    }
+    for call in calls {
+        stop_reason = "tool_calls";
+        if sender.send_blocking(Ok(Event::ToolCall(call))).is_err() {
+            return Ok(());
         }
     }
 
+    let prompt_tokens = u32::try_from(prompt_tokens.len()).unwrap_or(u32::MAX);
+    let mut usage = Usage::new(prompt_tokens, completion_tokens).with_stop_reason(stop_reason);
+    if reuse > 0 {
+        // Tokens served from a session's KV cache instead of being decoded.
+        usage.cache_read_tokens = Some(u32::try_from(reuse).unwrap_or(u32::MAX));
+    }
+    let _ = sender.send_blocking(Ok(Event::Usage(usage)));
+
     Ok(())
 }
 
-fn create_context<'a>(
+/// Picks the grammar constraining a response, if any.
+///
+/// An explicit grammar wins over an output schema, which wins over the
+/// configured default. Without either, a required tool call is enforced
+/// through the grammar the chat template derives from the tools.
+fn resolve_grammar(
+    parameters: &Parameters,
+    cfg: &LlamaConfig,
+    template_result: &llama_cpp_2::model::ChatTemplateResult,
+) -> Result<Option<String>, LlamaError> {
+    if let Some(grammar) = &parameters.grammar {
+        return Ok(Some(grammar.clone()));
+    }
+    if let Some(schema) = &parameters.response_format {
+        return json_schema_to_gbnf(&schema.clone().to_value()).map(Some);
+    }
+    if parameters.structured_outputs {
+        return json_schema_to_gbnf(&json!({ "type": "object" })).map(Some);
+    }
+    if let Some(grammar) = &cfg.grammar {
+        return Ok(Some(grammar.clone()));
+    }
+    let tool_required = matches!(
+        parameters.tool_choice,
+        ToolChoice::Required | ToolChoice::Exact(_)
+    );
+    if tool_required && !template_result.grammar_lazy {
+        return Ok(template_result.grammar.clone());
+    }
+    Ok(None)
+}
+
+pub(crate) fn create_context<'a>(
     model: &'a LlamaModel,
     backend: &LlamaBackend,
     cfg: &LlamaConfig,
@@ -397,7 +661,7 @@ fn create_context<'a>(
 
     if embeddings {
         params = params.with_embeddings(true);
-        params = params.with_pooling_type(LlamaPoolingType::Last);
+        params = params.with_pooling_type(cfg.pooling.to_llama());
     }
 
     model
@@ -440,7 +704,14 @@ fn build_prompt(
             &OpenAIChatTemplateParams {
                 messages_json: &messages_json,
                 tools_json: tools_json.as_deref(),
-                tool_choice: None,
+                // Only the chosen tool is left after filtering, so requiring
+                // any call enforces exact choices too.
+                tool_choice: match parameters.tool_choice {
+                    ToolChoice::Required | ToolChoice::Exact(_) if !tool_defs.is_empty() => {
+                        Some("required")
+                    }
+                    _ => None,
+                },
                 json_schema: None,
                 grammar: None,
                 reasoning_format: None,
@@ -0,0 +1,65 @@
+//! Pooling and normalization for llama.cpp embeddings.
+
+use llama_cpp_2::context::params::LlamaPoolingType;
+
+/// How token embeddings are combined into one vector per text.
+///
+/// Embedding GGUFs declare the pooling they were trained with, so
+/// [`Model`](Pooling::Model) is usually right; override it for models with
+/// missing or wrong metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Pooling {
+    /// Use the pooling declared in the GGUF metadata.
+    ///
+    /// Models that declare none, such as chat models, are mean-pooled.
+    #[default]
+    Model,
+    /// Average all token embeddings.
+    Mean,
+    /// Use the last token's embedding, as decoder embedding models do.
+    Last,
+    /// Use the first token's embedding, as BERT-style models do.
+    Cls,
+}
+
+impl Pooling {
+    pub(crate) const fn to_llama(self) -> LlamaPoolingType {
+        match self {
+            Self::Model => LlamaPoolingType::Unspecified,
+            Self::Mean => LlamaPoolingType::Mean,
+            Self::Last => LlamaPoolingType::Last,
+            Self::Cls => LlamaPoolingType::Cls,
+        }
+    }
+
+    /// Pools per-token embeddings, for when llama.cpp returned no pooled
+    /// embedding.
+    pub(crate) fn apply(self, tokens: &[&[f32]]) -> Vec<f32> {
+        match self {
+            Self::Last => tokens.last().map(|token| token.to_vec()),
+            Self::Cls => tokens.first().map(|token| token.to_vec()),
+            Self::Model | Self::Mean => tokens.first().map(|first| {
+                let mut sum = vec![0.0; first.len()];
+                for token in tokens {
+                    for (total, value) in sum.iter_mut().zip(token.iter()) {
+                        *total += value;
+                    }
+                }
+                let count = tokens.len() as f32;
+                sum.iter_mut().for_each(|total| *total /= count);
+                sum
+            }),
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// Scales `vec` to unit length, leaving zero vectors unchanged.
+pub(crate) fn l2_normalize(vec: &mut [f32]) {
+    let norm: f32 = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for v in vec.iter_mut() {
+            *v /= norm;
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use aither_core::llm::{LlmError, LlmErrorKind};
 use thiserror::Error;
 
 /// Errors raised by the local llama.cpp backend.
@@ -22,3 +23,20 @@ pub enum LlamaError {
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
 }
+
+impl From<LlamaError> for LlmError {
+    /// Local inference has no network or rate limits; failures are either
+    /// requests the backend cannot run or problems with the model itself.
+    fn from(err: LlamaError) -> Self {
+        let kind = match &err {
+            LlamaError::Unsupported(_) => LlmErrorKind::InvalidRequest,
+            LlamaError::Context(message)
+            | LlamaError::Token(message)
+            | LlamaError::Decode(message) => {
+                LlmErrorKind::from_message(message).unwrap_or(LlmErrorKind::Other)
+            }
+            LlamaError::Model(_) | LlamaError::Json(_) => LlmErrorKind::Other,
+        };
+        Self::new(kind, err.to_string())
+    }
+}
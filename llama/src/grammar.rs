@@ -0,0 +1,369 @@
+//! JSON schema to GBNF conversion.
+//!
+//! llama.cpp constrains sampling with grammars in its GBNF format. Converting
+//! a JSON schema into one lets local models produce JSON that always parses
+//! and matches the schema, instead of relying on the prompt alone.
+
+use crate::LlamaError;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Rules shared by every converted schema.
+const PRIMITIVES: &str = r#"space ::= | " " | "\n" [ \t]{0,20}
+string ::= "\"" char* "\"" space
+char ::= [^"\\\x7F\x00-\x1F] | [\\] (["\\/bfnrt] | "u" [0-9a-fA-F]{4})
+integral-part ::= [0] | [1-9] [0-9]{0,15}
+number ::= "-"? integral-part ("." [0-9]+)? ([eE] [-+]? [0-9]+)? space
+integer ::= "-"? integral-part space
+boolean ::= ("true" | "false") space
+null ::= "null" space
+value ::= object | array | string | number | boolean | null
+object ::= "{" space (string ":" space value ("," space string ":" space value)*)? "}" space
+array ::= "[" space (value ("," space value)*)? "]" space
+"#;
+
+/// Converts a JSON schema into a GBNF grammar whose start rule is `root`.
+///
+/// Supports objects with `properties` and `required`, arrays with `items`,
+/// the primitive types, `enum`, `const`, `anyOf`/`oneOf`, `nullable`, type
+/// arrays and local `$ref`s, including recursive ones. Required properties
+/// are generated in schema order, followed by optional ones. Keywords that
+/// don't change the shape of the JSON, such as `format` or `minLength`, are
+/// ignored, so the grammar may accept more than the schema does.
+///
+/// # Errors
+///
+/// Returns [`LlamaError::Unsupported`] if the schema uses a `$ref` that
+/// cannot be resolved, an empty `enum` or a type this converter doesn't
+/// know.
+pub fn json_schema_to_gbnf(schema: &Value) -> Result<String, LlamaError> {
+    let mut converter = Converter {
+        root: schema,
+        rules: BTreeMap::new(),
+        refs: BTreeMap::new(),
+    };
+    let root = converter.visit(schema, "root")?;
+    if root != "root" {
+        converter.rules.insert("root".to_string(), root);
+    }
+
+    let mut grammar = String::new();
+    for (name, body) in &converter.rules {
+        grammar.push_str(name);
+        grammar.push_str(" ::= ");
+        grammar.push_str(body);
+        grammar.push('\n');
+    }
+    grammar.push_str(PRIMITIVES);
+    Ok(grammar)
+}
+
+struct Converter<'a> {
+    root: &'a Value,
+    rules: BTreeMap<String, String>,
+    /// Rule names of `$ref` targets, filled before visiting them so that
+    /// recursive schemas terminate.
+    refs: BTreeMap<String, String>,
+}
+
+impl Converter<'_> {
+    /// Returns a GBNF expression for `schema`, adding rules as needed.
+    ///
+    /// `name` suggests a rule name for compound schemas; when it is `root`
+    /// the top-level rule is defined directly.
+    fn visit(&mut self, schema: &Value, name: &str) -> Result<String, LlamaError> {
+        let Value::Object(map) = schema else {
+            // `true` and other non-object schemas accept anything.
+            return Ok("value".to_string());
+        };
+
+        if let Some(Value::String(reference)) = map.get("$ref") {
+            return self.visit_ref(reference);
+        }
+
+        let expression = if let Some(value) = map.get("const") {
+            literal(value)
+        } else if let Some(Value::Array(values)) = map.get("enum") {
+            if values.is_empty() {
+                return Err(LlamaError::Unsupported(
+                    "JSON schema `enum` has no values".to_string(),
+                ));
+            }
+            alternatives(values.iter().map(literal))
+        } else if let Some(Value::Array(variants)) = map.get("anyOf").or_else(|| map.get("oneOf")) {
+            let mut options = Vec::with_capacity(variants.len());
+            for (index, variant) in variants.iter().enumerate() {
+                options.push(self.visit(variant, &format!("{name}-{index}"))?);
+            }
+            alternatives(options)
+        } else {
+            match map.get("type") {
+                Some(Value::String(kind)) => self.visit_type(kind, map, name)?,
+                Some(Value::Array(kinds)) => {
+                    let mut options = Vec::with_capacity(kinds.len());
+                    for kind in kinds.iter().filter_map(Value::as_str) {
+                        options.push(self.visit_type(kind, map, &format!("{name}-{kind}"))?);
+                    }
+                    alternatives(options)
+                }
+                _ if map.contains_key("properties") => self.visit_type("object", map, name)?,
+                _ if map.contains_key("items") => self.visit_type("array", map, name)?,
+                _ => "value".to_string(),
+            }
+        };
+
+        let expression = if map.get("nullable") == Some(&Value::Bool(true)) {
+            format!("({expression}) | null")
+        } else {
+            expression
+        };
+        Ok(self.define(name, expression))
+    }
+
+    fn visit_type(
+        &mut self,
+        kind: &str,
+        map: &Map<String, Value>,
+        name: &str,
+    ) -> Result<String, LlamaError> {
+        match kind {
+            "object" => self.visit_object(map, name),
+            "array" => {
+                let item = match map.get("items") {
+                    Some(items) => self.visit(items, &format!("{name}-item"))?,
+                    None => "value".to_string(),
+                };
+                Ok(format!(
+                    r#""[" space ({item} ("," space {item})*)? "]" space"#
+                ))
+            }
+            "string" | "number" | "integer" | "boolean" | "null" => Ok(kind.to_string()),
+            other => Err(LlamaError::Unsupported(format!(
+                "JSON schema type `{other}` has no grammar"
+            ))),
+        }
+    }
+
+    fn visit_object(&mut self, map: &Map<String, Value>, name: &str) -> Result<String, LlamaError> {
+        let Some(Value::Object(properties)) = map.get("properties") else {
+            return Ok("object".to_string());
+        };
+        let required: Vec<&str> = map
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut mandatory = Vec::new();
+        let mut optional = Vec::new();
+        for (key, property) in properties {
+            let value = self.visit(property, &format!("{name}-{}", rule_name(key)))?;
+            let pair = format!(
+                r#"{} ":" space {value}"#,
+                literal(&Value::String(key.clone()))
+            );
+            if required.contains(&key.as_str()) {
+                mandatory.push(pair);
+            } else {
+                optional.push(pair);
+            }
+        }
+
+        // Optional properties after something was written: each one may be
+        // skipped, but keeps its place in the order.
+        let tail = |from: usize| -> String {
+            optional[from..]
+                .iter()
+                .map(|pair| format!(r#"("," space {pair})?"#))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let body = if mandatory.is_empty() {
+            if optional.is_empty() {
+                String::new()
+            } else {
+                // The first property written is any one of the optional ones.
+                let firsts = (0..optional.len())
+                    .map(|index| sequence([optional[index].clone(), tail(index + 1)]))
+                    .collect::<Vec<_>>();
+                format!("({})?", firsts.join(" | "))
+            }
+        } else {
+            sequence([mandatory.join(r#" "," space "#), tail(0)])
+        };
+        Ok(sequence([
+            r#""{" space"#.to_string(),
+            body,
+            r#""}" space"#.to_string(),
+        ]))
+    }
+
+    fn visit_ref(&mut self, reference: &str) -> Result<String, LlamaError> {
+        if let Some(rule) = self.refs.get(reference) {
+            return Ok(rule.clone());
+        }
+        let target = reference
+            .strip_prefix('#')
+            .and_then(|pointer| self.root.pointer(pointer))
+            .ok_or_else(|| {
+                LlamaError::Unsupported(format!("cannot resolve JSON schema `$ref` {reference}"))
+            })?;
+        let name = format!(
+            "ref-{}",
+            rule_name(reference.rsplit('/').next().unwrap_or(reference))
+        );
+        self.refs.insert(reference.to_string(), name.clone());
+        let expression = self.visit(target, &name)?;
+        if expression != name {
+            self.rules.insert(name.clone(), expression);
+        }
+        Ok(name)
+    }
+
+    /// Stores compound expressions as a named rule and returns its name;
+    /// primitive rule names are returned as they are.
+    fn define(&mut self, name: &str, expression: String) -> String {
+        if is_rule_name(&expression) && name != "root" {
+            return expression;
+        }
+        let mut unique = name.to_string();
+        let mut counter = 1;
+        while self.rules.contains_key(&unique) {
+            counter += 1;
+            unique = format!("{name}-{counter}");
+        }
+        self.rules.insert(unique.clone(), expression);
+        unique
+    }
+}
+
+/// A GBNF literal matching `value` serialized as JSON.
+fn literal(value: &Value) -> String {
+    let json = value.to_string();
+    let mut escaped = String::with_capacity(json.len() + 2);
+    escaped.push('"');
+    for c in json.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push_str("\" space");
+    escaped
+}
+
+fn alternatives(options: impl IntoIterator<Item = String>) -> String {
+    options
+        .into_iter()
+        .map(|option| format!("({option})"))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Joins the non-empty parts of a sequence.
+fn sequence(parts: impl IntoIterator<Item = String>) -> String {
+    parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Turns a property or definition name into a valid rule name.
+fn rule_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if sanitized.is_empty() {
+        "x".to_string()
+    } else {
+        sanitized
+    }
+}
+
+fn is_rule_name(expression: &str) -> bool {
+    !expression.is_empty()
+        && expression
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule<'a>(grammar: &'a str, name: &str) -> &'a str {
+        grammar
+            .lines()
+            .find_map(|line| line.strip_prefix(&format!("{name} ::= ")))
+            .unwrap_or_else(|| panic!("no rule {name} in:\n{grammar}"))
+    }
+
+    #[test]
+    fn converts_objects_with_optional_properties() {
+        let grammar = json_schema_to_gbnf(&json!({
+            "type": "object",
+            "properties": {
+                "city": { "type": "string" },
+                "days": { "type": "integer" },
+                "units": { "enum": ["metric", "imperial"] }
+            },
+            "required": ["city"]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            rule(&grammar, "root"),
+            r#""{" space "\"city\"" space ":" space string ("," space "\"days\"" space ":" space integer)? ("," space "\"units\"" space ":" space root-units)? "}" space"#
+        );
+        assert_eq!(
+            rule(&grammar, "root-units"),
+            r#"("\"metric\"" space) | ("\"imperial\"" space)"#
+        );
+    }
+
+    #[test]
+    fn resolves_recursive_refs() {
+        let grammar = json_schema_to_gbnf(&json!({
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": {
+                        "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } }
+                    },
+                    "required": ["children"]
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(rule(&grammar, "root"), "ref-node");
+        assert_eq!(
+            rule(&grammar, "ref-node"),
+            r#""{" space "\"children\"" space ":" space ref-node-children "}" space"#
+        );
+        assert_eq!(
+            rule(&grammar, "ref-node-children"),
+            r#""[" space (ref-node ("," space ref-node)*)? "]" space"#
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_refs() {
+        assert!(json_schema_to_gbnf(&json!({ "$ref": "#/$defs/Missing" })).is_err());
+    }
+}
@@ -1,9 +1,16 @@
 //! Local llama.cpp provider integration for aither.
 
 mod client;
+mod embedding;
 mod error;
+mod grammar;
 mod provider;
+mod session;
+mod tool_parser;
 
 pub use client::{Builder, Llama};
+pub use embedding::Pooling;
 pub use error::LlamaError;
+pub use grammar::json_schema_to_gbnf;
 pub use provider::LlamaProvider;
+pub use tool_parser::{HermesParser, Llama3Parser, ToolCallParser};
@@ -0,0 +1,222 @@
+//! Contexts that keep their KV cache between requests.
+//!
+//! Creating a llama.cpp context and ingesting the whole prompt on every
+//! request makes agent loops slow, since each turn resends the conversation
+//! so far. A [`SessionPool`] keeps a few contexts alive, each on its own
+//! thread because contexts borrow the model and can't move between threads.
+//! Requests go to the idle session whose cached tokens share the longest
+//! prefix with the new prompt, so only the new tokens are decoded.
+
+use crate::{
+    LlamaError,
+    client::{LlamaConfig, create_context},
+};
+use llama_cpp_2::{
+    context::LlamaContext, llama_backend::LlamaBackend, model::LlamaModel, token::LlamaToken,
+};
+use std::{
+    fmt,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{Arc, Condvar, Mutex, PoisonError, mpsc},
+    thread,
+};
+
+/// A context and the tokens currently in its KV cache, in order.
+pub(crate) struct Slot<'a> {
+    pub(crate) context: LlamaContext<'a>,
+    pub(crate) tokens: Vec<LlamaToken>,
+}
+
+/// Work run on a session thread.
+pub(crate) type Work = Box<dyn FnOnce(Result<&mut Slot<'_>, LlamaError>) + Send>;
+
+struct Job {
+    cfg: Arc<LlamaConfig>,
+    work: Work,
+}
+
+#[derive(Default)]
+struct SessionState {
+    /// Copy of the session's cached tokens, for picking a session.
+    tokens: Vec<LlamaToken>,
+    busy: bool,
+    /// Value of [`Sessions::clock`] when the session was last picked.
+    last_used: u64,
+}
+
+struct Sessions {
+    states: Vec<SessionState>,
+    /// Incremented whenever a session is picked.
+    clock: u64,
+}
+
+struct Shared {
+    sessions: Mutex<Sessions>,
+    idle: Condvar,
+}
+
+/// Fixed set of long-lived contexts, reused by prompt prefix.
+pub(crate) struct SessionPool {
+    jobs: Vec<mpsc::Sender<Job>>,
+    shared: Arc<Shared>,
+}
+
+impl fmt::Debug for SessionPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionPool")
+            .field("sessions", &self.jobs.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionPool {
+    /// Starts `size` session threads; contexts are created on first use.
+    pub(crate) fn new(
+        size: usize,
+        model: &Arc<LlamaModel>,
+        backend: &Arc<LlamaBackend>,
+    ) -> Result<Self, LlamaError> {
+        let size = size.max(1);
+        let shared = Arc::new(Shared {
+            sessions: Mutex::new(Sessions {
+                states: (0..size).map(|_| SessionState::default()).collect(),
+                clock: 0,
+            }),
+            idle: Condvar::new(),
+        });
+
+        let mut jobs = Vec::with_capacity(size);
+        for index in 0..size {
+            let (sender, receiver) = mpsc::channel();
+            let model = model.clone();
+            let backend = backend.clone();
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name(format!("aither-llama-session-{index}"))
+                .spawn(move || run_session(index, &model, &backend, &receiver, &shared))
+                .map_err(|err| {
+                    LlamaError::Context(format!("failed to spawn llama session thread: {err}"))
+                })?;
+            jobs.push(sender);
+        }
+
+        Ok(Self { jobs, shared })
+    }
+
+    /// Runs `work` on the idle session sharing the longest prefix with
+    /// `tokens`, waiting for one to become idle if all are busy.
+    pub(crate) fn submit(&self, cfg: Arc<LlamaConfig>, tokens: &[LlamaToken], work: Work) {
+        let mut guard = self
+            .shared
+            .sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let index = loop {
+            if let Some(index) = pick(&guard.states, tokens) {
+                break index;
+            }
+            guard = self
+                .shared
+                .idle
+                .wait(guard)
+                .unwrap_or_else(PoisonError::into_inner);
+        };
+        guard.clock += 1;
+        let clock = guard.clock;
+        let state = &mut guard.states[index];
+        state.busy = true;
+        state.last_used = clock;
+        drop(guard);
+
+        if let Err(mpsc::SendError(job)) = self.jobs[index].send(Job { cfg, work }) {
+            (job.work)(Err(LlamaError::Context(
+                "llama session thread has stopped".to_string(),
+            )));
+        }
+    }
+}
+
+/// Picks the idle session with the longest shared prefix, preferring the
+/// least recently used one among equals so unrelated conversations don't
+/// evict each other.
+fn pick(states: &[SessionState], tokens: &[LlamaToken]) -> Option<usize> {
+    states
+        .iter()
+        .enumerate()
+        .filter(|(_, state)| !state.busy)
+        .max_by_key(|(_, state)| {
+            (
+                common_prefix(&state.tokens, tokens),
+                std::cmp::Reverse(state.last_used),
+            )
+        })
+        .map(|(index, _)| index)
+}
+
+/// Number of leading tokens `a` and `b` have in common.
+pub(crate) fn common_prefix(a: &[LlamaToken], b: &[LlamaToken]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+fn run_session(
+    index: usize,
+    model: &LlamaModel,
+    backend: &LlamaBackend,
+    jobs: &mpsc::Receiver<Job>,
+    shared: &Shared,
+) {
+    let mut current: Option<(Arc<LlamaConfig>, Slot<'_>)> = None;
+
+    for Job { cfg, work } in jobs {
+        // Clones of a model configured differently get a fresh context.
+        if current
+            .as_ref()
+            .is_some_and(|(current_cfg, _)| !Arc::ptr_eq(current_cfg, &cfg))
+        {
+            current = None;
+        }
+        if current.is_none() {
+            match create_context(model, backend, &cfg, false) {
+                Ok(context) => {
+                    current = Some((
+                        cfg,
+                        Slot {
+                            context,
+                            tokens: Vec::new(),
+                        },
+                    ));
+                }
+                Err(err) => {
+                    work(Err(err));
+                    release(shared, index, Vec::new());
+                    continue;
+                }
+            }
+        }
+        let Some((_, slot)) = &mut current else {
+            continue;
+        };
+
+        let mut tokens = Vec::new();
+        if catch_unwind(AssertUnwindSafe(|| work(Ok(&mut *slot)))).is_ok() {
+            tokens.clone_from(&slot.tokens);
+        } else {
+            tracing::error!("llama session {index} panicked; discarding its context");
+            current = None;
+        }
+        release(shared, index, tokens);
+    }
+}
+
+/// Marks a session idle with `tokens` cached and wakes a waiting request.
+fn release(shared: &Shared, index: usize, tokens: Vec<LlamaToken>) {
+    let mut guard = shared
+        .sessions
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let state = &mut guard.states[index];
+    state.tokens = tokens;
+    state.busy = false;
+    drop(guard);
+    shared.idle.notify_one();
+}
@@ -0,0 +1,182 @@
+//! Tool calls written in model-specific formats.
+//!
+//! Function-calling GGUFs write tool calls as text in the format they were
+//! trained on. llama.cpp recognises many of them from the chat template;
+//! the parsers here cover the common formats it misses and let callers plug
+//! in their own.
+
+use aither_core::llm::ToolCall;
+use serde_json::{Map, Value};
+use std::{fmt, sync::Arc};
+
+/// Extracts tool calls from the text a model generated.
+pub trait ToolCallParser: fmt::Debug + Send + Sync {
+    /// Returns the tool calls in `output`, in order, or nothing if it
+    /// contains none.
+    fn parse(&self, output: &str) -> Vec<ToolCall>;
+}
+
+/// Hermes-style calls, used by Hermes 2/3 and Qwen 2.5 models:
+/// `<tool_call>{"name": ..., "arguments": {...}}</tool_call>`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HermesParser;
+
+impl ToolCallParser for HermesParser {
+    fn parse(&self, output: &str) -> Vec<ToolCall> {
+        let mut calls = Vec::new();
+        let mut rest = output;
+        while let Some(start) = rest.find("<tool_call>") {
+            rest = &rest[start + "<tool_call>".len()..];
+            // The closing tag may be cut off when generation stops.
+            let end = rest.find("</tool_call>").unwrap_or(rest.len());
+            if let Ok(value) = serde_json::from_str::<Value>(rest[..end].trim())
+                && let Some(call) = call_from_json(&value, calls.len())
+            {
+                calls.push(call);
+            }
+            rest = &rest[end..];
+        }
+        calls
+    }
+}
+
+/// Llama 3.1+ calls: JSON objects with `name` and `parameters`, optionally
+/// after `<|python_tag|>` and separated by `;`, or
+/// `<function=name>{...}</function>`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Llama3Parser;
+
+impl ToolCallParser for Llama3Parser {
+    fn parse(&self, output: &str) -> Vec<ToolCall> {
+        let output = output.trim();
+        if output.contains("<function=") {
+            return parse_function_tags(output);
+        }
+
+        // Without the tag only a reply that is nothing but calls counts, so
+        // that JSON in an ordinary answer isn't mistaken for one.
+        let (body, tagged) = match output.find("<|python_tag|>") {
+            Some(start) => (&output[start + "<|python_tag|>".len()..], true),
+            None => (output, false),
+        };
+
+        let mut calls = Vec::new();
+        let mut rest = body;
+        loop {
+            rest = rest.trim_start().trim_start_matches(';').trim_start();
+            if rest.is_empty() {
+                return calls;
+            }
+            let mut values = serde_json::Deserializer::from_str(rest).into_iter::<Value>();
+            match values
+                .next()
+                .and_then(Result::ok)
+                .and_then(|value| call_from_json(&value, calls.len()))
+            {
+                Some(call) => {
+                    calls.push(call);
+                    rest = &rest[values.byte_offset()..];
+                }
+                None if tagged => return calls,
+                None => return Vec::new(),
+            }
+        }
+    }
+}
+
+fn parse_function_tags(output: &str) -> Vec<ToolCall> {
+    let mut calls = Vec::new();
+    let mut rest = output;
+    while let Some(start) = rest.find("<function=") {
+        rest = &rest[start + "<function=".len()..];
+        let Some(name_end) = rest.find('>') else {
+            break;
+        };
+        let name = rest[..name_end].trim();
+        rest = &rest[name_end + 1..];
+        let end = rest.find("</function>").unwrap_or(rest.len());
+        if let Ok(Value::Object(arguments)) = serde_json::from_str(rest[..end].trim()) {
+            calls.push(ToolCall::new(
+                call_id(calls.len()),
+                name,
+                Value::Object(arguments),
+            ));
+        }
+        rest = &rest[end..];
+    }
+    calls
+}
+
+/// Builds a call from `{"name": ..., "arguments" | "parameters": ...}`,
+/// where the arguments may also be a JSON-encoded string.
+fn call_from_json(value: &Value, index: usize) -> Option<ToolCall> {
+    let name = value.get("name")?.as_str()?;
+    let arguments = match value.get("arguments").or_else(|| value.get("parameters")) {
+        Some(Value::String(raw)) => {
+            serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.clone()))
+        }
+        Some(arguments) => arguments.clone(),
+        None => Value::Object(Map::new()),
+    };
+    Some(ToolCall::new(call_id(index), name, arguments))
+}
+
+fn call_id(index: usize) -> String {
+    format!("llama_tool_call_{index}")
+}
+
+/// Picks a parser from the chat template source, if it uses a known format.
+pub(crate) fn detect(template: &str) -> Option<Arc<dyn ToolCallParser>> {
+    if template.contains("<tool_call>") {
+        Some(Arc::new(HermesParser))
+    } else if template.contains("<|python_tag|>")
+        || (template.contains("<|start_header_id|>") && template.contains("\"parameters\""))
+    {
+        Some(Arc::new(Llama3Parser))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_hermes_calls() {
+        let calls = HermesParser.parse(
+            "Let me check.\n<tool_call>\n{\"name\": \"weather\", \"arguments\": {\"city\": \"Osaka\"}}\n</tool_call>\n<tool_call>{\"name\": \"time\", \"arguments\": \"{}\"}",
+        );
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].name, "weather");
+        assert_eq!(calls[0].arguments, json!({ "city": "Osaka" }));
+        assert_eq!(calls[1].name, "time");
+        assert_eq!(calls[1].arguments, json!({}));
+        assert_ne!(calls[0].id, calls[1].id);
+    }
+
+    #[test]
+    fn parses_llama3_calls() {
+        let calls = Llama3Parser.parse(
+            "<|python_tag|>{\"name\": \"weather\", \"parameters\": {\"city\": \"Osaka\"}}; {\"name\": \"time\", \"parameters\": {}}",
+        );
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].arguments, json!({ "city": "Osaka" }));
+
+        let calls = Llama3Parser.parse("<function=weather>{\"city\": \"Osaka\"}</function>");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "weather");
+    }
+
+    #[test]
+    fn ignores_json_in_plain_answers() {
+        assert!(
+            Llama3Parser
+                .parse("Here is the config: {\"name\": \"demo\", \"parameters\": {}} as asked.")
+                .is_empty()
+        );
+        assert!(HermesParser.parse("No tools needed.").is_empty());
+    }
+}
@@ -1,11 +1,14 @@
 //! MCP client for connecting to MCP servers.
 
+use std::collections::HashMap;
+
 use tracing::debug;
 
 use crate::protocol::{
-    CallToolParams, CallToolResult, InitializeParams, InitializeResult, JsonRpcRequest,
-    ListToolsResult, McpError, McpToolDefinition, Resource, ResourceContents, ServerCapabilities,
-    ServerInfo,
+    CallToolParams, CallToolResult, GetPromptParams, GetPromptResult, InitializeParams,
+    InitializeResult, JsonRpcRequest, ListPromptsResult, ListResourcesResult, ListToolsResult,
+    McpError, McpToolDefinition, PaginatedParams, Prompt, ReadResourceResult, Resource,
+    ResourceContents, ResourceUriParams, ServerCapabilities, ServerInfo,
 };
 use crate::transport::Transport;
 
@@ -120,6 +123,7 @@ impl<T: Transport> McpClient<T> {
         let params = CallToolParams {
             name: name.to_string(),
             arguments,
+            meta: None,
         };
         let request = JsonRpcRequest::with_params(0i64, "tools/call", params);
 
@@ -135,28 +139,36 @@ impl<T: Transport> McpClient<T> {
         Ok(result)
     }
 
-    /// List available resources from the server.
+    /// List available resources from the server, following pagination.
     ///
     /// # Errors
     ///
     /// Returns an error if the request fails or resources are not supported.
     pub async fn list_resources(&mut self) -> Result<Vec<Resource>, McpError> {
-        #[derive(serde::Deserialize)]
-        struct ListResourcesResult {
-            resources: Vec<Resource>,
+        let mut resources = Vec::new();
+        let mut cursor = None;
+        loop {
+            let params = PaginatedParams { cursor };
+            let request = JsonRpcRequest::with_params(0i64, "resources/list", params);
+            let response = self.transport.request(request).await?;
+            let page: ListResourcesResult =
+                serde_json::from_value(response.into_result().map_err(McpError::JsonRpc)?)?;
+            resources.extend(page.resources);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
         }
 
-        let request = JsonRpcRequest::new(0i64, "resources/list");
-        let response = self.transport.request(request).await?;
-        let result: ListResourcesResult =
-            serde_json::from_value(response.into_result().map_err(McpError::JsonRpc)?)?;
-
-        debug!("Listed {} resources", result.resources.len());
-        Ok(result.resources)
+        debug!("Listed {} resources", resources.len());
+        Ok(resources)
     }
 
     /// Read a resource from the server.
     ///
+    /// Returns the first content part; use
+    /// [`read_resource_contents`](Self::read_resource_contents) for all parts.
+    ///
     /// # Arguments
     ///
     /// * `uri` - The resource URI.
@@ -165,17 +177,23 @@ impl<T: Transport> McpClient<T> {
     ///
     /// Returns an error if the request fails.
     pub async fn read_resource(&mut self, uri: &str) -> Result<ResourceContents, McpError> {
-        #[derive(serde::Serialize)]
-        struct ReadResourceParams {
-            uri: String,
-        }
-
-        #[derive(serde::Deserialize)]
-        struct ReadResourceResult {
-            contents: Vec<ResourceContents>,
-        }
+        self.read_resource_contents(uri)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| McpError::Transport("No content returned".to_string()))
+    }
 
-        let params = ReadResourceParams {
+    /// Read every content part of a resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn read_resource_contents(
+        &mut self,
+        uri: &str,
+    ) -> Result<Vec<ResourceContents>, McpError> {
+        let params = ResourceUriParams {
             uri: uri.to_string(),
         };
         let request = JsonRpcRequest::with_params(0i64, "resources/read", params);
@@ -183,12 +201,139 @@ impl<T: Transport> McpClient<T> {
         let response = self.transport.request(request).await?;
         let result: ReadResourceResult =
             serde_json::from_value(response.into_result().map_err(McpError::JsonRpc)?)?;
+        Ok(result.contents)
+    }
 
-        result
-            .contents
-            .into_iter()
-            .next()
-            .ok_or_else(|| McpError::Transport("No content returned".to_string()))
+    /// Ask the server to notify us when a resource changes.
+    ///
+    /// Changed URIs are collected by [`take_resource_updates`](Self::take_resource_updates).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::Unsupported`] if the server does not offer subscriptions,
+    /// or an error if the request fails.
+    pub async fn subscribe_resource(&mut self, uri: &str) -> Result<(), McpError> {
+        self.resource_subscription("resources/subscribe", uri).await
+    }
+
+    /// Stop receiving change notifications for a resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::Unsupported`] if the server does not offer subscriptions,
+    /// or an error if the request fails.
+    pub async fn unsubscribe_resource(&mut self, uri: &str) -> Result<(), McpError> {
+        self.resource_subscription("resources/unsubscribe", uri)
+            .await
+    }
+
+    async fn resource_subscription(&mut self, method: &str, uri: &str) -> Result<(), McpError> {
+        let supported = self
+            .capabilities
+            .resources
+            .as_ref()
+            .and_then(|resources| resources.subscribe)
+            .unwrap_or(false);
+        if !supported {
+            return Err(McpError::Unsupported("resource subscriptions"));
+        }
+
+        let params = ResourceUriParams {
+            uri: uri.to_string(),
+        };
+        let request = JsonRpcRequest::with_params(0i64, method, params);
+        let response = self.transport.request(request).await?;
+        response.into_result().map_err(McpError::JsonRpc)?;
+        Ok(())
+    }
+
+    /// Take the URIs of subscribed resources that changed since the last call.
+    ///
+    /// Notifications are picked up while other requests are in flight, so
+    /// updates surface on the next request after the server sends them.
+    pub fn take_resource_updates(&mut self) -> Vec<String> {
+        let mut uris: Vec<String> = Vec::new();
+        for notification in self.transport.take_notifications() {
+            if notification.method != "notifications/resources/updated" {
+                continue;
+            }
+            let Some(params) = notification.params else {
+                continue;
+            };
+            if let Ok(ResourceUriParams { uri }) = serde_json::from_value(params)
+                && !uris.contains(&uri)
+            {
+                uris.push(uri);
+            }
+        }
+        uris
+    }
+
+    /// List available prompt templates from the server, following pagination.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or prompts are not supported.
+    pub async fn list_prompts(&mut self) -> Result<Vec<Prompt>, McpError> {
+        let mut prompts = Vec::new();
+        let mut cursor = None;
+        loop {
+            let params = PaginatedParams { cursor };
+            let request = JsonRpcRequest::with_params(0i64, "prompts/list", params);
+            let response = self.transport.request(request).await?;
+            let page: ListPromptsResult =
+                serde_json::from_value(response.into_result().map_err(McpError::JsonRpc)?)?;
+            prompts.extend(page.prompts);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        debug!("Listed {} prompts", prompts.len());
+        Ok(prompts)
+    }
+
+    /// Expand a prompt template with the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult, McpError> {
+        let params = GetPromptParams {
+            name: name.to_string(),
+            arguments,
+        };
+        let request = JsonRpcRequest::with_params(0i64, "prompts/get", params);
+
+        let response = self.transport.request(request).await?;
+        let result: GetPromptResult =
+            serde_json::from_value(response.into_result().map_err(McpError::JsonRpc)?)?;
+        Ok(result)
+    }
+
+    /// Send a `ping` request to check that the server is responsive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not answer.
+    pub async fn ping(&mut self) -> Result<(), McpError> {
+        let request = JsonRpcRequest::new(0i64, "ping");
+        let response = self.transport.request(request).await?;
+        response.into_result().map_err(McpError::JsonRpc)?;
+        Ok(())
+    }
+
+    /// Check whether the transport still considers the server reachable.
+    ///
+    /// This is a local check (for example, whether a child process is still
+    /// running); use [`ping`](Self::ping) for a round trip.
+    pub fn is_alive(&mut self) -> bool {
+        self.transport.is_alive()
     }
 
     /// Close the client connection.
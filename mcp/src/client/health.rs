@@ -0,0 +1,187 @@
+//! Connection supervision for MCP servers.
+//!
+//! [`McpToolService`](super::McpToolService) pings its server whenever the
+//! connection has been idle for [`ReconnectPolicy::ping_interval`], and
+//! reconnects with exponential backoff when the child process exits or the
+//! HTTP endpoint stops answering. While the connection is down, calls fail
+//! immediately with [`McpError::Unavailable`](crate::protocol::McpError::Unavailable)
+//! instead of queueing behind the reconnect.
+
+use std::fmt;
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::time::Duration;
+
+/// Health of a supervised MCP connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The server is answering requests.
+    Connected,
+    /// The connection was lost and a reconnect is in progress.
+    Reconnecting {
+        /// 1-based attempt number.
+        attempt: u32,
+    },
+    /// The connection is down and reconnect attempts have been exhausted.
+    ///
+    /// Another round of attempts starts at the next health check.
+    Disconnected {
+        /// Why the last attempt failed.
+        reason: String,
+    },
+}
+
+impl ConnectionState {
+    /// Returns `true` if requests can be sent.
+    #[must_use]
+    pub const fn is_connected(&self) -> bool {
+        matches!(self, Self::Connected)
+    }
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connected => f.write_str("connected"),
+            Self::Reconnecting { attempt } => write!(f, "reconnecting (attempt {attempt})"),
+            Self::Disconnected { reason } => write!(f, "disconnected: {reason}"),
+        }
+    }
+}
+
+/// Health check and reconnect settings for [`McpToolService`](super::McpToolService).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Idle time after which the server is pinged.
+    pub ping_interval: Duration,
+    /// How long to wait for a ping response before treating the server as dead.
+    pub ping_timeout: Duration,
+    /// Delay before the first reconnect attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between attempts.
+    pub max_backoff: Duration,
+    /// Reconnect attempts per outage; `0` disables reconnecting.
+    pub max_attempts: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(30),
+            ping_timeout: Duration::from_secs(10),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: 5,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Health checks only: a lost connection stays disconnected.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Set the idle time between pings.
+    #[must_use]
+    pub const fn ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = interval;
+        self
+    }
+
+    /// Set how long to wait for a ping response.
+    #[must_use]
+    pub const fn ping_timeout(mut self, timeout: Duration) -> Self {
+        self.ping_timeout = timeout;
+        self
+    }
+
+    /// Set the initial and maximum delay between reconnect attempts.
+    #[must_use]
+    pub const fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the number of reconnect attempts per outage.
+    #[must_use]
+    pub const fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Delay before the given 1-based attempt, doubling from
+    /// [`initial_backoff`](Self::initial_backoff) up to [`max_backoff`](Self::max_backoff).
+    ///
+    /// The delay is randomly shortened by up to half, so clients that lost the
+    /// same server don't all reconnect at once.
+    #[must_use]
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        let half = delay / 2;
+        let spread = u64::try_from(half.as_nanos()).unwrap_or(u64::MAX);
+        half + Duration::from_nanos(
+            RandomState::new().build_hasher().finish() % spread.saturating_add(1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy::default().backoff(Duration::from_secs(1), Duration::from_secs(60))
+    }
+
+    /// Asserts `delay` lies within the jitter range of the un-jittered `full` delay.
+    fn assert_jittered(delay: Duration, full: Duration) {
+        assert!(
+            delay >= full / 2 && delay <= full,
+            "{delay:?} outside {:?}..={full:?}",
+            full / 2
+        );
+    }
+
+    #[test]
+    fn delay_doubles_with_each_attempt() {
+        let policy = policy();
+        for (attempt, secs) in [(1, 1), (2, 2), (3, 4), (4, 8), (5, 16)] {
+            assert_jittered(policy.delay_for(attempt), Duration::from_secs(secs));
+        }
+    }
+
+    #[test]
+    fn delay_is_capped_at_max_backoff() {
+        let policy = policy();
+        for attempt in [7, 32, 33, u32::MAX] {
+            assert_jittered(policy.delay_for(attempt), Duration::from_secs(60));
+        }
+    }
+
+    #[test]
+    fn jitter_stays_in_bounds_and_varies() {
+        let policy = policy();
+        let delays: Vec<Duration> = (0..200).map(|_| policy.delay_for(3)).collect();
+        for delay in &delays {
+            assert_jittered(*delay, Duration::from_secs(4));
+        }
+        assert!(delays.iter().any(|delay| *delay != delays[0]));
+    }
+
+    #[test]
+    fn zero_backoff_has_no_delay() {
+        let policy = ReconnectPolicy::default().backoff(Duration::ZERO, Duration::ZERO);
+        assert_eq!(policy.delay_for(1), Duration::ZERO);
+    }
+}
@@ -4,7 +4,11 @@
 //! list and call tools, read resources, etc.
 
 mod client;
+mod health;
 mod toolset;
+mod watch;
 
 pub use client::McpClient;
+pub use health::{ConnectionState, ReconnectPolicy};
 pub use toolset::{McpConnection, McpServerConfig, McpServersConfig, McpToolService};
+pub use watch::{McpConfigEvent, McpConfigWatcher};
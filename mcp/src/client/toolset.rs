@@ -5,15 +5,21 @@
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 
 use aither_core::llm::tool::ToolDefinition;
 use async_channel::{Receiver, Sender};
+use async_io::Timer;
+use futures_lite::future;
 use serde::Deserialize;
 
-use crate::protocol::{CallToolResult, McpError, McpToolDefinition};
+use crate::protocol::{
+    CallToolResult, GetPromptResult, McpError, McpToolDefinition, Prompt, Resource,
+    ResourceContents, ServerCapabilities,
+};
 use crate::transport::{ChildProcessTransport, HttpTransport, StdioTransport};
 
-use super::McpClient;
+use super::{ConnectionState, McpClient, ReconnectPolicy};
 
 /// Configuration for a single MCP server.
 ///
@@ -27,7 +33,7 @@ use super::McpClient;
 ///   "args": ["-y", "@modelcontextprotocol/server-filesystem", "/path"]
 /// }
 /// ```
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct McpServerConfig {
     /// The command to run (for process-based servers).
     pub command: Option<String>,
@@ -68,6 +74,9 @@ pub type McpServersConfig = HashMap<String, McpServerConfig>;
 /// transport abstraction from users. Use the constructor methods
 /// ([`spawn`](Self::spawn), [`http`](Self::http), [`stdio`](Self::stdio))
 /// to create connections.
+///
+/// Process and HTTP connections remember how they were opened so they can
+/// be re-established with [`reconnect`](Self::reconnect).
 #[non_exhaustive]
 #[allow(missing_docs)]
 pub enum McpConnection {
@@ -76,12 +85,17 @@ pub enum McpConnection {
         client: McpClient<ChildProcessTransport>,
         tools: Vec<McpToolDefinition>,
         server_name: Option<String>,
+        program: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
     },
     /// Connection via HTTP.
     Http {
         client: McpClient<HttpTransport>,
         tools: Vec<McpToolDefinition>,
         server_name: Option<String>,
+        url: String,
+        auth: Option<String>,
     },
     /// Connection via stdio (for use as a subprocess).
     Stdio {
@@ -92,10 +106,53 @@ pub enum McpConnection {
 }
 
 /// Service wrapper that serializes MCP tool calls through a command channel.
+///
+/// The background worker also supervises the connection: see
+/// [`ReconnectPolicy`] for how health checks and reconnects are scheduled.
+/// Tool definitions are refreshed and resource subscriptions re-established
+/// after every successful reconnect.
 #[derive(Clone, Debug)]
 pub struct McpToolService {
     tx: Sender<McpCommand>,
-    tools: Vec<McpToolDefinition>,
+    server_name: Option<String>,
+    supervision: Arc<Supervision>,
+}
+
+/// State shared between a [`McpToolService`] and its worker thread.
+#[derive(Debug)]
+struct Supervision {
+    state: Mutex<ConnectionState>,
+    tools: RwLock<Vec<McpToolDefinition>>,
+    watchers: Mutex<Vec<Sender<ConnectionState>>>,
+}
+
+impl Supervision {
+    fn state(&self) -> ConnectionState {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn set_state(&self, state: &ConnectionState) {
+        {
+            let mut current = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            if *current == *state {
+                return;
+            }
+            current.clone_from(state);
+        }
+        let mut watchers = self.watchers.lock().unwrap_or_else(PoisonError::into_inner);
+        watchers.retain(|watcher| watcher.try_send(state.clone()).is_ok());
+    }
+
+    fn tools(&self) -> std::sync::RwLockReadGuard<'_, Vec<McpToolDefinition>> {
+        self.tools.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn set_tools(&self, tools: Vec<McpToolDefinition>) {
+        *self.tools.write().unwrap_or_else(PoisonError::into_inner) = tools;
+    }
 }
 
 #[derive(Debug)]
@@ -105,6 +162,46 @@ enum McpCommand {
         arguments: serde_json::Value,
         reply: Sender<Result<CallToolResult, McpError>>,
     },
+    ListResources {
+        reply: Sender<Result<Vec<Resource>, McpError>>,
+    },
+    ReadResource {
+        uri: String,
+        reply: Sender<Result<Vec<ResourceContents>, McpError>>,
+    },
+    Subscribe {
+        uri: String,
+        subscribe: bool,
+        reply: Sender<Result<(), McpError>>,
+    },
+    ResourceUpdates {
+        reply: Sender<Vec<String>>,
+    },
+    ListPrompts {
+        reply: Sender<Result<Vec<Prompt>, McpError>>,
+    },
+    GetPrompt {
+        name: String,
+        arguments: HashMap<String, String>,
+        reply: Sender<Result<GetPromptResult, McpError>>,
+    },
+}
+
+/// Runs `$body` with `$client` bound to the connection's client, whatever its transport.
+macro_rules! with_client {
+    ($conn:expr, $client:ident => $body:expr) => {
+        match $conn {
+            McpConnection::Process {
+                client: $client, ..
+            } => $body,
+            McpConnection::Http {
+                client: $client, ..
+            } => $body,
+            McpConnection::Stdio {
+                client: $client, ..
+            } => $body,
+        }
+    };
 }
 
 impl std::fmt::Debug for McpConnection {
@@ -164,7 +261,7 @@ impl McpConnection {
                 .iter()
                 .map(std::string::String::as_str)
                 .collect();
-            Self::spawn(command, &args).await
+            Self::spawn_with_env(command, &args, &config.env).await
         } else {
             Err(McpError::InvalidConfig(
                 "Config must have either 'command' or 'url'".to_string(),
@@ -218,7 +315,21 @@ impl McpConnection {
     ///
     /// Returns an error if the process cannot be spawned or connection fails.
     pub async fn spawn(program: &str, args: &[&str]) -> Result<Self, McpError> {
-        let transport = ChildProcessTransport::spawn(program, args)?;
+        Self::spawn_with_env(program, args, &HashMap::new()).await
+    }
+
+    /// Connect to an MCP server via a spawned child process, adding `env` to
+    /// the environment it inherits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process cannot be spawned or connection fails.
+    pub async fn spawn_with_env(
+        program: &str,
+        args: &[&str],
+        env: &HashMap<String, String>,
+    ) -> Result<Self, McpError> {
+        let transport = ChildProcessTransport::spawn_with_env(program, args, env)?;
         let mut client = McpClient::connect(transport).await?;
         let tools = client.list_tools().await?;
         let server_name = client.server_info().map(|i| i.name.clone());
@@ -227,6 +338,9 @@ impl McpConnection {
             client,
             tools,
             server_name,
+            program: program.to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+            env: env.clone(),
         })
     }
 
@@ -251,6 +365,8 @@ impl McpConnection {
             client,
             tools,
             server_name,
+            url: url.to_string(),
+            auth: None,
         })
     }
 
@@ -269,6 +385,8 @@ impl McpConnection {
             client,
             tools,
             server_name,
+            url: url.to_string(),
+            auth: Some(auth.to_string()),
         })
     }
 
@@ -347,6 +465,136 @@ impl McpConnection {
         }
     }
 
+    /// Returns the capabilities the server advertised.
+    #[must_use]
+    pub const fn capabilities(&self) -> &ServerCapabilities {
+        with_client!(self, client => client.capabilities())
+    }
+
+    /// List resources exposed by this server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or resources are not supported.
+    pub async fn list_resources(&mut self) -> Result<Vec<Resource>, McpError> {
+        with_client!(self, client => client.list_resources().await)
+    }
+
+    /// Read every content part of a resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn read_resource(&mut self, uri: &str) -> Result<Vec<ResourceContents>, McpError> {
+        with_client!(self, client => client.read_resource_contents(uri).await)
+    }
+
+    /// Subscribe to change notifications for a resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not support subscriptions or the request fails.
+    pub async fn subscribe_resource(&mut self, uri: &str) -> Result<(), McpError> {
+        with_client!(self, client => client.subscribe_resource(uri).await)
+    }
+
+    /// Cancel a resource subscription.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not support subscriptions or the request fails.
+    pub async fn unsubscribe_resource(&mut self, uri: &str) -> Result<(), McpError> {
+        with_client!(self, client => client.unsubscribe_resource(uri).await)
+    }
+
+    /// Take the URIs of subscribed resources that changed since the last call.
+    pub fn take_resource_updates(&mut self) -> Vec<String> {
+        with_client!(self, client => client.take_resource_updates())
+    }
+
+    /// List prompt templates exposed by this server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or prompts are not supported.
+    pub async fn list_prompts(&mut self) -> Result<Vec<Prompt>, McpError> {
+        with_client!(self, client => client.list_prompts().await)
+    }
+
+    /// Expand a prompt template.
+    ///
+    /// Use [`GetPromptResult::to_request`] to turn the result into a model request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult, McpError> {
+        with_client!(self, client => client.get_prompt(name, arguments).await)
+    }
+
+    /// Send a `ping` request to check that the server is responsive.
+    ///
+    /// Fails with [`McpError::ConnectionClosed`] without a round trip if the
+    /// transport already knows the server is gone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not answer.
+    pub async fn ping(&mut self) -> Result<(), McpError> {
+        if !self.is_alive() {
+            return Err(McpError::ConnectionClosed);
+        }
+        with_client!(self, client => client.ping().await)
+    }
+
+    /// Check locally whether the server is still reachable (for example,
+    /// whether the child process is still running).
+    pub fn is_alive(&mut self) -> bool {
+        with_client!(self, client => client.is_alive())
+    }
+
+    /// Returns `true` if [`reconnect`](Self::reconnect) is supported.
+    ///
+    /// Stdio connections are bound to this process's own streams and cannot
+    /// be re-opened.
+    #[must_use]
+    pub const fn can_reconnect(&self) -> bool {
+        !matches!(self, Self::Stdio { .. })
+    }
+
+    /// Close the connection and open it again the way it was first opened,
+    /// re-running the handshake and re-listing tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection cannot be re-established or is a
+    /// stdio connection.
+    pub async fn reconnect(&mut self) -> Result<(), McpError> {
+        if !self.can_reconnect() {
+            return Err(McpError::Unsupported("reconnecting stdio connections"));
+        }
+        let _ = self.close().await;
+        let fresh = match self {
+            Self::Process {
+                program, args, env, ..
+            } => {
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                Self::spawn_with_env(program, &args, env).await?
+            }
+            Self::Http { url, auth, .. } => match auth {
+                Some(auth) => Self::http_with_auth(url, auth).await?,
+                None => Self::http(url).await?,
+            },
+            Self::Stdio { .. } => unreachable!("checked by can_reconnect"),
+        };
+        *self = fresh;
+        Ok(())
+    }
+
     /// Close the connection.
     ///
     /// # Errors
@@ -362,25 +610,71 @@ impl McpConnection {
 }
 
 impl McpToolService {
-    /// Creates a new MCP tool service and starts its background worker.
+    /// Creates a new MCP tool service and starts its background worker,
+    /// supervised with the default [`ReconnectPolicy`].
+    #[must_use]
+    pub fn new(conn: McpConnection) -> Self {
+        Self::with_policy(conn, ReconnectPolicy::default())
+    }
+
+    /// Creates a new MCP tool service with a custom health check and
+    /// reconnect policy.
+    #[must_use]
+    pub fn with_policy(mut conn: McpConnection, policy: ReconnectPolicy) -> Self {
+        let supervision = Arc::new(Supervision {
+            state: Mutex::new(ConnectionState::Connected),
+            tools: RwLock::new(conn.mcp_definitions().to_vec()),
+            watchers: Mutex::new(Vec::new()),
+        });
+        let server_name = conn.server_name().map(ToString::to_string);
+        let (tx, rx) = async_channel::unbounded();
+        let worker = Arc::clone(&supervision);
+        std::thread::spawn(move || run_service(&rx, &mut conn, &worker, policy));
+        Self {
+            tx,
+            server_name,
+            supervision,
+        }
+    }
+
+    /// Returns the server name if available.
+    #[must_use]
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// Returns the current connection state.
+    #[must_use]
+    pub fn state(&self) -> ConnectionState {
+        self.supervision.state()
+    }
+
+    /// Subscribe to connection state changes.
+    ///
+    /// Each change is delivered once to every subscriber; dropped receivers
+    /// are pruned automatically.
     #[must_use]
-    pub fn new(mut conn: McpConnection) -> Self {
-        let tools = conn.mcp_definitions().to_vec();
+    pub fn watch_state(&self) -> Receiver<ConnectionState> {
         let (tx, rx) = async_channel::unbounded();
-        std::thread::spawn(move || run_service(rx, &mut conn));
-        Self { tx, tools }
+        self.supervision
+            .watchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tx);
+        rx
     }
 
-    /// Returns the MCP tool definitions.
+    /// Returns the MCP tool definitions, as of the latest (re)connect.
     #[must_use]
-    pub fn mcp_definitions(&self) -> &[McpToolDefinition] {
-        &self.tools
+    pub fn mcp_definitions(&self) -> Vec<McpToolDefinition> {
+        self.supervision.tools().clone()
     }
 
     /// Returns aither-compatible tool definitions.
     #[must_use]
     pub fn definitions(&self) -> Vec<ToolDefinition> {
-        self.tools
+        self.supervision
+            .tools()
             .iter()
             .map(|def| {
                 let name: Cow<'static, str> = Cow::Owned(def.name.clone());
@@ -394,48 +688,322 @@ impl McpToolService {
     /// Check if this service has a tool with the given name.
     #[must_use]
     pub fn has_tool(&self, name: &str) -> bool {
-        self.tools.iter().any(|d| d.name == name)
+        self.supervision.tools().iter().any(|d| d.name == name)
     }
 
     /// Call a tool on this MCP service.
     ///
     /// # Errors
     ///
-    /// Returns an error if the tool call fails.
+    /// Returns an error if the tool call fails, or [`McpError::Unavailable`]
+    /// immediately if the connection is down.
     pub async fn call(
         &self,
         name: &str,
         arguments: serde_json::Value,
     ) -> Result<CallToolResult, McpError> {
+        let name = name.to_string();
+        self.request(|reply| McpCommand::Call {
+            name,
+            arguments,
+            reply,
+        })
+        .await?
+    }
+
+    /// List resources exposed by the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or resources are not supported.
+    pub async fn list_resources(&self) -> Result<Vec<Resource>, McpError> {
+        self.request(|reply| McpCommand::ListResources { reply })
+            .await?
+    }
+
+    /// Read every content part of a resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn read_resource(&self, uri: &str) -> Result<Vec<ResourceContents>, McpError> {
+        let uri = uri.to_string();
+        self.request(|reply| McpCommand::ReadResource { uri, reply })
+            .await?
+    }
+
+    /// Subscribe to change notifications for a resource.
+    ///
+    /// The subscription is re-established automatically after a reconnect.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not support subscriptions or the request fails.
+    pub async fn subscribe_resource(&self, uri: &str) -> Result<(), McpError> {
+        let uri = uri.to_string();
+        self.request(|reply| McpCommand::Subscribe {
+            uri,
+            subscribe: true,
+            reply,
+        })
+        .await?
+    }
+
+    /// Cancel a resource subscription.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not support subscriptions or the request fails.
+    pub async fn unsubscribe_resource(&self, uri: &str) -> Result<(), McpError> {
+        let uri = uri.to_string();
+        self.request(|reply| McpCommand::Subscribe {
+            uri,
+            subscribe: false,
+            reply,
+        })
+        .await?
+    }
+
+    /// Take the URIs of subscribed resources that changed since the last call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the service has stopped.
+    pub async fn take_resource_updates(&self) -> Result<Vec<String>, McpError> {
+        self.request(|reply| McpCommand::ResourceUpdates { reply })
+            .await
+    }
+
+    /// List prompt templates exposed by the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or prompts are not supported.
+    pub async fn list_prompts(&self) -> Result<Vec<Prompt>, McpError> {
+        self.request(|reply| McpCommand::ListPrompts { reply })
+            .await?
+    }
+
+    /// Expand a prompt template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult, McpError> {
+        let name = name.to_string();
+        self.request(|reply| McpCommand::GetPrompt {
+            name,
+            arguments,
+            reply,
+        })
+        .await?
+    }
+
+    async fn request<R>(
+        &self,
+        command: impl FnOnce(Sender<R>) -> McpCommand,
+    ) -> Result<R, McpError> {
+        let state = self.state();
+        if !state.is_connected() {
+            let server = self.server_name.as_deref().unwrap_or("unnamed");
+            return Err(McpError::Unavailable(format!("'{server}' is {state}")));
+        }
         let (reply_tx, reply_rx) = async_channel::bounded(1);
         self.tx
-            .send(McpCommand::Call {
-                name: name.to_string(),
-                arguments,
-                reply: reply_tx,
-            })
+            .send(command(reply_tx))
             .await
             .map_err(|_| McpError::ConnectionClosed)?;
         reply_rx
             .recv()
             .await
-            .map_err(|_| McpError::ConnectionClosed)?
+            .map_err(|_| McpError::ConnectionClosed)
     }
 }
 
-fn run_service(rx: Receiver<McpCommand>, conn: &mut McpConnection) {
+/// What woke the worker up.
+enum Event {
+    Command(McpCommand),
+    HealthCheck,
+    Stopped,
+}
+
+fn run_service(
+    rx: &Receiver<McpCommand>,
+    conn: &mut McpConnection,
+    supervision: &Supervision,
+    policy: ReconnectPolicy,
+) {
     async_io::block_on(async {
-        while let Ok(cmd) = rx.recv().await {
-            match cmd {
-                McpCommand::Call {
-                    name,
-                    arguments,
-                    reply,
-                } => {
-                    let result = conn.call(&name, arguments).await;
-                    let _ = reply.send(result).await;
-                }
+        // Resources subscribed through the service, replayed after a reconnect.
+        let mut subscriptions: Vec<String> = Vec::new();
+        loop {
+            let event = future::or(
+                async { rx.recv().await.map_or(Event::Stopped, Event::Command) },
+                async {
+                    Timer::after(policy.ping_interval).await;
+                    Event::HealthCheck
+                },
+            )
+            .await;
+
+            let lost = match event {
+                Event::Stopped => break,
+                Event::HealthCheck => match supervision.state() {
+                    ConnectionState::Connected => check_health(conn, policy).await,
+                    // Start another round of attempts after an exhausted one.
+                    ConnectionState::Disconnected { reason }
+                        if conn.can_reconnect() && policy.max_attempts > 0 =>
+                    {
+                        Some(reason)
+                    }
+                    _ => None,
+                },
+                Event::Command(cmd) => handle_command(conn, &mut subscriptions, cmd).await,
+            };
+
+            if let Some(reason) = lost {
+                recover(conn, supervision, policy, &subscriptions, reason).await;
             }
         }
+        // Every service handle is gone; don't leave child processes behind.
+        let _ = conn.close().await;
     });
 }
+
+/// Ping the server, returning why the connection is considered lost, if it is.
+async fn check_health(conn: &mut McpConnection, policy: ReconnectPolicy) -> Option<String> {
+    let result = future::or(conn.ping(), async {
+        Timer::after(policy.ping_timeout).await;
+        Err(McpError::Timeout)
+    })
+    .await;
+    connection_lost(&result)
+}
+
+/// Reconnect with exponential backoff, publishing each state change.
+async fn recover(
+    conn: &mut McpConnection,
+    supervision: &Supervision,
+    policy: ReconnectPolicy,
+    subscriptions: &[String],
+    mut reason: String,
+) {
+    let server = conn.server_name().unwrap_or("unnamed").to_string();
+    tracing::warn!("MCP server '{server}' connection lost: {reason}");
+
+    if conn.can_reconnect() {
+        for attempt in 1..=policy.max_attempts {
+            supervision.set_state(&ConnectionState::Reconnecting { attempt });
+            Timer::after(policy.delay_for(attempt)).await;
+            match conn.reconnect().await {
+                Ok(()) => {
+                    tracing::info!("Reconnected to MCP server '{server}'");
+                    resubscribe(conn, subscriptions).await;
+                    supervision.set_tools(conn.mcp_definitions().to_vec());
+                    supervision.set_state(&ConnectionState::Connected);
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Reconnect attempt {attempt} to MCP server '{server}' failed: {e}"
+                    );
+                    reason = e.to_string();
+                }
+            }
+        }
+    }
+
+    supervision.set_state(&ConnectionState::Disconnected { reason });
+}
+
+/// Re-establish resource subscriptions on a fresh connection.
+async fn resubscribe(conn: &mut McpConnection, subscriptions: &[String]) {
+    for uri in subscriptions {
+        if let Err(e) = conn.subscribe_resource(uri).await {
+            tracing::warn!("Failed to resubscribe to MCP resource '{uri}': {e}");
+        }
+    }
+}
+
+fn connection_lost<T>(result: &Result<T, McpError>) -> Option<String> {
+    match result {
+        Err(e) if e.is_connection_error() => Some(e.to_string()),
+        _ => None,
+    }
+}
+
+/// Run one command, returning why the connection is considered lost, if it is.
+async fn handle_command(
+    conn: &mut McpConnection,
+    subscriptions: &mut Vec<String>,
+    cmd: McpCommand,
+) -> Option<String> {
+    match cmd {
+        McpCommand::Call {
+            name,
+            arguments,
+            reply,
+        } => {
+            let result = conn.call(&name, arguments).await;
+            let lost = connection_lost(&result);
+            let _ = reply.send(result).await;
+            lost
+        }
+        McpCommand::ListResources { reply } => {
+            let result = conn.list_resources().await;
+            let lost = connection_lost(&result);
+            let _ = reply.send(result).await;
+            lost
+        }
+        McpCommand::ReadResource { uri, reply } => {
+            let result = conn.read_resource(&uri).await;
+            let lost = connection_lost(&result);
+            let _ = reply.send(result).await;
+            lost
+        }
+        McpCommand::Subscribe {
+            uri,
+            subscribe,
+            reply,
+        } => {
+            let result = if subscribe {
+                conn.subscribe_resource(&uri).await
+            } else {
+                conn.unsubscribe_resource(&uri).await
+            };
+            if result.is_ok() {
+                subscriptions.retain(|subscribed| *subscribed != uri);
+                if subscribe {
+                    subscriptions.push(uri);
+                }
+            }
+            let lost = connection_lost(&result);
+            let _ = reply.send(result).await;
+            lost
+        }
+        McpCommand::ResourceUpdates { reply } => {
+            let _ = reply.send(conn.take_resource_updates()).await;
+            None
+        }
+        McpCommand::ListPrompts { reply } => {
+            let result = conn.list_prompts().await;
+            let lost = connection_lost(&result);
+            let _ = reply.send(result).await;
+            lost
+        }
+        McpCommand::GetPrompt {
+            name,
+            arguments,
+            reply,
+        } => {
+            let result = conn.get_prompt(&name, arguments).await;
+            let lost = connection_lost(&result);
+            let _ = reply.send(result).await;
+            lost
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! Hot reloading of MCP server configuration files.
+//!
+//! [`McpConnection::watch_config`] polls a config file and turns every edit
+//! into [`McpConfigEvent`]s: servers that appear are connected, servers whose
+//! entry changed are reconnected, and servers that disappear are reported as
+//! removed. The owner of the connections (usually the agent's tool registry)
+//! applies the events to keep its tool list current mid-session.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use async_channel::{Receiver, Sender};
+use async_io::Timer;
+
+use super::{McpConnection, McpServersConfig};
+use crate::protocol::McpError;
+
+/// How often the config file's modification time is checked.
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A change to the set of configured MCP servers.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum McpConfigEvent {
+    /// A new server entry was connected.
+    Connected {
+        /// Key of the server in the config file.
+        name: String,
+        /// The new connection.
+        connection: McpConnection,
+    },
+    /// An existing server entry changed and was reconnected with the new settings.
+    ///
+    /// The previous connection for `name` should be replaced.
+    Reconfigured {
+        /// Key of the server in the config file.
+        name: String,
+        /// The replacement connection.
+        connection: McpConnection,
+    },
+    /// A server entry was removed from the config file.
+    Removed {
+        /// Key of the server in the config file.
+        name: String,
+    },
+    /// Connecting a new or changed server entry failed.
+    ///
+    /// Any previous connection for `name` is left in place; the entry is
+    /// retried the next time the file changes.
+    Failed {
+        /// Key of the server in the config file.
+        name: String,
+        /// Why the connection failed.
+        error: McpError,
+    },
+    /// The config file could not be read or parsed; nothing was changed.
+    InvalidConfig(McpError),
+}
+
+/// Stream of [`McpConfigEvent`]s for a watched config file.
+///
+/// Watching stops when the watcher is dropped.
+#[derive(Debug)]
+pub struct McpConfigWatcher {
+    path: PathBuf,
+    events: Receiver<McpConfigEvent>,
+}
+
+impl McpConfigWatcher {
+    /// Returns the watched config file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Wait for the next event.
+    ///
+    /// Returns `None` if the watcher thread has stopped.
+    pub async fn next(&self) -> Option<McpConfigEvent> {
+        self.events.recv().await.ok()
+    }
+
+    /// Take the next event if one is ready, without waiting.
+    #[must_use]
+    pub fn try_next(&self) -> Option<McpConfigEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+impl McpConnection {
+    /// Watch a Claude-Desktop-format config file and connect, reconnect, or
+    /// drop servers as it changes.
+    ///
+    /// Both the bare server map and the `{"mcpServers": {...}}` wrapper are
+    /// accepted. The servers present when watching starts are reported as
+    /// [`McpConfigEvent::Connected`], so the watcher can be the only source
+    /// of connections for that file.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let watcher = McpConnection::watch_config("mcp.json");
+    /// while let Some(event) = watcher.next().await {
+    ///     match event {
+    ///         McpConfigEvent::Connected { name, connection } => { /* register */ }
+    ///         McpConfigEvent::Removed { name } => { /* unregister */ }
+    ///         other => println!("{other:?}"),
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn watch_config(path: impl Into<PathBuf>) -> McpConfigWatcher {
+        let path = path.into();
+        let (tx, events) = async_channel::unbounded();
+        let watched = path.clone();
+        std::thread::spawn(move || async_io::block_on(watch(&watched, &tx)));
+        McpConfigWatcher { path, events }
+    }
+}
+
+/// Parse a config file in either the bare or `mcpServers`-wrapped format.
+fn parse_config(json: &str) -> Result<McpServersConfig, McpError> {
+    let mut value: serde_json::Value = serde_json::from_str(json)?;
+    if let Some(servers) = value.get_mut("mcpServers") {
+        value = servers.take();
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+async fn watch(path: &Path, tx: &Sender<McpConfigEvent>) {
+    let mut current = McpServersConfig::new();
+    let mut last_modified = None;
+    let mut first = true;
+
+    while !tx.is_closed() {
+        let modified = modified(path);
+        if first || modified != last_modified {
+            first = false;
+            last_modified = modified;
+            let loaded = std::fs::read_to_string(path)
+                .map_err(McpError::from)
+                .and_then(|json| parse_config(&json));
+            match loaded {
+                Ok(next) => current = apply(&current, next, tx).await,
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid MCP config {}: {e}", path.display());
+                    let _ = tx.send(McpConfigEvent::InvalidConfig(e)).await;
+                }
+            }
+        }
+        Timer::after(CONFIG_POLL_INTERVAL).await;
+    }
+}
+
+/// Emit the events that turn `current` into `next`, returning the config that
+/// is actually in effect (entries that failed to connect keep their old value).
+async fn apply(
+    current: &McpServersConfig,
+    next: McpServersConfig,
+    tx: &Sender<McpConfigEvent>,
+) -> McpServersConfig {
+    let mut removed: Vec<&String> = current
+        .keys()
+        .filter(|name| !next.contains_key(*name))
+        .collect();
+    removed.sort();
+    for name in removed {
+        tracing::info!("MCP server removed from config: {name}");
+        let _ = tx
+            .send(McpConfigEvent::Removed { name: name.clone() })
+            .await;
+    }
+
+    let mut changed: Vec<String> = next
+        .iter()
+        .filter(|(name, config)| current.get(*name) != Some(*config))
+        .map(|(name, _)| name.clone())
+        .collect();
+    changed.sort();
+
+    let mut effective = next;
+    for name in changed {
+        let existed = current.contains_key(&name);
+        let event = match McpConnection::from_config(&effective[&name]).await {
+            Ok(connection) if existed => {
+                tracing::info!("Reconnected reconfigured MCP server: {name}");
+                McpConfigEvent::Reconfigured { name, connection }
+            }
+            Ok(connection) => {
+                tracing::info!("Connected to MCP server: {name}");
+                McpConfigEvent::Connected { name, connection }
+            }
+            Err(error) => {
+                tracing::warn!("Failed to connect to MCP server '{name}': {error}");
+                match current.get(&name) {
+                    Some(old) => effective.insert(name.clone(), old.clone()),
+                    None => effective.remove(&name),
+                };
+                McpConfigEvent::Failed { name, error }
+            }
+        };
+        let _ = tx.send(event).await;
+    }
+    effective
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures_lite::future::block_on;
+
+    use super::*;
+    use crate::client::McpServerConfig;
+
+    /// A server entry whose command doesn't exist, so connecting fails fast.
+    fn missing_server(env: &[(&str, &str)]) -> McpServerConfig {
+        McpServerConfig {
+            command: Some("aither-mcp-test-no-such-server".to_string()),
+            args: Vec::new(),
+            url: None,
+            env: env
+                .iter()
+                .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+                .collect(),
+        }
+    }
+
+    fn config(entries: &[(&str, McpServerConfig)]) -> McpServersConfig {
+        entries
+            .iter()
+            .map(|(name, config)| ((*name).to_string(), config.clone()))
+            .collect()
+    }
+
+    fn run(
+        current: &McpServersConfig,
+        next: McpServersConfig,
+    ) -> (McpServersConfig, Vec<McpConfigEvent>) {
+        let (tx, rx) = async_channel::unbounded();
+        let effective = block_on(apply(current, next, &tx));
+        drop(tx);
+        let events = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        (effective, events)
+    }
+
+    #[test]
+    fn parses_bare_and_wrapped_configs() {
+        let bare = r#"{"fs": {"command": "npx", "args": ["server"], "env": {"TOKEN": "x"}}}"#;
+        let wrapped = format!(r#"{{"mcpServers": {bare}}}"#);
+
+        let expected = McpServerConfig {
+            command: Some("npx".to_string()),
+            args: vec!["server".to_string()],
+            url: None,
+            env: HashMap::from([("TOKEN".to_string(), "x".to_string())]),
+        };
+        assert_eq!(
+            parse_config(bare).unwrap(),
+            config(&[("fs", expected.clone())])
+        );
+        assert_eq!(parse_config(&wrapped).unwrap(), config(&[("fs", expected)]));
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        assert!(parse_config("{not json").is_err());
+        assert!(parse_config(r#"{"mcpServers": {"fs": {"args": 3}}}"#).is_err());
+    }
+
+    #[test]
+    fn unchanged_config_emits_nothing() {
+        let current = config(&[("fs", missing_server(&[]))]);
+        let (effective, events) = run(&current, current.clone());
+        assert!(events.is_empty());
+        assert_eq!(effective, current);
+    }
+
+    #[test]
+    fn removed_servers_are_reported_in_order() {
+        let current = config(&[("b", missing_server(&[])), ("a", missing_server(&[]))]);
+        let (effective, events) = run(&current, McpServersConfig::new());
+        let removed: Vec<_> = events
+            .iter()
+            .map(|event| match event {
+                McpConfigEvent::Removed { name } => name.as_str(),
+                other => panic!("unexpected event: {other:?}"),
+            })
+            .collect();
+        assert_eq!(removed, ["a", "b"]);
+        assert!(effective.is_empty());
+    }
+
+    #[test]
+    fn added_server_that_fails_is_left_out() {
+        let next = config(&[("fs", missing_server(&[]))]);
+        let (effective, events) = run(&McpServersConfig::new(), next);
+        assert!(matches!(&events[..], [McpConfigEvent::Failed { name, .. }] if name == "fs"));
+        assert!(effective.is_empty());
+    }
+
+    #[test]
+    fn reconfigured_server_that_fails_keeps_its_old_entry() {
+        let old = missing_server(&[("TOKEN", "old")]);
+        let current = config(&[("fs", old.clone())]);
+        let next = config(&[("fs", missing_server(&[("TOKEN", "new")]))]);
+        let (effective, events) = run(&current, next);
+        assert!(matches!(&events[..], [McpConfigEvent::Failed { name, .. }] if name == "fs"));
+        assert_eq!(effective, config(&[("fs", old)]));
+    }
+
+    #[test]
+    fn entry_without_command_or_url_fails() {
+        let entry = McpServerConfig {
+            command: None,
+            args: Vec::new(),
+            url: None,
+            env: HashMap::new(),
+        };
+        let (_, events) = run(&McpServersConfig::new(), config(&[("fs", entry)]));
+        assert!(matches!(
+            &events[..],
+            [McpConfigEvent::Failed {
+                error: McpError::InvalidConfig(_),
+                ..
+            }]
+        ));
+    }
+}
@@ -76,6 +76,9 @@
 //! let agent = builder.build();
 //! ```
 //!
+//! To pick up edits to the file while running, watch it instead; see
+//! [`McpConnection::watch_config`] and [`McpConfigEvent`].
+//!
 //! ### HTTP-based MCP Servers
 //!
 //! Connect to MCP servers over HTTP:
@@ -87,6 +90,52 @@
 //! let conn = McpConnection::http_with_auth("http://localhost:3000/mcp", "Bearer token").await?;
 //! ```
 //!
+//! ### Resources and Prompts
+//!
+//! Besides tools, servers can expose resources (readable documents) and prompt
+//! templates. Resources can be placed in the agent's context, and prompts expand
+//! into ready-to-send requests:
+//!
+//! ```ignore
+//! use std::collections::HashMap;
+//!
+//! for resource in conn.list_resources().await? {
+//!     println!("{} ({})", resource.name, resource.uri);
+//! }
+//! let contents = conn.read_resource("file:///repo/README.md").await?;
+//!
+//! // Get notified when a resource changes (if the server supports it)
+//! conn.subscribe_resource("file:///repo/README.md").await?;
+//! let changed: Vec<String> = conn.take_resource_updates();
+//!
+//! let prompt = conn
+//!     .get_prompt("code_review", HashMap::from([("code".into(), source)]))
+//!     .await?;
+//! let request = prompt.to_request();
+//! ```
+//!
+//! ### Health Checks and Reconnection
+//!
+//! [`McpToolService`] pings idle servers and reconnects with exponential backoff
+//! when a child process exits or an HTTP server stops answering. Tools are
+//! re-listed after every reconnect, and calls made while the server is down
+//! fail immediately with [`McpError::Unavailable`]:
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use aither_mcp::{McpToolService, ReconnectPolicy};
+//!
+//! let policy = ReconnectPolicy::default()
+//!     .ping_interval(Duration::from_secs(15))
+//!     .max_attempts(10);
+//! let service = McpToolService::with_policy(conn, policy);
+//!
+//! let states = service.watch_state();
+//! while let Ok(state) = states.recv().await {
+//!     println!("MCP server is {state}");
+//! }
+//! ```
+//!
 //! ## Exposing Tools as an MCP Server
 //!
 //! To expose aither tools as an MCP server (e.g., for Claude Desktop):
@@ -105,6 +154,20 @@
 //! server.run().await?;
 //! ```
 //!
+//! Tools can be added or removed while the server runs through an
+//! [`McpServerHandle`]; connected clients receive
+//! `notifications/tools/list_changed`. The handle also sends log messages,
+//! filtered by the level clients request with `logging/setLevel`, and tool
+//! calls that carry a progress token get periodic `notifications/progress`.
+//!
+//! ```ignore
+//! use aither_mcp::protocol::LoggingLevel;
+//!
+//! let handle = server.handle();
+//! handle.register(DeployTool::new())?;
+//! handle.log(LoggingLevel::Info, Some("deploy"), serde_json::json!("tool added"))?;
+//! ```
+//!
 //! ### Claude Desktop Integration
 //!
 //! To use your MCP server with Claude Desktop, add it to your Claude Desktop config
@@ -142,6 +205,9 @@ mod server;
 pub mod transport;
 
 // Re-export main types
-pub use client::{McpConnection, McpServerConfig, McpServersConfig, McpToolService};
+pub use client::{
+    ConnectionState, McpConfigEvent, McpConfigWatcher, McpConnection, McpServerConfig,
+    McpServersConfig, McpToolService, ReconnectPolicy,
+};
 pub use protocol::{CallToolResult, Content, McpError};
-pub use server::McpServer;
+pub use server::{McpServer, McpServerHandle};
@@ -140,4 +140,24 @@ pub enum McpError {
     /// Invalid configuration.
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
+
+    /// The server did not advertise the capability needed for a request.
+    #[error("Server does not support {0}")]
+    Unsupported(&'static str),
+
+    /// The server connection is down; the message describes its state.
+    #[error("MCP server unavailable: {0}")]
+    Unavailable(String),
+}
+
+impl McpError {
+    /// Returns `true` if the error means the connection itself is broken,
+    /// as opposed to the server rejecting a single request.
+    #[must_use]
+    pub const fn is_connection_error(&self) -> bool {
+        matches!(
+            self,
+            Self::Transport(_) | Self::Io(_) | Self::ConnectionClosed | Self::Timeout
+        )
+    }
 }
@@ -9,7 +9,10 @@ pub use message::{
     JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, RequestId,
 };
 pub use types::{
-    CallToolParams, CallToolResult, ClientCapabilities, Content, ImageContent, InitializeParams,
-    InitializeResult, ListToolsResult, McpToolDefinition, PROTOCOL_VERSION, PromptMessage,
-    Resource, ResourceContents, ServerCapabilities, ServerInfo, TextContent, ToolsCapability,
+    CallToolParams, CallToolResult, ClientCapabilities, Content, GetPromptParams, GetPromptResult,
+    ImageContent, InitializeParams, InitializeResult, ListPromptsResult, ListResourcesResult,
+    ListToolsResult, LoggingLevel, LoggingMessageParams, McpToolDefinition, PROTOCOL_VERSION,
+    PaginatedParams, ProgressParams, Prompt, PromptArgument, PromptMessage, ReadResourceResult,
+    RequestMeta, Resource, ResourceContents, ResourceUriParams, ServerCapabilities, ServerInfo,
+    SetLevelParams, TextContent, ToolsCapability,
 };
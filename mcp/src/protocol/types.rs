@@ -1,5 +1,10 @@
 //! MCP-specific protocol types.
 
+use std::collections::HashMap;
+
+use aither_core::llm::{LLMRequest, Message, ToolOutput};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -171,6 +176,78 @@ pub struct CallToolParams {
     /// Tool arguments.
     #[serde(default)]
     pub arguments: Value,
+    /// Request metadata, such as a progress token.
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RequestMeta>,
+}
+
+/// Metadata attached to a request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestMeta {
+    /// Token the server echoes in `notifications/progress` for this request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_token: Option<Value>,
+}
+
+/// Parameters of a `notifications/progress` notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressParams {
+    /// Token from the request being reported on.
+    pub progress_token: Value,
+    /// Progress so far; increases with every notification.
+    pub progress: f64,
+    /// Total amount of work, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+    /// Human-readable status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Severity of a log message, in increasing order (RFC 5424).
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LoggingLevel {
+    /// Debugging detail.
+    Debug,
+    /// Informational message.
+    #[default]
+    Info,
+    /// Normal but significant event.
+    Notice,
+    /// Warning condition.
+    Warning,
+    /// Error condition.
+    Error,
+    /// Critical condition.
+    Critical,
+    /// Action must be taken immediately.
+    Alert,
+    /// System is unusable.
+    Emergency,
+}
+
+/// Parameters of a `logging/setLevel` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetLevelParams {
+    /// Minimum level the client wants to receive.
+    pub level: LoggingLevel,
+}
+
+/// Parameters of a `notifications/message` log notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingMessageParams {
+    /// Severity of the message.
+    pub level: LoggingLevel,
+    /// Name of the component that logged the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logger: Option<String>,
+    /// Message payload; any JSON value.
+    pub data: Value,
 }
 
 /// Tool call result.
@@ -208,6 +285,70 @@ impl CallToolResult {
             is_error: true,
         }
     }
+
+    /// Joins the text parts of the result, including embedded text resources.
+    ///
+    /// Images and binary resources are replaced by short placeholders.
+    #[must_use]
+    pub fn text_content(&self) -> String {
+        self.content
+            .iter()
+            .map(Content::to_text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Converts each content item into a [`ToolOutput`], keeping images and
+    /// binary resources as binary outputs with their MIME type so they can be
+    /// forwarded to vision-capable models.
+    #[must_use]
+    pub fn into_tool_outputs(self) -> Vec<ToolOutput> {
+        self.content
+            .into_iter()
+            .map(Content::into_tool_output)
+            .collect()
+    }
+}
+
+impl Content {
+    /// Text representation of this item, with placeholders for binary data.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.text.clone(),
+            Self::Image(image) => format!("[image: {}]", image.mime_type),
+            Self::Resource(resource) => resource.resource.to_context_text(),
+        }
+    }
+
+    /// Converts this item into a [`ToolOutput`].
+    ///
+    /// Base64 payloads are decoded; items whose data cannot be decoded fall
+    /// back to their [text representation](Self::to_text).
+    #[must_use]
+    pub fn into_tool_output(self) -> ToolOutput {
+        let binary = match &self {
+            Self::Text(text) => return ToolOutput::text(text.text.clone()),
+            Self::Image(image) => Some((image.data.as_str(), image.mime_type.as_str())),
+            Self::Resource(ResourceContent { resource, .. }) => {
+                resource.blob.as_deref().map(|blob| {
+                    let mime = resource
+                        .mime_type
+                        .as_deref()
+                        .unwrap_or("application/octet-stream");
+                    (blob, mime)
+                })
+            }
+        };
+        match binary.map(|(data, mime)| (BASE64.decode(data), mime)) {
+            Some((Ok(bytes), mime)) => ToolOutput::image(bytes, mime),
+            Some((Err(e), _)) => {
+                tracing::warn!("Failed to decode MCP binary content: {e}");
+                ToolOutput::text(self.to_text())
+            }
+            None => ToolOutput::text(self.to_text()),
+        }
+    }
 }
 
 /// Content types in MCP responses.
@@ -272,6 +413,25 @@ pub struct EmbeddedResource {
     pub blob: Option<String>,
 }
 
+impl EmbeddedResource {
+    /// Returns the resource text, or a placeholder describing binary contents.
+    #[must_use]
+    pub fn to_context_text(&self) -> String {
+        match (&self.text, &self.blob) {
+            (Some(text), _) => text.clone(),
+            (None, Some(blob)) => format!(
+                "[binary resource {}: {}, {} base64 bytes]",
+                self.uri,
+                self.mime_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+                blob.len()
+            ),
+            (None, None) => format!("[resource: {}]", self.uri),
+        }
+    }
+}
+
 /// Content annotations.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Annotations {
@@ -316,6 +476,147 @@ pub struct ResourceContents {
     pub blob: Option<String>,
 }
 
+impl ResourceContents {
+    /// Renders the contents as text for inclusion in model context.
+    ///
+    /// Binary contents are summarized rather than inlined.
+    #[must_use]
+    pub fn to_context_text(&self) -> String {
+        match (&self.text, &self.blob) {
+            (Some(text), _) => text.clone(),
+            (None, Some(blob)) => format!(
+                "[binary resource: {}, {} base64 bytes]",
+                self.mime_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+                blob.len()
+            ),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// Pagination parameters for list requests.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaginatedParams {
+    /// Cursor returned by the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// List resources result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResourcesResult {
+    /// Available resources.
+    pub resources: Vec<Resource>,
+    /// Pagination cursor for next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Parameters naming a single resource, used by `resources/read`,
+/// `resources/subscribe`, `resources/unsubscribe`, and
+/// `notifications/resources/updated`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceUriParams {
+    /// Resource URI.
+    pub uri: String,
+}
+
+/// Read resource result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadResourceResult {
+    /// Resource contents (a URI may expand to several parts).
+    pub contents: Vec<ResourceContents>,
+}
+
+/// Prompt template exposed by a server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prompt {
+    /// Prompt name.
+    pub name: String,
+    /// Prompt description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Arguments the template accepts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgument>,
+}
+
+impl Prompt {
+    /// Names of arguments that must be supplied to `prompts/get`.
+    pub fn required_arguments(&self) -> impl Iterator<Item = &str> {
+        self.arguments
+            .iter()
+            .filter(|arg| arg.required)
+            .map(|arg| arg.name.as_str())
+    }
+}
+
+/// Argument accepted by a prompt template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgument {
+    /// Argument name.
+    pub name: String,
+    /// Argument description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument must be provided.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// List prompts result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPromptsResult {
+    /// Available prompts.
+    pub prompts: Vec<Prompt>,
+    /// Pagination cursor for next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Get prompt parameters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptParams {
+    /// Prompt name.
+    pub name: String,
+    /// Template arguments.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Get prompt result: the template expanded into messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPromptResult {
+    /// Prompt description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Expanded messages.
+    pub messages: Vec<PromptMessage>,
+}
+
+impl GetPromptResult {
+    /// Converts the expanded prompt into aither messages.
+    ///
+    /// Image content has no text form and is replaced by a placeholder.
+    #[must_use]
+    pub fn to_messages(&self) -> Vec<Message> {
+        self.messages
+            .iter()
+            .map(PromptMessage::to_message)
+            .collect()
+    }
+
+    /// Builds a request from the expanded prompt, ready to send to a model.
+    #[must_use]
+    pub fn to_request(&self) -> LLMRequest {
+        LLMRequest::new(self.to_messages())
+    }
+}
+
 /// Prompt message for prompt templates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PromptMessage {
@@ -324,3 +625,30 @@ pub struct PromptMessage {
     /// Message content.
     pub content: Content,
 }
+
+impl PromptMessage {
+    /// Converts this message into an aither message.
+    ///
+    /// Unknown roles are treated as user messages.
+    #[must_use]
+    pub fn to_message(&self) -> Message {
+        let text = match &self.content {
+            Content::Text(text) => text.text.clone(),
+            Content::Image(image) => format!("[image: {}]", image.mime_type),
+            Content::Resource(resource) => {
+                let resource = &resource.resource;
+                match &resource.text {
+                    Some(text) => {
+                        format!("<resource uri=\"{}\">\n{text}\n</resource>", resource.uri)
+                    }
+                    None => format!("[resource: {}]", resource.uri),
+                }
+            }
+        };
+        if self.role == "assistant" {
+            Message::assistant(text)
+        } else {
+            Message::user(text)
+        }
+    }
+}
@@ -4,4 +4,4 @@
 
 mod server;
 
-pub use server::McpServer;
+pub use server::{McpServer, McpServerHandle};
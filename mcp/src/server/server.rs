@@ -1,15 +1,104 @@
 //! MCP server that exposes aither tools.
 
-use aither_core::llm::tool::Tools;
+use std::pin::pin;
+use std::time::{Duration, Instant};
+
+use aither_core::llm::tool::{Tool, Tools};
+use async_channel::{Receiver, Sender};
+use async_io::Timer;
+use futures_lite::future;
 use tracing::debug;
 
 use crate::protocol::{
     CallToolParams, CallToolResult, InitializeParams, InitializeResult, JsonRpcError,
-    JsonRpcMessage, JsonRpcRequest, JsonRpcResponse, ListToolsResult, McpError, McpToolDefinition,
-    PROTOCOL_VERSION, ServerCapabilities, ServerInfo, TextContent, ToolsCapability,
+    JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, ListToolsResult,
+    LoggingLevel, LoggingMessageParams, McpError, McpToolDefinition, PROTOCOL_VERSION,
+    ProgressParams, ServerCapabilities, ServerInfo, SetLevelParams, TextContent, ToolsCapability,
 };
 use crate::transport::{BidirectionalTransport, StdioTransport};
 
+/// How often progress is reported for tool calls that carry a progress token.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A change requested through an [`McpServerHandle`].
+enum ServerCommand {
+    UpdateTools(Box<dyn FnOnce(&mut Tools) + Send>),
+    Log(LoggingMessageParams),
+}
+
+/// What woke the server loop up.
+enum Event {
+    Message(Result<Option<JsonRpcMessage>, McpError>),
+    Command(ServerCommand),
+}
+
+/// Handle for changing a running [`McpServer`].
+///
+/// Obtained from [`McpServer::handle`]. Cloning is cheap; changes are applied
+/// by the server loop between requests, and connected clients are notified.
+#[derive(Clone)]
+pub struct McpServerHandle {
+    tx: Sender<ServerCommand>,
+}
+
+impl std::fmt::Debug for McpServerHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpServerHandle").finish_non_exhaustive()
+    }
+}
+
+impl McpServerHandle {
+    /// Add a tool and emit `notifications/tools/list_changed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server has been dropped.
+    pub fn register<U: Tool + 'static>(&self, tool: U) -> Result<(), McpError> {
+        self.send(ServerCommand::UpdateTools(Box::new(move |tools| {
+            tools.register(tool);
+        })))
+    }
+
+    /// Remove a tool by name and emit `notifications/tools/list_changed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server has been dropped.
+    pub fn unregister(&self, name: impl Into<String>) -> Result<(), McpError> {
+        let name = name.into();
+        self.send(ServerCommand::UpdateTools(Box::new(move |tools| {
+            tools.unregister(&name);
+        })))
+    }
+
+    /// Send a log message to the client as `notifications/message`.
+    ///
+    /// Messages below the level the client chose with `logging/setLevel`
+    /// are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server has been dropped.
+    pub fn log(
+        &self,
+        level: LoggingLevel,
+        logger: Option<&str>,
+        data: serde_json::Value,
+    ) -> Result<(), McpError> {
+        self.send(ServerCommand::Log(LoggingMessageParams {
+            level,
+            logger: logger.map(ToString::to_string),
+            data,
+        }))
+    }
+
+    fn send(&self, command: ServerCommand) -> Result<(), McpError> {
+        self.tx
+            .try_send(command)
+            .map_err(|_| McpError::ConnectionClosed)
+    }
+}
+
 /// MCP server that exposes aither tools to external clients.
 ///
 /// # Example
@@ -22,6 +111,11 @@ use crate::transport::{BidirectionalTransport, StdioTransport};
 /// tools.register(my_tool);
 ///
 /// let mut server = McpServer::stdio(tools, "my-server", "1.0.0")?;
+///
+/// // Add tools later, e.g. from another task
+/// let handle = server.handle();
+/// handle.register(another_tool)?;
+///
 /// server.run().await?;
 /// ```
 pub struct McpServer<T: BidirectionalTransport> {
@@ -29,6 +123,9 @@ pub struct McpServer<T: BidirectionalTransport> {
     tools: Tools,
     info: ServerInfo,
     initialized: bool,
+    log_level: LoggingLevel,
+    commands: Receiver<ServerCommand>,
+    handle: Sender<ServerCommand>,
 }
 
 impl<T: BidirectionalTransport> std::fmt::Debug for McpServer<T> {
@@ -61,15 +158,7 @@ impl McpServer<StdioTransport> {
         version: impl Into<String>,
     ) -> Result<Self, McpError> {
         let transport = StdioTransport::new().map_err(|e| McpError::Transport(e.to_string()))?;
-        Ok(Self {
-            transport,
-            tools,
-            info: ServerInfo {
-                name: name.into(),
-                version: Some(version.into()),
-            },
-            initialized: false,
-        })
+        Ok(Self::new(transport, tools, name, version))
     }
 }
 
@@ -91,6 +180,7 @@ impl<T: BidirectionalTransport> McpServer<T> {
         name: impl Into<String>,
         version: impl Into<String>,
     ) -> Self {
+        let (handle, commands) = async_channel::unbounded();
         Self {
             transport,
             tools,
@@ -99,7 +189,62 @@ impl<T: BidirectionalTransport> McpServer<T> {
                 version: Some(version.into()),
             },
             initialized: false,
+            log_level: LoggingLevel::default(),
+            commands,
+            handle,
+        }
+    }
+
+    /// Returns a handle for adding and removing tools or logging while the
+    /// server runs.
+    #[must_use]
+    pub fn handle(&self) -> McpServerHandle {
+        McpServerHandle {
+            tx: self.handle.clone(),
+        }
+    }
+
+    /// Add a tool, notifying the client if it has already initialized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification cannot be sent.
+    pub async fn register_tool<U: Tool + 'static>(&mut self, tool: U) -> Result<(), McpError> {
+        self.tools.register(tool);
+        self.notify_tools_changed().await
+    }
+
+    /// Remove a tool by name, notifying the client if it has already initialized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification cannot be sent.
+    pub async fn unregister_tool(&mut self, name: &str) -> Result<(), McpError> {
+        self.tools.unregister(name);
+        self.notify_tools_changed().await
+    }
+
+    /// Send `notifications/tools/list_changed` so the client re-lists tools.
+    async fn notify_tools_changed(&mut self) -> Result<(), McpError> {
+        if !self.initialized {
+            return Ok(());
         }
+        self.transport
+            .notify(JsonRpcNotification::new("notifications/tools/list_changed"))
+            .await
+    }
+
+    /// Send a log message if it meets the client's requested level.
+    async fn send_log(&mut self, params: LoggingMessageParams) -> Result<(), McpError> {
+        if !self.initialized || params.level < self.log_level {
+            return Ok(());
+        }
+        self.transport
+            .notify(JsonRpcNotification::with_params(
+                "notifications/message",
+                params,
+            ))
+            .await
     }
 
     /// Run the server main loop.
@@ -113,19 +258,50 @@ impl<T: BidirectionalTransport> McpServer<T> {
         debug!("MCP server starting: {}", self.info.name);
 
         loop {
-            if let Some(msg) = self.transport.recv().await? {
-                if let Err(e) = self.handle_message(msg).await {
-                    debug!("Error handling message: {e}");
+            let transport = &mut self.transport;
+            let commands = &self.commands;
+            let event = future::or(async { Event::Message(transport.recv().await) }, async {
+                match commands.recv().await {
+                    Ok(command) => Event::Command(command),
+                    // The server holds a sender itself, so this never happens.
+                    Err(_) => future::pending().await,
+                }
+            })
+            .await;
+
+            match event {
+                Event::Message(msg) => {
+                    if let Some(msg) = msg? {
+                        if let Err(e) = self.handle_message(msg).await {
+                            debug!("Error handling message: {e}");
+                        }
+                    } else {
+                        debug!("Connection closed");
+                        break;
+                    }
+                }
+                Event::Command(command) => {
+                    if let Err(e) = self.handle_command(command).await {
+                        debug!("Error applying server update: {e}");
+                    }
                 }
-            } else {
-                debug!("Connection closed");
-                break;
             }
         }
 
         Ok(())
     }
 
+    /// Apply a change requested through an [`McpServerHandle`].
+    async fn handle_command(&mut self, command: ServerCommand) -> Result<(), McpError> {
+        match command {
+            ServerCommand::UpdateTools(update) => {
+                update(&mut self.tools);
+                self.notify_tools_changed().await
+            }
+            ServerCommand::Log(params) => self.send_log(params).await,
+        }
+    }
+
     /// Handle an incoming JSON-RPC message.
     async fn handle_message(&mut self, msg: JsonRpcMessage) -> Result<(), McpError> {
         match msg {
@@ -154,6 +330,8 @@ impl<T: BidirectionalTransport> McpServer<T> {
 
         match req.method.as_str() {
             "initialize" => self.handle_initialize(req),
+            "ping" => JsonRpcResponse::success(req.id, serde_json::json!({})),
+            "logging/setLevel" => self.handle_set_level(req),
             "tools/list" => self.handle_list_tools(req),
             "tools/call" => self.handle_call_tool(req).await,
             method => JsonRpcResponse::error(req.id, JsonRpcError::method_not_found(method)),
@@ -174,7 +352,10 @@ impl<T: BidirectionalTransport> McpServer<T> {
         let result = InitializeResult {
             protocol_version: PROTOCOL_VERSION.to_string(),
             capabilities: ServerCapabilities {
-                tools: Some(ToolsCapability { list_changed: None }),
+                tools: Some(ToolsCapability {
+                    list_changed: Some(true),
+                }),
+                logging: Some(serde_json::json!({})),
                 ..Default::default()
             },
             server_info: self.info.clone(),
@@ -184,6 +365,20 @@ impl<T: BidirectionalTransport> McpServer<T> {
         JsonRpcResponse::success(req.id, result)
     }
 
+    /// Handle logging/setLevel request.
+    fn handle_set_level(&mut self, req: JsonRpcRequest) -> JsonRpcResponse {
+        match req.params.map(serde_json::from_value::<SetLevelParams>) {
+            Some(Ok(params)) => {
+                self.log_level = params.level;
+                JsonRpcResponse::success(req.id, serde_json::json!({}))
+            }
+            Some(Err(e)) => {
+                JsonRpcResponse::error(req.id, JsonRpcError::invalid_params(e.to_string()))
+            }
+            None => JsonRpcResponse::error(req.id, JsonRpcError::invalid_params("Missing params")),
+        }
+    }
+
     /// Handle tools/list request.
     fn handle_list_tools(&self, req: JsonRpcRequest) -> JsonRpcResponse {
         let definitions = self.tools.definitions();
@@ -221,8 +416,41 @@ impl<T: BidirectionalTransport> McpServer<T> {
         };
 
         let args_str = serde_json::to_string(&params.arguments).unwrap_or_default();
+        let progress_token = params.meta.and_then(|meta| meta.progress_token);
+
+        let mut call = pin!(self.tools.call(&params.name, &args_str));
+        let result = match progress_token {
+            None => call.await,
+            // Report elapsed time until the tool finishes so clients can show
+            // activity and keep long calls from timing out.
+            Some(progress_token) => {
+                let started = Instant::now();
+                loop {
+                    let finished = future::or(async { Some(call.as_mut().await) }, async {
+                        Timer::after(PROGRESS_INTERVAL).await;
+                        None
+                    })
+                    .await;
+                    if let Some(result) = finished {
+                        break result;
+                    }
+                    let elapsed = started.elapsed().as_secs_f64();
+                    let progress = ProgressParams {
+                        progress_token: progress_token.clone(),
+                        progress: elapsed,
+                        total: None,
+                        message: Some(format!("{} running for {elapsed:.0}s", params.name)),
+                    };
+                    let notification =
+                        JsonRpcNotification::with_params("notifications/progress", progress);
+                    if let Err(e) = self.transport.notify(notification).await {
+                        debug!("Failed to send progress: {e}");
+                    }
+                }
+            }
+        };
 
-        match self.tools.call(&params.name, &args_str).await {
+        match result {
             Ok(output) => {
                 let text = output.as_str().unwrap_or("").to_string();
                 let result = CallToolResult {
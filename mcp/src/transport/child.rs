@@ -1,14 +1,17 @@
 //! Child process transport for MCP.
 //!
 //! This transport spawns a subprocess and communicates with it via stdio pipes.
+//! It is bidirectional, so it also serves clients of agent protocols where the
+//! child sends requests of its own (such as ACP permission prompts).
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicI64, Ordering};
 
 use async_process::{Child, ChildStdin, ChildStdout, Command, Stdio};
 use futures_lite::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tracing::debug;
 
-use super::traits::{Result, Transport};
+use super::traits::{BidirectionalTransport, Result, Transport};
 use crate::protocol::{
     JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, McpError, RequestId,
 };
@@ -28,6 +31,10 @@ pub struct ChildProcessTransport {
     next_id: AtomicI64,
     /// Whether the transport is closed.
     closed: bool,
+    /// Notifications received while waiting for responses.
+    notifications: Vec<JsonRpcNotification>,
+    /// Partially read line, kept across cancelled reads.
+    line: Vec<u8>,
 }
 
 impl std::fmt::Debug for ChildProcessTransport {
@@ -50,10 +57,26 @@ impl ChildProcessTransport {
     ///
     /// Returns an error if the process cannot be spawned.
     pub fn spawn(program: &str, args: &[&str]) -> Result<Self> {
+        Self::spawn_with_env(program, args, &HashMap::new())
+    }
+
+    /// Spawn a new child process transport with extra environment variables.
+    ///
+    /// The child inherits this process's environment, with `env` added on top.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process cannot be spawned.
+    pub fn spawn_with_env(
+        program: &str,
+        args: &[&str],
+        env: &HashMap<String, String>,
+    ) -> Result<Self> {
         debug!("Spawning MCP server: {} {:?}", program, args);
 
         let mut child = Command::new(program)
             .args(args)
+            .envs(env)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::inherit())
@@ -75,6 +98,8 @@ impl ChildProcessTransport {
             stdout: BufReader::new(stdout),
             next_id: AtomicI64::new(1),
             closed: false,
+            notifications: Vec::new(),
+            line: Vec::new(),
         })
     }
 
@@ -104,6 +129,8 @@ impl ChildProcessTransport {
             stdout: BufReader::new(stdout),
             next_id: AtomicI64::new(1),
             closed: false,
+            notifications: Vec::new(),
+            line: Vec::new(),
         })
     }
 
@@ -124,12 +151,34 @@ impl ChildProcessTransport {
         Ok(())
     }
 
+    /// Send a request without waiting for its response.
+    ///
+    /// Returns the assigned request ID; the response arrives through
+    /// [`recv`](BidirectionalTransport::recv), interleaved with whatever the
+    /// child sends in the meantime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transport is closed or the write fails.
+    pub async fn send_request(&mut self, mut req: JsonRpcRequest) -> Result<RequestId> {
+        if self.closed {
+            return Err(McpError::ConnectionClosed);
+        }
+        let id = self.next_request_id();
+        req.id = id.clone();
+        self.write_message(&req).await?;
+        Ok(id)
+    }
+
     /// Read a message from the child's stdout.
+    ///
+    /// Cancel-safe: bytes of an unfinished line stay buffered for the next call.
     async fn read_message(&mut self) -> Result<Option<JsonRpcMessage>> {
-        let mut line = String::new();
-        match self.stdout.read_line(&mut line).await {
-            Ok(0) => Ok(None), // EOF
+        match self.stdout.read_until(b'\n', &mut self.line).await {
+            Ok(0) if self.line.is_empty() => Ok(None), // EOF
             Ok(_) => {
+                let bytes = std::mem::take(&mut self.line);
+                let line = String::from_utf8_lossy(&bytes);
                 let line = line.trim();
                 if line.is_empty() {
                     return Ok(None);
@@ -162,6 +211,9 @@ impl Transport for ChildProcessTransport {
                 Some(JsonRpcMessage::Response(response)) if response.id == id => {
                     return Ok(response);
                 }
+                Some(JsonRpcMessage::Notification(notification)) => {
+                    self.notifications.push(notification);
+                }
                 Some(_) => {
                     // Skip non-matching messages
                     continue;
@@ -180,6 +232,14 @@ impl Transport for ChildProcessTransport {
         self.write_message(&notif).await
     }
 
+    fn take_notifications(&mut self) -> Vec<JsonRpcNotification> {
+        std::mem::take(&mut self.notifications)
+    }
+
+    fn is_alive(&mut self) -> bool {
+        !self.closed && matches!(self.child.try_status(), Ok(None))
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.closed = true;
         let _ = self.child.kill();
@@ -187,3 +247,19 @@ impl Transport for ChildProcessTransport {
         Ok(())
     }
 }
+
+impl BidirectionalTransport for ChildProcessTransport {
+    async fn recv(&mut self) -> Result<Option<JsonRpcMessage>> {
+        if self.closed {
+            return Ok(None);
+        }
+        self.read_message().await
+    }
+
+    async fn respond(&mut self, response: JsonRpcResponse) -> Result<()> {
+        if self.closed {
+            return Err(McpError::ConnectionClosed);
+        }
+        self.write_message(&response).await
+    }
+}
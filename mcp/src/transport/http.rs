@@ -169,6 +169,10 @@ impl Transport for HttpTransport {
         Ok(())
     }
 
+    fn is_alive(&mut self) -> bool {
+        !self.closed
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.closed = true;
         Ok(())
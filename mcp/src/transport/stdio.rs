@@ -3,10 +3,11 @@
 //! This transport uses stdin/stdout for communication, which is the standard
 //! method for MCP servers that run as subprocesses (e.g., Claude Desktop integration).
 
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicI64, Ordering};
 
 use async_io::Async;
-use futures_lite::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use futures_lite::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 use tracing::debug;
 
 use super::traits::{BidirectionalTransport, Result, Transport};
@@ -19,18 +20,27 @@ use crate::protocol::{
 /// Messages are sent as newline-delimited JSON. This transport is typically
 /// used when running as a subprocess where the parent process communicates
 /// via pipes.
-pub struct StdioTransport {
+///
+/// The reader and writer default to the process's stdin and stdout; use
+/// [`StdioTransport::with_streams`] to run over other streams.
+pub struct StdioTransport<R = BufReader<Async<std::io::Stdin>>, W = Async<std::io::Stdout>> {
     /// Async stdin reader.
-    stdin: BufReader<Async<std::io::Stdin>>,
+    stdin: R,
     /// Async stdout writer.
-    stdout: Async<std::io::Stdout>,
+    stdout: W,
     /// Next request ID.
     next_id: AtomicI64,
     /// Whether the transport is closed.
     closed: bool,
+    /// Notifications received while waiting for responses.
+    notifications: Vec<JsonRpcNotification>,
+    /// Requests received while waiting for responses, handed out by `recv`.
+    requests: VecDeque<JsonRpcRequest>,
+    /// Partially read line, kept across cancelled reads.
+    line: Vec<u8>,
 }
 
-impl std::fmt::Debug for StdioTransport {
+impl<R, W> std::fmt::Debug for StdioTransport<R, W> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("StdioTransport")
             .field("closed", &self.closed)
@@ -47,13 +57,26 @@ impl StdioTransport {
     pub fn new() -> std::io::Result<Self> {
         let stdin = Async::new(std::io::stdin())?;
         let stdout = Async::new(std::io::stdout())?;
+        Ok(Self::with_streams(BufReader::new(stdin), stdout))
+    }
+}
 
-        Ok(Self {
-            stdin: BufReader::new(stdin),
-            stdout,
+impl<R, W> StdioTransport<R, W>
+where
+    R: AsyncBufRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
+    /// Create a transport that reads messages from `reader` and writes them to `writer`.
+    pub const fn with_streams(reader: R, writer: W) -> Self {
+        Self {
+            stdin: reader,
+            stdout: writer,
             next_id: AtomicI64::new(1),
             closed: false,
-        })
+            notifications: Vec::new(),
+            requests: VecDeque::new(),
+            line: Vec::new(),
+        }
     }
 
     /// Generate the next request ID.
@@ -62,7 +85,7 @@ impl StdioTransport {
     }
 
     /// Write a message to stdout.
-    async fn write_message(&mut self, msg: &impl serde::Serialize) -> Result<()> {
+    async fn write_message(&mut self, msg: &(impl serde::Serialize + Sync)) -> Result<()> {
         let json = serde_json::to_string(msg)?;
         debug!("MCP TX: {}", json);
 
@@ -74,12 +97,14 @@ impl StdioTransport {
     }
 
     /// Read a message from stdin.
+    ///
+    /// Cancel-safe: bytes of an unfinished line stay buffered for the next call.
     async fn read_message(&mut self) -> Result<Option<JsonRpcMessage>> {
-        let mut line = String::new();
-
-        match self.stdin.read_line(&mut line).await {
-            Ok(0) => Ok(None), // EOF
+        match self.stdin.read_until(b'\n', &mut self.line).await {
+            Ok(0) if self.line.is_empty() => Ok(None), // EOF
             Ok(_) => {
+                let bytes = std::mem::take(&mut self.line);
+                let line = String::from_utf8_lossy(&bytes);
                 let line = line.trim();
                 if line.is_empty() {
                     return Ok(None);
@@ -93,7 +118,11 @@ impl StdioTransport {
     }
 }
 
-impl Transport for StdioTransport {
+impl<R, W> Transport for StdioTransport<R, W>
+where
+    R: AsyncBufRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
     async fn request(&mut self, mut req: JsonRpcRequest) -> Result<JsonRpcResponse> {
         if self.closed {
             return Err(McpError::ConnectionClosed);
@@ -112,9 +141,15 @@ impl Transport for StdioTransport {
                 Some(JsonRpcMessage::Response(response)) if response.id == id => {
                     return Ok(response);
                 }
-                Some(_) => {
-                    // Skip non-matching messages (notifications, other responses)
-                    continue;
+                Some(JsonRpcMessage::Notification(notification)) => {
+                    self.notifications.push(notification);
+                }
+                Some(JsonRpcMessage::Request(request)) => {
+                    // The peer may send its own requests before replying to ours.
+                    self.requests.push_back(request);
+                }
+                Some(JsonRpcMessage::Response(_)) => {
+                    // Skip responses to other requests
                 }
                 None => {
                     return Err(McpError::ConnectionClosed);
@@ -130,17 +165,32 @@ impl Transport for StdioTransport {
         self.write_message(&notif).await
     }
 
+    fn take_notifications(&mut self) -> Vec<JsonRpcNotification> {
+        std::mem::take(&mut self.notifications)
+    }
+
+    fn is_alive(&mut self) -> bool {
+        !self.closed
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.closed = true;
         Ok(())
     }
 }
 
-impl BidirectionalTransport for StdioTransport {
+impl<R, W> BidirectionalTransport for StdioTransport<R, W>
+where
+    R: AsyncBufRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send,
+{
     async fn recv(&mut self) -> Result<Option<JsonRpcMessage>> {
         if self.closed {
             return Ok(None);
         }
+        if let Some(request) = self.requests.pop_front() {
+            return Ok(Some(JsonRpcMessage::Request(request)));
+        }
         self.read_message().await
     }
 
@@ -151,3 +201,50 @@ impl BidirectionalTransport for StdioTransport {
         self.write_message(&response).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures_lite::future::block_on;
+    use futures_lite::io::Cursor;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn requests_received_while_awaiting_a_response_are_buffered() {
+        let incoming = [
+            json!({"jsonrpc": "2.0", "method": "session/update", "params": {}}),
+            json!({"jsonrpc": "2.0", "id": 7, "method": "session/prompt", "params": {"sessionId": "s"}}),
+            json!({"jsonrpc": "2.0", "id": 1, "result": {"outcome": "selected"}}),
+            json!({"jsonrpc": "2.0", "id": 8, "method": "session/new", "params": {}}),
+        ];
+        let mut input = incoming.map(|msg| msg.to_string()).join("\n");
+        input.push('\n');
+        let mut transport =
+            StdioTransport::with_streams(Cursor::new(input.into_bytes()), Vec::new());
+
+        block_on(async {
+            let response = transport
+                .request(JsonRpcRequest::new(0, "session/request_permission"))
+                .await
+                .unwrap();
+            assert_eq!(response.id, RequestId::Number(1));
+            assert_eq!(transport.take_notifications().len(), 1);
+
+            let Some(JsonRpcMessage::Request(request)) = transport.recv().await.unwrap() else {
+                panic!("buffered request was lost");
+            };
+            assert_eq!(request.id, RequestId::Number(7));
+            assert_eq!(request.method, "session/prompt");
+
+            let Some(JsonRpcMessage::Request(request)) = transport.recv().await.unwrap() else {
+                panic!("expected the next request from the stream");
+            };
+            assert_eq!(request.method, "session/new");
+            assert!(transport.recv().await.unwrap().is_none());
+        });
+
+        let sent = String::from_utf8(transport.stdout).unwrap();
+        assert!(sent.contains("session/request_permission"));
+    }
+}
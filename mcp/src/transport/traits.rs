@@ -28,6 +28,21 @@ pub trait Transport: Send {
 
     /// Close the transport connection.
     fn close(&mut self) -> impl Future<Output = Result<()>> + Send;
+
+    /// Take server notifications that arrived while waiting for responses.
+    ///
+    /// Transports that cannot receive server-initiated messages return nothing.
+    fn take_notifications(&mut self) -> Vec<JsonRpcNotification> {
+        Vec::new()
+    }
+
+    /// Cheap local liveness check, without a round trip to the server.
+    ///
+    /// Returns `false` once the transport knows the peer is gone, for example
+    /// because the child process exited.
+    fn is_alive(&mut self) -> bool {
+        true
+    }
 }
 
 /// Bidirectional transport that can also receive incoming messages.
@@ -38,6 +53,9 @@ pub trait BidirectionalTransport: Transport {
     /// Receive the next incoming message.
     ///
     /// Returns `None` if the connection is closed.
+    ///
+    /// Must be cancel-safe: the server races this against runtime updates,
+    /// and dropping the future must not lose a partially read message.
     fn recv(&mut self) -> impl Future<Output = Result<Option<JsonRpcMessage>>> + Send;
 
     /// Send a response to a request.
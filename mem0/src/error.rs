@@ -11,6 +11,9 @@ pub enum Mem0Error {
     #[error("Store error: {0}")]
     Store(String),
 
+    #[error("Prompt error: {0}")]
+    Prompt(anyhow::Error),
+
     #[error("Extraction failed: {0}")]
     Extraction(String),
 }
@@ -3,8 +3,10 @@ use std::sync::Arc;
 
 use aither_core::embedding::EmbeddingModel;
 use aither_core::llm::{LLMRequest, LanguageModel, Message, Tool, ToolOutput};
+use aither_core::prompt::PromptLibrary;
 use anyhow::Context;
 use llm::{Action, ExtractedFacts, MemoryDecision};
+use serde::Serialize;
 use store::{MemoryStore, SearchFilters};
 use tracing::debug;
 use uuid::Uuid;
@@ -74,6 +76,12 @@ pub struct Config {
     pub user_id: Option<String>,
     /// Agent ID to associate with memories.
     pub agent_id: Option<String>,
+    /// Prompts used for fact extraction and memory updates.
+    ///
+    /// Starts with the built-in prompts, named `mem0.extractor`,
+    /// `mem0.extract_user`, `mem0.manager` and `mem0.manager_user`. Replace
+    /// any of them to change how memories are managed.
+    pub prompts: PromptLibrary,
 }
 
 impl Default for Config {
@@ -82,10 +90,38 @@ impl Default for Config {
             retrieve_count: 5,
             user_id: None,
             agent_id: None,
+            prompts: default_prompts(),
         }
     }
 }
 
+/// Variables of the `mem0.extract_user` prompt.
+#[derive(Serialize)]
+struct ExtractPrompt<'a> {
+    conversation: &'a str,
+}
+
+/// Variables of the `mem0.manager_user` prompt.
+#[derive(Serialize)]
+struct ManagerPrompt<'a> {
+    fact: &'a str,
+    memories: &'a str,
+}
+
+fn default_prompts() -> PromptLibrary {
+    PromptLibrary::new()
+        .with("mem0.extractor", include_str!("../prompts/extractor.txt"))
+        .with(
+            "mem0.extract_user",
+            "Extract facts from the following conversation:\n\n{conversation}",
+        )
+        .with("mem0.manager", include_str!("../prompts/manager.txt"))
+        .with(
+            "mem0.manager_user",
+            "New Fact: {fact}\n\nExisting Memories:\n{memories}\n\nDecide the operation.",
+        )
+}
+
 /// Mem0 memory manager.
 struct Mem0Inner<L, E, S> {
     new_facts: async_lock::RwLock<Vec<String>>, // Store new facts temporarily
@@ -164,19 +200,20 @@ where
 
         let store = &self.inner.store;
 
-        for fact in facts {
-            // 2. Embed the fact for search
-            let embedding = self
-                .inner
-                .embedder
-                .lock()
-                .await
-                .embed(&fact)
-                .await
-                .map_err(Mem0Error::Embedding)?;
+        // 2. Embed all facts for search in one batch
+        let texts: Vec<&str> = facts.iter().map(String::as_str).collect();
+        let embeddings = self
+            .inner
+            .embedder
+            .lock()
+            .await
+            .embed_batch(&texts)
+            .await
+            .map_err(Mem0Error::Embedding)?;
 
-            debug!("Embedding generated for fact: {}", fact);
+        debug!("Embeddings generated for {} facts", embeddings.len());
 
+        for (fact, embedding) in facts.into_iter().zip(embeddings) {
             // 3. Retrieve similar memories
             let filters = SearchFilters {
                 user_id: self.inner.config.user_id.clone(),
@@ -311,14 +348,22 @@ where
             .collect::<Vec<_>>()
             .join("\n");
 
-        let system_prompt = include_str!("../prompts/extractor.txt");
+        let prompts = &self.inner.config.prompts;
+        let system_prompt = prompts
+            .render("mem0.extractor", &())
+            .map_err(Mem0Error::Prompt)?;
+        let user_prompt = prompts
+            .render(
+                "mem0.extract_user",
+                &ExtractPrompt {
+                    conversation: &context,
+                },
+            )
+            .map_err(Mem0Error::Prompt)?;
 
         let request = LLMRequest::new(vec![
             Message::system(system_prompt),
-            Message::user(format!(
-                "Extract facts from the following conversation:\n\n{}",
-                context
-            )),
+            Message::user(user_prompt),
         ]);
 
         let extracted: ExtractedFacts = self
@@ -341,12 +386,19 @@ where
             .collect::<Vec<_>>()
             .join("\n---\n");
 
-        let system_prompt = include_str!("../prompts/manager.txt");
-
-        let user_prompt = format!(
-            "New Fact: {}\n\nExisting Memories:\n{}\n\nDecide the operation.",
-            fact, memories_context
-        );
+        let prompts = &self.inner.config.prompts;
+        let system_prompt = prompts
+            .render("mem0.manager", &())
+            .map_err(Mem0Error::Prompt)?;
+        let user_prompt = prompts
+            .render(
+                "mem0.manager_user",
+                &ManagerPrompt {
+                    fact,
+                    memories: &memories_context,
+                },
+            )
+            .map_err(Mem0Error::Prompt)?;
 
         let request = LLMRequest::new(vec![
             Message::system(system_prompt),
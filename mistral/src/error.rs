@@ -1,3 +1,4 @@
+use aither_core::llm::{LlmError, LlmErrorKind};
 use std::fmt;
 
 /// Errors from `aither-mistral`.
@@ -46,3 +47,19 @@ impl From<base64::DecodeError> for MistralError {
         Self::Decode(value)
     }
 }
+
+impl From<MistralError> for LlmError {
+    fn from(err: MistralError) -> Self {
+        let kind = match &err {
+            MistralError::MissingModel(_) => LlmErrorKind::InvalidRequest,
+            MistralError::Api(message) => {
+                LlmErrorKind::from_message(message).unwrap_or(LlmErrorKind::Other)
+            }
+            MistralError::Runtime(err) => {
+                LlmErrorKind::from_message(&err.to_string()).unwrap_or(LlmErrorKind::Other)
+            }
+            MistralError::Json(_) | MistralError::Decode(_) => LlmErrorKind::Other,
+        };
+        Self::new(kind, err.to_string())
+    }
+}
@@ -29,10 +29,10 @@ use mistralrs::{DiffusionLoaderType, Model};
 
 #[cfg(feature = "image")]
 use base64::{Engine as _, engine::general_purpose};
-#[cfg(feature = "embedding")]
-use mistralrs::EmbeddingModelBuilder;
 #[cfg(feature = "image")]
 use mistralrs::{DiffusionGenerationParams, ImageGenerationResponseFormat};
+#[cfg(feature = "embedding")]
+use mistralrs::{EmbeddingModelBuilder, EmbeddingRequest};
 #[cfg(feature = "llm")]
 use mistralrs::{
     Function, RequestBuilder, TextMessageRole, TextModelBuilder, Tool,
@@ -46,12 +46,14 @@ use std::collections::HashMap;
 pub struct Mistral {
     inner: Arc<Mutex<Inner>>,
     embedding_dimensions: usize,
+    embedding_batch_size: usize,
 }
 
 impl core::fmt::Debug for Mistral {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Mistral")
             .field("embedding_dimensions", &self.embedding_dimensions)
+            .field("embedding_batch_size", &self.embedding_batch_size)
             .finish_non_exhaustive()
     }
 }
@@ -76,7 +78,7 @@ impl Default for Mistral {
 
 impl Mistral {
     /// Create a new mistral backend with no preconfigured model IDs.
-    #[must_use] 
+    #[must_use]
     pub fn new() -> Self {
         Self {
             inner: Arc::new(Mutex::new(Inner {
@@ -91,6 +93,7 @@ impl Mistral {
                 image: None,
             })),
             embedding_dimensions: 1024,
+            embedding_batch_size: 32,
         }
     }
 
@@ -173,6 +176,18 @@ impl Mistral {
         self
     }
 
+    /// Set how many texts [`embed_batch`](EmbeddingModel::embed_batch) sends
+    /// to mistral.rs per request.
+    ///
+    /// Larger batches are faster but hold all their activations in memory at
+    /// once; inputs beyond this size are embedded in successive batches.
+    /// Defaults to 32.
+    #[must_use]
+    pub const fn with_embedding_batch_size(mut self, batch_size: usize) -> Self {
+        self.embedding_batch_size = if batch_size == 0 { 1 } else { batch_size };
+        self
+    }
+
     #[cfg(feature = "llm")]
     async fn ensure_llm(inner: &Arc<Mutex<Inner>>) -> Result<Arc<Model>, MistralError> {
         if let Some(model) = inner.lock().expect("mistral state poisoned").llm.clone() {
@@ -356,8 +371,8 @@ impl LanguageModel for Mistral {
                 .llm_model_id
                 .clone()
                 .unwrap_or_else(|| "mistral-local".to_string());
-            let context_length = aither_models::lookup(&name)
-                .map_or(32_768, |model| model.context_window);
+            let context_length =
+                aither_models::lookup(&name).map_or(32_768, |model| model.context_window);
 
             Profile::new(
                 name.clone(),
@@ -385,7 +400,30 @@ impl EmbeddingModel for Mistral {
         let text = text.to_string();
         async move {
             let model = Self::ensure_embedding(&inner).await?;
-            model.generate_embedding(text).await}
+            model.generate_embedding(text).await
+        }
+    }
+
+    fn embed_batch(
+        &self,
+        texts: &[&str],
+    ) -> impl core::future::Future<Output = aither_core::Result<Vec<Vec<f32>>>> + Send {
+        let inner = self.inner.clone();
+        let count = texts.len();
+        let requests: Vec<_> = texts
+            .chunks(self.embedding_batch_size)
+            .map(|chunk| {
+                EmbeddingRequest::builder().add_prompts(chunk.iter().map(|&text| text.to_string()))
+            })
+            .collect();
+        async move {
+            let model = Self::ensure_embedding(&inner).await?;
+            let mut embeddings = Vec::with_capacity(count);
+            for request in requests {
+                embeddings.extend(model.generate_embeddings(request).await?);
+            }
+            Ok(embeddings)
+        }
     }
 }
 
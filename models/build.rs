@@ -39,6 +39,28 @@ struct LlmTomlModel {
     #[serde(default)]
     reasoning_budget_tokens_max: Option<u32>,
     #[serde(default)]
+    attachment_inline_max_bytes: Option<u64>,
+    #[serde(default)]
+    attachment_upload_max_bytes: Option<u64>,
+    #[serde(default)]
+    input_price: Option<f64>,
+    #[serde(default)]
+    output_price: Option<f64>,
+    #[serde(default)]
+    cached_input_price: Option<f64>,
+    #[serde(default)]
+    cache_write_price: Option<f64>,
+    #[serde(default)]
+    tokenizer: Option<String>,
+    #[serde(default)]
+    hf_tokenizer: Option<String>,
+    #[serde(default)]
+    deprecation_date: Option<String>,
+    #[serde(default)]
+    shutdown_date: Option<String>,
+    #[serde(default)]
+    successor: Option<String>,
+    #[serde(default)]
     outdated: bool,
 }
 
@@ -56,6 +78,12 @@ struct ImageTomlModel {
     #[serde(default)]
     image_max_resolution: Option<String>,
     #[serde(default)]
+    deprecation_date: Option<String>,
+    #[serde(default)]
+    shutdown_date: Option<String>,
+    #[serde(default)]
+    successor: Option<String>,
+    #[serde(default)]
     outdated: bool,
 }
 
@@ -72,6 +100,12 @@ struct EmbeddingTomlModel {
     capabilities: Vec<String>,
     embedding_dimensions: u32,
     #[serde(default)]
+    deprecation_date: Option<String>,
+    #[serde(default)]
+    shutdown_date: Option<String>,
+    #[serde(default)]
+    successor: Option<String>,
+    #[serde(default)]
     outdated: bool,
 }
 
@@ -89,6 +123,12 @@ struct RerankerTomlModel {
     #[serde(default)]
     reranker_max_documents: Option<u32>,
     #[serde(default)]
+    deprecation_date: Option<String>,
+    #[serde(default)]
+    shutdown_date: Option<String>,
+    #[serde(default)]
+    successor: Option<String>,
+    #[serde(default)]
     outdated: bool,
 }
 
@@ -112,6 +152,17 @@ struct UnifiedModel {
     embedding_dimensions: Option<u32>,
     image_max_resolution: Option<String>,
     reranker_max_documents: Option<u32>,
+    attachment_inline_max_bytes: Option<u64>,
+    attachment_upload_max_bytes: Option<u64>,
+    input_price: Option<f64>,
+    output_price: Option<f64>,
+    cached_input_price: Option<f64>,
+    cache_write_price: Option<f64>,
+    tokenizer: Option<String>,
+    hf_tokenizer: Option<String>,
+    deprecation_date: Option<String>,
+    shutdown_date: Option<String>,
+    successor: Option<String>,
     outdated: bool,
 }
 
@@ -151,6 +202,17 @@ fn main() {
         embedding_dimensions: None,
         image_max_resolution: None,
         reranker_max_documents: None,
+        attachment_inline_max_bytes: m.attachment_inline_max_bytes,
+        attachment_upload_max_bytes: m.attachment_upload_max_bytes,
+        input_price: m.input_price,
+        output_price: m.output_price,
+        cached_input_price: m.cached_input_price,
+        cache_write_price: m.cache_write_price,
+        tokenizer: m.tokenizer,
+        hf_tokenizer: m.hf_tokenizer,
+        deprecation_date: m.deprecation_date,
+        shutdown_date: m.shutdown_date,
+        successor: m.successor,
         outdated: m.outdated,
     }));
 
@@ -173,6 +235,17 @@ fn main() {
         embedding_dimensions: None,
         image_max_resolution: m.image_max_resolution,
         reranker_max_documents: None,
+        attachment_inline_max_bytes: None,
+        attachment_upload_max_bytes: None,
+        input_price: None,
+        output_price: None,
+        cached_input_price: None,
+        cache_write_price: None,
+        tokenizer: None,
+        hf_tokenizer: None,
+        deprecation_date: m.deprecation_date,
+        shutdown_date: m.shutdown_date,
+        successor: m.successor,
         outdated: m.outdated,
     }));
 
@@ -195,6 +268,17 @@ fn main() {
         embedding_dimensions: Some(m.embedding_dimensions),
         image_max_resolution: None,
         reranker_max_documents: None,
+        attachment_inline_max_bytes: None,
+        attachment_upload_max_bytes: None,
+        input_price: None,
+        output_price: None,
+        cached_input_price: None,
+        cache_write_price: None,
+        tokenizer: None,
+        hf_tokenizer: None,
+        deprecation_date: m.deprecation_date,
+        shutdown_date: m.shutdown_date,
+        successor: m.successor,
         outdated: m.outdated,
     }));
 
@@ -217,6 +301,17 @@ fn main() {
         embedding_dimensions: None,
         image_max_resolution: None,
         reranker_max_documents: m.reranker_max_documents,
+        attachment_inline_max_bytes: None,
+        attachment_upload_max_bytes: None,
+        input_price: None,
+        output_price: None,
+        cached_input_price: None,
+        cache_write_price: None,
+        tokenizer: None,
+        hf_tokenizer: None,
+        deprecation_date: m.deprecation_date,
+        shutdown_date: m.shutdown_date,
+        successor: m.successor,
         outdated: m.outdated,
     }));
 
@@ -323,8 +418,16 @@ fn main() {
     code.push_str("];\n\n");
 
     code.push_str(
-        "const MODEL_REASONING_META: &[(&str, Option<&str>, bool, Option<u32>, Option<u32>)] = &[\n",
+        "/// Row of `MODEL_REASONING_META`: reasoning defaults and budget bounds.\n\
+         struct ReasoningRow {\n    \
+             id: &'static str,\n    \
+             default_effort: Option<&'static str>,\n    \
+             adaptive_reasoning: bool,\n    \
+             budget_tokens_min: Option<u32>,\n    \
+             budget_tokens_max: Option<u32>,\n\
+         }\n\n",
     );
+    code.push_str("const MODEL_REASONING_META: &[ReasoningRow] = &[\n");
     for model in &models {
         let default_effort_code = model
             .reasoning_effort_default
@@ -340,15 +443,22 @@ fn main() {
             .map(|v| format!("Some({v})"))
             .unwrap_or_else(|| "None".to_string());
         code.push_str(&format!(
-            "    ({:?}, {}, {}, {}, {}),\n",
+            "    ReasoningRow {{ id: {:?}, default_effort: {}, adaptive_reasoning: {}, budget_tokens_min: {}, budget_tokens_max: {} }},\n",
             model.id, default_effort_code, model.adaptive_reasoning, min_code, max_code
         ));
     }
     code.push_str("];\n\n");
 
     code.push_str(
-        "const MODEL_SPECIALIZED_META: &[(&str, Option<u32>, Option<&str>, Option<u32>)] = &[\n",
+        "/// Row of `MODEL_SPECIALIZED_META`: limits of image, embedding and reranker models.\n\
+         struct SpecializedRow {\n    \
+             id: &'static str,\n    \
+             embedding_dimensions: Option<u32>,\n    \
+             image_max_resolution: Option<&'static str>,\n    \
+             reranker_max_documents: Option<u32>,\n\
+         }\n\n",
     );
+    code.push_str("const MODEL_SPECIALIZED_META: &[SpecializedRow] = &[\n");
     for model in &models {
         let emb_code = model
             .embedding_dimensions
@@ -364,10 +474,129 @@ fn main() {
             .map(|v| format!("Some({v})"))
             .unwrap_or_else(|| "None".to_string());
         code.push_str(&format!(
-            "    ({:?}, {}, {}, {}),\n",
+            "    SpecializedRow {{ id: {:?}, embedding_dimensions: {}, image_max_resolution: {}, reranker_max_documents: {} }},\n",
             model.id, emb_code, img_code, rerank_code
         ));
     }
+    code.push_str("];\n\n");
+
+    code.push_str("const MODEL_ATTACHMENT_LIMITS: &[(&str, Option<u64>, Option<u64>)] = &[\n");
+    for model in &models {
+        if model.kind != "llm" {
+            continue;
+        }
+        let inline_code = model
+            .attachment_inline_max_bytes
+            .map(|v| format!("Some({v})"))
+            .unwrap_or_else(|| "None".to_string());
+        let upload_code = model
+            .attachment_upload_max_bytes
+            .map(|v| format!("Some({v})"))
+            .unwrap_or_else(|| "None".to_string());
+        code.push_str(&format!(
+            "    ({:?}, {}, {}),\n",
+            model.id, inline_code, upload_code
+        ));
+    }
+    code.push_str("];\n\n");
+
+    code.push_str(
+        "/// Row of `MODEL_PRICING`: list prices in USD per million tokens.\n\
+         struct PricingRow {\n    \
+             id: &'static str,\n    \
+             input: Option<f64>,\n    \
+             output: Option<f64>,\n    \
+             cached_input: Option<f64>,\n    \
+             cache_write: Option<f64>,\n\
+         }\n\n",
+    );
+    code.push_str("const MODEL_PRICING: &[PricingRow] = &[\n");
+    for model in &models {
+        if model.kind != "llm" {
+            continue;
+        }
+        let price_code = |price: Option<f64>| {
+            price
+                .map(|v| format!("Some({v:?})"))
+                .unwrap_or_else(|| "None".to_string())
+        };
+        code.push_str(&format!(
+            "    PricingRow {{ id: {:?}, input: {}, output: {}, cached_input: {}, cache_write: {} }},\n",
+            model.id,
+            price_code(model.input_price),
+            price_code(model.output_price),
+            price_code(model.cached_input_price),
+            price_code(model.cache_write_price),
+        ));
+    }
+    code.push_str("];\n\n");
+
+    code.push_str("const MODEL_TOKENIZERS: &[(&str, Option<&str>, Option<&str>)] = &[\n");
+    for model in &models {
+        if model.kind != "llm" {
+            continue;
+        }
+        let name_code = |name: &Option<String>| {
+            name.as_ref()
+                .map(|v| format!("Some({v:?})"))
+                .unwrap_or_else(|| "None".to_string())
+        };
+        code.push_str(&format!(
+            "    ({:?}, {}, {}),\n",
+            model.id,
+            name_code(&model.tokenizer),
+            name_code(&model.hf_tokenizer),
+        ));
+    }
+    code.push_str("];\n\n");
+
+    code.push_str(
+        "/// Row of `MODEL_LIFECYCLE`: deprecation dates and the recommended successor.\n\
+         struct LifecycleRow {\n    \
+             id: &'static str,\n    \
+             deprecation_date: Option<&'static str>,\n    \
+             shutdown_date: Option<&'static str>,\n    \
+             successor: Option<&'static str>,\n\
+         }\n\n",
+    );
+    code.push_str("const MODEL_LIFECYCLE: &[LifecycleRow] = &[\n");
+    for model in &models {
+        for date in [&model.deprecation_date, &model.shutdown_date]
+            .into_iter()
+            .flatten()
+        {
+            if !is_iso_date(date) {
+                panic!(
+                    "Model {} has invalid date {date:?}, expected YYYY-MM-DD",
+                    model.id
+                );
+            }
+        }
+        if let Some(ref successor) = model.successor
+            && !models.iter().any(|m| m.id == *successor)
+        {
+            panic!("Model {} has unknown successor {successor}", model.id);
+        }
+        if model.deprecation_date.is_none()
+            && model.shutdown_date.is_none()
+            && model.successor.is_none()
+        {
+            continue;
+        }
+        let value_code = |value: &Option<String>| {
+            value
+                .as_ref()
+                .map(|v| format!("Some({v:?})"))
+                .unwrap_or_else(|| "None".to_string())
+        };
+        code.push_str(&format!(
+            "    LifecycleRow {{ id: {:?}, deprecation_date: {}, shutdown_date: {}, successor: {} }},\n",
+            model.id,
+            value_code(&model.deprecation_date),
+            value_code(&model.shutdown_date),
+            value_code(&model.successor),
+        ));
+    }
     code.push_str("];\n");
 
     fs::write(&out_path, code).expect("Failed to write generated.rs");
@@ -380,6 +609,15 @@ fn read_toml<T: for<'de> Deserialize<'de>>(manifest_dir: &str, file_name: &str)
     toml::from_str(&content).unwrap_or_else(|_| panic!("Failed to parse {}", file_name))
 }
 
+fn is_iso_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
 fn tier_variant(tier: &str) -> &'static str {
     match tier.to_lowercase().as_str() {
         "flagship" => "aither_core::llm::model::ModelTier::Flagship",
@@ -22,18 +22,58 @@
 //!     assert_eq!(info.id, "deepseek-v3"); // Resolved to canonical ID
 //! }
 //! ```
+//!
+//! The compiled-in tables can be extended at runtime from a models.dev-style
+//! manifest with [`load_manifest`] and friends, or with custom models via
+//! [`register_model`]; every query sees the merged registry.
+//!
+//! With the `tokenizer` feature, [`count_tokens`] counts tokens with the
+//! model's own tiktoken encoding.
+
+mod runtime;
+#[cfg(feature = "tokenizer")]
+mod tokens;
 
 // Re-export types from core for convenience
+pub use aither_core::llm::Usage;
 pub use aither_core::llm::model::{Ability, ModelInfo, ModelTier};
 
+#[cfg(feature = "remote")]
+pub use runtime::load_manifest_url;
+pub use runtime::{
+    ManifestError, clear_runtime_models, load_manifest, load_manifest_file, register_model,
+    register_model_with_aliases, unregister_model,
+};
+
+#[cfg(feature = "tokenizer")]
+pub use tokens::{count_tokens, count_tokens_with_encoding};
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use runtime::with_runtime_models;
+
 // Include generated code from build.rs
 include!(concat!(env!("OUT_DIR"), "/generated.rs"));
 
 /// Resolve model ID or alias to canonical model ID.
+///
+/// Runtime models take precedence over the compiled-in tables.
 #[must_use]
 fn canonical_model_id(model_id: &str) -> Option<&'static str> {
     let model_lower = model_id.to_lowercase();
 
+    let runtime_match = with_runtime_models(|models| {
+        models
+            .iter()
+            .find(|m| {
+                m.info.id.eq_ignore_ascii_case(&model_lower) || m.aliases.contains(&model_lower)
+            })
+            .map(|m| m.info.id)
+    });
+    if runtime_match.is_some() {
+        return runtime_match;
+    }
+
     if let Some((id, _, _, _)) = MODEL_META
         .iter()
         .find(|(id, _, _, _)| id.eq_ignore_ascii_case(&model_lower))
@@ -45,11 +85,35 @@ fn canonical_model_id(model_id: &str) -> Option<&'static str> {
         return Some(ALIASES[idx].1);
     }
 
+    let runtime_ids: Vec<&'static str> =
+        with_runtime_models(|models| models.iter().map(|m| m.info.id).collect());
     MODEL_META
         .iter()
-        .filter(|(id, _, _, _)| model_lower.starts_with(&id.to_lowercase()))
-        .max_by_key(|(id, _, _, _)| id.len())
         .map(|(id, _, _, _)| *id)
+        .chain(runtime_ids)
+        .filter(|id| model_lower.starts_with(&id.to_lowercase()))
+        .max_by_key(|id| id.len())
+}
+
+/// Runtime model info for a canonical ID, if one was loaded.
+fn runtime_info(canonical_id: &str) -> Option<&'static ModelInfo> {
+    with_runtime_models(|models| {
+        models
+            .iter()
+            .find(|m| m.info.id.eq_ignore_ascii_case(canonical_id))
+            .map(|m| m.info)
+    })
+}
+
+/// Compiled and runtime LLM models, with runtime entries replacing compiled ones.
+fn merged_models() -> Vec<&'static ModelInfo> {
+    let runtime: Vec<&'static ModelInfo> =
+        with_runtime_models(|models| models.iter().map(|m| m.info).collect());
+    MODELS
+        .iter()
+        .filter(|m| !runtime.iter().any(|r| r.id.eq_ignore_ascii_case(m.id)))
+        .chain(runtime.iter().copied())
+        .collect()
 }
 
 /// Look up LLM model info by ID or alias.
@@ -58,38 +122,38 @@ fn canonical_model_id(model_id: &str) -> Option<&'static str> {
 #[must_use]
 pub fn lookup(model_id: &str) -> Option<&'static ModelInfo> {
     let canonical_id = canonical_model_id(model_id)?;
-    MODELS
-        .iter()
-        .find(|m| m.id.eq_ignore_ascii_case(canonical_id))
+    runtime_info(canonical_id).or_else(|| {
+        MODELS
+            .iter()
+            .find(|m| m.id.eq_ignore_ascii_case(canonical_id))
+    })
 }
 
 /// Get all models for a provider.
-#[must_use]
 pub fn models_for_provider(provider: &str) -> impl Iterator<Item = &'static ModelInfo> {
     let provider_lower = provider.to_lowercase();
-    MODELS
-        .iter()
+    merged_models()
+        .into_iter()
         .filter(move |m| m.provider.eq_ignore_ascii_case(&provider_lower))
 }
 
 /// Get all models with a specific ability.
-#[must_use]
 pub fn models_with_ability(ability: Ability) -> impl Iterator<Item = &'static ModelInfo> {
-    MODELS
-        .iter()
+    merged_models()
+        .into_iter()
         .filter(move |m| m.abilities.contains(&ability))
 }
 
 /// Get all models of a specific tier.
-#[must_use]
 pub fn models_by_tier(tier: ModelTier) -> impl Iterator<Item = &'static ModelInfo> {
-    MODELS.iter().filter(move |m| m.has_tier(tier))
+    merged_models()
+        .into_iter()
+        .filter(move |m| m.has_tier(tier))
 }
 
 /// Get all known models.
-#[must_use]
 pub fn all_models() -> impl Iterator<Item = &'static ModelInfo> {
-    MODELS.iter()
+    merged_models().into_iter()
 }
 
 /// Returns metadata-only capability labels for a model id/alias.
@@ -141,6 +205,14 @@ pub fn model_kind(model_id: &str) -> Option<&'static str> {
 #[must_use]
 pub fn model_meta(model_id: &str) -> Option<ModelMeta> {
     let id = canonical_model_id(model_id)?;
+    if let Some(info) = runtime_info(id) {
+        return Some(ModelMeta {
+            kind: "llm",
+            name: info.name,
+            provider: info.provider,
+            context_window: info.context_window,
+        });
+    }
     MODEL_META
         .iter()
         .find_map(|(model_id, name, provider, context_window)| {
@@ -169,19 +241,12 @@ pub fn specialized_meta(model_id: &str) -> SpecializedMeta {
 
     MODEL_SPECIALIZED_META
         .iter()
-        .find_map(
-            |(model_id, embedding_dimensions, image_max_resolution, reranker_max_documents)| {
-                if model_id.eq_ignore_ascii_case(id) {
-                    Some(SpecializedMeta {
-                        embedding_dimensions: *embedding_dimensions,
-                        image_max_resolution: *image_max_resolution,
-                        reranker_max_documents: *reranker_max_documents,
-                    })
-                } else {
-                    None
-                }
-            },
-        )
+        .find(|row| row.id.eq_ignore_ascii_case(id))
+        .map(|row| SpecializedMeta {
+            embedding_dimensions: row.embedding_dimensions,
+            image_max_resolution: row.image_max_resolution,
+            reranker_max_documents: row.reranker_max_documents,
+        })
         .unwrap_or(SpecializedMeta {
             embedding_dimensions: None,
             image_max_resolution: None,
@@ -228,17 +293,12 @@ pub fn reasoning_meta(model_id: &str) -> ReasoningMeta {
 
     MODEL_REASONING_META
         .iter()
-        .find_map(|(model_id, default_effort, adaptive_reasoning, min, max)| {
-            if model_id.eq_ignore_ascii_case(id) {
-                Some(ReasoningMeta {
-                    default_effort: *default_effort,
-                    adaptive_reasoning: *adaptive_reasoning,
-                    budget_tokens_min: *min,
-                    budget_tokens_max: *max,
-                })
-            } else {
-                None
-            }
+        .find(|row| row.id.eq_ignore_ascii_case(id))
+        .map(|row| ReasoningMeta {
+            default_effort: row.default_effort,
+            adaptive_reasoning: row.adaptive_reasoning,
+            budget_tokens_min: row.budget_tokens_min,
+            budget_tokens_max: row.budget_tokens_max,
         })
         .unwrap_or(ReasoningMeta {
             default_effort: None,
@@ -248,6 +308,325 @@ pub fn reasoning_meta(model_id: &str) -> ReasoningMeta {
         })
 }
 
+/// Size limits for attachments sent to a model.
+///
+/// Inline limits apply to the encoded request payload, so base64 overhead
+/// counts against them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttachmentLimits {
+    /// Largest inline (base64) payload the API accepts, if documented.
+    pub max_inline_bytes: Option<u64>,
+    /// Largest file the provider's files API accepts, or `None` if the
+    /// provider has no files API.
+    pub max_upload_bytes: Option<u64>,
+}
+
+const MIB: u64 = 1024 * 1024;
+
+/// Documented attachment limits for a provider.
+///
+/// Providers without published limits get no inline cap and no files API.
+#[must_use]
+pub fn provider_attachment_limits(provider: &str) -> AttachmentLimits {
+    let (max_inline_bytes, max_upload_bytes) = match provider.to_lowercase().as_str() {
+        "openai" => (Some(20 * MIB), Some(512 * MIB)),
+        "anthropic" => (Some(32 * MIB), Some(500 * MIB)),
+        "google" => (Some(20 * MIB), Some(2048 * MIB)),
+        _ => (None, None),
+    };
+    AttachmentLimits {
+        max_inline_bytes,
+        max_upload_bytes,
+    }
+}
+
+/// Attachment limits for a model id/alias.
+///
+/// Per-model values from the registry take precedence over the provider's
+/// defaults. Returns `None` for unknown models.
+#[must_use]
+pub fn attachment_limits(model_id: &str) -> Option<AttachmentLimits> {
+    let info = lookup(model_id)?;
+    let defaults = provider_attachment_limits(info.provider);
+    let (inline, upload) = MODEL_ATTACHMENT_LIMITS
+        .iter()
+        .find(|(id, _, _)| id.eq_ignore_ascii_case(info.id))
+        .map_or((None, None), |(_, inline, upload)| (*inline, *upload));
+    Some(AttachmentLimits {
+        max_inline_bytes: inline.or(defaults.max_inline_bytes),
+        max_upload_bytes: upload.or(defaults.max_upload_bytes),
+    })
+}
+
+/// List prices for a model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    /// Price of uncached input tokens.
+    pub input: f64,
+    /// Price of output tokens, including reasoning tokens.
+    pub output: f64,
+    /// Price of input tokens read from the prompt cache, if discounted.
+    pub cached_input: Option<f64>,
+    /// Price of input tokens written to the prompt cache, if charged extra.
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPricing {
+    /// Estimated cost in USD of `usage` at these prices.
+    ///
+    /// `provider` selects how token counts are reported: Anthropic counts
+    /// cache reads and writes separately from `prompt_tokens`, while other
+    /// providers include cached tokens in it. Gemini reports reasoning tokens
+    /// separately from `completion_tokens`; other providers include them.
+    /// Returns `None` if `usage` has no token counts.
+    #[must_use]
+    pub fn cost(&self, provider: &str, usage: &Usage) -> Option<f64> {
+        if usage.prompt_tokens.is_none() && usage.completion_tokens.is_none() {
+            return None;
+        }
+        let tokens = |count: Option<u32>| count.map_or(0.0, f64::from);
+        let provider = provider.to_lowercase();
+
+        let cache_read = tokens(usage.cache_read_tokens);
+        let cache_write = tokens(usage.cache_write_tokens);
+        let uncached = if provider == "anthropic" {
+            tokens(usage.prompt_tokens)
+        } else {
+            (tokens(usage.prompt_tokens) - cache_read - cache_write).max(0.0)
+        };
+        let mut output = tokens(usage.completion_tokens);
+        if provider == "google" {
+            output += tokens(usage.reasoning_tokens);
+        }
+
+        let total = uncached * self.input
+            + cache_read * self.cached_input.unwrap_or(self.input)
+            + cache_write * self.cache_write.unwrap_or(self.input)
+            + output * self.output;
+        Some(total / 1_000_000.0)
+    }
+}
+
+/// List prices for a model id/alias, if known.
+#[must_use]
+pub fn pricing(model_id: &str) -> Option<ModelPricing> {
+    let id = canonical_model_id(model_id)?;
+    let runtime = with_runtime_models(|models| {
+        models
+            .iter()
+            .find(|m| m.info.id.eq_ignore_ascii_case(id))
+            .and_then(|m| m.pricing)
+    });
+    if runtime.is_some() {
+        return runtime;
+    }
+    MODEL_PRICING
+        .iter()
+        .find(|row| row.id.eq_ignore_ascii_case(id))
+        .and_then(|row| {
+            Some(ModelPricing {
+                input: row.input?,
+                output: row.output?,
+                cached_input: row.cached_input,
+                cache_write: row.cache_write,
+            })
+        })
+}
+
+/// Estimated cost in USD of `usage` on `model_id`.
+///
+/// Returns `None` if the model has no pricing data or `usage` has no token
+/// counts. See [`ModelPricing::cost`] for how token counts are interpreted.
+#[must_use]
+pub fn estimate_cost(model_id: &str, usage: &Usage) -> Option<f64> {
+    let info = lookup(model_id)?;
+    pricing(info.id)?.cost(info.provider, usage)
+}
+
+/// Where a model is in its provider's lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    /// Current and supported.
+    Active,
+    /// Superseded by a newer model but not scheduled for removal.
+    Outdated,
+    /// Deprecated or scheduled for shutdown; still served for now.
+    Deprecated,
+    /// Shut down; requests will fail.
+    Retired,
+}
+
+/// Deprecation status of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelStatus {
+    /// Canonical model ID.
+    pub id: &'static str,
+    /// Lifecycle stage on the date the status was computed.
+    pub lifecycle: Lifecycle,
+    /// Date the provider deprecated the model (`YYYY-MM-DD`).
+    pub deprecation_date: Option<&'static str>,
+    /// Date the provider shuts the model down (`YYYY-MM-DD`).
+    pub shutdown_date: Option<&'static str>,
+    /// Recommended replacement model ID.
+    pub successor: Option<&'static str>,
+}
+
+impl ModelStatus {
+    /// Returns `true` if the model has been shut down.
+    #[must_use]
+    pub fn is_retired(&self) -> bool {
+        self.lifecycle == Lifecycle::Retired
+    }
+
+    /// A message worth logging when the model is used, if it is deprecated
+    /// or retired.
+    #[must_use]
+    pub fn warning(&self) -> Option<String> {
+        let mut message = match (self.lifecycle, self.shutdown_date) {
+            (Lifecycle::Retired, Some(date)) => format!("model {} was retired on {date}", self.id),
+            (Lifecycle::Retired, None) => format!("model {} is retired", self.id),
+            (Lifecycle::Deprecated, Some(date)) => {
+                format!("model {} is deprecated and shuts down on {date}", self.id)
+            }
+            (Lifecycle::Deprecated, None) => format!("model {} is deprecated", self.id),
+            (Lifecycle::Active | Lifecycle::Outdated, _) => return None,
+        };
+        if let Some(successor) = self.successor {
+            message.push_str(&format!("; use {successor} instead"));
+        }
+        Some(message)
+    }
+}
+
+/// Deprecation status of a model id/alias as of today.
+///
+/// Returns `None` for unknown models.
+#[must_use]
+pub fn lookup_status(model_id: &str) -> Option<ModelStatus> {
+    lookup_status_on(model_id, &today())
+}
+
+/// Deprecation status of a model id/alias as of `date` (`YYYY-MM-DD`).
+///
+/// Returns `None` for unknown models.
+#[must_use]
+pub fn lookup_status_on(model_id: &str, date: &str) -> Option<ModelStatus> {
+    let id = canonical_model_id(model_id)?;
+    let (deprecation_date, shutdown_date, successor) = MODEL_LIFECYCLE
+        .iter()
+        .find(|row| row.id.eq_ignore_ascii_case(id))
+        .map_or((None, None, None), |row| {
+            (row.deprecation_date, row.shutdown_date, row.successor)
+        });
+
+    // ISO dates compare correctly as strings.
+    let lifecycle = if shutdown_date.is_some_and(|d| d <= date) {
+        Lifecycle::Retired
+    } else if shutdown_date.is_some() || deprecation_date.is_some_and(|d| d <= date) {
+        Lifecycle::Deprecated
+    } else if successor.is_some() || lookup(id).is_some_and(|m| m.outdated) {
+        Lifecycle::Outdated
+    } else {
+        Lifecycle::Active
+    };
+
+    Some(ModelStatus {
+        id,
+        lifecycle,
+        deprecation_date,
+        shutdown_date,
+        successor,
+    })
+}
+
+/// The model to use in place of a retired one.
+///
+/// Follows successors past models that are also retired. Returns `None` if
+/// `model_id` is unknown, not retired, or has no usable successor.
+#[must_use]
+pub fn replacement_model(model_id: &str) -> Option<&'static str> {
+    let date = today();
+    let mut status = lookup_status_on(model_id, &date)?;
+    // Bounded in case the tables ever contain a successor cycle.
+    for _ in 0..MODEL_LIFECYCLE.len() {
+        if !status.is_retired() {
+            return (!status.id.eq_ignore_ascii_case(model_id)).then_some(status.id);
+        }
+        status = lookup_status_on(status.successor?, &date)?;
+    }
+    None
+}
+
+/// Today's UTC date as `YYYY-MM-DD`.
+fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // Civil-from-days conversion (Howard Hinnant's algorithm).
+    let z = i64::try_from(secs / 86_400).unwrap_or(0) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Tokenizers known to match a model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenizerHint {
+    /// tiktoken encoding name (e.g., "o200k_base").
+    pub tiktoken: Option<&'static str>,
+    /// Hugging Face repository whose `tokenizer.json` matches the model.
+    pub huggingface: Option<&'static str>,
+}
+
+impl TokenizerHint {
+    /// Returns `true` if no tokenizer is known.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.tiktoken.is_none() && self.huggingface.is_none()
+    }
+}
+
+/// Tokenizers known to match a model id/alias.
+///
+/// Returns an empty hint for unknown models and for models whose tokenizer
+/// is not public.
+#[must_use]
+pub fn tokenizer_hint(model_id: &str) -> TokenizerHint {
+    canonical_model_id(model_id)
+        .and_then(|id| {
+            MODEL_TOKENIZERS
+                .iter()
+                .find(|(model_id, _, _)| model_id.eq_ignore_ascii_case(id))
+        })
+        .map_or_else(TokenizerHint::default, |(_, tiktoken, huggingface)| {
+            TokenizerHint {
+                tiktoken: *tiktoken,
+                huggingface: *huggingface,
+            }
+        })
+}
+
+/// Number of tokens `text` takes on `model_id`.
+///
+/// Uses the model's tokenizer when the `tokenizer` feature is enabled and
+/// the encoding is known, and falls back to four bytes per token otherwise.
+#[must_use]
+pub fn estimate_tokens(model_id: &str, text: &str) -> usize {
+    #[cfg(feature = "tokenizer")]
+    if let Some(count) = count_tokens(model_id, text) {
+        return count;
+    }
+    #[cfg(not(feature = "tokenizer"))]
+    let _ = model_id;
+    text.len().div_ceil(4)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,4 +710,76 @@ mod tests {
         assert_eq!(info.id, "deepseek-r1");
         assert!(info.abilities.contains(&Ability::Reasoning));
     }
+
+    #[test]
+    fn test_attachment_limits() {
+        let limits = attachment_limits("gemini-2.5-flash").unwrap();
+        assert_eq!(limits.max_inline_bytes, Some(20 * MIB));
+        assert_eq!(limits.max_upload_bytes, Some(2048 * MIB));
+
+        let limits = attachment_limits("deepseek-chat").unwrap();
+        assert_eq!(limits.max_upload_bytes, None);
+
+        assert!(attachment_limits("unknown-model").is_none());
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        // 1M input and 1M output tokens on GPT-4o.
+        let usage = Usage::new(1_000_000, 1_000_000);
+        let cost = estimate_cost("gpt-4o-2024-08-06", &usage).unwrap();
+        assert!((cost - 12.5).abs() < 1e-9);
+
+        // Half the OpenAI prompt hit the cache.
+        let mut cached = usage.clone();
+        cached.cache_read_tokens = Some(500_000);
+        let cost = estimate_cost("gpt-4o", &cached).unwrap();
+        assert!((cost - 11.875).abs() < 1e-9);
+
+        // Anthropic reports cache reads on top of the prompt.
+        let mut claude = Usage::new(1_000_000, 0);
+        claude.cache_read_tokens = Some(1_000_000);
+        let cost = estimate_cost("claude-sonnet-4", &claude).unwrap();
+        assert!((cost - 3.3).abs() < 1e-9);
+
+        assert!(estimate_cost("unknown-model", &usage).is_none());
+    }
+
+    #[test]
+    fn test_tokenizer_hint() {
+        assert_eq!(tokenizer_hint("gpt-4o").tiktoken, Some("o200k_base"));
+        assert_eq!(tokenizer_hint("gpt-4").tiktoken, Some("cl100k_base"));
+
+        let llama = tokenizer_hint("llama-3.3-70b");
+        assert!(llama.tiktoken.is_none());
+        assert_eq!(llama.huggingface, Some("meta-llama/Llama-3.3-70B-Instruct"));
+
+        assert!(tokenizer_hint("unknown-model").is_empty());
+        assert_eq!(estimate_tokens("unknown-model", "abcdefgh"), 2);
+    }
+
+    #[test]
+    fn test_lookup_status() {
+        let before = lookup_status_on("gpt-4-32k", "2025-01-01").unwrap();
+        assert_eq!(before.lifecycle, Lifecycle::Deprecated);
+        assert_eq!(before.successor, Some("gpt-4.1"));
+        assert!(before.warning().unwrap().contains("2025-06-06"));
+
+        let after = lookup_status_on("gpt-4-32k", "2025-06-06").unwrap();
+        assert!(after.is_retired());
+
+        let active = lookup_status_on("gpt-4o", "2025-01-01").unwrap();
+        assert_eq!(active.lifecycle, Lifecycle::Active);
+        assert!(active.warning().is_none());
+        assert_eq!(
+            lookup_status_on("gpt-3.5-turbo", "2025-01-01")
+                .unwrap()
+                .lifecycle,
+            Lifecycle::Outdated
+        );
+
+        assert_eq!(replacement_model("claude-2"), Some("claude-sonnet-4.5"));
+        assert!(replacement_model("gpt-4o").is_none());
+        assert_eq!(today().len(), 10);
+    }
 }
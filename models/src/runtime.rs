@@ -0,0 +1,388 @@
+//! Runtime additions to the compiled-in registry.
+//!
+//! New models ship weekly, faster than crate releases. A JSON manifest in the
+//! [models.dev](https://models.dev) `api.json` format can be loaded at
+//! runtime and is merged over the compiled tables, so [`lookup`](crate::lookup)
+//! and the other queries see the new entries immediately:
+//!
+//! ```ignore
+//! aither_models::load_manifest_file("models.json")?;
+//! // With the `remote` feature:
+//! aither_models::load_manifest_url("https://models.dev/api.json").await?;
+//! ```
+//!
+//! A manifest entry with the same ID as a compiled model updates it (context
+//! window, output limit, abilities, prices) while keeping its tiers and
+//! aliases. Only entries from the compiled model's own provider are applied,
+//! since resellers list the same IDs with their own limits and prices.
+//!
+//! Custom models, such as fine-tunes or models behind a proxy with a
+//! nonstandard name, can be added directly with [`register_model`]:
+//!
+//! ```ignore
+//! aither_models::register_model_with_aliases(info, &["ft-support-bot"]);
+//! ```
+//!
+//! Runtime entries are leaked to hand out `&'static` references like the
+//! compiled tables, so reloading is meant for occasional refreshes rather
+//! than a hot loop.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+
+use serde::Deserialize;
+
+use crate::{Ability, MODELS, ModelInfo, ModelPricing};
+
+/// A model added or updated at runtime.
+#[derive(Debug, Clone)]
+pub(crate) struct RuntimeModel {
+    pub(crate) info: &'static ModelInfo,
+    /// Lowercase aliases resolving to `info.id`.
+    pub(crate) aliases: Vec<String>,
+    pub(crate) pricing: Option<ModelPricing>,
+}
+
+static RUNTIME_MODELS: RwLock<Vec<RuntimeModel>> = RwLock::new(Vec::new());
+
+/// Run `f` over the runtime models.
+pub(crate) fn with_runtime_models<R>(f: impl FnOnce(&[RuntimeModel]) -> R) -> R {
+    f(&RUNTIME_MODELS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Add runtime models, replacing earlier runtime entries with the same ID.
+///
+/// Aliases of a replaced entry carry over to its replacement.
+pub(crate) fn insert_runtime_models(models: Vec<RuntimeModel>) {
+    let mut runtime = RUNTIME_MODELS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    for mut model in models {
+        if let Some(pos) = runtime
+            .iter()
+            .position(|existing| existing.info.id.eq_ignore_ascii_case(model.info.id))
+        {
+            for alias in runtime.remove(pos).aliases {
+                if !model.aliases.contains(&alias) {
+                    model.aliases.push(alias);
+                }
+            }
+        }
+        runtime.push(model);
+    }
+}
+
+/// Register a custom model.
+///
+/// The model takes part in [`lookup`](crate::lookup), alias resolution and
+/// the provider, ability and tier queries. Registering an ID that already
+/// exists replaces its info, keeping any aliases added at runtime.
+pub fn register_model(info: ModelInfo) {
+    register_model_with_aliases(info, &[]);
+}
+
+/// Register a custom model reachable under additional `aliases`.
+///
+/// Aliases are matched case-insensitively.
+pub fn register_model_with_aliases(info: ModelInfo, aliases: &[&str]) {
+    insert_runtime_models(vec![RuntimeModel {
+        info: Box::leak(Box::new(info)),
+        aliases: aliases.iter().map(|alias| alias.to_lowercase()).collect(),
+        pricing: None,
+    }]);
+}
+
+/// Remove a model added at runtime.
+///
+/// Compiled-in models cannot be removed; an override of one is dropped and
+/// the compiled entry becomes visible again. Returns `true` if an entry was
+/// removed.
+pub fn unregister_model(model_id: &str) -> bool {
+    let mut runtime = RUNTIME_MODELS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    let before = runtime.len();
+    runtime.retain(|model| !model.info.id.eq_ignore_ascii_case(model_id));
+    runtime.len() != before
+}
+
+/// Remove every model added at runtime, restoring the compiled-in registry.
+pub fn clear_runtime_models() {
+    RUNTIME_MODELS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
+
+/// Error loading a model manifest.
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+    /// The manifest file could not be read.
+    #[error("failed to read model manifest: {0}")]
+    Io(#[from] std::io::Error),
+    /// The manifest is not valid JSON in the expected format.
+    #[error("invalid model manifest: {0}")]
+    Parse(#[from] serde_json::Error),
+    /// The manifest could not be downloaded.
+    #[cfg(feature = "remote")]
+    #[error("failed to fetch model manifest: {0}")]
+    Http(String),
+}
+
+/// Merge a models.dev-format manifest over the registry.
+///
+/// Returns the number of models added or updated.
+///
+/// # Errors
+///
+/// Returns an error if `json` is not a valid manifest; the registry is left
+/// unchanged in that case.
+pub fn load_manifest(json: &str) -> Result<usize, ManifestError> {
+    let manifest: Manifest = serde_json::from_str(json)?;
+    Ok(apply_manifest(manifest))
+}
+
+/// Merge a manifest file over the registry.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a valid manifest.
+pub fn load_manifest_file(path: impl AsRef<Path>) -> Result<usize, ManifestError> {
+    let json = std::fs::read_to_string(path)?;
+    load_manifest(&json)
+}
+
+/// Download a manifest and merge it over the registry.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response is not a valid manifest.
+#[cfg(feature = "remote")]
+pub async fn load_manifest_url(url: &str) -> Result<usize, ManifestError> {
+    use zenwave::{Client, client, header};
+
+    let mut backend = client();
+    let manifest: Manifest = backend
+        .get(url)
+        .map_err(|e| ManifestError::Http(e.to_string()))?
+        .header(header::ACCEPT.as_str(), "application/json")
+        .map_err(|e| ManifestError::Http(e.to_string()))?
+        .json()
+        .await
+        .map_err(|e| ManifestError::Http(e.to_string()))?;
+    Ok(apply_manifest(manifest))
+}
+
+/// models.dev `api.json`: providers keyed by ID.
+type Manifest = BTreeMap<String, ManifestProvider>;
+
+#[derive(Debug, Deserialize)]
+struct ManifestProvider {
+    #[serde(default)]
+    models: BTreeMap<String, ManifestModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestModel {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    attachment: bool,
+    #[serde(default)]
+    reasoning: bool,
+    #[serde(default)]
+    tool_call: bool,
+    #[serde(default)]
+    cost: Option<ManifestCost>,
+    #[serde(default)]
+    limit: Option<ManifestLimit>,
+    #[serde(default)]
+    modalities: Option<ManifestModalities>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestCost {
+    input: Option<f64>,
+    output: Option<f64>,
+    cache_read: Option<f64>,
+    cache_write: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestLimit {
+    context: Option<u32>,
+    output: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestModalities {
+    #[serde(default)]
+    input: Vec<String>,
+}
+
+fn apply_manifest(manifest: Manifest) -> usize {
+    let mut seen = HashSet::new();
+    let mut models = Vec::new();
+    // Providers are visited in ID order, so the first listing of a new
+    // model ID wins deterministically.
+    for (provider, entry) in manifest {
+        for (key, model) in entry.models {
+            let id = model.id.clone().unwrap_or(key);
+            let compiled = MODELS.iter().find(|m| m.id.eq_ignore_ascii_case(&id));
+            if compiled.is_some_and(|m| !m.provider.eq_ignore_ascii_case(&provider)) {
+                continue;
+            }
+            if !seen.insert(id.to_lowercase()) {
+                continue;
+            }
+            models.push(runtime_model(&provider, id, model, compiled));
+        }
+    }
+    let count = models.len();
+    insert_runtime_models(models);
+    count
+}
+
+fn runtime_model(
+    provider: &str,
+    id: String,
+    model: ManifestModel,
+    compiled: Option<&'static ModelInfo>,
+) -> RuntimeModel {
+    let mut abilities: Vec<Ability> = compiled.map_or_else(Vec::new, |m| m.abilities.to_vec());
+    let inputs = model.modalities.map(|m| m.input).unwrap_or_default();
+    let flags = [
+        (model.tool_call, Ability::ToolUse),
+        (model.reasoning, Ability::Reasoning),
+        (
+            model.attachment || inputs.iter().any(|m| m == "image"),
+            Ability::Vision,
+        ),
+        (inputs.iter().any(|m| m == "audio"), Ability::Audio),
+        (inputs.iter().any(|m| m == "video"), Ability::Video),
+        (inputs.iter().any(|m| m == "pdf"), Ability::Pdf),
+    ];
+    for (enabled, ability) in flags {
+        if enabled && !abilities.contains(&ability) {
+            abilities.push(ability);
+        }
+    }
+
+    let limit = model.limit;
+    let info = ModelInfo {
+        id: leak(id.clone()),
+        name: match (model.name, compiled) {
+            (Some(name), _) => leak(name),
+            (None, Some(m)) => m.name,
+            (None, None) => leak(id),
+        },
+        provider: compiled.map_or_else(|| leak(provider.to_string()), |m| m.provider),
+        context_window: limit
+            .as_ref()
+            .and_then(|l| l.context)
+            .or(compiled.map(|m| m.context_window))
+            .unwrap_or(0),
+        max_output_tokens: limit
+            .as_ref()
+            .and_then(|l| l.output)
+            .or(compiled.and_then(|m| m.max_output_tokens)),
+        tiers: compiled.map(|m| m.tiers).unwrap_or_default(),
+        abilities: Box::leak(abilities.into_boxed_slice()),
+        outdated: compiled.is_some_and(|m| m.outdated),
+    };
+
+    let pricing = model.cost.and_then(|cost| {
+        Some(ModelPricing {
+            input: cost.input?,
+            output: cost.output?,
+            cached_input: cost.cache_read,
+            cache_write: cost.cache_write,
+        })
+    });
+
+    RuntimeModel {
+        info: Box::leak(Box::new(info)),
+        aliases: Vec::new(),
+        pricing,
+    }
+}
+
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ModelTier, Usage, estimate_cost, lookup, models_by_tier, models_for_provider};
+
+    #[test]
+    fn test_load_manifest() {
+        let json = r#"{
+            "acme": {
+                "id": "acme",
+                "models": {
+                    "acme-runtime-1": {
+                        "id": "acme-runtime-1",
+                        "name": "Acme Runtime 1",
+                        "tool_call": true,
+                        "reasoning": true,
+                        "cost": { "input": 1.0, "output": 2.0 },
+                        "limit": { "context": 64000, "output": 8000 },
+                        "modalities": { "input": ["text", "image"] }
+                    }
+                }
+            },
+            "reseller": {
+                "models": {
+                    "gpt-4o": { "limit": { "context": 1 } }
+                }
+            }
+        }"#;
+        assert_eq!(load_manifest(json).unwrap(), 1);
+
+        let info = lookup("acme-runtime-1").unwrap();
+        assert_eq!(info.provider, "acme");
+        assert_eq!(info.context_window, 64_000);
+        assert!(info.abilities.contains(&Ability::Vision));
+        assert!(models_for_provider("acme").any(|m| m.id == "acme-runtime-1"));
+
+        let cost = estimate_cost("acme-runtime-1", &Usage::new(1_000_000, 1_000_000)).unwrap();
+        assert!((cost - 3.0).abs() < 1e-9);
+
+        // Resellers don't override compiled models.
+        assert_eq!(lookup("gpt-4o").unwrap().context_window, 128_000);
+    }
+
+    #[test]
+    fn test_register_model() {
+        register_model_with_aliases(
+            ModelInfo {
+                id: "ft:acme-support-7b",
+                name: "Acme Support Fine-tune",
+                provider: "acme-proxy",
+                context_window: 32_000,
+                max_output_tokens: Some(4_096),
+                tiers: &[ModelTier::Fast],
+                abilities: &[Ability::ToolUse],
+                outdated: false,
+            },
+            &["Support-Bot"],
+        );
+
+        let info = lookup("support-bot").unwrap();
+        assert_eq!(info.id, "ft:acme-support-7b");
+        assert_eq!(info.context_window, 32_000);
+        assert!(models_by_tier(ModelTier::Fast).any(|m| m.id == "ft:acme-support-7b"));
+
+        assert!(unregister_model("ft:acme-support-7b"));
+        assert!(lookup("support-bot").is_none());
+        assert!(!unregister_model("gpt-4o"));
+        assert!(lookup("gpt-4o").is_some());
+    }
+}
@@ -0,0 +1,56 @@
+//! Token counting with tiktoken encodings.
+//!
+//! Encodings are built on first use and shared afterwards; building one
+//! takes tens of milliseconds, counting with it is cheap.
+
+use std::sync::OnceLock;
+
+use tiktoken_rs::CoreBPE;
+
+use crate::tokenizer_hint;
+
+/// Number of tokens `text` takes on `model_id`.
+///
+/// Returns `None` if the model has no known tiktoken encoding; callers fall
+/// back to a heuristic such as [`estimate_tokens`](crate::estimate_tokens).
+#[must_use]
+pub fn count_tokens(model_id: &str, text: &str) -> Option<usize> {
+    count_tokens_with_encoding(tokenizer_hint(model_id).tiktoken?, text)
+}
+
+/// Number of tokens `text` takes in the tiktoken `encoding`.
+///
+/// Returns `None` for unsupported encoding names.
+#[must_use]
+pub fn count_tokens_with_encoding(encoding: &str, text: &str) -> Option<usize> {
+    Some(bpe(encoding)?.encode_ordinary(text).len())
+}
+
+fn bpe(encoding: &str) -> Option<&'static CoreBPE> {
+    static O200K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+    static CL100K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+    static P50K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+    static R50K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+
+    let (cell, build): (_, fn() -> _) = match encoding {
+        "o200k_base" => (&O200K, tiktoken_rs::o200k_base),
+        "cl100k_base" => (&CL100K, tiktoken_rs::cl100k_base),
+        "p50k_base" => (&P50K, tiktoken_rs::p50k_base),
+        "r50k_base" => (&R50K, tiktoken_rs::r50k_base),
+        _ => return None,
+    };
+    cell.get_or_init(|| build().ok()).as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_tokens() {
+        let count = count_tokens("gpt-4o", "Hello, world!").unwrap();
+        assert!(count > 0 && count < 10);
+        assert!(count_tokens("llama-3.3-70b", "Hello").is_none());
+        assert!(count_tokens_with_encoding("unknown", "Hello").is_none());
+    }
+}
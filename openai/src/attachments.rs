@@ -8,11 +8,11 @@ use crate::client::Config;
 use aither_core::llm::Message;
 
 #[cfg(not(target_arch = "wasm32"))]
-use crate::files::{FilePurpose, FilesConfig, upload_file};
+use crate::files::{FilePurpose, FilesConfig, delete_file, upload_file};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::mime::mime_from_path;
 #[cfg(not(target_arch = "wasm32"))]
-use aither_attachments::{FileCache, default_cache_dir};
+use aither_attachments::{AttachmentManager, AttachmentUploader, CachePolicy, UploadedFile};
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 
@@ -37,10 +37,11 @@ pub async fn resolve_messages(
         return Ok(messages);
     }
 
-    let mut cache = FileCache::open(default_cache_dir())
-        .await
-        .map_err(OpenAIError::from)?;
-    let mut cache_dirty = cache.prune_expired();
+    let uploader = OpenAIUploader {
+        files_cfg: build_files_config(cfg),
+    };
+    let mut manager = AttachmentManager::open(uploader).await?;
+    manager.gc(&CachePolicy::default()).await;
 
     let mut resolved = Vec::with_capacity(messages.len());
     for message in messages {
@@ -55,9 +56,7 @@ pub async fn resolve_messages(
 
         let mut next_attachments = Vec::with_capacity(attachments.len());
         for attachment in attachments {
-            let resolved_url = resolve_attachment(cfg, &mut cache, &attachment).await?;
-            cache_dirty |= resolved_url.cache_updated;
-            next_attachments.push(resolved_url.url);
+            next_attachments.push(resolve_attachment(&mut manager, &attachment).await?);
         }
 
         resolved.push(Message::User {
@@ -66,79 +65,63 @@ pub async fn resolve_messages(
         });
     }
 
-    if cache_dirty {
-        cache.save().await.map_err(OpenAIError::from)?;
-    }
-
+    manager.save().await?;
     Ok(resolved)
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-struct ResolvedUrl {
-    url: Url,
-    cache_updated: bool,
-}
-
 #[cfg(not(target_arch = "wasm32"))]
 async fn resolve_attachment(
-    cfg: &Config,
-    cache: &mut FileCache,
+    manager: &mut AttachmentManager<OpenAIUploader>,
     attachment: &Url,
-) -> Result<ResolvedUrl, OpenAIError> {
+) -> Result<Url, OpenAIError> {
     match attachment.scheme() {
         "file" => {
             let path = attachment.to_file_path().map_err(|()| {
                 OpenAIError::Api("Attachment file URL could not be converted to path".to_string())
             })?;
-            resolve_file_attachment(cfg, cache, &path).await
+            manager.resolve(&path).await
         }
-        "http" | "https" | "data" => Ok(ResolvedUrl {
-            url: attachment.clone(),
-            cache_updated: false,
-        }),
+        "http" | "https" | "data" => Ok(attachment.clone()),
         other => Err(OpenAIError::Api(format!(
             "Unsupported attachment URL scheme: {other}"
         ))),
     }
 }
 
+/// Uploads attachments through the OpenAI Files API.
 #[cfg(not(target_arch = "wasm32"))]
-async fn resolve_file_attachment(
-    cfg: &Config,
-    cache: &mut FileCache,
-    path: &Path,
-) -> Result<ResolvedUrl, OpenAIError> {
-    let kind = file_kind_for_path(path)?;
-    let provider = "openai";
-
-    if let Some(entry) = cache.get(path, provider).await.map_err(OpenAIError::from)? {
-        let url = build_openai_file_url(kind, &entry.reference)?;
-        return Ok(ResolvedUrl {
-            url,
-            cache_updated: false,
-        });
+struct OpenAIUploader {
+    files_cfg: FilesConfig,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AttachmentUploader for OpenAIUploader {
+    type Error = OpenAIError;
+
+    fn provider(&self) -> &str {
+        "openai"
     }
 
-    let files_cfg = build_files_config(cfg);
-    let purpose = purpose_for_kind(kind);
-    let file = upload_file(&files_cfg, path, purpose).await?;
-    if !file.is_ready() {
-        return Err(OpenAIError::Api(format!(
-            "OpenAI file upload not ready (status: {:?})",
-            file.status
-        )));
+    async fn upload<'a>(&'a self, path: &'a Path) -> Result<UploadedFile, OpenAIError> {
+        let purpose = purpose_for_kind(file_kind_for_path(path)?);
+        let file = upload_file(&self.files_cfg, path, purpose).await?;
+        if !file.is_ready() {
+            return Err(OpenAIError::Api(format!(
+                "OpenAI file upload not ready (status: {:?})",
+                file.status
+            )));
+        }
+        Ok(UploadedFile::new(file.id, None))
     }
 
-    cache
-        .insert(path, provider, file.id.clone(), None)
-        .await
-        .map_err(OpenAIError::from)?;
+    fn to_url(&self, path: &Path, reference: &str) -> Result<Url, OpenAIError> {
+        build_openai_file_url(file_kind_for_path(path)?, reference)
+    }
 
-    let url = build_openai_file_url(kind, &file.id)?;
-    Ok(ResolvedUrl {
-        url,
-        cache_updated: true,
-    })
+    async fn delete<'a>(&'a self, reference: &'a str) -> Result<(), OpenAIError> {
+        delete_file(&self.files_cfg, reference).await?;
+        Ok(())
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -37,7 +37,7 @@ async fn synthesize(cfg: Arc<Config>, text: String) -> Result<Vec<u8>, OpenAIErr
     let mut builder = backend
         .post(endpoint)
         .map_err(OpenAIError::Http)?
-        .header(header::AUTHORIZATION.as_str(), cfg.request_auth())
+        .header(cfg.auth_header_name(), cfg.request_auth())
         .map_err(OpenAIError::Http)?
         .header(header::USER_AGENT.as_str(), "aither-openai/0.1")
         .map_err(OpenAIError::Http)?;
@@ -69,7 +69,7 @@ async fn transcribe_once(cfg: Arc<Config>, audio: Vec<u8>) -> Result<String, Ope
     let mut builder = backend
         .post(endpoint)
         .map_err(OpenAIError::Http)?
-        .header(header::AUTHORIZATION.as_str(), cfg.request_auth())
+        .header(cfg.auth_header_name(), cfg.request_auth())
         .map_err(OpenAIError::Http)?
         .header(header::USER_AGENT.as_str(), "aither-openai/0.1")
         .map_err(OpenAIError::Http)?;
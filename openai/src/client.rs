@@ -1,7 +1,8 @@
 use crate::{
     DEEPSEEK_BASE_URL, DEFAULT_AUDIO_FORMAT, DEFAULT_AUDIO_MODEL, DEFAULT_AUDIO_VOICE,
     DEFAULT_BASE_URL, DEFAULT_EMBEDDING_DIM, DEFAULT_EMBEDDING_MODEL, DEFAULT_IMAGE_MODEL,
-    DEFAULT_MODEL, DEFAULT_MODERATION_MODEL, DEFAULT_TRANSCRIPTION_MODEL, OPENROUTER_BASE_URL,
+    DEFAULT_MODEL, DEFAULT_MODERATION_MODEL, DEFAULT_RERANK_MODEL, DEFAULT_TRANSCRIPTION_MODEL,
+    OPENROUTER_BASE_URL,
     error::OpenAIError,
     request::{
         ChatCompletionRequest, ChatMessagePayload, ParameterSnapshot, ResponsesInputItem,
@@ -14,9 +15,9 @@ use crate::{
     },
 };
 use aither_core::{
-    LanguageModel,
+    LanguageModel, ProviderRetryInfo, RetryInfo,
     llm::{
-        Event, LLMRequest, ToolCall, Usage,
+        Citation, Event, LLMRequest, ToolCall, ToolCallDelta, Usage,
         model::{Ability, Profile as ModelProfile, ToolChoice},
         oneshot,
     },
@@ -69,40 +70,13 @@ impl RetryConfig {
     }
 }
 
-/// Check if an error is retryable.
-const fn is_retryable_error(err: &OpenAIError) -> bool {
-    match err {
-        // Network/transport errors are retryable
-        OpenAIError::Http(_) => true,
-        // Body errors (connection issues) may be retryable
-        OpenAIError::Body(_) => true,
-        // SSE stream errors may be retryable
-        OpenAIError::Stream(_) => true,
-        // Rate limit and server errors are retryable
-        OpenAIError::RateLimit { .. } => true,
-        OpenAIError::ServerError { .. } => true,
-        // Timeout is retryable
-        OpenAIError::Timeout => true,
-        // API errors are generally not retryable (bad request, auth, etc.)
-        OpenAIError::Api(_) => false,
-        // Parse errors are not retryable
-        OpenAIError::Json(_) => false,
-        // Decode errors are not retryable
-        OpenAIError::Decode(_) => false,
-    }
-}
-
-/// Get retry delay for an error, respecting Retry-After header for rate limits.
-fn get_retry_delay(err: &OpenAIError, attempt: u32, config: &RetryConfig) -> Duration {
-    if let OpenAIError::RateLimit {
-        retry_after: Some(delay),
-        ..
-    } = err
-    {
-        // Respect Retry-After header, but cap at max_delay
-        return (*delay).min(config.max_delay);
-    }
-    config.delay_for_attempt(attempt)
+/// Get retry delay for an error, respecting the provider's retry hint.
+fn get_retry_delay(retry: ProviderRetryInfo, attempt: u32, config: &RetryConfig) -> Duration {
+    // Respect Retry-After, but cap at max_delay
+    retry.retry_after.map_or_else(
+        || config.delay_for_attempt(attempt),
+        |delay| delay.min(config.max_delay),
+    )
 }
 
 /// Sleep for the given duration (runtime-agnostic).
@@ -121,7 +95,7 @@ fn map_zenwave_error(error: zenwave::Error) -> OpenAIError {
     if matches!(error, zenwave::Error::Timeout) {
         OpenAIError::Timeout
     } else {
-        OpenAIError::Http(error)
+        OpenAIError::from_http(error)
     }
 }
 
@@ -173,8 +147,10 @@ where
             Ok(stream) => return Ok(stream),
             Err(err) => {
                 // Check if we should retry
-                if attempt < retry_config.max_retries && is_retryable_error(&err) {
-                    let delay = get_retry_delay(&err, attempt, retry_config);
+                if let Some(retry) = err.retry_info()
+                    && attempt < retry_config.max_retries
+                {
+                    let delay = get_retry_delay(retry, attempt, retry_config);
                     tracing::warn!(
                         attempt = attempt + 1,
                         max_retries = retry_config.max_retries,
@@ -333,6 +309,16 @@ impl OpenAI {
         self
     }
 
+    /// Override the rerank model identifier.
+    ///
+    /// `OpenAI` has no rerank API; this is for compatible servers that expose
+    /// `/rerank`, such as vLLM or Jina.
+    #[must_use]
+    pub fn with_rerank_model(mut self, model: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner).rerank_model = sanitize_model(model);
+        self
+    }
+
     pub(crate) fn config(&self) -> Arc<Config> {
         self.inner.clone()
     }
@@ -443,6 +429,12 @@ impl LanguageModel for OpenAI {
     fn profile(&self) -> impl Future<Output = ModelProfile> + Send {
         let cfg = self.inner.clone();
         async move {
+            if let Some(warning) =
+                aither_models::lookup_status(&cfg.chat_model).and_then(|status| status.warning())
+            {
+                tracing::warn!("{warning}");
+            }
+
             // Try to fetch context window from API, fallback to models database
             let context_length = match fetch_model_context_length(&cfg).await {
                 Ok(len) => len,
@@ -489,10 +481,7 @@ async fn fetch_model_context_length(cfg: &Config) -> Result<u32, OpenAIError> {
         backend
             .get(&url)
             .map_err(OpenAIError::Http)?
-            .header(
-                header::AUTHORIZATION.as_str(),
-                format!("Bearer {}", cfg.api_key),
-            )
+            .header(cfg.auth_header_name(), cfg.request_auth())
             .map_err(OpenAIError::Http)?
             .json(),
     )
@@ -560,7 +549,7 @@ async fn chat_completions_request(
 
     let build_result = backend
         .post(endpoint)
-        .and_then(|b| b.header(header::AUTHORIZATION.as_str(), cfg.request_auth()))
+        .and_then(|b| b.header(cfg.auth_header_name(), cfg.request_auth()))
         .and_then(|b| b.header(header::USER_AGENT.as_str(), "aither-openai/0.1"))
         .and_then(|b| b.header(header::ACCEPT.as_str(), "text/event-stream"));
 
@@ -681,6 +670,11 @@ fn chat_completions_stream_inner(
                                             }
                                             if let Some(args) = &function.arguments {
                                                 acc.arguments.push_str(args);
+                                                if let (Some(id), Some(name)) = (&acc.id, &acc.name)
+                                                    && !args.is_empty()
+                                                {
+                                                    yield Ok(Event::ToolCallDelta(ToolCallDelta::new(id, name, args.as_str())));
+                                                }
                                             }
                                         }
                                     }
@@ -788,6 +782,18 @@ fn usage_from_responses(raw: &ResponsesUsage) -> Usage {
     }
 }
 
+/// Converts a `url_citation` annotation from web search into a citation.
+fn url_citation(annotation: &serde_json::Value) -> Option<Citation> {
+    if annotation["type"] != "url_citation" {
+        return None;
+    }
+    let citation = Citation::new(annotation["url"].as_str()?);
+    Some(match annotation["title"].as_str() {
+        Some(title) => citation.title(title),
+        None => citation,
+    })
+}
+
 fn mark_and_emit_done_function_call(
     function_calls: &mut HashMap<String, FunctionCallAccumulator>,
     id: String,
@@ -836,7 +842,7 @@ async fn responses_request(cfg: &Config, request: &ResponsesRequest) -> SseStrea
 
     let build_result = backend
         .post(endpoint)
-        .and_then(|b| b.header(header::AUTHORIZATION.as_str(), cfg.request_auth()))
+        .and_then(|b| b.header(cfg.auth_header_name(), cfg.request_auth()))
         .and_then(|b| b.header(header::USER_AGENT.as_str(), "aither-openai/0.1"))
         .and_then(|b| b.header(header::ACCEPT.as_str(), "text/event-stream"));
 
@@ -911,6 +917,7 @@ fn responses_stream_inner(
         let mut function_calls: HashMap<String, FunctionCallAccumulator> = HashMap::new();
         let mut usage: Option<Usage> = None;
         let mut usage_emitted = false;
+        let mut cited_urls: Vec<String> = Vec::new();
 
         while let Some(event) = sse_stream.next().await {
             match event {
@@ -948,6 +955,14 @@ fn responses_stream_inner(
                                         yield Ok(Event::Text(delta));
                                     }
                                 }
+                                ResponsesStreamEvent::OutputTextAnnotationAdded { annotation } => {
+                                    if let Some(citation) = url_citation(&annotation)
+                                        && !cited_urls.contains(&citation.url)
+                                    {
+                                        cited_urls.push(citation.url.clone());
+                                        yield Ok(Event::Citation(citation));
+                                    }
+                                }
                                 ResponsesStreamEvent::ReasoningTextDelta { delta, .. } |
                                 ResponsesStreamEvent::ReasoningSummaryTextDelta { delta, .. } => {
                                     if include_reasoning && !delta.is_empty() {
@@ -965,6 +980,11 @@ fn responses_stream_inner(
                                 ResponsesStreamEvent::FunctionCallArgumentsDelta { delta, item_id, .. } => {
                                     let acc = function_calls.entry(item_id).or_default();
                                     acc.arguments.push_str(&delta);
+                                    if let (Some(call_id), Some(name)) = (&acc.call_id, &acc.name)
+                                        && !delta.is_empty()
+                                    {
+                                        yield Ok(Event::ToolCallDelta(ToolCallDelta::new(call_id, name, delta)));
+                                    }
                                 }
                                 ResponsesStreamEvent::FunctionCallArgumentsDone { arguments, item_id, .. } => {
                                     let acc = function_calls.entry(item_id).or_default();
@@ -1156,8 +1176,10 @@ pub struct Builder {
     audio_format: String,
     transcription_model: String,
     moderation_model: String,
+    rerank_model: String,
     legacy_max_tokens: bool,
     organization: Option<String>,
+    auth_header: Option<String>,
     native_abilities: Vec<Ability>,
     retry: RetryConfig,
     request_timeout: Duration,
@@ -1181,8 +1203,10 @@ impl Builder {
             audio_format: DEFAULT_AUDIO_FORMAT.to_string(),
             transcription_model: DEFAULT_TRANSCRIPTION_MODEL.to_string(),
             moderation_model: DEFAULT_MODERATION_MODEL.to_string(),
+            rerank_model: DEFAULT_RERANK_MODEL.to_string(),
             legacy_max_tokens: false,
             organization: None,
+            auth_header: None,
             native_abilities: Vec::new(),
             retry: RetryConfig::default(),
             request_timeout: DEFAULT_REQUEST_TIMEOUT,
@@ -1286,6 +1310,13 @@ impl Builder {
         self
     }
 
+    /// Select the rerank model identifier for servers that expose `/rerank`.
+    #[must_use]
+    pub fn rerank_model(mut self, model: impl Into<String>) -> Self {
+        self.rerank_model = sanitize_model(model);
+        self
+    }
+
     /// Attach an `OpenAI` organization header.
     #[must_use]
     pub fn organization(mut self, organization: impl Into<String>) -> Self {
@@ -1293,6 +1324,15 @@ impl Builder {
         self
     }
 
+    /// Send the API key verbatim in header `name` instead of `Authorization: Bearer`.
+    ///
+    /// Some OpenAI-compatible gateways (e.g., Azure's `api-key`) expect this.
+    #[must_use]
+    pub fn auth_header(mut self, name: impl Into<String>) -> Self {
+        self.auth_header = Some(name.into());
+        self
+    }
+
     /// Declare extra native capabilities (e.g., web search, PDF understanding) supported by the upstream model.
     #[must_use]
     pub fn native_capabilities(mut self, abilities: impl IntoIterator<Item = Ability>) -> Self {
@@ -1367,8 +1407,10 @@ impl Builder {
                 audio_format: self.audio_format,
                 transcription_model: self.transcription_model,
                 moderation_model: self.moderation_model,
+                rerank_model: self.rerank_model,
                 legacy_max_tokens: self.legacy_max_tokens,
                 organization: self.organization,
+                auth_header: self.auth_header,
                 native_abilities: self.native_abilities,
                 retry: self.retry,
                 request_timeout: self.request_timeout,
@@ -1391,8 +1433,10 @@ pub struct Config {
     pub(crate) audio_format: String,
     pub(crate) transcription_model: String,
     pub(crate) moderation_model: String,
+    pub(crate) rerank_model: String,
     pub(crate) legacy_max_tokens: bool,
     pub(crate) organization: Option<String>,
+    pub(crate) auth_header: Option<String>,
     pub(crate) native_abilities: Vec<Ability>,
     pub(crate) retry: RetryConfig,
     pub(crate) request_timeout: Duration,
@@ -1407,8 +1451,18 @@ impl Config {
         )
     }
 
+    pub(crate) fn auth_header_name(&self) -> &str {
+        self.auth_header
+            .as_deref()
+            .unwrap_or(header::AUTHORIZATION.as_str())
+    }
+
     pub(crate) fn request_auth(&self) -> String {
-        format!("Bearer {}", self.api_key)
+        if self.auth_header.is_some() {
+            self.api_key.clone()
+        } else {
+            format!("Bearer {}", self.api_key)
+        }
     }
 }
 
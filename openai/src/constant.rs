@@ -166,3 +166,21 @@ pub const MODERATION_LATEST: &str = "omni-moderation-latest";
 
 /// Legacy text-only moderation model.
 pub const MODERATION_TEXT: &str = "text-moderation-latest";
+
+// ============================================================
+// 11. REALTIME (Speech-to-Speech)
+// ============================================================
+
+/// Realtime speech-to-speech model.
+pub const REALTIME: &str = "gpt-realtime";
+
+/// Cheaper, faster realtime model.
+pub const REALTIME_MINI: &str = "gpt-realtime-mini";
+
+// ============================================================
+// 12. RERANKING (OpenAI-compatible servers)
+// ============================================================
+
+/// Multilingual BGE reranker, served by vLLM, Jina and other `/rerank`
+/// endpoints. `OpenAI` itself has no rerank API.
+pub const RERANK_BGE_V2_M3: &str = "bge-reranker-v2-m3";
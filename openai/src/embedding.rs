@@ -16,19 +16,41 @@ impl EmbeddingModel for OpenAI {
         let cfg = self.config();
         let input = text.to_owned();
         async move {
-            let vector = embed_once(cfg, input).await?;
+            let vector = embed_inputs(cfg, &[input])
+                .await?
+                .pop()
+                .ok_or_else(|| OpenAIError::Api("embedding response missing vector data".into()))?;
             Ok(vector)
         }
     }
+
+    fn embed_batch(
+        &self,
+        texts: &[&str],
+    ) -> impl core::future::Future<Output = CoreResult<Vec<Vec<f32>>>> + Send {
+        let cfg = self.config();
+        let texts: Vec<String> = texts.iter().map(|&text| text.to_owned()).collect();
+        async move {
+            let mut vectors = Vec::with_capacity(texts.len());
+            for chunk in texts.chunks(MAX_BATCH_SIZE) {
+                vectors.extend(embed_inputs(cfg.clone(), chunk).await?);
+            }
+            Ok(vectors)
+        }
+    }
 }
 
-async fn embed_once(cfg: Arc<Config>, input: String) -> Result<Vec<f32>, OpenAIError> {
+/// Most inputs the embeddings endpoint accepts in one request.
+const MAX_BATCH_SIZE: usize = 2048;
+
+/// Embed every input in one request, returning vectors in input order.
+async fn embed_inputs(cfg: Arc<Config>, input: &[String]) -> Result<Vec<Vec<f32>>, OpenAIError> {
     let endpoint = cfg.request_url("/embeddings");
     let mut backend = client();
     let mut builder = backend
         .post(endpoint)
         .map_err(OpenAIError::Http)?
-        .header(header::AUTHORIZATION.as_str(), cfg.request_auth())
+        .header(cfg.auth_header_name(), cfg.request_auth())
         .map_err(OpenAIError::Http)?
         .header(header::USER_AGENT.as_str(), "aither-openai/0.1")
         .map_err(OpenAIError::Http)?;
@@ -39,7 +61,7 @@ async fn embed_once(cfg: Arc<Config>, input: String) -> Result<Vec<f32>, OpenAIE
     }
     let request = EmbeddingRequest {
         model: &cfg.embedding_model,
-        input: &input,
+        input,
         dimensions: embedding_dimensions_for(&cfg.embedding_model, cfg.embedding_dimensions),
     };
     let response: EmbeddingResponse = builder
@@ -48,18 +70,22 @@ async fn embed_once(cfg: Arc<Config>, input: String) -> Result<Vec<f32>, OpenAIE
         .json()
         .await
         .map_err(OpenAIError::Http)?;
-    response
-        .data
-        .into_iter()
-        .next()
-        .map(|item| item.embedding)
-        .ok_or_else(|| OpenAIError::Api("embedding response missing vector data".into()))
+    let mut data = response.data;
+    if data.len() != input.len() {
+        return Err(OpenAIError::Api(format!(
+            "expected {} embeddings, got {}",
+            input.len(),
+            data.len()
+        )));
+    }
+    data.sort_by_key(|item| item.index);
+    Ok(data.into_iter().map(|item| item.embedding).collect())
 }
 
 #[derive(Debug, Serialize)]
 struct EmbeddingRequest<'a> {
     model: &'a str,
-    input: &'a str,
+    input: &'a [String],
     #[serde(skip_serializing_if = "Option::is_none")]
     dimensions: Option<usize>,
 }
@@ -71,6 +97,8 @@ struct EmbeddingResponse {
 
 #[derive(Debug, Deserialize)]
 struct EmbeddingItem {
+    #[serde(default)]
+    index: usize,
     embedding: Vec<f32>,
 }
 
@@ -1,3 +1,7 @@
+use aither_core::{
+    llm::{LlmError, LlmErrorKind},
+    retry::{ProviderRetryInfo, RetryInfo, is_transient_status, parse_delay},
+};
 use std::fmt;
 use std::time::Duration;
 use zenwave::{BodyError, Error as ZenwaveError, sse::ParseError as SseParseError};
@@ -70,9 +74,14 @@ impl OpenAIError {
         // Check if it's a rate limit error
         if let zenwave::Error::Http { status, .. } = &err {
             if status.as_u16() == 429 {
+                let message = err
+                    .response_body()
+                    .and_then(|body| serde_json::from_str::<serde_json::Value>(body).ok())
+                    .and_then(|body| body["error"]["message"].as_str().map(str::to_string))
+                    .unwrap_or_else(|| "Rate limit exceeded".to_string());
                 return Self::RateLimit {
-                    message: "Rate limit exceeded".to_string(),
-                    retry_after: None,
+                    retry_after: retry_after_from_message(&message),
+                    message,
                 };
             }
             if status.as_u16() >= 500 {
@@ -89,6 +98,70 @@ impl OpenAIError {
     }
 }
 
+impl RetryInfo for OpenAIError {
+    fn retry_info(&self) -> Option<ProviderRetryInfo> {
+        match self {
+            Self::RateLimit { retry_after, .. } => {
+                Some(ProviderRetryInfo::new().retry_after(*retry_after))
+            }
+            Self::Http(err) => is_transient_http(err).then(ProviderRetryInfo::new),
+            Self::Body(_) | Self::Stream(_) | Self::ServerError { .. } | Self::Timeout => {
+                Some(ProviderRetryInfo::new())
+            }
+            Self::Json(_) | Self::Decode(_) | Self::Api(_) => None,
+        }
+    }
+}
+
+impl From<OpenAIError> for LlmError {
+    fn from(err: OpenAIError) -> Self {
+        let kind = match &err {
+            OpenAIError::Http(http) => http_error_kind(http),
+            OpenAIError::RateLimit { .. } => LlmErrorKind::RateLimit,
+            OpenAIError::ServerError { .. } => LlmErrorKind::Unavailable,
+            OpenAIError::Body(_) | OpenAIError::Stream(_) | OpenAIError::Timeout => {
+                LlmErrorKind::Network
+            }
+            OpenAIError::Api(message) => {
+                LlmErrorKind::from_message(message).unwrap_or(LlmErrorKind::Other)
+            }
+            OpenAIError::Json(_) | OpenAIError::Decode(_) => LlmErrorKind::Other,
+        };
+        let error = Self::new(kind, err.to_string());
+        match err.retry_info() {
+            Some(retry) => error.with_retry(retry),
+            None => error,
+        }
+    }
+}
+
+/// Classify an HTTP error by its status and error body.
+fn http_error_kind(err: &ZenwaveError) -> LlmErrorKind {
+    match err {
+        ZenwaveError::Http { status, .. } => {
+            LlmErrorKind::classify(status.as_u16(), err.response_body().unwrap_or_default())
+        }
+        _ if err.is_network_error() || err.is_timeout() => LlmErrorKind::Network,
+        _ => LlmErrorKind::Other,
+    }
+}
+
+/// Rate limits, server errors, timeouts and network failures.
+fn is_transient_http(err: &ZenwaveError) -> bool {
+    if let ZenwaveError::Http { status, .. } = err {
+        return is_transient_status(status.as_u16());
+    }
+    err.is_network_error() || err.is_timeout()
+}
+
+/// Rate limit messages end with the wait, e.g. "Please try again in 20s."
+fn retry_after_from_message(message: &str) -> Option<Duration> {
+    const MARKER: &str = "try again in ";
+    let rest = &message[message.find(MARKER)? + MARKER.len()..];
+    let delay = rest.split_whitespace().next()?.trim_end_matches('.');
+    parse_delay(delay)
+}
+
 impl From<BodyError> for OpenAIError {
     fn from(value: BodyError) -> Self {
         Self::Body(value)
@@ -71,7 +71,7 @@ async fn generate_images(
     let mut builder = backend
         .post(endpoint)
         .map_err(OpenAIError::Http)?
-        .header(header::AUTHORIZATION.as_str(), cfg.request_auth())
+        .header(cfg.auth_header_name(), cfg.request_auth())
         .map_err(OpenAIError::Http)?
         .header(header::USER_AGENT.as_str(), "aither-openai/0.1")
         .map_err(OpenAIError::Http)?;
@@ -108,7 +108,7 @@ async fn edit_image(
     let mut builder = backend
         .post(endpoint)
         .map_err(OpenAIError::Http)?
-        .header(header::AUTHORIZATION.as_str(), cfg.request_auth())
+        .header(cfg.auth_header_name(), cfg.request_auth())
         .map_err(OpenAIError::Http)?
         .header(header::USER_AGENT.as_str(), "aither-openai/0.1")
         .map_err(OpenAIError::Http)?;
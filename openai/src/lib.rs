@@ -34,12 +34,20 @@ mod image;
 mod mime;
 mod moderation;
 mod provider;
+#[cfg(all(feature = "realtime", not(target_arch = "wasm32")))]
+mod realtime;
 mod request;
+mod rerank;
 mod response;
 
 pub use client::{ApiKind, Builder, OpenAI};
 pub use error::OpenAIError;
 pub use provider::OpenAIProvider;
+#[cfg(all(feature = "realtime", not(target_arch = "wasm32")))]
+pub use realtime::{
+    REALTIME_SAMPLE_RATE, Realtime, RealtimeEvent, RealtimeEvents, RealtimeOptions, RealtimeSender,
+    RealtimeSession,
+};
 
 mod constant;
 pub use constant::*;
@@ -54,3 +62,4 @@ pub(crate) const DEFAULT_AUDIO_VOICE: &str = "alloy";
 pub(crate) const DEFAULT_AUDIO_FORMAT: &str = "mp3";
 pub(crate) const DEFAULT_TRANSCRIPTION_MODEL: &str = STT_GPT4O;
 pub(crate) const DEFAULT_MODERATION_MODEL: &str = MODERATION_LATEST;
+pub(crate) const DEFAULT_RERANK_MODEL: &str = RERANK_BGE_V2_M3;
@@ -26,7 +26,7 @@ async fn moderate_once(cfg: Arc<Config>, content: String) -> Result<ModerationRe
     let mut builder = backend
         .post(endpoint)
         .map_err(OpenAIError::Http)?
-        .header(header::AUTHORIZATION.as_str(), cfg.request_auth())
+        .header(cfg.auth_header_name(), cfg.request_auth())
         .map_err(OpenAIError::Http)?
         .header(header::USER_AGENT.as_str(), "aither-openai/0.1")
         .map_err(OpenAIError::Http)?;
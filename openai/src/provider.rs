@@ -1,5 +1,7 @@
 use crate::{
-    DEEPSEEK_BASE_URL, DEFAULT_BASE_URL, OPENROUTER_BASE_URL, client::OpenAI, error::OpenAIError,
+    DEEPSEEK_BASE_URL, DEFAULT_BASE_URL, OPENROUTER_BASE_URL,
+    client::{ApiKind, OpenAI},
+    error::OpenAIError,
 };
 use aither_core::llm::{
     LanguageModelProvider, model::Profile as ModelProfile, provider::Profile as ProviderProfile,
@@ -23,6 +25,8 @@ impl OpenAIProvider {
                 api_key: api_key.into(),
                 base_url: DEFAULT_BASE_URL.to_string(),
                 organization: None,
+                auth_header: None,
+                api_kind: None,
             }),
         }
     }
@@ -53,6 +57,22 @@ impl OpenAIProvider {
         self
     }
 
+    /// Send the API key verbatim in header `name` instead of `Authorization: Bearer`.
+    #[must_use]
+    pub fn auth_header(mut self, name: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner).auth_header = Some(name.into());
+        self
+    }
+
+    /// Select the API surface used by created models.
+    ///
+    /// Most OpenAI-compatible servers only implement chat completions.
+    #[must_use]
+    pub fn api(mut self, api: ApiKind) -> Self {
+        Arc::make_mut(&mut self.inner).api_kind = Some(api);
+        self
+    }
+
     fn client_for(&self, model: impl Into<String>) -> OpenAI {
         let mut builder = OpenAI::builder(self.inner.api_key.clone())
             .base_url(self.inner.base_url.clone())
@@ -60,6 +80,14 @@ impl OpenAIProvider {
         if let Some(org) = &self.inner.organization {
             builder = builder.organization(org.clone());
         }
+        if let Some(name) = &self.inner.auth_header {
+            builder = builder.auth_header(name.clone());
+        }
+        match self.inner.api_kind {
+            Some(ApiKind::ChatCompletions) => builder = builder.use_chat_completions_api(),
+            Some(ApiKind::Responses) => builder = builder.use_responses_api(),
+            None => {}
+        }
         builder.build()
     }
 }
@@ -76,10 +104,7 @@ impl LanguageModelProvider for OpenAIProvider {
             let mut builder = backend
                 .get(endpoint)
                 .map_err(OpenAIError::Http)?
-                .header(
-                    header::AUTHORIZATION.as_str(),
-                    format!("Bearer {}", cfg.api_key),
-                )
+                .header(cfg.auth_header_name(), cfg.request_auth())
                 .map_err(OpenAIError::Http)?;
             if let Some(org) = &cfg.organization {
                 builder = builder
@@ -133,6 +158,24 @@ struct ProviderConfig {
     api_key: String,
     base_url: String,
     organization: Option<String>,
+    auth_header: Option<String>,
+    api_kind: Option<ApiKind>,
+}
+
+impl ProviderConfig {
+    fn auth_header_name(&self) -> &str {
+        self.auth_header
+            .as_deref()
+            .unwrap_or(header::AUTHORIZATION.as_str())
+    }
+
+    fn request_auth(&self) -> String {
+        if self.auth_header.is_some() {
+            self.api_key.clone()
+        } else {
+            format!("Bearer {}", self.api_key)
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,1088 @@
+//! Realtime API: low-latency speech and text conversations over a WebSocket.
+//!
+//! A [`RealtimeSession`] keeps one connection open for a whole conversation.
+//! Audio is streamed in as it is captured, and the model answers with audio,
+//! transcripts, text and tool calls as [`RealtimeEvent`]s:
+//!
+//! ```ignore
+//! let realtime = OpenAI::new(key).realtime().options(
+//!     RealtimeOptions::new()
+//!         .instructions("You are a friendly voice assistant.")
+//!         .tool(ToolDefinition::new(&weather)),
+//! );
+//! let (mut sender, mut events) = realtime.connect().await?.into_split();
+//! sender.append_audio(&microphone_chunk).await?;
+//! while let Some(event) = events.next().await {
+//!     match event? {
+//!         RealtimeEvent::AudioDelta(pcm) => speaker.play(&pcm),
+//!         RealtimeEvent::ToolCall(call) => {
+//!             let output = run_tool(&call).await;
+//!             sender.send_tool_result(&call.id, &output).await?;
+//!             sender.create_response().await?;
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+//!
+//! Audio in both directions is 16-bit little-endian mono PCM at 24 kHz.
+//! With server voice activity detection (the default), the server decides
+//! when the user has finished speaking and answers on its own; with
+//! [`RealtimeOptions::manual_turns`], call [`RealtimeSender::commit_audio`]
+//! and [`RealtimeSender::create_response`] yourself.
+//!
+//! [`Realtime`] also implements [`AudioGenerator`] and [`AudioTranscriber`],
+//! opening a short-lived session per call.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use aither_core::{
+    audio::{AudioGenerator, AudioTranscriber, Data},
+    llm::{ToolCall, Usage, tool::ToolDefinition},
+};
+use async_tungstenite::{
+    WebSocketStream,
+    async_std::{ConnectStream, connect_async},
+    tungstenite::{
+        Message,
+        client::IntoClientRequest,
+        http::{HeaderName, HeaderValue},
+    },
+};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use futures_core::Stream;
+use futures_util::{
+    SinkExt, StreamExt,
+    stream::{SplitSink, SplitStream},
+};
+use serde_json::{Value, json};
+
+use crate::{
+    DEFAULT_AUDIO_VOICE, REALTIME, STT_GPT4O_MINI,
+    client::{Config, OpenAI},
+    error::OpenAIError,
+};
+
+/// Sample rate of realtime PCM audio, in Hz.
+pub const REALTIME_SAMPLE_RATE: u32 = 24_000;
+
+type Socket = WebSocketStream<ConnectStream>;
+
+/// Settings for a realtime session.
+#[derive(Debug, Clone)]
+pub struct RealtimeOptions {
+    model: String,
+    voice: String,
+    instructions: Option<String>,
+    text_only: bool,
+    server_vad: bool,
+    transcription_model: Option<String>,
+    tools: Vec<ToolDefinition>,
+}
+
+impl Default for RealtimeOptions {
+    fn default() -> Self {
+        Self {
+            model: REALTIME.to_string(),
+            voice: DEFAULT_AUDIO_VOICE.to_string(),
+            instructions: None,
+            text_only: false,
+            server_vad: true,
+            transcription_model: None,
+            tools: Vec::new(),
+        }
+    }
+}
+
+impl RealtimeOptions {
+    /// Create options for [`REALTIME`] with audio output and server voice
+    /// activity detection.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the realtime model.
+    #[must_use]
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Set the output voice.
+    #[must_use]
+    pub fn voice(mut self, voice: impl Into<String>) -> Self {
+        self.voice = voice.into();
+        self
+    }
+
+    /// Set the system instructions.
+    #[must_use]
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Answer with text instead of audio.
+    #[must_use]
+    pub const fn text_only(mut self) -> Self {
+        self.text_only = true;
+        self
+    }
+
+    /// Disable server voice activity detection; turns end only when the
+    /// audio buffer is committed.
+    #[must_use]
+    pub const fn manual_turns(mut self) -> Self {
+        self.server_vad = false;
+        self
+    }
+
+    /// Transcribe the user's audio with `model`, emitting
+    /// [`RealtimeEvent::InputTranscriptDelta`] and
+    /// [`RealtimeEvent::InputTranscript`].
+    #[must_use]
+    pub fn transcription(mut self, model: impl Into<String>) -> Self {
+        self.transcription_model = Some(model.into());
+        self
+    }
+
+    /// Offer a tool to the model.
+    #[must_use]
+    pub fn tool(mut self, tool: ToolDefinition) -> Self {
+        self.tools.push(tool);
+        self
+    }
+
+    /// Offer several tools to the model.
+    #[must_use]
+    pub fn tools(mut self, tools: impl IntoIterator<Item = ToolDefinition>) -> Self {
+        self.tools.extend(tools);
+        self
+    }
+
+    fn session_payload(&self) -> Value {
+        let turn_detection = if self.server_vad {
+            json!({ "type": "server_vad" })
+        } else {
+            Value::Null
+        };
+        let mut input = json!({
+            "format": { "type": "audio/pcm", "rate": REALTIME_SAMPLE_RATE },
+            "turn_detection": turn_detection,
+        });
+        if let Some(model) = &self.transcription_model {
+            input["transcription"] = json!({ "model": model });
+        }
+        let tools: Vec<Value> = self
+            .tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "type": "function",
+                    "name": tool.name(),
+                    "description": tool.description(),
+                    "parameters": tool.arguments_openai_schema(),
+                })
+            })
+            .collect();
+
+        let modality = if self.text_only { "text" } else { "audio" };
+        let mut session = json!({
+            "type": "realtime",
+            "model": self.model,
+            "output_modalities": [modality],
+            "audio": {
+                "input": input,
+                "output": {
+                    "format": { "type": "audio/pcm", "rate": REALTIME_SAMPLE_RATE },
+                    "voice": self.voice,
+                },
+            },
+            "tools": tools,
+        });
+        if let Some(instructions) = &self.instructions {
+            session["instructions"] = json!(instructions);
+        }
+        session
+    }
+}
+
+/// Entry point for realtime sessions, created by [`OpenAI::realtime`].
+#[derive(Debug, Clone)]
+pub struct Realtime {
+    config: Arc<Config>,
+    options: RealtimeOptions,
+}
+
+impl OpenAI {
+    /// Start configuring a realtime session with this client's credentials.
+    #[must_use]
+    pub fn realtime(&self) -> Realtime {
+        Realtime {
+            config: self.config(),
+            options: RealtimeOptions::default(),
+        }
+    }
+}
+
+impl Realtime {
+    /// Replace the session options.
+    #[must_use]
+    pub fn options(mut self, options: RealtimeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Open a session and apply the configured options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection cannot be established or the
+    /// session cannot be configured.
+    pub async fn connect(&self) -> Result<RealtimeSession, OpenAIError> {
+        connect(&self.config, &self.options).await
+    }
+
+    /// Options for a one-shot spoken reply: audio out, one manually started turn.
+    fn speech_options(&self) -> RealtimeOptions {
+        RealtimeOptions {
+            text_only: false,
+            server_vad: false,
+            ..self.options.clone()
+        }
+    }
+
+    /// Options for a one-shot transcription: one manually committed turn,
+    /// transcribed with [`STT_GPT4O_MINI`] unless a model was chosen.
+    fn transcription_options(&self) -> RealtimeOptions {
+        let mut options = RealtimeOptions {
+            server_vad: false,
+            ..self.options.clone()
+        };
+        options
+            .transcription_model
+            .get_or_insert_with(|| STT_GPT4O_MINI.to_string());
+        options
+    }
+}
+
+async fn connect(cfg: &Config, options: &RealtimeOptions) -> Result<RealtimeSession, OpenAIError> {
+    let url = format!(
+        "{}?model={}",
+        websocket_url(&cfg.request_url("/realtime")),
+        options.model
+    );
+    let mut request = url.into_client_request().map_err(websocket_error)?;
+    let headers = request.headers_mut();
+    headers.insert(
+        HeaderName::from_bytes(cfg.auth_header_name().as_bytes())
+            .map_err(|err| OpenAIError::Api(format!("Invalid auth header: {err}")))?,
+        HeaderValue::from_str(&cfg.request_auth())
+            .map_err(|err| OpenAIError::Api(format!("Invalid API key: {err}")))?,
+    );
+    if let Some(org) = &cfg.organization {
+        headers.insert(
+            "OpenAI-Organization",
+            HeaderValue::from_str(org)
+                .map_err(|err| OpenAIError::Api(format!("Invalid organization: {err}")))?,
+        );
+    }
+
+    let (socket, _) = connect_async(request).await.map_err(websocket_error)?;
+    let (sink, stream) = socket.split();
+    let mut session = RealtimeSession {
+        sender: RealtimeSender { sink },
+        events: RealtimeEvents { stream },
+    };
+    session.sender.update_session(options).await?;
+    Ok(session)
+}
+
+/// An open realtime conversation.
+///
+/// Use [`into_split`](Self::into_split) to send audio while events are
+/// being read on another task.
+#[derive(Debug)]
+pub struct RealtimeSession {
+    sender: RealtimeSender,
+    events: RealtimeEvents,
+}
+
+impl RealtimeSession {
+    /// The sending half of the session.
+    pub const fn sender(&mut self) -> &mut RealtimeSender {
+        &mut self.sender
+    }
+
+    /// Wait for the next server event.
+    ///
+    /// Returns `None` once the server closes the connection.
+    pub async fn next_event(&mut self) -> Option<Result<RealtimeEvent, OpenAIError>> {
+        self.events.next().await
+    }
+
+    /// Split into independently usable sending and receiving halves.
+    #[must_use]
+    pub fn into_split(self) -> (RealtimeSender, RealtimeEvents) {
+        (self.sender, self.events)
+    }
+}
+
+/// Sending half of a [`RealtimeSession`].
+pub struct RealtimeSender {
+    sink: SplitSink<Socket, Message>,
+}
+
+impl std::fmt::Debug for RealtimeSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RealtimeSender").finish_non_exhaustive()
+    }
+}
+
+impl RealtimeSender {
+    /// Send a raw client event, for API features without a dedicated method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn send_event(&mut self, event: Value) -> Result<(), OpenAIError> {
+        self.sink
+            .send(Message::Text(event.to_string().into()))
+            .await
+            .map_err(websocket_error)
+    }
+
+    /// Change the session settings mid-conversation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn update_session(&mut self, options: &RealtimeOptions) -> Result<(), OpenAIError> {
+        self.send_event(json!({
+            "type": "session.update",
+            "session": options.session_payload(),
+        }))
+        .await
+    }
+
+    /// Add a user text message and ask the model to respond.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn send_text(&mut self, text: &str) -> Result<(), OpenAIError> {
+        self.send_event(json!({
+            "type": "conversation.item.create",
+            "item": {
+                "type": "message",
+                "role": "user",
+                "content": [{ "type": "input_text", "text": text }],
+            },
+        }))
+        .await?;
+        self.create_response().await
+    }
+
+    /// Append PCM audio to the input buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn append_audio(&mut self, pcm: &[u8]) -> Result<(), OpenAIError> {
+        self.send_event(json!({
+            "type": "input_audio_buffer.append",
+            "audio": STANDARD.encode(pcm),
+        }))
+        .await
+    }
+
+    /// Commit the input buffer as a user turn.
+    ///
+    /// Only needed with [`RealtimeOptions::manual_turns`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn commit_audio(&mut self) -> Result<(), OpenAIError> {
+        self.send_event(json!({ "type": "input_audio_buffer.commit" }))
+            .await
+    }
+
+    /// Discard the uncommitted input audio.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn clear_audio(&mut self) -> Result<(), OpenAIError> {
+        self.send_event(json!({ "type": "input_audio_buffer.clear" }))
+            .await
+    }
+
+    /// Ask the model to respond to the conversation so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn create_response(&mut self) -> Result<(), OpenAIError> {
+        self.send_event(json!({ "type": "response.create" })).await
+    }
+
+    /// Stop the response in progress, e.g. when the user interrupts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn cancel_response(&mut self) -> Result<(), OpenAIError> {
+        self.send_event(json!({ "type": "response.cancel" })).await
+    }
+
+    /// Add the output of a tool call to the conversation.
+    ///
+    /// The model does not continue on its own; call
+    /// [`create_response`](Self::create_response) once all results are in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection is closed.
+    pub async fn send_tool_result(
+        &mut self,
+        call_id: &str,
+        output: &str,
+    ) -> Result<(), OpenAIError> {
+        self.send_event(json!({
+            "type": "conversation.item.create",
+            "item": {
+                "type": "function_call_output",
+                "call_id": call_id,
+                "output": output,
+            },
+        }))
+        .await
+    }
+
+    /// Close the connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the close frame cannot be sent.
+    pub async fn close(&mut self) -> Result<(), OpenAIError> {
+        self.sink.close().await.map_err(websocket_error)
+    }
+}
+
+/// Receiving half of a [`RealtimeSession`].
+pub struct RealtimeEvents {
+    stream: SplitStream<Socket>,
+}
+
+impl std::fmt::Debug for RealtimeEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RealtimeEvents").finish_non_exhaustive()
+    }
+}
+
+impl Stream for RealtimeEvents {
+    type Item = Result<RealtimeEvent, OpenAIError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match self.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(message))) => message,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(websocket_error(err)))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            match message {
+                Message::Text(text) => {
+                    return Poll::Ready(Some(
+                        serde_json::from_str(&text)
+                            .map_err(OpenAIError::from)
+                            .and_then(parse_event),
+                    ));
+                }
+                Message::Close(_) => return Poll::Ready(None),
+                // Pings are answered by the WebSocket layer.
+                _ => {}
+            }
+        }
+    }
+}
+
+/// An event received from a realtime session.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RealtimeEvent {
+    /// The session settings were applied.
+    SessionUpdated,
+    /// The user started speaking (server voice activity detection).
+    SpeechStarted,
+    /// The user stopped speaking (server voice activity detection).
+    SpeechStopped,
+    /// Part of the transcript of the user's audio.
+    InputTranscriptDelta(String),
+    /// The complete transcript of a user turn.
+    InputTranscript(String),
+    /// A chunk of PCM audio from the model.
+    AudioDelta(Data),
+    /// Part of the transcript of the model's audio.
+    AudioTranscriptDelta(String),
+    /// A chunk of text from the model.
+    TextDelta(String),
+    /// The model wants to call a tool.
+    ToolCall(ToolCall),
+    /// A response finished.
+    ResponseDone {
+        /// Token usage, when reported.
+        usage: Option<Usage>,
+    },
+    /// Any other server event, as raw JSON.
+    Other(Value),
+}
+
+fn parse_event(event: Value) -> Result<RealtimeEvent, OpenAIError> {
+    let text = |key: &str| event[key].as_str().unwrap_or_default().to_string();
+    let kind = event["type"].as_str().unwrap_or_default();
+    let parsed = match kind {
+        "error" => {
+            return Err(OpenAIError::Api(
+                event["error"]["message"]
+                    .as_str()
+                    .unwrap_or("Realtime session error")
+                    .to_string(),
+            ));
+        }
+        "session.created" | "session.updated" => RealtimeEvent::SessionUpdated,
+        "input_audio_buffer.speech_started" => RealtimeEvent::SpeechStarted,
+        "input_audio_buffer.speech_stopped" => RealtimeEvent::SpeechStopped,
+        "conversation.item.input_audio_transcription.delta" => {
+            RealtimeEvent::InputTranscriptDelta(text("delta"))
+        }
+        "conversation.item.input_audio_transcription.completed" => {
+            RealtimeEvent::InputTranscript(text("transcript"))
+        }
+        // The beta API names output events without the `output_` prefix.
+        "response.output_audio.delta" | "response.audio.delta" => {
+            RealtimeEvent::AudioDelta(STANDARD.decode(text("delta"))?)
+        }
+        "response.output_audio_transcript.delta" | "response.audio_transcript.delta" => {
+            RealtimeEvent::AudioTranscriptDelta(text("delta"))
+        }
+        "response.output_text.delta" | "response.text.delta" => {
+            RealtimeEvent::TextDelta(text("delta"))
+        }
+        "response.output_item.done" if event["item"]["type"] == "function_call" => {
+            let item = &event["item"];
+            let arguments = item["arguments"].as_str().unwrap_or("{}");
+            RealtimeEvent::ToolCall(ToolCall::new(
+                item["call_id"].as_str().unwrap_or_default(),
+                item["name"].as_str().unwrap_or_default(),
+                serde_json::from_str(arguments)
+                    .unwrap_or_else(|_| Value::String(arguments.to_string())),
+            ))
+        }
+        "response.done" => RealtimeEvent::ResponseDone {
+            usage: parse_usage(&event["response"]["usage"]),
+        },
+        _ => RealtimeEvent::Other(event),
+    };
+    Ok(parsed)
+}
+
+fn parse_usage(usage: &Value) -> Option<Usage> {
+    let tokens = |key: &str| {
+        usage[key]
+            .as_u64()
+            .and_then(|value| u32::try_from(value).ok())
+    };
+    Some(Usage::new(
+        tokens("input_tokens")?,
+        tokens("output_tokens")?,
+    ))
+}
+
+impl AudioGenerator for Realtime {
+    /// Speak a response to `prompt`, yielding PCM audio chunks.
+    ///
+    /// The audio is the model's spoken reply, not a verbatim reading; set
+    /// instructions accordingly for text-to-speech use.
+    fn generate(&self, prompt: &str) -> impl Stream<Item = Data> + Send {
+        let cfg = self.config.clone();
+        let options = self.speech_options();
+        let prompt = prompt.to_owned();
+        async_stream::stream! {
+            let mut session = match connect(&cfg, &options).await {
+                Ok(session) => session,
+                Err(err) => {
+                    report_failure("speech", &err);
+                    return;
+                }
+            };
+            if let Err(err) = session.sender.send_text(&prompt).await {
+                report_failure("speech", &err);
+                return;
+            }
+            while let Some(event) = session.next_event().await {
+                match event {
+                    Ok(RealtimeEvent::AudioDelta(pcm)) => yield pcm,
+                    Ok(RealtimeEvent::ResponseDone { .. }) => break,
+                    Ok(_) => {}
+                    Err(err) => {
+                        report_failure("speech", &err);
+                        break;
+                    }
+                }
+            }
+            let _ = session.sender.close().await;
+        }
+    }
+}
+
+impl AudioTranscriber for Realtime {
+    /// Transcribe 24 kHz PCM audio, yielding transcript deltas.
+    fn transcribe(&self, audio: &[u8]) -> impl Stream<Item = String> + Send {
+        let cfg = self.config.clone();
+        let options = self.transcription_options();
+        let audio = audio.to_vec();
+        async_stream::stream! {
+            let mut session = match connect(&cfg, &options).await {
+                Ok(session) => session,
+                Err(err) => {
+                    report_failure("transcription", &err);
+                    return;
+                }
+            };
+            let sent = match session.sender.append_audio(&audio).await {
+                Ok(()) => session.sender.commit_audio().await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = sent {
+                report_failure("transcription", &err);
+                return;
+            }
+            let mut streamed = false;
+            while let Some(event) = session.next_event().await {
+                match event {
+                    Ok(RealtimeEvent::InputTranscriptDelta(delta)) => {
+                        streamed = true;
+                        yield delta;
+                    }
+                    Ok(RealtimeEvent::InputTranscript(transcript)) => {
+                        // Models without incremental transcription only send the final text.
+                        if !streamed {
+                            yield transcript;
+                        }
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        report_failure("transcription", &err);
+                        break;
+                    }
+                }
+            }
+            let _ = session.sender.close().await;
+        }
+    }
+}
+
+/// Mirror the HTTP audio endpoints: fail loudly in debug builds, yield
+/// nothing in release builds.
+fn report_failure(context: &'static str, err: &OpenAIError) {
+    assert!(
+        !cfg!(debug_assertions),
+        "OpenAI realtime {context} failed: {err}"
+    );
+    tracing::warn!("OpenAI realtime {context} failed: {err}");
+}
+
+/// Turn an HTTP(S) API URL into the matching WebSocket URL.
+fn websocket_url(url: &str) -> String {
+    for (http, ws) in [("https://", "wss://"), ("http://", "ws://")] {
+        if let Some(rest) = url.strip_prefix(http) {
+            return format!("{ws}{rest}");
+        }
+    }
+    url.to_string()
+}
+
+fn websocket_error(err: impl std::fmt::Display) -> OpenAIError {
+    OpenAIError::Api(format!("WebSocket error: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{TcpListener, TcpStream};
+    use std::thread::{self, JoinHandle};
+
+    use aither_core::llm::{Tool, ToolOutput};
+    use async_tungstenite::tungstenite::{
+        self, WebSocket,
+        handshake::server::{Request, Response},
+    };
+    use futures_lite::future::block_on;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+    use std::borrow::Cow;
+
+    use super::*;
+
+    /// Looks up the current weather.
+    #[derive(JsonSchema, Deserialize)]
+    struct WeatherArgs {
+        city: String,
+    }
+
+    struct Weather;
+
+    impl Tool for Weather {
+        fn name(&self) -> Cow<'static, str> {
+            "weather".into()
+        }
+        type Arguments = WeatherArgs;
+
+        async fn call(&self, args: Self::Arguments) -> aither_core::Result<ToolOutput> {
+            Ok(ToolOutput::text(format!("sunny in {}", args.city)))
+        }
+    }
+
+    fn event(value: &Value) -> RealtimeEvent {
+        parse_event(value.clone()).unwrap()
+    }
+
+    /// Read the next client event, skipping the client's pongs.
+    fn client_event(socket: &mut WebSocket<TcpStream>) -> Value {
+        loop {
+            match socket.read().unwrap() {
+                Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                Message::Pong(_) => {}
+                other => panic!("expected a text frame, got {other:?}"),
+            }
+        }
+    }
+
+    fn send(socket: &mut WebSocket<TcpStream>, event: &Value) {
+        socket
+            .send(Message::Text(event.to_string().into()))
+            .unwrap();
+    }
+
+    /// Accept one WebSocket connection on a local port and run `script` on it.
+    ///
+    /// Returns the base URL to configure the client with.
+    // The handshake callback's error type is fixed by tungstenite.
+    #[allow(clippy::result_large_err)]
+    fn serve(
+        script: impl FnOnce(&Request, &mut WebSocket<TcpStream>) + Send + 'static,
+    ) -> (String, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut request = None;
+            let mut socket = tungstenite::accept_hdr(stream, |req: &Request, res: Response| {
+                request = Some(req.clone());
+                Ok(res)
+            })
+            .unwrap();
+            script(&request.unwrap(), &mut socket);
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn default_session_uses_audio_and_server_vad() {
+        let session = RealtimeOptions::new().session_payload();
+
+        assert_eq!(session["model"], REALTIME);
+        assert_eq!(session["output_modalities"], json!(["audio"]));
+        assert_eq!(
+            session["audio"]["input"]["turn_detection"]["type"],
+            "server_vad"
+        );
+        assert_eq!(
+            session["audio"]["input"]["format"]["rate"],
+            REALTIME_SAMPLE_RATE
+        );
+        assert_eq!(session["audio"]["output"]["voice"], DEFAULT_AUDIO_VOICE);
+        assert!(session["audio"]["input"].get("transcription").is_none());
+        assert!(session.get("instructions").is_none());
+        assert_eq!(session["tools"], json!([]));
+    }
+
+    #[test]
+    fn session_payload_reflects_options() {
+        let tool = ToolDefinition::new(&Weather);
+        let session = RealtimeOptions::new()
+            .model("gpt-realtime-mini")
+            .voice("verse")
+            .instructions("Be brief.")
+            .text_only()
+            .manual_turns()
+            .transcription("whisper-1")
+            .tool(tool)
+            .session_payload();
+
+        assert_eq!(session["model"], "gpt-realtime-mini");
+        assert_eq!(session["output_modalities"], json!(["text"]));
+        assert_eq!(session["instructions"], "Be brief.");
+        assert_eq!(session["audio"]["output"]["voice"], "verse");
+        assert!(session["audio"]["input"]["turn_detection"].is_null());
+        assert_eq!(
+            session["audio"]["input"]["transcription"]["model"],
+            "whisper-1"
+        );
+        assert_eq!(session["tools"][0]["type"], "function");
+        assert_eq!(session["tools"][0]["name"], "weather");
+        assert_eq!(session["tools"][0]["parameters"]["type"], "object");
+    }
+
+    #[test]
+    fn one_shot_sessions_use_manual_turns() {
+        let realtime = OpenAI::new("key")
+            .realtime()
+            .options(RealtimeOptions::new().text_only());
+
+        let speech = realtime.speech_options();
+        assert!(!speech.text_only);
+        assert!(!speech.server_vad);
+
+        let transcription = realtime.transcription_options();
+        assert!(!transcription.server_vad);
+        assert_eq!(
+            transcription.transcription_model.as_deref(),
+            Some(STT_GPT4O_MINI)
+        );
+        let chosen = OpenAI::new("key")
+            .realtime()
+            .options(RealtimeOptions::new().transcription("whisper-1"))
+            .transcription_options();
+        assert_eq!(chosen.transcription_model.as_deref(), Some("whisper-1"));
+    }
+
+    #[test]
+    fn parses_session_and_transcript_events() {
+        assert_eq!(
+            event(&json!({ "type": "session.created" })),
+            RealtimeEvent::SessionUpdated
+        );
+        assert_eq!(
+            event(&json!({ "type": "session.updated" })),
+            RealtimeEvent::SessionUpdated
+        );
+        assert_eq!(
+            event(&json!({ "type": "input_audio_buffer.speech_started" })),
+            RealtimeEvent::SpeechStarted
+        );
+        assert_eq!(
+            event(&json!({ "type": "input_audio_buffer.speech_stopped" })),
+            RealtimeEvent::SpeechStopped
+        );
+        assert_eq!(
+            event(&json!({
+                "type": "conversation.item.input_audio_transcription.delta",
+                "delta": "hel",
+            })),
+            RealtimeEvent::InputTranscriptDelta("hel".into())
+        );
+        assert_eq!(
+            event(&json!({
+                "type": "conversation.item.input_audio_transcription.completed",
+                "transcript": "hello",
+            })),
+            RealtimeEvent::InputTranscript("hello".into())
+        );
+    }
+
+    #[test]
+    fn parses_output_deltas_under_both_api_names() {
+        let pcm = [0_u8, 1, 254, 255];
+        for kind in ["response.output_audio.delta", "response.audio.delta"] {
+            assert_eq!(
+                event(&json!({ "type": kind, "delta": STANDARD.encode(pcm) })),
+                RealtimeEvent::AudioDelta(pcm.to_vec())
+            );
+        }
+        for kind in [
+            "response.output_audio_transcript.delta",
+            "response.audio_transcript.delta",
+        ] {
+            assert_eq!(
+                event(&json!({ "type": kind, "delta": "Hi" })),
+                RealtimeEvent::AudioTranscriptDelta("Hi".into())
+            );
+        }
+        for kind in ["response.output_text.delta", "response.text.delta"] {
+            assert_eq!(
+                event(&json!({ "type": kind, "delta": "Hi" })),
+                RealtimeEvent::TextDelta("Hi".into())
+            );
+        }
+        assert!(
+            parse_event(json!({ "type": "response.output_audio.delta", "delta": "not base64!" }))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_tool_calls() {
+        let call = |arguments: &str| {
+            event(&json!({
+                "type": "response.output_item.done",
+                "item": {
+                    "type": "function_call",
+                    "call_id": "call_1",
+                    "name": "weather",
+                    "arguments": arguments,
+                },
+            }))
+        };
+
+        assert_eq!(
+            call(r#"{"city":"Paris"}"#),
+            RealtimeEvent::ToolCall(ToolCall::new(
+                "call_1",
+                "weather",
+                json!({ "city": "Paris" })
+            ))
+        );
+        assert_eq!(
+            call("{not json"),
+            RealtimeEvent::ToolCall(ToolCall::new("call_1", "weather", json!("{not json")))
+        );
+
+        let message = json!({
+            "type": "response.output_item.done",
+            "item": { "type": "message" },
+        });
+        assert_eq!(event(&message), RealtimeEvent::Other(message));
+    }
+
+    #[test]
+    fn parses_response_done_usage_and_errors() {
+        assert_eq!(
+            event(&json!({
+                "type": "response.done",
+                "response": { "usage": { "input_tokens": 12, "output_tokens": 34 } },
+            })),
+            RealtimeEvent::ResponseDone {
+                usage: Some(Usage::new(12, 34))
+            }
+        );
+        assert_eq!(
+            event(&json!({ "type": "response.done", "response": {} })),
+            RealtimeEvent::ResponseDone { usage: None }
+        );
+
+        let error = parse_event(json!({
+            "type": "error",
+            "error": { "message": "Invalid session" },
+        }))
+        .unwrap_err();
+        assert_eq!(error.to_string(), "Invalid session");
+
+        let unknown = json!({ "type": "rate_limits.updated", "rate_limits": [] });
+        assert_eq!(event(&unknown), RealtimeEvent::Other(unknown));
+    }
+
+    #[test]
+    fn websocket_url_swaps_the_scheme() {
+        assert_eq!(
+            websocket_url("https://api.openai.com/v1/realtime"),
+            "wss://api.openai.com/v1/realtime"
+        );
+        assert_eq!(
+            websocket_url("http://localhost:8080/realtime"),
+            "ws://localhost:8080/realtime"
+        );
+        assert_eq!(websocket_url("wss://host/realtime"), "wss://host/realtime");
+    }
+
+    #[test]
+    fn session_round_trip() {
+        let (base_url, server) = serve(|request, socket| {
+            assert_eq!(request.uri(), "/v1/realtime?model=gpt-realtime");
+            assert_eq!(request.headers()["authorization"], "Bearer key");
+
+            let update = client_event(socket);
+            assert_eq!(update["type"], "session.update");
+            assert_eq!(update["session"]["instructions"], "Be brief.");
+
+            let item = client_event(socket);
+            assert_eq!(item["type"], "conversation.item.create");
+            assert_eq!(item["item"]["content"][0]["text"], "What's the weather?");
+            assert_eq!(client_event(socket)["type"], "response.create");
+
+            send(socket, &json!({ "type": "session.updated" }));
+            socket
+                .send(Message::Ping(b"ping".as_slice().into()))
+                .unwrap();
+            send(
+                socket,
+                &json!({ "type": "response.output_text.delta", "delta": "Let me check." }),
+            );
+            send(
+                socket,
+                &json!({
+                    "type": "response.output_item.done",
+                    "item": {
+                        "type": "function_call",
+                        "call_id": "call_1",
+                        "name": "weather",
+                        "arguments": "{}",
+                    },
+                }),
+            );
+            send(socket, &json!({ "type": "response.done", "response": {} }));
+
+            let output = client_event(socket);
+            assert_eq!(output["item"]["type"], "function_call_output");
+            assert_eq!(output["item"]["call_id"], "call_1");
+            assert_eq!(output["item"]["output"], "sunny");
+            assert_eq!(client_event(socket)["type"], "response.create");
+            socket.close(None).unwrap();
+            while socket.read().is_ok() {}
+        });
+
+        let realtime = OpenAI::builder("key")
+            .base_url(base_url)
+            .build()
+            .realtime()
+            .options(RealtimeOptions::new().instructions("Be brief."));
+        block_on(async {
+            let mut session = realtime.connect().await.unwrap();
+            session
+                .sender()
+                .send_text("What's the weather?")
+                .await
+                .unwrap();
+
+            let mut events = Vec::new();
+            while let Some(event) = session.next_event().await {
+                let event = event.unwrap();
+                let done = matches!(event, RealtimeEvent::ResponseDone { .. });
+                events.push(event);
+                if done {
+                    break;
+                }
+            }
+            assert_eq!(
+                events,
+                [
+                    RealtimeEvent::SessionUpdated,
+                    RealtimeEvent::TextDelta("Let me check.".into()),
+                    RealtimeEvent::ToolCall(ToolCall::new("call_1", "weather", json!({}))),
+                    RealtimeEvent::ResponseDone { usage: None },
+                ]
+            );
+
+            let (mut sender, mut events) = session.into_split();
+            sender.send_tool_result("call_1", "sunny").await.unwrap();
+            sender.create_response().await.unwrap();
+            assert!(events.next().await.is_none());
+        });
+        server.join().unwrap();
+    }
+}
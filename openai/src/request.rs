@@ -1,5 +1,5 @@
 use aither_core::llm::{
-    Message, Role,
+    ContentPart as MessagePart, Message, Role,
     model::{OpenAIPromptCacheRetention, Parameters, ReasoningEffort, ToolChoice},
     tool::ToolDefinition,
 };
@@ -198,6 +198,35 @@ pub enum ContentPart {
     /// Image URL content part.
     #[serde(rename = "image_url")]
     ImageUrl { image_url: ImageUrlPayload },
+    /// Base64 audio content part.
+    #[serde(rename = "input_audio")]
+    InputAudio { input_audio: InputAudioPayload },
+    /// File content part, inline or by uploaded file ID.
+    #[serde(rename = "file")]
+    File { file: FilePayload },
+}
+
+/// Audio payload for audio-capable chat models.
+#[derive(Debug, Clone, Serialize)]
+pub struct InputAudioPayload {
+    /// Base64-encoded audio without the data URL prefix.
+    data: String,
+    /// `wav` or `mp3`.
+    format: &'static str,
+}
+
+/// File payload for chat messages.
+#[derive(Debug, Clone, Serialize)]
+pub struct FilePayload {
+    /// ID of a file uploaded through the Files API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_id: Option<String>,
+    /// Inline file as a base64 data URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_data: Option<String>,
+    /// File name shown to the model for inline files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
 }
 
 /// Image URL payload for vision.
@@ -308,32 +337,77 @@ pub fn to_chat_messages(messages: &[Message]) -> Vec<ChatMessagePayload> {
 ///
 /// Returns simple text for messages without attachments,
 /// or multimodal content parts for messages with attachments.
+/// Attachments that cannot be sent are described in a text part.
 fn build_content(message: &Message) -> ContentPayload {
-    let attachments = message.attachments();
-
-    if attachments.is_empty() {
+    if message.attachments().is_empty() {
         return ContentPayload::Text(message.content().to_owned());
     }
 
-    let mut parts = Vec::new();
+    let parts = message
+        .parts()
+        .into_iter()
+        .map(|part| {
+            let mapped = match &part {
+                MessagePart::Text(text) => Some(ContentPart::Text { text: text.clone() }),
+                MessagePart::Image(url) => url_to_data_url(url).map(|url| ContentPart::ImageUrl {
+                    image_url: ImageUrlPayload { url },
+                }),
+                MessagePart::Audio(url) => input_audio_payload(url)
+                    .map(|input_audio| ContentPart::InputAudio { input_audio }),
+                MessagePart::File(url) => file_payload(url).map(|file| ContentPart::File { file }),
+                _ => None,
+            };
+            mapped.unwrap_or_else(|| ContentPart::Text {
+                text: part.placeholder(),
+            })
+        })
+        .collect();
+
+    ContentPayload::Parts(parts)
+}
 
-    // Add image parts first
-    for attachment in attachments {
-        if let Some(data_url) = url_to_data_url(attachment) {
-            parts.push(ContentPart::ImageUrl {
-                image_url: ImageUrlPayload { url: data_url },
-            });
-        }
-    }
+/// Convert an audio attachment to an `input_audio` payload.
+///
+/// Chat Completions only accepts inline WAV and MP3 audio.
+fn input_audio_payload(url: &Url) -> Option<InputAudioPayload> {
+    let data_url = url_to_data_url(url)?;
+    let (header, data) = data_url.strip_prefix("data:")?.split_once(',')?;
+    let format = match header.strip_suffix(";base64")? {
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        _ => return None,
+    };
+    Some(InputAudioPayload {
+        data: data.to_string(),
+        format,
+    })
+}
 
-    // Add text content
-    if !message.content().is_empty() {
-        parts.push(ContentPart::Text {
-            text: message.content().to_owned(),
+/// Convert a file attachment to a `file` payload.
+///
+/// Uploaded files are referenced by ID; local and `data:` files are sent
+/// inline. Remote URLs are not fetched by Chat Completions.
+fn file_payload(url: &Url) -> Option<FilePayload> {
+    if let Some((_, id)) = parse_openai_file_url(url) {
+        return Some(FilePayload {
+            file_id: Some(id),
+            file_data: None,
+            filename: None,
         });
     }
-
-    ContentPayload::Parts(parts)
+    if !matches!(url.scheme(), "data" | "file") {
+        return None;
+    }
+    let filename = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|_| url.scheme() == "file")
+        .map_or_else(|| "attachment".to_string(), ToString::to_string);
+    Some(FilePayload {
+        file_id: None,
+        file_data: Some(url_to_data_url(url)?),
+        filename: Some(filename),
+    })
 }
 
 /// Flatten message content to a simple string.
@@ -551,7 +625,14 @@ pub enum ResponsesInputContent {
         source: InputImageSource,
     },
     InputFile {
-        file_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_data: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filename: Option<String>,
     },
 }
 
@@ -724,13 +805,16 @@ pub fn to_responses_input(messages: &[Message]) -> Result<Vec<ResponsesInputItem
                     ));
                 } else {
                     let mut parts = Vec::new();
-                    for attachment in attachments {
-                        parts.push(attachment_to_responses_part(attachment)?);
-                    }
-                    if !message.content().is_empty() {
-                        parts.push(ResponsesInputContent::InputText {
-                            text: message.content().to_owned(),
-                        });
+                    for part in message.parts() {
+                        let content = match &part {
+                            MessagePart::Text(text) => {
+                                Some(ResponsesInputContent::InputText { text: text.clone() })
+                            }
+                            _ => attachment_to_responses_part(&part)?,
+                        };
+                        parts.push(content.unwrap_or_else(|| ResponsesInputContent::InputText {
+                            text: part.placeholder(),
+                        }));
                     }
                     items.push(ResponsesInputItem::message(
                         "user",
@@ -793,26 +877,61 @@ pub fn to_responses_input(messages: &[Message]) -> Result<Vec<ResponsesInputItem
     Ok(items)
 }
 
-fn attachment_to_responses_part(url: &Url) -> Result<ResponsesInputContent, OpenAIError> {
+/// Map an attachment to a Responses API input part.
+///
+/// Returns `None` for parts the Responses API cannot take, such as audio.
+fn attachment_to_responses_part(
+    part: &MessagePart,
+) -> Result<Option<ResponsesInputContent>, OpenAIError> {
+    let Some(url) = part.url() else {
+        return Ok(None);
+    };
     if let Some((kind, id)) = parse_openai_file_url(url) {
         if kind.is_image() {
-            return Ok(ResponsesInputContent::InputImage {
+            return Ok(Some(ResponsesInputContent::InputImage {
                 source: InputImageSource::from_file_id(id),
-            });
+            }));
         }
-        return Ok(ResponsesInputContent::InputFile { file_id: id });
+        return Ok(Some(input_file(Some(id), None, None)));
     }
 
     match url.scheme() {
-        "http" | "https" | "data" => Ok(ResponsesInputContent::InputImage {
+        "http" | "https" | "data" => {}
+        "file" => {
+            return Err(OpenAIError::Api(
+                "file:// attachments must be uploaded via Files API".to_string(),
+            ));
+        }
+        other => {
+            return Err(OpenAIError::Api(format!(
+                "Unsupported attachment URL scheme: {other}"
+            )));
+        }
+    }
+
+    Ok(match part {
+        MessagePart::Image(_) => Some(ResponsesInputContent::InputImage {
             source: InputImageSource::from_url(url.as_str().to_string()),
         }),
-        "file" => Err(OpenAIError::Api(
-            "file:// attachments must be uploaded via Files API".to_string(),
-        )),
-        other => Err(OpenAIError::Api(format!(
-            "Unsupported attachment URL scheme: {other}"
-        ))),
+        MessagePart::File(_) if url.scheme() == "data" => {
+            Some(input_file(None, None, Some(url.as_str().to_string())))
+        }
+        MessagePart::File(_) => Some(input_file(None, Some(url.as_str().to_string()), None)),
+        _ => None,
+    })
+}
+
+fn input_file(
+    file_id: Option<String>,
+    file_url: Option<String>,
+    file_data: Option<String>,
+) -> ResponsesInputContent {
+    let filename = file_data.as_ref().map(|_| "attachment".to_string());
+    ResponsesInputContent::InputFile {
+        file_id,
+        file_url,
+        file_data,
+        filename,
     }
 }
 
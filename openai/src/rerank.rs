@@ -0,0 +1,106 @@
+use crate::{
+    client::{Config, OpenAI},
+    error::OpenAIError,
+};
+use aither_core::{
+    Reranker, Result as CoreResult,
+    rerank::{RankedDocument, sort_by_score},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use zenwave::{Client, client, header};
+
+/// Documents per request when the registry has no limit for the model.
+const DEFAULT_MAX_DOCUMENTS: usize = 100;
+
+/// Reranks through the `/rerank` endpoint of OpenAI-compatible servers
+/// such as vLLM, Jina and llama.cpp's server.
+impl Reranker for OpenAI {
+    fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+    ) -> impl core::future::Future<Output = CoreResult<Vec<RankedDocument>>> + Send {
+        let cfg = self.config();
+        async move {
+            let max_documents = aither_models::specialized_meta(&cfg.rerank_model)
+                .reranker_max_documents
+                .and_then(|max| usize::try_from(max).ok())
+                .filter(|&max| max > 0)
+                .unwrap_or(DEFAULT_MAX_DOCUMENTS);
+
+            let mut ranked = Vec::with_capacity(documents.len());
+            for (chunk_index, chunk) in documents.chunks(max_documents).enumerate() {
+                let offset = chunk_index * max_documents;
+                let results = rerank_once(cfg.clone(), query, chunk).await?;
+                ranked.extend(results.into_iter().map(|result| {
+                    RankedDocument::new(offset + result.index, result.relevance_score)
+                }));
+            }
+            sort_by_score(&mut ranked);
+            Ok(ranked)
+        }
+    }
+}
+
+async fn rerank_once(
+    cfg: Arc<Config>,
+    query: &str,
+    documents: &[&str],
+) -> Result<Vec<RerankResult>, OpenAIError> {
+    let endpoint = cfg.request_url("/rerank");
+    let mut backend = client();
+    let mut builder = backend
+        .post(endpoint)
+        .map_err(OpenAIError::Http)?
+        .header(cfg.auth_header_name(), cfg.request_auth())
+        .map_err(OpenAIError::Http)?
+        .header(header::USER_AGENT.as_str(), "aither-openai/0.1")
+        .map_err(OpenAIError::Http)?;
+    if let Some(org) = &cfg.organization {
+        builder = builder
+            .header("OpenAI-Organization", org.clone())
+            .map_err(OpenAIError::Http)?;
+    }
+    let request = RerankRequest {
+        model: &cfg.rerank_model,
+        query,
+        documents,
+    };
+    let response: RerankResponse = builder
+        .json_body(&request)
+        .map_err(OpenAIError::Http)?
+        .json()
+        .await
+        .map_err(OpenAIError::Http)?;
+    if let Some(result) = response
+        .results
+        .iter()
+        .find(|result| result.index >= documents.len())
+    {
+        return Err(OpenAIError::Api(format!(
+            "rerank result index {} out of range for {} documents",
+            result.index,
+            documents.len()
+        )));
+    }
+    Ok(response.results)
+}
+
+#[derive(Debug, Serialize)]
+struct RerankRequest<'a> {
+    model: &'a str,
+    query: &'a str,
+    documents: &'a [&'a str],
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResult {
+    index: usize,
+    relevance_score: f32,
+}
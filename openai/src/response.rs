@@ -66,6 +66,9 @@ pub enum ResponsesStreamEvent {
         #[serde(default)]
         text: String,
     },
+    /// Annotation added to output text, such as a web search citation
+    #[serde(rename = "response.output_text.annotation.added")]
+    OutputTextAnnotationAdded { annotation: Value },
     /// Reasoning text delta
     #[serde(rename = "response.reasoning_text.delta")]
     ReasoningTextDelta {
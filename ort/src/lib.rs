@@ -8,7 +8,11 @@
 //! - **No auto-download**: You provide the model and tokenizer paths
 //! - **Auto-detect dimension**: Embedding dimension is detected from model outputs
 //! - **Multiple pooling strategies**: `LastToken`, `Mean`, `Cls`
-//! - **GPU acceleration**: CUDA and `CoreML` enabled by default
+//! - **Batched, concurrent inference**: padded batches spread over a pool of sessions
+//! - **Long texts**: truncation or windowed chunk-and-average past the maximum length
+//! - **Asymmetric models**: separate query and document prefixes, such as E5's `query: `
+//! - **GPU acceleration**: CUDA and `CoreML` enabled by default, `DirectML` behind a feature,
+//!   with automatic fallback to the CPU
 //!
 //! # Example
 //!
@@ -28,18 +32,32 @@
 //! ```
 
 mod error;
+mod long_text;
 mod pooling;
+mod provider;
 
 pub use error::OrtError;
+pub use long_text::LongTextStrategy;
 pub use pooling::PoolingStrategy;
+pub use provider::{ExecutionProvider, ProviderReport};
 
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 use aither_core::EmbeddingModel;
-use ndarray::{Axis, Ix2, Ix3};
+use ndarray::{Axis, Ix2, Ix3, Slice};
 use ort::session::{Session, builder::GraphOptimizationLevel};
-use tokenizers::Tokenizer;
+use tokenizers::{Encoding, Tokenizer};
+
+use crate::long_text::weighted_mean;
+
+/// Texts per forward pass unless configured otherwise.
+const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// Maximum sequence length when neither the builder nor `tokenizer.json`
+/// sets one.
+const DEFAULT_MAX_LENGTH: usize = 512;
 
 /// An embedding model backed by ONNX Runtime.
 ///
@@ -63,11 +81,19 @@ use tokenizers::Tokenizer;
 /// # Ok::<(), aither_ort::OrtError>(())
 /// ```
 pub struct OrtEmbedding {
-    session: Mutex<Session>,
+    sessions: Vec<Mutex<Session>>,
+    /// Round-robin cursor for waiting on a session when all are busy.
+    next_session: AtomicUsize,
     tokenizer: Tokenizer,
     dimension: usize,
     pooling: PoolingStrategy,
     normalize: bool,
+    batch_size: usize,
+    max_length: usize,
+    long_text: LongTextStrategy,
+    provider: ProviderReport,
+    query_prefix: Option<String>,
+    document_prefix: Option<String>,
 }
 
 impl std::fmt::Debug for OrtEmbedding {
@@ -76,6 +102,13 @@ impl std::fmt::Debug for OrtEmbedding {
             .field("dimension", &self.dimension)
             .field("pooling", &self.pooling)
             .field("normalize", &self.normalize)
+            .field("sessions", &self.sessions.len())
+            .field("batch_size", &self.batch_size)
+            .field("max_length", &self.max_length)
+            .field("long_text", &self.long_text)
+            .field("provider", &self.provider.selected)
+            .field("query_prefix", &self.query_prefix)
+            .field("document_prefix", &self.document_prefix)
             .finish_non_exhaustive()
     }
 }
@@ -129,6 +162,26 @@ impl OrtEmbedding {
     pub const fn normalize(&self) -> bool {
         self.normalize
     }
+
+    /// Returns the maximum number of tokens per sequence.
+    #[must_use]
+    pub const fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Returns how texts longer than [`max_length`](Self::max_length) are
+    /// handled.
+    #[must_use]
+    pub const fn long_text(&self) -> LongTextStrategy {
+        self.long_text
+    }
+
+    /// Returns the execution provider the model runs on and why any
+    /// preferred ones were skipped.
+    #[must_use]
+    pub const fn provider(&self) -> &ProviderReport {
+        &self.provider
+    }
 }
 
 impl EmbeddingModel for OrtEmbedding {
@@ -137,37 +190,200 @@ impl EmbeddingModel for OrtEmbedding {
     }
 
     async fn embed(&self, text: &str) -> aither_core::Result<Vec<f32>> {
-        // Tokenize
-        let encoding = self
-            .tokenizer
-            .encode(text, true)
-            .map_err(|e| OrtError::Tokenization(e.to_string()))?;
+        let mut embeddings = self.embed_texts(&[text])?;
+        Ok(embeddings.pop().ok_or(OrtError::InvalidOutputShape(0))?)
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> aither_core::Result<Vec<Vec<f32>>> {
+        Ok(self.embed_texts(texts)?)
+    }
+
+    async fn embed_query(&self, query: &str) -> aither_core::Result<Vec<f32>> {
+        let mut embeddings = self.embed_prefixed(self.query_prefix.as_deref(), &[query])?;
+        Ok(embeddings.pop().ok_or(OrtError::InvalidOutputShape(0))?)
+    }
+
+    async fn embed_document(&self, text: &str) -> aither_core::Result<Vec<f32>> {
+        let mut embeddings = self.embed_prefixed(self.document_prefix.as_deref(), &[text])?;
+        Ok(embeddings.pop().ok_or(OrtError::InvalidOutputShape(0))?)
+    }
+
+    async fn embed_documents(&self, texts: &[&str]) -> aither_core::Result<Vec<Vec<f32>>> {
+        Ok(self.embed_prefixed(self.document_prefix.as_deref(), texts)?)
+    }
+}
+
+impl OrtEmbedding {
+    /// Embed `texts` with `prefix` prepended to each.
+    fn embed_prefixed(
+        &self,
+        prefix: Option<&str>,
+        texts: &[&str],
+    ) -> Result<Vec<Vec<f32>>, OrtError> {
+        let Some(prefix) = prefix else {
+            return self.embed_texts(texts);
+        };
+        let prefixed: Vec<String> = texts.iter().map(|text| format!("{prefix}{text}")).collect();
+        let prefixed: Vec<&str> = prefixed.iter().map(String::as_str).collect();
+        self.embed_texts(&prefixed)
+    }
+
+    /// Embed `texts`, applying the long-text strategy and normalization.
+    fn embed_texts(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, OrtError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Tokenize; the tokenizer truncates to `max_length` and keeps the
+        // rest as overflowing windows.
+        let mut encodings = Vec::with_capacity(texts.len());
+        let mut owners = Vec::with_capacity(texts.len());
+        for (index, text) in texts.iter().enumerate() {
+            let mut encoding = self
+                .tokenizer
+                .encode(*text, true)
+                .map_err(|e| OrtError::Tokenization(e.to_string()))?;
+            let overflowing = encoding.take_overflowing();
+            encodings.push(encoding);
+            owners.push(index);
+            if self.long_text.keeps_overflow() {
+                owners.extend(std::iter::repeat_n(index, overflowing.len()));
+                encodings.extend(overflowing);
+            }
+        }
 
-        let input_ids: Vec<i64> = encoding.get_ids().iter().map(|&id| i64::from(id)).collect();
-        let attention_mask: Vec<i64> = encoding
-            .get_attention_mask()
+        let windows = self.embed_encodings(&encodings)?;
+        let mut per_text: Vec<Vec<(Vec<f32>, usize)>> = vec![Vec::new(); texts.len()];
+        for ((embedding, encoding), owner) in windows.into_iter().zip(&encodings).zip(owners) {
+            let tokens = encoding
+                .get_attention_mask()
+                .iter()
+                .filter(|&&m| m != 0)
+                .count();
+            per_text[owner].push((embedding, tokens));
+        }
+
+        let mut embeddings: Vec<Vec<f32>> = per_text
+            .iter()
+            .map(|windows| weighted_mean(windows))
+            .collect();
+
+        // Normalize if enabled
+        if self.normalize {
+            for embedding in &mut embeddings {
+                l2_normalize(embedding);
+            }
+        }
+
+        Ok(embeddings)
+    }
+
+    /// Embed `encodings` in batches of at most `batch_size`.
+    ///
+    /// Encodings are grouped by token count so each batch pads as little as
+    /// possible, and batches run in parallel when there are several
+    /// sessions. Results are unnormalized and in input order.
+    fn embed_encodings(&self, encodings: &[Encoding]) -> Result<Vec<Vec<f32>>, OrtError> {
+        let lengths: Vec<usize> = encodings
+            .iter()
+            .map(|encoding| encoding.get_ids().len())
+            .collect();
+        let batches = length_sorted_batches(&lengths, self.batch_size);
+        let run = |batch: &[usize]| {
+            let batch: Vec<&Encoding> = batch.iter().map(|&index| &encodings[index]).collect();
+            self.run_batch(&batch)
+        };
+
+        let workers = self.sessions.len().min(batches.len());
+        let results = if workers <= 1 {
+            batches
+                .iter()
+                .map(|batch| run(batch))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            let next = AtomicUsize::new(0);
+            let mut results: Vec<Option<Vec<Vec<f32>>>> = vec![None; batches.len()];
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = (0..workers)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut done = Vec::new();
+                            loop {
+                                let index = next.fetch_add(1, Ordering::Relaxed);
+                                let Some(batch) = batches.get(index) else {
+                                    return Ok::<_, OrtError>(done);
+                                };
+                                done.push((index, run(batch)?));
+                            }
+                        })
+                    })
+                    .collect();
+                for handle in handles {
+                    let done = handle.join().expect("embedding worker panicked")?;
+                    for (index, embeddings) in done {
+                        results[index] = Some(embeddings);
+                    }
+                }
+                Ok::<_, OrtError>(())
+            })?;
+            results.into_iter().flatten().collect()
+        };
+
+        let mut embeddings = vec![Vec::new(); encodings.len()];
+        for (batch, batch_embeddings) in batches.iter().zip(results) {
+            for (&index, embedding) in batch.iter().zip(batch_embeddings) {
+                embeddings[index] = embedding;
+            }
+        }
+        Ok(embeddings)
+    }
+
+    /// Embed `encodings` in a single forward pass.
+    ///
+    /// Shorter inputs are right-padded to the longest one; padding is masked
+    /// out by the attention mask and ignored by pooling.
+    fn run_batch(&self, encodings: &[&Encoding]) -> Result<Vec<Vec<f32>>, OrtError> {
+        let batch = encodings.len();
+        let seq_len = encodings
+            .iter()
+            .map(|encoding| encoding.get_ids().len())
+            .max()
+            .unwrap_or(0);
+        let pad_id = self
+            .tokenizer
+            .get_padding()
+            .map_or(0, |padding| i64::from(padding.pad_id));
+
+        let mut input_ids = Vec::with_capacity(batch * seq_len);
+        let mut attention_masks: Vec<Vec<u32>> = Vec::with_capacity(batch);
+        for encoding in encodings {
+            let ids = encoding.get_ids();
+            input_ids.extend(ids.iter().map(|&id| i64::from(id)));
+            input_ids.extend(std::iter::repeat_n(pad_id, seq_len - ids.len()));
+
+            let mut mask = encoding.get_attention_mask().to_vec();
+            mask.resize(seq_len, 0);
+            attention_masks.push(mask);
+        }
+        let attention_mask: Vec<i64> = attention_masks
             .iter()
+            .flatten()
             .map(|&m| i64::from(m))
             .collect();
-        let seq_len = input_ids.len();
 
-        // Create tensors [batch=1, seq_len]
+        // Create tensors [batch, seq_len]
         let input_ids_tensor =
-            ort::value::Tensor::from_array(([1, seq_len], input_ids.into_boxed_slice()))
-                .map_err(OrtError::from)?;
+            ort::value::Tensor::from_array(([batch, seq_len], input_ids.into_boxed_slice()))?;
         let attention_mask_tensor =
-            ort::value::Tensor::from_array(([1, seq_len], attention_mask.into_boxed_slice()))
-                .map_err(OrtError::from)?;
+            ort::value::Tensor::from_array(([batch, seq_len], attention_mask.into_boxed_slice()))?;
 
         // Run inference and extract to owned array (before releasing session lock)
         let hidden_states_owned = {
-            let mut session = self.session.lock().expect("session lock poisoned");
-            let outputs = session
-                .run(ort::inputs![
-                    "input_ids" => input_ids_tensor,
-                    "attention_mask" => attention_mask_tensor,
-                ])
-                .map_err(OrtError::from)?;
+            let mut session = self.acquire_session();
+            let outputs = session.run(ort::inputs![
+                "input_ids" => input_ids_tensor,
+                "attention_mask" => attention_mask_tensor,
+            ])?;
 
             // Extract hidden states - try common output names
             let hidden_states = outputs
@@ -176,41 +392,70 @@ impl EmbeddingModel for OrtEmbedding {
                 .or_else(|| outputs.get("output"))
                 .ok_or(OrtError::InvalidOutputShape(0))?;
 
-            let view = hidden_states
-                .try_extract_array::<f32>()
-                .map_err(OrtError::from)?;
+            let view = hidden_states.try_extract_array::<f32>()?;
 
             // Convert to owned array before releasing lock
             view.to_owned()
         };
 
         let rank = hidden_states_owned.shape().len();
-        let hidden_states_3d = match rank {
-            3 => hidden_states_owned
-                .into_dimensionality::<Ix3>()
-                .map_err(|e| OrtError::Shape(e.to_string()))?,
+        let embeddings = match rank {
+            3 => {
+                let hidden_states = hidden_states_owned
+                    .into_dimensionality::<Ix3>()
+                    .map_err(|e| OrtError::Shape(e.to_string()))?;
+                // Apply pooling per sequence
+                attention_masks
+                    .iter()
+                    .enumerate()
+                    .map(|(index, mask)| {
+                        let sequence =
+                            hidden_states.slice_axis(Axis(0), Slice::from(index..=index));
+                        self.pooling.apply(&sequence, mask)
+                    })
+                    .collect::<Vec<_>>()
+            }
+            // Already pooled by the model: one row per sequence.
             2 => hidden_states_owned
                 .into_dimensionality::<Ix2>()
                 .map_err(|e| OrtError::Shape(e.to_string()))?
-                .insert_axis(Axis(0)),
-            _ => return Err(OrtError::InvalidOutputShape(rank).into()),
+                .outer_iter()
+                .map(|row| row.to_vec())
+                .collect(),
+            _ => return Err(OrtError::InvalidOutputShape(rank)),
         };
+        if embeddings.len() != batch {
+            return Err(OrtError::InvalidOutputShape(rank));
+        }
 
-        // Apply pooling
-        let attention_mask_u32: Vec<u32> = encoding.get_attention_mask().to_vec();
-        let mut embedding = self
-            .pooling
-            .apply(&hidden_states_3d.view(), &attention_mask_u32);
+        Ok(embeddings)
+    }
 
-        // Normalize if enabled
-        if self.normalize {
-            l2_normalize(&mut embedding);
+    /// Lock an idle session, or wait for one in round-robin order.
+    fn acquire_session(&self) -> MutexGuard<'_, Session> {
+        for session in &self.sessions {
+            if let Ok(guard) = session.try_lock() {
+                return guard;
+            }
         }
-
-        Ok(embedding)
+        let index = self.next_session.fetch_add(1, Ordering::Relaxed) % self.sessions.len();
+        self.sessions[index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
     }
 }
 
+/// Splits input indices into batches of at most `batch_size`, grouping
+/// inputs of similar length to minimize padding.
+fn length_sorted_batches(lengths: &[usize], batch_size: usize) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..lengths.len()).collect();
+    order.sort_by_key(|&index| lengths[index]);
+    order
+        .chunks(batch_size.max(1))
+        .map(<[usize]>::to_vec)
+        .collect()
+}
+
 /// Builder for [`OrtEmbedding`].
 #[derive(Debug, Default)]
 pub struct OrtEmbeddingBuilder {
@@ -218,6 +463,15 @@ pub struct OrtEmbeddingBuilder {
     tokenizer_path: Option<PathBuf>,
     pooling: PoolingStrategy,
     normalize: bool,
+    sessions: Option<usize>,
+    batch_size: Option<usize>,
+    max_length: Option<usize>,
+    long_text: LongTextStrategy,
+    providers: Option<Vec<ExecutionProvider>>,
+    intra_threads: Option<usize>,
+    inter_threads: Option<usize>,
+    query_prefix: Option<String>,
+    document_prefix: Option<String>,
 }
 
 impl OrtEmbeddingBuilder {
@@ -253,6 +507,103 @@ impl OrtEmbeddingBuilder {
         self
     }
 
+    /// Set how many sessions run inference concurrently.
+    ///
+    /// Each session holds its own copy of the model, so memory grows with
+    /// the count; CPU threads are split between them. Default: `1`
+    #[must_use]
+    pub const fn sessions(mut self, count: usize) -> Self {
+        self.sessions = Some(if count == 0 { 1 } else { count });
+        self
+    }
+
+    /// Set the maximum number of texts per forward pass.
+    ///
+    /// Default: `32`
+    #[must_use]
+    pub const fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = Some(if size == 0 { 1 } else { size });
+        self
+    }
+
+    /// Set the maximum number of tokens per sequence, special tokens
+    /// included.
+    ///
+    /// Default: the truncation length in `tokenizer.json`, or `512`
+    #[must_use]
+    pub const fn max_length(mut self, tokens: usize) -> Self {
+        self.max_length = Some(tokens);
+        self
+    }
+
+    /// Set how texts longer than the maximum length are handled.
+    ///
+    /// Default: [`LongTextStrategy::Truncate`]
+    #[must_use]
+    pub const fn long_text(mut self, strategy: LongTextStrategy) -> Self {
+        self.long_text = strategy;
+        self
+    }
+
+    /// Set the execution providers to try, in order of preference.
+    ///
+    /// The first one that registers is used; if none does, the model runs on
+    /// the CPU. Check [`OrtEmbedding::provider`] for the outcome.
+    ///
+    /// Default: every provider compiled in, fastest first
+    #[must_use]
+    pub fn execution_providers(
+        mut self,
+        providers: impl IntoIterator<Item = ExecutionProvider>,
+    ) -> Self {
+        self.providers = Some(providers.into_iter().collect());
+        self
+    }
+
+    /// Set the number of threads each session uses within an operator.
+    ///
+    /// Default: the available cores divided between the sessions
+    #[must_use]
+    pub const fn intra_threads(mut self, threads: usize) -> Self {
+        self.intra_threads = Some(threads);
+        self
+    }
+
+    /// Set the number of threads each session uses to run independent
+    /// operators in parallel.
+    ///
+    /// Default: chosen by ONNX Runtime
+    #[must_use]
+    pub const fn inter_threads(mut self, threads: usize) -> Self {
+        self.inter_threads = Some(threads);
+        self
+    }
+
+    /// Set the text prepended to queries embedded with
+    /// [`EmbeddingModel::embed_query`].
+    ///
+    /// Asymmetric models are trained with one, for example `"query: "` for
+    /// E5, or a task instruction for Qwen3-Embedding:
+    /// `"Instruct: Given a web search query, retrieve relevant passages that answer the query\nQuery: "`.
+    ///
+    /// Default: none
+    #[must_use]
+    pub fn query_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.query_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Set the text prepended to documents embedded with
+    /// [`EmbeddingModel::embed_document`] and
+    /// [`EmbeddingModel::embed_documents`], such as `"passage: "` for E5.
+    ///
+    /// Default: none
+    #[must_use]
+    pub fn document_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.document_prefix = Some(prefix.into());
+        self
+    }
+
     /// Build the [`OrtEmbedding`] instance.
     ///
     /// # Errors
@@ -274,24 +625,58 @@ impl OrtEmbeddingBuilder {
             OrtError::TokenizerNotFound(model_path.parent().unwrap_or(&model_path).to_path_buf())
         })?;
 
-        let tokenizer = Tokenizer::from_file(&tokenizer_path)
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
             .map_err(|e| OrtError::tokenizer(&tokenizer_path, e))?;
 
-        // Load ONNX session with optimizations
-        let session = Session::builder()?
-            .with_optimization_level(GraphOptimizationLevel::Level3)?
-            .with_intra_threads(num_cpus())?
-            .commit_from_file(&model_path)?;
+        // Truncate through the tokenizer so every window keeps the model's
+        // special tokens.
+        let mut truncation = tokenizer.get_truncation().cloned().unwrap_or_default();
+        let max_length = self
+            .max_length
+            .or_else(|| tokenizer.get_truncation().map(|params| params.max_length))
+            .unwrap_or(DEFAULT_MAX_LENGTH);
+        truncation.max_length = max_length;
+        truncation.stride = self.long_text.stride();
+        tokenizer
+            .with_truncation(Some(truncation))
+            .map_err(|e| OrtError::tokenizer(&tokenizer_path, e))?;
+
+        // Load ONNX sessions with optimizations, sharing the CPU cores
+        let count = self.sessions.unwrap_or(1);
+        let intra_threads = self
+            .intra_threads
+            .unwrap_or_else(|| (num_cpus() / count).max(1));
+        let preferences = self.providers.unwrap_or_else(ExecutionProvider::compiled);
+        let mut sessions = Vec::with_capacity(count);
+        let mut selection = None;
+        for _ in 0..count {
+            let mut builder = Session::builder()?
+                .with_optimization_level(GraphOptimizationLevel::Level3)?
+                .with_intra_threads(intra_threads)?;
+            if let Some(threads) = self.inter_threads {
+                builder = builder.with_inter_threads(threads)?;
+            }
+            let report = provider::select(&mut builder, &preferences);
+            sessions.push(builder.commit_from_file(&model_path)?);
+            selection.get_or_insert(report);
+        }
 
         // Auto-detect dimension from model outputs
-        let dimension = detect_embedding_dimension(&session)?;
+        let dimension = detect_embedding_dimension(&sessions[0])?;
 
         Ok(OrtEmbedding {
-            session: Mutex::new(session),
+            sessions: sessions.into_iter().map(Mutex::new).collect(),
+            next_session: AtomicUsize::new(0),
             tokenizer,
             dimension,
             pooling: self.pooling,
             normalize: self.normalize,
+            batch_size: self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE),
+            max_length,
+            long_text: self.long_text,
+            provider: selection.expect("at least one session is built"),
+            query_prefix: self.query_prefix,
+            document_prefix: self.document_prefix,
         })
     }
 }
@@ -420,6 +805,19 @@ mod tests {
         assert!(matches!(result, Err(OrtError::ModelNotFound(_))));
     }
 
+    #[test]
+    fn batches_group_similar_lengths() {
+        let batches = length_sorted_batches(&[5, 1, 9, 2, 6], 2);
+        assert_eq!(batches, vec![vec![1, 3], vec![0, 4], vec![2]]);
+    }
+
+    #[test]
+    fn chunk_mean_weights_windows_by_tokens() {
+        let mean = weighted_mean(&[(vec![1.0, 0.0], 3), (vec![0.0, 1.0], 1)]);
+        assert!((mean[0] - 0.75).abs() < 1e-6);
+        assert!((mean[1] - 0.25).abs() < 1e-6);
+    }
+
     #[test]
     fn l2_normalize_works() {
         let mut vec = vec![3.0, 4.0];
@@ -0,0 +1,68 @@
+//! Handling for texts longer than the model's maximum sequence length.
+
+/// What to do with texts that tokenize to more than the configured maximum
+/// length.
+///
+/// Transformer models only attend over a fixed number of positions; longer
+/// inputs either fail inside ONNX Runtime or produce meaningless vectors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LongTextStrategy {
+    /// Keep the first `max_length` tokens and drop the rest.
+    ///
+    /// Cheap, and a good fit for queries and short passages where the start
+    /// carries the meaning.
+    #[default]
+    Truncate,
+
+    /// Split the text into windows of `max_length` tokens and average their
+    /// embeddings, weighted by the number of tokens in each window.
+    ///
+    /// Covers the whole document at the cost of one extra sequence per
+    /// window.
+    ChunkMean {
+        /// Number of tokens consecutive windows share, so sentences cut at a
+        /// boundary still appear whole in one of them. Must be smaller than
+        /// the maximum length minus the model's special tokens.
+        overlap: usize,
+    },
+}
+
+impl LongTextStrategy {
+    /// Token overlap passed to the tokenizer's truncation.
+    pub(crate) const fn stride(self) -> usize {
+        match self {
+            Self::Truncate => 0,
+            Self::ChunkMean { overlap } => overlap,
+        }
+    }
+
+    /// Whether windows past the first one are embedded.
+    pub(crate) const fn keeps_overflow(self) -> bool {
+        matches!(self, Self::ChunkMean { .. })
+    }
+}
+
+/// Averages window embeddings, weighting each by its token count.
+pub(crate) fn weighted_mean(windows: &[(Vec<f32>, usize)]) -> Vec<f32> {
+    let Some((first, _)) = windows.first() else {
+        return Vec::new();
+    };
+    if windows.len() == 1 {
+        return first.clone();
+    }
+
+    let total: usize = windows.iter().map(|(_, tokens)| tokens).sum();
+    let mut mean = vec![0.0; first.len()];
+    for (embedding, tokens) in windows {
+        #[allow(clippy::cast_precision_loss)]
+        let weight = if total == 0 {
+            1.0 / windows.len() as f32
+        } else {
+            *tokens as f32 / total as f32
+        };
+        for (sum, value) in mean.iter_mut().zip(embedding) {
+            *sum += value * weight;
+        }
+    }
+    mean
+}
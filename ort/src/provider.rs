@@ -0,0 +1,132 @@
+//! Execution provider selection.
+
+use ort::session::builder::SessionBuilder;
+
+/// Hardware backend ONNX Runtime runs the model on.
+///
+/// Accelerated providers need both the matching crate feature (`cuda`,
+/// `coreml` or `directml`) and the runtime libraries on the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionProvider {
+    /// NVIDIA GPUs through CUDA.
+    Cuda {
+        /// Index of the GPU to use.
+        device_id: i32,
+    },
+    /// Apple Neural Engine and GPU through `CoreML`.
+    CoreMl,
+    /// `DirectX` 12 GPUs on Windows through `DirectML`.
+    DirectMl {
+        /// Index of the adapter to use.
+        device_id: i32,
+    },
+    /// The CPU, which is always available.
+    Cpu,
+}
+
+impl ExecutionProvider {
+    /// Short name of the provider, as used in logs.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Cuda { .. } => "cuda",
+            Self::CoreMl => "coreml",
+            Self::DirectMl { .. } => "directml",
+            Self::Cpu => "cpu",
+        }
+    }
+
+    /// Every provider compiled into this build, fastest first.
+    pub(crate) fn compiled() -> Vec<Self> {
+        let mut providers = Vec::new();
+        if cfg!(feature = "cuda") {
+            providers.push(Self::Cuda { device_id: 0 });
+        }
+        if cfg!(feature = "coreml") {
+            providers.push(Self::CoreMl);
+        }
+        if cfg!(feature = "directml") {
+            providers.push(Self::DirectMl { device_id: 0 });
+        }
+        providers.push(Self::Cpu);
+        providers
+    }
+
+    /// Registers the provider on `builder`, explaining why if it can't be.
+    #[cfg_attr(
+        not(any(feature = "cuda", feature = "coreml", feature = "directml")),
+        allow(unused_variables)
+    )]
+    fn register(self, builder: &mut SessionBuilder) -> Result<(), String> {
+        match self {
+            #[cfg(feature = "cuda")]
+            Self::Cuda { device_id } => register(
+                &ort::execution_providers::CUDAExecutionProvider::default()
+                    .with_device_id(device_id),
+                builder,
+            ),
+            #[cfg(feature = "coreml")]
+            Self::CoreMl => register(
+                &ort::execution_providers::CoreMLExecutionProvider::default(),
+                builder,
+            ),
+            #[cfg(feature = "directml")]
+            Self::DirectMl { device_id } => register(
+                &ort::execution_providers::DirectMLExecutionProvider::default()
+                    .with_device_id(device_id),
+                builder,
+            ),
+            Self::Cpu => Ok(()),
+            #[allow(unreachable_patterns)]
+            other => Err(format!(
+                "aither-ort was built without the `{}` feature",
+                other.name()
+            )),
+        }
+    }
+}
+
+#[cfg(any(feature = "cuda", feature = "coreml", feature = "directml"))]
+fn register(
+    provider: &impl ort::execution_providers::ExecutionProvider,
+    builder: &mut SessionBuilder,
+) -> Result<(), String> {
+    if !provider.is_available().unwrap_or(false) {
+        return Err("not available in this ONNX Runtime build".to_string());
+    }
+    provider.register(builder).map_err(|e| e.to_string())
+}
+
+/// Which execution provider a model ended up on, and why the preferred ones
+/// were passed over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderReport {
+    /// Provider the sessions run on.
+    pub selected: ExecutionProvider,
+    /// Providers tried before it, with the reason each was skipped.
+    pub skipped: Vec<(ExecutionProvider, String)>,
+}
+
+/// Registers the first provider in `preferences` that works, falling back to
+/// the CPU.
+pub(crate) fn select(
+    builder: &mut SessionBuilder,
+    preferences: &[ExecutionProvider],
+) -> ProviderReport {
+    let mut skipped = Vec::new();
+    for &provider in preferences {
+        match provider.register(builder) {
+            Ok(()) => {
+                return ProviderReport {
+                    selected: provider,
+                    skipped,
+                };
+            }
+            Err(reason) => skipped.push((provider, reason)),
+        }
+    }
+    ProviderReport {
+        selected: ExecutionProvider::Cpu,
+        skipped,
+    }
+}
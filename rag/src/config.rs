@@ -11,6 +11,9 @@ pub struct RagConfig {
     pub similarity_threshold: f32,
     /// Default number of results to return.
     pub default_top_k: usize,
+    /// Number of candidates fetched by embedding similarity before
+    /// reranking in [`RagStore::search_reranked`](crate::RagStore::search_reranked).
+    pub rerank_candidates: usize,
     /// Whether to enable content deduplication.
     pub deduplication: bool,
     /// Whether to automatically save after indexing operations.
@@ -23,6 +26,7 @@ impl Default for RagConfig {
             index_path: PathBuf::from("./rag_index.redb"),
             similarity_threshold: 0.0,
             default_top_k: 5,
+            rerank_candidates: 20,
             deduplication: true,
             auto_save: true,
         }
@@ -79,6 +83,13 @@ impl RagConfigBuilder {
         self
     }
 
+    /// Sets how many candidates are reranked per search.
+    #[must_use]
+    pub const fn rerank_candidates(mut self, count: usize) -> Self {
+        self.config.rerank_candidates = count;
+        self
+    }
+
     /// Enables or disables content deduplication.
     #[must_use]
     pub const fn deduplication(mut self, enabled: bool) -> Self {
@@ -110,6 +121,7 @@ mod tests {
         assert_eq!(config.index_path, PathBuf::from("./rag_index.redb"));
         assert_eq!(config.similarity_threshold, 0.0);
         assert_eq!(config.default_top_k, 5);
+        assert_eq!(config.rerank_candidates, 20);
         assert!(config.deduplication);
         assert!(config.auto_save);
     }
@@ -120,6 +132,7 @@ mod tests {
             .index_path("/custom/path.redb")
             .similarity_threshold(0.5)
             .default_top_k(10)
+            .rerank_candidates(50)
             .deduplication(false)
             .auto_save(false)
             .build();
@@ -127,6 +140,7 @@ mod tests {
         assert_eq!(config.index_path, PathBuf::from("/custom/path.redb"));
         assert_eq!(config.similarity_threshold, 0.5);
         assert_eq!(config.default_top_k, 10);
+        assert_eq!(config.rerank_candidates, 50);
         assert!(!config.deduplication);
         assert!(!config.auto_save);
     }
@@ -10,6 +10,10 @@ pub enum RagError {
     #[error("embedding failed: {0}")]
     Embedding(#[source] anyhow::Error),
 
+    /// Reranking failed.
+    #[error("reranking failed: {0}")]
+    Rerank(#[source] anyhow::Error),
+
     /// Vector index operation failed.
     #[error("index error: {0}")]
     Index(String),
@@ -5,6 +5,7 @@
 //! - **Text chunking** strategies (fixed-size and sentence-based)
 //! - **Persistence** backends (rkyv binary and redb embedded database)
 //! - **Deduplication** using content hashing
+//! - **Reranking** of search results with any [`aither_core::Reranker`]
 //! - **Tool integration** for LLM function calling
 //!
 //! # Quick Start
@@ -4,6 +4,7 @@ use std::fs;
 use std::path::Path;
 
 use aither_core::embedding::EmbeddingModel;
+use aither_core::rerank::Reranker;
 
 use crate::chunking::{Chunker, CodeChunker, FixedSizeChunker, ParagraphChunker, SentenceChunker};
 use crate::cleaning::{BasicCleaner, Cleaner};
@@ -184,6 +185,18 @@ where
         self.store.search_with_k(query, top_k).await
     }
 
+    /// Searches and reorders the candidates with `reranker`.
+    ///
+    /// See [`RagStore::search_reranked`].
+    pub async fn search_reranked<R: Reranker>(
+        &self,
+        query: &str,
+        top_k: usize,
+        reranker: &R,
+    ) -> Result<Vec<SearchResult>> {
+        self.store.search_reranked(query, top_k, reranker).await
+    }
+
     /// Returns a reference to the underlying store.
     pub const fn store(&self) -> &RagStore<M, C, L> {
         &self.store
@@ -271,6 +284,13 @@ where
         self
     }
 
+    /// Sets how many candidates are reranked per search.
+    #[must_use]
+    pub fn rerank_candidates(mut self, count: usize) -> Self {
+        self.config_builder = self.config_builder.rerank_candidates(count);
+        self
+    }
+
     /// Enables or disables deduplication.
     #[must_use]
     pub fn deduplication(mut self, enabled: bool) -> Self {
@@ -1,8 +1,10 @@
 //! Core RAG store implementation.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use aither_core::embedding::EmbeddingModel;
+use aither_core::rerank::Reranker;
 
 use crate::chunking::{Chunker, FixedSizeChunker};
 use crate::cleaning::{BasicCleaner, Cleaner};
@@ -120,36 +122,56 @@ where
     pub async fn insert(&self, document: Document) -> Result<usize> {
         let cleaned = self.cleaner.clean(&document);
         let chunks = self.chunker.chunk(&cleaned)?;
-        let mut inserted = 0;
-
-        for chunk in chunks {
-            if self.config.deduplication && self.index.contains_hash(chunk.content_hash) {
-                continue;
-            }
-
-            let embedding = self
-                .embedder
-                .embed(&chunk.text)
-                .await
-                .map_err(RagError::Embedding)?;
-
-            self.index.insert(chunk, embedding)?;
-            inserted += 1;
-        }
-
-        Ok(inserted)
+        self.insert_chunks(chunks).await
     }
 
     /// Inserts multiple documents into the store.
     ///
+    /// Chunks from all documents are embedded together in as few requests
+    /// as the embedding model allows.
+    ///
     /// # Returns
     /// The total number of chunks inserted across all documents.
     pub async fn insert_batch(&self, documents: Vec<Document>) -> Result<usize> {
-        let mut total_inserted = 0;
+        let mut chunks = Vec::new();
         for doc in documents {
-            total_inserted += self.insert(doc).await?;
+            let cleaned = self.cleaner.clean(&doc);
+            chunks.extend(self.chunker.chunk(&cleaned)?);
+        }
+        self.insert_chunks(chunks).await
+    }
+
+    /// Deduplicates (if enabled), embeds and indexes chunks.
+    async fn insert_chunks(&self, chunks: Vec<Chunk>) -> Result<usize> {
+        let chunks = if self.config.deduplication {
+            let mut seen = HashSet::new();
+            chunks
+                .into_iter()
+                .filter(|chunk| {
+                    !self.index.contains_hash(chunk.content_hash) && seen.insert(chunk.content_hash)
+                })
+                .collect()
+        } else {
+            chunks
+        };
+        if chunks.is_empty() {
+            return Ok(0);
+        }
+
+        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        let embeddings = self
+            .embedder
+            .embed_documents(&texts)
+            .await
+            .map_err(RagError::Embedding)?;
+
+        let mut inserted = 0;
+        for (chunk, embedding) in chunks.into_iter().zip(embeddings) {
+            self.index.insert(chunk, embedding)?;
+            inserted += 1;
         }
-        Ok(total_inserted)
+
+        Ok(inserted)
     }
 
     /// Inserts a pre-chunked chunk with a precomputed embedding.
@@ -189,7 +211,7 @@ where
     pub async fn search_with_k(&self, query: &str, top_k: usize) -> Result<Vec<SearchResult>> {
         let embedding = self
             .embedder
-            .embed(query)
+            .embed_query(query)
             .await
             .map_err(RagError::Embedding)?;
 
@@ -197,6 +219,45 @@ where
             .search(&embedding, top_k, self.config.similarity_threshold)
     }
 
+    /// Searches for chunks similar to the query and reorders them with `reranker`.
+    ///
+    /// Fetches [`RagConfig::rerank_candidates`] chunks (at least `top_k`) by
+    /// embedding similarity and returns the `top_k` the reranker scores
+    /// highest. Result scores are the reranker's scores.
+    pub async fn search_reranked<R: Reranker>(
+        &self,
+        query: &str,
+        top_k: usize,
+        reranker: &R,
+    ) -> Result<Vec<SearchResult>> {
+        let candidates = self
+            .search_with_k(query, top_k.max(self.config.rerank_candidates))
+            .await?;
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+
+        let texts: Vec<&str> = candidates
+            .iter()
+            .map(|result| result.chunk.text.as_str())
+            .collect();
+        let ranked = reranker
+            .rerank(query, &texts)
+            .await
+            .map_err(RagError::Rerank)?;
+
+        let mut candidates: Vec<Option<SearchResult>> = candidates.into_iter().map(Some).collect();
+        Ok(ranked
+            .into_iter()
+            .filter_map(|ranked| {
+                let mut result = candidates.get_mut(ranked.index)?.take()?;
+                result.score = ranked.score;
+                Some(result)
+            })
+            .take(top_k)
+            .collect())
+    }
+
     /// Returns the number of indexed chunks.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -244,6 +305,7 @@ where
 mod tests {
     use super::*;
     use aither_core::EmbeddingModel;
+    use aither_core::rerank::RankedDocument;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[derive(Clone)]
@@ -290,6 +352,47 @@ mod tests {
         assert!(!results.is_empty());
     }
 
+    /// Ranks documents containing "rust" first.
+    struct RustFirst;
+
+    impl Reranker for RustFirst {
+        async fn rerank(
+            &self,
+            _query: &str,
+            documents: &[&str],
+        ) -> aither_core::Result<Vec<RankedDocument>> {
+            let mut ranked: Vec<_> = documents
+                .iter()
+                .enumerate()
+                .map(|(index, text)| {
+                    RankedDocument::new(index, if text.contains("rust") { 1.0 } else { 0.0 })
+                })
+                .collect();
+            aither_core::rerank::sort_by_score(&mut ranked);
+            Ok(ranked)
+        }
+    }
+
+    #[tokio::test]
+    async fn search_reranked_orders_by_reranker() {
+        let embedder = MockEmbedder::new(4);
+        let store = RagStore::new(embedder);
+
+        store
+            .insert_batch(vec![
+                Document::new("doc1", "bananas"),
+                Document::new("doc2", "rust is fun"),
+                Document::new("doc3", "apples"),
+            ])
+            .await
+            .unwrap();
+
+        let results = store.search_reranked("query", 2, &RustFirst).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].chunk.text, "rust is fun");
+        assert!((results[0].score - 1.0).abs() < f32::EPSILON);
+    }
+
     #[tokio::test]
     async fn delete_document() {
         let embedder = MockEmbedder::new(4);
@@ -9,10 +9,12 @@
 
 #[cfg(unix)]
 use std::net::{TcpListener as StdTcpListener, TcpStream as StdTcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener as StdUnixListener, UnixStream as StdUnixStream};
 use std::{
     borrow::Cow,
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Stdio,
     sync::{
         Arc,
@@ -27,7 +29,7 @@ use async_channel::{Receiver, Sender};
 use async_io::Async;
 use executor_core::{Executor, Task};
 #[cfg(unix)]
-use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use leash::{
     AllowAll, DomainRequest, IpcRouter, NetworkPolicy, Sandbox, SandboxConfig, SecurityConfig,
     StdioConfig, WorkingDir,
@@ -37,13 +39,14 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use crate::{
-    builtin::builtin_router,
-    command::ToolRegistry,
+    builtin::{SearchCommand, builtin_router, register_job_commands},
+    command::{IpcToolCommand, ToolRegistry},
     job_registry::{JobRegistry, job_registry_channel},
     output::{
-        Content, INLINE_OUTPUT_LIMIT, OutputEntry, OutputFormat, OutputStore, save_raw_to_file,
+        Content, INLINE_OUTPUT_LIMIT, OutputEntry, OutputFormat, OutputMetadata, OutputStore,
+        RetentionPolicy, save_raw_to_file, write_metadata,
     },
-    permission::{BashMode, PermissionError, PermissionHandler},
+    permission::{BashMode, NetworkAccess, PermissionError, PermissionHandler},
     shell_session::{ShellBackend, ShellSessionRegistry, SshRuntimeProfile, bootstrap_ssh_runtime},
 };
 
@@ -55,16 +58,51 @@ fn random_task_id() -> String {
 #[derive(Clone)]
 struct PermissionNetworkPolicy<P> {
     permission_handler: Arc<P>,
+    access: NetworkAccess,
 }
 
 impl<P: PermissionHandler + 'static> NetworkPolicy for PermissionNetworkPolicy<P> {
     async fn check(&self, request: &DomainRequest) -> bool {
+        if let Some(allowed) = self.access.decide(request.target(), request.port()) {
+            if !allowed {
+                debug!(
+                    host = request.target(),
+                    port = request.port(),
+                    "network policy blocked connection"
+                );
+            }
+            return allowed;
+        }
         self.permission_handler
             .check_domain(request.target(), request.port())
             .await
     }
 }
 
+/// Refuses backends that bypass the leash network hook when the policy restricts hosts.
+fn ensure_network_enforceable(
+    access: &NetworkAccess,
+    backend: ShellBackend,
+    mode: BashMode,
+) -> Result<(), BashError> {
+    if !access.is_restrictive() {
+        return Ok(());
+    }
+    let bypass = match (backend, mode) {
+        (ShellBackend::Local, BashMode::Unsafe) => Some("unsafe mode"),
+        (ShellBackend::Local, _) => None,
+        (ShellBackend::Container, _) => Some("the container backend"),
+        (ShellBackend::Ssh, _) => Some("the ssh backend"),
+    };
+    match bypass {
+        Some(what) => Err(BashError::Execution(format!(
+            "network policy ({}) cannot be enforced for {what}",
+            access.description()
+        ))),
+        None => Ok(()),
+    }
+}
+
 async fn ensure_mode_allowed<P: PermissionHandler>(
     permission_handler: &P,
     mode: BashMode,
@@ -385,6 +423,8 @@ pub struct BashTool<P, E, State = Unconfigured> {
     writable_paths: Vec<PathBuf>,
     /// Additional paths that should be readable (but not writable) in the sandbox.
     readable_paths: Vec<PathBuf>,
+    /// Network policy applied before the permission handler's domain checks.
+    network_access: NetworkAccess,
     /// Tool registry state.
     registry: State,
 }
@@ -403,6 +443,7 @@ impl<P, E: Clone, State: Clone> Clone for BashTool<P, E, State> {
             completed_tx: self.completed_tx.clone(),
             writable_paths: self.writable_paths.clone(),
             readable_paths: self.readable_paths.clone(),
+            network_access: self.network_access.clone(),
             registry: self.registry.clone(),
         }
     }
@@ -416,6 +457,40 @@ impl<P, E: Executor + Clone + 'static> BashTool<P, E, Unconfigured> {
         self
     }
 
+    /// Runs `default`-mode scripts inside an already launched container.
+    ///
+    /// Scripts exec into `container_id` through `exec` (for example
+    /// [`BollardContainerExec`](crate::BollardContainerExec) after
+    /// [`launch`](crate::BollardContainerExec::launch)), and registered IPC commands
+    /// reach the host through the same bridge as the local backend. Use this when the
+    /// host lacks leash/landlock support or the code is untrusted.
+    #[must_use]
+    pub fn with_container_backend<T>(self, exec: Arc<T>, container_id: impl Into<String>) -> Self
+    where
+        T: crate::shell_session::ContainerExec + 'static,
+    {
+        self.shell_sessions.set_container_exec(exec);
+        self.shell_sessions.set_container_id(container_id.into());
+        let availability = crate::shell_session::ShellRuntimeAvailability {
+            container: true,
+            ..self.shell_sessions.availability()
+        };
+        let _ = self.shell_sessions.set_availability(availability);
+        self
+    }
+
+    /// Serves container IPC commands over unix sockets in the host directory `dir`.
+    ///
+    /// `dir` must be the directory passed to
+    /// [`ContainerLaunchSpec::with_ipc_dir`](crate::ContainerLaunchSpec::with_ipc_dir).
+    /// Without it, IPC goes over TCP through `host.docker.internal`, which a container
+    /// launched without network cannot reach.
+    #[must_use]
+    pub fn with_container_ipc_dir(self, dir: impl Into<PathBuf>) -> Self {
+        self.shell_sessions.set_container_ipc_dir(dir.into());
+        self
+    }
+
     /// Sets dynamic runtime availability for bash execution.
     #[must_use]
     pub fn with_shell_runtime_availability(
@@ -474,6 +549,7 @@ impl<P, E: Executor + Clone + 'static> BashTool<P, E, Unconfigured> {
             completed_tx,
             writable_paths: Vec::new(),
             readable_paths: Vec::new(),
+            network_access: NetworkAccess::default(),
             registry: Unconfigured,
         })
     }
@@ -521,6 +597,7 @@ impl<P, E: Executor + Clone + 'static> BashTool<P, E, Unconfigured> {
             completed_tx,
             writable_paths: Vec::new(),
             readable_paths: Vec::new(),
+            network_access: NetworkAccess::default(),
             registry: Unconfigured,
         })
     }
@@ -539,6 +616,7 @@ impl<P, E: Executor + Clone + 'static> BashTool<P, E, Unconfigured> {
             completed_tx: self.completed_tx,
             writable_paths: self.writable_paths,
             readable_paths: self.readable_paths,
+            network_access: self.network_access,
             registry: Configured { registry },
         }
     }
@@ -574,6 +652,31 @@ where
         self
     }
 
+    /// Sets the network policy for scripts.
+    ///
+    /// Restrictive policies ([`NetworkAccess::DenyAll`], [`NetworkAccess::AllowList`])
+    /// are enforced by the leash network hook, so unsafe mode and the SSH and container
+    /// backends refuse to run while one is set.
+    #[must_use]
+    pub fn with_network_access(mut self, access: NetworkAccess) -> Self {
+        self.network_access = access;
+        self
+    }
+
+    /// Returns the network policy for scripts.
+    pub const fn network_access(&self) -> &NetworkAccess {
+        &self.network_access
+    }
+
+    /// Sets how much stored output is kept; applied after every script finishes.
+    ///
+    /// The policy lives on the shared [`OutputStore`], so child tools see it too.
+    #[must_use]
+    pub fn with_output_retention(self, policy: RetentionPolicy) -> Self {
+        self.output_store.set_retention(policy);
+        self
+    }
+
     /// Creates a child `BashTool` that shares the same sandbox and permission handler
     /// but has independent background task tracking.
     ///
@@ -594,6 +697,7 @@ where
             completed_tx,
             writable_paths: self.writable_paths.clone(),
             readable_paths: self.readable_paths.clone(),
+            network_access: self.network_access.clone(),
             registry: self.registry.clone(),
         }
     }
@@ -784,6 +888,8 @@ impl<P: PermissionHandler + 'static, E: Executor + Clone + 'static> Tool
             }
         };
 
+        ensure_network_enforceable(&self.network_access, backend, mode)
+            .map_err(anyhow::Error::new)?;
         ensure_mode_allowed(self.permission_handler.as_ref(), mode, &script)
             .await
             .map_err(anyhow::Error::new)?;
@@ -797,16 +903,20 @@ impl<P: PermissionHandler + 'static, E: Executor + Clone + 'static> Tool
         }
 
         let container_exec = self.shell_sessions.container_exec();
+        let container_ipc_dir = self.shell_sessions.container_ipc_dir();
         let working_dir = self.working_dir.clone();
         let writable_paths = self.writable_paths.clone();
         let readable_paths = self.readable_paths.clone();
         let executor = self.executor.clone();
         let registry = self.registry().clone();
         let permission_handler = self.permission_handler.clone();
+        let network_access = self.network_access.clone();
         let store_dir = self.output_store.dir().to_path_buf();
         let store_dir_for_spawn = store_dir.clone();
+        let output_store = self.output_store.clone();
         let completed_tx = self.completed_tx.clone();
         let job_registry = self.job_registry.clone();
+        let retention_jobs = self.job_registry.clone();
         let background_mode = Arc::new(AtomicBool::new(timeout == 0));
         let (result_tx, result_rx) = async_channel::bounded(1);
         let (stdin_blocked_tx, stdin_blocked_rx) = async_channel::bounded(1);
@@ -827,6 +937,7 @@ impl<P: PermissionHandler + 'static, E: Executor + Clone + 'static> Tool
                     executor,
                     registry,
                     permission_handler,
+                    network_access,
                     job_registry,
                     &task_id_for_spawn,
                     &execution_id,
@@ -838,6 +949,7 @@ impl<P: PermissionHandler + 'static, E: Executor + Clone + 'static> Tool
                     ssh_runtime.clone(),
                     container_id.as_deref(),
                     container_exec.as_ref(),
+                    container_ipc_dir.as_deref(),
                     stdin_blocked_notice,
                     expect,
                     &store_dir_for_spawn,
@@ -846,6 +958,21 @@ impl<P: PermissionHandler + 'static, E: Executor + Clone + 'static> Tool
                 )
                 .await;
 
+                let keep = referenced_outputs(
+                    &retention_jobs,
+                    &store_dir_for_spawn,
+                    &task_id_for_spawn,
+                    result.as_ref().ok(),
+                )
+                .await;
+                match output_store.enforce_retention(&keep).await {
+                    Ok(removed) if !removed.is_empty() => {
+                        debug!(count = removed.len(), "pruned stored outputs");
+                    }
+                    Ok(_) => {}
+                    Err(err) => warn!(error = %err, "failed to apply output retention"),
+                }
+
                 let quick_result = match &result {
                     Ok(ok) => Ok(ok.clone()),
                     Err(err) => Err(err.to_string()),
@@ -969,6 +1096,35 @@ impl<P: PermissionHandler + 'static, E: Executor + Clone + 'static> Tool
     }
 }
 
+/// URLs of stored outputs that retention must not remove: those running jobs
+/// and this task write to, and those `result` refers to.
+async fn referenced_outputs(
+    job_registry: &JobRegistry,
+    store_dir: &Path,
+    task_id: &str,
+    result: Option<&BashResult>,
+) -> Vec<String> {
+    let mut urls: Vec<String> = job_registry
+        .list()
+        .await
+        .into_iter()
+        .filter(|job| job.status.is_running() || job.task_id == task_id)
+        .filter_map(|job| {
+            let path = job.output_path?;
+            let filename = path.strip_prefix(store_dir).ok()?.to_str()?.to_string();
+            Some(format!("outputs/{filename}"))
+        })
+        .collect();
+    if let Some(result) = result {
+        for entry in std::iter::once(&result.stdout).chain(result.stderr.as_ref()) {
+            if let OutputEntry::Stored { url, .. } = entry {
+                urls.push(url.clone());
+            }
+        }
+    }
+    urls
+}
+
 async fn start_background_output_redirect(
     job_registry: &JobRegistry,
     store_dir: &PathBuf,
@@ -1003,6 +1159,7 @@ async fn execute_script_standalone<P, E>(
     executor: E,
     registry: Arc<ToolRegistry>,
     permission_handler: Arc<P>,
+    network_access: NetworkAccess,
     job_registry: JobRegistry,
     task_id: &str,
     execution_id: &str,
@@ -1014,6 +1171,7 @@ async fn execute_script_standalone<P, E>(
     ssh_runtime: Option<SshRuntimeProfile>,
     container_id: Option<&str>,
     container_exec: Option<&Arc<dyn crate::shell_session::ContainerExecObject>>,
+    container_ipc_dir: Option<&Path>,
     stdin_blocked_notice: Option<async_channel::Sender<String>>,
     expect: OutputFormat,
     store_dir: &PathBuf,
@@ -1043,6 +1201,7 @@ where
             mode,
             container_id,
             container_exec,
+            container_ipc_dir,
             &ipc_commands,
             &job_registry,
             stdin_blocked_notice,
@@ -1072,8 +1231,12 @@ where
                     execution_id,
                     script,
                     mode,
-                    PermissionNetworkPolicy { permission_handler },
+                    PermissionNetworkPolicy {
+                        permission_handler,
+                        access: network_access,
+                    },
                     &job_registry,
+                    store_dir,
                 )
                 .await?
             }
@@ -1089,6 +1252,7 @@ where
                     script,
                     mode,
                     &job_registry,
+                    store_dir,
                 )
                 .await?
             }
@@ -1184,6 +1348,16 @@ where
     let exit_code = output.status.code().unwrap_or(-1);
     debug!(exit_code, "background script completed");
 
+    let producer = OutputProducer {
+        script,
+        task_id: background_output.then_some(task_id),
+        exit_code,
+    };
+    record_output_metadata(store_dir, &stdout, "stdout", &producer).await;
+    if let Some(stderr) = &stderr {
+        record_output_metadata(store_dir, stderr, "stderr", &producer).await;
+    }
+
     let output_path = stdout.stored_path(store_dir);
     job_registry.complete(pid, exit_code, output_path).await;
 
@@ -1196,6 +1370,38 @@ where
     })
 }
 
+/// The command behind a stored output, recorded in its metadata sidecar.
+struct OutputProducer<'a> {
+    script: &'a str,
+    task_id: Option<&'a str>,
+    exit_code: i32,
+}
+
+async fn record_output_metadata(
+    store_dir: &Path,
+    entry: &OutputEntry,
+    stream: &str,
+    producer: &OutputProducer<'_>,
+) {
+    let OutputEntry::Stored { url, .. } = entry else {
+        return;
+    };
+    let size = entry
+        .stored_path(store_dir)
+        .and_then(|path| std::fs::metadata(path).ok())
+        .map_or(0, |meta| meta.len());
+    let metadata = OutputMetadata {
+        command: Some(producer.script.to_string()),
+        task_id: producer.task_id.map(str::to_string),
+        stream: Some(stream.to_string()),
+        exit_code: Some(producer.exit_code),
+        ..OutputMetadata::new(url.clone(), size)
+    };
+    if let Err(err) = write_metadata(store_dir, &metadata).await {
+        warn!(url = %url, error = %err, "failed to record output metadata");
+    }
+}
+
 async fn execute_sandboxed_background<E, N>(
     working_dir: &PathBuf,
     writable_paths: &[PathBuf],
@@ -1208,12 +1414,13 @@ async fn execute_sandboxed_background<E, N>(
     mode: BashMode,
     policy: N,
     job_registry: &JobRegistry,
+    store_dir: &Path,
 ) -> Result<(u32, std::process::Output), BashError>
 where
     E: Executor + Clone + 'static,
     N: NetworkPolicy + 'static,
 {
-    let router = create_ipc_router(registry);
+    let router = create_ipc_router(registry, job_registry, store_dir);
     let config = SandboxConfig::builder()
         .network(policy)
         .working_dir(working_dir)
@@ -1298,8 +1505,9 @@ async fn execute_unsafe_background<E: Executor + Clone + 'static>(
     script: &str,
     mode: BashMode,
     job_registry: &JobRegistry,
+    store_dir: &Path,
 ) -> Result<(u32, std::process::Output), BashError> {
-    let router = create_ipc_gateway_router(registry);
+    let router = create_ipc_gateway_router(registry, job_registry, store_dir);
     let config = SandboxConfig::builder()
         .network(AllowAll)
         .working_dir(working_dir)
@@ -1381,6 +1589,7 @@ async fn execute_container_background<E: Executor + Clone + 'static>(
     mode: BashMode,
     container_id: Option<&str>,
     container_exec: Option<&Arc<dyn crate::shell_session::ContainerExecObject>>,
+    ipc_dir: Option<&Path>,
     ipc_commands: &[String],
     job_registry: &JobRegistry,
     stdin_blocked_notice: Option<async_channel::Sender<String>>,
@@ -1402,19 +1611,19 @@ async fn execute_container_background<E: Executor + Clone + 'static>(
     let ipc_bridge = if ipc_commands.is_empty() {
         None
     } else {
-        Some(start_container_ipc_bridge(executor, registry)?)
+        Some(start_container_ipc_bridge(executor, registry, ipc_dir)?)
     };
     let wrapped_script = wrap_container_script(
         script,
         ipc_commands,
-        ipc_bridge.as_ref().map(ContainerIpcBridge::port),
+        ipc_bridge.as_ref().map(ContainerIpcBridge::endpoint),
     )?;
 
     let execution = exec
         .exec_boxed(
             container_id,
             &wrapped_script,
-            "/workspace",
+            crate::container::CONTAINER_WORKSPACE,
             kill_rx,
             stdin_blocked_notice,
         )
@@ -1525,16 +1734,18 @@ fn shell_escape(value: &str) -> String {
     format!("'{}'", value.replace('\'', "'\"'\"'"))
 }
 
+/// Wraps `script` so registered IPC commands resolve to `leash-ipc` shims that
+/// talk to the host bridge at `ipc_endpoint` (the `LEASH_IPC_SOCKET` value).
 fn wrap_container_script(
     script: &str,
     ipc_commands: &[String],
-    ipc_port: Option<u16>,
+    ipc_endpoint: Option<&str>,
 ) -> Result<String, BashError> {
     if ipc_commands.is_empty() {
         return Ok(script.to_string());
     }
 
-    let port = ipc_port.ok_or_else(|| {
+    let endpoint = ipc_endpoint.ok_or_else(|| {
         BashError::Execution(
             "missing container IPC endpoint for wrapped script execution".to_string(),
         )
@@ -1570,8 +1781,8 @@ fn wrap_container_script(
     wrapped.push_str("done; ");
     wrapped.push_str("export PATH=\"$MAY_IPC_DIR:$PATH\"; ");
     wrapped.push_str("hash -r; ");
-    wrapped.push_str("export LEASH_IPC_SOCKET=\"tcp://${MAY_HOST_GATEWAY:-host.docker.internal}:");
-    wrapped.push_str(&port.to_string());
+    wrapped.push_str("export LEASH_IPC_SOCKET=\"");
+    wrapped.push_str(endpoint);
     wrapped.push_str("\"; ");
     wrapped.push_str(script);
 
@@ -1580,64 +1791,101 @@ fn wrap_container_script(
 
 struct ContainerIpcBridge {
     shutdown_tx: Sender<()>,
-    port: u16,
+    endpoint: String,
+    socket_path: Option<PathBuf>,
 }
 
 impl ContainerIpcBridge {
-    fn port(&self) -> u16 {
-        self.port
+    fn endpoint(&self) -> &str {
+        &self.endpoint
     }
 
     async fn stop(self) {
         tracing::debug!("stopping container IPC bridge");
         let _ = self.shutdown_tx.send(()).await;
+        if let Some(path) = self.socket_path {
+            let _ = std::fs::remove_file(path);
+        }
     }
 }
 
+/// Starts a bridge serving `registry` to one container script.
+///
+/// With `ipc_dir`, the bridge listens on a fresh unix socket in that directory,
+/// which the container sees under [`CONTAINER_IPC_DIR`](crate::CONTAINER_IPC_DIR),
+/// so it works without container networking. Otherwise it listens on a loopback
+/// TCP port reached through `host.docker.internal`.
 #[cfg(unix)]
 fn start_container_ipc_bridge<E: Executor + Clone + 'static>(
     executor: E,
     registry: Arc<ToolRegistry>,
+    ipc_dir: Option<&Path>,
 ) -> Result<ContainerIpcBridge, BashError> {
-    let listener = StdTcpListener::bind("127.0.0.1:0")
-        .map_err(|e| BashError::Execution(format!("failed to bind container IPC tcp port: {e}")))?;
-    let local_addr = listener.local_addr().map_err(|e| {
-        BashError::Execution(format!("failed to resolve container IPC tcp endpoint: {e}"))
-    })?;
-    let endpoint = format!("tcp://host.docker.internal:{}", local_addr.port());
-    tracing::debug!(
-        bind = %local_addr,
-        endpoint = %endpoint,
-        "starting container IPC bridge"
-    );
-    listener.set_nonblocking(true).map_err(|e| {
-        BashError::Execution(format!(
-            "failed to set IPC tcp listener nonblocking mode: {e}"
-        ))
-    })?;
-    let listener = Async::new(listener).map_err(|e| {
-        BashError::Execution(format!(
-            "failed to register IPC tcp listener with async reactor: {e}"
-        ))
-    })?;
+    let (listener, endpoint, socket_path) = match ipc_dir {
+        Some(dir) => {
+            let name = format!("{}.sock", uuid::Uuid::new_v4().simple());
+            let socket_path = dir.join(&name);
+            let listener = StdUnixListener::bind(&socket_path).map_err(|e| {
+                BashError::Execution(format!(
+                    "failed to bind container IPC socket {}: {e}",
+                    socket_path.display()
+                ))
+            })?;
+            let endpoint = Path::new(crate::container::CONTAINER_IPC_DIR)
+                .join(&name)
+                .display()
+                .to_string();
+            let listener = Async::new(listener).map_err(|e| {
+                BashError::Execution(format!(
+                    "failed to register IPC unix listener with async reactor: {e}"
+                ))
+            })?;
+            (
+                ContainerIpcListener::Unix(listener),
+                endpoint,
+                Some(socket_path),
+            )
+        }
+        None => {
+            let listener = StdTcpListener::bind("127.0.0.1:0").map_err(|e| {
+                BashError::Execution(format!("failed to bind container IPC tcp port: {e}"))
+            })?;
+            let local_addr = listener.local_addr().map_err(|e| {
+                BashError::Execution(format!("failed to resolve container IPC tcp endpoint: {e}"))
+            })?;
+            let endpoint = format!(
+                "tcp://${{MAY_HOST_GATEWAY:-host.docker.internal}}:{}",
+                local_addr.port()
+            );
+            let listener = Async::new(listener).map_err(|e| {
+                BashError::Execution(format!(
+                    "failed to register IPC tcp listener with async reactor: {e}"
+                ))
+            })?;
+            (ContainerIpcListener::Tcp(listener), endpoint, None)
+        }
+    };
+    tracing::debug!(endpoint = %endpoint, "starting container IPC bridge");
 
     let (shutdown_tx, shutdown_rx) = async_channel::bounded::<()>(1);
     let bridge_executor = executor.clone();
+    let bridge_endpoint = endpoint.clone();
     executor
         .spawn(async move {
-            tracing::debug!(bind = %local_addr, "container IPC bridge listening");
+            tracing::debug!(endpoint = %bridge_endpoint, "container IPC bridge listening");
             if let Err(error) =
                 run_container_ipc_bridge(listener, registry, shutdown_rx, bridge_executor).await
             {
                 tracing::debug!(error = %error, "container IPC bridge stopped with error");
             }
-            tracing::debug!(bind = %local_addr, "container IPC bridge stopped");
+            tracing::debug!(endpoint = %bridge_endpoint, "container IPC bridge stopped");
         })
         .detach();
 
     Ok(ContainerIpcBridge {
         shutdown_tx,
-        port: local_addr.port(),
+        endpoint,
+        socket_path,
     })
 }
 
@@ -1645,21 +1893,50 @@ fn start_container_ipc_bridge<E: Executor + Clone + 'static>(
 fn start_container_ipc_bridge<E: Executor + Clone + 'static>(
     _executor: E,
     _registry: Arc<ToolRegistry>,
+    _ipc_dir: Option<&Path>,
 ) -> Result<ContainerIpcBridge, BashError> {
     Err(BashError::Execution(
         "container IPC bridge requires unix domain sockets".to_string(),
     ))
 }
 
+#[cfg(unix)]
+enum ContainerIpcListener {
+    Tcp(Async<StdTcpListener>),
+    Unix(Async<StdUnixListener>),
+}
+
+#[cfg(unix)]
+enum ContainerIpcStream {
+    Tcp(Async<StdTcpStream>),
+    Unix(Async<StdUnixStream>),
+}
+
+#[cfg(unix)]
+impl ContainerIpcListener {
+    async fn accept(&self) -> std::io::Result<ContainerIpcStream> {
+        match self {
+            Self::Tcp(listener) => listener
+                .accept()
+                .await
+                .map(|(stream, _)| ContainerIpcStream::Tcp(stream)),
+            Self::Unix(listener) => listener
+                .accept()
+                .await
+                .map(|(stream, _)| ContainerIpcStream::Unix(stream)),
+        }
+    }
+}
+
 #[cfg(unix)]
 enum ContainerIpcBridgeEvent {
-    Accept(std::io::Result<(Async<StdTcpStream>, std::net::SocketAddr)>),
+    Accept(std::io::Result<ContainerIpcStream>),
     Shutdown,
 }
 
 #[cfg(unix)]
 async fn run_container_ipc_bridge<E: Executor + Clone + 'static>(
-    listener: Async<StdTcpListener>,
+    listener: ContainerIpcListener,
     registry: Arc<ToolRegistry>,
     shutdown_rx: Receiver<()>,
     executor: E,
@@ -1676,13 +1953,20 @@ async fn run_container_ipc_bridge<E: Executor + Clone + 'static>(
 
         match event {
             ContainerIpcBridgeEvent::Shutdown => break,
-            ContainerIpcBridgeEvent::Accept(Ok((stream, _addr))) => {
+            ContainerIpcBridgeEvent::Accept(Ok(stream)) => {
                 tracing::debug!("container IPC bridge accepted connection");
                 let registry = registry.clone();
                 executor
                     .spawn(async move {
-                        if let Err(error) = handle_container_ipc_connection(stream, registry).await
-                        {
+                        let result = match stream {
+                            ContainerIpcStream::Tcp(stream) => {
+                                handle_container_ipc_connection(stream, registry).await
+                            }
+                            ContainerIpcStream::Unix(stream) => {
+                                handle_container_ipc_connection(stream, registry).await
+                            }
+                        };
+                        if let Err(error) = result {
                             tracing::debug!(error = %error, "container IPC connection failed");
                         }
                     })
@@ -1702,8 +1986,8 @@ async fn run_container_ipc_bridge<E: Executor + Clone + 'static>(
 }
 
 #[cfg(unix)]
-async fn handle_container_ipc_connection(
-    mut stream: Async<StdTcpStream>,
+async fn handle_container_ipc_connection<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
     registry: Arc<ToolRegistry>,
 ) -> Result<(), String> {
     loop {
@@ -1774,8 +2058,8 @@ fn decode_container_ipc_args(params: &[u8]) -> Result<Vec<String>, String> {
 }
 
 #[cfg(unix)]
-async fn write_container_ipc_success(
-    stream: &mut Async<StdTcpStream>,
+async fn write_container_ipc_success<S: AsyncWrite + Unpin>(
+    stream: &mut S,
     response: &serde_json::Value,
 ) -> Result<(), String> {
     let payload = leash::rmp_serde::to_vec(response)
@@ -1784,8 +2068,8 @@ async fn write_container_ipc_success(
 }
 
 #[cfg(unix)]
-async fn write_container_ipc_error(
-    stream: &mut Async<StdTcpStream>,
+async fn write_container_ipc_error<S: AsyncWrite + Unpin>(
+    stream: &mut S,
     message: &str,
 ) -> Result<(), String> {
     let payload = leash::rmp_serde::to_vec(&message.to_string())
@@ -1794,8 +2078,8 @@ async fn write_container_ipc_error(
 }
 
 #[cfg(unix)]
-async fn write_container_ipc_response(
-    stream: &mut Async<StdTcpStream>,
+async fn write_container_ipc_response<S: AsyncWrite + Unpin>(
+    stream: &mut S,
     success: bool,
     payload: &[u8],
 ) -> Result<(), String> {
@@ -1939,8 +2223,13 @@ async fn wait_for_terminal_stream_close(job_registry: &JobRegistry, pid: u32) {
 }
 
 /// Creates the IPC router with built-in and tool commands (standalone version).
-fn create_ipc_router(registry: Arc<ToolRegistry>) -> IpcRouter {
-    let mut router = builtin_router();
+fn create_ipc_router(
+    registry: Arc<ToolRegistry>,
+    job_registry: &JobRegistry,
+    store_dir: &Path,
+) -> IpcRouter {
+    let mut router = register_job_commands(builtin_router(), job_registry, true)
+        .register(IpcToolCommand::new(SearchCommand::new(store_dir)));
 
     // Register all configured tools as IPC commands
     let tool_names = registry.registered_tool_names();
@@ -1952,8 +2241,14 @@ fn create_ipc_router(registry: Arc<ToolRegistry>) -> IpcRouter {
     router
 }
 
-fn create_ipc_gateway_router(registry: Arc<ToolRegistry>) -> IpcRouter {
+fn create_ipc_gateway_router(
+    registry: Arc<ToolRegistry>,
+    job_registry: &JobRegistry,
+    store_dir: &Path,
+) -> IpcRouter {
     let mut router = crate::register_ipc_gateway_command(IpcRouter::new(), registry.clone());
+    router = register_job_commands(router, job_registry, false)
+        .register(IpcToolCommand::new(SearchCommand::new(store_dir)));
 
     // In unsafe mode, keep tool commands usable (websearch/webfetch/ask/task/todo...),
     // but never override native shell task/process commands like kill/jobs.
@@ -2111,7 +2406,7 @@ mod tests {
         let wrapped = wrap_container_script(
             "websearch \"gold price\"",
             &[String::from("websearch")],
-            Some(9000),
+            Some("tcp://host.docker.internal:9000"),
         )
         .expect("wrap script");
         assert!(wrapped.contains("hash -r;"));
@@ -2122,12 +2417,27 @@ mod tests {
     #[test]
     fn wrap_container_script_preserves_user_script_semantics() {
         let script = "echo '$5,040' && subagent --subagent .skills/slide/subagents/art_direction.md --prompt 'x'";
-        let wrapped = wrap_container_script(script, &[String::from("subagent")], Some(9000))
-            .expect("wrap script");
+        let wrapped = wrap_container_script(
+            script,
+            &[String::from("subagent")],
+            Some("tcp://host.docker.internal:9000"),
+        )
+        .expect("wrap script");
         assert!(wrapped.contains(script));
         assert!(!wrapped.contains("set -euo pipefail"));
     }
 
+    #[test]
+    fn wrap_container_script_exports_unix_ipc_endpoint() {
+        let wrapped = wrap_container_script(
+            "websearch rust",
+            &[String::from("websearch")],
+            Some("/run/aither-ipc/bridge.sock"),
+        )
+        .expect("wrap script");
+        assert!(wrapped.contains("export LEASH_IPC_SOCKET=\"/run/aither-ipc/bridge.sock\";"));
+    }
+
     #[tokio::test]
     async fn ensure_mode_allowed_requires_network_approval() {
         let handler = TestPermissionHandler {
@@ -2154,6 +2464,7 @@ mod tests {
         });
         let policy = PermissionNetworkPolicy {
             permission_handler: handler.clone(),
+            access: NetworkAccess::Ask,
         };
         let request = DomainRequest::new(
             "example.com".to_string(),
@@ -2164,4 +2475,39 @@ mod tests {
         assert!(policy.check(&request).await);
         assert_eq!(handler.domain_checks.load(AtomicOrdering::Relaxed), 1);
     }
+
+    #[tokio::test]
+    async fn network_access_overrides_domain_checks() {
+        let handler = Arc::new(TestPermissionHandler {
+            allow_network: true,
+            allow_domain: true,
+            ..Default::default()
+        });
+        let policy = PermissionNetworkPolicy {
+            permission_handler: handler.clone(),
+            access: NetworkAccess::allow_hosts(["*.crates.io"]),
+        };
+        let request = |host: &str| {
+            DomainRequest::new(host.to_string(), 443, ConnectionDirection::Outbound, 1234)
+        };
+        assert!(policy.check(&request("static.crates.io")).await);
+        assert!(!policy.check(&request("example.com")).await);
+        assert_eq!(handler.domain_checks.load(AtomicOrdering::Relaxed), 0);
+    }
+
+    #[test]
+    fn restrictive_network_access_rejects_unenforceable_backends() {
+        let access = NetworkAccess::DenyAll;
+        assert!(
+            ensure_network_enforceable(&access, ShellBackend::Local, BashMode::Network).is_ok()
+        );
+        assert!(
+            ensure_network_enforceable(&access, ShellBackend::Local, BashMode::Unsafe).is_err()
+        );
+        assert!(ensure_network_enforceable(&access, ShellBackend::Ssh, BashMode::Network).is_err());
+        assert!(
+            ensure_network_enforceable(&NetworkAccess::Full, ShellBackend::Ssh, BashMode::Network)
+                .is_ok()
+        );
+    }
 }
@@ -9,8 +9,13 @@ use async_channel::{Receiver, Sender};
 use bollard::Docker;
 use bollard::container::LogOutput;
 use bollard::exec::CreateExecOptions;
+use bollard::models::{ContainerCreateBody, HostConfig};
+use bollard::query_parameters::{
+    CreateContainerOptionsBuilder, RemoveContainerOptionsBuilder, StartContainerOptions,
+};
 use futures_lite::StreamExt;
 
+use crate::container::{CONTAINER_WORKSPACE, ContainerLaunchSpec};
 use crate::shell_session::{ContainerExec, ContainerExecOutcome};
 
 /// Foreground tasks are promoted when the process blocks on stdin.
@@ -30,6 +35,74 @@ impl BollardContainerExec {
     pub const fn new(client: Arc<Docker>) -> Self {
         Self { client }
     }
+
+    /// Create and start a long-lived container for `BashTool` to exec into.
+    ///
+    /// The container idles on `sleep infinity`; the workspace is mounted at
+    /// [`CONTAINER_WORKSPACE`] and the IPC directory, if any, at
+    /// [`CONTAINER_IPC_DIR`](crate::CONTAINER_IPC_DIR). With networking enabled,
+    /// `host.docker.internal` resolves to the host so the TCP IPC bridge is
+    /// reachable. Returns the container id.
+    pub async fn launch(&self, spec: &ContainerLaunchSpec) -> Result<String, String> {
+        let limits = spec.limits;
+        let host_config = HostConfig {
+            binds: Some(spec.binds()),
+            memory: limits.memory_bytes.map(saturating_i64),
+            nano_cpus: limits
+                .cpu_millis
+                .map(|millis| saturating_i64(millis.saturating_mul(1_000_000))),
+            pids_limit: limits.pids.map(saturating_i64),
+            network_mode: limits.network_disabled.then(|| "none".to_string()),
+            extra_hosts: (!limits.network_disabled)
+                .then(|| vec!["host.docker.internal:host-gateway".to_string()]),
+            ..Default::default()
+        };
+        let body = ContainerCreateBody {
+            image: Some(spec.image.clone()),
+            cmd: Some(vec!["sleep".to_string(), "infinity".to_string()]),
+            env: Some(spec.env.clone()),
+            working_dir: Some(CONTAINER_WORKSPACE.to_string()),
+            host_config: Some(host_config),
+            ..Default::default()
+        };
+
+        let created = self
+            .client
+            .create_container(
+                Some(
+                    CreateContainerOptionsBuilder::default()
+                        .name(&spec.name)
+                        .build(),
+                ),
+                body,
+            )
+            .await
+            .map_err(|e| format!("failed to create container '{}': {e}", spec.name))?;
+        if let Err(e) = self
+            .client
+            .start_container(&created.id, None::<StartContainerOptions>)
+            .await
+        {
+            let _ = self.remove(&created.id).await;
+            return Err(format!("failed to start container '{}': {e}", spec.name));
+        }
+        Ok(created.id)
+    }
+
+    /// Force-remove a container started with [`launch`](Self::launch).
+    pub async fn remove(&self, container_id: &str) -> Result<(), String> {
+        self.client
+            .remove_container(
+                container_id,
+                Some(RemoveContainerOptionsBuilder::default().force(true).build()),
+            )
+            .await
+            .map_err(|e| format!("failed to remove container '{container_id}': {e}"))
+    }
+}
+
+fn saturating_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
 }
 
 fn parse_kernel_u64(value: &str) -> Option<u64> {
@@ -0,0 +1,282 @@
+//! Job inspection commands for background bash tasks.
+//!
+//! These are registered as IPC commands so scripts can check on long-running
+//! work started earlier:
+//!
+//! ```bash
+//! jobs
+//! job-status amber-forest-thunder-pearl --wait 300
+//! job-logs amber-forest-thunder-pearl --tail 50
+//! job-kill amber-forest-thunder-pearl
+//! ```
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aither_core::llm::{Tool, ToolOutput};
+use leash::IpcRouter;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::command::IpcToolCommand;
+use crate::job_registry::{JobInfo, JobRegistry, JobStatus};
+use crate::permission::BashMode;
+
+/// Serializable view of a background job.
+#[derive(Debug, Clone, Serialize)]
+struct JobSummary {
+    task_id: String,
+    pid: u32,
+    script: String,
+    mode: BashMode,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    elapsed_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_path: Option<PathBuf>,
+}
+
+impl From<JobInfo> for JobSummary {
+    fn from(info: JobInfo) -> Self {
+        let elapsed_secs = info.elapsed().as_secs();
+        let (status, exit_code, error) = match info.status {
+            JobStatus::Running => ("running", None, None),
+            JobStatus::Completed { exit_code } => ("completed", Some(exit_code), None),
+            JobStatus::Failed { error } => ("failed", None, Some(error)),
+            JobStatus::Killed => ("killed", None, None),
+        };
+        Self {
+            task_id: info.task_id,
+            pid: info.pid,
+            script: info.script,
+            mode: info.mode,
+            status,
+            exit_code,
+            error,
+            elapsed_secs,
+            output_path: info.output_path,
+        }
+    }
+}
+
+/// List background tasks and their status.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct JobsArgs {
+    /// Only show tasks that are still running.
+    #[serde(default)]
+    pub running: bool,
+}
+
+/// The `jobs` command.
+#[derive(Debug, Clone)]
+pub struct JobsCommand {
+    registry: JobRegistry,
+}
+
+impl JobsCommand {
+    /// Creates the command over a job registry.
+    #[must_use]
+    pub const fn new(registry: JobRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl Tool for JobsCommand {
+    fn name(&self) -> Cow<'static, str> {
+        "jobs".into()
+    }
+
+    type Arguments = JobsArgs;
+
+    async fn call(&self, args: Self::Arguments) -> aither_core::Result<ToolOutput> {
+        let mut jobs = self.registry.list().await;
+        if args.running {
+            jobs.retain(|job| job.status.is_running());
+        }
+        jobs.sort_by_key(|job| job.started_at);
+        let jobs: Vec<JobSummary> = jobs.into_iter().map(JobSummary::from).collect();
+        ToolOutput::json(&jobs)
+    }
+}
+
+/// Show the status of a background task, optionally waiting for it to finish.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobStatusArgs {
+    /// Task identifier returned by bash when the task is backgrounded.
+    pub task_id: String,
+    /// Seconds to wait for the task to finish before reporting (default 0).
+    #[serde(default)]
+    pub wait: u64,
+}
+
+/// The `job-status` command.
+#[derive(Debug, Clone)]
+pub struct JobStatusCommand {
+    registry: JobRegistry,
+}
+
+impl JobStatusCommand {
+    /// Creates the command over a job registry.
+    #[must_use]
+    pub const fn new(registry: JobRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl Tool for JobStatusCommand {
+    fn name(&self) -> Cow<'static, str> {
+        "job-status".into()
+    }
+
+    type Arguments = JobStatusArgs;
+
+    async fn call(&self, args: Self::Arguments) -> aither_core::Result<ToolOutput> {
+        let task_id = args.task_id.trim();
+        let info = self
+            .registry
+            .wait(task_id, Some(Duration::from_secs(args.wait)))
+            .await
+            .ok_or_else(|| anyhow::anyhow!("unknown task_id: {task_id}"))?;
+        ToolOutput::json(&JobSummary::from(info))
+    }
+}
+
+/// Print the output a background task has produced so far.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobLogsArgs {
+    /// Task identifier returned by bash when the task is backgrounded.
+    pub task_id: String,
+    /// Only print the last N lines.
+    #[serde(default)]
+    pub tail: Option<usize>,
+    /// Print stderr instead of stdout.
+    #[serde(default)]
+    pub stderr: bool,
+}
+
+/// The `job-logs` command.
+#[derive(Debug, Clone)]
+pub struct JobLogsCommand {
+    registry: JobRegistry,
+}
+
+impl JobLogsCommand {
+    /// Creates the command over a job registry.
+    #[must_use]
+    pub const fn new(registry: JobRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl Tool for JobLogsCommand {
+    fn name(&self) -> Cow<'static, str> {
+        "job-logs".into()
+    }
+
+    type Arguments = JobLogsArgs;
+
+    async fn call(&self, args: Self::Arguments) -> aither_core::Result<ToolOutput> {
+        let task_id = args.task_id.trim();
+        let logs = self
+            .registry
+            .logs(task_id)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("unknown task_id: {task_id}"))?;
+        let bytes = if args.stderr {
+            logs.stderr
+        } else {
+            logs.stdout
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        let text = match args.tail {
+            Some(lines) => tail_lines(&text, lines),
+            None => text.as_ref(),
+        };
+        Ok(ToolOutput::text(text))
+    }
+}
+
+/// Stop a running background task.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobKillArgs {
+    /// Task identifier returned by bash when the task is backgrounded.
+    pub task_id: String,
+}
+
+/// The `job-kill` command.
+#[derive(Debug, Clone)]
+pub struct JobKillCommand {
+    registry: JobRegistry,
+}
+
+impl JobKillCommand {
+    /// Creates the command over a job registry.
+    #[must_use]
+    pub const fn new(registry: JobRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl Tool for JobKillCommand {
+    fn name(&self) -> Cow<'static, str> {
+        "job-kill".into()
+    }
+
+    type Arguments = JobKillArgs;
+
+    async fn call(&self, args: Self::Arguments) -> aither_core::Result<ToolOutput> {
+        let task_id = args.task_id.trim();
+        let killed = self.registry.kill_by_task_id(task_id).await;
+        ToolOutput::json(&serde_json::json!({
+            "task_id": task_id,
+            "killed": killed,
+        }))
+    }
+}
+
+/// Registers `job-status`, `job-logs`, and `job-kill`, plus `jobs` unless
+/// `include_list` is false (unsafe mode keeps the shell's own `jobs` builtin).
+pub fn register_job_commands(
+    router: IpcRouter,
+    registry: &JobRegistry,
+    include_list: bool,
+) -> IpcRouter {
+    let router = if include_list {
+        router.register(IpcToolCommand::new(JobsCommand::new(registry.clone())))
+    } else {
+        router
+    };
+    router
+        .register(IpcToolCommand::new(JobStatusCommand::new(registry.clone())))
+        .register(IpcToolCommand::new(JobLogsCommand::new(registry.clone())))
+        .register(IpcToolCommand::new(JobKillCommand::new(registry.clone())))
+}
+
+fn tail_lines(text: &str, lines: usize) -> &str {
+    if lines == 0 {
+        return "";
+    }
+    let trimmed = text.strip_suffix('\n').unwrap_or(text);
+    trimmed
+        .match_indices('\n')
+        .rev()
+        .nth(lines - 1)
+        .map_or(text, |(index, _)| &text[index + 1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tail_lines;
+
+    #[test]
+    fn tail_lines_keeps_last_lines() {
+        assert_eq!(tail_lines("a\nb\nc\n", 2), "b\nc\n");
+        assert_eq!(tail_lines("a\nb\nc", 1), "c");
+        assert_eq!(tail_lines("a\nb\n", 5), "a\nb\n");
+        assert_eq!(tail_lines("a\n", 0), "");
+    }
+}
@@ -1,9 +1,16 @@
 //! Built-in sandbox tools.
 
 mod ask;
+mod jobs;
+mod search;
 mod terminal;
 
 pub use ask::AskCommand;
+pub use jobs::{
+    JobKillArgs, JobKillCommand, JobLogsArgs, JobLogsCommand, JobStatusArgs, JobStatusCommand,
+    JobsArgs, JobsCommand, register_job_commands,
+};
+pub use search::{SearchArgs, SearchCommand};
 pub use terminal::{InputTerminalArgs, InputTerminalTool, KillTerminalArgs, KillTerminalTool};
 
 use leash::IpcRouter;
@@ -0,0 +1,81 @@
+//! Search command - grep through stored outputs.
+//!
+//! # Usage
+//!
+//! ```bash
+//! search "error[E0308]"
+//! search timeout --ignore-case --max-results 20
+//! ```
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use aither_core::llm::{Tool, ToolOutput};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::output::search_outputs;
+
+/// Search earlier command outputs stored under `outputs/`.
+///
+/// Prints each matching line with the output URL, line number, and the
+/// command that produced it, newest outputs first.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchArgs {
+    /// Text to look for.
+    pub query: String,
+    /// Match regardless of case.
+    #[serde(default)]
+    pub ignore_case: bool,
+    /// Maximum number of matching lines to print (default 50).
+    #[serde(default)]
+    pub max_results: Option<usize>,
+}
+
+const DEFAULT_MAX_RESULTS: usize = 50;
+
+/// The search command tool.
+#[derive(Debug, Clone)]
+pub struct SearchCommand {
+    dir: PathBuf,
+}
+
+impl SearchCommand {
+    /// Creates a search command over an outputs directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl Tool for SearchCommand {
+    fn name(&self) -> Cow<'static, str> {
+        "search".into()
+    }
+
+    type Arguments = SearchArgs;
+
+    async fn call(&self, args: Self::Arguments) -> aither_core::Result<ToolOutput> {
+        if args.query.is_empty() {
+            return Err(anyhow::anyhow!("query must not be empty"));
+        }
+        let max_results = args.max_results.unwrap_or(DEFAULT_MAX_RESULTS);
+        let matches = search_outputs(&self.dir, &args.query, args.ignore_case, max_results).await?;
+        if matches.is_empty() {
+            return Ok(ToolOutput::text(format!(
+                "No stored output contains '{}'",
+                args.query
+            )));
+        }
+
+        let mut output = String::new();
+        for found in matches {
+            output.push_str(&format!("{}:{}: {}", found.url, found.line, found.text));
+            if let Some(command) = found.command {
+                let command = command.lines().next().unwrap_or_default();
+                output.push_str(&format!("  [from `{command}`]"));
+            }
+            output.push('\n');
+        }
+        Ok(ToolOutput::text(output))
+    }
+}
@@ -186,6 +186,56 @@ impl ContainerImageSpec {
     }
 }
 
+/// Resource limits applied to a launched container.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ContainerLimits {
+    /// CPU quota in thousandths of a CPU (1000 = one full core).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_millis: Option<u64>,
+    /// Memory limit in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+    /// Maximum number of processes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pids: Option<u64>,
+    /// Disable networking entirely.
+    ///
+    /// IPC commands then only reach the host through
+    /// [`ContainerLaunchSpec::ipc_dir`], since the TCP bridge is unreachable.
+    #[serde(default)]
+    pub network_disabled: bool,
+}
+
+impl ContainerLimits {
+    /// Set the CPU quota in thousandths of a CPU.
+    #[must_use]
+    pub const fn with_cpu_millis(mut self, cpu_millis: u64) -> Self {
+        self.cpu_millis = Some(cpu_millis);
+        self
+    }
+
+    /// Set the memory limit in bytes.
+    #[must_use]
+    pub const fn with_memory_bytes(mut self, memory_bytes: u64) -> Self {
+        self.memory_bytes = Some(memory_bytes);
+        self
+    }
+
+    /// Set the maximum number of processes.
+    #[must_use]
+    pub const fn with_pids(mut self, pids: u64) -> Self {
+        self.pids = Some(pids);
+        self
+    }
+
+    /// Disable networking for the container.
+    #[must_use]
+    pub const fn without_network(mut self) -> Self {
+        self.network_disabled = true;
+        self
+    }
+}
+
 /// Container launch specification.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ContainerLaunchSpec {
@@ -196,8 +246,12 @@ pub struct ContainerLaunchSpec {
     pub mounts: Vec<MountSpec>,
     #[serde(default)]
     pub env: Vec<String>,
+    /// Host directory mounted at [`CONTAINER_IPC_DIR`] where IPC bridges
+    /// place their unix sockets.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub ipc_socket: Option<PathBuf>,
+    pub ipc_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub limits: ContainerLimits,
 }
 
 impl ContainerLaunchSpec {
@@ -213,7 +267,8 @@ impl ContainerLaunchSpec {
             workspace: workspace.into(),
             mounts: Vec::new(),
             env: Vec::new(),
-            ipc_socket: None,
+            ipc_dir: None,
+            limits: ContainerLimits::default(),
         }
     }
 
@@ -229,16 +284,61 @@ impl ContainerLaunchSpec {
         self
     }
 
+    /// Serves IPC commands over unix sockets in `dir` instead of TCP.
+    ///
+    /// Pass the same directory to
+    /// [`BashTool::with_container_ipc_dir`](crate::BashTool::with_container_ipc_dir).
+    /// This is the only IPC path that works with [`ContainerLimits::without_network`].
+    #[must_use]
+    pub fn with_ipc_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.ipc_dir = Some(dir.into());
+        self
+    }
+
     #[must_use]
-    pub fn with_ipc_socket(mut self, socket_path: impl Into<PathBuf>) -> Self {
-        self.ipc_socket = Some(socket_path.into());
+    pub const fn with_limits(mut self, limits: ContainerLimits) -> Self {
+        self.limits = limits;
         self
     }
+
+    /// Docker `binds` entries (`host:container[:ro]`) for the workspace and extra mounts.
+    ///
+    /// The workspace is mounted read-write at `/workspace`, where scripts run, and
+    /// the IPC directory, if any, at [`CONTAINER_IPC_DIR`].
+    #[must_use]
+    pub fn binds(&self) -> Vec<String> {
+        let workspace = MountSpec::read_write(&self.workspace, CONTAINER_WORKSPACE);
+        let ipc = self
+            .ipc_dir
+            .as_ref()
+            .map(|dir| MountSpec::read_write(dir, CONTAINER_IPC_DIR));
+        std::iter::once(&workspace)
+            .chain(&self.mounts)
+            .chain(ipc.as_ref())
+            .map(|mount| {
+                let mut bind = format!(
+                    "{}:{}",
+                    mount.host_path.display(),
+                    mount.container_path.display()
+                );
+                if mount.access.read_only() {
+                    bind.push_str(":ro");
+                }
+                bind
+            })
+            .collect()
+    }
 }
 
+/// Directory inside the container where scripts run.
+pub const CONTAINER_WORKSPACE: &str = "/workspace";
+
+/// Directory inside the container where the host IPC directory is mounted.
+pub const CONTAINER_IPC_DIR: &str = "/run/aither-ipc";
+
 #[cfg(test)]
 mod tests {
-    use super::{MountRoot, MountRootError};
+    use super::{ContainerLaunchSpec, ContainerLimits, MountRoot, MountRootError, MountSpec};
 
     #[test]
     fn mount_root_maps_relative_path() {
@@ -264,4 +364,27 @@ mod tests {
             .expect_err("parent traversal must fail");
         assert!(matches!(error, MountRootError::ParentTraversal(_)));
     }
+
+    #[test]
+    fn launch_spec_binds_workspace_and_mounts() {
+        let spec = ContainerLaunchSpec::new("agent", "ubuntu:24.04", "/tmp/work")
+            .with_mount(MountSpec::read_only("/host/skills", "/root/skills"))
+            .with_limits(ContainerLimits::default().with_memory_bytes(1 << 30));
+        assert_eq!(
+            spec.binds(),
+            ["/tmp/work:/workspace", "/host/skills:/root/skills:ro"]
+        );
+        assert_eq!(spec.limits.memory_bytes, Some(1 << 30));
+    }
+
+    #[test]
+    fn launch_spec_binds_ipc_dir() {
+        let spec = ContainerLaunchSpec::new("agent", "ubuntu:24.04", "/tmp/work")
+            .with_ipc_dir("/tmp/ipc")
+            .with_limits(ContainerLimits::default().without_network());
+        assert_eq!(
+            spec.binds(),
+            ["/tmp/work:/workspace", "/tmp/ipc:/run/aither-ipc"]
+        );
+    }
 }
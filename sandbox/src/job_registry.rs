@@ -4,7 +4,7 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_channel::{Receiver, Sender};
 use futures_lite::io::AsyncWriteExt;
@@ -26,6 +26,8 @@ pub struct JobInfo {
     pub mode: BashMode,
     /// When the job was started.
     pub started_at: Instant,
+    /// When the job stopped running, if it has.
+    pub finished_at: Option<Instant>,
     /// Current status of the job.
     pub status: JobStatus,
     /// Path to output file.
@@ -51,6 +53,35 @@ pub enum JobStatus {
     Killed,
 }
 
+impl JobInfo {
+    /// Time the job has been running, or ran for once it stopped.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.finished_at
+            .map_or_else(|| self.started_at.elapsed(), |end| end - self.started_at)
+    }
+}
+
+impl JobStatus {
+    /// Returns true while the job is still running.
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        matches!(self, Self::Running)
+    }
+}
+
+/// Output captured from a job so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobLogs {
+    /// Bytes written to stdout.
+    pub stdout: Vec<u8>,
+    /// Bytes written to stderr.
+    pub stderr: Vec<u8>,
+}
+
+/// How often [`JobRegistry::wait`] re-checks a running job.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Sender used by the registry to push bytes into a running process stdin.
 pub type TerminalInputSender = Sender<Vec<u8>>;
 
@@ -71,7 +102,6 @@ struct JobState {
     input_tx: Option<TerminalInputSender>,
     kill_switch: Option<Sender<()>>,
     output_redirect: Option<async_fs::File>,
-    finished_at: Option<Instant>,
 }
 
 enum JobCommand {
@@ -117,6 +147,10 @@ enum JobCommand {
         pid: u32,
         reply: Sender<Option<JobInfo>>,
     },
+    GetByTaskId {
+        task_id: String,
+        reply: Sender<Option<JobInfo>>,
+    },
     Kill {
         pid: u32,
         reply: Sender<bool>,
@@ -384,6 +418,49 @@ impl JobRegistry {
             .expect("job registry response dropped")
     }
 
+    /// Gets information about a specific job by task ID.
+    pub async fn get_by_task_id(&self, task_id: &str) -> Option<JobInfo> {
+        let (reply_tx, reply_rx) = async_channel::bounded(1);
+        self.tx
+            .send(JobCommand::GetByTaskId {
+                task_id: task_id.to_string(),
+                reply: reply_tx,
+            })
+            .await
+            .expect("job registry service unavailable");
+        reply_rx
+            .recv()
+            .await
+            .expect("job registry response dropped")
+    }
+
+    /// Returns the output captured so far for a task.
+    pub async fn logs(&self, task_id: &str) -> Option<JobLogs> {
+        let info = self.get_by_task_id(task_id).await?;
+        let (stdout, stderr) = self.terminal_output(info.pid).await?;
+        Some(JobLogs { stdout, stderr })
+    }
+
+    /// Waits until a task stops running or `timeout` elapses.
+    ///
+    /// Returns the latest job information, which is still
+    /// [`JobStatus::Running`] if the timeout was reached first, or `None` if
+    /// no job has this task ID. Pass `None`, or a timeout too large to
+    /// represent as a deadline, to wait indefinitely.
+    pub async fn wait(&self, task_id: &str, timeout: Option<Duration>) -> Option<JobInfo> {
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        loop {
+            let info = self.get_by_task_id(task_id).await?;
+            if !info.status.is_running() || deadline.is_some_and(|end| Instant::now() >= end) {
+                return Some(info);
+            }
+            let interval = deadline.map_or(WAIT_POLL_INTERVAL, |end| {
+                WAIT_POLL_INTERVAL.min(end.saturating_duration_since(Instant::now()))
+            });
+            async_io::Timer::after(interval).await;
+        }
+    }
+
     /// Kills a running job by its PID.
     pub async fn kill(&self, pid: u32) -> bool {
         let (reply_tx, reply_rx) = async_channel::bounded(1);
@@ -498,6 +575,7 @@ impl JobRegistryService {
                         script,
                         mode,
                         started_at: Instant::now(),
+                        finished_at: None,
                         status: JobStatus::Running,
                         output_path,
                     };
@@ -513,7 +591,6 @@ impl JobRegistryService {
                             input_tx: None,
                             kill_switch: None,
                             output_redirect: None,
-                            finished_at: None,
                         },
                     );
                     tracing::debug!(pid = %pid, "registered background job");
@@ -588,7 +665,7 @@ impl JobRegistryService {
                             continue;
                         }
                         job.info.status = JobStatus::Completed { exit_code };
-                        job.finished_at = Some(Instant::now());
+                        job.info.finished_at = Some(Instant::now());
                         job.kill_switch = None;
                         if output_path.is_some() {
                             job.info.output_path = output_path;
@@ -635,6 +712,12 @@ impl JobRegistryService {
                     let item = jobs.get(&pid).map(|state| state.info.clone());
                     let _ = reply.send(item).await;
                 }
+                JobCommand::GetByTaskId { task_id, reply } => {
+                    let item = find_pid_by_task_id(&jobs, &task_id)
+                        .and_then(|pid| jobs.get(&pid))
+                        .map(|state| state.info.clone());
+                    let _ = reply.send(item).await;
+                }
                 JobCommand::Kill { pid, reply } => {
                     let result = kill_job(&mut jobs, pid).await;
                     let _ = reply.send(result).await;
@@ -795,7 +878,7 @@ async fn start_output_redirect(
 
 fn mark_job_failed(job: &mut JobState, error: String) {
     job.info.status = JobStatus::Failed { error };
-    job.finished_at = Some(Instant::now());
+    job.info.finished_at = Some(Instant::now());
     job.kill_switch = None;
 }
 
@@ -841,7 +924,7 @@ async fn kill_job(jobs: &mut HashMap<u32, JobState>, pid: u32) -> bool {
                 );
             }
             job.info.status = JobStatus::Killed;
-            job.finished_at = Some(Instant::now());
+            job.info.finished_at = Some(Instant::now());
             job.stdout_closed = true;
             job.stderr_closed = true;
             tracing::info!(pid = %pid, "killed job via runtime kill switch");
@@ -860,7 +943,7 @@ async fn kill_job(jobs: &mut HashMap<u32, JobState>, pid: u32) -> bool {
                     );
                 }
                 job.info.status = JobStatus::Killed;
-                job.finished_at = Some(Instant::now());
+                job.info.finished_at = Some(Instant::now());
                 job.stdout_closed = true;
                 job.stderr_closed = true;
                 tracing::info!(pid = %pid, "killed job");
@@ -887,7 +970,7 @@ async fn kill_job(jobs: &mut HashMap<u32, JobState>, pid: u32) -> bool {
                         );
                     }
                     job.info.status = JobStatus::Killed;
-                    job.finished_at = Some(Instant::now());
+                    job.info.finished_at = Some(Instant::now());
                     job.stdout_closed = true;
                     job.stderr_closed = true;
                     tracing::info!(pid = %pid, "killed job (Windows)");
@@ -1113,4 +1196,75 @@ mod tests {
         let job = registry.get(556).await.expect("job should exist");
         assert!(matches!(job.status, JobStatus::Killed));
     }
+
+    #[tokio::test]
+    async fn test_wait_and_logs_by_task_id() {
+        let (registry, service) = job_registry_channel();
+        TokioGlobal
+            .spawn(async move { service.serve().await })
+            .detach();
+
+        registry
+            .register(
+                777,
+                "task-build",
+                "exec-build",
+                "make",
+                BashMode::Network,
+                None,
+            )
+            .await;
+        registry.append_stdout(777, b"compiling\n".to_vec()).await;
+
+        let pending = registry
+            .wait("task-build", Some(Duration::from_millis(10)))
+            .await
+            .expect("job should exist");
+        assert!(pending.status.is_running());
+        assert!(pending.finished_at.is_none());
+
+        let waiter = registry.clone();
+        let done = TokioGlobal.spawn(async move { waiter.wait("task-build", None).await });
+        registry.complete(777, 0, None).await;
+        let job = done.await.expect("job should exist");
+        assert!(matches!(job.status, JobStatus::Completed { exit_code: 0 }));
+        assert!(job.finished_at.is_some());
+
+        let logs = registry
+            .logs("task-build")
+            .await
+            .expect("logs should exist");
+        assert_eq!(logs.stdout, b"compiling\n");
+        assert!(logs.stderr.is_empty());
+        assert!(registry.wait("missing", None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_wait_with_huge_timeout_waits_indefinitely() {
+        let (registry, service) = job_registry_channel();
+        TokioGlobal
+            .spawn(async move { service.serve().await })
+            .detach();
+
+        registry
+            .register(
+                778,
+                "task-forever",
+                "exec-forever",
+                "sleep 10",
+                BashMode::Network,
+                None,
+            )
+            .await;
+
+        let waiter = registry.clone();
+        let done = TokioGlobal.spawn(async move {
+            waiter
+                .wait("task-forever", Some(Duration::from_secs(u64::MAX)))
+                .await
+        });
+        registry.complete(778, 0, None).await;
+        let job = done.await.expect("job should exist");
+        assert!(matches!(job.status, JobStatus::Completed { exit_code: 0 }));
+    }
 }
@@ -82,12 +82,16 @@ pub use command::{
     register_tool_direct, schema_to_help,
 };
 pub use container::{
-    ContainerImageSpec, ContainerLaunchSpec, ContainerRuntimeKind, MountAccess, MountRoot,
-    MountRootError, MountSpec, RuntimePreference,
+    CONTAINER_IPC_DIR, CONTAINER_WORKSPACE, ContainerImageSpec, ContainerLaunchSpec,
+    ContainerLimits, ContainerRuntimeKind, MountAccess, MountRoot, MountRootError, MountSpec,
+    RuntimePreference,
 };
-pub use job_registry::{JobInfo, JobRegistry, JobStatus};
-pub use output::{Content, OutputEntry, OutputFormat, OutputStore, PendingUrl};
-pub use permission::{BashMode, PermissionHandler};
+pub use job_registry::{JobInfo, JobLogs, JobRegistry, JobStatus};
+pub use output::{
+    Content, OutputEntry, OutputFormat, OutputMetadata, OutputStore, PendingUrl, RetentionPolicy,
+    SearchMatch,
+};
+pub use permission::{BashMode, NetworkAccess, PermissionHandler};
 pub use shell_session::{
     ContainerExec, ContainerExecOutcome, ListSshTool, OpenSshArgs, OpenSshTool, ShellBackend,
     ShellRuntimeAvailability, ShellSessionRegistry, SshRuntimeProfile, SshServer,
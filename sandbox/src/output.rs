@@ -4,13 +4,20 @@
 //! - **Inline**: Super tiny text (< 5 lines) - always in context, never gets URL
 //! - **Loaded**: Small text/images - in context, URL generated only on offload
 //! - **Stored**: Large text/binary/video - file created immediately
+//!
+//! Stored files can carry a metadata sidecar (producing command, timestamp,
+//! exit status) under `outputs/.meta/`, are searchable with
+//! [`search_outputs`], and are pruned according to a [`RetentionPolicy`].
 
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use async_fs as fs;
+use futures_lite::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, Serializer, ser::SerializeMap};
 use tracing::debug;
@@ -229,6 +236,106 @@ pub struct PendingUrl {
     pub format: OutputFormat,
 }
 
+/// Directory inside the outputs directory that holds metadata sidecars.
+const METADATA_DIR: &str = ".meta";
+
+/// Describes where a stored output came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputMetadata {
+    /// Relative URL of the output (e.g., "outputs/amber-oak-swift-river.txt").
+    pub url: String,
+    /// Script or command that produced the output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Background task that produced the output, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    /// Stream the output was captured from ("stdout" or "stderr").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<String>,
+    /// Exit status of the producing command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Creation time in seconds since the Unix epoch.
+    pub created_at: u64,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+impl OutputMetadata {
+    /// Creates metadata for `url` stamped with the current time.
+    #[must_use]
+    pub fn new(url: impl Into<String>, size: u64) -> Self {
+        Self {
+            url: url.into(),
+            command: None,
+            task_id: None,
+            stream: None,
+            exit_code: None,
+            created_at: unix_secs(SystemTime::now()),
+            size,
+        }
+    }
+}
+
+/// Limits on how much stored output is kept on disk.
+///
+/// Files are aged by their last modification. Outputs passed as `keep` to
+/// [`apply_retention`], such as those running jobs are still writing, are
+/// never evicted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Maximum combined size of stored outputs; oldest files are removed first.
+    pub max_total_bytes: Option<u64>,
+    /// Maximum age of a stored output.
+    pub ttl: Option<Duration>,
+}
+
+impl RetentionPolicy {
+    /// Keeps everything.
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_total_bytes: None,
+            ttl: None,
+        }
+    }
+
+    /// Caps the combined size of stored outputs.
+    #[must_use]
+    pub const fn max_total_bytes(mut self, bytes: u64) -> Self {
+        self.max_total_bytes = Some(bytes);
+        self
+    }
+
+    /// Removes outputs older than `ttl`.
+    #[must_use]
+    pub const fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Returns true if the policy never removes anything.
+    #[must_use]
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_total_bytes.is_none() && self.ttl.is_none()
+    }
+}
+
+/// A line in a stored output that matched a search.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchMatch {
+    /// URL of the output containing the match.
+    pub url: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// The matching line.
+    pub text: String,
+    /// Command that produced the output, if recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
 /// Manages output storage for bash executions.
 #[derive(Debug)]
 pub struct OutputStore {
@@ -238,6 +345,8 @@ pub struct OutputStore {
     entries: HashMap<String, OutputRef>,
     /// Counter for unique IDs
     next_id: u64,
+    /// Retention policy applied by `enforce_retention`
+    retention: Mutex<RetentionPolicy>,
 }
 
 impl OutputStore {
@@ -253,9 +362,80 @@ impl OutputStore {
             dir,
             entries: HashMap::new(),
             next_id: 0,
+            retention: Mutex::new(RetentionPolicy::default()),
         })
     }
 
+    /// Sets the retention policy used by [`OutputStore::enforce_retention`].
+    pub fn set_retention(&self, policy: RetentionPolicy) {
+        *self
+            .retention
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = policy;
+    }
+
+    /// Returns the current retention policy.
+    #[must_use]
+    pub fn retention(&self) -> RetentionPolicy {
+        *self
+            .retention
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Removes stored outputs that violate the retention policy, except the
+    /// URLs in `keep`.
+    ///
+    /// Returns the URLs that were removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outputs directory cannot be scanned.
+    pub async fn enforce_retention(&self, keep: &[String]) -> std::io::Result<Vec<String>> {
+        apply_retention(&self.dir, self.retention(), SystemTime::now(), keep).await
+    }
+
+    /// Records metadata for a stored output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sidecar file cannot be written.
+    pub async fn record_metadata(&self, metadata: &OutputMetadata) -> std::io::Result<()> {
+        write_metadata(&self.dir, metadata).await
+    }
+
+    /// Returns metadata for a stored output URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output or its sidecar cannot be read.
+    pub async fn metadata(&self, url: &str) -> std::io::Result<Option<OutputMetadata>> {
+        read_metadata(&self.dir, url).await
+    }
+
+    /// Lists stored outputs, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outputs directory cannot be scanned.
+    pub async fn list(&self) -> std::io::Result<Vec<OutputMetadata>> {
+        list_outputs(&self.dir).await
+    }
+
+    /// Finds lines containing `query` in stored text outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outputs directory cannot be scanned.
+    pub async fn search(
+        &self,
+        query: &str,
+        ignore_case: bool,
+        max_results: usize,
+    ) -> std::io::Result<Vec<SearchMatch>> {
+        search_outputs(&self.dir, query, ignore_case, max_results).await
+    }
+
     /// Returns the base directory path.
     #[must_use]
     pub fn dir(&self) -> &Path {
@@ -491,12 +671,242 @@ impl OutputStore {
                 if let Err(e) = fs::remove_file(&filepath).await {
                     tracing::warn!(path = %filepath.display(), error = %e, "failed to remove output file");
                 }
+                remove_metadata(&self.dir, filename).await;
             }
         }
         Ok(())
     }
 }
 
+/// Writes a metadata sidecar for a stored output.
+///
+/// # Errors
+///
+/// Returns an error if the URL does not name a file directly inside the
+/// outputs directory, or the sidecar file cannot be written.
+pub async fn write_metadata(dir: &Path, metadata: &OutputMetadata) -> std::io::Result<()> {
+    let filename = metadata
+        .url
+        .strip_prefix("outputs/")
+        .unwrap_or(&metadata.url);
+    let valid = !filename.is_empty()
+        && !filename.starts_with('.')
+        && filename
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid output URL: {}", metadata.url),
+        ));
+    }
+    let meta_dir = dir.join(METADATA_DIR);
+    fs::create_dir_all(&meta_dir).await?;
+    let json = serde_json::to_vec(metadata).map_err(std::io::Error::other)?;
+    fs::write(meta_dir.join(format!("{filename}.json")), json).await
+}
+
+/// Reads metadata for a stored output.
+///
+/// Outputs without a sidecar get metadata derived from the file itself.
+/// Returns `None` if the output does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the file or its sidecar cannot be read.
+pub async fn read_metadata(dir: &Path, url: &str) -> std::io::Result<Option<OutputMetadata>> {
+    let filename = url.strip_prefix("outputs/").unwrap_or(url);
+    let file_meta = match fs::metadata(dir.join(filename)).await {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    Ok(Some(load_metadata(dir, filename, &file_meta).await))
+}
+
+/// Lists stored outputs with their metadata, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if the outputs directory cannot be scanned.
+pub async fn list_outputs(dir: &Path) -> std::io::Result<Vec<OutputMetadata>> {
+    let mut outputs = Vec::new();
+    for (filename, file_meta) in stored_files(dir).await? {
+        outputs.push(load_metadata(dir, &filename, &file_meta).await);
+    }
+    outputs.sort_by_key(|meta| meta.created_at);
+    Ok(outputs)
+}
+
+/// Removes stored outputs that violate `policy`, returning the removed URLs.
+///
+/// Expired outputs go first; then the least recently written outputs are
+/// removed until the total size fits. Outputs whose URLs are in `keep` are
+/// never removed, though their size still counts towards the total.
+///
+/// # Errors
+///
+/// Returns an error if the outputs directory cannot be scanned.
+pub async fn apply_retention(
+    dir: &Path,
+    policy: RetentionPolicy,
+    now: SystemTime,
+    keep: &[String],
+) -> std::io::Result<Vec<String>> {
+    if policy.is_unlimited() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<(String, u64, SystemTime)> = stored_files(dir)
+        .await?
+        .into_iter()
+        .map(|(filename, meta)| {
+            let modified = meta.modified().unwrap_or(now);
+            (filename, meta.len(), modified)
+        })
+        .collect();
+    files.sort_by_key(|(_, _, modified)| *modified);
+
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    let mut removed = Vec::new();
+    for (filename, size, modified) in files {
+        let expired = policy
+            .ttl
+            .is_some_and(|ttl| now.duration_since(modified).unwrap_or_default() > ttl);
+        let over_budget = policy.max_total_bytes.is_some_and(|max| total > max);
+        if !expired && !over_budget {
+            continue;
+        }
+        let url = format!("outputs/{filename}");
+        if keep.contains(&url) {
+            continue;
+        }
+        match fs::remove_file(dir.join(&filename)).await {
+            Ok(()) => {
+                remove_metadata(dir, &filename).await;
+                total -= size;
+                debug!(file = %filename, size, expired, "removed output by retention policy");
+                removed.push(url);
+            }
+            Err(e) => {
+                tracing::warn!(file = %filename, error = %e, "failed to remove output file");
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Finds lines containing `query` in stored text outputs, newest outputs first.
+///
+/// Outputs containing NUL bytes are treated as binary and skipped. At most `max_results` matches are returned.
+///
+/// # Errors
+///
+/// Returns an error if the outputs directory cannot be scanned.
+pub async fn search_outputs(
+    dir: &Path,
+    query: &str,
+    ignore_case: bool,
+    max_results: usize,
+) -> std::io::Result<Vec<SearchMatch>> {
+    let needle = if ignore_case {
+        query.to_lowercase()
+    } else {
+        query.to_string()
+    };
+    let mut matches = Vec::new();
+    for meta in list_outputs(dir).await?.into_iter().rev() {
+        if matches.len() >= max_results {
+            break;
+        }
+        let filename = meta.url.strip_prefix("outputs/").unwrap_or(&meta.url);
+        let Ok(data) = fs::read(dir.join(filename)).await else {
+            continue;
+        };
+        if data.contains(&0) {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&data);
+        for (index, line) in text.lines().enumerate() {
+            let found = if ignore_case {
+                line.to_lowercase().contains(&needle)
+            } else {
+                line.contains(&needle)
+            };
+            if !found {
+                continue;
+            }
+            matches.push(SearchMatch {
+                url: meta.url.clone(),
+                line: index + 1,
+                text: line.to_string(),
+                command: meta.command.clone(),
+            });
+            if matches.len() >= max_results {
+                break;
+            }
+        }
+    }
+    Ok(matches)
+}
+
+/// Lists regular files in the outputs directory, skipping hidden entries.
+async fn stored_files(dir: &Path) -> std::io::Result<Vec<(String, std::fs::Metadata)>> {
+    let mut files = Vec::new();
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next().await {
+        let entry = entry?;
+        let Some(filename) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if filename.starts_with('.') {
+            continue;
+        }
+        let meta = entry.metadata().await?;
+        if meta.is_file() {
+            files.push((filename, meta));
+        }
+    }
+    Ok(files)
+}
+
+async fn load_metadata(
+    dir: &Path,
+    filename: &str,
+    file_meta: &std::fs::Metadata,
+) -> OutputMetadata {
+    let sidecar = dir.join(METADATA_DIR).join(format!("{filename}.json"));
+    if let Ok(bytes) = fs::read(&sidecar).await
+        && let Ok(mut metadata) = serde_json::from_slice::<OutputMetadata>(&bytes)
+    {
+        metadata.size = file_meta.len();
+        return metadata;
+    }
+    let created = file_meta
+        .created()
+        .or_else(|_| file_meta.modified())
+        .unwrap_or_else(|_| SystemTime::now());
+    OutputMetadata {
+        created_at: unix_secs(created),
+        ..OutputMetadata::new(format!("outputs/{filename}"), file_meta.len())
+    }
+}
+
+async fn remove_metadata(dir: &Path, filename: &str) {
+    let sidecar = dir.join(METADATA_DIR).join(format!("{filename}.json"));
+    if let Err(e) = fs::remove_file(&sidecar).await
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!(path = %sidecar.display(), error = %e, "failed to remove output metadata");
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 /// Saves raw data to a file without any processing, returning the URL.
 ///
 /// Used to preserve the original uncompressed output alongside a compressed version.
@@ -798,4 +1208,101 @@ mod tests {
         let parts: Vec<&str> = name.split('-').collect();
         assert_eq!(parts.len(), 4, "Should have 4 words: {}", name);
     }
+
+    #[tokio::test]
+    async fn test_metadata_roundtrip_and_search() {
+        let temp = tempfile::tempdir().unwrap();
+        let store = OutputStore::new(temp.path()).await.unwrap();
+
+        let url = save_raw_to_file(store.dir(), b"compiling foo\nerror: missing semicolon\n")
+            .await
+            .unwrap();
+        let metadata = OutputMetadata {
+            command: Some("cargo build".to_string()),
+            exit_code: Some(101),
+            ..OutputMetadata::new(url.clone(), 0)
+        };
+        store.record_metadata(&metadata).await.unwrap();
+        save_raw_to_file(store.dir(), b"all good\n").await.unwrap();
+
+        let loaded = store.metadata(&url).await.unwrap().unwrap();
+        assert_eq!(loaded.command.as_deref(), Some("cargo build"));
+        assert_eq!(loaded.exit_code, Some(101));
+        assert_eq!(loaded.size, 39);
+        assert_eq!(store.list().await.unwrap().len(), 2);
+
+        let matches = store.search("ERROR", true, 10).await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].url, url);
+        assert_eq!(matches[0].line, 2);
+        assert_eq!(matches[0].command.as_deref(), Some("cargo build"));
+        assert!(store.search("ERROR", false, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retention_removes_oldest_and_expired() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let old = save_raw_to_file(dir, &[b'a'; 100]).await.unwrap();
+        let new = save_raw_to_file(dir, &[b'b'; 100]).await.unwrap();
+        let old_file = std::fs::File::options()
+            .write(true)
+            .open(dir.join(old.strip_prefix("outputs/").unwrap()))
+            .unwrap();
+        old_file
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        let policy = RetentionPolicy::unlimited().max_total_bytes(150);
+        let removed = apply_retention(dir, policy, SystemTime::now(), &[])
+            .await
+            .unwrap();
+        assert_eq!(removed, vec![old]);
+
+        let policy = RetentionPolicy::unlimited().ttl(Duration::from_secs(60));
+        let later = SystemTime::now() + Duration::from_secs(120);
+        let removed = apply_retention(dir, policy, later, &[]).await.unwrap();
+        assert_eq!(removed, vec![new]);
+        assert!(list_outputs(dir).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retention_keeps_referenced_outputs() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let running = save_raw_to_file(dir, &[b'a'; 100]).await.unwrap();
+        let result = save_raw_to_file(dir, &[b'b'; 100]).await.unwrap();
+        let stale = save_raw_to_file(dir, &[b'c'; 100]).await.unwrap();
+
+        let policy = RetentionPolicy::unlimited().ttl(Duration::from_secs(60));
+        let later = SystemTime::now() + Duration::from_secs(120);
+        let keep = [running.clone(), result.clone()];
+        let removed = apply_retention(dir, policy, later, &keep).await.unwrap();
+        assert_eq!(removed, vec![stale]);
+
+        let remaining: Vec<String> = list_outputs(dir)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|meta| meta.url)
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&running) && remaining.contains(&result));
+    }
+
+    #[tokio::test]
+    async fn test_write_metadata_rejects_paths_outside_the_store() {
+        let temp = tempfile::tempdir().unwrap();
+        for url in [
+            "outputs/../escape",
+            "outputs/nested/file.txt",
+            "outputs/",
+            "../x",
+        ] {
+            let err = write_metadata(temp.path(), &OutputMetadata::new(url, 0))
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{url}");
+        }
+    }
 }
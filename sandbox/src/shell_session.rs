@@ -2,6 +2,7 @@ use std::{
     borrow::Cow,
     collections::HashSet,
     future::Future,
+    path::PathBuf,
     pin::Pin,
     sync::{Arc, OnceLock, RwLock},
 };
@@ -135,6 +136,8 @@ pub struct ShellSessionRegistry {
     container_exec: Arc<OnceLock<Arc<dyn ContainerExecObject>>>,
     /// Default container ID — set once, used by container backend.
     container_id: Arc<OnceLock<String>>,
+    /// Host directory for container IPC sockets — set once, used by container backend.
+    container_ipc_dir: Arc<OnceLock<PathBuf>>,
 }
 
 impl std::fmt::Debug for ShellSessionRegistry {
@@ -153,6 +156,7 @@ impl ShellSessionRegistry {
             ssh_authorizer: Arc::new(RwLock::new(None)),
             container_exec: Arc::new(OnceLock::new()),
             container_id: Arc::new(OnceLock::new()),
+            container_ipc_dir: Arc::new(OnceLock::new()),
         }
     }
 
@@ -170,6 +174,12 @@ impl ShellSessionRegistry {
         let _ = self.container_id.set(id);
     }
 
+    /// Set the host directory mounted at [`CONTAINER_IPC_DIR`](crate::CONTAINER_IPC_DIR)
+    /// for container IPC sockets.
+    pub fn set_container_ipc_dir(&self, dir: PathBuf) {
+        let _ = self.container_ipc_dir.set(dir);
+    }
+
     pub fn set_availability(&self, availability: ShellRuntimeAvailability) -> Result<(), String> {
         *self
             .availability
@@ -208,6 +218,12 @@ impl ShellSessionRegistry {
         self.container_id.get().cloned()
     }
 
+    /// Get the configured container IPC directory (if set).
+    #[must_use]
+    pub fn container_ipc_dir(&self) -> Option<PathBuf> {
+        self.container_ipc_dir.get().cloned()
+    }
+
     pub fn set_ssh_servers(&self, servers: Vec<SshServer>) -> Result<(), String> {
         let mut seen = HashSet::new();
         let mut deduped = Vec::new();
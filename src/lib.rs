@@ -88,4 +88,15 @@ pub use aither_command as command;
 /// For internal use only.
 pub mod __hidden {
     pub type CowStr = alloc::borrow::Cow<'static, str>;
+
+    /// Parses a `#[tool(example = "...")]` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `json` is not valid JSON, when the tool's schema is built.
+    #[must_use]
+    pub fn example(json: &str) -> serde_json::Value {
+        serde_json::from_str(json)
+            .unwrap_or_else(|e| panic!("invalid JSON in #[tool(example = {json:?})]: {e}"))
+    }
 }
@@ -0,0 +1,28 @@
+//! Testing utilities for code built on aither.
+//!
+//! [`MockLanguageModel`] answers with scripted events, [`MockEmbedder`]
+//! produces deterministic vectors and [`MockTool`] records its calls, so
+//! tests don't need hand-written mocks.
+//!
+//! [`RecordingModel`] wraps a real model and writes every request and the
+//! events it produced to a [`Cassette`] file. [`ReplayModel`] serves those
+//! events back without network access or API keys, so integration tests
+//! stay deterministic in CI:
+//!
+//! ```rust,ignore
+//! use aither_test::{RecordingModel, ReplayModel};
+//!
+//! // Once, with credentials: record the conversation.
+//! let model = RecordingModel::new(openai, "tests/cassettes/weather.json");
+//! run_agent(&model).await?;
+//!
+//! // In CI: replay it.
+//! let model = ReplayModel::open("tests/cassettes/weather.json")?;
+//! run_agent(&model).await?;
+//! ```
+
+mod mock;
+mod replay;
+
+pub use mock::{MockEmbedder, MockError, MockLanguageModel, MockTool};
+pub use replay::{Cassette, Interaction, RecordingModel, ReplayError, ReplayModel};
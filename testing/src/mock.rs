@@ -0,0 +1,448 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use aither_core::{
+    EmbeddingModel, LanguageModel,
+    llm::{Event, LLMRequest, Tool, ToolOutput, model::Profile},
+};
+use futures_core::Stream;
+use serde_json::Value;
+
+type Matcher = Box<dyn Fn(&LLMRequest) -> bool + Send + Sync>;
+type Response = Vec<Result<Event, MockError>>;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Errors from a [`MockLanguageModel`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MockError {
+    /// No scripted response is left for the request.
+    #[error("no scripted response for request ending with: {last_message}")]
+    Unexpected {
+        /// Content of the request's last message.
+        last_message: String,
+    },
+    /// A failure scripted with [`MockLanguageModel::fail`].
+    #[error("{0}")]
+    Scripted(String),
+}
+
+struct Rule {
+    matcher: Option<Matcher>,
+    responses: VecDeque<Response>,
+}
+
+#[derive(Default)]
+struct MockState {
+    rules: Vec<Rule>,
+    requests: Vec<LLMRequest>,
+}
+
+/// A language model that answers with scripted events.
+///
+/// Responses are queued per request matcher and each is used once. A request
+/// is answered by the first matcher, in the order they were added, that
+/// accepts it and still has responses queued; responses added without a
+/// matcher accept any request. Requests nobody answers fail with
+/// [`MockError::Unexpected`].
+///
+/// Clones share the script and the request log, so a test can keep one
+/// clone for assertions after handing another to the code under test.
+///
+/// ```rust,ignore
+/// use aither_core::llm::Event;
+/// use aither_test::MockLanguageModel;
+///
+/// let model = MockLanguageModel::new()
+///     .when(
+///         |request| request.messages().len() == 1,
+///         [Event::tool_call("1", "weather", json!({ "city": "Paris" }))],
+///     )
+///     .text("It is sunny in Paris.");
+///
+/// agent_run(model.clone()).await;
+/// assert_eq!(model.requests().len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct MockLanguageModel {
+    state: Arc<Mutex<MockState>>,
+    profile: Profile,
+}
+
+impl std::fmt::Debug for MockLanguageModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = lock(&self.state);
+        f.debug_struct("MockLanguageModel")
+            .field("rules", &state.rules.len())
+            .field("requests", &state.requests.len())
+            .field("profile", &self.profile.name)
+            .finish()
+    }
+}
+
+impl Default for MockLanguageModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockLanguageModel {
+    /// Creates a model with nothing scripted.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: Arc::default(),
+            profile: Profile::new("mock", "aither", "mock", "Scripted test model", 128_000),
+        }
+    }
+
+    /// Reports `profile` from [`LanguageModel::profile`].
+    #[must_use]
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Queues a response for any request.
+    #[must_use]
+    pub fn respond_with(self, events: impl IntoIterator<Item = Event>) -> Self {
+        self.push(None, events.into_iter().map(Ok).collect());
+        self
+    }
+
+    /// Queues a text response for any request.
+    #[must_use]
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.respond_with([Event::text(text)])
+    }
+
+    /// Queues a response with a single tool call for any request.
+    #[must_use]
+    pub fn tool_call(self, id: &str, name: &str, arguments: Value) -> Self {
+        self.respond_with([Event::tool_call(id, name, arguments)])
+    }
+
+    /// Queues a failure for any request.
+    #[must_use]
+    pub fn fail(self, message: impl Into<String>) -> Self {
+        self.push(None, vec![Err(MockError::Scripted(message.into()))]);
+        self
+    }
+
+    /// Queues a response for requests accepted by `matcher`.
+    #[must_use]
+    pub fn when(
+        self,
+        matcher: impl Fn(&LLMRequest) -> bool + Send + Sync + 'static,
+        events: impl IntoIterator<Item = Event>,
+    ) -> Self {
+        self.push(
+            Some(Box::new(matcher)),
+            events.into_iter().map(Ok).collect(),
+        );
+        self
+    }
+
+    /// Queues a text response for requests whose last message contains
+    /// `needle`.
+    #[must_use]
+    pub fn when_last_message_contains(self, needle: &str, text: impl Into<String>) -> Self {
+        let needle = needle.to_owned();
+        self.when(
+            move |request| {
+                request
+                    .messages()
+                    .last()
+                    .is_some_and(|message| message.content().contains(&needle))
+            },
+            [Event::text(text)],
+        )
+    }
+
+    /// Requests received so far, oldest first.
+    #[must_use]
+    pub fn requests(&self) -> Vec<LLMRequest> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// Returns `true` once every scripted response has been used.
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        lock(&self.state)
+            .rules
+            .iter()
+            .all(|rule| rule.responses.is_empty())
+    }
+
+    /// Adds `response` to the rule with `matcher`, or to a new rule.
+    ///
+    /// Unconditional responses share one rule so they are served in order.
+    fn push(&self, matcher: Option<Matcher>, response: Response) {
+        let mut state = lock(&self.state);
+        if matcher.is_none()
+            && let Some(rule) = state.rules.iter_mut().find(|rule| rule.matcher.is_none())
+        {
+            rule.responses.push_back(response);
+            return;
+        }
+        state.rules.push(Rule {
+            matcher,
+            responses: VecDeque::from([response]),
+        });
+    }
+
+    fn next_response(&self, request: &LLMRequest) -> Response {
+        let response = {
+            let mut state = lock(&self.state);
+            state.requests.push(request.clone());
+            state
+                .rules
+                .iter_mut()
+                .find(|rule| {
+                    !rule.responses.is_empty()
+                        && rule.matcher.as_ref().is_none_or(|matcher| matcher(request))
+                })
+                .and_then(|rule| rule.responses.pop_front())
+        };
+        response.unwrap_or_else(|| {
+            vec![Err(MockError::Unexpected {
+                last_message: request
+                    .messages()
+                    .last()
+                    .map(|message| message.content().to_owned())
+                    .unwrap_or_default(),
+            })]
+        })
+    }
+}
+
+impl LanguageModel for MockLanguageModel {
+    type Error = MockError;
+
+    fn respond(
+        &self,
+        request: LLMRequest,
+    ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+        futures_lite::stream::iter(self.next_response(&request))
+    }
+
+    async fn profile(&self) -> Profile {
+        self.profile.clone()
+    }
+}
+
+/// An embedding model that hashes words into a fixed number of dimensions.
+///
+/// Texts sharing words get similar vectors, which is enough to test
+/// retrieval ranking without a real model. Vectors are normalized to unit
+/// length; text without words embeds to the zero vector.
+#[derive(Debug, Clone)]
+pub struct MockEmbedder {
+    dim: usize,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockEmbedder {
+    /// Creates an embedder producing `dim`-dimensional vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is zero.
+    #[must_use]
+    pub fn new(dim: usize) -> Self {
+        assert!(dim > 0, "embedding dimension must be positive");
+        Self {
+            dim,
+            calls: Arc::default(),
+        }
+    }
+
+    /// Texts embedded so far, in order.
+    #[must_use]
+    pub fn calls(&self) -> Vec<String> {
+        lock(&self.calls).clone()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn vector(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0; self.dim];
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            let mut hasher = DefaultHasher::new();
+            word.to_lowercase().hash(&mut hasher);
+            vector[(hasher.finish() % self.dim as u64) as usize] += 1.0;
+        }
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for x in &mut vector {
+                *x /= norm;
+            }
+        }
+        vector
+    }
+}
+
+impl EmbeddingModel for MockEmbedder {
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    async fn embed(&self, text: &str) -> aither_core::Result<Vec<f32>> {
+        lock(&self.calls).push(text.to_owned());
+        Ok(self.vector(text))
+    }
+}
+
+/// A tool that records its arguments and answers with scripted outputs.
+///
+/// Takes any JSON arguments. Queued outputs are returned in order; once
+/// they run out, every call returns the fallback, `"ok"` unless set with
+/// [`otherwise`](Self::otherwise). Clones share the script and call log.
+#[derive(Debug, Clone)]
+pub struct MockTool {
+    name: Cow<'static, str>,
+    outputs: Arc<Mutex<VecDeque<Result<ToolOutput, String>>>>,
+    fallback: ToolOutput,
+    calls: Arc<Mutex<Vec<Value>>>,
+}
+
+impl MockTool {
+    /// Creates a tool called `name`.
+    #[must_use]
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            outputs: Arc::default(),
+            fallback: ToolOutput::text("ok"),
+            calls: Arc::default(),
+        }
+    }
+
+    /// Queues `output` for the next unanswered call.
+    #[must_use]
+    pub fn returns(self, output: ToolOutput) -> Self {
+        lock(&self.outputs).push_back(Ok(output));
+        self
+    }
+
+    /// Queues a text output for the next unanswered call.
+    #[must_use]
+    pub fn returns_text(self, text: impl Into<String>) -> Self {
+        self.returns(ToolOutput::text(text))
+    }
+
+    /// Queues a failure for the next unanswered call.
+    #[must_use]
+    pub fn fails(self, message: impl Into<String>) -> Self {
+        lock(&self.outputs).push_back(Err(message.into()));
+        self
+    }
+
+    /// Returns `output` once the queued outputs run out.
+    #[must_use]
+    pub fn otherwise(mut self, output: ToolOutput) -> Self {
+        self.fallback = output;
+        self
+    }
+
+    /// Arguments of every call so far, in order.
+    #[must_use]
+    pub fn calls(&self) -> Vec<Value> {
+        lock(&self.calls).clone()
+    }
+}
+
+impl Tool for MockTool {
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    type Arguments = Value;
+
+    async fn call(&self, arguments: Self::Arguments) -> aither_core::Result<ToolOutput> {
+        lock(&self.calls).push(arguments);
+        let next = lock(&self.outputs).pop_front();
+        match next {
+            Some(Ok(output)) => Ok(output),
+            Some(Err(message)) => Err(anyhow::anyhow!(message)),
+            None => Ok(self.fallback.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aither_core::llm::{Message, collect_text};
+    use serde_json::json;
+
+    fn ask(question: &str) -> LLMRequest {
+        LLMRequest::new([Message::user(question)])
+    }
+
+    #[tokio::test]
+    async fn serves_matching_responses_once() {
+        let model = MockLanguageModel::new()
+            .when_last_message_contains("weather", "sunny")
+            .text("first")
+            .text("second");
+        let handle = model.clone();
+
+        assert_eq!(
+            collect_text(model.respond(ask("hi"))).await.unwrap(),
+            "first"
+        );
+        assert_eq!(
+            collect_text(model.respond(ask("weather?"))).await.unwrap(),
+            "sunny"
+        );
+        assert_eq!(
+            collect_text(model.respond(ask("weather?"))).await.unwrap(),
+            "second"
+        );
+        assert!(handle.is_exhausted());
+        assert_eq!(
+            collect_text(model.respond(ask("more"))).await.unwrap_err(),
+            MockError::Unexpected {
+                last_message: "more".into()
+            }
+        );
+        assert_eq!(handle.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn embedder_ranks_shared_words_closer() {
+        let embedder = MockEmbedder::new(64);
+        let query = embedder.embed("rust borrow checker").await.unwrap();
+        let close = embedder.embed("the Rust borrow checker").await.unwrap();
+        let far = embedder.embed("banana bread").await.unwrap();
+
+        let dot = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+        assert!(dot(&query, &close) > dot(&query, &far));
+        assert_eq!(embedder.calls().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn tool_records_calls_and_follows_script() {
+        let tool = MockTool::new("search")
+            .returns_text("found")
+            .fails("offline");
+
+        assert!(tool.call(json!({ "q": "a" })).await.is_ok());
+        assert_eq!(
+            tool.call(json!({ "q": "b" }))
+                .await
+                .unwrap_err()
+                .to_string(),
+            "offline"
+        );
+        assert!(tool.call(json!({ "q": "c" })).await.is_ok());
+        assert_eq!(tool.calls()[1], json!({ "q": "b" }));
+    }
+}
@@ -0,0 +1,409 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use aither_core::{
+    LanguageModel,
+    llm::{Event, LLMRequest, Message, model::Profile},
+};
+use futures_core::Stream;
+use futures_lite::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// Recorded requests and the events the model answered them with.
+///
+/// Stored as pretty-printed JSON so recordings can be reviewed and edited
+/// by hand.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Profile of the recorded model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+    /// Interactions in the order they were recorded.
+    #[serde(default)]
+    pub interactions: Vec<Interaction>,
+}
+
+/// One request and the model's response to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Messages of the request.
+    pub messages: Vec<Message>,
+    /// Names of the tools offered in the request.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Events the model produced, in order.
+    #[serde(default)]
+    pub events: Vec<Event>,
+    /// The error that ended the response, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Interaction {
+    fn matches(&self, request: &LLMRequest) -> bool {
+        self.messages == request.messages() && self.tools == tool_names(request)
+    }
+}
+
+impl Cassette {
+    /// Creates an empty cassette.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a cassette from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a cassette.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(io::Error::other)
+    }
+
+    /// Writes the cassette to `path`, creating parent directories as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        std::fs::write(path, json)
+    }
+}
+
+fn tool_names(request: &LLMRequest) -> Vec<String> {
+    request
+        .tool_definitions()
+        .iter()
+        .map(|definition| definition.name().to_string())
+        .collect()
+}
+
+/// Records a model's responses to a [`Cassette`] file.
+///
+/// Each response is appended once its stream has been read to the end and
+/// the file is rewritten right away, so a test that fails halfway still
+/// leaves the earlier interactions on disk. Responses whose stream is
+/// dropped early are not recorded.
+#[derive(Debug, Clone)]
+pub struct RecordingModel<M> {
+    model: M,
+    path: PathBuf,
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl<M> RecordingModel<M> {
+    /// Records `model` to `path`, replacing any cassette already there.
+    #[must_use]
+    pub fn new(model: M, path: impl Into<PathBuf>) -> Self {
+        Self {
+            model,
+            path: path.into(),
+            cassette: Arc::default(),
+        }
+    }
+
+    /// The recorded model.
+    #[must_use]
+    pub const fn inner(&self) -> &M {
+        &self.model
+    }
+
+    /// A copy of what has been recorded so far.
+    #[must_use]
+    pub fn cassette(&self) -> Cassette {
+        self.cassette
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl<M: LanguageModel> LanguageModel for RecordingModel<M> {
+    type Error = M::Error;
+
+    /// # Panics
+    ///
+    /// Panics if the cassette cannot be written, since the recording would
+    /// otherwise be silently incomplete.
+    fn respond(
+        &self,
+        request: LLMRequest,
+    ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+        let messages = request.messages().to_vec();
+        let tools = tool_names(&request);
+        async_stream::stream! {
+            let mut events = Vec::new();
+            let mut error = None;
+            let mut stream = std::pin::pin!(self.model.respond(request));
+            while let Some(result) = stream.next().await {
+                match result {
+                    Ok(event) => {
+                        events.push(event.clone());
+                        yield Ok(event);
+                    }
+                    Err(err) => {
+                        error = Some(err.to_string());
+                        yield Err(err);
+                        break;
+                    }
+                }
+            }
+
+            let needs_profile = self
+                .cassette
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .profile
+                .is_none();
+            let profile = if needs_profile {
+                Some(self.model.profile().await)
+            } else {
+                None
+            };
+
+            let mut cassette = self.cassette.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(profile) = profile {
+                cassette.profile = Some(profile);
+            }
+            cassette.interactions.push(Interaction {
+                messages,
+                tools,
+                events,
+                error,
+            });
+            if let Err(err) = cassette.save(&self.path) {
+                panic!("failed to write cassette {}: {err}", self.path.display());
+            }
+        }
+    }
+
+    fn profile(&self) -> impl Future<Output = Profile> + Send {
+        self.model.profile()
+    }
+}
+
+/// Answers requests from a [`Cassette`] instead of a real model.
+///
+/// By default a request is answered by the first unused interaction whose
+/// messages and tool names are equal to it, so a test that asks the same
+/// question twice gets the two recorded answers in order. Use
+/// [`sequential`](Self::sequential) to serve interactions in recorded
+/// order regardless of the request.
+#[derive(Debug)]
+pub struct ReplayModel {
+    cassette: Cassette,
+    used: Mutex<Vec<bool>>,
+    sequential: bool,
+}
+
+impl ReplayModel {
+    /// Replays `cassette`.
+    #[must_use]
+    pub fn new(cassette: Cassette) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+        Self {
+            cassette,
+            used: Mutex::new(used),
+            sequential: false,
+        }
+    }
+
+    /// Replays the cassette stored at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a cassette.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Cassette::load(path).map(Self::new)
+    }
+
+    /// Serve interactions in recorded order without comparing requests.
+    ///
+    /// Useful when requests contain timestamps or other values that change
+    /// between runs.
+    #[must_use]
+    pub const fn sequential(mut self) -> Self {
+        self.sequential = true;
+        self
+    }
+
+    /// Returns `true` once every recorded interaction has been served.
+    ///
+    /// Assert this at the end of a test to catch requests the code under
+    /// test no longer makes.
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.used
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .all(|&used| used)
+    }
+
+    fn take(&self, request: &LLMRequest) -> Option<&Interaction> {
+        let index = {
+            let mut used = self.used.lock().unwrap_or_else(PoisonError::into_inner);
+            let index = self
+                .cassette
+                .interactions
+                .iter()
+                .zip(used.iter())
+                .position(|(interaction, &used)| {
+                    !used && (self.sequential || interaction.matches(request))
+                })?;
+            used[index] = true;
+            index
+        };
+        Some(&self.cassette.interactions[index])
+    }
+}
+
+/// Errors from a [`ReplayModel`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReplayError {
+    /// No unused recorded interaction matches the request.
+    #[error("no recorded response for request ending with: {last_message}")]
+    Unmatched {
+        /// Content of the request's last message, to help find the
+        /// difference.
+        last_message: String,
+    },
+    /// The recorded response ended with this error.
+    #[error("recorded error: {0}")]
+    Recorded(String),
+}
+
+impl LanguageModel for ReplayModel {
+    type Error = ReplayError;
+
+    fn respond(
+        &self,
+        request: LLMRequest,
+    ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+        let results: Vec<Result<Event, ReplayError>> = self.take(&request).map_or_else(
+            || {
+                vec![Err(ReplayError::Unmatched {
+                    last_message: request
+                        .messages()
+                        .last()
+                        .map(|message| message.content().to_string())
+                        .unwrap_or_default(),
+                })]
+            },
+            |interaction| {
+                interaction
+                    .events
+                    .iter()
+                    .cloned()
+                    .map(Ok)
+                    .chain(
+                        interaction
+                            .error
+                            .clone()
+                            .map(ReplayError::Recorded)
+                            .map(Err),
+                    )
+                    .collect()
+            },
+        );
+        futures_lite::stream::iter(results)
+    }
+
+    async fn profile(&self) -> Profile {
+        self.cassette
+            .profile
+            .clone()
+            .unwrap_or_else(|| Profile::new("replay", "aither", "replay", "Recorded responses", 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aither_core::llm::collect_text;
+
+    /// Answers with the request's last message in upper case.
+    struct Shout;
+
+    impl LanguageModel for Shout {
+        type Error = ReplayError;
+
+        fn respond(
+            &self,
+            request: LLMRequest,
+        ) -> impl Stream<Item = Result<Event, Self::Error>> + Send {
+            let text = request.messages().last().unwrap().content().to_uppercase();
+            futures_lite::stream::iter([Ok(Event::text(text))])
+        }
+
+        async fn profile(&self) -> Profile {
+            Profile::new("shout", "test", "shout", "Shouts back", 1024)
+        }
+    }
+
+    fn ask(question: &str) -> LLMRequest {
+        LLMRequest::new([Message::user(question)])
+    }
+
+    #[tokio::test]
+    async fn replays_what_was_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassettes/shout.json");
+
+        let recorder = RecordingModel::new(Shout, &path);
+        assert_eq!(
+            collect_text(recorder.respond(ask("hi"))).await.unwrap(),
+            "HI"
+        );
+        assert_eq!(
+            collect_text(recorder.respond(ask("bye"))).await.unwrap(),
+            "BYE"
+        );
+
+        let replay = ReplayModel::open(&path).unwrap();
+        assert_eq!(replay.profile().await.name, "shout");
+        assert_eq!(
+            collect_text(replay.respond(ask("bye"))).await.unwrap(),
+            "BYE"
+        );
+        assert!(!replay.is_exhausted());
+        assert_eq!(collect_text(replay.respond(ask("hi"))).await.unwrap(), "HI");
+        assert!(replay.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn unmatched_requests_fail() {
+        let mut cassette = Cassette::new();
+        cassette.interactions.push(Interaction {
+            messages: vec![Message::user("hi")],
+            tools: Vec::new(),
+            events: vec![Event::text("HI")],
+            error: None,
+        });
+
+        let replay = ReplayModel::new(cassette.clone());
+        let err = collect_text(replay.respond(ask("other")))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ReplayError::Unmatched {
+                last_message: "other".into()
+            }
+        );
+
+        let replay = ReplayModel::new(cassette).sequential();
+        assert_eq!(
+            collect_text(replay.respond(ask("other"))).await.unwrap(),
+            "HI"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Background command jobs.
+//!
+//! A background command runs on its own thread; its output is buffered in the
+//! [`JobManager`] until the model polls it through [`CommandJobsTool`].
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use aither_core::llm::{Tool, ToolOutput, tool::json};
+use anyhow::{Result, anyhow};
+use async_io::Timer;
+use async_process::Child;
+use futures_lite::future;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{OutputObserver, OutputStream, pump};
+
+/// How often a job whose output streams have closed is checked for exit.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Registry of background jobs started by a [`CommandTool`](crate::CommandTool).
+///
+/// Cloning is cheap; clones share the same jobs.
+#[derive(Debug, Clone, Default)]
+pub struct JobManager {
+    inner: Arc<JobManagerInner>,
+}
+
+#[derive(Debug, Default)]
+struct JobManagerInner {
+    next_id: AtomicU64,
+    jobs: Mutex<BTreeMap<String, Arc<Job>>>,
+}
+
+#[derive(Debug)]
+struct Job {
+    program: String,
+    args: Vec<String>,
+    started: Instant,
+    max_output: usize,
+    child: Mutex<Child>,
+    state: Mutex<JobState>,
+}
+
+#[derive(Debug, Default)]
+struct JobState {
+    stdout: String,
+    stderr: String,
+    status: Option<i32>,
+    finished_after: Option<Duration>,
+    killed: bool,
+}
+
+/// Point-in-time view of a background job.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobSnapshot {
+    /// Job identifier returned when the job was started.
+    pub job_id: String,
+    /// Program being run.
+    pub program: String,
+    /// Program arguments.
+    pub args: Vec<String>,
+    /// Whether the process is still running.
+    pub running: bool,
+    /// Exit status once finished.
+    pub status: Option<i32>,
+    /// Whether the job was killed.
+    pub killed: bool,
+    /// Milliseconds since start (or until exit, once finished).
+    pub elapsed_ms: u64,
+    /// Most recent stdout, truncated from the front to the output limit.
+    pub stdout: String,
+    /// Most recent stderr, truncated from the front to the output limit.
+    pub stderr: String,
+}
+
+impl JobManager {
+    /// Create an empty job registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take ownership of a spawned child and pump its output on a background thread.
+    pub(crate) fn start(
+        &self,
+        program: String,
+        args: Vec<String>,
+        mut child: Child,
+        max_output: usize,
+        observer: Option<OutputObserver>,
+    ) -> Result<String> {
+        let id = format!(
+            "job-{}",
+            self.inner.next_id.fetch_add(1, Ordering::Relaxed) + 1
+        );
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let job = Arc::new(Job {
+            program,
+            args,
+            started: Instant::now(),
+            max_output,
+            child: Mutex::new(child),
+            state: Mutex::new(JobState::default()),
+        });
+        self.inner
+            .jobs
+            .lock()
+            .unwrap()
+            .insert(id.clone(), Arc::clone(&job));
+
+        let job_id = id.clone();
+        std::thread::Builder::new()
+            .name(format!("aither-command-{id}"))
+            .spawn(move || {
+                future::block_on(async move {
+                    let on_chunk = |stream: OutputStream, text: &str| {
+                        job.append(stream, text);
+                        if let Some(observer) = &observer {
+                            observer.emit(Some(&job_id), stream, text);
+                        }
+                    };
+                    future::zip(
+                        pump(stdout, OutputStream::Stdout, &on_chunk),
+                        pump(stderr, OutputStream::Stderr, &on_chunk),
+                    )
+                    .await;
+                    job.wait_for_exit().await;
+                });
+            })
+            .map_err(|err| anyhow!("failed to start job thread: {err}"))?;
+        Ok(id)
+    }
+
+    /// Snapshot of a job.
+    ///
+    /// # Errors
+    /// Returns an error if no job has this id.
+    pub fn poll(&self, job_id: &str) -> Result<JobSnapshot> {
+        Ok(self.get(job_id)?.snapshot(job_id))
+    }
+
+    /// Kill a running job. Killing a finished job is a no-op.
+    ///
+    /// # Errors
+    /// Returns an error if no job has this id or the process cannot be signalled.
+    pub fn kill(&self, job_id: &str) -> Result<JobSnapshot> {
+        let job = self.get(job_id)?;
+        if job.state.lock().unwrap().finished_after.is_none() {
+            job.child.lock().unwrap().kill()?;
+            job.state.lock().unwrap().killed = true;
+        }
+        Ok(job.snapshot(job_id))
+    }
+
+    /// Snapshots of all jobs, oldest first.
+    pub fn list(&self) -> Vec<JobSnapshot> {
+        let jobs = self.inner.jobs.lock().unwrap();
+        let mut snapshots: Vec<JobSnapshot> =
+            jobs.iter().map(|(id, job)| job.snapshot(id)).collect();
+        snapshots.sort_by_key(|snapshot| {
+            snapshot
+                .job_id
+                .trim_start_matches("job-")
+                .parse::<u64>()
+                .unwrap_or_default()
+        });
+        snapshots
+    }
+
+    /// Kill every job that is still running.
+    pub fn kill_all(&self) {
+        let jobs: Vec<String> = self.inner.jobs.lock().unwrap().keys().cloned().collect();
+        for id in jobs {
+            self.kill(&id).ok();
+        }
+    }
+
+    fn get(&self, job_id: &str) -> Result<Arc<Job>> {
+        self.inner
+            .jobs
+            .lock()
+            .unwrap()
+            .get(job_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown job '{job_id}'"))
+    }
+}
+
+impl Job {
+    fn append(&self, stream: OutputStream, text: &str) {
+        let mut state = self.state.lock().unwrap();
+        let buffer = match stream {
+            OutputStream::Stdout => &mut state.stdout,
+            OutputStream::Stderr => &mut state.stderr,
+        };
+        buffer.push_str(text);
+        keep_tail(buffer, self.max_output);
+    }
+
+    /// Poll for exit without holding the child lock across awaits, so `kill` stays responsive.
+    async fn wait_for_exit(&self) {
+        loop {
+            let exit = self.child.lock().unwrap().try_status();
+            match exit {
+                Ok(Some(status)) => {
+                    let mut state = self.state.lock().unwrap();
+                    state.status = status.code();
+                    state.finished_after = Some(self.started.elapsed());
+                    return;
+                }
+                Ok(None) => {
+                    Timer::after(EXIT_POLL_INTERVAL).await;
+                }
+                Err(_) => {
+                    self.state.lock().unwrap().finished_after = Some(self.started.elapsed());
+                    return;
+                }
+            }
+        }
+    }
+
+    fn snapshot(&self, job_id: &str) -> JobSnapshot {
+        let state = self.state.lock().unwrap();
+        let elapsed = state
+            .finished_after
+            .unwrap_or_else(|| self.started.elapsed());
+        JobSnapshot {
+            job_id: job_id.to_string(),
+            program: self.program.clone(),
+            args: self.args.clone(),
+            running: state.finished_after.is_none(),
+            status: state.status,
+            killed: state.killed,
+            elapsed_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            stdout: state.stdout.clone(),
+            stderr: state.stderr.clone(),
+        }
+    }
+}
+
+/// Drop leading bytes so `buffer` holds at most `max` bytes, on a char boundary.
+fn keep_tail(buffer: &mut String, max: usize) {
+    if buffer.len() <= max {
+        return;
+    }
+    let mut cut = buffer.len() - max;
+    while !buffer.is_char_boundary(cut) {
+        cut += 1;
+    }
+    buffer.drain(..cut);
+}
+
+/// Check on or stop background jobs started by the `command` tool.
+///
+/// Start a job by calling `command` with `background: true`; it returns a job id.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum JobAction {
+    /// Get the status and latest output of a job.
+    Poll {
+        /// Job id returned by the `command` tool.
+        job_id: String,
+    },
+    /// Kill a running job.
+    Kill {
+        /// Job id returned by the `command` tool.
+        job_id: String,
+    },
+    /// List all jobs.
+    List,
+}
+
+/// Companion tool for polling and killing background jobs.
+///
+/// Obtain one from [`CommandTool::jobs_tool`](crate::CommandTool::jobs_tool) so both share the same jobs.
+#[derive(Debug, Clone)]
+pub struct CommandJobsTool {
+    jobs: JobManager,
+    name: String,
+}
+
+impl CommandJobsTool {
+    pub(crate) fn new(jobs: JobManager) -> Self {
+        Self {
+            jobs,
+            name: "command_jobs".into(),
+        }
+    }
+
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+impl Tool for CommandJobsTool {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Owned(self.name.clone())
+    }
+
+    type Arguments = JobAction;
+
+    async fn call(&self, arguments: Self::Arguments) -> aither_core::Result<ToolOutput> {
+        let output = match arguments {
+            JobAction::Poll { job_id } => json(&self.jobs.poll(&job_id)?),
+            JobAction::Kill { job_id } => json(&self.jobs.kill(&job_id)?),
+            JobAction::List => json(&self.jobs.list()),
+        };
+        Ok(ToolOutput::text(output))
+    }
+}
@@ -1,18 +1,31 @@
-use std::{borrow::Cow, path::PathBuf};
+mod jobs;
+mod limits;
+mod policy;
+
+use std::{borrow::Cow, fmt, path::PathBuf, sync::Arc, time::Duration};
 
 use aither_core::llm::{Tool, ToolOutput, tool::json};
 use anyhow::{Context, Result, bail};
-use async_process::Command;
+use async_io::Timer;
+use async_process::{Command, Stdio};
+use futures_lite::{AsyncRead, AsyncReadExt, future};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub use crate::jobs::{CommandJobsTool, JobAction, JobManager, JobSnapshot};
+pub use crate::limits::{LimitKind, ResourceLimits};
+use crate::policy::CommandPolicy;
+pub use crate::policy::{CommandRule, PolicyVerdict, RuleEffect};
+
 /// Execute a shell command with arguments.
 ///
 /// Runs a program with the specified arguments and returns stdout, stderr,
 /// and exit code. Use this for executing system commands when you need
 /// fine-grained control over arguments.
 ///
-/// For complex scripts with pipelines, use `bash` instead.
+/// For complex scripts with pipelines, use `bash` instead. Long-running commands
+/// (servers, watchers, slow builds) can run with `background: true`; poll them
+/// with `command_jobs`.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommandArgs {
     /// Program name to execute (e.g., "ls", "grep", "git", "cargo").
@@ -22,6 +35,15 @@ pub struct CommandArgs {
     pub args: Vec<String>,
     /// Working directory for command execution. Omit to use default.
     pub cwd: Option<PathBuf>,
+    /// Kill the command if it runs longer than this many milliseconds. Can only tighten the configured limit.
+    pub timeout_ms: Option<u64>,
+    /// Maximum CPU time in seconds. Can only tighten the configured limit.
+    pub max_cpu_secs: Option<u64>,
+    /// Maximum memory in megabytes. Can only tighten the configured limit.
+    pub max_memory_mb: Option<u64>,
+    /// Start the command in the background and return a job id immediately.
+    #[serde(default)]
+    pub background: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -30,63 +52,199 @@ pub struct CommandOutput {
     pub status: i32,
     pub stdout: String,
     pub stderr: String,
+    /// Resource limit that stopped the command, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_exceeded: Option<LimitKind>,
+    /// Whether stdout or stderr was cut to the output limit.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// Which output stream a chunk came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// A piece of command output delivered while the command is still running.
+#[derive(Debug, Clone, Copy)]
+pub struct OutputChunk<'a> {
+    /// Program producing the output.
+    pub program: &'a str,
+    /// Background job id, or `None` for foreground commands.
+    pub job_id: Option<&'a str>,
+    /// Stream the chunk was read from.
+    pub stream: OutputStream,
+    /// Chunk text (invalid UTF-8 is replaced).
+    pub text: &'a str,
 }
 
-#[derive(Debug, Clone)]
+type ObserverFn = dyn for<'a> Fn(&OutputChunk<'a>) + Send + Sync;
+
+/// Callback receiving live output, e.g. to forward it to an agent event stream or UI.
+#[derive(Clone)]
+pub(crate) struct OutputObserver {
+    program: String,
+    callback: Arc<ObserverFn>,
+}
+
+impl OutputObserver {
+    pub(crate) fn emit(&self, job_id: Option<&str>, stream: OutputStream, text: &str) {
+        (self.callback)(&OutputChunk {
+            program: &self.program,
+            job_id,
+            stream,
+            text,
+        });
+    }
+}
+
+#[derive(Clone)]
 pub struct CommandTool {
-    allowed: Option<Vec<String>>,
+    policy: CommandPolicy,
     default_cwd: PathBuf,
     max_output: usize,
+    limits: ResourceLimits,
+    observer: Option<Arc<ObserverFn>>,
+    jobs: JobManager,
     name: String,
 }
 
+impl fmt::Debug for CommandTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandTool")
+            .field("allowed", &self.policy.allowed)
+            .field("rules", &self.policy.rules)
+            .field("default_cwd", &self.default_cwd)
+            .field("max_output", &self.max_output)
+            .field("limits", &self.limits)
+            .field("observer", &self.observer.is_some())
+            .field("jobs", &self.jobs)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
 impl CommandTool {
     pub fn new(default_cwd: impl Into<PathBuf>) -> Self {
         let default_cwd = default_cwd.into();
         Self {
-            allowed: None,
+            policy: CommandPolicy::default(),
             default_cwd,
             max_output: 16 * 1024,
+            limits: ResourceLimits::none(),
+            observer: None,
+            jobs: JobManager::new(),
             name: "command".into(),
         }
     }
 
     pub fn restrict_to(mut self, commands: impl IntoIterator<Item = impl Into<String>>) -> Self {
-        self.allowed = Some(commands.into_iter().map(Into::into).collect());
+        self.policy.allowed = Some(commands.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add an argument-level allow or deny rule. Deny rules take precedence over
+    /// allow rules and over [`restrict_to`](Self::restrict_to).
+    pub fn rule(mut self, rule: CommandRule) -> Self {
+        self.policy.rules.push(rule);
+        self
+    }
+
+    /// Add several rules at once.
+    pub fn rules(mut self, rules: impl IntoIterator<Item = CommandRule>) -> Self {
+        self.policy.rules.extend(rules);
         self
     }
 
+    /// Check whether `program args...` would be allowed, and why, without running it.
+    pub fn explain(&self, program: &str, args: &[String]) -> PolicyVerdict {
+        self.policy.explain(program, args)
+    }
+
     pub fn max_output(mut self, bytes: usize) -> Self {
         self.max_output = bytes;
         self
     }
 
+    /// Kill foreground commands that run longer than `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.limits.max_runtime = Some(timeout);
+        self
+    }
+
+    /// CPU, memory, and runtime limits for every spawned process.
+    ///
+    /// CPU and memory limits are enforced with rlimits on Unix and ignored elsewhere.
+    pub fn limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Receive stdout/stderr chunks as they are produced, for foreground and background commands.
+    pub fn on_output(
+        mut self,
+        observer: impl for<'a> Fn(&OutputChunk<'a>) + Send + Sync + 'static,
+    ) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Background jobs started by this tool.
+    pub fn jobs(&self) -> &JobManager {
+        &self.jobs
+    }
+
+    /// Companion tool for polling and killing this tool's background jobs.
+    pub fn jobs_tool(&self) -> CommandJobsTool {
+        CommandJobsTool::new(self.jobs.clone())
+    }
+
     pub fn named(mut self, name: impl Into<String>) -> Self {
         self.name = name.into();
         self
     }
 
-    fn ensure_allowed(&self, program: &str) -> Result<()> {
-        if let Some(allowed) = &self.allowed
-            && !allowed.iter().any(|entry| entry == program)
-        {
-            bail!(
-                "Program '{program}' is not allowed. Allowed commands: {}",
-                allowed.join(", ")
-            );
+    fn observer_for(&self, program: &str) -> Option<OutputObserver> {
+        self.observer.as_ref().map(|callback| OutputObserver {
+            program: program.to_string(),
+            callback: Arc::clone(callback),
+        })
+    }
+
+    fn ensure_allowed(&self, program: &str, args: &[String]) -> Result<()> {
+        let verdict = self.explain(program, args);
+        if !verdict.allowed {
+            bail!(verdict.reason);
         }
         Ok(())
     }
 
-    fn truncate(&self, text: String) -> String {
+    fn truncate(&self, text: String) -> (String, bool) {
         if text.len() <= self.max_output {
-            return text;
+            return (text, false);
         }
 
+        let mut cut = self.max_output;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
         let mut truncated = text;
-        truncated.truncate(self.max_output);
+        truncated.truncate(cut);
         truncated.push_str("\n...output truncated...");
-        truncated
+        (truncated, true)
+    }
+
+    fn limits_for(&self, arguments: &CommandArgs) -> ResourceLimits {
+        self.limits.tightened(ResourceLimits {
+            cpu_time: arguments.max_cpu_secs.map(Duration::from_secs),
+            memory_bytes: arguments
+                .max_memory_mb
+                .map(|megabytes| megabytes.saturating_mul(1024 * 1024)),
+            max_runtime: arguments.timeout_ms.map(Duration::from_millis),
+        })
     }
 }
 
@@ -98,14 +256,20 @@ impl Tool for CommandTool {
     type Arguments = CommandArgs;
 
     async fn call(&self, arguments: Self::Arguments) -> aither_core::Result<ToolOutput> {
-        self.ensure_allowed(&arguments.program)?;
+        self.ensure_allowed(&arguments.program, &arguments.args)?;
 
+        let limits = self.limits_for(&arguments);
         let working_dir = arguments.cwd.unwrap_or_else(|| self.default_cwd.clone());
-        let output = Command::new(&arguments.program)
+        let mut command = std::process::Command::new(&arguments.program);
+        limits.apply(&mut command);
+        let mut child = Command::from(command)
             .args(&arguments.args)
             .current_dir(&working_dir)
-            .output()
-            .await
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(!arguments.background)
+            .spawn()
             .with_context(|| {
                 format!(
                     "failed to execute '{}' in {}",
@@ -113,14 +277,112 @@ impl Tool for CommandTool {
                     working_dir.display()
                 )
             })?;
+        let observer = self.observer_for(&arguments.program);
 
+        if arguments.background {
+            let job_id = self.jobs.start(
+                arguments.program,
+                arguments.args,
+                child,
+                self.max_output,
+                observer,
+            )?;
+            return Ok(ToolOutput::text(format!(
+                "Started background job {job_id}. Poll it with command_jobs."
+            )));
+        }
+
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let mut stdout_text = String::new();
+        let mut stderr_text = String::new();
+        let run = async {
+            let collect = |sink: &mut String, stream: OutputStream, text: &str| {
+                sink.push_str(text);
+                if let Some(observer) = &observer {
+                    observer.emit(None, stream, text);
+                }
+            };
+            future::zip(
+                pump(stdout, OutputStream::Stdout, |stream, text| {
+                    collect(&mut stdout_text, stream, text);
+                }),
+                pump(stderr, OutputStream::Stderr, |stream, text| {
+                    collect(&mut stderr_text, stream, text);
+                }),
+            )
+            .await;
+            child.status().await
+        };
+        let status = match limits.max_runtime {
+            Some(timeout) => {
+                future::or(async { Some(run.await) }, async {
+                    Timer::after(timeout).await;
+                    None
+                })
+                .await
+            }
+            None => Some(run.await),
+        };
+
+        let (status, limit_exceeded) = match status {
+            Some(status) => {
+                let status = status?;
+                (status.code(), limits.exceeded(status))
+            }
+            None => {
+                child.kill().ok();
+                let status = child.status().await.ok().and_then(|s| s.code());
+                (status, Some(LimitKind::Runtime))
+            }
+        };
+
+        let (stdout, stdout_truncated) = self.truncate(stdout_text);
+        let (stderr, stderr_truncated) = self.truncate(stderr_text);
         let response = CommandOutput {
             program: arguments.program,
-            status: output.status.code().unwrap_or_default(),
-            stdout: self.truncate(String::from_utf8_lossy(&output.stdout).to_string()),
-            stderr: self.truncate(String::from_utf8_lossy(&output.stderr).to_string()),
+            // Processes killed by a signal have no exit code.
+            status: status.unwrap_or(-1),
+            stdout,
+            stderr,
+            limit_exceeded,
+            truncated: stdout_truncated || stderr_truncated,
         };
 
         Ok(ToolOutput::text(json(&response)))
     }
 }
+
+/// Read `reader` to EOF, handing each chunk to `on_chunk` as it arrives.
+pub(crate) async fn pump<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    stream: OutputStream,
+    mut on_chunk: impl FnMut(OutputStream, &str),
+) {
+    let Some(mut reader) = reader else {
+        return;
+    };
+    let mut buffer = [0u8; 8192];
+    // Bytes of a UTF-8 sequence split across reads, carried into the next chunk.
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let read = match reader.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => read,
+        };
+        pending.extend_from_slice(&buffer[..read]);
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(_) => pending.len(),
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            Err(_) => pending.len(),
+        };
+        let text = String::from_utf8_lossy(&pending[..valid]).into_owned();
+        pending.drain(..valid);
+        if !text.is_empty() {
+            on_chunk(stream, &text);
+        }
+    }
+    if !pending.is_empty() {
+        on_chunk(stream, &String::from_utf8_lossy(&pending));
+    }
+}
@@ -0,0 +1,235 @@
+//! Resource limits for spawned commands.
+//!
+//! CPU time and memory are enforced with `setrlimit` in the child before it
+//! execs, so they are only available on Unix; elsewhere they are ignored.
+//! The hook is installed on a [`std::process::Command`], which is then
+//! converted into an `async_process::Command`.
+//! Wall-clock runtime is enforced by the tool itself on every platform.
+
+use std::{process::ExitStatus, time::Duration};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Limits applied to every process a [`CommandTool`](crate::CommandTool) spawns.
+///
+/// Per-call limits requested by the model can only tighten these, never loosen them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum CPU time (`RLIMIT_CPU`).
+    pub cpu_time: Option<Duration>,
+    /// Maximum address-space size in bytes (`RLIMIT_AS`).
+    pub memory_bytes: Option<u64>,
+    /// Maximum wall-clock time for foreground commands.
+    pub max_runtime: Option<Duration>,
+}
+
+/// Which limit stopped a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitKind {
+    /// Wall-clock runtime exceeded; the process was killed.
+    Runtime,
+    /// CPU time exceeded.
+    CpuTime,
+    /// The process died after hitting the memory limit (best effort).
+    Memory,
+    /// The process was killed while both CPU and memory limits were set, and
+    /// either could be responsible.
+    CpuTimeOrMemory,
+}
+
+impl ResourceLimits {
+    /// No limits.
+    pub const fn none() -> Self {
+        Self {
+            cpu_time: None,
+            memory_bytes: None,
+            max_runtime: None,
+        }
+    }
+
+    /// Limit CPU time.
+    #[must_use]
+    pub const fn cpu_time(mut self, limit: Duration) -> Self {
+        self.cpu_time = Some(limit);
+        self
+    }
+
+    /// Limit memory (address space) in bytes.
+    #[must_use]
+    pub const fn memory_bytes(mut self, limit: u64) -> Self {
+        self.memory_bytes = Some(limit);
+        self
+    }
+
+    /// Limit wall-clock runtime.
+    #[must_use]
+    pub const fn max_runtime(mut self, limit: Duration) -> Self {
+        self.max_runtime = Some(limit);
+        self
+    }
+
+    /// Combine with `other`, keeping the stricter value of each limit.
+    #[must_use]
+    pub fn tightened(self, other: Self) -> Self {
+        Self {
+            cpu_time: stricter(self.cpu_time, other.cpu_time),
+            memory_bytes: stricter(self.memory_bytes, other.memory_bytes),
+            max_runtime: stricter(self.max_runtime, other.max_runtime),
+        }
+    }
+
+    /// Install CPU and memory limits in the child before it execs.
+    #[cfg(unix)]
+    pub(crate) fn apply(&self, command: &mut std::process::Command) {
+        use std::os::unix::process::CommandExt;
+
+        if self.cpu_time.is_none() && self.memory_bytes.is_none() {
+            return;
+        }
+        let cpu = self.cpu_time.map(|limit| limit.as_secs().max(1));
+        let memory = self.memory_bytes;
+        // SAFETY: the closure only calls `setrlimit`, which is async-signal-safe.
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(seconds) = cpu {
+                    // Soft limit delivers SIGXCPU; the hard limit one second later kills.
+                    check(libc::setrlimit(
+                        libc::RLIMIT_CPU,
+                        &rlimit(seconds, seconds + 1),
+                    ))?;
+                }
+                if let Some(bytes) = memory {
+                    check(libc::setrlimit(libc::RLIMIT_AS, &rlimit(bytes, bytes)))?;
+                }
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn apply(&self, _command: &mut std::process::Command) {}
+
+    /// Work out which limit, if any, explains how the process exited.
+    pub(crate) fn exceeded(&self, status: ExitStatus) -> Option<LimitKind> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+
+            let cpu = self.cpu_time.is_some();
+            let memory = self.memory_bytes.is_some();
+            match status.signal()? {
+                libc::SIGXCPU if cpu => return Some(LimitKind::CpuTime),
+                // The hard CPU limit and an out-of-memory kill both end in SIGKILL.
+                libc::SIGKILL if cpu && memory => return Some(LimitKind::CpuTimeOrMemory),
+                libc::SIGKILL if cpu => return Some(LimitKind::CpuTime),
+                libc::SIGABRT | libc::SIGSEGV | libc::SIGKILL if memory => {
+                    return Some(LimitKind::Memory);
+                }
+                _ => {}
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = status;
+        None
+    }
+}
+
+fn stricter<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(unix)]
+const fn rlimit(soft: u64, hard: u64) -> libc::rlimit {
+    libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    }
+}
+
+#[cfg(unix)]
+fn check(result: libc::c_int) -> std::io::Result<()> {
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+
+    use aither_core::llm::Tool;
+    use futures_lite::future::block_on;
+
+    use super::*;
+    use crate::{CommandArgs, CommandOutput, CommandTool};
+
+    fn run(limits: ResourceLimits, program: &str, args: &[&str]) -> CommandOutput {
+        let tool = CommandTool::new(std::env::temp_dir()).limits(limits);
+        let output = block_on(tool.call(CommandArgs {
+            program: program.into(),
+            args: args.iter().map(ToString::to_string).collect(),
+            cwd: None,
+            timeout_ms: None,
+            max_cpu_secs: None,
+            max_memory_mb: None,
+            background: false,
+        }))
+        .unwrap();
+        serde_json::from_str(output.as_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn runtime_limit_kills_the_command() {
+        let limits = ResourceLimits::none().max_runtime(Duration::from_millis(200));
+        let output = run(limits, "sleep", &["5"]);
+        assert_eq!(output.limit_exceeded, Some(LimitKind::Runtime));
+    }
+
+    #[test]
+    fn cpu_limit_is_installed_and_reported() {
+        let limits = ResourceLimits::none().cpu_time(Duration::from_secs(3));
+        let output = run(limits, "sh", &["-c", "ulimit -t"]);
+        assert_eq!(output.stdout.trim(), "3");
+
+        let limits = ResourceLimits::none()
+            .cpu_time(Duration::from_secs(1))
+            .max_runtime(Duration::from_secs(30));
+        let output = run(limits, "sh", &["-c", "while :; do :; done"]);
+        assert_eq!(output.limit_exceeded, Some(LimitKind::CpuTime));
+    }
+
+    #[test]
+    fn memory_limit_is_installed_and_reported() {
+        let limits = ResourceLimits::none().memory_bytes(512 * 1024 * 1024);
+        let output = run(limits, "sh", &["-c", "ulimit -v"]);
+        assert_eq!(output.stdout.trim(), "524288");
+
+        assert_eq!(
+            limits.exceeded(ExitStatus::from_raw(libc::SIGABRT)),
+            Some(LimitKind::Memory)
+        );
+        assert_eq!(limits.exceeded(ExitStatus::from_raw(0)), None);
+    }
+
+    #[test]
+    fn sigkill_is_ambiguous_when_both_limits_are_set() {
+        let killed = ExitStatus::from_raw(libc::SIGKILL);
+        let cpu = ResourceLimits::none().cpu_time(Duration::from_secs(1));
+        let memory = ResourceLimits::none().memory_bytes(1024);
+
+        assert_eq!(cpu.exceeded(killed), Some(LimitKind::CpuTime));
+        assert_eq!(memory.exceeded(killed), Some(LimitKind::Memory));
+        assert_eq!(
+            cpu.tightened(memory).exceeded(killed),
+            Some(LimitKind::CpuTimeOrMemory)
+        );
+        assert_eq!(ResourceLimits::none().exceeded(killed), None);
+    }
+}
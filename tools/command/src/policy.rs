@@ -0,0 +1,281 @@
+//! Argument-level allow/deny rules for [`CommandTool`](crate::CommandTool).
+//!
+//! Rules match a program name plus a regex over the argument list joined with
+//! single spaces, so `git push --force` or `rm -rf /` can be refused even when
+//! `git` and `rm` are otherwise allowed. Programs are matched by file name, so
+//! a rule for `git` also covers `/usr/bin/git` and `./git`.
+
+use std::{ffi::OsStr, fmt, path::Path};
+
+use regex::Regex;
+use serde::Serialize;
+
+/// Whether a matching rule permits or refuses the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEffect {
+    /// Permit matching commands.
+    Allow,
+    /// Refuse matching commands.
+    Deny,
+}
+
+/// A single policy rule.
+///
+/// Deny rules are checked first and always win. If a program has any allow
+/// rules, at least one of them must match for the command to run.
+#[derive(Debug, Clone)]
+pub struct CommandRule {
+    program: String,
+    args: Option<Regex>,
+    effect: RuleEffect,
+    reason: Option<String>,
+}
+
+impl CommandRule {
+    /// Refuse `program` when its arguments match `args_pattern`.
+    ///
+    /// Use `"*"` as the program to match every program.
+    ///
+    /// # Errors
+    /// Returns an error if `args_pattern` is not a valid regex.
+    pub fn deny(program: impl Into<String>, args_pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self::new(
+            program,
+            Some(Regex::new(args_pattern)?),
+            RuleEffect::Deny,
+        ))
+    }
+
+    /// Permit `program` only with arguments matching `args_pattern`.
+    ///
+    /// # Errors
+    /// Returns an error if `args_pattern` is not a valid regex.
+    pub fn allow(program: impl Into<String>, args_pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self::new(
+            program,
+            Some(Regex::new(args_pattern)?),
+            RuleEffect::Allow,
+        ))
+    }
+
+    /// Refuse `program` regardless of its arguments.
+    pub fn deny_program(program: impl Into<String>) -> Self {
+        Self::new(program, None, RuleEffect::Deny)
+    }
+
+    /// Explanation shown to the model when this rule rejects a command.
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    fn new(program: impl Into<String>, args: Option<Regex>, effect: RuleEffect) -> Self {
+        Self {
+            program: program.into(),
+            args,
+            effect,
+            reason: None,
+        }
+    }
+
+    fn applies_to(&self, program: &str) -> bool {
+        self.program == "*" || program_name(&self.program) == program_name(program)
+    }
+
+    fn matches(&self, program: &str, args: &str) -> bool {
+        self.applies_to(program) && self.args.as_ref().is_none_or(|re| re.is_match(args))
+    }
+}
+
+/// File name of `program`, so `/usr/bin/git` and `./git` both match `git`.
+fn program_name(program: &str) -> &OsStr {
+    let path = Path::new(program);
+    path.file_name().unwrap_or(path.as_os_str())
+}
+
+impl fmt::Display for CommandRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let effect = match self.effect {
+            RuleEffect::Allow => "allow",
+            RuleEffect::Deny => "deny",
+        };
+        match &self.args {
+            Some(args) => write!(f, "{effect} {} /{}/", self.program, args.as_str()),
+            None => write!(f, "{effect} {}", self.program),
+        }
+    }
+}
+
+/// Outcome of checking a command against the policy, without running it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PolicyVerdict {
+    /// Whether the command would run.
+    pub allowed: bool,
+    /// The rule that decided the outcome, if any (e.g. `deny git /--force/`).
+    pub rule: Option<String>,
+    /// Human-readable explanation.
+    pub reason: String,
+}
+
+/// Ordered rule set plus the optional program allowlist from
+/// [`CommandTool::restrict_to`](crate::CommandTool::restrict_to).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CommandPolicy {
+    pub(crate) allowed: Option<Vec<String>>,
+    pub(crate) rules: Vec<CommandRule>,
+}
+
+impl CommandPolicy {
+    pub(crate) fn explain(&self, program: &str, args: &[String]) -> PolicyVerdict {
+        let joined = args.join(" ");
+
+        if let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| rule.effect == RuleEffect::Deny && rule.matches(program, &joined))
+        {
+            let reason = rule.reason.clone().unwrap_or_else(|| {
+                let command = if joined.is_empty() {
+                    program.to_string()
+                } else {
+                    format!("{program} {joined}")
+                };
+                format!("'{command}' matches a deny rule")
+            });
+            return PolicyVerdict {
+                allowed: false,
+                rule: Some(rule.to_string()),
+                reason,
+            };
+        }
+
+        if let Some(allowed) = &self.allowed
+            && !allowed.iter().any(|entry| entry == program)
+        {
+            return PolicyVerdict {
+                allowed: false,
+                rule: None,
+                reason: format!(
+                    "Program '{program}' is not allowed. Allowed commands: {}",
+                    allowed.join(", ")
+                ),
+            };
+        }
+
+        let mut allow_rules = self
+            .rules
+            .iter()
+            .filter(|rule| rule.effect == RuleEffect::Allow && rule.applies_to(program))
+            .peekable();
+        if allow_rules.peek().is_some() {
+            let patterns: Vec<String> = allow_rules.clone().map(ToString::to_string).collect();
+            return match allow_rules.find(|rule| rule.matches(program, &joined)) {
+                Some(rule) => PolicyVerdict {
+                    allowed: true,
+                    rule: Some(rule.to_string()),
+                    reason: format!("'{program}' arguments match an allow rule"),
+                },
+                None => PolicyVerdict {
+                    allowed: false,
+                    rule: None,
+                    reason: format!(
+                        "Arguments for '{program}' match no allow rule. Permitted: {}",
+                        patterns.join("; ")
+                    ),
+                },
+            };
+        }
+
+        PolicyVerdict {
+            allowed: true,
+            rule: None,
+            reason: format!("No rule restricts '{program}'"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(rules: impl IntoIterator<Item = CommandRule>) -> CommandPolicy {
+        CommandPolicy {
+            allowed: None,
+            rules: rules.into_iter().collect(),
+        }
+    }
+
+    fn allowed(policy: &CommandPolicy, program: &str, args: &[&str]) -> bool {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        policy.explain(program, &args).allowed
+    }
+
+    #[test]
+    fn deny_rules_refuse_matching_arguments() {
+        let policy = policy([CommandRule::deny("git", r"push.*--force")
+            .unwrap()
+            .with_reason("force pushes are not allowed")]);
+
+        let verdict = policy.explain("git", &["push".into(), "--force".into()]);
+        assert!(!verdict.allowed);
+        assert_eq!(verdict.rule.as_deref(), Some("deny git /push.*--force/"));
+        assert_eq!(verdict.reason, "force pushes are not allowed");
+        assert!(allowed(&policy, "git", &["push"]));
+        assert!(allowed(&policy, "ls", &["--force"]));
+    }
+
+    #[test]
+    fn deny_program_and_wildcard_refuse_everything() {
+        let policy = policy([
+            CommandRule::deny_program("curl"),
+            CommandRule::deny("*", r"^-rf /$").unwrap(),
+        ]);
+
+        assert!(!allowed(&policy, "curl", &[]));
+        assert!(!allowed(&policy, "rm", &["-rf", "/"]));
+        assert!(allowed(&policy, "rm", &["-rf", "target"]));
+    }
+
+    #[test]
+    fn allow_rules_restrict_a_program_to_matching_arguments() {
+        let policy = policy([
+            CommandRule::allow("cargo", r"^(build|test)\b").unwrap(),
+            CommandRule::deny("cargo", r"--release").unwrap(),
+        ]);
+
+        assert!(allowed(&policy, "cargo", &["test", "--workspace"]));
+        assert!(!allowed(&policy, "cargo", &["publish"]));
+        assert!(!allowed(&policy, "cargo", &["build", "--release"]));
+        assert!(allowed(&policy, "make", &["all"]));
+    }
+
+    #[test]
+    fn path_qualified_programs_match_rules_by_file_name() {
+        let policy = policy([
+            CommandRule::deny("git", r"--force").unwrap(),
+            CommandRule::deny_program("/bin/curl"),
+            CommandRule::allow("cargo", r"^test\b").unwrap(),
+        ]);
+
+        for git in ["git", "/usr/bin/git", "./git", "../bin/git"] {
+            assert!(!allowed(&policy, git, &["push", "--force"]), "{git}");
+            assert!(allowed(&policy, git, &["status"]), "{git}");
+        }
+        assert!(!allowed(&policy, "curl", &[]));
+        assert!(!allowed(&policy, "/usr/local/bin/curl", &[]));
+        assert!(!allowed(&policy, "/usr/bin/cargo", &["publish"]));
+        assert!(allowed(&policy, "/usr/bin/cargo", &["test"]));
+    }
+
+    #[test]
+    fn allowlist_requires_an_exact_program() {
+        let mut policy = policy([]);
+        policy.allowed = Some(vec!["git".into()]);
+
+        assert!(allowed(&policy, "git", &["status"]));
+        let verdict = policy.explain("./git", &[]);
+        assert!(!verdict.allowed);
+        assert!(verdict.reason.contains("Allowed commands: git"));
+    }
+}
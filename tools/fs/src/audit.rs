@@ -0,0 +1,101 @@
+//! Symlink policy and audit log for accesses blocked by [`LocalFileSystem`](crate::LocalFileSystem).
+
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// How [`LocalFileSystem`](crate::LocalFileSystem) treats symbolic links inside its root.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Follow symlinks whose target stays inside the root; refuse the rest.
+    #[default]
+    WithinRoot,
+    /// Refuse any path that passes through a symlink, even one pointing inside the root.
+    Deny,
+}
+
+/// Why an access was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockReason {
+    /// The path (or a symlink along it) resolves outside the root.
+    EscapesRoot,
+    /// The path passes through a symlink and [`SymlinkPolicy::Deny`] is active.
+    Symlink,
+    /// The path passes through a symlink whose target does not exist.
+    DanglingSymlink,
+}
+
+impl fmt::Display for BlockReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::EscapesRoot => "path escapes the filesystem root",
+            Self::Symlink => "symlinks are not allowed",
+            Self::DanglingSymlink => "path goes through a dangling symlink",
+        })
+    }
+}
+
+/// A refused filesystem access.
+#[derive(Debug, Clone)]
+pub struct BlockedAccess {
+    /// Path as requested, relative to the root.
+    pub path: PathBuf,
+    /// Why it was refused.
+    pub reason: BlockReason,
+    /// When it happened.
+    pub at: SystemTime,
+}
+
+type Observer = Arc<dyn Fn(&BlockedAccess) + Send + Sync>;
+
+/// Log of accesses refused by the root jail.
+///
+/// Cloning is cheap; clones share the same log. Register an observer with
+/// [`AccessAudit::on_blocked`] to forward refusals to an agent hook or UI as they happen.
+#[derive(Clone, Default)]
+pub struct AccessAudit {
+    blocked: Arc<Mutex<Vec<BlockedAccess>>>,
+    observer: Option<Observer>,
+}
+
+impl AccessAudit {
+    /// Create an empty audit log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `observer` for every blocked access.
+    pub fn on_blocked(mut self, observer: impl Fn(&BlockedAccess) + Send + Sync + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// All blocked accesses so far, oldest first.
+    pub fn blocked(&self) -> Vec<BlockedAccess> {
+        self.blocked.lock().unwrap().clone()
+    }
+
+    pub(crate) fn record(&self, path: PathBuf, reason: BlockReason) {
+        let access = BlockedAccess {
+            path,
+            reason,
+            at: SystemTime::now(),
+        };
+        if let Some(observer) = &self.observer {
+            observer(&access);
+        }
+        self.blocked.lock().unwrap().push(access);
+    }
+}
+
+impl fmt::Debug for AccessAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessAudit")
+            .field("blocked", &self.blocked.lock().unwrap().len())
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,94 @@
+//! Minimal `.gitignore`-style matching used by directory listings and
+//! anything else that walks a project tree.
+
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+/// Ignore files consulted in every listed directory.
+pub const IGNORE_FILES: &[&str] = &[".gitignore", ".aitherignore"];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Accumulated ignore rules, evaluated in order so later rules (and `!` negations) win.
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+#[derive(Debug)]
+struct IgnoreRule {
+    /// Directory containing the ignore file, relative to the filesystem root.
+    base: PathBuf,
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Whether the pattern is matched against the path relative to `base`
+    /// rather than just the file name.
+    anchored: bool,
+}
+
+impl IgnoreRules {
+    /// Add the rules from an ignore file located in `base`.
+    pub fn extend(&mut self, base: &Path, contents: &str) {
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = line
+                .strip_prefix('!')
+                .map_or((false, line), |rest| (true, rest));
+            let (dir_only, line) = line
+                .strip_suffix('/')
+                .map_or((false, line), |rest| (true, rest));
+            let anchored = line.contains('/');
+            let line = line.trim_start_matches('/');
+            let Ok(pattern) = Pattern::new(line) else {
+                continue;
+            };
+            self.rules.push(IgnoreRule {
+                base: base.to_path_buf(),
+                pattern,
+                negated,
+                dir_only,
+                anchored,
+            });
+        }
+    }
+
+    /// Whether `path` (relative to the filesystem root) is ignored.
+    #[must_use]
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.matches(path, is_dir) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+impl IgnoreRule {
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Ok(relative) = path.strip_prefix(&self.base) else {
+            return false;
+        };
+        if self.anchored {
+            self.pattern.matches_path_with(relative, MATCH_OPTIONS)
+        } else {
+            relative.file_name().is_some_and(|name| {
+                self.pattern
+                    .matches_with(&name.to_string_lossy(), MATCH_OPTIONS)
+            })
+        }
+    }
+}
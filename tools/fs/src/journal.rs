@@ -0,0 +1,350 @@
+//! Per-run change journal backing [`FsOperation::Undo`](crate::FsOperation::Undo).
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use crate::FileSystem;
+
+/// Kind of change recorded in a [`ChangeJournal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// File written (created or overwritten).
+    Write,
+    /// Content appended to a file.
+    Append,
+    /// Exact-match edit.
+    Edit,
+    /// File or directory tree deleted.
+    Delete,
+    /// File copied over its destination.
+    Copy,
+    /// File or directory moved, including moves into the trash.
+    Move,
+    /// Directory created.
+    CreateDir,
+}
+
+/// Contents of a path captured before a change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Snapshot {
+    /// A file and its raw bytes.
+    File(Vec<u8>),
+    /// A directory tree: every entry below it by relative path, parents before
+    /// their children, with file bytes or `None` for directories.
+    Dir(Vec<(PathBuf, Option<Vec<u8>>)>),
+}
+
+impl Snapshot {
+    /// Capture what is at `path`, or `None` if nothing is.
+    ///
+    /// # Errors
+    /// Fails if `path` cannot be read, or with [`io::ErrorKind::FileTooLarge`] once the
+    /// captured bytes exceed `limit`.
+    pub async fn capture<FS: FileSystem>(
+        filesystem: &FS,
+        path: &Path,
+        limit: u64,
+    ) -> io::Result<Option<Self>> {
+        let metadata = match filesystem.metadata(path).await {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        if !metadata.is_dir {
+            if metadata.size > limit {
+                return Err(too_large(path, limit));
+            }
+            return Ok(Some(Self::File(filesystem.read_bytes(path).await?)));
+        }
+
+        let mut entries = Vec::new();
+        let mut total = 0_u64;
+        for (relative, size) in walk(filesystem, path).await? {
+            let contents = match size {
+                Some(size) => {
+                    total = total.saturating_add(size);
+                    if total > limit {
+                        return Err(too_large(path, limit));
+                    }
+                    Some(filesystem.read_bytes(&path.join(&relative)).await?)
+                }
+                None => None,
+            };
+            entries.push((relative, contents));
+        }
+        Ok(Some(Self::Dir(entries)))
+    }
+
+    /// Cheap fingerprint matching [`fingerprint`] of the captured state.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            Self::File(bytes) => bytes.hash(&mut hasher),
+            Self::Dir(entries) => {
+                for (relative, contents) in entries {
+                    (relative, contents.as_ref().map(|bytes| bytes.len() as u64)).hash(&mut hasher);
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Recreate the captured state at `path`, which must not exist.
+    async fn restore<FS: FileSystem>(&self, filesystem: &FS, path: &Path) -> io::Result<()> {
+        match self {
+            Self::File(bytes) => filesystem.write_bytes(path, bytes.clone()).await,
+            Self::Dir(entries) => {
+                filesystem.create_dir(path).await?;
+                for (relative, contents) in entries {
+                    let target = path.join(relative);
+                    match contents {
+                        Some(bytes) => filesystem.write_bytes(&target, bytes.clone()).await?,
+                        None => filesystem.create_dir(&target).await?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Fingerprint of what is at `path`, or `None` if nothing is.
+///
+/// Files are hashed by content; directories by the names and sizes of everything
+/// below them, so checking a large tree doesn't read it.
+pub(crate) async fn fingerprint<FS: FileSystem>(
+    filesystem: &FS,
+    path: &Path,
+) -> io::Result<Option<u64>> {
+    let metadata = match filesystem.metadata(path).await {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let mut hasher = DefaultHasher::new();
+    if metadata.is_dir {
+        for entry in walk(filesystem, path).await? {
+            entry.hash(&mut hasher);
+        }
+    } else {
+        filesystem.read_bytes(path).await?.hash(&mut hasher);
+    }
+    Ok(Some(hasher.finish()))
+}
+
+/// Every entry below the directory `root`, parents first and siblings by name,
+/// with file sizes or `None` for directories.
+async fn walk<FS: FileSystem>(
+    filesystem: &FS,
+    root: &Path,
+) -> io::Result<Vec<(PathBuf, Option<u64>)>> {
+    let mut entries = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(dir) = pending.pop() {
+        let mut children = filesystem.list_dir(&root.join(&dir)).await?;
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        for child in children {
+            let relative = dir.join(&child.name);
+            if child.is_dir {
+                pending.push(relative.clone());
+                entries.push((relative, None));
+            } else {
+                entries.push((relative, Some(child.size.unwrap_or(0))));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+fn too_large(path: &Path, limit: u64) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::FileTooLarge,
+        format!(
+            "'{}' holds more than {limit} bytes and cannot be backed up",
+            path.display()
+        ),
+    )
+}
+
+/// A single journaled change.
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    /// Path of the changed file or directory, relative to the filesystem root.
+    /// For moves, the destination.
+    pub path: PathBuf,
+    /// For moves, where the entry came from.
+    pub source: Option<PathBuf>,
+    /// What kind of change was made.
+    pub kind: ChangeKind,
+    /// What was at `path` before the change, or `None` if nothing was.
+    pub backup: Option<Snapshot>,
+    /// Fingerprint of `backup`.
+    pub before_hash: Option<u64>,
+    /// Fingerprint of `path` right after the change, or `None` if it no longer exists.
+    pub after_hash: Option<u64>,
+    /// When the change was made.
+    pub at: SystemTime,
+}
+
+impl JournalEntry {
+    fn touches(&self, path: &Path) -> bool {
+        self.path == path || self.source.as_deref() == Some(path)
+    }
+}
+
+/// Records every change made through a [`FileSystemTool`](crate::FileSystemTool) so it can be undone.
+///
+/// Cloning is cheap; clones share the same journal.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeJournal {
+    entries: Arc<Mutex<Vec<JournalEntry>>>,
+}
+
+impl ChangeJournal {
+    /// Create an empty journal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of all recorded changes, oldest first.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Paths changed during the run, in first-change order.
+    pub fn changed_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for entry in self.entries.lock().unwrap().iter() {
+            for path in entry.source.iter().chain([&entry.path]) {
+                if !paths.contains(path) {
+                    paths.push(path.clone());
+                }
+            }
+        }
+        paths
+    }
+
+    /// Forget all recorded changes, starting a new run.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Record a change to `path`, moved there from `source` if set. `backup` is what
+    /// was at `path` before; `after_hash` is its [`fingerprint`] now.
+    pub(crate) fn record(
+        &self,
+        kind: ChangeKind,
+        path: &Path,
+        source: Option<&Path>,
+        backup: Option<Snapshot>,
+        after_hash: Option<u64>,
+    ) {
+        self.entries.lock().unwrap().push(JournalEntry {
+            path: path.to_path_buf(),
+            source: source.map(Path::to_path_buf),
+            kind,
+            before_hash: backup.as_ref().map(Snapshot::fingerprint),
+            after_hash,
+            backup,
+            at: SystemTime::now(),
+        });
+    }
+
+    /// Revert the most recent change to `path`, or the most recent move away from it.
+    ///
+    /// # Errors
+    /// Fails if `path` has no journaled changes, if the file was modified outside the
+    /// tool since that change, or if restoring it fails. The entry is kept on failure.
+    pub async fn undo<FS: FileSystem>(
+        &self,
+        filesystem: &FS,
+        path: &Path,
+    ) -> io::Result<JournalEntry> {
+        let entry = {
+            let entries = self.entries.lock().unwrap();
+            entries
+                .iter()
+                .rev()
+                .find(|entry| entry.touches(path))
+                .cloned()
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("No recorded changes for '{}'", path.display()),
+                    )
+                })?
+        };
+
+        restore(filesystem, &entry).await?;
+
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(index) = entries
+            .iter()
+            .rposition(|candidate| candidate.touches(path))
+        {
+            entries.remove(index);
+        }
+        Ok(entry)
+    }
+
+    /// Revert every recorded change, newest first, and clear the journal.
+    ///
+    /// Returns the restored paths.
+    ///
+    /// # Errors
+    /// Stops at the first file that was modified outside the tool or cannot be restored;
+    /// changes not yet reverted stay in the journal.
+    pub async fn revert_all<FS: FileSystem>(&self, filesystem: &FS) -> io::Result<Vec<PathBuf>> {
+        let mut reverted: Vec<PathBuf> = Vec::new();
+        loop {
+            let Some(entry) = self.entries.lock().unwrap().last().cloned() else {
+                break;
+            };
+            restore(filesystem, &entry).await?;
+            self.entries.lock().unwrap().pop();
+            let restored = entry.source.unwrap_or(entry.path);
+            if !reverted.contains(&restored) {
+                reverted.push(restored);
+            }
+        }
+        Ok(reverted)
+    }
+}
+
+/// Undo `entry` after checking its path still holds what the tool left there.
+async fn restore<FS: FileSystem>(filesystem: &FS, entry: &JournalEntry) -> io::Result<()> {
+    let current = fingerprint(filesystem, &entry.path).await?;
+    if current != entry.after_hash {
+        return Err(io::Error::other(format!(
+            "'{}' was modified after the recorded change; refusing to undo",
+            entry.path.display()
+        )));
+    }
+
+    if let Some(source) = &entry.source {
+        if fingerprint(filesystem, source).await?.is_some() {
+            return Err(io::Error::other(format!(
+                "'{}' exists again; refusing to move '{}' back over it",
+                source.display(),
+                entry.path.display()
+            )));
+        }
+        filesystem.rename(&entry.path, source).await?;
+    } else if current.is_some() {
+        if filesystem.metadata(&entry.path).await?.is_dir {
+            filesystem.remove_dir_all(&entry.path).await?;
+        } else {
+            filesystem.remove_file(&entry.path).await?;
+        }
+    }
+
+    match &entry.backup {
+        Some(backup) => backup.restore(filesystem, &entry.path).await,
+        None => Ok(()),
+    }
+}
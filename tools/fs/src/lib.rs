@@ -1,22 +1,37 @@
+mod audit;
+mod ignore;
+mod journal;
+mod lines;
+
 use std::{
     borrow::Cow,
     collections::BTreeMap,
+    ffi::OsString,
     fmt, fs,
     future::Future,
     io,
     path::{Component, Path, PathBuf},
     sync::{Arc, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use aither_core::llm::{Tool, ToolOutput, tool::json};
 use anyhow::{Result, anyhow};
 use async_fs::{
-    OpenOptions, create_dir_all, read_dir, read_to_string, remove_dir, remove_file, write,
+    File, create_dir_all, read_dir, remove_dir, remove_dir_all, remove_file, rename,
+    symlink_metadata,
 };
-use futures_lite::{AsyncWriteExt, StreamExt};
+use futures_lite::{AsyncReadExt, AsyncWriteExt, StreamExt, io::BufReader};
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub use crate::audit::{AccessAudit, BlockReason, BlockedAccess, SymlinkPolicy};
+pub use crate::ignore::{IGNORE_FILES, IgnoreRules};
+use crate::journal::fingerprint;
+pub use crate::journal::{ChangeJournal, ChangeKind, JournalEntry, Snapshot};
+pub use crate::lines::LineWindow;
+
 /// Abstract filesystem interface for agent tools.
 ///
 /// Implementors can provide virtual or real filesystem access.
@@ -46,29 +61,212 @@ pub trait FileSystem: Send + Sync + 'static {
     fn create_dir<'a>(&'a self, dir: &'a Path) -> impl Future<Output = io::Result<()>> + Send + 'a;
     fn remove_dir<'a>(&'a self, dir: &'a Path) -> impl Future<Output = io::Result<()>> + Send + 'a;
 
+    /// Read raw file bytes.
+    ///
+    /// The default implementation reads the file as text.
+    fn read_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Future<Output = io::Result<Vec<u8>>> + Send + 'a {
+        async move { self.read_file(path).await.map(String::into_bytes) }
+    }
+
+    /// Write raw file bytes, creating or overwriting the file.
+    ///
+    /// The default implementation writes the bytes as text and rejects invalid UTF-8.
+    fn write_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        contents: Vec<u8>,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        async move {
+            let contents = String::from_utf8(contents)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            self.write_file(path, contents).await
+        }
+    }
+
+    /// Read at most `len` bytes from the start of a file.
+    ///
+    /// The default implementation reads the whole file and truncates it.
+    fn read_head<'a>(
+        &'a self,
+        path: &'a Path,
+        len: usize,
+    ) -> impl Future<Output = io::Result<Vec<u8>>> + Send + 'a {
+        async move {
+            let mut bytes = self.read_bytes(path).await?;
+            bytes.truncate(len);
+            Ok(bytes)
+        }
+    }
+
+    /// Read up to `limit` lines starting at the 0-based line `start`.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the lines are not UTF-8.
+    /// The default implementation reads the whole file.
+    fn read_lines<'a>(
+        &'a self,
+        path: &'a Path,
+        start: usize,
+        limit: usize,
+    ) -> impl Future<Output = io::Result<LineWindow>> + Send + 'a {
+        async move {
+            let content = self.read_file(path).await?;
+            Ok(LineWindow::from_text(&content, start, limit))
+        }
+    }
+
+    /// Read the last `count` lines of a file.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the lines are not UTF-8.
+    /// The default implementation reads the whole file.
+    fn read_last_lines<'a>(
+        &'a self,
+        path: &'a Path,
+        count: usize,
+    ) -> impl Future<Output = io::Result<LineWindow>> + Send + 'a {
+        async move {
+            let content = self.read_file(path).await?;
+            Ok(LineWindow::last_of(&content, count))
+        }
+    }
+
+    /// Size and type information for a path.
+    ///
+    /// The default implementation reads the file to measure it.
+    fn metadata<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Future<Output = io::Result<FileMetadata>> + Send + 'a {
+        async move {
+            let contents = self.read_file(path).await?;
+            Ok(FileMetadata {
+                size: contents.len() as u64,
+                is_dir: false,
+                modified: None,
+            })
+        }
+    }
+
+    /// Remove a directory and everything beneath it.
+    ///
+    /// The default implementation delegates to [`FileSystem::remove_dir`].
+    fn remove_dir_all<'a>(
+        &'a self,
+        dir: &'a Path,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        self.remove_dir(dir)
+    }
+
+    /// Move or rename a file.
+    ///
+    /// The default implementation copies the contents and removes the source.
+    fn rename<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        async move {
+            let contents = self.read_bytes(from).await?;
+            self.write_bytes(to, contents).await?;
+            self.remove_file(from).await
+        }
+    }
+
+    /// Copy a file, overwriting the destination.
+    fn copy_file<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        async move {
+            let contents = self.read_bytes(from).await?;
+            self.write_bytes(to, contents).await
+        }
+    }
+
     /// Find files matching a glob pattern. Returns relative paths.
     fn glob(&self, pattern: &str) -> io::Result<Vec<String>> {
         // Default implementation returns empty - override for real filesystems
         let _ = pattern;
         Ok(Vec::new())
     }
+
+    /// Search text files under `path` for lines matching `pattern`.
+    ///
+    /// Returns at most `max_results` matches, each with up to `context` surrounding lines.
+    fn grep(
+        &self,
+        pattern: &Regex,
+        path: &Path,
+        max_results: usize,
+        context: usize,
+    ) -> io::Result<Vec<GrepMatch>> {
+        // Default implementation returns empty - override for real filesystems
+        let _ = (pattern, path, max_results, context);
+        Ok(Vec::new())
+    }
 }
 
-/// File system operations: read, write, append, delete, list.
+/// Deepest recursion allowed for [`FsOperation::List`].
+const MAX_LIST_DEPTH: usize = 10;
+/// Maximum number of entries returned by one [`FsOperation::List`].
+const MAX_LIST_ENTRIES: usize = 1000;
+/// Default limit for full (non-ranged) reads.
+const DEFAULT_MAX_READ_BYTES: u64 = 256 * 1024;
+/// Number of leading bytes inspected when sniffing for binary content.
+const BINARY_SNIFF_BYTES: usize = 8000;
+/// Default number of lines returned by [`FsOperation::Head`] and [`FsOperation::Tail`].
+const DEFAULT_HEAD_TAIL_LINES: usize = 20;
+/// Default number of matches returned by [`FsOperation::Grep`].
+const DEFAULT_GREP_RESULTS: usize = 100;
+/// Upper bound on context lines around a grep match.
+const MAX_GREP_CONTEXT: usize = 5;
+/// Files larger than this are skipped by grep.
+const MAX_GREP_FILE_SIZE: u64 = 1024 * 1024;
+/// Matched lines longer than this are truncated in grep output.
+const MAX_GREP_LINE_CHARS: usize = 300;
+/// Directories never descended into by grep.
+const GREP_SKIP_DIRS: &[&str] = &["node_modules", "target"];
+
+/// File system operations: read, write, edit, move, copy, delete, list, and search.
 ///
 /// Provides direct filesystem access within the sandbox. All operations
 /// respect the sandbox permission model - writes go to the sandbox directory
 /// unless running in unsafe mode.
 ///
-/// Large file reads may be automatically truncated or saved to a separate
-/// file. Use standard Unix tools to process large files in parts.
+/// Large files can be paged through with `Read`'s `offset`/`limit`, or
+/// sampled with `Head` and `Tail`.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "operation", rename_all = "snake_case")]
 pub enum FsOperation {
-    /// Read entire file contents.
+    /// Read file contents, optionally a range of lines.
+    /// Ranged reads end with a "[Lines X-Y of N]" footer, without "of N" when the file
+    /// goes on past the range; continue with offset = Y + 1.
     Read {
         /// Relative path to the file (e.g., "src/main.rs", "data/config.json").
         path: String,
+        /// 1-based line to start reading from. Omit to start at the beginning.
+        offset: Option<usize>,
+        /// Maximum number of lines to return. Omit to read to the end.
+        limit: Option<usize>,
+    },
+    /// Read the first lines of a file.
+    Head {
+        /// Relative path to the file.
+        path: String,
+        /// Number of lines to return (default 20).
+        lines: Option<usize>,
+    },
+    /// Read the last lines of a file.
+    /// Ends with a "[Lines X-Y of N]" footer, or "[Last K lines]" when only the end of
+    /// the file had to be read.
+    Tail {
+        /// Relative path to the file.
+        path: String,
+        /// Number of lines to return (default 20).
+        lines: Option<usize>,
     },
     /// Write content to a file, creating it if needed, overwriting if exists.
     Write {
@@ -77,6 +275,19 @@ pub enum FsOperation {
         /// Text content to write.
         content: String,
     },
+    /// Replace an exact snippet of text in a file.
+    /// Prefer this over `write` for changing existing files: include enough surrounding
+    /// lines in `old_text` to make it unique.
+    Edit {
+        /// Relative path to the file.
+        path: String,
+        /// Exact text to replace, including whitespace and indentation.
+        old_text: String,
+        /// Replacement text.
+        new_text: String,
+        /// Which match to replace (1-based) when `old_text` occurs more than once.
+        occurrence: Option<usize>,
+    },
     /// Append content to the end of an existing file.
     Append {
         /// Relative path to the file.
@@ -84,15 +295,51 @@ pub enum FsOperation {
         /// Text content to append.
         content: String,
     },
-    /// Delete a file.
+    /// Delete a file, or a directory and everything in it when `recursive` is set.
     Delete {
-        /// Relative path to the file to delete.
+        /// Relative path to the file or directory to delete.
+        path: String,
+        /// Must be true to delete a directory and all of its contents.
+        recursive: Option<bool>,
+        /// Move the target into `.trash/` instead of deleting it permanently.
+        trash: Option<bool>,
+    },
+    /// Move or rename a file or directory.
+    Move {
+        /// Relative path to move from.
+        from: String,
+        /// Relative destination path. Parent directories are created as needed.
+        to: String,
+    },
+    /// Copy a file.
+    Copy {
+        /// Relative path of the file to copy.
+        from: String,
+        /// Relative destination path. An existing file is overwritten.
+        to: String,
+    },
+    /// Revert the most recent write, append, edit, delete, move, copy, or directory
+    /// creation of a path made in this session. Call repeatedly to step further back.
+    Undo {
+        /// Relative path of the file or directory to restore.
+        path: String,
+    },
+    /// Create a directory and any missing parents.
+    CreateDir {
+        /// Relative path of the directory to create.
         path: String,
     },
     /// List directory contents.
+    /// Hidden entries and paths matched by `.gitignore`/`.aitherignore` are skipped by default.
     List {
         /// Relative path to directory. Omit or use empty string for current directory.
         path: Option<String>,
+        /// How many levels to descend (default 1 = direct children only, max 10).
+        depth: Option<usize>,
+        /// Include entries whose names start with "." (default false).
+        include_hidden: Option<bool>,
+        /// Include entries matched by ignore files (default false).
+        include_ignored: Option<bool>,
     },
     /// Find files matching a glob pattern recursively.
     /// Examples: "**/*.rs" finds all Rust files, "src/**/*.json" finds JSON in src.
@@ -100,18 +347,75 @@ pub enum FsOperation {
         /// Glob pattern to match (e.g., "**/*.rs", "src/**/*.ts", "*.md").
         pattern: String,
     },
+    /// Search file contents recursively with a regular expression (ripgrep-style).
+    /// Output lines look like "path:line:text"; context lines use "-" instead of ":".
+    Grep {
+        /// Regular expression to search for (Rust regex syntax, e.g. "fn\s+main", "TODO|FIXME").
+        pattern: String,
+        /// File or directory to search. Omit to search from the root.
+        path: Option<String>,
+        /// Maximum number of matching lines to return (default 100).
+        max_results: Option<usize>,
+        /// Lines of context to show before and after each match (default 0, max 5).
+        context: Option<usize>,
+    },
+}
+
+/// A line matched by [`FileSystem::grep`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GrepMatch {
+    /// Path of the file, relative to the filesystem root.
+    pub path: String,
+    /// 1-based line number of the match.
+    pub line: usize,
+    /// Matched line.
+    pub text: String,
+    /// Lines immediately before the match.
+    pub before: Vec<String>,
+    /// Lines immediately after the match.
+    pub after: Vec<String>,
+}
+
+/// Size and type information returned by [`FileSystem::metadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMetadata {
+    /// Size in bytes.
+    pub size: u64,
+    /// Whether the path is a directory.
+    pub is_dir: bool,
+    /// Last modification time, when known.
+    pub modified: Option<SystemTime>,
+}
+
+/// Returned instead of contents when a read targets a binary file.
+#[derive(Debug, Clone, Serialize)]
+struct BinaryFileInfo<'a> {
+    path: &'a str,
+    binary: bool,
+    size: u64,
+    mime: &'static str,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DirEntry {
+    /// Entry name; for recursive listings, the path relative to the listed directory.
     pub name: String,
     pub is_dir: bool,
+    /// File size in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Last modification time in seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
 }
 
 #[derive(Clone)]
 pub struct FileSystemTool<FS> {
     filesystem: FS,
     allow_writes: bool,
+    trash_deletes: bool,
+    max_read_bytes: u64,
+    journal: ChangeJournal,
     name: String,
 }
 
@@ -146,6 +450,9 @@ impl<FS: FileSystem> FileSystemTool<FS> {
         Self {
             filesystem: fs,
             allow_writes: true,
+            trash_deletes: false,
+            max_read_bytes: DEFAULT_MAX_READ_BYTES,
+            journal: ChangeJournal::new(),
             name: "filesystem".into(),
         }
     }
@@ -155,6 +462,34 @@ impl<FS: FileSystem> FileSystemTool<FS> {
         self
     }
 
+    /// Make `Delete` move targets into `.trash/` unless the call opts out.
+    pub fn trash_deletes(mut self, trash: bool) -> Self {
+        self.trash_deletes = trash;
+        self
+    }
+
+    /// Largest file `Read` returns in full; bigger files need `offset`/`limit`.
+    pub fn max_read_bytes(mut self, bytes: u64) -> Self {
+        self.max_read_bytes = bytes;
+        self
+    }
+
+    /// Record changes into `journal` instead of a private one, e.g. to share it across tools.
+    pub fn with_journal(mut self, journal: ChangeJournal) -> Self {
+        self.journal = journal;
+        self
+    }
+
+    /// Journal of every change made through this tool.
+    pub fn journal(&self) -> &ChangeJournal {
+        &self.journal
+    }
+
+    /// Revert every journaled change, newest first. Returns the restored paths.
+    pub async fn revert_all(&self) -> io::Result<Vec<PathBuf>> {
+        self.journal.revert_all(&self.filesystem).await
+    }
+
     pub fn named(mut self, name: impl Into<String>) -> Self {
         self.name = name.into();
         self
@@ -167,6 +502,205 @@ impl<FS: FileSystem> FileSystemTool<FS> {
             Err(anyhow!("Filesystem tool is read-only"))
         }
     }
+
+    /// Capture what is at `path` before changing it.
+    ///
+    /// Returns `Some(None)` if nothing exists there yet, and `None` if it
+    /// cannot be read (in which case the change is not journaled).
+    async fn snapshot(&self, path: &Path) -> Option<Option<Snapshot>> {
+        Snapshot::capture(&self.filesystem, path, u64::MAX)
+            .await
+            .ok()
+    }
+
+    /// Journal a change to `path` (moved from `source`, if set) whose prior state
+    /// was captured by [`Self::snapshot`].
+    async fn journal_change(
+        &self,
+        kind: ChangeKind,
+        path: &Path,
+        source: Option<&Path>,
+        before: Option<Option<Snapshot>>,
+    ) {
+        if let Some(backup) = before {
+            let after = fingerprint(&self.filesystem, path).await.unwrap_or(None);
+            self.journal.record(
+                kind,
+                &journal_key(path),
+                source.map(journal_key).as_deref(),
+                backup,
+                after,
+            );
+        }
+    }
+
+    /// List `dir`, descending `options.depth` levels and applying the hidden/ignore policy.
+    ///
+    /// Returns the entries sorted by path and whether the listing hit [`MAX_LIST_ENTRIES`].
+    async fn list_tree(&self, dir: &Path, options: ListOptions) -> Result<(Vec<DirEntry>, bool)> {
+        let mut rules = IgnoreRules::default();
+        if !options.include_ignored {
+            // Rules from ancestors apply to everything below them.
+            let ancestors: Vec<&Path> = dir.ancestors().skip(1).collect();
+            for ancestor in ancestors.into_iter().rev() {
+                self.load_ignore_rules(ancestor, &mut rules).await;
+            }
+        }
+
+        let mut listing = Vec::new();
+        let mut pending = vec![(dir.to_path_buf(), 1)];
+        while let Some((current, level)) = pending.pop() {
+            if !options.include_ignored {
+                self.load_ignore_rules(&current, &mut rules).await;
+            }
+            let entries = self
+                .filesystem
+                .list_dir(&current)
+                .await
+                .map_err(anyhow::Error::new)?;
+
+            for mut entry in entries {
+                let full = current.join(&entry.name);
+                if !options.include_hidden && entry.name.starts_with('.') {
+                    continue;
+                }
+                if !options.include_ignored && rules.is_ignored(&full, entry.is_dir) {
+                    continue;
+                }
+                if listing.len() >= MAX_LIST_ENTRIES {
+                    return Ok((sorted_entries(listing), true));
+                }
+                if entry.is_dir && level < options.depth {
+                    pending.push((full.clone(), level + 1));
+                }
+                entry.name = full
+                    .strip_prefix(dir)
+                    .unwrap_or(&full)
+                    .to_string_lossy()
+                    .into_owned();
+                listing.push(entry);
+            }
+        }
+        Ok((sorted_entries(listing), false))
+    }
+
+    async fn load_ignore_rules(&self, dir: &Path, rules: &mut IgnoreRules) {
+        for name in IGNORE_FILES {
+            if let Ok(contents) = self.filesystem.read_file(&dir.join(name)).await {
+                rules.extend(dir, &contents);
+            }
+        }
+    }
+
+    /// Check that `path` is a file and sniff its first bytes for binary content.
+    ///
+    /// Binary files yield `Ok(Err(info))`, where `info` is a JSON description
+    /// (size, MIME guess) returned to the model in place of the contents.
+    /// Text files yield their metadata and sniffed bytes.
+    async fn inspect(
+        &self,
+        path: &str,
+    ) -> Result<std::result::Result<(FileMetadata, Vec<u8>), String>> {
+        let target = Path::new(path);
+        let metadata = self
+            .filesystem
+            .metadata(target)
+            .await
+            .map_err(anyhow::Error::new)?;
+        if metadata.is_dir {
+            return Err(anyhow!("'{path}' is a directory; use list instead"));
+        }
+
+        // Binary detection only needs the first few kilobytes.
+        let head = self
+            .filesystem
+            .read_head(target, BINARY_SNIFF_BYTES)
+            .await
+            .map_err(anyhow::Error::new)?;
+        if head.contains(&0) {
+            return Ok(Err(binary_info(path, metadata.size, &head)));
+        }
+        Ok(Ok((metadata, head)))
+    }
+
+    /// Load a whole file for reading.
+    ///
+    /// Binary files yield `Ok(Err(info))`, as for [`inspect`](Self::inspect).
+    /// Files larger than `max_read_bytes` fail with a hint to page through the
+    /// file instead.
+    async fn load_text(&self, path: &str) -> Result<std::result::Result<String, String>> {
+        let (metadata, head) = match self.inspect(path).await? {
+            Ok(text) => text,
+            Err(info) => return Ok(Err(info)),
+        };
+
+        // Checked before reading so oversize files are never loaded whole.
+        if metadata.size > self.max_read_bytes {
+            return Err(anyhow!(
+                "'{path}' is {} bytes, over the {} byte read limit; \
+                 read it in parts with offset/limit, or use head/tail/grep",
+                metadata.size,
+                self.max_read_bytes
+            ));
+        }
+
+        let bytes = self
+            .filesystem
+            .read_bytes(Path::new(path))
+            .await
+            .map_err(anyhow::Error::new)?;
+        let Ok(text) = String::from_utf8(bytes) else {
+            return Ok(Err(binary_info(path, metadata.size, &head)));
+        };
+        Ok(Ok(text))
+    }
+
+    /// Load up to `limit` lines starting at the 0-based line `start`, or the
+    /// last `limit` lines if `start` is `None`, with a footer giving the range shown.
+    ///
+    /// Only the lines asked for are read, so this works on files of any size.
+    /// Binary files yield `Ok(Err(info))`, as for [`inspect`](Self::inspect).
+    async fn load_lines(
+        &self,
+        path: &str,
+        start: Option<usize>,
+        limit: usize,
+    ) -> Result<std::result::Result<String, String>> {
+        let (metadata, head) = match self.inspect(path).await? {
+            Ok(text) => text,
+            Err(info) => return Ok(Err(info)),
+        };
+        let target = Path::new(path);
+        let rendered = match start {
+            Some(start) => self
+                .filesystem
+                .read_lines(target, start, limit)
+                .await
+                .map(|window| window.render(start)),
+            None => self
+                .filesystem
+                .read_last_lines(target, limit)
+                .await
+                .map(|window| window.render_last()),
+        };
+        match rendered {
+            Ok(rendered) => Ok(Ok(rendered)),
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                Ok(Err(binary_info(path, metadata.size, &head)))
+            }
+            Err(err) => Err(anyhow::Error::new(err)),
+        }
+    }
+}
+
+/// JSON description of a binary file, returned to the model in place of its contents.
+fn binary_info(path: &str, size: u64, head: &[u8]) -> String {
+    json(&BinaryFileInfo {
+        path,
+        binary: true,
+        size,
+        mime: guess_mime(path, head),
+    })
 }
 
 impl<FS: FileSystem> Tool for FileSystemTool<FS> {
@@ -178,59 +712,464 @@ impl<FS: FileSystem> Tool for FileSystemTool<FS> {
 
     async fn call(&self, arguments: Self::Arguments) -> aither_core::Result<ToolOutput> {
         match arguments {
-            FsOperation::Read { path } => {
+            FsOperation::Read {
+                path,
+                offset,
+                limit,
+            } => {
+                let content = if offset.is_none() && limit.is_none() {
+                    self.load_text(&path).await?
+                } else {
+                    let start = offset.unwrap_or(1).saturating_sub(1);
+                    self.load_lines(&path, Some(start), limit.unwrap_or(usize::MAX))
+                        .await?
+                };
+                Ok(ToolOutput::text(content.unwrap_or_else(|info| info)))
+            }
+            FsOperation::Head { path, lines } => {
+                let lines = lines.unwrap_or(DEFAULT_HEAD_TAIL_LINES);
+                let content = self.load_lines(&path, Some(0), lines).await?;
+                Ok(ToolOutput::text(content.unwrap_or_else(|info| info)))
+            }
+            FsOperation::Tail { path, lines } => {
+                let lines = lines.unwrap_or(DEFAULT_HEAD_TAIL_LINES);
+                let content = self.load_lines(&path, None, lines).await?;
+                Ok(ToolOutput::text(content.unwrap_or_else(|info| info)))
+            }
+            FsOperation::Write { path, content } => {
+                self.ensure_writable()?;
+                let target = Path::new(&path);
+                let before = self.snapshot(target).await;
+                self.filesystem
+                    .write_file(target, content)
+                    .await
+                    .map_err(anyhow::Error::new)?;
+                self.journal_change(ChangeKind::Write, target, None, before)
+                    .await;
+                Ok(ToolOutput::Done)
+            }
+            FsOperation::Edit {
+                path,
+                old_text,
+                new_text,
+                occurrence,
+            } => {
+                self.ensure_writable()?;
                 let content = self
                     .filesystem
                     .read_file(Path::new(&path))
                     .await
                     .map_err(anyhow::Error::new)?;
-                Ok(ToolOutput::text(content))
+                let (updated, line) = replace_exact(&content, &old_text, &new_text, occurrence)?;
+                self.filesystem
+                    .write_file(Path::new(&path), updated)
+                    .await
+                    .map_err(anyhow::Error::new)?;
+                self.journal_change(
+                    ChangeKind::Edit,
+                    Path::new(&path),
+                    None,
+                    Some(Some(Snapshot::File(content.into_bytes()))),
+                )
+                .await;
+                Ok(ToolOutput::text(format!("Edited {path} at line {line}")))
             }
-            FsOperation::Write { path, content } => {
+            FsOperation::Append { path, content } => {
                 self.ensure_writable()?;
+                let target = Path::new(&path);
+                let before = self.snapshot(target).await;
                 self.filesystem
-                    .write_file(Path::new(&path), content)
+                    .append_file(target, content)
                     .await
                     .map_err(anyhow::Error::new)?;
+                self.journal_change(ChangeKind::Append, target, None, before)
+                    .await;
                 Ok(ToolOutput::Done)
             }
-            FsOperation::Append { path, content } => {
+            FsOperation::Delete {
+                path,
+                recursive,
+                trash,
+            } => {
                 self.ensure_writable()?;
+                let target = Path::new(&path);
+                if trash.unwrap_or(self.trash_deletes) {
+                    let destination = trash_path(target)?;
+                    self.filesystem
+                        .rename(target, &destination)
+                        .await
+                        .map_err(anyhow::Error::new)?;
+                    self.journal_change(ChangeKind::Move, &destination, Some(target), Some(None))
+                        .await;
+                    return Ok(ToolOutput::text(format!(
+                        "Moved {path} to {}",
+                        destination.display()
+                    )));
+                }
+                if recursive.unwrap_or(false) {
+                    let before =
+                        match Snapshot::capture(&self.filesystem, target, MAX_TREE_BACKUP_BYTES)
+                            .await
+                        {
+                            Ok(before) => Some(before),
+                            Err(err) if err.kind() == io::ErrorKind::FileTooLarge => {
+                                return Err(anyhow!(
+                                    "'{path}' holds more than {MAX_TREE_BACKUP_BYTES} bytes, \
+                                     too much to back up for undo; delete it with trash: true"
+                                ));
+                            }
+                            Err(_) => None,
+                        };
+                    self.filesystem
+                        .remove_dir_all(target)
+                        .await
+                        .map_err(anyhow::Error::new)?;
+                    self.journal_change(ChangeKind::Delete, target, None, before)
+                        .await;
+                } else {
+                    let before = self.snapshot(target).await;
+                    self.filesystem
+                        .remove_file(target)
+                        .await
+                        .map_err(anyhow::Error::new)?;
+                    self.journal_change(ChangeKind::Delete, target, None, before)
+                        .await;
+                }
+                Ok(ToolOutput::Done)
+            }
+            FsOperation::Undo { path } => {
+                self.ensure_writable()?;
+                let entry = self
+                    .journal
+                    .undo(&self.filesystem, &journal_key(Path::new(&path)))
+                    .await
+                    .map_err(anyhow::Error::new)?;
+                let restored = match (&entry.source, &entry.backup) {
+                    (Some(source), _) => format!(
+                        "moved {} back to {}",
+                        entry.path.display(),
+                        source.display()
+                    ),
+                    (None, Some(_)) => "restored previous contents".to_string(),
+                    (None, None) => "removed the newly created entry".to_string(),
+                };
+                Ok(ToolOutput::text(format!(
+                    "Undid {:?} of {path}: {restored}",
+                    entry.kind
+                )))
+            }
+            FsOperation::Move { from, to } => {
+                self.ensure_writable()?;
+                let (source, target) = (Path::new(&from), Path::new(&to));
+                let before = self.snapshot(target).await;
                 self.filesystem
-                    .append_file(Path::new(&path), content)
+                    .rename(source, target)
                     .await
                     .map_err(anyhow::Error::new)?;
+                self.journal_change(ChangeKind::Move, target, Some(source), before)
+                    .await;
                 Ok(ToolOutput::Done)
             }
-            FsOperation::Delete { path } => {
+            FsOperation::Copy { from, to } => {
                 self.ensure_writable()?;
+                let target = Path::new(&to);
+                let before = self.snapshot(target).await;
                 self.filesystem
-                    .remove_file(Path::new(&path))
+                    .copy_file(Path::new(&from), target)
                     .await
                     .map_err(anyhow::Error::new)?;
+                self.journal_change(ChangeKind::Copy, target, None, before)
+                    .await;
                 Ok(ToolOutput::Done)
             }
-            FsOperation::List { path } => {
-                let listing = self
-                    .filesystem
-                    .list_dir(path.as_deref().map_or(Path::new(""), Path::new))
+            FsOperation::CreateDir { path } => {
+                self.ensure_writable()?;
+                let target = Path::new(&path);
+                // Missing parents are created too; journal the outermost new directory.
+                let mut created = None;
+                let mut prefix = PathBuf::new();
+                for component in journal_key(target).components() {
+                    prefix.push(component);
+                    if let Err(err) = self.filesystem.metadata(&prefix).await
+                        && err.kind() == io::ErrorKind::NotFound
+                    {
+                        created = Some(prefix);
+                        break;
+                    }
+                }
+                self.filesystem
+                    .create_dir(target)
                     .await
                     .map_err(anyhow::Error::new)?;
-                Ok(ToolOutput::text(json(&listing)))
+                if let Some(created) = created {
+                    self.journal_change(ChangeKind::CreateDir, &created, None, Some(None))
+                        .await;
+                }
+                Ok(ToolOutput::Done)
+            }
+            FsOperation::List {
+                path,
+                depth,
+                include_hidden,
+                include_ignored,
+            } => {
+                let options = ListOptions {
+                    depth: depth.unwrap_or(1).clamp(1, MAX_LIST_DEPTH),
+                    include_hidden: include_hidden.unwrap_or(false),
+                    include_ignored: include_ignored.unwrap_or(false),
+                };
+                let (listing, truncated) = self
+                    .list_tree(path.as_deref().map_or(Path::new(""), Path::new), options)
+                    .await?;
+                let mut output = json(&listing);
+                if truncated {
+                    output.push_str(&format!(
+                        "\n[Truncated after {MAX_LIST_ENTRIES} entries; list a subdirectory or reduce depth]"
+                    ));
+                }
+                Ok(ToolOutput::text(output))
             }
             FsOperation::Glob { pattern } => {
                 let matches = self.filesystem.glob(&pattern)?;
                 Ok(ToolOutput::text(json(&matches)))
             }
+            FsOperation::Grep {
+                pattern,
+                path,
+                max_results,
+                context,
+            } => {
+                let regex = Regex::new(&pattern).map_err(|e| anyhow!("Invalid regex: {e}"))?;
+                let max_results = max_results.unwrap_or(DEFAULT_GREP_RESULTS).max(1);
+                let context = context.unwrap_or(0).min(MAX_GREP_CONTEXT);
+                let matches = self.filesystem.grep(
+                    &regex,
+                    path.as_deref().map_or(Path::new(""), Path::new),
+                    max_results,
+                    context,
+                )?;
+                Ok(ToolOutput::text(format_grep(&matches, max_results)))
+            }
         }
     }
 }
 
+/// Listing behaviour for [`FsOperation::List`].
+#[derive(Debug, Clone, Copy)]
+struct ListOptions {
+    depth: usize,
+    include_hidden: bool,
+    include_ignored: bool,
+}
+
+fn sorted_entries(mut entries: Vec<DirEntry>) -> Vec<DirEntry> {
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// Best-effort MIME type from magic bytes, falling back to the file extension.
+fn guess_mime(path: &str, head: &[u8]) -> &'static str {
+    const MAGIC: &[(&[u8], &str)] = &[
+        (b"\x89PNG", "image/png"),
+        (b"\xFF\xD8\xFF", "image/jpeg"),
+        (b"GIF8", "image/gif"),
+        (b"%PDF", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1F\x8B", "application/gzip"),
+        (b"\x7FELF", "application/x-elf"),
+        (b"\0asm", "application/wasm"),
+        (b"SQLite format 3", "application/vnd.sqlite3"),
+    ];
+    if let Some((_, mime)) = MAGIC.iter().find(|(magic, _)| head.starts_with(magic)) {
+        return mime;
+    }
+    if head.starts_with(b"RIFF") && head.get(8..12) == Some(&b"WEBP"[..]) {
+        return "image/webp";
+    }
+
+    let extension = Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        "zip" | "jar" => "application/zip",
+        "gz" | "tgz" => "application/gzip",
+        "wasm" => "application/wasm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "ttf" => "font/ttf",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Lexically normalized path used to key journal entries, so `./a` and `a` match.
+fn journal_key(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
+/// Largest directory tree a recursive delete backs up for undo; bigger trees
+/// must be trashed instead.
+const MAX_TREE_BACKUP_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Directory (relative to the root) that receives trashed files.
+const TRASH_DIR: &str = ".trash";
+
+/// Destination inside [`TRASH_DIR`] for a trashed path, unique per deletion.
+fn trash_path(target: &Path) -> Result<PathBuf> {
+    let name = target
+        .file_name()
+        .ok_or_else(|| anyhow!("Cannot trash '{}'", target.display()))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    Ok(Path::new(TRASH_DIR).join(format!("{stamp}-{}", name.to_string_lossy())))
+}
+
+/// Replace one exact occurrence of `old` in `content`.
+///
+/// Returns the new content and the 1-based line where the replacement starts.
+/// Fails when `old` is missing, or occurs several times and `occurrence` does not pick one.
+fn replace_exact(
+    content: &str,
+    old: &str,
+    new: &str,
+    occurrence: Option<usize>,
+) -> Result<(String, usize)> {
+    if old.is_empty() {
+        return Err(anyhow!("old_text must not be empty"));
+    }
+    let positions: Vec<usize> = content.match_indices(old).map(|(index, _)| index).collect();
+    let line_of = |index: usize| content[..index].matches('\n').count() + 1;
+
+    let index = match (positions.as_slice(), occurrence) {
+        ([], _) => {
+            let hint = if content.contains(old.trim()) {
+                " (a match exists ignoring leading/trailing whitespace; check indentation)"
+            } else {
+                ""
+            };
+            return Err(anyhow!("old_text not found in file{hint}"));
+        }
+        ([only], None | Some(1)) => *only,
+        (all, None) => {
+            let lines: Vec<String> = all.iter().map(|&i| line_of(i).to_string()).collect();
+            return Err(anyhow!(
+                "old_text matches {} times (lines {}); add surrounding context or set occurrence",
+                all.len(),
+                lines.join(", ")
+            ));
+        }
+        (all, Some(n)) => *all.get(n.wrapping_sub(1)).ok_or_else(|| {
+            anyhow!(
+                "occurrence {n} out of range; old_text matches {} time(s)",
+                all.len()
+            )
+        })?,
+    };
+
+    let mut updated = String::with_capacity(content.len() + new.len());
+    updated.push_str(&content[..index]);
+    updated.push_str(new);
+    updated.push_str(&content[index + old.len()..]);
+    Ok((updated, line_of(index)))
+}
+
+/// Render grep matches in ripgrep's `path:line:text` format.
+fn format_grep(matches: &[GrepMatch], max_results: usize) -> String {
+    if matches.is_empty() {
+        return "No matches found.".into();
+    }
+    let mut out = String::new();
+    for (index, found) in matches.iter().enumerate() {
+        if index > 0 && (!found.before.is_empty() || !matches[index - 1].after.is_empty()) {
+            out.push_str("--\n");
+        }
+        let first = found.line - found.before.len();
+        for (offset, line) in found.before.iter().enumerate() {
+            out.push_str(&format!("{}-{}-{line}\n", found.path, first + offset));
+        }
+        out.push_str(&format!("{}:{}:{}\n", found.path, found.line, found.text));
+        for (offset, line) in found.after.iter().enumerate() {
+            out.push_str(&format!(
+                "{}-{}-{line}\n",
+                found.path,
+                found.line + 1 + offset
+            ));
+        }
+    }
+    if matches.len() >= max_results {
+        out.push_str(&format!(
+            "[Stopped after {max_results} matches; narrow the pattern or path to see more]\n"
+        ));
+    }
+    out
+}
+
+/// Collect matches from one file's text into `out`, stopping once `max_results` is reached.
+fn grep_text(
+    label: &str,
+    text: &str,
+    pattern: &Regex,
+    max_results: usize,
+    context: usize,
+    out: &mut Vec<GrepMatch>,
+) {
+    if text.contains('\0') {
+        return;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        if out.len() >= max_results {
+            return;
+        }
+        if !pattern.is_match(line) {
+            continue;
+        }
+        let start = index.saturating_sub(context);
+        let end = (index + 1 + context).min(lines.len());
+        out.push(GrepMatch {
+            path: label.to_string(),
+            line: index + 1,
+            text: truncate_line(line),
+            before: lines[start..index]
+                .iter()
+                .map(|l| truncate_line(l))
+                .collect(),
+            after: lines[index + 1..end]
+                .iter()
+                .map(|l| truncate_line(l))
+                .collect(),
+        });
+    }
+}
+
+fn truncate_line(line: &str) -> String {
+    if line.chars().count() <= MAX_GREP_LINE_CHARS {
+        return line.to_string();
+    }
+    let mut truncated: String = line.chars().take(MAX_GREP_LINE_CHARS).collect();
+    truncated.push('…');
+    truncated
+}
+
 #[derive(Debug, Clone)]
 pub struct LocalFileSystem {
     root: PathBuf,
     canonical_root: PathBuf,
     allow_writes: bool,
+    symlink_policy: SymlinkPolicy,
+    audit: AccessAudit,
 }
 
 impl LocalFileSystem {
@@ -253,38 +1192,172 @@ impl LocalFileSystem {
             root: canonical_root.clone(),
             canonical_root,
             allow_writes,
+            symlink_policy: SymlinkPolicy::default(),
+            audit: AccessAudit::new(),
         })
     }
 
+    /// Choose how symlinks inside the root are treated.
+    pub fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = policy;
+        self
+    }
+
+    /// Record blocked accesses into `audit`, e.g. one with an observer attached.
+    pub fn with_audit(mut self, audit: AccessAudit) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Log of accesses refused by the root jail.
+    pub fn audit(&self) -> &AccessAudit {
+        &self.audit
+    }
+
     async fn resolve(&self, relative: &Path, create_parent: bool) -> io::Result<PathBuf> {
-        let candidate = if relative.as_os_str().is_empty() {
-            self.root.clone()
-        } else {
-            self.root.join(relative)
-        };
+        let resolved = self.jail(relative)?;
 
-        if create_parent && let Some(parent) = candidate.parent() {
+        // Parents are only created once the path is known to stay inside the root.
+        if create_parent && let Some(parent) = resolved.parent() {
             create_dir_all(parent).await?;
         }
 
-        let canonical = if candidate.exists() {
-            candidate.canonicalize()?
-        } else {
-            candidate.clone()
+        Ok(resolved)
+    }
+
+    /// Resolve `relative` against the root one component at a time, refusing it if
+    /// it leaves the root or crosses a symlink the [`SymlinkPolicy`] does not allow.
+    ///
+    /// Unlike canonicalizing the whole path, this also covers paths whose tail does not
+    /// exist yet, so a symlinked parent directory cannot redirect a write outside the root.
+    fn jail(&self, relative: &Path) -> io::Result<PathBuf> {
+        // Absolute paths are accepted when they already point inside the root.
+        let relative = relative
+            .strip_prefix(&self.canonical_root)
+            .unwrap_or(relative);
+        let mut resolved = self.canonical_root.clone();
+        for component in relative.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() || !resolved.starts_with(&self.canonical_root) {
+                        return Err(self.block(relative, BlockReason::EscapesRoot));
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => {
+                    return Err(self.block(relative, BlockReason::EscapesRoot));
+                }
+                Component::Normal(part) => {
+                    resolved.push(part);
+                    let is_symlink = fs::symlink_metadata(&resolved)
+                        .is_ok_and(|metadata| metadata.file_type().is_symlink());
+                    if !is_symlink {
+                        continue;
+                    }
+                    if self.symlink_policy == SymlinkPolicy::Deny {
+                        return Err(self.block(relative, BlockReason::Symlink));
+                    }
+                    resolved = match fs::canonicalize(&resolved) {
+                        Ok(target) => target,
+                        Err(_) => return Err(self.block(relative, BlockReason::DanglingSymlink)),
+                    };
+                    if !resolved.starts_with(&self.canonical_root) {
+                        return Err(self.block(relative, BlockReason::EscapesRoot));
+                    }
+                }
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Open `path`, which [`jail`](Self::jail) resolved from `relative`, and check that the
+    /// handle really lies inside the root.
+    ///
+    /// A directory along `path` may have been swapped for a symlink since it was checked.
+    /// The last component is opened without following symlinks and the handle's real path
+    /// is read back, so such a swap is refused before any data is read or written.
+    fn open_checked(
+        &self,
+        relative: &Path,
+        path: &Path,
+        options: &fs::OpenOptions,
+        directory: bool,
+    ) -> io::Result<fs::File> {
+        let mut options = options.clone();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            let mut flags = libc::O_NOFOLLOW;
+            if directory {
+                flags |= libc::O_DIRECTORY;
+            }
+            options.custom_flags(flags);
+        }
+        #[cfg(not(unix))]
+        let _ = directory;
+        let file = match options.open(path) {
+            Ok(file) => file,
+            #[cfg(unix)]
+            Err(error) if error.raw_os_error() == Some(libc::ELOOP) => {
+                return Err(self.block(relative, BlockReason::Symlink));
+            }
+            Err(error) => return Err(error),
         };
-
-        if !canonical.starts_with(&self.canonical_root) {
-            return Err(io::Error::new(
-                io::ErrorKind::PermissionDenied,
-                format!(
-                    "Access to '{}' escapes filesystem root {}",
-                    canonical.display(),
-                    self.canonical_root.display()
-                ),
-            ));
+        if !opened_path(&file, path)?.starts_with(&self.canonical_root) {
+            return Err(self.block(relative, BlockReason::EscapesRoot));
         }
+        Ok(file)
+    }
 
-        Ok(canonical)
+    /// Jail `relative` and hold its parent directory open, so later operations on the
+    /// entry cannot be redirected by swapping a directory along the path.
+    fn pin(&self, relative: &Path) -> io::Result<Pinned> {
+        let target = self.jail(relative)?;
+        let (dir_path, name) = match (target.parent(), target.file_name()) {
+            (Some(parent), Some(name)) if target != self.canonical_root => {
+                (parent.to_path_buf(), name.to_os_string())
+            }
+            _ => (target, OsString::from(".")),
+        };
+        let dir =
+            self.open_checked(relative, &dir_path, fs::OpenOptions::new().read(true), true)?;
+        Ok(Pinned::new(dir, &dir_path, name))
+    }
+
+    /// Like [`pin`](Self::pin), but holds the directory `relative` itself open.
+    fn pin_dir(&self, relative: &Path) -> io::Result<Pinned> {
+        let parent = self.pin(relative)?;
+        let dir = self.open_checked(
+            relative,
+            &parent.path(),
+            fs::OpenOptions::new().read(true),
+            true,
+        )?;
+        Ok(Pinned::new(
+            dir,
+            &parent.dir_path.join(&parent.name),
+            OsString::from("."),
+        ))
+    }
+
+    /// Open the file at `relative` through its pinned parent directory.
+    fn open_file(&self, relative: &Path, options: &fs::OpenOptions) -> io::Result<File> {
+        let pinned = self.pin(relative)?;
+        let file = self.open_checked(relative, &pinned.path(), options, false)?;
+        Ok(File::from(file))
+    }
+
+    /// Record a refused access and build the error returned to the caller.
+    fn block(&self, relative: &Path, reason: BlockReason) -> io::Error {
+        self.audit.record(relative.to_path_buf(), reason);
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "Access to '{}' denied: {reason} (root {})",
+                relative.display(),
+                self.canonical_root.display()
+            ),
+        )
     }
 
     fn ensure_writable(&self) -> io::Result<()> {
@@ -297,46 +1370,191 @@ impl LocalFileSystem {
             ))
         }
     }
+
+    fn relative_label(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn grep_walk(
+        &self,
+        path: &Path,
+        pattern: &Regex,
+        max_results: usize,
+        context: usize,
+        out: &mut Vec<GrepMatch>,
+    ) -> io::Result<()> {
+        if out.len() >= max_results {
+            return Ok(());
+        }
+        let metadata = fs::symlink_metadata(path)?;
+        if metadata.is_file() {
+            if metadata.len() <= MAX_GREP_FILE_SIZE
+                && let Ok(text) = fs::read_to_string(path)
+            {
+                grep_text(
+                    &self.relative_label(path),
+                    &text,
+                    pattern,
+                    max_results,
+                    context,
+                    out,
+                );
+            }
+            return Ok(());
+        }
+        if !metadata.is_dir() {
+            return Ok(());
+        }
+
+        let mut children: Vec<PathBuf> = fs::read_dir(path)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|child| {
+                let name = child
+                    .file_name()
+                    .map(|n| n.to_string_lossy())
+                    .unwrap_or_default();
+                !name.starts_with('.') && !GREP_SKIP_DIRS.contains(&name.as_ref())
+            })
+            .collect();
+        children.sort();
+        for child in children {
+            // Unreadable entries are skipped rather than failing the whole search.
+            self.grep_walk(&child, pattern, max_results, context, out)
+                .ok();
+        }
+        Ok(())
+    }
+}
+
+/// A directory inside the root held open, and the name of an entry in it.
+#[derive(Debug)]
+struct Pinned {
+    /// Keeps `base` pointing at the checked directory.
+    _dir: fs::File,
+    dir_path: PathBuf,
+    base: PathBuf,
+    name: OsString,
+}
+
+impl Pinned {
+    fn new(dir: fs::File, dir_path: &Path, name: OsString) -> Self {
+        let base = pinned_base(&dir).unwrap_or_else(|| dir_path.to_path_buf());
+        Self {
+            _dir: dir,
+            dir_path: dir_path.to_path_buf(),
+            base,
+            name,
+        }
+    }
+
+    /// Path of the entry, resolved through the open directory where the platform allows.
+    fn path(&self) -> PathBuf {
+        self.base.join(&self.name)
+    }
+}
+
+/// `/proc/self/fd` path of `dir`, which resolves to exactly the open directory.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn pinned_base(dir: &fs::File) -> Option<PathBuf> {
+    use std::os::fd::AsRawFd;
+    let path = PathBuf::from(format!("/proc/self/fd/{}", dir.as_raw_fd()));
+    path.is_dir().then_some(path)
+}
+
+/// Without `/proc`, entries are reached through the directory's checked real path.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const fn pinned_base(_dir: &fs::File) -> Option<PathBuf> {
+    None
+}
+
+/// Real path of an open file, read back from the kernel.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn opened_path(file: &fs::File, opened_as: &Path) -> io::Result<PathBuf> {
+    use std::os::fd::AsRawFd;
+    fs::read_link(format!("/proc/self/fd/{}", file.as_raw_fd()))
+        .or_else(|_| fs::canonicalize(opened_as))
+}
+
+/// Real path of an open file, read back from the kernel.
+#[cfg(target_vendor = "apple")]
+fn opened_path(file: &fs::File, _opened_as: &Path) -> io::Result<PathBuf> {
+    use std::os::{fd::AsRawFd, unix::ffi::OsStrExt};
+    let mut buf = [0_u8; libc::PATH_MAX as usize];
+    // SAFETY: `buf` holds PATH_MAX bytes, as F_GETPATH requires.
+    if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETPATH, buf.as_mut_ptr()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    let len = buf.iter().position(|&byte| byte == 0).unwrap_or(buf.len());
+    Ok(PathBuf::from(std::ffi::OsStr::from_bytes(&buf[..len])))
+}
+
+/// Platforms that can't name an open file fall back to resolving the path again.
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+fn opened_path(_file: &fs::File, opened_as: &Path) -> io::Result<PathBuf> {
+    fs::canonicalize(opened_as)
 }
 
 impl FileSystem for LocalFileSystem {
     async fn read_file<'a>(&'a self, path: &'a Path) -> io::Result<String> {
-        let target = self.resolve(path, false).await?;
-        read_to_string(&target).await
+        let mut file = self.open_file(path, fs::OpenOptions::new().read(true))?;
+        let mut text = String::new();
+        file.read_to_string(&mut text).await?;
+        Ok(text)
     }
 
     async fn write_file<'a>(&'a self, path: &'a Path, contents: String) -> io::Result<()> {
+        self.write_bytes(path, contents.into_bytes()).await
+    }
+
+    async fn write_bytes<'a>(&'a self, path: &'a Path, contents: Vec<u8>) -> io::Result<()> {
         self.ensure_writable()?;
-        let target = self.resolve(path, true).await?;
-        write(&target, contents).await
+        self.resolve(path, true).await?;
+        // Truncate only once the handle is known to be inside the root.
+        let mut file = self.open_file(path, fs::OpenOptions::new().write(true).create(true))?;
+        file.set_len(0).await?;
+        file.write_all(&contents).await?;
+        file.flush().await
     }
 
     async fn append_file<'a>(&'a self, path: &'a Path, contents: String) -> io::Result<()> {
         self.ensure_writable()?;
-        let target = self.resolve(path, true).await?;
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&target)
-            .await?;
-        file.write_all(contents.as_bytes()).await
+        self.resolve(path, true).await?;
+        let mut file = self.open_file(path, fs::OpenOptions::new().append(true).create(true))?;
+        file.write_all(contents.as_bytes()).await?;
+        file.flush().await
     }
 
     async fn remove_file<'a>(&'a self, path: &'a Path) -> io::Result<()> {
         self.ensure_writable()?;
-        let target = self.resolve(path, false).await?;
-        remove_file(&target).await
+        let target = self.pin(path)?;
+        remove_file(target.path()).await
     }
 
     async fn list_dir<'a>(&'a self, dir: &'a Path) -> io::Result<Vec<DirEntry>> {
-        let target = self.resolve(dir, false).await?;
-        let mut reader = read_dir(&target).await?;
+        let target = self.pin_dir(dir)?;
+        let mut reader = read_dir(target.path()).await?;
         let mut entries = Vec::new();
         while let Some(entry) = reader.next().await {
             if let Ok(entry) = entry {
+                let metadata = entry.metadata().await.ok();
+                let is_dir = metadata
+                    .as_ref()
+                    .map_or_else(|| entry.path().is_dir(), fs::Metadata::is_dir);
                 entries.push(DirEntry {
                     name: entry.file_name().to_string_lossy().into_owned(),
-                    is_dir: entry.path().is_dir(),
+                    is_dir,
+                    size: metadata
+                        .as_ref()
+                        .filter(|m| m.is_file())
+                        .map(fs::Metadata::len),
+                    modified: metadata
+                        .and_then(|m| m.modified().ok())
+                        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                        .map(|elapsed| elapsed.as_secs()),
                 });
             }
         }
@@ -345,17 +1563,95 @@ impl FileSystem for LocalFileSystem {
 
     async fn create_dir<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
         self.ensure_writable()?;
-        let target = self.resolve(dir, true).await?;
-        create_dir_all(&target).await
+        self.resolve(dir, true).await?;
+        let target = self.pin(dir)?;
+        create_dir_all(target.path()).await
     }
 
     async fn remove_dir<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
         self.ensure_writable()?;
-        let target = self.resolve(dir, false).await?;
-        remove_dir(&target).await
+        let target = self.pin(dir)?;
+        remove_dir(target.path()).await
+    }
+
+    async fn read_bytes<'a>(&'a self, path: &'a Path) -> io::Result<Vec<u8>> {
+        let mut file = self.open_file(path, fs::OpenOptions::new().read(true))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).await?;
+        Ok(bytes)
+    }
+
+    async fn read_head<'a>(&'a self, path: &'a Path, len: usize) -> io::Result<Vec<u8>> {
+        let file = self.open_file(path, fs::OpenOptions::new().read(true))?;
+        let mut head = Vec::with_capacity(len);
+        file.take(len as u64).read_to_end(&mut head).await?;
+        Ok(head)
+    }
+
+    async fn read_lines<'a>(
+        &'a self,
+        path: &'a Path,
+        start: usize,
+        limit: usize,
+    ) -> io::Result<LineWindow> {
+        let file = self.open_file(path, fs::OpenOptions::new().read(true))?;
+        lines::read_forward(BufReader::new(file), start, limit).await
+    }
+
+    async fn read_last_lines<'a>(&'a self, path: &'a Path, count: usize) -> io::Result<LineWindow> {
+        let file = self.open_file(path, fs::OpenOptions::new().read(true))?;
+        lines::read_backward(file, count).await
+    }
+
+    async fn metadata<'a>(&'a self, path: &'a Path) -> io::Result<FileMetadata> {
+        let target = self.pin(path)?;
+        let metadata = symlink_metadata(target.path()).await?;
+        Ok(FileMetadata {
+            size: metadata.len(),
+            is_dir: metadata.is_dir(),
+            modified: metadata.modified().ok(),
+        })
+    }
+
+    async fn remove_dir_all<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
+        self.ensure_writable()?;
+        if self.jail(dir)? == self.canonical_root {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Cannot remove root directory",
+            ));
+        }
+        let target = self.pin(dir)?;
+        remove_dir_all(target.path()).await
+    }
+
+    async fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        self.ensure_writable()?;
+        self.resolve(to, true).await?;
+        let source = self.pin(from)?;
+        let destination = self.pin(to)?;
+        rename(source.path(), destination.path()).await
+    }
+
+    async fn copy_file<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        self.ensure_writable()?;
+        self.resolve(to, true).await?;
+        let source = self.open_file(from, fs::OpenOptions::new().read(true))?;
+        let mut destination =
+            self.open_file(to, fs::OpenOptions::new().write(true).create(true))?;
+        destination.set_len(0).await?;
+        futures_lite::io::copy(source, &mut destination).await?;
+        destination.flush().await
     }
 
     fn glob(&self, pattern: &str) -> io::Result<Vec<String>> {
+        // `..` and absolute components would match names outside the root.
+        if Path::new(pattern)
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(self.block(Path::new(pattern), BlockReason::EscapesRoot));
+        }
         let full_pattern = self.root.join(pattern);
         let pattern_str = full_pattern.to_string_lossy();
 
@@ -364,6 +1660,10 @@ impl FileSystem for LocalFileSystem {
 
         let mut results = Vec::new();
         for entry in entries.flatten() {
+            // A symlink inside the root can still lead a match out of it.
+            if !fs::canonicalize(&entry).is_ok_and(|real| real.starts_with(&self.canonical_root)) {
+                continue;
+            }
             // Convert to relative path
             if let Ok(relative) = entry.strip_prefix(&self.root) {
                 results.push(relative.to_string_lossy().into_owned());
@@ -372,6 +1672,19 @@ impl FileSystem for LocalFileSystem {
         results.sort();
         Ok(results)
     }
+
+    fn grep(
+        &self,
+        pattern: &Regex,
+        path: &Path,
+        max_results: usize,
+        context: usize,
+    ) -> io::Result<Vec<GrepMatch>> {
+        let target = self.jail(path)?;
+        let mut matches = Vec::new();
+        self.grep_walk(&target, pattern, max_results, context, &mut matches)?;
+        Ok(matches)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -438,9 +1751,17 @@ impl InMemoryFileSystem {
                     .file_name()
                     .map(|n| n.to_string_lossy().into_owned())
                     .unwrap_or_else(|| "/".into());
-                let is_dir = matches!(entry, MemoryEntry::Directory);
+                let (is_dir, size) = match entry {
+                    MemoryEntry::File(contents) => (false, Some(contents.len() as u64)),
+                    MemoryEntry::Directory => (true, None),
+                };
                 if name != "/" {
-                    result.push(DirEntry { name, is_dir });
+                    result.push(DirEntry {
+                        name,
+                        is_dir,
+                        size,
+                        modified: None,
+                    });
                 }
             }
         }
@@ -562,6 +1883,90 @@ impl FileSystem for InMemoryFileSystem {
         }
         Ok(())
     }
+
+    async fn metadata<'a>(&'a self, path: &'a Path) -> io::Result<FileMetadata> {
+        let path = Self::normalize(path)?;
+        let guard = self.state.read().unwrap();
+        match guard.entries.get(&path) {
+            Some(MemoryEntry::File(contents)) => Ok(FileMetadata {
+                size: contents.len() as u64,
+                is_dir: false,
+                modified: None,
+            }),
+            Some(MemoryEntry::Directory) => Ok(FileMetadata {
+                size: 0,
+                is_dir: true,
+                modified: None,
+            }),
+            None => Err(io::Error::new(io::ErrorKind::NotFound, "File not found")),
+        }
+    }
+
+    async fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        let from = Self::normalize(from)?;
+        let to = Self::normalize(to)?;
+        if from == Path::new("/") || to.starts_with(&from) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Cannot move a directory into itself",
+            ));
+        }
+        let mut guard = self.state.write().unwrap();
+        let moved: Vec<PathBuf> = guard
+            .entries
+            .keys()
+            .filter(|path| path.starts_with(&from))
+            .cloned()
+            .collect();
+        if moved.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "File not found"));
+        }
+        if let Some(parent) = to.parent() {
+            Self::ensure_dir_present(&mut guard.entries, parent)?;
+        }
+        for path in moved {
+            if let Some(entry) = guard.entries.remove(&path) {
+                let destination = match path.strip_prefix(&from) {
+                    Ok(relative) if !relative.as_os_str().is_empty() => to.join(relative),
+                    _ => to.clone(),
+                };
+                guard.entries.insert(destination, entry);
+            }
+        }
+        Ok(())
+    }
+
+    fn grep(
+        &self,
+        pattern: &Regex,
+        path: &Path,
+        max_results: usize,
+        context: usize,
+    ) -> io::Result<Vec<GrepMatch>> {
+        let dir = Self::normalize(path)?;
+        let guard = self.state.read().unwrap();
+        let mut matches = Vec::new();
+        for (path, entry) in guard.entries.range(dir.clone()..) {
+            if !path.starts_with(&dir) {
+                break;
+            }
+            if let MemoryEntry::File(contents) = entry {
+                let label = path.strip_prefix("/").unwrap_or(path).to_string_lossy();
+                grep_text(
+                    &label,
+                    contents,
+                    pattern,
+                    max_results,
+                    context,
+                    &mut matches,
+                );
+            }
+            if matches.len() >= max_results {
+                break;
+            }
+        }
+        Ok(matches)
+    }
 }
 
 #[derive(Clone)]
@@ -655,6 +2060,11 @@ where
         fs.as_ref().write_file(&relative, contents).await
     }
 
+    async fn write_bytes<'a>(&'a self, path: &'a Path, contents: Vec<u8>) -> io::Result<()> {
+        let (fs, relative) = self.route(path)?;
+        fs.as_ref().write_bytes(&relative, contents).await
+    }
+
     async fn append_file<'a>(&'a self, path: &'a Path, contents: String) -> io::Result<()> {
         let (fs, relative) = self.route(path)?;
         fs.as_ref().append_file(&relative, contents).await
@@ -679,6 +2089,60 @@ where
         let (fs, relative) = self.route(dir)?;
         fs.as_ref().remove_dir(&relative).await
     }
+
+    async fn read_bytes<'a>(&'a self, path: &'a Path) -> io::Result<Vec<u8>> {
+        let (fs, relative) = self.route(path)?;
+        fs.as_ref().read_bytes(&relative).await
+    }
+
+    async fn read_head<'a>(&'a self, path: &'a Path, len: usize) -> io::Result<Vec<u8>> {
+        let (fs, relative) = self.route(path)?;
+        fs.as_ref().read_head(&relative, len).await
+    }
+
+    async fn read_lines<'a>(
+        &'a self,
+        path: &'a Path,
+        start: usize,
+        limit: usize,
+    ) -> io::Result<LineWindow> {
+        let (fs, relative) = self.route(path)?;
+        fs.as_ref().read_lines(&relative, start, limit).await
+    }
+
+    async fn read_last_lines<'a>(&'a self, path: &'a Path, count: usize) -> io::Result<LineWindow> {
+        let (fs, relative) = self.route(path)?;
+        fs.as_ref().read_last_lines(&relative, count).await
+    }
+
+    async fn metadata<'a>(&'a self, path: &'a Path) -> io::Result<FileMetadata> {
+        let (fs, relative) = self.route(path)?;
+        fs.as_ref().metadata(&relative).await
+    }
+
+    async fn remove_dir_all<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
+        let (fs, relative) = self.route(dir)?;
+        fs.as_ref().remove_dir_all(&relative).await
+    }
+
+    async fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        let (source_fs, source) = self.route(from)?;
+        let (target_fs, target) = self.route(to)?;
+        if !Arc::ptr_eq(&source_fs, &target_fs) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Cannot move between different mounts",
+            ));
+        }
+        source_fs.as_ref().rename(&source, &target).await
+    }
+
+    async fn copy_file<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        let (source_fs, source) = self.route(from)?;
+        let (target_fs, target) = self.route(to)?;
+        let contents = source_fs.as_ref().read_bytes(&source).await?;
+        target_fs.as_ref().write_bytes(&target, contents).await
+    }
 }
 
 fn normalize_mount(path: PathBuf) -> PathBuf {
@@ -765,6 +2229,11 @@ where
         self.inner.write_file(path, contents).await
     }
 
+    async fn write_bytes<'a>(&'a self, path: &'a Path, contents: Vec<u8>) -> io::Result<()> {
+        self.require(self.permissions.write, "write")?;
+        self.inner.write_bytes(path, contents).await
+    }
+
     async fn append_file<'a>(&'a self, path: &'a Path, contents: String) -> io::Result<()> {
         self.require(self.permissions.write, "append")?;
         self.inner.append_file(path, contents).await
@@ -789,6 +2258,67 @@ where
         self.require(self.permissions.delete, "remove_dir")?;
         self.inner.remove_dir(dir).await
     }
+
+    async fn read_bytes<'a>(&'a self, path: &'a Path) -> io::Result<Vec<u8>> {
+        self.require(self.permissions.read, "read")?;
+        self.inner.read_bytes(path).await
+    }
+
+    async fn read_head<'a>(&'a self, path: &'a Path, len: usize) -> io::Result<Vec<u8>> {
+        self.require(self.permissions.read, "read")?;
+        self.inner.read_head(path, len).await
+    }
+
+    async fn read_lines<'a>(
+        &'a self,
+        path: &'a Path,
+        start: usize,
+        limit: usize,
+    ) -> io::Result<LineWindow> {
+        self.require(self.permissions.read, "read")?;
+        self.inner.read_lines(path, start, limit).await
+    }
+
+    async fn read_last_lines<'a>(&'a self, path: &'a Path, count: usize) -> io::Result<LineWindow> {
+        self.require(self.permissions.read, "read")?;
+        self.inner.read_last_lines(path, count).await
+    }
+
+    async fn metadata<'a>(&'a self, path: &'a Path) -> io::Result<FileMetadata> {
+        self.require(self.permissions.read || self.permissions.list, "metadata")?;
+        self.inner.metadata(path).await
+    }
+
+    async fn remove_dir_all<'a>(&'a self, dir: &'a Path) -> io::Result<()> {
+        self.require(self.permissions.delete, "remove_dir_all")?;
+        self.inner.remove_dir_all(dir).await
+    }
+
+    async fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        self.require(self.permissions.write && self.permissions.delete, "rename")?;
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_file<'a>(&'a self, from: &'a Path, to: &'a Path) -> io::Result<()> {
+        self.require(self.permissions.read && self.permissions.write, "copy")?;
+        self.inner.copy_file(from, to).await
+    }
+
+    fn glob(&self, pattern: &str) -> io::Result<Vec<String>> {
+        self.require(self.permissions.list, "glob")?;
+        self.inner.glob(pattern)
+    }
+
+    fn grep(
+        &self,
+        pattern: &Regex,
+        path: &Path,
+        max_results: usize,
+        context: usize,
+    ) -> io::Result<Vec<GrepMatch>> {
+        self.require(self.permissions.read, "grep")?;
+        self.inner.grep(pattern, path, max_results, context)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -805,6 +2335,9 @@ pub enum FsHookOperation {
     List { path: PathBuf },
     CreateDir { path: PathBuf },
     RemoveDir { path: PathBuf },
+    Rename { from: PathBuf, to: PathBuf },
+    Copy { from: PathBuf, to: PathBuf },
+    Grep { path: PathBuf, pattern: String },
 }
 
 #[derive(Clone)]
@@ -852,6 +2385,18 @@ where
         self.inner.write_file(path, contents)
     }
 
+    fn write_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        contents: Vec<u8>,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        self.emit(FsHookOperation::Write {
+            path: path.to_path_buf(),
+            bytes: contents.len(),
+        });
+        self.inner.write_bytes(path, contents)
+    }
+
     fn append_file<'a>(
         &'a self,
         path: &'a Path,
@@ -897,4 +2442,633 @@ where
         });
         self.inner.remove_dir(dir)
     }
+
+    fn read_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Future<Output = io::Result<Vec<u8>>> + Send + 'a {
+        self.emit(FsHookOperation::Read {
+            path: path.to_path_buf(),
+        });
+        self.inner.read_bytes(path)
+    }
+
+    fn read_head<'a>(
+        &'a self,
+        path: &'a Path,
+        len: usize,
+    ) -> impl Future<Output = io::Result<Vec<u8>>> + Send + 'a {
+        self.emit(FsHookOperation::Read {
+            path: path.to_path_buf(),
+        });
+        self.inner.read_head(path, len)
+    }
+
+    fn read_lines<'a>(
+        &'a self,
+        path: &'a Path,
+        start: usize,
+        limit: usize,
+    ) -> impl Future<Output = io::Result<LineWindow>> + Send + 'a {
+        self.emit(FsHookOperation::Read {
+            path: path.to_path_buf(),
+        });
+        self.inner.read_lines(path, start, limit)
+    }
+
+    fn read_last_lines<'a>(
+        &'a self,
+        path: &'a Path,
+        count: usize,
+    ) -> impl Future<Output = io::Result<LineWindow>> + Send + 'a {
+        self.emit(FsHookOperation::Read {
+            path: path.to_path_buf(),
+        });
+        self.inner.read_last_lines(path, count)
+    }
+
+    fn metadata<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Future<Output = io::Result<FileMetadata>> + Send + 'a {
+        self.inner.metadata(path)
+    }
+
+    fn remove_dir_all<'a>(
+        &'a self,
+        dir: &'a Path,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        self.emit(FsHookOperation::RemoveDir {
+            path: dir.to_path_buf(),
+        });
+        self.inner.remove_dir_all(dir)
+    }
+
+    fn rename<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        self.emit(FsHookOperation::Rename {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+        self.inner.rename(from, to)
+    }
+
+    fn copy_file<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> impl Future<Output = io::Result<()>> + Send + 'a {
+        self.emit(FsHookOperation::Copy {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+        self.inner.copy_file(from, to)
+    }
+
+    fn glob(&self, pattern: &str) -> io::Result<Vec<String>> {
+        self.inner.glob(pattern)
+    }
+
+    fn grep(
+        &self,
+        pattern: &Regex,
+        path: &Path,
+        max_results: usize,
+        context: usize,
+    ) -> io::Result<Vec<GrepMatch>> {
+        self.emit(FsHookOperation::Grep {
+            path: path.to_path_buf(),
+            pattern: pattern.as_str().to_string(),
+        });
+        self.inner.grep(pattern, path, max_results, context)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        os::unix::fs::symlink,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use futures_lite::future::block_on;
+
+    use super::*;
+
+    /// A fresh directory holding a `root` to jail and an `outside` next to it.
+    struct Sandbox {
+        dir: PathBuf,
+    }
+
+    impl Sandbox {
+        fn new() -> Self {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "aither-fs-{}-{}",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(dir.join("root")).unwrap();
+            fs::create_dir_all(dir.join("outside")).unwrap();
+            fs::write(dir.join("outside/secret"), "secret").unwrap();
+            Self { dir }
+        }
+
+        fn root(&self) -> PathBuf {
+            self.dir.join("root")
+        }
+
+        fn outside(&self) -> PathBuf {
+            self.dir.join("outside")
+        }
+    }
+
+    impl Drop for Sandbox {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn reasons(audit: &AccessAudit) -> Vec<BlockReason> {
+        audit.blocked().iter().map(|access| access.reason).collect()
+    }
+
+    #[test]
+    fn local_operations_round_trip() {
+        let sandbox = Sandbox::new();
+        let fs = LocalFileSystem::new(sandbox.root()).unwrap();
+        block_on(async {
+            fs.write_file(Path::new("a/b.txt"), "hello".into())
+                .await
+                .unwrap();
+            fs.append_file(Path::new("a/b.txt"), " world".into())
+                .await
+                .unwrap();
+            fs.write_file(Path::new("a/c.txt"), "longer contents".into())
+                .await
+                .unwrap();
+            fs.copy_file(Path::new("a/b.txt"), Path::new("a/c.txt"))
+                .await
+                .unwrap();
+            assert_eq!(
+                fs.read_file(Path::new("a/c.txt")).await.unwrap(),
+                "hello world"
+            );
+            fs.rename(Path::new("a/c.txt"), Path::new("d/c.txt"))
+                .await
+                .unwrap();
+            assert_eq!(
+                fs.read_head(Path::new("d/c.txt"), 5).await.unwrap(),
+                b"hello"
+            );
+
+            let mut names: Vec<_> = fs
+                .list_dir(Path::new("."))
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.name)
+                .collect();
+            names.sort();
+            assert_eq!(names, ["a", "d"]);
+            assert!(fs.metadata(Path::new("a")).await.unwrap().is_dir);
+
+            fs.remove_file(Path::new("a/b.txt")).await.unwrap();
+            fs.remove_dir(Path::new("a")).await.unwrap();
+            fs.remove_dir_all(Path::new("d")).await.unwrap();
+            assert!(fs.remove_dir_all(Path::new(".")).await.is_err());
+        });
+        assert!(fs.audit().blocked().is_empty());
+    }
+
+    #[test]
+    fn symlink_out_of_root_is_refused_and_audited() {
+        let sandbox = Sandbox::new();
+        symlink(sandbox.outside(), sandbox.root().join("link")).unwrap();
+        let fs = LocalFileSystem::new(sandbox.root()).unwrap();
+
+        let error = block_on(fs.read_file(Path::new("link/secret"))).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        let error = block_on(fs.write_file(Path::new("link/new"), "x".into())).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        assert!(!sandbox.outside().join("new").exists());
+
+        let blocked = fs.audit().blocked();
+        assert_eq!(reasons(fs.audit()), [BlockReason::EscapesRoot; 2]);
+        assert_eq!(blocked[0].path, Path::new("link/secret"));
+    }
+
+    #[test]
+    fn deny_policy_refuses_symlinks_inside_root() {
+        let sandbox = Sandbox::new();
+        fs::write(sandbox.root().join("real"), "inside").unwrap();
+        symlink(sandbox.root().join("real"), sandbox.root().join("alias")).unwrap();
+        let fs = LocalFileSystem::new(sandbox.root()).unwrap();
+        assert_eq!(
+            block_on(fs.read_file(Path::new("alias"))).unwrap(),
+            "inside"
+        );
+
+        let fs = fs.with_symlink_policy(SymlinkPolicy::Deny);
+        assert!(block_on(fs.read_file(Path::new("alias"))).is_err());
+        assert_eq!(reasons(fs.audit()), [BlockReason::Symlink]);
+    }
+
+    #[test]
+    fn directory_swapped_after_check_is_refused() {
+        let sandbox = Sandbox::new();
+        fs::create_dir(sandbox.root().join("dir")).unwrap();
+        fs::write(sandbox.root().join("dir/secret"), "inside").unwrap();
+        let fs = LocalFileSystem::new(sandbox.root()).unwrap();
+        let relative = Path::new("dir/secret");
+        let checked = fs.jail(relative).unwrap();
+
+        // Swap the checked directory for a symlink out of the root before opening.
+        fs::remove_dir_all(sandbox.root().join("dir")).unwrap();
+        symlink(sandbox.outside(), sandbox.root().join("dir")).unwrap();
+
+        let error = fs
+            .open_checked(relative, &checked, fs::OpenOptions::new().read(true), false)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(reasons(fs.audit()), [BlockReason::EscapesRoot]);
+    }
+
+    #[test]
+    fn pinned_directory_survives_swap() {
+        let sandbox = Sandbox::new();
+        fs::create_dir(sandbox.root().join("dir")).unwrap();
+        let fs = LocalFileSystem::new(sandbox.root()).unwrap();
+        let pinned = fs.pin(Path::new("dir/new")).unwrap();
+
+        fs::rename(sandbox.root().join("dir"), sandbox.root().join("moved")).unwrap();
+        symlink(sandbox.outside(), sandbox.root().join("dir")).unwrap();
+
+        fs::write(pinned.path(), "written").unwrap();
+        assert!(!sandbox.outside().join("new").exists());
+        assert!(sandbox.root().join("moved/new").exists());
+    }
+
+    #[test]
+    fn glob_stays_inside_root() {
+        let sandbox = Sandbox::new();
+        fs::write(sandbox.root().join("inside"), "").unwrap();
+        symlink(sandbox.outside(), sandbox.root().join("link")).unwrap();
+        let fs = LocalFileSystem::new(sandbox.root()).unwrap();
+
+        assert_eq!(fs.glob("*").unwrap(), ["inside"]);
+        assert!(fs.glob("link/*").unwrap().is_empty());
+        for pattern in ["../*", "../outside/*", "*/../../*"] {
+            let error = fs.glob(pattern).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        }
+        let absolute = format!("{}/*", sandbox.outside().display());
+        assert!(fs.glob(&absolute).is_err());
+        assert_eq!(fs.audit().blocked().len(), 4);
+    }
+
+    #[test]
+    fn observer_sees_blocked_access() {
+        let sandbox = Sandbox::new();
+        let seen = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&seen);
+        let audit = AccessAudit::new().on_blocked(move |access| {
+            assert_eq!(access.reason, BlockReason::EscapesRoot);
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        let fs = LocalFileSystem::new(sandbox.root())
+            .unwrap()
+            .with_audit(audit.clone());
+
+        assert!(block_on(fs.read_file(Path::new("../outside/secret"))).is_err());
+        assert_eq!(seen.load(Ordering::Relaxed), 1);
+        assert_eq!(audit.blocked().len(), 1);
+    }
+
+    /// Every entry under `root` except the trash, with file bytes or `None` for directories.
+    fn tree(root: &Path) -> BTreeMap<PathBuf, Option<Vec<u8>>> {
+        let mut entries = BTreeMap::new();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                let relative = path.strip_prefix(root).unwrap().to_path_buf();
+                if relative == Path::new(TRASH_DIR) {
+                    continue;
+                }
+                if path.is_dir() {
+                    entries.insert(relative, None);
+                    pending.push(path);
+                } else {
+                    entries.insert(relative, Some(fs::read(&path).unwrap()));
+                }
+            }
+        }
+        entries
+    }
+
+    /// Run `operations` through the tool, then check `revert_all` restores the tree.
+    fn assert_reverts(sandbox: &Sandbox, operations: impl IntoIterator<Item = FsOperation>) {
+        let root = sandbox.root();
+        let before = tree(&root);
+        let tool = FileSystemTool::new(&root);
+        block_on(async {
+            for operation in operations {
+                tool.call(operation).await.unwrap();
+            }
+            assert_ne!(tree(&root), before);
+            tool.revert_all().await.unwrap();
+        });
+        assert_eq!(tree(&root), before);
+        assert!(tool.journal().entries().is_empty());
+    }
+
+    /// A sandbox whose root holds a text file, a binary file, and a small tree.
+    fn populated() -> Sandbox {
+        let sandbox = Sandbox::new();
+        let root = sandbox.root();
+        fs::write(root.join("notes.txt"), "first\nsecond\n").unwrap();
+        fs::write(root.join("image.bin"), [0x89, 0x50, 0x00, 0xff, 0xfe]).unwrap();
+        fs::create_dir_all(root.join("tree/sub")).unwrap();
+        fs::write(root.join("tree/a.txt"), "a").unwrap();
+        fs::write(root.join("tree/sub/b.bin"), [0xc3, 0x28]).unwrap();
+        sandbox
+    }
+
+    #[test]
+    fn reverts_write_edit_and_append() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            [
+                FsOperation::Write {
+                    path: "notes.txt".into(),
+                    content: "replaced".into(),
+                },
+                FsOperation::Write {
+                    path: "new.txt".into(),
+                    content: "created".into(),
+                },
+                FsOperation::Edit {
+                    path: "notes.txt".into(),
+                    old_text: "replaced".into(),
+                    new_text: "edited".into(),
+                    occurrence: None,
+                },
+                FsOperation::Append {
+                    path: "tree/a.txt".into(),
+                    content: "ppended".into(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn reverts_overwritten_binary_file() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            [FsOperation::Write {
+                path: "image.bin".into(),
+                content: "text".into(),
+            }],
+        );
+    }
+
+    #[test]
+    fn reverts_deleted_files() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            ["notes.txt", "image.bin"].map(|path| FsOperation::Delete {
+                path: path.into(),
+                recursive: None,
+                trash: None,
+            }),
+        );
+    }
+
+    #[test]
+    fn reverts_recursive_delete() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            [FsOperation::Delete {
+                path: "tree".into(),
+                recursive: Some(true),
+                trash: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn reverts_trashed_directory() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            [FsOperation::Delete {
+                path: "tree".into(),
+                recursive: None,
+                trash: Some(true),
+            }],
+        );
+    }
+
+    #[test]
+    fn reverts_copy_over_existing_file() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            [
+                FsOperation::Copy {
+                    from: "notes.txt".into(),
+                    to: "image.bin".into(),
+                },
+                FsOperation::Copy {
+                    from: "image.bin".into(),
+                    to: "copy.bin".into(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn reverts_moves() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            [
+                FsOperation::Move {
+                    from: "image.bin".into(),
+                    to: "notes.txt".into(),
+                },
+                FsOperation::Move {
+                    from: "tree".into(),
+                    to: "renamed".into(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn reverts_created_directories() {
+        let sandbox = populated();
+        assert_reverts(
+            &sandbox,
+            [
+                FsOperation::CreateDir {
+                    path: "x/y/z".into(),
+                },
+                FsOperation::CreateDir {
+                    path: "tree/sub/deeper".into(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn undo_refuses_externally_modified_paths() {
+        let sandbox = populated();
+        let root = sandbox.root();
+        let tool = FileSystemTool::new(&root);
+        block_on(async {
+            tool.call(FsOperation::Move {
+                from: "notes.txt".into(),
+                to: "moved.txt".into(),
+            })
+            .await
+            .unwrap();
+            fs::write(root.join("notes.txt"), "recreated").unwrap();
+            assert!(
+                tool.call(FsOperation::Undo {
+                    path: "notes.txt".into()
+                })
+                .await
+                .is_err()
+            );
+
+            fs::remove_file(root.join("notes.txt")).unwrap();
+            tool.call(FsOperation::Undo {
+                path: "notes.txt".into(),
+            })
+            .await
+            .unwrap();
+        });
+        assert_eq!(
+            fs::read_to_string(root.join("notes.txt")).unwrap(),
+            "first\nsecond\n"
+        );
+        assert!(!root.join("moved.txt").exists());
+    }
+
+    fn text(output: ToolOutput) -> String {
+        match output {
+            ToolOutput::Output { content, .. } => String::from_utf8(content).unwrap(),
+            other => panic!("expected text output, got {other:?}"),
+        }
+    }
+
+    /// A sandbox whose `long.txt` holds `line 1` to `line 10000`, CRLF-terminated.
+    fn long_file() -> Sandbox {
+        let sandbox = Sandbox::new();
+        let content: String = (1..=10_000).map(|n| format!("line {n}\r\n")).collect();
+        fs::write(sandbox.root().join("long.txt"), content).unwrap();
+        sandbox
+    }
+
+    #[test]
+    fn ranged_read_stops_after_requested_lines() {
+        let sandbox = long_file();
+        let tool = FileSystemTool::new(sandbox.root());
+        let read = |offset, limit| {
+            text(
+                block_on(tool.call(FsOperation::Read {
+                    path: "long.txt".into(),
+                    offset,
+                    limit,
+                }))
+                .unwrap(),
+            )
+        };
+
+        assert_eq!(
+            read(Some(3), Some(2)),
+            "line 3\nline 4\n[Lines 3-4; continue with offset=5]"
+        );
+        assert_eq!(
+            read(Some(9_999), Some(5)),
+            "line 9999\nline 10000\n[Lines 9999-10000 of 10000]"
+        );
+        assert_eq!(
+            read(Some(20_001), None),
+            "[No lines at offset 20001; file has 10000 lines]"
+        );
+    }
+
+    #[test]
+    fn ranged_read_of_invalid_utf8_reports_binary() {
+        let sandbox = Sandbox::new();
+        fs::write(sandbox.root().join("latin1.txt"), b"caf\xe9\n").unwrap();
+        let tool = FileSystemTool::new(sandbox.root());
+        let output = text(
+            block_on(tool.call(FsOperation::Read {
+                path: "latin1.txt".into(),
+                offset: Some(1),
+                limit: Some(1),
+            }))
+            .unwrap(),
+        );
+        assert!(output.contains(r#""binary": true"#), "{output}");
+    }
+
+    #[test]
+    fn head_and_tail_read_only_the_ends() {
+        let sandbox = long_file();
+        fs::write(sandbox.root().join("short.txt"), "a\nb\nc").unwrap();
+        fs::write(sandbox.root().join("empty.txt"), "").unwrap();
+        let tool = FileSystemTool::new(sandbox.root());
+        let head = |path: &str, lines| {
+            text(
+                block_on(tool.call(FsOperation::Head {
+                    path: path.into(),
+                    lines,
+                }))
+                .unwrap(),
+            )
+        };
+        let tail = |path: &str, lines| {
+            text(
+                block_on(tool.call(FsOperation::Tail {
+                    path: path.into(),
+                    lines,
+                }))
+                .unwrap(),
+            )
+        };
+
+        assert_eq!(
+            head("long.txt", Some(2)),
+            "line 1\nline 2\n[Lines 1-2; continue with offset=3]"
+        );
+        assert_eq!(
+            tail("long.txt", Some(2)),
+            "line 9999\nline 10000\n[Last 2 lines]"
+        );
+        assert_eq!(tail("short.txt", Some(2)), "b\nc\n[Lines 2-3 of 3]");
+        assert_eq!(tail("short.txt", None), "a\nb\nc\n[Lines 1-3 of 3]");
+        assert_eq!(
+            tail("empty.txt", None),
+            "[No lines at offset 1; file has 0 lines]"
+        );
+
+        // Spans many chunks when read backwards.
+        let many = tail("long.txt", Some(3_000));
+        let lines: Vec<&str> = many.lines().collect();
+        assert_eq!(lines.len(), 3_001);
+        assert_eq!(lines[0], "line 7001");
+        assert_eq!(lines[2_999], "line 10000");
+    }
 }
@@ -0,0 +1,166 @@
+//! Line windows backing `Read`'s `offset`/`limit`, `Head` and `Tail`.
+
+use std::io::{self, SeekFrom};
+
+use futures_lite::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt,
+};
+
+/// Bytes read per step when reading a file backwards.
+const TAIL_CHUNK_BYTES: usize = 8 * 1024;
+
+/// Consecutive lines read from part of a file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineWindow {
+    /// The lines, without their terminators.
+    pub lines: Vec<String>,
+    /// Number of lines in the whole file, if the read got far enough to know it.
+    pub total: Option<usize>,
+}
+
+impl LineWindow {
+    /// Up to `limit` lines of `content` starting at the 0-based line `start`.
+    pub(crate) fn from_text(content: &str, start: usize, limit: usize) -> Self {
+        Self {
+            lines: content
+                .lines()
+                .skip(start)
+                .take(limit)
+                .map(str::to_string)
+                .collect(),
+            total: Some(content.lines().count()),
+        }
+    }
+
+    /// The last `count` lines of `content`.
+    pub(crate) fn last_of(content: &str, count: usize) -> Self {
+        let start = content.lines().count().saturating_sub(count);
+        Self::from_text(content, start, count)
+    }
+
+    /// Render the lines starting at the 0-based line `start`, followed by a
+    /// footer giving the range shown and, when known, the file's line count.
+    pub(crate) fn render(&self, start: usize) -> String {
+        let end = start + self.lines.len();
+        let Some(total) = self.total else {
+            // The read stopped early, so the file continues past `end`.
+            return format!(
+                "{}\n[Lines {}-{end}; continue with offset={}]",
+                self.lines.join("\n"),
+                start + 1,
+                end + 1
+            );
+        };
+        if start >= total {
+            return format!("[No lines at offset {}; file has {total} lines]", start + 1);
+        }
+        let mut out = self.lines.join("\n");
+        out.push_str(&format!("\n[Lines {}-{end} of {total}", start + 1));
+        if end < total {
+            out.push_str(&format!("; continue with offset={}", end + 1));
+        }
+        out.push(']');
+        out
+    }
+
+    /// Render lines that end the file, followed by a footer giving the range
+    /// shown when the file's line count is known.
+    pub(crate) fn render_last(&self) -> String {
+        match self.total {
+            Some(total) => self.render(total - self.lines.len()),
+            None => format!(
+                "{}\n[Last {} lines]",
+                self.lines.join("\n"),
+                self.lines.len()
+            ),
+        }
+    }
+}
+
+/// Read up to `limit` lines from `reader` starting at the 0-based line `start`,
+/// stopping as soon as one line past them shows the file goes on.
+///
+/// # Errors
+/// Fails if reading fails, or with [`io::ErrorKind::InvalidData`] if a
+/// returned line is not UTF-8.
+pub(crate) async fn read_forward(
+    mut reader: impl AsyncBufRead + Unpin,
+    start: usize,
+    limit: usize,
+) -> io::Result<LineWindow> {
+    let mut lines = Vec::new();
+    let mut line = Vec::new();
+    let mut index = 0;
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line).await? == 0 {
+            return Ok(LineWindow {
+                lines,
+                total: Some(index),
+            });
+        }
+        if index >= start {
+            if lines.len() == limit {
+                return Ok(LineWindow { lines, total: None });
+            }
+            lines.push(decode(&line)?);
+        }
+        index += 1;
+    }
+}
+
+/// Read the last `count` lines of `file`, seeking back from its end one chunk
+/// at a time until enough line breaks have been seen.
+///
+/// # Errors
+/// Fails if reading fails, or with [`io::ErrorKind::InvalidData`] if a
+/// returned line is not UTF-8.
+pub(crate) async fn read_backward(
+    mut file: impl AsyncRead + AsyncSeek + Unpin,
+    count: usize,
+) -> io::Result<LineWindow> {
+    let mut pos = file.seek(SeekFrom::End(0)).await?;
+    let mut tail: Vec<u8> = Vec::new();
+    // Line breaks in `tail`, not counting the file's final terminator.
+    let mut breaks = 0;
+    while pos > 0 && breaks < count {
+        let len = usize::try_from(pos).map_or(TAIL_CHUNK_BYTES, |pos| pos.min(TAIL_CHUNK_BYTES));
+        pos -= len as u64;
+        file.seek(SeekFrom::Start(pos)).await?;
+        let mut chunk = vec![0; len];
+        file.read_exact(&mut chunk).await?;
+        breaks += chunk.iter().filter(|&&byte| byte == b'\n').count();
+        if tail.is_empty() && chunk.ends_with(b"\n") {
+            breaks -= 1;
+        }
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+    }
+
+    let reached_start = pos == 0;
+    if tail.is_empty() {
+        return Ok(LineWindow {
+            lines: Vec::new(),
+            total: reached_start.then_some(0),
+        });
+    }
+    let body = tail.strip_suffix(b"\n").unwrap_or(&tail);
+    let mut segments: Vec<&[u8]> = body.split(|&byte| byte == b'\n').collect();
+    if !reached_start {
+        // The first segment starts mid-line.
+        segments.remove(0);
+    }
+    let total = reached_start.then_some(segments.len());
+    let lines = segments[segments.len().saturating_sub(count)..]
+        .iter()
+        .map(|segment| decode(segment))
+        .collect::<io::Result<_>>()?;
+    Ok(LineWindow { lines, total })
+}
+
+/// Decode one line, dropping its `\n` or `\r\n` terminator if it has one.
+fn decode(line: &[u8]) -> io::Result<String> {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    String::from_utf8(line.to_vec()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
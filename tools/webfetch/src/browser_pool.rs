@@ -0,0 +1,498 @@
+//! Shared headless browser pool.
+//!
+//! Launching Chrome dominates headless fetch latency, so the pool keeps a single
+//! browser process alive and recycles its tabs between requests. The browser is
+//! launched lazily on the first lease and shut down again once the pool has been
+//! idle for [`BrowserPoolConfig::idle_timeout`].
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::fetch::{
+    ContinueRequestParams, DisableParams, EnableParams, EventRequestPaused, HeaderEntry,
+    RequestPattern,
+};
+use chromiumoxide::cdp::browser_protocol::page::AddScriptToEvaluateOnNewDocumentParams;
+use chromiumoxide::handler::viewport::Viewport;
+use chromiumoxide::page::Page;
+use futures_lite::StreamExt;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+const DEFAULT_MAX_PAGES: usize = 4;
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+const DEFAULT_SETTLE_DELAY: Duration = Duration::from_secs(5);
+const DEFAULT_SCROLL_DELAY: Duration = Duration::from_secs(2);
+
+/// Anti-detection browser arguments.
+const BROWSER_ARGS: &[&str] = &[
+    "--disable-blink-features=AutomationControlled",
+    "--disable-infobars",
+    "--disable-dev-shm-usage",
+    "--disable-gpu",
+    "--no-sandbox",
+    "--window-size=1920,1080",
+    "--start-maximized",
+    "--disable-extensions",
+    "--disable-popup-blocking",
+    "--disable-background-networking",
+    "--disable-sync",
+    "--disable-translate",
+    "--metrics-recording-only",
+    "--no-first-run",
+    "--safebrowsing-disable-auto-update",
+    "--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+];
+
+/// Injected before any page script runs to hide common automation fingerprints.
+const STEALTH_JS: &str = r"
+    Object.defineProperty(navigator, 'webdriver', { get: () => undefined });
+    Object.defineProperty(navigator, 'plugins', { get: () => [1, 2, 3, 4, 5] });
+    Object.defineProperty(navigator, 'languages', { get: () => ['en-US', 'en'] });
+    window.chrome = { runtime: {} };
+";
+
+/// Configuration for a [`BrowserPool`].
+#[derive(Debug, Clone)]
+pub struct BrowserPoolConfig {
+    /// Maximum number of pages (tabs) that can be leased concurrently.
+    pub max_pages: usize,
+    /// How long the pool may sit unused before the browser process is shut down.
+    pub idle_timeout: Duration,
+    /// Extra wait after navigation so client-side rendering can settle.
+    pub settle_delay: Duration,
+    /// Wait after scrolling so lazily loaded content can appear.
+    pub scroll_delay: Duration,
+}
+
+impl Default for BrowserPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_pages: DEFAULT_MAX_PAGES,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            settle_delay: DEFAULT_SETTLE_DELAY,
+            scroll_delay: DEFAULT_SCROLL_DELAY,
+        }
+    }
+}
+
+impl BrowserPoolConfig {
+    /// Set the maximum number of concurrently leased pages.
+    #[must_use]
+    pub const fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Set the idle shutdown timeout.
+    #[must_use]
+    pub const fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Set the post-navigation settle delay.
+    #[must_use]
+    pub const fn with_settle_delay(mut self, settle_delay: Duration) -> Self {
+        self.settle_delay = settle_delay;
+        self
+    }
+
+    /// Set the post-scroll delay.
+    #[must_use]
+    pub const fn with_scroll_delay(mut self, scroll_delay: Duration) -> Self {
+        self.scroll_delay = scroll_delay;
+        self
+    }
+}
+
+/// A pool of recycled pages backed by one lazily launched headless browser.
+///
+/// Cloning is cheap; clones share the same browser process.
+#[derive(Debug, Clone)]
+pub struct BrowserPool {
+    inner: Arc<PoolInner>,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    config: BrowserPoolConfig,
+    permits: Arc<Semaphore>,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    browser: Option<RunningBrowser>,
+    idle_pages: Vec<Page>,
+    /// Bumped on every lease so stale idle-shutdown timers can detect reuse.
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct RunningBrowser {
+    browser: Browser,
+    handler: JoinHandle<()>,
+}
+
+impl RunningBrowser {
+    async fn launch() -> Result<Self> {
+        debug!("Launching pooled headless browser");
+
+        let config = BrowserConfig::builder()
+            .viewport(Some(Viewport {
+                width: 1920,
+                height: 1080,
+                device_scale_factor: Some(1.0),
+                ..Default::default()
+            }))
+            .args(BROWSER_ARGS.iter().copied())
+            .build()
+            .map_err(|e| anyhow!("Browser config error: {e}"))?;
+
+        let (browser, mut handler) = Browser::launch(config)
+            .await
+            .map_err(|e| anyhow!("Failed to launch browser: {e}"))?;
+        let handler = tokio::spawn(async move { while handler.next().await.is_some() {} });
+
+        Ok(Self { browser, handler })
+    }
+
+    async fn new_page(&self) -> Result<Page> {
+        let page = self
+            .browser
+            .new_page("about:blank")
+            .await
+            .map_err(|e| anyhow!("Failed to create page: {e}"))?;
+        page.execute(AddScriptToEvaluateOnNewDocumentParams::new(STEALTH_JS))
+            .await
+            .ok();
+        Ok(page)
+    }
+
+    /// Whether the browser connection is still open; it closes when Chrome exits.
+    fn is_alive(&self) -> bool {
+        !self.handler.is_finished()
+    }
+
+    async fn shutdown(mut self) {
+        debug!("Shutting down idle headless browser");
+        self.browser.close().await.ok();
+        self.browser.wait().await.ok();
+        self.handler.abort();
+    }
+
+    /// Tear down a browser that stopped responding, without waiting on it.
+    async fn kill(mut self) {
+        self.browser.kill().await;
+        self.handler.abort();
+    }
+}
+
+impl Default for BrowserPool {
+    fn default() -> Self {
+        Self::new(BrowserPoolConfig::default())
+    }
+}
+
+impl BrowserPool {
+    /// Create a pool with the given configuration. No browser is launched until first use.
+    #[must_use]
+    pub fn new(config: BrowserPoolConfig) -> Self {
+        let max_pages = config.max_pages.max(1);
+        Self {
+            inner: Arc::new(PoolInner {
+                config,
+                permits: Arc::new(Semaphore::new(max_pages)),
+                state: Mutex::new(PoolState::default()),
+            }),
+        }
+    }
+
+    /// Process-wide pool used by [`HeadlessFetcher::default`](crate::HeadlessFetcher).
+    #[must_use]
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<BrowserPool> = OnceLock::new();
+        SHARED.get_or_init(Self::default).clone()
+    }
+
+    /// Returns the pool configuration.
+    #[must_use]
+    pub fn config(&self) -> &BrowserPoolConfig {
+        &self.inner.config
+    }
+
+    /// Render `url` in a pooled page and return the resulting HTML.
+    ///
+    /// # Errors
+    /// Returns an error if the browser cannot be launched or navigation fails.
+    pub async fn fetch_html(&self, url: &str) -> Result<String> {
+        self.fetch_html_with_headers(url, &[]).await
+    }
+
+    /// Like [`fetch_html`](Self::fetch_html), adding `headers` to requests for
+    /// `url`'s origin.
+    ///
+    /// Subresources from other origins are loaded without them, so credentials
+    /// for the target site are not sent to third parties.
+    ///
+    /// # Errors
+    /// Returns an error if the browser cannot be launched or navigation fails.
+    // `release` consumes the page as soon as rendering is done; clippy can't see that.
+    #[allow(clippy::significant_drop_tightening)]
+    pub async fn fetch_html_with_headers(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+    ) -> Result<String> {
+        let page = self.lease().await?;
+        let html = render(page.page(), url, headers, &self.inner.config).await;
+        page.release().await;
+        html
+    }
+
+    /// Lease a page from the pool, launching the browser if needed.
+    ///
+    /// Waits when `max_pages` pages are already leased.
+    ///
+    /// # Errors
+    /// Returns an error if the browser cannot be launched or a page cannot be opened.
+    pub async fn lease(&self) -> Result<PooledPage> {
+        let permit = Arc::clone(&self.inner.permits)
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("Browser pool closed: {e}"))?;
+
+        let mut state = self.inner.state.lock().await;
+        state.generation = state.generation.wrapping_add(1);
+
+        if state
+            .browser
+            .as_ref()
+            .is_some_and(|browser| !browser.is_alive())
+        {
+            debug!("Pooled headless browser exited; relaunching");
+            state.idle_pages.clear();
+            state.browser = None;
+        }
+
+        if let Some(page) = state.idle_pages.pop() {
+            return Ok(PooledPage::new(self.clone(), page, permit));
+        }
+
+        let page = match state.browser.as_ref() {
+            Some(browser) => match browser.new_page().await {
+                Ok(page) => page,
+                Err(err) => {
+                    // A hung or crashed browser can keep its connection open; start over once.
+                    debug!("Pooled headless browser failed to open a page ({err}); relaunching");
+                    state.idle_pages.clear();
+                    if let Some(stale) = state.browser.take() {
+                        stale.kill().await;
+                    }
+                    launch_page(&mut state).await?
+                }
+            },
+            None => launch_page(&mut state).await?,
+        };
+        Ok(PooledPage::new(self.clone(), page, permit))
+    }
+
+    /// Close the browser immediately, dropping all idle pages.
+    pub async fn shutdown(&self) {
+        let running = {
+            let mut state = self.inner.state.lock().await;
+            state.idle_pages.clear();
+            state.browser.take()
+        };
+        if let Some(running) = running {
+            running.shutdown().await;
+        }
+    }
+
+    async fn recycle(&self, page: Page) {
+        // Navigating away drops page state (timers, sockets) before the tab is reused.
+        let reusable = page.goto("about:blank").await.is_ok();
+
+        let mut state = self.inner.state.lock().await;
+        let generation = state.generation;
+        if reusable && state.browser.is_some() {
+            state.idle_pages.push(page);
+            drop(state);
+        } else {
+            drop(state);
+            page.close().await.ok();
+        }
+
+        self.schedule_idle_shutdown(generation);
+    }
+
+    fn schedule_idle_shutdown(&self, generation: u64) {
+        let pool = self.clone();
+        let idle_timeout = self.inner.config.idle_timeout;
+        tokio::spawn(async move {
+            tokio::time::sleep(idle_timeout).await;
+            pool.shutdown_if_idle(generation).await;
+        });
+    }
+
+    async fn shutdown_if_idle(&self, generation: u64) {
+        let running = {
+            let mut state = self.inner.state.lock().await;
+            let all_returned =
+                self.inner.permits.available_permits() == self.inner.config.max_pages.max(1);
+            if state.generation != generation || !all_returned {
+                return;
+            }
+            state.idle_pages.clear();
+            state.browser.take()
+        };
+        if let Some(running) = running {
+            running.shutdown().await;
+        }
+    }
+}
+
+/// A page leased from a [`BrowserPool`].
+///
+/// Call [`PooledPage::release`] to return the page for reuse; dropping it closes the tab instead.
+#[derive(Debug)]
+pub struct PooledPage {
+    pool: BrowserPool,
+    page: Option<Page>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl PooledPage {
+    const fn new(pool: BrowserPool, page: Page, permit: OwnedSemaphorePermit) -> Self {
+        Self {
+            pool,
+            page: Some(page),
+            _permit: permit,
+        }
+    }
+
+    /// Returns the underlying page.
+    ///
+    /// # Panics
+    /// Never panics; the page is only taken by `release` or `drop`.
+    #[must_use]
+    pub const fn page(&self) -> &Page {
+        self.page.as_ref().expect("pooled page already released")
+    }
+
+    /// Return the page to the pool so later requests can reuse it.
+    pub async fn release(mut self) {
+        if let Some(page) = self.page.take() {
+            self.pool.recycle(page).await;
+        }
+    }
+}
+
+impl Drop for PooledPage {
+    fn drop(&mut self) {
+        if let Some(page) = self.page.take() {
+            tokio::spawn(async move {
+                page.close().await.ok();
+            });
+        }
+    }
+}
+
+/// Launch a browser into `state` and open its first page.
+async fn launch_page(state: &mut PoolState) -> Result<Page> {
+    let browser = state.browser.insert(RunningBrowser::launch().await?);
+    browser.new_page().await
+}
+
+async fn render(
+    page: &Page,
+    url: &str,
+    headers: &[(String, String)],
+    config: &BrowserPoolConfig,
+) -> Result<String> {
+    let interceptor = if headers.is_empty() {
+        None
+    } else {
+        Some(intercept_origin(page, url, headers).await?)
+    };
+    let html = navigate(page, url, config).await;
+    if let Some(interceptor) = interceptor {
+        interceptor.abort();
+        page.execute(DisableParams::default()).await.ok();
+    }
+    html
+}
+
+/// Pause every request for `url`'s origin and continue it with `headers` added.
+///
+/// Returns the task answering paused requests; abort it once navigation is done.
+async fn intercept_origin(
+    page: &Page,
+    url: &str,
+    headers: &[(String, String)],
+) -> Result<JoinHandle<()>> {
+    let origin = url::Url::parse(url)
+        .map_err(|e| anyhow!("Invalid URL: {e}"))?
+        .origin()
+        .ascii_serialization();
+    let mut paused = page
+        .event_listener::<EventRequestPaused>()
+        .await
+        .map_err(|e| anyhow!("Failed to intercept requests: {e}"))?;
+    page.execute(EnableParams {
+        patterns: Some(vec![RequestPattern {
+            url_pattern: Some(format!("{origin}/*")),
+            resource_type: None,
+            request_stage: None,
+        }]),
+        handle_auth_requests: None,
+    })
+    .await
+    .map_err(|e| anyhow!("Failed to intercept requests: {e}"))?;
+
+    let page = page.clone();
+    let extra = headers.to_vec();
+    Ok(tokio::spawn(async move {
+        while let Some(event) = paused.next().await {
+            // Continuing with headers replaces the originals, so merge them in.
+            let mut merged: Vec<HeaderEntry> = event
+                .request
+                .headers
+                .inner()
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(name, _)| !extra.iter().any(|(n, _)| n.eq_ignore_ascii_case(name)))
+                .filter_map(|(name, value)| Some(HeaderEntry::new(name, value.as_str()?)))
+                .collect();
+            merged.extend(
+                extra
+                    .iter()
+                    .map(|(name, value)| HeaderEntry::new(name, value)),
+            );
+            let mut params = ContinueRequestParams::new(event.request_id.clone());
+            params.headers = Some(merged);
+            page.execute(params).await.ok();
+        }
+    }))
+}
+
+async fn navigate(page: &Page, url: &str, config: &BrowserPoolConfig) -> Result<String> {
+    page.goto(url)
+        .await
+        .map_err(|e| anyhow!("Failed to navigate: {e}"))?;
+
+    tokio::time::sleep(config.settle_delay).await;
+
+    // Scroll to trigger lazy loading
+    page.evaluate("window.scrollTo(0, 500)").await.ok();
+    tokio::time::sleep(config.scroll_delay).await;
+
+    page.content()
+        .await
+        .map_err(|e| anyhow!("Failed to get page content: {e}"))
+}
@@ -7,7 +7,7 @@
 //! - **Provider chain**: Jina Reader -> static fetch -> headless (optional)
 //! - **Static fetch**: HTTP fetching with markdown negotiation and HTML fallback
 //! - **Headless browser**: Full JavaScript rendering via Chrome DevTools Protocol
-//!   (enable with `headless` feature)
+//!   (enable with `headless` feature), backed by a pooled, lazily launched browser
 //! - **Image extraction**: Extract image URLs from pages
 //! - **Image fetching**: Fetch images with automatic JPEG conversion
 //!
@@ -60,6 +60,11 @@ use serde::{Deserialize, Serialize};
 use tracing::debug;
 use zenwave::{Client, ResponseExt, client, header};
 
+#[cfg(feature = "headless")]
+mod browser_pool;
+#[cfg(feature = "headless")]
+pub use browser_pool::{BrowserPool, BrowserPoolConfig, PooledPage};
+
 fn ensure_rustls_provider() {
     let _ = rustls::crypto::ring::default_provider().install_default();
 }
@@ -122,6 +127,26 @@ pub struct FetchRequest {
     pub jina_api_key: Option<String>,
     /// Total deadline budget for the full fallback chain.
     pub deadline: Duration,
+    /// Which fetcher(s) to run.
+    pub strategy: FetchStrategy,
+    /// Extra HTTP headers sent to the target host by the static and headless
+    /// fetchers. They are never forwarded to Jina Reader.
+    pub headers: Vec<(String, String)>,
+}
+
+/// Selects which fetcher(s) handle a [`FetchRequest`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchStrategy {
+    /// Run the default fallback chain.
+    #[default]
+    Auto,
+    /// Only use Jina Reader.
+    Jina,
+    /// Only use static HTTP fetching.
+    Static,
+    /// Only use the headless browser (requires the `headless` feature).
+    Headless,
 }
 
 impl FetchRequest {
@@ -132,6 +157,8 @@ impl FetchRequest {
             url: url.into(),
             jina_api_key: std::env::var(JINA_API_KEY_ENV).ok(),
             deadline: DEFAULT_TOTAL_BUDGET,
+            strategy: FetchStrategy::Auto,
+            headers: Vec::new(),
         }
     }
 
@@ -149,9 +176,51 @@ impl FetchRequest {
         self
     }
 
+    /// Override which fetcher(s) handle the request.
+    #[must_use]
+    pub const fn with_strategy(mut self, strategy: FetchStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Add an extra HTTP header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
     fn effective_jina_api_key(&self) -> Option<&str> {
         self.jina_api_key.as_deref()
     }
+
+    /// Headers sent to Jina Reader.
+    ///
+    /// Only the Jina API key is included; [`headers`](Self::headers) may carry
+    /// credentials for the target site and must not leave for a third party.
+    fn jina_headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![
+            (
+                header::ACCEPT.as_str().to_string(),
+                "text/markdown".to_string(),
+            ),
+            (
+                header::USER_AGENT.as_str().to_string(),
+                "aither-webfetch/0.1".to_string(),
+            ),
+        ];
+        if let Some(key) = self
+            .effective_jina_api_key()
+            .filter(|k| !k.trim().is_empty())
+        {
+            headers.push((
+                header::AUTHORIZATION.as_str().to_string(),
+                format!("Bearer {key}"),
+            ));
+            headers.push(("X-API-Key".to_string(), key.to_string()));
+        }
+        headers
+    }
 }
 
 /// Execution context shared by all fetch providers in a fallback chain.
@@ -302,7 +371,7 @@ pub type DefaultFetcher = Fallback2<JinaFetcher, StaticFetcher>;
 #[cfg(feature = "headless")]
 #[must_use]
 pub fn default_fetcher() -> DefaultFetcher {
-    Fallback3::new(JinaFetcher, StaticFetcher, HeadlessFetcher)
+    Fallback3::new(JinaFetcher, StaticFetcher, HeadlessFetcher::default())
 }
 
 /// Build the default fetcher chain.
@@ -462,8 +531,19 @@ pub async fn fetch_with_request(request: FetchRequest) -> Result<FetchResult> {
     ensure_rustls_provider();
     let mut ctx = FetchContext::new(request.deadline);
 
-    let pipeline = default_fetcher();
-    let result = pipeline.fetch(&request, &mut ctx).await;
+    let result = match request.strategy {
+        FetchStrategy::Auto => default_fetcher().fetch(&request, &mut ctx).await,
+        FetchStrategy::Jina => run_stage(&JinaFetcher, &request, &mut ctx).await,
+        FetchStrategy::Static => run_stage(&StaticFetcher, &request, &mut ctx).await,
+        #[cfg(feature = "headless")]
+        FetchStrategy::Headless => run_stage(&HeadlessFetcher::default(), &request, &mut ctx).await,
+        #[cfg(not(feature = "headless"))]
+        FetchStrategy::Headless => {
+            return Err(anyhow!(
+                "headless fetch requested but the `headless` feature is disabled"
+            ));
+        }
+    };
 
     result.map_err(anyhow::Error::from)
 }
@@ -480,7 +560,7 @@ pub async fn fetch_with_request(request: FetchRequest) -> Result<FetchResult> {
 pub async fn fetch_with_browser(url: &str) -> Result<FetchResult> {
     ensure_rustls_provider();
     let mut ctx = FetchContext::new(DEFAULT_TOTAL_BUDGET);
-    HeadlessFetcher
+    HeadlessFetcher::default()
         .fetch(&FetchRequest::new(url), &mut ctx)
         .await
         .map_err(anyhow::Error::from)
@@ -719,12 +799,13 @@ impl From<zenwave::Error> for FetchHttpError {
 async fn fetch_document_static(
     url: &str,
     accept: &str,
+    headers: &[(String, String)],
     timeout: Duration,
 ) -> std::result::Result<StaticFetchResponse, FetchHttpError> {
     let user_agent = get_user_agent(url);
 
     let mut backend = client().timeout(clamp_timeout(timeout));
-    let response = backend
+    let mut builder = backend
         .get(url)?
         .header(header::USER_AGENT.as_str(), user_agent)?
         .header(header::ACCEPT.as_str(), accept)?
@@ -740,7 +821,12 @@ async fn fetch_document_static(
         .header("Sec-Fetch-Site", "none")?
         .header("Sec-Fetch-User", "?1")?
         .header("Upgrade-Insecure-Requests", "1")?
-        .header("Cache-Control", "max-age=0")?
+        .header("Cache-Control", "max-age=0")?;
+    for (name, value) in headers {
+        builder = builder.header(name.as_str(), value.as_str())?;
+    }
+
+    let response = builder
         .await
         .map_err(|e| FetchHttpError::http(anyhow!("{e}")))?;
 
@@ -776,39 +862,35 @@ async fn fetch_document_static(
 
 async fn fetch_markdown_static(
     url: &str,
+    headers: &[(String, String)],
     timeout: Duration,
 ) -> std::result::Result<StaticFetchResponse, FetchHttpError> {
-    fetch_document_static(url, "text/markdown", timeout).await
+    fetch_document_static(url, "text/markdown", headers, timeout).await
 }
 
 async fn fetch_html_static(
     url: &str,
+    headers: &[(String, String)],
     timeout: Duration,
 ) -> std::result::Result<StaticFetchResponse, FetchHttpError> {
     fetch_document_static(
         url,
         "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+        headers,
         timeout,
     )
     .await
 }
 
 async fn fetch_document_jina(
-    url: &str,
-    api_key: Option<&str>,
+    req: &FetchRequest,
     timeout: Duration,
 ) -> std::result::Result<StaticFetchResponse, FetchHttpError> {
-    let jina_url = build_jina_url(url).map_err(FetchHttpError::decode)?;
+    let jina_url = build_jina_url(&req.url).map_err(FetchHttpError::decode)?;
     let mut backend = client().timeout(clamp_timeout(timeout));
-    let mut builder = backend
-        .get(&jina_url)?
-        .header(header::ACCEPT.as_str(), "text/markdown")?
-        .header(header::USER_AGENT.as_str(), "aither-webfetch/0.1")?;
-
-    if let Some(key) = api_key.filter(|k| !k.trim().is_empty()) {
-        builder = builder
-            .header(header::AUTHORIZATION.as_str(), format!("Bearer {key}"))?
-            .header("X-API-Key", key.to_string())?;
+    let mut builder = backend.get(&jina_url)?;
+    for (name, value) in &req.jina_headers() {
+        builder = builder.header(name.as_str(), value.as_str())?;
     }
 
     let response = builder
@@ -1007,7 +1089,7 @@ impl WebFetcher for JinaFetcher {
             ));
         }
 
-        let response = fetch_document_jina(&req.url, req.effective_jina_api_key(), jina_budget)
+        let response = fetch_document_jina(req, jina_budget)
             .await
             .map_err(|err| map_fetch_http_error(self.name(), err))?;
 
@@ -1046,7 +1128,7 @@ impl WebFetcher for StaticFetcher {
             ));
         }
 
-        let markdown_attempt = fetch_markdown_static(&req.url, static_budget)
+        let markdown_attempt = fetch_markdown_static(&req.url, &req.headers, static_budget)
             .await
             .map_err(|err| map_fetch_http_error(self.name(), err));
 
@@ -1073,7 +1155,7 @@ impl WebFetcher for StaticFetcher {
             ));
         }
 
-        let html_response = fetch_html_static(&req.url, html_budget)
+        let html_response = fetch_html_static(&req.url, &req.headers, html_budget)
             .await
             .map_err(|err| map_fetch_http_error(self.name(), err))?;
 
@@ -1099,9 +1181,46 @@ impl WebFetcher for StaticFetcher {
     }
 }
 
+/// Headless browser fetcher backed by a [`BrowserPool`].
+///
+/// [`HeadlessFetcher::new`] and the default instance use [`BrowserPool::shared`],
+/// so every fetcher in the process reuses the same browser.
+///
+/// This used to be a unit struct; code that named the value `HeadlessFetcher`
+/// directly must now call [`HeadlessFetcher::new`] or [`HeadlessFetcher::default`].
 #[cfg(feature = "headless")]
-#[derive(Debug, Clone, Copy, Default)]
-pub struct HeadlessFetcher;
+#[derive(Debug, Clone)]
+pub struct HeadlessFetcher {
+    pool: BrowserPool,
+}
+
+#[cfg(feature = "headless")]
+impl Default for HeadlessFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "headless")]
+impl HeadlessFetcher {
+    /// Create a fetcher that renders pages with the process-wide [`BrowserPool::shared`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_pool(BrowserPool::shared())
+    }
+
+    /// Create a fetcher that renders pages with the given pool.
+    #[must_use]
+    pub const fn with_pool(pool: BrowserPool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the pool used by this fetcher.
+    #[must_use]
+    pub const fn pool(&self) -> &BrowserPool {
+        &self.pool
+    }
+}
 
 #[cfg(feature = "headless")]
 #[allow(async_fn_in_trait)]
@@ -1122,7 +1241,9 @@ impl WebFetcher for HeadlessFetcher {
             ));
         }
 
-        let html = fetch_html_headless(&req.url)
+        let html = self
+            .pool
+            .fetch_html_with_headers(&req.url, &req.headers)
             .await
             .map_err(|err| ProviderError::http(self.name(), err))?;
         let mut result = html_to_result_with_metadata(&req.url, &html, None, None)
@@ -1133,94 +1254,11 @@ impl WebFetcher for HeadlessFetcher {
     }
 }
 
-/// Fetches raw HTML using a headless Chrome browser with anti-detection measures.
+/// Fetches raw HTML using the shared headless browser pool.
 #[cfg(feature = "headless")]
 async fn fetch_html_headless(url: &str) -> Result<String> {
-    use chromiumoxide::browser::{Browser, BrowserConfig};
-    use chromiumoxide::handler::viewport::Viewport;
-    use futures_lite::StreamExt;
-    use std::time::Duration;
-
-    debug!("Launching headless browser for {}", url);
-
-    // Anti-detection browser arguments
-    let args = [
-        "--disable-blink-features=AutomationControlled",
-        "--disable-infobars",
-        "--disable-dev-shm-usage",
-        "--disable-gpu",
-        "--no-sandbox",
-        "--window-size=1920,1080",
-        "--start-maximized",
-        "--disable-extensions",
-        "--disable-popup-blocking",
-        "--disable-background-networking",
-        "--disable-sync",
-        "--disable-translate",
-        "--metrics-recording-only",
-        "--no-first-run",
-        "--safebrowsing-disable-auto-update",
-        "--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-    ];
-
-    let config = BrowserConfig::builder()
-        .viewport(Some(Viewport {
-            width: 1920,
-            height: 1080,
-            device_scale_factor: Some(1.0),
-            ..Default::default()
-        }))
-        .args(args)
-        .build()
-        .map_err(|e| anyhow!("Browser config error: {e}"))?;
-
-    let (browser, mut handler) = Browser::launch(config)
-        .await
-        .map_err(|e| anyhow!("Failed to launch browser: {e}"))?;
-
-    // Spawn handler task
-    let handle = tokio::spawn(async move { while handler.next().await.is_some() {} });
-
-    // Create blank page first
-    let page = browser
-        .new_page("about:blank")
-        .await
-        .map_err(|e| anyhow!("Failed to create page: {e}"))?;
-
-    // Inject stealth script BEFORE navigation
-    let stealth_js = r#"
-        Object.defineProperty(navigator, 'webdriver', { get: () => undefined });
-        Object.defineProperty(navigator, 'plugins', { get: () => [1, 2, 3, 4, 5] });
-        Object.defineProperty(navigator, 'languages', { get: () => ['en-US', 'en'] });
-        window.chrome = { runtime: {} };
-    "#;
-
-    // Use Page.addScriptToEvaluateOnNewDocument to inject before any page script runs
-    use chromiumoxide::cdp::browser_protocol::page::AddScriptToEvaluateOnNewDocumentParams;
-    page.execute(AddScriptToEvaluateOnNewDocumentParams::new(stealth_js))
-        .await
-        .ok();
-
-    // Now navigate to the actual URL
-    page.goto(url)
-        .await
-        .map_err(|e| anyhow!("Failed to navigate: {e}"))?;
-
-    // Wait for page to load
-    tokio::time::sleep(Duration::from_millis(5000)).await;
-
-    // Scroll to trigger lazy loading
-    page.evaluate("window.scrollTo(0, 500)").await.ok();
-    tokio::time::sleep(Duration::from_millis(2000)).await;
-
-    let html = page
-        .content()
-        .await
-        .map_err(|e| anyhow!("Failed to get page content: {e}"))?;
-
-    handle.abort();
-
-    Ok(html)
+    debug!("Fetching {} with pooled headless browser", url);
+    BrowserPool::shared().fetch_html(url).await
 }
 
 /// Check if a URL looks like an image based on extension or known image hosts.
@@ -1278,13 +1316,86 @@ pub struct WebFetchArgs {
     pub timeout_ms: Option<u64>,
 }
 
+/// Per-domain overrides applied by [`WebFetchTool`].
+///
+/// A policy registered for `example.com` also applies to its subdomains,
+/// with the most specific registered domain winning.
+#[derive(Debug, Clone, Default)]
+pub struct DomainPolicy {
+    strategy: Option<FetchStrategy>,
+    headers: Vec<(String, String)>,
+    deadline: Option<Duration>,
+}
+
+impl DomainPolicy {
+    /// Create an empty policy that leaves requests unchanged.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Always render pages with the headless browser.
+    #[must_use]
+    pub const fn force_headless(self) -> Self {
+        self.with_strategy(FetchStrategy::Headless)
+    }
+
+    /// Always fetch through Jina Reader.
+    #[must_use]
+    pub const fn require_jina(self) -> Self {
+        self.with_strategy(FetchStrategy::Jina)
+    }
+
+    /// Always use static HTTP fetching.
+    #[must_use]
+    pub const fn force_static(self) -> Self {
+        self.with_strategy(FetchStrategy::Static)
+    }
+
+    /// Override the fetch strategy.
+    #[must_use]
+    pub const fn with_strategy(mut self, strategy: FetchStrategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Send an extra HTTP header to this domain, e.g. a session cookie.
+    ///
+    /// The header only reaches the target host through the static and headless
+    /// fetchers; Jina Reader never receives it.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Override the total fetch deadline for this domain.
+    #[must_use]
+    pub const fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    fn apply(&self, mut request: FetchRequest) -> FetchRequest {
+        if let Some(strategy) = self.strategy {
+            request.strategy = strategy;
+        }
+        if let Some(deadline) = self.deadline {
+            request.deadline = deadline;
+        }
+        request.headers.extend(self.headers.iter().cloned());
+        request
+    }
+}
+
 /// Web content fetching tool for LLM agents.
 ///
 /// Fetches web content and returns it in a format suitable for LLM consumption:
 /// - Webpages: Extracts main content, converts to markdown with images embedded
 /// - Images: Fetches and converts to JPEG format
 ///
-/// Supports URL filtering via whitelist/blacklist regex patterns.
+/// Supports URL filtering via whitelist/blacklist regex patterns and
+/// per-domain fetch policies (see [`DomainPolicy`]).
 #[derive(Debug, Clone, Default)]
 pub struct WebFetchTool {
     name: String,
@@ -1292,6 +1403,8 @@ pub struct WebFetchTool {
     whitelist: Vec<Regex>,
     /// URLs matching any pattern will be blocked.
     blacklist: Vec<Regex>,
+    /// Per-domain fetch overrides, keyed by lowercase domain.
+    policies: Vec<(String, DomainPolicy)>,
 }
 
 impl WebFetchTool {
@@ -1302,6 +1415,7 @@ impl WebFetchTool {
             name: "webfetch".into(),
             whitelist: Vec::new(),
             blacklist: Vec::new(),
+            policies: Vec::new(),
         }
     }
 
@@ -1334,6 +1448,34 @@ impl WebFetchTool {
         self
     }
 
+    /// Register a fetch policy for a domain and its subdomains.
+    #[must_use]
+    pub fn with_domain_policy(mut self, domain: &str, policy: DomainPolicy) -> Self {
+        let domain = domain.trim().trim_start_matches("*.").to_ascii_lowercase();
+        self.policies.retain(|(existing, _)| existing != &domain);
+        self.policies.push((domain, policy));
+        self
+    }
+
+    /// Find the most specific policy registered for the URL's host.
+    fn policy_for(&self, url: &str) -> Option<&DomainPolicy> {
+        let host = url::Url::parse(url)
+            .ok()?
+            .host_str()?
+            .trim_end_matches('.')
+            .to_ascii_lowercase();
+        self.policies
+            .iter()
+            .filter(|(domain, _)| {
+                host == *domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+            })
+            .max_by_key(|(domain, _)| domain.len())
+            .map(|(_, policy)| policy)
+    }
+
     /// Check if a URL is allowed by the whitelist/blacklist rules.
     fn is_url_allowed(&self, url: &str) -> bool {
         // Check blacklist first - blocked URLs are never allowed
@@ -1380,6 +1522,9 @@ impl Tool for WebFetchTool {
         if let Some(key) = jina_api_key.filter(|k| !k.trim().is_empty()) {
             request = request.with_jina_api_key(key);
         }
+        if let Some(policy) = self.policy_for(&url) {
+            request = policy.apply(request);
+        }
         if let Some(ms) = timeout_ms {
             request = request.with_deadline(Duration::from_millis(ms));
         }
@@ -1503,6 +1648,76 @@ mod tests {
         assert!(!tool.is_url_allowed("https://other.com/page"));
     }
 
+    #[test]
+    fn domain_policy_matches_subdomains() {
+        let tool = WebFetchTool::new()
+            .with_domain_policy("twitter.com", DomainPolicy::new().force_headless())
+            .with_domain_policy("docs.rs", DomainPolicy::new().force_static())
+            .with_domain_policy(
+                "api.docs.rs",
+                DomainPolicy::new().with_deadline(Duration::from_secs(20)),
+            );
+
+        let twitter = tool
+            .policy_for("https://mobile.twitter.com/rustlang")
+            .unwrap();
+        assert_eq!(twitter.strategy, Some(FetchStrategy::Headless));
+
+        let docs = tool.policy_for("https://docs.rs/serde").unwrap();
+        assert_eq!(docs.strategy, Some(FetchStrategy::Static));
+
+        let api = tool.policy_for("https://api.docs.rs/crate").unwrap();
+        assert_eq!(api.deadline, Some(Duration::from_secs(20)));
+        assert_eq!(api.strategy, None);
+
+        assert!(tool.policy_for("https://nottwitter.com/").is_none());
+        assert!(tool.policy_for("not a url").is_none());
+    }
+
+    #[test]
+    fn domain_policy_applies_to_request() {
+        let policy = DomainPolicy::new()
+            .require_jina()
+            .with_header("Cookie", "session=1")
+            .with_deadline(Duration::from_secs(3));
+        let request = policy.apply(FetchRequest::new("https://example.com"));
+
+        assert_eq!(request.strategy, FetchStrategy::Jina);
+        assert_eq!(request.deadline, Duration::from_secs(3));
+        assert_eq!(
+            request.headers,
+            vec![("Cookie".to_string(), "session=1".to_string())]
+        );
+    }
+
+    #[test]
+    fn jina_request_omits_domain_policy_headers() {
+        let policy = DomainPolicy::new()
+            .with_header("Cookie", "session=1")
+            .with_header("Authorization", "Bearer site-token");
+        let mut request = policy.apply(FetchRequest::new("https://example.com"));
+        request.jina_api_key = None;
+
+        let leaked = |headers: &[(String, String)]| {
+            headers.iter().any(|(name, value)| {
+                name.eq_ignore_ascii_case("cookie") || value.contains("site-token")
+            })
+        };
+        assert!(leaked(&request.headers));
+        assert!(!leaked(&request.jina_headers()));
+        assert!(
+            !request
+                .jina_headers()
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+        );
+
+        let request = request.with_jina_api_key("jina-key");
+        let jina = request.jina_headers();
+        assert!(!leaked(&jina));
+        assert!(jina.contains(&("authorization".to_string(), "Bearer jina-key".to_string())));
+    }
+
     #[test]
     fn markdown_content_type_detection() {
         assert!(is_markdown_response(
@@ -1661,4 +1876,18 @@ mod headless_tests {
         let result = fetch_smart("https://example.com").await;
         assert!(result.is_ok(), "Smart fetch failed: {:?}", result.err());
     }
+
+    #[tokio::test]
+    async fn pooled_fetcher_reuses_browser() {
+        let pool = BrowserPool::new(BrowserPoolConfig::default().with_max_pages(1));
+        let fetcher = HeadlessFetcher::with_pool(pool.clone());
+        for _ in 0..2 {
+            let mut ctx = FetchContext::new(Duration::from_secs(30));
+            let result = fetcher
+                .fetch(&FetchRequest::new("https://example.com"), &mut ctx)
+                .await;
+            assert!(result.is_ok(), "Pooled fetch failed: {:?}", result.err());
+        }
+        pool.shutdown().await;
+    }
 }
@@ -0,0 +1,177 @@
+//! Typed errors shared by all search providers.
+
+use std::fmt;
+
+/// Error returned by built-in [`SearchProvider`](crate::SearchProvider) implementations.
+///
+/// Providers surface this through `anyhow::Error`; use
+/// [`anyhow::Error::downcast_ref`] to inspect it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchError {
+    /// No API key was configured for a provider that requires one.
+    MissingApiKey {
+        /// Provider name.
+        provider: &'static str,
+        /// Environment variable consulted by `from_env`.
+        env: &'static str,
+    },
+    /// The provider rejected the request because a quota or rate limit was hit.
+    RateLimited {
+        /// Provider name.
+        provider: &'static str,
+    },
+    /// The provider (or its upstream engines) demanded a CAPTCHA.
+    Captcha {
+        /// Provider name.
+        provider: &'static str,
+        /// Upstream engines that were blocked, when known.
+        engines: Vec<String>,
+    },
+    /// Transport or HTTP status failure.
+    Http {
+        /// Provider name.
+        provider: &'static str,
+        /// HTTP status code, when the server responded.
+        status: Option<u16>,
+        /// Error message.
+        message: String,
+    },
+    /// The provider answered with a payload that could not be understood.
+    InvalidResponse {
+        /// Provider name.
+        provider: &'static str,
+        /// Error message.
+        message: String,
+    },
+}
+
+impl SearchError {
+    /// Map a transport error into a typed search error.
+    pub(crate) fn http(provider: &'static str, err: zenwave::Error) -> Self {
+        if let zenwave::Error::Http { status, .. } = &err {
+            if status.as_u16() == 429 {
+                return Self::RateLimited { provider };
+            }
+            return Self::Http {
+                provider,
+                status: Some(status.as_u16()),
+                message: err.to_string(),
+            };
+        }
+        Self::Http {
+            provider,
+            status: None,
+            message: err.to_string(),
+        }
+    }
+
+    /// Build an invalid-response error.
+    pub(crate) fn invalid(provider: &'static str, message: impl Into<String>) -> Self {
+        Self::InvalidResponse {
+            provider,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the provider that produced this error.
+    #[must_use]
+    pub const fn provider(&self) -> &'static str {
+        match self {
+            Self::MissingApiKey { provider, .. }
+            | Self::RateLimited { provider }
+            | Self::Captcha { provider, .. }
+            | Self::Http { provider, .. }
+            | Self::InvalidResponse { provider, .. } => provider,
+        }
+    }
+
+    /// Whether retrying the same provider right away may succeed.
+    ///
+    /// Configuration problems, CAPTCHAs, quota exhaustion, and client errors (4xx) are final.
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        match self {
+            Self::Http { status, .. } => !matches!(status, Some(400..=499)),
+            Self::InvalidResponse { .. } => true,
+            Self::MissingApiKey { .. } | Self::RateLimited { .. } | Self::Captcha { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingApiKey { provider, env } => {
+                write!(f, "{provider}: missing API key (set {env})")
+            }
+            Self::RateLimited { provider } => write!(f, "{provider}: rate limit exceeded"),
+            Self::Captcha { provider, engines } if engines.is_empty() => {
+                write!(f, "{provider}: CAPTCHA detected")
+            }
+            Self::Captcha { provider, engines } => write!(
+                f,
+                "{provider}: CAPTCHA detected for engines: {}",
+                engines.join(", ")
+            ),
+            Self::Http {
+                provider,
+                status: Some(status),
+                message,
+            } => write!(f, "{provider}: HTTP {status}: {message}"),
+            Self::Http {
+                provider, message, ..
+            } => write!(f, "{provider}: HTTP error: {message}"),
+            Self::InvalidResponse { provider, message } => {
+                write!(f, "{provider}: invalid response: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SearchError {}
+
+/// Read a non-empty API key from the environment.
+pub(crate) fn api_key_from_env(
+    provider: &'static str,
+    env: &'static str,
+) -> Result<String, SearchError> {
+    std::env::var(env)
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+        .ok_or(SearchError::MissingApiKey { provider, env })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retryability() {
+        let server = SearchError::Http {
+            provider: "brave",
+            status: Some(503),
+            message: String::new(),
+        };
+        let client = SearchError::Http {
+            provider: "brave",
+            status: Some(401),
+            message: String::new(),
+        };
+        assert!(server.is_retryable());
+        assert!(!client.is_retryable());
+        assert!(!SearchError::RateLimited { provider: "brave" }.is_retryable());
+    }
+
+    #[test]
+    fn missing_env_key_is_typed() {
+        let err = api_key_from_env("test", "AITHER_WEBSEARCH_TEST_UNSET_KEY").unwrap_err();
+        assert_eq!(
+            err,
+            SearchError::MissingApiKey {
+                provider: "test",
+                env: "AITHER_WEBSEARCH_TEST_UNSET_KEY"
+            }
+        );
+        assert!(err.to_string().contains("AITHER_WEBSEARCH_TEST_UNSET_KEY"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Provider fallback chains.
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{SearchError, SearchOptions, SearchProvider, SearchResult};
+
+/// Outcome of a single provider attempt.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SearchOutcome {
+    /// The provider returned at least one result.
+    Success,
+    /// The provider answered but found nothing.
+    Empty,
+    /// The provider failed.
+    Failure,
+}
+
+/// Per-provider trace information.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchTrace {
+    /// Provider name.
+    pub provider: String,
+    /// Time spent in the provider.
+    pub elapsed_ms: u128,
+    /// Attempt outcome.
+    pub outcome: SearchOutcome,
+    /// Error message for failures.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl SearchTrace {
+    pub(crate) fn record(
+        provider: &str,
+        elapsed: Duration,
+        result: &Result<Vec<SearchResult>>,
+    ) -> Self {
+        let (outcome, message) = match result {
+            Ok(results) if results.is_empty() => (SearchOutcome::Empty, None),
+            Ok(_) => (SearchOutcome::Success, None),
+            Err(err) => (SearchOutcome::Failure, Some(err.to_string())),
+        };
+        Self {
+            provider: provider.to_string(),
+            elapsed_ms: elapsed.as_millis(),
+            outcome,
+            message,
+        }
+    }
+}
+
+/// Tries `primary`, then `fallback` when the primary provider is unavailable.
+///
+/// A provider counts as unavailable on transport/HTTP failures, rate limits,
+/// CAPTCHAs, missing API keys, and untyped errors from custom providers.
+/// Malformed responses ([`SearchError::InvalidResponse`]) are returned as-is.
+/// Chain more providers with [`FallbackSearchProvider::or`].
+#[derive(Debug, Clone)]
+pub struct FallbackSearchProvider<A, B> {
+    primary: A,
+    fallback: B,
+    last_trace: Arc<Mutex<Vec<SearchTrace>>>,
+}
+
+impl<A, B> FallbackSearchProvider<A, B> {
+    /// Create a two-provider chain.
+    #[must_use]
+    pub fn new(primary: A, fallback: B) -> Self {
+        Self {
+            primary,
+            fallback,
+            last_trace: Arc::default(),
+        }
+    }
+
+    /// Append another provider to the end of the chain.
+    #[must_use]
+    pub fn or<C>(self, next: C) -> FallbackSearchProvider<Self, C> {
+        FallbackSearchProvider::new(self, next)
+    }
+
+    /// Traces of the most recent search, in execution order.
+    #[must_use]
+    pub fn last_trace(&self) -> Vec<SearchTrace> {
+        self.last_trace
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+fn should_fall_back(err: &anyhow::Error) -> bool {
+    !matches!(
+        err.downcast_ref::<SearchError>(),
+        Some(SearchError::InvalidResponse { .. })
+    )
+}
+
+impl<A, B> SearchProvider for FallbackSearchProvider<A, B>
+where
+    A: SearchProvider,
+    B: SearchProvider,
+{
+    fn name(&self) -> &'static str {
+        "fallback"
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let mut trace = Vec::new();
+        let result = self.search_traced(query, limit, options, &mut trace).await;
+        *self
+            .last_trace
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = trace;
+        result
+    }
+
+    async fn search_traced(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+        trace: &mut Vec<SearchTrace>,
+    ) -> Result<Vec<SearchResult>> {
+        match self
+            .primary
+            .search_traced(query, limit, options, trace)
+            .await
+        {
+            Err(err) if should_fall_back(&err) => {
+                tracing::warn!(
+                    provider = self.primary.name(),
+                    error = %err,
+                    "search provider unavailable, falling back"
+                );
+                self.fallback
+                    .search_traced(query, limit, options, trace)
+                    .await
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct MockProvider {
+        name: &'static str,
+        calls: Arc<AtomicUsize>,
+        result: std::result::Result<usize, SearchError>,
+    }
+
+    impl MockProvider {
+        fn new(name: &'static str, result: std::result::Result<usize, SearchError>) -> Self {
+            Self {
+                name,
+                calls: Arc::default(),
+                result,
+            }
+        }
+    }
+
+    impl SearchProvider for MockProvider {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn search(&self, _query: &str, _limit: usize) -> Result<Vec<SearchResult>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match &self.result {
+                Ok(count) => Ok((0..*count)
+                    .map(|i| SearchResult {
+                        title: format!("{} {i}", self.name),
+                        url: format!("https://{}.example/{i}", self.name),
+                        snippet: String::new(),
+                    })
+                    .collect()),
+                Err(err) => Err(err.clone().into()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_rate_limit_and_flattens_trace() {
+        let first = MockProvider::new("first", Err(SearchError::RateLimited { provider: "first" }));
+        let second = MockProvider::new(
+            "second",
+            Err(SearchError::MissingApiKey {
+                provider: "second",
+                env: "SECOND_KEY",
+            }),
+        );
+        let third = MockProvider::new("third", Ok(2));
+        let third_calls = Arc::clone(&third.calls);
+
+        let chain = FallbackSearchProvider::new(first, second).or(third);
+        let results = chain.search("rust", 5).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(third_calls.load(Ordering::SeqCst), 1);
+        let trace = chain.last_trace();
+        let providers: Vec<_> = trace.iter().map(|t| t.provider.as_str()).collect();
+        assert_eq!(providers, ["first", "second", "third"]);
+        assert_eq!(trace[0].outcome, SearchOutcome::Failure);
+        assert_eq!(trace[2].outcome, SearchOutcome::Success);
+    }
+
+    #[tokio::test]
+    async fn stops_on_invalid_response() {
+        let first = MockProvider::new("first", Err(SearchError::invalid("first", "bad json")));
+        let second = MockProvider::new("second", Ok(1));
+        let second_calls = Arc::clone(&second.calls);
+
+        let chain = FallbackSearchProvider::new(first, second);
+        assert!(chain.search("rust", 5).await.is_err());
+        assert_eq!(second_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(chain.last_trace().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn empty_results_do_not_fall_back() {
+        let chain = FallbackSearchProvider::new(
+            MockProvider::new("first", Ok(0)),
+            MockProvider::new("second", Ok(3)),
+        );
+        assert!(chain.search("rust", 5).await.unwrap().is_empty());
+        assert_eq!(chain.last_trace()[0].outcome, SearchOutcome::Empty);
+    }
+}
@@ -21,12 +21,45 @@
 //! |----------|---------|-------------|
 //! | [`SearXNG`] | Not required | **Default** - Free metasearch engine |
 //! | [`DuckDuckGo`] | Not required | Instant answers only (not full web search) |
+//! | [`DuckDuckGoHtml`] | Not required | Full web results scraped from the HTML endpoint |
 //! | [`BraveSearch`] | Required | Privacy-first search with independent index |
 //! | [`Tavily`] | Required | AI-optimized search for RAG workflows |
 //! | [`GoogleSearch`] | Required (+CX ID) | Google Custom Search API |
 //! | [`Serper`] | Required | Fast Google SERP API |
+//! | [`SerpApi`] | Required | Structured SERP scraping for Google, Bing, and more |
 //! | [`Exa`] | Required | Semantic web search optimized for AI |
 //!
+//! Each provider lives behind a cargo feature of the same name (`brave`,
+//! `duckduckgo`, `exa`, `google`, `searxng`, `serpapi`, `serper`, `tavily`),
+//! all enabled by default. Key-based providers offer a `from_env()` constructor
+//! and every provider reports failures as a typed [`SearchError`].
+//!
+//! # Post-processing
+//!
+//! [`WebSearchTool`] removes duplicate results (same canonical URL, or the same
+//! title on the same host) before returning them. With the `enrich` feature,
+//! [`WebSearchTool::with_snippet_enrichment`] fills empty snippets by fetching
+//! the top pages through `aither-webfetch`.
+//!
+//! # Fallback Chains
+//!
+//! [`FallbackSearchProvider`] tries providers in order, moving on when one is
+//! unreachable, rate limited, or unconfigured:
+//!
+//! ```no_run
+//! use aither_websearch::{BraveSearch, FallbackSearchProvider, SearXNG, Tavily, WebSearchTool};
+//!
+//! let chain = FallbackSearchProvider::new(Tavily::new("KEY"), BraveSearch::new("KEY"))
+//!     .or(SearXNG::default());
+//! let tool = WebSearchTool::new(chain);
+//! ```
+//!
+//! # Query Planning
+//!
+//! [`QueryExpander`] uses a language model to turn a research question into
+//! several diverse (and optionally localized) queries, each carrying the
+//! [`SearchOptions`] to run it with.
+//!
 //! # Custom Provider
 //!
 //! ```no_run
@@ -35,9 +68,19 @@
 //! let tool = WebSearchTool::new(Tavily::new("YOUR_API_KEY"));
 //! ```
 
+mod error;
+mod fallback;
+mod options;
+mod postprocess;
 mod providers;
+mod query;
 
+pub use error::SearchError;
+pub use fallback::{FallbackSearchProvider, SearchOutcome, SearchTrace};
+pub use options::{Freshness, SafeSearch, SearchOptions};
+pub use postprocess::{canonical_url, dedupe_results};
 pub use providers::*;
+pub use query::{ExpandedQuery, QueryExpander};
 
 use std::borrow::Cow;
 
@@ -65,6 +108,35 @@ pub struct WebSearchArgs {
     /// Maximum number of results to return (1-10).
     #[serde(default = "default_limit")]
     pub limit: usize,
+
+    /// Only return results from this domain, e.g. `docs.rs`. Prefer this over a `site:` operator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site: Option<String>,
+
+    /// Only return pages published within this period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<Freshness>,
+
+    /// Preferred result language as an ISO 639-1 code, e.g. `en` or `ja`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Safe-search level. Leave unset to use the provider default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe_search: Option<SafeSearch>,
+}
+
+impl WebSearchArgs {
+    /// Scoping options carried by these arguments.
+    #[must_use]
+    pub fn options(&self) -> SearchOptions {
+        SearchOptions {
+            site: self.site.clone(),
+            freshness: self.freshness,
+            language: self.language.clone(),
+            safe_search: self.safe_search,
+        }
+    }
 }
 
 fn default_limit() -> usize {
@@ -84,14 +156,56 @@ pub trait SearchProvider: Send + Sync {
         query: &str,
         limit: usize,
     ) -> impl Future<Output = Result<Vec<SearchResult>>> + Send;
+
+    /// Search with scoping options.
+    ///
+    /// The default implementation only honors `site` (as a `site:` operator);
+    /// providers override this to map options onto native parameters.
+    fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> impl Future<Output = Result<Vec<SearchResult>>> + Send {
+        let query = options.query_with_site(query);
+        async move { self.search(&query, limit).await }
+    }
+
+    /// Short provider name used in errors and traces.
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    /// Search while appending a [`SearchTrace`] per provider attempt.
+    ///
+    /// Combinators such as [`FallbackSearchProvider`] override this so nested
+    /// chains produce one flat trace.
+    fn search_traced(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+        trace: &mut Vec<SearchTrace>,
+    ) -> impl Future<Output = Result<Vec<SearchResult>>> + Send {
+        async move {
+            let started = std::time::Instant::now();
+            let result = self.search_with_options(query, limit, options).await;
+            trace.push(SearchTrace::record(self.name(), started.elapsed(), &result));
+            result
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct WebSearchTool<P> {
     provider: P,
     name: String,
+    dedupe: bool,
+    #[cfg(feature = "enrich")]
+    enrich_top: usize,
 }
 
+#[cfg(feature = "searxng")]
 impl Default for WebSearchTool<SearXNG> {
     fn default() -> Self {
         Self::new(SearXNG::default())
@@ -101,10 +215,7 @@ impl Default for WebSearchTool<SearXNG> {
 impl<P> WebSearchTool<P> {
     /// Create a web search tool with a custom provider.
     pub fn new(provider: P) -> Self {
-        Self {
-            provider,
-            name: "websearch".into(),
-        }
+        Self::with_name(provider, "websearch")
     }
 
     /// Create a web search tool with custom name.
@@ -112,19 +223,89 @@ impl<P> WebSearchTool<P> {
         Self {
             provider,
             name: name.into(),
+            dedupe: true,
+            #[cfg(feature = "enrich")]
+            enrich_top: 0,
+        }
+    }
+
+    /// Return provider results verbatim instead of deduplicating them.
+    #[must_use]
+    pub const fn without_dedupe(mut self) -> Self {
+        self.dedupe = false;
+        self
+    }
+
+    /// Fill empty snippets of the top `top_n` results by fetching the pages.
+    ///
+    /// Disabled (`0`) by default since every enriched result costs a page fetch.
+    #[cfg(feature = "enrich")]
+    #[must_use]
+    pub const fn with_snippet_enrichment(mut self, top_n: usize) -> Self {
+        self.enrich_top = top_n;
+        self
+    }
+
+    /// Number of results to request so deduplication can still fill `limit`.
+    const fn fetch_limit(&self, limit: usize) -> usize {
+        if self.dedupe {
+            limit + limit / 2
+        } else {
+            limit
+        }
+    }
+
+    async fn postprocess(&self, results: Vec<SearchResult>, limit: usize) -> Vec<SearchResult> {
+        let mut results = if self.dedupe {
+            dedupe_results(results)
+        } else {
+            results
+        };
+        results.truncate(limit);
+        #[cfg(feature = "enrich")]
+        self.enrich(&mut results).await;
+        results
+    }
+
+    #[cfg(feature = "enrich")]
+    async fn enrich(&self, results: &mut [SearchResult]) {
+        for result in results.iter_mut().take(self.enrich_top) {
+            if !result.snippet.trim().is_empty() {
+                continue;
+            }
+            let request = aither_webfetch::FetchRequest::new(result.url.clone())
+                .with_deadline(ENRICH_DEADLINE);
+            match aither_webfetch::fetch_with_request(request).await {
+                Ok(page) => {
+                    result.snippet =
+                        postprocess::snippet_from_markdown(&page.content, ENRICH_SNIPPET_CHARS);
+                }
+                Err(e) => {
+                    tracing::debug!(url = %result.url, error = %e, "snippet enrichment failed");
+                }
+            }
         }
     }
 }
 
+/// Per-page deadline for snippet enrichment fetches.
+#[cfg(feature = "enrich")]
+const ENRICH_DEADLINE: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Maximum length of an enriched snippet, in characters.
+#[cfg(feature = "enrich")]
+const ENRICH_SNIPPET_CHARS: usize = 300;
+
 /// Maximum retry attempts when search returns empty results.
 const MAX_RETRIES: u32 = 3;
 
 /// Delay between retry attempts in milliseconds.
 const RETRY_DELAY_MS: u64 = 500;
 
-/// Check if an error is non-retryable (e.g., CAPTCHA).
+/// Check if an error is non-retryable (e.g., CAPTCHA or a missing API key).
 fn is_non_retryable(e: &anyhow::Error) -> bool {
-    e.to_string().contains("CAPTCHA")
+    e.downcast_ref::<SearchError>()
+        .is_some_and(|err| !err.is_retryable())
 }
 
 impl<P> Tool for WebSearchTool<P>
@@ -139,11 +320,17 @@ where
 
     async fn call(&self, arguments: Self::Arguments) -> aither_core::Result<ToolOutput> {
         let limit = arguments.limit.clamp(1, 10);
+        let options = arguments.options();
 
         // Retry on empty results (search engines may temporarily fail)
         for attempt in 0..MAX_RETRIES {
-            match self.provider.search(&arguments.query, limit).await {
+            match self
+                .provider
+                .search_with_options(&arguments.query, self.fetch_limit(limit), &options)
+                .await
+            {
                 Ok(results) if !results.is_empty() => {
+                    let results = self.postprocess(results, limit).await;
                     return Ok(ToolOutput::text(json(&results)));
                 }
                 Ok(_empty) if attempt < MAX_RETRIES - 1 => {
@@ -0,0 +1,140 @@
+//! Query scoping options shared by all providers.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Restrict results to recently published pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Freshness {
+    /// Past 24 hours.
+    Day,
+    /// Past week.
+    Week,
+    /// Past month.
+    Month,
+    /// Past year.
+    Year,
+}
+
+impl Freshness {
+    /// Single-letter code used by Google-style `qdr:` and DuckDuckGo `df=` parameters.
+    #[must_use]
+    pub const fn letter(self) -> &'static str {
+        match self {
+            Self::Day => "d",
+            Self::Week => "w",
+            Self::Month => "m",
+            Self::Year => "y",
+        }
+    }
+
+    /// Lowercase word (`day`, `week`, ...) used by SearXNG and Tavily.
+    #[must_use]
+    pub const fn word(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+}
+
+/// Safe-search filtering level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SafeSearch {
+    /// No filtering.
+    Off,
+    /// Filter explicit images and video.
+    Moderate,
+    /// Filter all explicit content.
+    Strict,
+}
+
+/// Optional scoping applied to a search.
+///
+/// Providers map each option onto their native parameters where one exists;
+/// `site` falls back to a `site:` query operator everywhere else, and
+/// unsupported options are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchOptions {
+    /// Only return results from this domain (e.g. `docs.rs`).
+    pub site: Option<String>,
+    /// Only return recently published results.
+    pub freshness: Option<Freshness>,
+    /// Preferred result language as an ISO 639-1 code (e.g. `en`, `de`).
+    pub language: Option<String>,
+    /// Safe-search level.
+    pub safe_search: Option<SafeSearch>,
+}
+
+impl SearchOptions {
+    /// Returns `true` if no option is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.site.is_none()
+            && self.freshness.is_none()
+            && self.language.is_none()
+            && self.safe_search.is_none()
+    }
+
+    /// Normalized `site` value without scheme, path, or surrounding whitespace.
+    #[must_use]
+    pub fn site(&self) -> Option<&str> {
+        self.site
+            .as_deref()
+            .map(|site| {
+                let site = site.trim();
+                let site = site.split_once("://").map_or(site, |(_, rest)| rest);
+                site.split('/').next().unwrap_or(site)
+            })
+            .filter(|site| !site.is_empty())
+    }
+
+    /// Normalized lowercase language code.
+    #[must_use]
+    pub fn language(&self) -> Option<String> {
+        self.language
+            .as_deref()
+            .map(|lang| lang.trim().to_ascii_lowercase())
+            .filter(|lang| !lang.is_empty())
+    }
+
+    /// Prefix `query` with a `site:` operator when a site is set.
+    #[must_use]
+    pub fn query_with_site(&self, query: &str) -> String {
+        match self.site() {
+            Some(site) => format!("site:{site} {query}"),
+            None => query.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn site_is_normalized_into_operator() {
+        let options = SearchOptions {
+            site: Some(" https://docs.rs/tokio ".to_string()),
+            ..SearchOptions::default()
+        };
+        assert_eq!(options.site(), Some("docs.rs"));
+        assert_eq!(options.query_with_site("spawn"), "site:docs.rs spawn");
+        assert_eq!(SearchOptions::default().query_with_site("spawn"), "spawn");
+    }
+
+    #[test]
+    fn empty_values_are_ignored() {
+        let options = SearchOptions {
+            site: Some("  ".to_string()),
+            language: Some(String::new()),
+            ..SearchOptions::default()
+        };
+        assert_eq!(options.site(), None);
+        assert_eq!(options.language(), None);
+    }
+}
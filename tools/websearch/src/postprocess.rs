@@ -0,0 +1,197 @@
+//! Result post-processing: deduplication and snippet enrichment.
+
+use crate::SearchResult;
+
+/// Query parameters that only track the referrer and never change page content.
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "msclkid", "ref", "ref_src", "igshid"];
+
+/// Canonical form of a URL used for deduplication.
+///
+/// Drops the scheme (so `http` and `https` collapse), `www.`, the fragment,
+/// tracking parameters (`utm_*`, `fbclid`, ...), and trailing slashes, and
+/// lowercases the host.
+#[must_use]
+pub fn canonical_url(raw: &str) -> String {
+    let Ok(mut url) = url::Url::parse(raw.trim()) else {
+        return raw.trim().trim_end_matches('/').to_ascii_lowercase();
+    };
+    url.set_fragment(None);
+
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+
+    let host = url
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches("www.")
+        .to_ascii_lowercase();
+    let path = url.path().trim_end_matches('/');
+    let query = url.query().map(|q| format!("?{q}")).unwrap_or_default();
+    let port = url.port().map(|p| format!(":{p}")).unwrap_or_default();
+    format!("{host}{port}{path}{query}")
+}
+
+/// Normalized title used to detect near-duplicates.
+///
+/// Drops a trailing site name (`Title - Site`, `Title | Site`), punctuation, and case.
+fn normalized_title(title: &str) -> String {
+    let main = title
+        .rsplit_once(" | ")
+        .or_else(|| title.rsplit_once(" - "))
+        .or_else(|| title.rsplit_once(" — "))
+        .map_or(title, |(head, _)| head);
+    main.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn host_of(url: &str) -> String {
+    canonical_url(url)
+        .split(['/', '?'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Remove duplicate results while preserving rank order.
+///
+/// Two results are duplicates when their [`canonical_url`]s match, or when they
+/// come from the same host and their titles are equal after normalization (for
+/// example mirrored `?page=` variants). The first occurrence wins; an empty
+/// snippet is filled from a later duplicate.
+#[must_use]
+pub fn dedupe_results(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut kept: Vec<(String, String, String, SearchResult)> = Vec::new();
+
+    for result in results {
+        let canonical = canonical_url(&result.url);
+        let host = host_of(&result.url);
+        let title = normalized_title(&result.title);
+
+        let existing = kept
+            .iter_mut()
+            .find(|(seen_url, seen_host, seen_title, _)| {
+                *seen_url == canonical
+                    || (!title.is_empty() && *seen_host == host && *seen_title == title)
+            });
+
+        match existing {
+            Some((_, _, _, first)) => {
+                if first.snippet.trim().is_empty() && !result.snippet.trim().is_empty() {
+                    first.snippet = result.snippet;
+                }
+            }
+            None => kept.push((canonical, host, title, result)),
+        }
+    }
+
+    kept.into_iter().map(|(_, _, _, result)| result).collect()
+}
+
+/// Build a short snippet from fetched page markdown.
+///
+/// Skips headings, images, and link-only lines, then truncates on a word boundary.
+#[cfg(any(feature = "enrich", test))]
+pub(crate) fn snippet_from_markdown(markdown: &str, max_chars: usize) -> String {
+    let mut snippet = String::new();
+    for line in markdown.lines().map(str::trim) {
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("![")
+            || line.starts_with('[')
+            || line.starts_with("---")
+            || line.ends_with(':')
+            || (snippet.is_empty() && line.contains(": ") && line.len() < 80)
+        {
+            continue;
+        }
+        if !snippet.is_empty() {
+            snippet.push(' ');
+        }
+        snippet.push_str(line);
+        if snippet.chars().count() >= max_chars {
+            break;
+        }
+    }
+
+    if snippet.chars().count() <= max_chars {
+        return snippet;
+    }
+    let truncated: String = snippet.chars().take(max_chars).collect();
+    let cut = truncated.rfind(' ').unwrap_or(truncated.len());
+    format!("{}…", truncated[..cut].trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(title: &str, url: &str, snippet: &str) -> SearchResult {
+        SearchResult {
+            title: title.to_string(),
+            url: url.to_string(),
+            snippet: snippet.to_string(),
+        }
+    }
+
+    #[test]
+    fn canonicalizes_tracking_and_case() {
+        assert_eq!(
+            canonical_url("https://WWW.Example.com/docs/?utm_source=x&id=3#intro"),
+            canonical_url("http://example.com/docs?id=3")
+        );
+        assert_ne!(
+            canonical_url("https://example.com/docs?id=3"),
+            canonical_url("https://example.com/docs?id=4")
+        );
+    }
+
+    #[test]
+    fn dedupes_by_url_and_title() {
+        let results = vec![
+            result("Rust Book", "https://doc.rust-lang.org/book/", ""),
+            result(
+                "Rust Book",
+                "https://doc.rust-lang.org/book?utm_medium=a",
+                "The book.",
+            ),
+            result(
+                "Tokio - Docs",
+                "https://tokio.rs/tokio/tutorial",
+                "Tutorial",
+            ),
+            result(
+                "tokio | docs",
+                "https://tokio.rs/tokio/tutorial/?page=1",
+                "Dup",
+            ),
+            result("Tokio", "https://docs.rs/tokio", "API docs"),
+        ];
+
+        let deduped = dedupe_results(results);
+        assert_eq!(deduped.len(), 3);
+        assert_eq!(deduped[0].snippet, "The book.");
+        assert_eq!(deduped[1].snippet, "Tutorial");
+        assert_eq!(deduped[2].url, "https://docs.rs/tokio");
+    }
+
+    #[test]
+    fn snippet_skips_headings_and_truncates() {
+        let markdown = "# Title\n\n![logo](x.png)\n\nFirst paragraph of text here.\nSecond line.";
+        assert_eq!(
+            snippet_from_markdown(markdown, 200),
+            "First paragraph of text here. Second line."
+        );
+        assert_eq!(snippet_from_markdown(markdown, 12), "First…");
+    }
+}
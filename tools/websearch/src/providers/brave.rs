@@ -18,11 +18,18 @@
 //! # }
 //! ```
 
-use crate::{SearchProvider, SearchResult};
-use anyhow::{Result, anyhow};
+use crate::error::api_key_from_env;
+use crate::{SafeSearch, SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
 use serde::Deserialize;
 use zenwave::{Client, client, header};
 
+/// Environment variable read by [`BraveSearch::from_env`].
+pub const BRAVE_API_KEY_ENV: &str = "BRAVE_API_KEY";
+
+/// Provider name used in errors.
+const PROVIDER: &str = "brave";
+
 /// Brave Search API endpoint.
 const BRAVE_API_URL: &str = "https://api.search.brave.com/res/v1/web/search";
 
@@ -43,25 +50,62 @@ impl BraveSearch {
             api_key: api_key.into(),
         }
     }
+
+    /// Create a provider using the API key from the `BRAVE_API_KEY` environment variable.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::MissingApiKey`] if the variable is unset or empty.
+    pub fn from_env() -> Result<Self, SearchError> {
+        api_key_from_env(PROVIDER, BRAVE_API_KEY_ENV).map(Self::new)
+    }
 }
 
 impl SearchProvider for BraveSearch {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
     async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
-        let url = format!("{}?q={}&count={}", BRAVE_API_URL, urlencoded(query), limit);
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let query = options.query_with_site(query);
+        let mut url = format!("{}?q={}&count={}", BRAVE_API_URL, urlencoded(&query), limit);
+        if let Some(freshness) = options.freshness {
+            url.push_str(&format!("&freshness=p{}", freshness.letter()));
+        }
+        if let Some(language) = options.language() {
+            url.push_str(&format!("&search_lang={}", urlencoded(&language)));
+        }
+        if let Some(safe_search) = options.safe_search {
+            let level = match safe_search {
+                SafeSearch::Off => "off",
+                SafeSearch::Moderate => "moderate",
+                SafeSearch::Strict => "strict",
+            };
+            url.push_str(&format!("&safesearch={level}"));
+        }
 
         let mut backend = client();
         let response: BraveResponse = backend
             .get(&url)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header("X-Subscription-Token", &self.api_key)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::ACCEPT.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json()
             .await
-            .map_err(|e| anyhow!("{e}"))?;
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
 
         Ok(response
             .web
@@ -19,11 +19,14 @@
 //! # }
 //! ```
 
-use crate::{SearchProvider, SearchResult};
-use anyhow::{Result, anyhow};
+use crate::{SearchError, SearchProvider, SearchResult};
+use anyhow::Result;
 use serde::Deserialize;
 use zenwave::{Client, client, header};
 
+/// Provider name used in errors.
+const PROVIDER: &str = "duckduckgo";
+
 /// DuckDuckGo Instant Answer API endpoint.
 const DDG_API_URL: &str = "https://api.duckduckgo.com/";
 
@@ -46,6 +49,10 @@ impl DuckDuckGo {
 }
 
 impl SearchProvider for DuckDuckGo {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
     async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
         let url = format!(
             "{}?q={}&format=json&no_html=1&skip_disambig=1",
@@ -56,14 +63,14 @@ impl SearchProvider for DuckDuckGo {
         let mut backend = client();
         let response: DdgResponse = backend
             .get(&url)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::ACCEPT.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json()
             .await
-            .map_err(|e| anyhow!("{e}"))?;
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
 
         let mut results = Vec::new();
 
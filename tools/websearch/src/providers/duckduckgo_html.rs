@@ -0,0 +1,227 @@
+//! DuckDuckGo HTML results provider.
+//!
+//! Scrapes the JavaScript-free [DuckDuckGo HTML](https://html.duckduckgo.com/html/)
+//! endpoint, which returns full web results without an API key. Unlike
+//! [`DuckDuckGo`](crate::DuckDuckGo), this is a real web search.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aither_websearch::{DuckDuckGoHtml, SearchProvider};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let provider = DuckDuckGoHtml::new();
+//! let results = provider.search("rust borrow checker", 5).await?;
+//! for result in results {
+//!     println!("{}: {}", result.title, result.url);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{SafeSearch, SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
+use regex::Regex;
+use zenwave::{Client, ResponseExt, client, header};
+
+/// Provider name used in errors.
+const PROVIDER: &str = "duckduckgo_html";
+
+/// DuckDuckGo HTML endpoint.
+const DDG_HTML_URL: &str = "https://html.duckduckgo.com/html/";
+
+/// Browser-like User-Agent; the HTML endpoint rejects obvious bots.
+const USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// DuckDuckGo HTML results provider.
+///
+/// Free and keyless, but subject to anti-bot measures; a challenge page is
+/// reported as [`SearchError::Captcha`].
+#[derive(Debug, Clone, Default)]
+pub struct DuckDuckGoHtml {
+    region: Option<String>,
+}
+
+impl DuckDuckGoHtml {
+    /// Create a new DuckDuckGo HTML provider.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict results to a region code such as `us-en` or `de-de`.
+    #[must_use]
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+}
+
+impl SearchProvider for DuckDuckGoHtml {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let query = options.query_with_site(query);
+        let mut url = format!("{}?q={}", DDG_HTML_URL, urlencoded(&query));
+        if let Some(freshness) = options.freshness {
+            url.push_str(&format!("&df={}", freshness.letter()));
+        }
+        if let Some(safe_search) = options.safe_search {
+            let level = match safe_search {
+                SafeSearch::Off => "-2",
+                SafeSearch::Moderate => "-1",
+                SafeSearch::Strict => "1",
+            };
+            url.push_str(&format!("&kp={level}"));
+        }
+        if let Some(region) = &self.region {
+            url.push_str(&format!("&kl={}", urlencoded(region)));
+        }
+
+        let mut backend = client();
+        let html = backend
+            .get(&url)
+            .map_err(|e| SearchError::http(PROVIDER, e))?
+            .header(header::ACCEPT.as_str(), "text/html")
+            .map_err(|e| SearchError::http(PROVIDER, e))?
+            .header(header::USER_AGENT.as_str(), USER_AGENT)
+            .map_err(|e| SearchError::http(PROVIDER, e))?
+            .await
+            .map_err(|e| SearchError::http(PROVIDER, e))?
+            .into_string()
+            .await
+            .map_err(|e| SearchError::invalid(PROVIDER, e.to_string()))?
+            .to_string();
+
+        if html.contains("anomaly-modal") || html.contains("challenge-form") {
+            return Err(SearchError::Captcha {
+                provider: PROVIDER,
+                engines: Vec::new(),
+            }
+            .into());
+        }
+
+        Ok(parse_results(&html, limit))
+    }
+}
+
+/// Extract results from the HTML endpoint markup.
+fn parse_results(html: &str, limit: usize) -> Vec<SearchResult> {
+    let link_re = Regex::new(r#"(?s)<a[^>]*class="result__a"[^>]*href="([^"]+)"[^>]*>(.*?)</a>"#)
+        .expect("valid result link regex");
+    let snippet_re =
+        Regex::new(r#"(?s)class="result__snippet"[^>]*>(.*?)</a>"#).expect("valid snippet regex");
+
+    let links: Vec<_> = link_re.captures_iter(html).collect();
+    let mut results = Vec::new();
+
+    for (index, link) in links.iter().enumerate() {
+        if results.len() >= limit {
+            break;
+        }
+        let Some(url) = resolve_redirect(&link[1]) else {
+            continue;
+        };
+        // Snippets sit between this result's link and the next one.
+        let start = link.get(0).map_or(0, |m| m.end());
+        let end = links
+            .get(index + 1)
+            .and_then(|next| next.get(0))
+            .map_or(html.len(), |m| m.start());
+        let snippet = snippet_re
+            .captures(&html[start..end])
+            .map(|cap| clean_text(&cap[1]))
+            .unwrap_or_default();
+
+        results.push(SearchResult {
+            title: clean_text(&link[2]),
+            url,
+            snippet,
+        });
+    }
+
+    results
+}
+
+/// Unwrap DuckDuckGo's `/l/?uddg=` redirect links and drop ad links.
+fn resolve_redirect(href: &str) -> Option<String> {
+    let href = html_escape::decode_html_entities(href);
+    let absolute = if href.starts_with("//") {
+        format!("https:{href}")
+    } else {
+        href.to_string()
+    };
+    let parsed = url::Url::parse(&absolute).ok()?;
+
+    if parsed.domain() == Some("duckduckgo.com") {
+        if parsed.path().starts_with("/y.js") {
+            return None;
+        }
+        return parsed
+            .query_pairs()
+            .find(|(key, _)| key == "uddg")
+            .map(|(_, value)| value.into_owned());
+    }
+    Some(absolute)
+}
+
+/// Strip tags and decode entities.
+fn clean_text(fragment: &str) -> String {
+    let tag_re = Regex::new(r"<[^>]+>").expect("valid tag regex");
+    let text = tag_re.replace_all(fragment, "");
+    html_escape::decode_html_entities(text.trim()).into_owned()
+}
+
+fn urlencoded(s: &str) -> String {
+    url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r##"
+        <div class="result">
+          <a rel="nofollow" class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.rust-lang.org%2F&amp;rut=abc">The <b>Rust</b> Programming Language</a>
+          <a class="result__snippet" href="#">A language empowering everyone to build <b>reliable</b> software.</a>
+        </div>
+        <div class="result">
+          <a rel="nofollow" class="result__a" href="//duckduckgo.com/y.js?ad_provider=x">Sponsored</a>
+        </div>
+        <div class="result">
+          <a rel="nofollow" class="result__a" href="https://doc.rust-lang.org/book/">The Book &amp; more</a>
+        </div>
+    "##;
+
+    #[test]
+    fn parses_results_and_skips_ads() {
+        let results = parse_results(SAMPLE, 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].url, "https://www.rust-lang.org/");
+        assert_eq!(results[0].title, "The Rust Programming Language");
+        assert_eq!(
+            results[0].snippet,
+            "A language empowering everyone to build reliable software."
+        );
+        assert_eq!(results[1].url, "https://doc.rust-lang.org/book/");
+        assert_eq!(results[1].title, "The Book & more");
+        assert!(results[1].snippet.is_empty());
+    }
+
+    #[test]
+    fn respects_limit() {
+        assert_eq!(parse_results(SAMPLE, 1).len(), 1);
+    }
+}
@@ -3,6 +3,10 @@
 //! [Exa](https://exa.ai) provides semantic web search and content extraction
 //! optimized for AI applications.
 //!
+//! Site restrictions map to `includeDomains` and freshness to
+//! `startPublishedDate`; Exa has no language or safe-search filters, so those
+//! options are ignored.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -18,11 +22,19 @@
 //! # }
 //! ```
 
-use crate::{SearchProvider, SearchResult};
-use anyhow::{Result, anyhow};
+use crate::error::api_key_from_env;
+use crate::{Freshness, SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
 use zenwave::{Client, client, header};
 
+/// Environment variable read by [`Exa::from_env`].
+pub const EXA_API_KEY_ENV: &str = "EXA_API_KEY";
+
+/// Provider name used in errors.
+const PROVIDER: &str = "exa";
+
 /// Exa Search API endpoint.
 const EXA_SEARCH_API_URL: &str = "https://api.exa.ai/search";
 
@@ -43,29 +55,54 @@ impl Exa {
             api_key: api_key.into(),
         }
     }
+
+    /// Create a provider using the API key from the `EXA_API_KEY` environment variable.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::MissingApiKey`] if the variable is unset or empty.
+    pub fn from_env() -> Result<Self, SearchError> {
+        api_key_from_env(PROVIDER, EXA_API_KEY_ENV).map(Self::new)
+    }
 }
 
 impl SearchProvider for Exa {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
     async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
-        let request = build_request(query, limit.min(100));
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let start_date = options
+            .freshness
+            .map(|freshness| start_published_date(freshness, SystemTime::now()));
+        let request = build_request(query, limit.min(100), options, start_date);
 
         let mut backend = client();
         let response: ExaResponse = backend
             .post(EXA_SEARCH_API_URL)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header("x-api-key", &self.api_key)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::CONTENT_TYPE.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::ACCEPT.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json_body(&request)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json()
             .await
-            .map_err(|e| anyhow!("{e}"))?;
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
 
         Ok(response.results.into_iter().map(map_result).collect())
     }
@@ -79,20 +116,62 @@ fn map_result(result: ExaResult) -> SearchResult {
     }
 }
 
-fn build_request(query: &str, limit: usize) -> ExaRequest<'_> {
+fn build_request<'a>(
+    query: &'a str,
+    limit: usize,
+    options: &'a SearchOptions,
+    start_published_date: Option<String>,
+) -> ExaRequest<'a> {
     ExaRequest {
         query,
         num_results: limit,
         contents: ExaContentsRequest { text: true },
+        include_domains: options.site().into_iter().collect(),
+        start_published_date,
     }
 }
 
+/// ISO-8601 date (`YYYY-MM-DD`) at the start of the freshness window.
+fn start_published_date(freshness: Freshness, now: SystemTime) -> String {
+    let window_days = match freshness {
+        Freshness::Day => 1,
+        Freshness::Week => 7,
+        Freshness::Month => 30,
+        Freshness::Year => 365,
+    };
+    let now_days = now
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+    let (year, month, day) = civil_from_days(now_days.saturating_sub(window_days));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Convert days since the Unix epoch to a proleptic Gregorian date.
+///
+/// Howard Hinnant's `civil_from_days` algorithm, restricted to post-epoch dates.
+const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
 #[derive(Debug, Serialize)]
 struct ExaRequest<'a> {
     query: &'a str,
     #[serde(rename = "numResults")]
     num_results: usize,
     contents: ExaContentsRequest,
+    #[serde(rename = "includeDomains", skip_serializing_if = "Vec::is_empty")]
+    include_domains: Vec<&'a str>,
+    #[serde(rename = "startPublishedDate", skip_serializing_if = "Option::is_none")]
+    start_published_date: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -120,12 +199,33 @@ mod tests {
 
     #[test]
     fn request_serializes_expected_fields() {
-        let request = build_request("rust async", 7);
+        let options = SearchOptions::default();
+        let request = build_request("rust async", 7, &options, None);
         let json = serde_json::to_value(request).expect("serialize request");
 
         assert_eq!(json["query"], "rust async");
         assert_eq!(json["numResults"], 7);
         assert_eq!(json["contents"]["text"], true);
+        assert!(json.get("includeDomains").is_none());
+        assert!(json.get("startPublishedDate").is_none());
+    }
+
+    #[test]
+    fn request_maps_search_options() {
+        let options = SearchOptions {
+            site: Some("docs.rs".to_string()),
+            freshness: Some(Freshness::Week),
+            ..SearchOptions::default()
+        };
+        // 2024-03-08T00:00:00Z
+        let now = UNIX_EPOCH + std::time::Duration::from_secs(1_709_856_000);
+        let start = start_published_date(Freshness::Week, now);
+        assert_eq!(start, "2024-03-01");
+
+        let request = build_request("tokio", 5, &options, Some(start));
+        let json = serde_json::to_value(request).expect("serialize request");
+        assert_eq!(json["includeDomains"][0], "docs.rs");
+        assert_eq!(json["startPublishedDate"], "2024-03-01");
     }
 
     #[test]
@@ -26,11 +26,21 @@
 //! # }
 //! ```
 
-use crate::{SearchProvider, SearchResult};
-use anyhow::{Result, anyhow};
+use crate::error::api_key_from_env;
+use crate::{SafeSearch, SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
 use serde::Deserialize;
 use zenwave::{Client, client, header};
 
+/// Environment variable holding the API key for [`GoogleSearch::from_env`].
+pub const GOOGLE_API_KEY_ENV: &str = "GOOGLE_API_KEY";
+
+/// Environment variable holding the search engine ID for [`GoogleSearch::from_env`].
+pub const GOOGLE_CSE_ID_ENV: &str = "GOOGLE_CSE_ID";
+
+/// Provider name used in errors.
+const PROVIDER: &str = "google";
+
 /// Google Custom Search API endpoint.
 const GOOGLE_API_URL: &str = "https://www.googleapis.com/customsearch/v1";
 
@@ -58,14 +68,38 @@ impl GoogleSearch {
             cx: cx.into(),
         }
     }
+
+    /// Create a provider from the `GOOGLE_API_KEY` and `GOOGLE_CSE_ID` environment variables.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::MissingApiKey`] if either variable is unset or empty.
+    pub fn from_env() -> Result<Self, SearchError> {
+        let api_key = api_key_from_env(PROVIDER, GOOGLE_API_KEY_ENV)?;
+        let cx = api_key_from_env(PROVIDER, GOOGLE_CSE_ID_ENV)?;
+        Ok(Self::new(api_key, cx))
+    }
 }
 
 impl SearchProvider for GoogleSearch {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
     async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
         // Google CSE has a max of 10 results per request
         let num = limit.min(10);
 
-        let url = format!(
+        let mut url = format!(
             "{}?key={}&cx={}&q={}&num={}",
             GOOGLE_API_URL,
             urlencoded(&self.api_key),
@@ -73,18 +107,38 @@ impl SearchProvider for GoogleSearch {
             urlencoded(query),
             num
         );
+        if let Some(site) = options.site() {
+            url.push_str(&format!(
+                "&siteSearch={}&siteSearchFilter=i",
+                urlencoded(site)
+            ));
+        }
+        if let Some(freshness) = options.freshness {
+            url.push_str(&format!("&dateRestrict={}1", freshness.letter()));
+        }
+        if let Some(language) = options.language() {
+            url.push_str(&format!("&lr=lang_{}", urlencoded(&language)));
+        }
+        if let Some(safe_search) = options.safe_search {
+            let level = if safe_search == SafeSearch::Off {
+                "off"
+            } else {
+                "active"
+            };
+            url.push_str(&format!("&safe={level}"));
+        }
 
         let mut backend = client();
         let response: GoogleResponse = backend
             .get(&url)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::ACCEPT.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json()
             .await
-            .map_err(|e| anyhow!("{e}"))?;
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
 
         Ok(response
             .items
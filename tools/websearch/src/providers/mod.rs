@@ -2,19 +2,43 @@
 //!
 //! This module contains implementations for various web search APIs that can be
 //! used with LLM agents to provide real-time internet search capabilities.
+//! Each provider is gated behind a cargo feature of the same name; all are
+//! enabled by default.
 
+#[cfg(feature = "brave")]
 mod brave;
+#[cfg(feature = "duckduckgo")]
 mod duckduckgo;
+#[cfg(feature = "duckduckgo")]
+mod duckduckgo_html;
+#[cfg(feature = "exa")]
 mod exa;
+#[cfg(feature = "google")]
 mod google;
+#[cfg(feature = "searxng")]
 mod searxng;
+#[cfg(feature = "serpapi")]
+mod serpapi;
+#[cfg(feature = "serper")]
 mod serper;
+#[cfg(feature = "tavily")]
 mod tavily;
 
-pub use brave::BraveSearch;
+#[cfg(feature = "brave")]
+pub use brave::{BRAVE_API_KEY_ENV, BraveSearch};
+#[cfg(feature = "duckduckgo")]
 pub use duckduckgo::DuckDuckGo;
-pub use exa::Exa;
-pub use google::GoogleSearch;
+#[cfg(feature = "duckduckgo")]
+pub use duckduckgo_html::DuckDuckGoHtml;
+#[cfg(feature = "exa")]
+pub use exa::{EXA_API_KEY_ENV, Exa};
+#[cfg(feature = "google")]
+pub use google::{GOOGLE_API_KEY_ENV, GOOGLE_CSE_ID_ENV, GoogleSearch};
+#[cfg(feature = "searxng")]
 pub use searxng::{DEFAULT_SEARXNG_URL, SearXNG};
-pub use serper::Serper;
-pub use tavily::Tavily;
+#[cfg(feature = "serpapi")]
+pub use serpapi::{SERPAPI_API_KEY_ENV, SerpApi};
+#[cfg(feature = "serper")]
+pub use serper::{SERPER_API_KEY_ENV, Serper, SerperSearchType};
+#[cfg(feature = "tavily")]
+pub use tavily::{SearchDepth, TAVILY_API_KEY_ENV, Tavily};
@@ -19,8 +19,8 @@
 //! # }
 //! ```
 
-use crate::{SearchProvider, SearchResult};
-use anyhow::{Result, anyhow};
+use crate::{SafeSearch, SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
 use serde::Deserialize;
 use zenwave::{Client, client, header};
 
@@ -28,6 +28,9 @@ fn ensure_rustls_provider() {
     let _ = rustls::crypto::ring::default_provider().install_default();
 }
 
+/// Provider name used in errors.
+const PROVIDER: &str = "searxng";
+
 /// Default public SearXNG endpoint.
 pub const DEFAULT_SEARXNG_URL: &str = "https://serxng-deployment-production.up.railway.app";
 
@@ -79,13 +82,42 @@ impl SearXNG {
 }
 
 impl SearchProvider for SearXNG {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
     async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
         ensure_rustls_provider();
+        let query = options.query_with_site(query);
         let mut url = format!(
             "{}/search?q={}&format=json",
             self.base_url,
-            urlencoded(query)
+            urlencoded(&query)
         );
+        if let Some(freshness) = options.freshness {
+            url.push_str(&format!("&time_range={}", freshness.word()));
+        }
+        if let Some(language) = options.language() {
+            url.push_str(&format!("&language={}", urlencoded(&language)));
+        }
+        if let Some(safe_search) = options.safe_search {
+            let level = match safe_search {
+                SafeSearch::Off => 0,
+                SafeSearch::Moderate => 1,
+                SafeSearch::Strict => 2,
+            };
+            url.push_str(&format!("&safesearch={level}"));
+        }
 
         if let Some(ref engines) = self.engines {
             url.push_str(&format!("&engines={engines}"));
@@ -94,14 +126,14 @@ impl SearchProvider for SearXNG {
         let mut backend = client();
         let response: SearxngResponse = backend
             .get(&url)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::ACCEPT.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json()
             .await
-            .map_err(|e| anyhow!("{e}"))?;
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
 
         // Check for CAPTCHA errors when no results
         if response.results.is_empty() {
@@ -109,14 +141,15 @@ impl SearchProvider for SearXNG {
                 .unresponsive_engines
                 .iter()
                 .filter(|(_, reason)| reason.contains("CAPTCHA"))
-                .map(|(engine, _)| engine.as_str())
+                .map(|(engine, _)| engine.clone())
                 .collect();
 
             if !captcha_engines.is_empty() {
-                return Err(anyhow!(
-                    "CAPTCHA detected for engines: {}. Try a different SearXNG instance.",
-                    captcha_engines.join(", ")
-                ));
+                return Err(SearchError::Captcha {
+                    provider: PROVIDER,
+                    engines: captcha_engines,
+                }
+                .into());
             }
         }
 
@@ -162,7 +195,7 @@ mod tests {
         match results {
             Ok(r) if !r.is_empty() => {} // Success
             Ok(_) => tracing::warn!("SearXNG returned no results; endpoint may be degraded"),
-            Err(e) if e.to_string().contains("CAPTCHA") => {
+            Err(e) if matches!(e.downcast_ref(), Some(SearchError::Captcha { .. })) => {
                 tracing::warn!("SearXNG CAPTCHA detected; skipping test");
             }
             Err(e) => panic!("SearXNG search failed: {e}"),
@@ -176,7 +209,7 @@ mod tests {
         let results = provider.search("天気予報", 5).await;
         match results {
             Ok(_) => {} // Success (empty is ok for unicode)
-            Err(e) if e.to_string().contains("CAPTCHA") => {
+            Err(e) if matches!(e.downcast_ref(), Some(SearchError::Captcha { .. })) => {
                 tracing::warn!("SearXNG CAPTCHA detected; skipping test");
             }
             Err(e) => panic!("SearXNG unicode search failed: {e}"),
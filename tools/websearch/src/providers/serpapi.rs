@@ -0,0 +1,164 @@
+//! SerpAPI Google results provider.
+//!
+//! [SerpAPI](https://serpapi.com/) scrapes search engine result pages and returns
+//! them as structured JSON.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aither_websearch::{SerpApi, SearchProvider};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let provider = SerpApi::new("YOUR_API_KEY");
+//! let results = provider.search("rust async runtimes", 5).await?;
+//! for result in results {
+//!     println!("{}: {}", result.title, result.url);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::api_key_from_env;
+use crate::{SafeSearch, SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
+use serde::Deserialize;
+use zenwave::{Client, client, header};
+
+/// Environment variable read by [`SerpApi::from_env`].
+pub const SERPAPI_API_KEY_ENV: &str = "SERPAPI_API_KEY";
+
+/// Provider name used in errors.
+const PROVIDER: &str = "serpapi";
+
+/// SerpAPI endpoint.
+const SERPAPI_URL: &str = "https://serpapi.com/search.json";
+
+/// SerpAPI provider.
+///
+/// Uses the [SerpAPI](https://serpapi.com/) JSON endpoint. Defaults to the
+/// `google` engine; other engines (`bing`, `duckduckgo`, ...) can be selected.
+#[derive(Debug, Clone)]
+pub struct SerpApi {
+    api_key: String,
+    engine: String,
+}
+
+impl SerpApi {
+    /// Create a new SerpAPI provider with the given API key.
+    #[must_use]
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            engine: "google".to_string(),
+        }
+    }
+
+    /// Create a provider using the API key from the `SERPAPI_API_KEY` environment variable.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::MissingApiKey`] if the variable is unset or empty.
+    pub fn from_env() -> Result<Self, SearchError> {
+        api_key_from_env(PROVIDER, SERPAPI_API_KEY_ENV).map(Self::new)
+    }
+
+    /// Select the upstream search engine (for example `google` or `bing`).
+    #[must_use]
+    pub fn with_engine(mut self, engine: impl Into<String>) -> Self {
+        self.engine = engine.into();
+        self
+    }
+}
+
+impl SearchProvider for SerpApi {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let query = options.query_with_site(query);
+        let mut url = format!(
+            "{}?engine={}&q={}&num={}&api_key={}",
+            SERPAPI_URL,
+            urlencoded(&self.engine),
+            urlencoded(&query),
+            limit,
+            urlencoded(&self.api_key)
+        );
+        if let Some(freshness) = options.freshness {
+            url.push_str(&format!("&tbs=qdr:{}", freshness.letter()));
+        }
+        if let Some(language) = options.language() {
+            url.push_str(&format!("&hl={}", urlencoded(&language)));
+        }
+        if let Some(safe_search) = options.safe_search {
+            let level = if safe_search == SafeSearch::Off {
+                "off"
+            } else {
+                "active"
+            };
+            url.push_str(&format!("&safe={level}"));
+        }
+
+        let mut backend = client();
+        let response: SerpApiResponse = backend
+            .get(&url)
+            .map_err(|e| SearchError::http(PROVIDER, e))?
+            .header(header::ACCEPT.as_str(), "application/json")
+            .map_err(|e| SearchError::http(PROVIDER, e))?
+            .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
+            .map_err(|e| SearchError::http(PROVIDER, e))?
+            .json()
+            .await
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
+
+        if let Some(error) = response.error {
+            // SerpAPI reports an empty result set through `error` as well.
+            if error.contains("hasn't returned any results") {
+                return Ok(Vec::new());
+            }
+            if error.contains("run out of searches") {
+                return Err(SearchError::RateLimited { provider: PROVIDER }.into());
+            }
+            return Err(SearchError::invalid(PROVIDER, error).into());
+        }
+
+        Ok(response
+            .organic_results
+            .into_iter()
+            .take(limit)
+            .map(|r| SearchResult {
+                title: r.title,
+                url: r.link,
+                snippet: r.snippet.unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SerpApiResponse {
+    #[serde(default)]
+    organic_results: Vec<SerpApiResult>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SerpApiResult {
+    title: String,
+    link: String,
+    snippet: Option<String>,
+}
+
+fn urlencoded(s: &str) -> String {
+    url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
+}
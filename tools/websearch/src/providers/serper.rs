@@ -18,11 +18,18 @@
 //! # }
 //! ```
 
-use crate::{SearchProvider, SearchResult};
-use anyhow::{Result, anyhow};
+use crate::error::api_key_from_env;
+use crate::{SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use zenwave::{Client, client, header};
 
+/// Environment variable read by [`Serper::from_env`].
+pub const SERPER_API_KEY_ENV: &str = "SERPER_API_KEY";
+
+/// Provider name used in errors.
+const PROVIDER: &str = "serper";
+
 /// Serper API endpoint.
 const SERPER_API_URL: &str = "https://google.serper.dev/search";
 
@@ -65,6 +72,14 @@ impl Serper {
         }
     }
 
+    /// Create a provider using the API key from the `SERPER_API_KEY` environment variable.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::MissingApiKey`] if the variable is unset or empty.
+    pub fn from_env() -> Result<Self, SearchError> {
+        api_key_from_env(PROVIDER, SERPER_API_KEY_ENV).map(Self::new)
+    }
+
     /// Set the search type (search, news, images, places).
     #[must_use]
     pub const fn with_search_type(mut self, search_type: SerperSearchType) -> Self {
@@ -88,31 +103,51 @@ impl Serper {
 }
 
 impl SearchProvider for Serper {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
     async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let query = options.query_with_site(query);
+        let language = options.language();
+        let tbs = options
+            .freshness
+            .map(|freshness| format!("qdr:{}", freshness.letter()));
         let request = SerperRequest {
-            q: query,
+            q: &query,
             num: Some(limit.min(100)), // Serper supports up to 100 results
             gl: self.country.as_deref(),
-            hl: self.locale.as_deref(),
+            hl: language.as_deref().or(self.locale.as_deref()),
+            tbs: tbs.as_deref(),
         };
 
         let mut backend = client();
         let response: SerperResponse = backend
             .post(SERPER_API_URL)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header("X-API-KEY", &self.api_key)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::CONTENT_TYPE.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::ACCEPT.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json_body(&request)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json()
             .await
-            .map_err(|e| anyhow!("{e}"))?;
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
 
         Ok(response
             .organic
@@ -136,6 +171,8 @@ struct SerperRequest<'a> {
     gl: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     hl: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tbs: Option<&'a str>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -19,11 +19,18 @@
 //! # }
 //! ```
 
-use crate::{SearchProvider, SearchResult};
-use anyhow::{Result, anyhow};
+use crate::error::api_key_from_env;
+use crate::{Freshness, SearchError, SearchOptions, SearchProvider, SearchResult};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use zenwave::{Client, client, header};
 
+/// Environment variable read by [`Tavily::from_env`].
+pub const TAVILY_API_KEY_ENV: &str = "TAVILY_API_KEY";
+
+/// Provider name used in errors.
+const PROVIDER: &str = "tavily";
+
 /// Tavily API endpoint.
 const TAVILY_API_URL: &str = "https://api.tavily.com/search";
 
@@ -61,6 +68,14 @@ impl Tavily {
         }
     }
 
+    /// Create a provider using the API key from the `TAVILY_API_KEY` environment variable.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::MissingApiKey`] if the variable is unset or empty.
+    pub fn from_env() -> Result<Self, SearchError> {
+        api_key_from_env(PROVIDER, TAVILY_API_KEY_ENV).map(Self::new)
+    }
+
     /// Set the search depth (basic or advanced).
     #[must_use]
     pub const fn with_search_depth(mut self, depth: SearchDepth) -> Self {
@@ -77,30 +92,46 @@ impl Tavily {
 }
 
 impl SearchProvider for Tavily {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
     async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_options(query, limit, &SearchOptions::default())
+            .await
+    }
+
+    async fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
         let request = TavilyRequest {
             api_key: &self.api_key,
             query,
             search_depth: self.search_depth,
             include_answer: self.include_answer,
             max_results: limit,
+            time_range: options.freshness.map(Freshness::word),
+            include_domains: options.site().into_iter().collect(),
         };
 
         let mut backend = client();
         let response: TavilyResponse = backend
             .post(TAVILY_API_URL)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::CONTENT_TYPE.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::ACCEPT.as_str(), "application/json")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .header(header::USER_AGENT.as_str(), "aither-websearch/0.1")
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json_body(&request)
-            .map_err(|e| anyhow!("{e}"))?
+            .map_err(|e| SearchError::http(PROVIDER, e))?
             .json()
             .await
-            .map_err(|e| anyhow!("{e}"))?;
+            .map_err(|e| SearchError::http(PROVIDER, e))?;
 
         Ok(response
             .results
@@ -121,6 +152,10 @@ struct TavilyRequest<'a> {
     search_depth: SearchDepth,
     include_answer: bool,
     max_results: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_range: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    include_domains: Vec<&'a str>,
 }
 
 #[derive(Debug, Deserialize)]
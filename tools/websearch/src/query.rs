@@ -0,0 +1,262 @@
+//! LLM-driven query planning for research workflows.
+//!
+//! A single research question rarely maps onto one good search query.
+//! [`QueryExpander`] asks a language model for several complementary queries
+//! (different angles, synonyms, sub-questions) and, optionally, translations
+//! into other languages so non-English sources are reachable too.
+
+use aither_core::LanguageModel;
+use aither_core::llm::{LLMRequest, Message};
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::SearchOptions;
+
+const DEFAULT_QUERY_COUNT: usize = 4;
+
+const SYSTEM_PROMPT: &str = "You plan web searches for a research assistant. \
+Given a research question, write diverse search engine queries that together cover it: \
+rephrase with synonyms, split compound questions into parts, and target primary sources, \
+recent news, and expert discussion. Queries must be short keyword phrases, not sentences, \
+and must not repeat each other.";
+
+/// A single planned search query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ExpandedQuery {
+    /// Query text to send to the search provider.
+    pub query: String,
+    /// Language of the query as a BCP 47 code, or `None` for the question's own language.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Short explanation of what this query is meant to find.
+    #[serde(default)]
+    pub purpose: Option<String>,
+}
+
+impl ExpandedQuery {
+    /// Search options matching this query's language.
+    #[must_use]
+    pub fn options(&self) -> SearchOptions {
+        SearchOptions {
+            language: self.language.clone(),
+            ..SearchOptions::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct QueryPlan {
+    /// Planned queries, most important first.
+    queries: Vec<ExpandedQuery>,
+}
+
+/// Turns a research question into several diverse search queries.
+///
+/// # Example
+///
+/// ```no_run
+/// use aither_core::LanguageModel;
+/// use aither_websearch::{QueryExpander, SearchProvider};
+///
+/// # async fn example(model: impl LanguageModel, search: impl SearchProvider) -> anyhow::Result<()> {
+/// let expander = QueryExpander::new(model).with_count(3).with_language("de");
+/// for planned in expander.expand("How do heat pumps perform in cold climates?").await? {
+///     let results = search
+///         .search_with_options(&planned.query, 5, &planned.options())
+///         .await?;
+///     println!("{}: {} results", planned.query, results.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryExpander<M> {
+    model: M,
+    count: usize,
+    languages: Vec<String>,
+}
+
+impl<M: LanguageModel> QueryExpander<M> {
+    /// Create an expander producing four queries in the question's language.
+    pub const fn new(model: M) -> Self {
+        Self {
+            model,
+            count: DEFAULT_QUERY_COUNT,
+            languages: Vec::new(),
+        }
+    }
+
+    /// Set how many queries to produce per language (at least one).
+    #[must_use]
+    pub fn with_count(mut self, count: usize) -> Self {
+        self.count = count.max(1);
+        self
+    }
+
+    /// Also produce localized queries in `language` (a BCP 47 code such as `"ja"`).
+    #[must_use]
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.languages.push(language.into());
+        self
+    }
+
+    /// Plan search queries for `question`.
+    ///
+    /// The result is deduplicated, capped at the configured count per language,
+    /// and never empty: the question itself is used when the model returns nothing usable.
+    ///
+    /// # Errors
+    /// Returns an error if the model call fails or its output cannot be parsed.
+    pub async fn expand(&self, question: &str) -> Result<Vec<ExpandedQuery>> {
+        let request = LLMRequest::new([
+            Message::system(SYSTEM_PROMPT),
+            Message::user(self.prompt(question)),
+        ]);
+        let plan: QueryPlan = self.model.generate(request).await?;
+        Ok(self.normalize(question, plan.queries))
+    }
+
+    fn prompt(&self, question: &str) -> String {
+        let mut prompt = format!(
+            "Research question: {question}\n\nWrite {} search queries in the language of the question (language: null).",
+            self.count
+        );
+        for language in &self.languages {
+            prompt.push_str(&format!(
+                "\nThen write {} queries translated and localized for language \"{language}\" (language: \"{language}\").",
+                self.count
+            ));
+        }
+        prompt
+    }
+
+    fn normalize(&self, question: &str, planned: Vec<ExpandedQuery>) -> Vec<ExpandedQuery> {
+        let mut queries: Vec<ExpandedQuery> = Vec::new();
+
+        for mut planned in planned {
+            planned.query = planned
+                .query
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            planned.language = planned
+                .language
+                .map(|language| language.trim().to_string())
+                .filter(|language| !language.is_empty());
+            if planned.query.is_empty() {
+                continue;
+            }
+
+            let same_language = queries
+                .iter()
+                .filter(|seen| seen.language == planned.language)
+                .count();
+            let duplicate = queries
+                .iter()
+                .any(|seen| seen.query.eq_ignore_ascii_case(&planned.query));
+            if duplicate || same_language >= self.count {
+                continue;
+            }
+            queries.push(planned);
+        }
+
+        if queries.is_empty() {
+            queries.push(ExpandedQuery {
+                query: question.trim().to_string(),
+                language: None,
+                purpose: None,
+            });
+        }
+        queries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct NoModel;
+
+    #[derive(Debug)]
+    struct NoModelError;
+
+    impl std::fmt::Display for NoModelError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("no model")
+        }
+    }
+
+    impl std::error::Error for NoModelError {}
+
+    impl LanguageModel for NoModel {
+        type Error = NoModelError;
+
+        fn respond(
+            &self,
+            _request: LLMRequest,
+        ) -> impl futures_core::Stream<Item = Result<aither_core::llm::Event, Self::Error>> + Send
+        {
+            futures_lite::stream::empty()
+        }
+
+        async fn profile(&self) -> aither_core::llm::model::Profile {
+            aither_core::llm::model::Profile::new("none", "test", "none", "unused", 0)
+        }
+    }
+
+    fn planned(query: &str, language: Option<&str>) -> ExpandedQuery {
+        ExpandedQuery {
+            query: query.to_string(),
+            language: language.map(str::to_string),
+            purpose: None,
+        }
+    }
+
+    #[test]
+    fn normalize_dedupes_and_caps_per_language() {
+        let expander = QueryExpander::new(NoModel)
+            .with_count(2)
+            .with_language("de");
+        let queries = expander.normalize(
+            "heat pumps in cold climates",
+            vec![
+                planned("heat pump  cold climate efficiency", None),
+                planned("Heat pump cold climate efficiency", None),
+                planned("", None),
+                planned("heat pump COP below freezing", None),
+                planned("heat pump field study", None),
+                planned("Wärmepumpe Kälte Effizienz", Some(" de ")),
+            ],
+        );
+
+        let texts: Vec<_> = queries.iter().map(|q| q.query.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "heat pump cold climate efficiency",
+                "heat pump COP below freezing",
+                "Wärmepumpe Kälte Effizienz",
+            ]
+        );
+        assert_eq!(queries[2].options().language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn normalize_falls_back_to_question() {
+        let expander = QueryExpander::new(NoModel);
+        let queries = expander.normalize("  what is rust?  ", Vec::new());
+        assert_eq!(queries, [planned("what is rust?", None)]);
+    }
+
+    #[test]
+    fn prompt_mentions_languages() {
+        let prompt = QueryExpander::new(NoModel)
+            .with_count(3)
+            .with_language("ja")
+            .prompt("rust async");
+        assert!(prompt.contains("Write 3 search queries"));
+        assert!(prompt.contains("\"ja\""));
+    }
+}